@@ -56,7 +56,7 @@ pub(crate) fn compute_estimated_duration(dag: &DAGCircuit, target: &Target) -> P
                                                 Ok(dur_float * dt)
                                             })
                                         },
-                                        Param::ParameterExpression(_) => Err(QiskitError::new_err(
+                                        Param::ParameterExpression(_) | Param::Symbolic(_) => Err(QiskitError::new_err(
                                             "Circuit contains parameterized delays, can't compute a duration estimate with this circuit"
                                         )),
                                     }
@@ -0,0 +1,632 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2025
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+use hashbrown::HashSet;
+use ndarray::Array2;
+use num_complex::Complex64;
+use pyo3::prelude::*;
+use rustworkx_core::coloring::greedy_node_color;
+use rustworkx_core::petgraph::graph::NodeIndex;
+use smallvec::{smallvec, SmallVec};
+
+use qiskit_circuit::dag_circuit::{DAGCircuit, NodeType};
+use qiskit_circuit::operations::{
+    DelayUnit, Operation, OperationRef, Param, StandardGate, StandardInstruction,
+};
+use qiskit_circuit::packed_instruction::PackedOperation;
+use qiskit_circuit::{PhysicalQubit, Qubit, VarsMode};
+use qiskit_transpiler::passes::{instruction_duration, NodeStartTimes};
+use qiskit_transpiler::target::Target;
+use qiskit_transpiler::TranspilerError;
+
+const IDENTITY_TOLERANCE: f64 = 1e-10;
+
+/// Where to put the slack that is left over after rounding the delays of a DD sequence down to
+/// the alignment constraints of the target.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ExtraSlackDistribution {
+    /// Put the extra slack in the delay in the middle of the sequence.
+    Middle,
+    /// Split the extra slack as evenly as possible between the first and last delays.
+    Edges,
+}
+
+/// The configuration of the dynamical decoupling pass.
+#[derive(Clone, Debug)]
+pub struct DynamicalDecouplingConfig {
+    /// The gates of the DD sequence, in the order they are applied.  The sequence must have an
+    /// even number of gates and compose to the identity, up to a global phase.
+    pub sequence: Vec<(StandardGate, SmallVec<[Param; 3]>)>,
+    /// The physical qubits to apply DD to.  If ``None``, DD is applied to every qubit that
+    /// supports all the gates of the sequence.
+    pub qubits: Option<HashSet<usize>>,
+    /// The fractions of each idle window to put before the first gate, between each pair of
+    /// gates and after the last gate.  This must have one more entry than the sequence, and sum
+    /// to 1.  If ``None``, the balanced spacing ``[d/2, d, ..., d, d/2]`` is used.
+    pub spacing: Option<Vec<f64>>,
+    /// Whether to stagger the DD sequences of neighbouring qubits.  If set, the qubits of the
+    /// target's coupling graph are coloured so that no two neighbours share a colour, and the
+    /// gates of the qubits of each colour are shifted by a different fraction of the spacing
+    /// between gates, so neighbouring qubits do not pulse at the same time.
+    pub staggered: bool,
+    /// Whether to skip idle windows that follow the start of the circuit or a reset, where the
+    /// qubit is in its ground state.
+    pub skip_reset_qubits: bool,
+    /// Where to put the slack left over after aligning the delays.
+    pub extra_slack_distribution: ExtraSlackDistribution,
+}
+
+impl DynamicalDecouplingConfig {
+    /// Create a configuration with the default options for a given DD sequence.
+    pub fn new(sequence: Vec<(StandardGate, SmallVec<[Param; 3]>)>) -> Self {
+        Self {
+            sequence,
+            qubits: None,
+            spacing: None,
+            staggered: false,
+            skip_reset_qubits: true,
+            extra_slack_distribution: ExtraSlackDistribution::Middle,
+        }
+    }
+
+    /// Create a configuration for a DD sequence of parameterless gates, such as ``[X, X]`` or
+    /// the XY4 sequence ``[X, Y, X, Y]``.
+    pub fn from_gates(gates: &[StandardGate]) -> Self {
+        Self::new(gates.iter().map(|gate| (*gate, smallvec![])).collect())
+    }
+
+    pub fn with_qubits(mut self, qubits: HashSet<usize>) -> Self {
+        self.qubits = Some(qubits);
+        self
+    }
+
+    pub fn with_spacing(mut self, spacing: Vec<f64>) -> Self {
+        self.spacing = Some(spacing);
+        self
+    }
+
+    pub fn with_staggered(mut self, staggered: bool) -> Self {
+        self.staggered = staggered;
+        self
+    }
+
+    pub fn with_skip_reset_qubits(mut self, skip_reset_qubits: bool) -> Self {
+        self.skip_reset_qubits = skip_reset_qubits;
+        self
+    }
+
+    pub fn with_extra_slack_distribution(mut self, distribution: ExtraSlackDistribution) -> Self {
+        self.extra_slack_distribution = distribution;
+        self
+    }
+}
+
+/// Check that the DD sequence composes to the identity and return its global phase.
+fn sequence_phase(sequence: &[(StandardGate, SmallVec<[Param; 3]>)]) -> PyResult<f64> {
+    if sequence.is_empty() || sequence.len() % 2 != 0 {
+        return Err(TranspilerError::new_err(
+            "DD sequence must contain an even number of gates.",
+        ));
+    }
+    let mut total = Array2::<Complex64>::eye(2);
+    for (gate, params) in sequence {
+        if gate.num_qubits() != 1 {
+            return Err(TranspilerError::new_err(format!(
+                "DD sequence gate {} is not a single-qubit gate.",
+                gate.name()
+            )));
+        }
+        let Some(matrix) = gate.matrix(params) else {
+            return Err(TranspilerError::new_err(format!(
+                "DD sequence gate {} has unbound parameters.",
+                gate.name()
+            )));
+        };
+        total = matrix.dot(&total);
+    }
+    let phase = total[[0, 0]];
+    let is_identity = (phase.norm() - 1.).abs() < IDENTITY_TOLERANCE
+        && total[[0, 1]].norm() < IDENTITY_TOLERANCE
+        && total[[1, 0]].norm() < IDENTITY_TOLERANCE
+        && (total[[1, 1]] - phase).norm() < IDENTITY_TOLERANCE;
+    if !is_identity {
+        return Err(TranspilerError::new_err(
+            "The DD sequence does not make an identity operation.",
+        ));
+    }
+    Ok(phase.arg())
+}
+
+/// Get the spacing of the DD sequence on each qubit, indexed by qubit.
+fn qubit_spacings(
+    config: &DynamicalDecouplingConfig,
+    target: &Target,
+    num_qubits: usize,
+) -> PyResult<Vec<Vec<f64>>> {
+    let num_gates = config.sequence.len();
+    if let Some(spacing) = &config.spacing {
+        if spacing.len() != num_gates + 1 {
+            return Err(TranspilerError::new_err(
+                "The number of spacings must be one more than the number of DD gates.",
+            ));
+        }
+        if (spacing.iter().sum::<f64>() - 1.).abs() > IDENTITY_TOLERANCE
+            || spacing.iter().any(|x| *x < 0.)
+        {
+            return Err(TranspilerError::new_err(
+                "The spacings must be given in terms of fractions of the slack period and sum to 1.",
+            ));
+        }
+        if config.staggered {
+            return Err(TranspilerError::new_err(
+                "Staggered spacing cannot be combined with an explicit spacing.",
+            ));
+        }
+        return Ok(vec![spacing.clone(); num_qubits]);
+    }
+
+    let mid = 1. / num_gates as f64;
+    let balanced = |shift: f64| {
+        let mut spacing = vec![mid; num_gates + 1];
+        spacing[0] = mid / 2. + shift;
+        spacing[num_gates] = mid / 2. - shift;
+        spacing
+    };
+    if !config.staggered {
+        return Ok(vec![balanced(0.); num_qubits]);
+    }
+    // Without a coupling graph, there is no notion of neighbouring qubits to stagger.
+    let Ok(coupling) = target.coupling_graph() else {
+        return Ok(vec![balanced(0.); num_qubits]);
+    };
+    let colors = greedy_node_color(&coupling);
+    let num_colors = colors.values().max().map_or(1, |max| max + 1);
+    Ok((0..num_qubits)
+        .map(|qubit| {
+            let color = colors.get(&NodeIndex::new(qubit)).copied().unwrap_or(0);
+            // Shift each colour by a different fraction of the gate spacing, wrapped into
+            // `[-mid / 2, mid / 2)` so all the delays stay non-negative.
+            let fraction = color as f64 / num_colors as f64;
+            balanced(((fraction + 0.5).fract() - 0.5) * mid)
+        })
+        .collect())
+}
+
+/// Round a time down to a multiple of ``quantum``.
+fn constrained_length(value: f64, quantum: f64) -> f64 {
+    quantum * (value / quantum).floor()
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// The precomputed information needed to pad the idle windows of a circuit.
+struct Padder<'a> {
+    config: &'a DynamicalDecouplingConfig,
+    target: &'a Target,
+    delay_unit: DelayUnit,
+    /// The quantum that all DD delays are rounded down to, or ``None`` if the times are in
+    /// seconds and are not constrained.
+    quantum: Option<f64>,
+    phase: f64,
+    /// The duration of each gate of the sequence, on each qubit that DD is applied to.
+    gate_durations: Vec<Option<Vec<f64>>>,
+    spacings: Vec<Vec<f64>>,
+}
+
+impl Padder<'_> {
+    fn push_delay(
+        &self,
+        dag: &mut DAGCircuit,
+        node_start_time: &mut NodeStartTimes,
+        qubit: Qubit,
+        t_start: f64,
+        duration: f64,
+    ) -> PyResult<()> {
+        let node = dag.apply_operation_back(
+            PackedOperation::from_standard_instruction(StandardInstruction::Delay(self.delay_unit)),
+            &[qubit],
+            &[],
+            Some(smallvec![Param::Float(duration)]),
+            None,
+            #[cfg(feature = "cache_pygates")]
+            None,
+        )?;
+        node_start_time.insert(node, t_start);
+        Ok(())
+    }
+
+    /// Compute the delays between the DD gates for an idle window, or return ``None`` if the
+    /// window should be filled with a single delay instead.
+    fn taus(&self, qubit: usize, time_interval: f64) -> Option<Vec<f64>> {
+        let gate_durations = self.gate_durations[qubit].as_ref()?;
+        let slack = time_interval - gate_durations.iter().sum::<f64>();
+        if slack <= 0. {
+            return None;
+        }
+        let constrain = |value: f64| match self.quantum {
+            Some(quantum) => constrained_length(value, quantum),
+            None => value,
+        };
+        let mut taus = self.spacings[qubit]
+            .iter()
+            .map(|fraction| constrain(slack * fraction))
+            .collect::<Vec<_>>();
+        let extra_slack = slack - taus.iter().sum::<f64>();
+        let last = taus.len() - 1;
+        match self.config.extra_slack_distribution {
+            ExtraSlackDistribution::Middle => {
+                let to_middle = constrain(extra_slack);
+                taus[last / 2] += to_middle;
+                // Any remainder that cannot be aligned goes to the end of the window.
+                taus[last] += extra_slack - to_middle;
+            }
+            ExtraSlackDistribution::Edges => {
+                let to_begin = constrain(extra_slack / 2.);
+                taus[0] += to_begin;
+                taus[last] += extra_slack - to_begin;
+            }
+        }
+        let min_length = self.target.min_length as f64;
+        if self.quantum.is_some() && taus.iter().any(|tau| *tau > 0. && *tau < min_length) {
+            return None;
+        }
+        Some(taus)
+    }
+
+    /// Fill the idle window ``[t_start, t_end)`` on ``qubit`` with the DD sequence, or with a
+    /// single delay if the sequence does not fit or should not be applied.
+    fn pad(
+        &self,
+        dag: &mut DAGCircuit,
+        node_start_time: &mut NodeStartTimes,
+        qubit: Qubit,
+        t_start: f64,
+        t_end: f64,
+        after_ground_state: bool,
+    ) -> PyResult<()> {
+        let time_interval = t_end - t_start;
+        if self.quantum.is_some() {
+            let alignment = self.target.pulse_alignment as f64;
+            if time_interval % alignment != 0. {
+                return Err(TranspilerError::new_err(format!(
+                    "Time interval {} is not divisible by alignment {} on qubit {}.",
+                    time_interval,
+                    alignment,
+                    qubit.index()
+                )));
+            }
+        }
+        let taus = if self.config.skip_reset_qubits && after_ground_state {
+            None
+        } else {
+            self.taus(qubit.index(), time_interval)
+        };
+        let Some(taus) = taus else {
+            return self.push_delay(dag, node_start_time, qubit, t_start, time_interval);
+        };
+        let gate_durations = self.gate_durations[qubit.index()].as_ref().unwrap();
+        let mut idle_after = t_start;
+        for (index, tau) in taus.into_iter().enumerate() {
+            if tau > 0. {
+                self.push_delay(dag, node_start_time, qubit, idle_after, tau)?;
+                idle_after += tau;
+            }
+            if let Some((gate, params)) = self.config.sequence.get(index) {
+                let node = dag.apply_operation_back(
+                    (*gate).into(),
+                    &[qubit],
+                    &[],
+                    (!params.is_empty()).then(|| params.clone()),
+                    None,
+                    #[cfg(feature = "cache_pygates")]
+                    None,
+                )?;
+                node_start_time.insert(node, idle_after);
+                idle_after += gate_durations[index];
+            }
+        }
+        dag.add_global_phase(&Param::Float(-self.phase))
+    }
+}
+
+/// Insert dynamical decoupling sequences into the idle windows of a scheduled circuit.
+///
+/// ``node_start_time`` must contain the start time of every operation node of ``dag``, as
+/// produced by [qiskit_transpiler::passes::run_asap_schedule_analysis] or
+/// [qiskit_transpiler::passes::run_alap_schedule_analysis].  Delays in the input circuit are
+/// treated as idle time.  Each idle window on a qubit that supports the gates of the sequence is
+/// filled with the sequence, separated by delays according to the spacing; windows that are too
+/// short for the sequence are filled with a single delay instead.  Qubits on which the target
+/// does not support delays are not padded at all.
+///
+/// When the target specifies ``dt``, the delays between the gates are multiples of both the
+/// target's ``pulse_alignment`` and ``granularity``, and a window is only filled with the
+/// sequence if each of its delays is at least ``min_length`` long.  The durations of the gates
+/// of the sequence must then be multiples of the ``pulse_alignment``.
+///
+/// Returns the padded circuit, and the start time of each of its operation nodes.
+pub fn run_dynamical_decoupling(
+    dag: &DAGCircuit,
+    target: &Target,
+    node_start_time: &NodeStartTimes,
+    config: &DynamicalDecouplingConfig,
+) -> PyResult<(DAGCircuit, NodeStartTimes)> {
+    let phase = sequence_phase(&config.sequence)?;
+    let num_qubits = dag.num_qubits();
+    let gate_durations = (0..num_qubits)
+        .map(|qubit| -> PyResult<Option<Vec<f64>>> {
+            if config
+                .qubits
+                .as_ref()
+                .is_some_and(|qubits| !qubits.contains(&qubit))
+            {
+                return Ok(None);
+            }
+            let qargs = [PhysicalQubit::new(qubit as u32)];
+            let mut durations = Vec::with_capacity(config.sequence.len());
+            for (gate, _) in config.sequence.iter() {
+                if !target.instruction_supported(gate.name(), &qargs) {
+                    return Ok(None);
+                }
+                let Some(duration) = target.get_duration(gate.name(), &qargs) else {
+                    return Err(TranspilerError::new_err(format!(
+                        "Duration of {} on qubit {} is not found.",
+                        gate.name(),
+                        qubit
+                    )));
+                };
+                let Some(dt) = target.dt else {
+                    durations.push(duration);
+                    continue;
+                };
+                let duration = (duration / dt).round();
+                // The delays are aligned on the assumption that the gates keep the alignment.
+                if duration % target.pulse_alignment as f64 != 0. {
+                    return Err(TranspilerError::new_err(format!(
+                        "Pulse gate {} with length non-multiple of {} is not acceptable in \
+                        the dynamical decoupling pass.",
+                        gate.name(),
+                        target.pulse_alignment
+                    )));
+                }
+                durations.push(duration);
+            }
+            Ok(Some(durations))
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    let quantum = target.dt.map(|_| {
+        let (alignment, granularity) = (target.pulse_alignment, target.granularity);
+        (alignment / gcd(alignment, granularity) * granularity) as f64
+    });
+    let padder = Padder {
+        config,
+        target,
+        delay_unit: if target.dt.is_some() {
+            DelayUnit::DT
+        } else {
+            DelayUnit::S
+        },
+        quantum,
+        phase,
+        gate_durations,
+        spacings: qubit_spacings(config, target, num_qubits)?,
+    };
+    let delay_supported = (0..num_qubits)
+        .map(|qubit| target.instruction_supported("delay", &[PhysicalQubit::new(qubit as u32)]))
+        .collect::<Vec<_>>();
+
+    let mut new_dag = dag.copy_empty_like(VarsMode::Alike)?;
+    let mut new_start_time = NodeStartTimes::with_capacity(node_start_time.len());
+    let mut idle_after = vec![0.; num_qubits];
+    // Whether the qubit is known to be in its ground state at the start of its idle window.
+    let mut ground_state = vec![true; num_qubits];
+    let mut circuit_duration: f64 = 0.;
+    for node in dag.topological_op_nodes()? {
+        let NodeType::Operation(inst) = &dag[node] else {
+            unreachable!("op node iterators only yield operation nodes");
+        };
+        let Some(&t0) = node_start_time.get(&node) else {
+            return Err(TranspilerError::new_err(format!(
+                "Operation {} is likely added after the circuit is scheduled. Schedule the \
+                circuit again if you transformed it.",
+                inst.op.name()
+            )));
+        };
+        let t1 = t0 + instruction_duration(dag, target, inst)?.unwrap_or(0.);
+        circuit_duration = circuit_duration.max(t1);
+        // Delays are idle time, which is filled by the padding.
+        if let OperationRef::StandardInstruction(StandardInstruction::Delay(_)) = inst.op.view() {
+            continue;
+        }
+        let is_reset = matches!(
+            inst.op.view(),
+            OperationRef::StandardInstruction(StandardInstruction::Reset)
+        );
+        for qubit in dag.get_qargs(inst.qubits) {
+            let index = qubit.index();
+            if t0 > idle_after[index] && delay_supported[index] {
+                padder.pad(
+                    &mut new_dag,
+                    &mut new_start_time,
+                    *qubit,
+                    idle_after[index],
+                    t0,
+                    ground_state[index],
+                )?;
+            }
+            idle_after[index] = t1;
+            ground_state[index] = is_reset;
+        }
+        let new_node = new_dag.push_back(inst.clone())?;
+        new_start_time.insert(new_node, t0);
+    }
+    for index in 0..num_qubits {
+        if circuit_duration > idle_after[index] && delay_supported[index] {
+            padder.pad(
+                &mut new_dag,
+                &mut new_start_time,
+                Qubit(index as u32),
+                idle_after[index],
+                circuit_duration,
+                ground_state[index],
+            )?;
+        }
+    }
+    Ok((new_dag, new_start_time))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qiskit_circuit::circuit_data::CircuitData;
+    use qiskit_transpiler::passes::run_asap_schedule_analysis;
+    use qiskit_transpiler::test_utils::scheduling_target;
+
+    fn target(min_length: u32) -> Target {
+        scheduling_target(min_length, 1)
+    }
+
+    /// Both qubits are excited, and then qubit 0 idles for 1000dt before a CX.
+    fn circuit() -> DAGCircuit {
+        let mut circuit = CircuitData::with_capacity(2, 0, 0, Param::Float(0.)).unwrap();
+        circuit.push_standard_gate(StandardGate::X, &[], &[Qubit(0)]);
+        circuit.push_standard_gate(StandardGate::X, &[], &[Qubit(1)]);
+        circuit.push_packed_operation(
+            PackedOperation::from_standard_instruction(StandardInstruction::Delay(DelayUnit::DT)),
+            &[Param::Float(1000.)],
+            &[Qubit(0)],
+            &[],
+        );
+        circuit.push_standard_gate(StandardGate::CX, &[], &[Qubit(0), Qubit(1)]);
+        DAGCircuit::from_circuit_data(circuit, false).unwrap()
+    }
+
+    /// Get the sorted start times of each gate of the given name on a qubit.
+    fn start_times(
+        dag: &DAGCircuit,
+        node_start_time: &NodeStartTimes,
+        name: &str,
+        qubit: u32,
+    ) -> Vec<f64> {
+        let mut times = dag
+            .op_nodes(false)
+            .filter(|(_, inst)| {
+                inst.op.name() == name && dag.get_qargs(inst.qubits) == [Qubit(qubit)]
+            })
+            .map(|(node, _)| node_start_time[&node])
+            .collect::<Vec<_>>();
+        times.sort_by(f64::total_cmp);
+        times
+    }
+
+    #[test]
+    fn test_balanced_xx() {
+        let target = target(1);
+        let dag = circuit();
+        let node_start_time = run_asap_schedule_analysis(&dag, &target, 0.).unwrap();
+        let config = DynamicalDecouplingConfig::from_gates(&[StandardGate::X, StandardGate::X]);
+        let (out, out_start_time) =
+            run_dynamical_decoupling(&dag, &target, &node_start_time, &config).unwrap();
+        // The slack of 1000 - 2 * 160 = 680dt is split as 170, 340, 170.
+        assert_eq!(
+            start_times(&out, &out_start_time, "x", 0),
+            vec![0., 330., 830.]
+        );
+        assert_eq!(
+            start_times(&out, &out_start_time, "delay", 0),
+            vec![160., 490., 990.]
+        );
+        assert_eq!(
+            start_times(&out, &out_start_time, "x", 1),
+            vec![0., 330., 830.]
+        );
+        assert_eq!(out.num_ops(), 13);
+    }
+
+    #[test]
+    fn test_staggered_xy4() {
+        let target = target(1);
+        let dag = circuit();
+        let node_start_time = run_asap_schedule_analysis(&dag, &target, 0.).unwrap();
+        let config = DynamicalDecouplingConfig::from_gates(&[
+            StandardGate::X,
+            StandardGate::Y,
+            StandardGate::X,
+            StandardGate::Y,
+        ])
+        .with_staggered(true);
+        let (out, out_start_time) =
+            run_dynamical_decoupling(&dag, &target, &node_start_time, &config).unwrap();
+        // The slack of 1000 - 4 * 160 = 360dt is split as 45, 90, 90, 90, 45 on one qubit, and
+        // shifted by half a spacing to 0, 90, 90, 90, 90 on its neighbour.
+        let mut times = [
+            start_times(&out, &out_start_time, "y", 0),
+            start_times(&out, &out_start_time, "y", 1),
+        ];
+        times.sort_by(|a, b| a[0].total_cmp(&b[0]));
+        assert_eq!(times, [vec![410., 910.], vec![455., 955.]]);
+    }
+
+    #[test]
+    fn test_min_length_falls_back_to_delay() {
+        let target = target(200);
+        let dag = circuit();
+        let node_start_time = run_asap_schedule_analysis(&dag, &target, 0.).unwrap();
+        let config = DynamicalDecouplingConfig::from_gates(&[StandardGate::X, StandardGate::X]);
+        let (out, out_start_time) =
+            run_dynamical_decoupling(&dag, &target, &node_start_time, &config).unwrap();
+        assert_eq!(start_times(&out, &out_start_time, "x", 0), vec![0.]);
+        assert_eq!(start_times(&out, &out_start_time, "delay", 0), vec![160.]);
+    }
+
+    #[test]
+    fn test_sequence_phase() {
+        let xx = DynamicalDecouplingConfig::from_gates(&[StandardGate::X, StandardGate::X]);
+        assert_eq!(sequence_phase(&xx.sequence).unwrap(), 0.);
+        // XY4 composes to -I.
+        let xy4 = DynamicalDecouplingConfig::from_gates(&[
+            StandardGate::X,
+            StandardGate::Y,
+            StandardGate::X,
+            StandardGate::Y,
+        ]);
+        let phase = sequence_phase(&xy4.sequence).unwrap();
+        assert!((phase.abs() - std::f64::consts::PI).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_misaligned_gate_duration() {
+        let dag = circuit();
+        let node_start_time = run_asap_schedule_analysis(&dag, &target(1), 0.).unwrap();
+        let config = DynamicalDecouplingConfig::from_gates(&[StandardGate::X, StandardGate::X]);
+        // The 160dt X gates are a multiple of an alignment of 8, but not of 64.
+        let aligned = scheduling_target(1, 8);
+        assert!(run_dynamical_decoupling(&dag, &aligned, &node_start_time, &config).is_ok());
+        let misaligned = scheduling_target(1, 64);
+        assert!(run_dynamical_decoupling(&dag, &misaligned, &node_start_time, &config).is_err());
+    }
+
+    #[test]
+    fn test_non_identity_sequence() {
+        let target = target(1);
+        let dag = circuit();
+        let node_start_time = run_asap_schedule_analysis(&dag, &target, 0.).unwrap();
+        let config = DynamicalDecouplingConfig::from_gates(&[StandardGate::X, StandardGate::Y]);
+        assert!(run_dynamical_decoupling(&dag, &target, &node_start_time, &config).is_err());
+    }
+}
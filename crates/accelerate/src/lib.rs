@@ -14,11 +14,13 @@ use pyo3::import_exception;
 
 pub mod circuit_duration;
 pub mod circuit_library;
+pub mod dynamical_decoupling;
 pub mod isometry;
 pub mod optimize_1q_gates;
 pub mod pauli_exp_val;
 pub mod results;
 pub mod sampled_exp_val;
+pub mod statevector;
 pub mod twirling;
 pub mod uc_gate;
 
@@ -21,7 +21,7 @@ use rayon::prelude::*;
 use qiskit_circuit::getenv_use_multiple_threads;
 use qiskit_circuit::util::c64;
 
-const PARALLEL_THRESHOLD: usize = 19;
+pub(crate) const PARALLEL_THRESHOLD: usize = 19;
 
 #[pulp::with_simd(fast_sum = pulp::Arch::new())]
 #[inline(always)]
@@ -0,0 +1,538 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2025
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+use hashbrown::HashMap;
+use ndarray::ArrayView2;
+use num_complex::Complex64;
+use rand::distr::weighted::WeightedIndex;
+use rand::prelude::*;
+use rand_pcg::Pcg64Mcg;
+use rayon::prelude::*;
+use smallvec::SmallVec;
+use thiserror::Error;
+
+use qiskit_circuit::circuit_data::CircuitData;
+use qiskit_circuit::getenv_use_multiple_threads;
+use qiskit_circuit::operations::{Operation, OperationRef, Param, StandardInstruction};
+use qiskit_circuit::packed_instruction::PackedInstruction;
+use qiskit_circuit::util::{c64, C_ZERO};
+use qiskit_circuit::{Clbit, Qubit};
+
+use crate::pauli_exp_val::{fast_sum, PARALLEL_THRESHOLD};
+
+/// Errors that can occur while simulating a circuit.
+#[derive(Error, Debug)]
+pub enum SimulatorError {
+    /// The circuit contains an operation the simulator has no action for.
+    #[error("Operation '{0}' is not supported by the statevector simulator.")]
+    UnsupportedOperation(String),
+    /// The circuit contains unbound parameters, either in an operation or in the global phase.
+    #[error("Circuit contains unbound parameters.")]
+    ParameterizedCircuit,
+    /// The statevector of this many qubits cannot be allocated.
+    #[error("The number of qubits, {0}, is too large to allocate a statevector.")]
+    TooManyQubits(usize),
+}
+
+/// The largest number of qubits for which a statevector can be allocated.  This ensures that the
+/// number of bytes in the statevector, at 16 bytes per amplitude, fits in an `isize`.
+pub const MAX_STATEVECTOR_QUBITS: u32 = usize::BITS - 6;
+
+/// A dense statevector in Qiskit's little-endian ordering, where qubit ``i`` corresponds to bit
+/// ``i`` of the index into the amplitudes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Statevector {
+    num_qubits: u32,
+    data: Vec<Complex64>,
+}
+
+impl Statevector {
+    /// Create the all-zeros state ``|0...0>`` on ``num_qubits`` qubits.  Returns an error if there
+    /// are more than [MAX_STATEVECTOR_QUBITS] qubits, or if the allocation fails.
+    pub fn zero(num_qubits: u32) -> Result<Self, SimulatorError> {
+        let too_many = || SimulatorError::TooManyQubits(num_qubits as usize);
+        if num_qubits > MAX_STATEVECTOR_QUBITS {
+            return Err(too_many());
+        }
+        let len = 1_usize << num_qubits;
+        let mut data = Vec::new();
+        data.try_reserve_exact(len).map_err(|_| too_many())?;
+        data.resize(len, C_ZERO);
+        data[0] = c64(1., 0.);
+        Ok(Self { num_qubits, data })
+    }
+
+    /// Create a statevector from its amplitudes.  Returns `None` if the length of `data` is not a
+    /// power of two.  The amplitudes are not checked for normalization.
+    pub fn from_data(data: Vec<Complex64>) -> Option<Self> {
+        if !data.len().is_power_of_two() {
+            return None;
+        }
+        Some(Self {
+            num_qubits: data.len().ilog2(),
+            data,
+        })
+    }
+
+    #[inline]
+    pub fn num_qubits(&self) -> u32 {
+        self.num_qubits
+    }
+
+    #[inline]
+    pub fn data(&self) -> &[Complex64] {
+        &self.data
+    }
+
+    #[inline]
+    pub fn into_data(self) -> Vec<Complex64> {
+        self.data
+    }
+
+    #[inline]
+    fn run_in_parallel(&self) -> bool {
+        self.num_qubits as usize >= PARALLEL_THRESHOLD && getenv_use_multiple_threads()
+    }
+
+    /// Multiply all amplitudes by a scalar, such as a global phase.
+    pub fn scale(&mut self, factor: Complex64) {
+        if self.run_in_parallel() {
+            self.data.par_iter_mut().for_each(|amp| *amp *= factor);
+        } else {
+            self.data.iter_mut().for_each(|amp| *amp *= factor);
+        }
+    }
+
+    /// Apply the `2**k x 2**k` `matrix` to the `k` given `qubits`.  The matrix is in the usual
+    /// Qiskit convention, so `qubits[0]` is the least-significant bit of the matrix's indices.
+    ///
+    /// # Panics
+    ///
+    /// If the matrix dimensions do not match the number of qubits, or a qubit is out of range.
+    pub fn apply_matrix(&mut self, matrix: ArrayView2<Complex64>, qubits: &[Qubit]) {
+        let dim = 1_usize << qubits.len();
+        assert_eq!(matrix.dim(), (dim, dim));
+        let Some(max_qubit) = qubits.iter().map(|q| q.0).max() else {
+            // A zero-qubit operation is a global phase.
+            self.scale(matrix[[0, 0]]);
+            return;
+        };
+        assert!(max_qubit < self.num_qubits);
+
+        let mut positions: SmallVec<[u32; 4]> = qubits.iter().map(|q| q.0).collect();
+        positions.sort_unstable();
+        // `offsets[j]` is the index offset of the basis state `j` of the operation's subspace.
+        let offsets: SmallVec<[usize; 16]> = (0..dim)
+            .map(|j| {
+                qubits
+                    .iter()
+                    .enumerate()
+                    .filter(|(bit, _)| (j >> bit) & 1 == 1)
+                    .fold(0, |acc, (_, q)| acc | (1 << q.0))
+            })
+            .collect();
+        let kernel = |chunk: &mut [Complex64]| {
+            let mut scratch: SmallVec<[Complex64; 16]> = SmallVec::from_elem(C_ZERO, dim);
+            for i in 0..(chunk.len() >> qubits.len()) {
+                let base = insert_zero_bits(i, &positions);
+                for (amp, offset) in scratch.iter_mut().zip(offsets.iter()) {
+                    *amp = chunk[base | offset];
+                }
+                for (row, offset) in offsets.iter().enumerate() {
+                    chunk[base | offset] = scratch
+                        .iter()
+                        .enumerate()
+                        .map(|(col, amp)| matrix[[row, col]] * amp)
+                        .sum();
+                }
+            }
+        };
+        // Every chunk of `2 ** (max_qubit + 1)` amplitudes is closed under the action of the
+        // operation, so the chunks can be handled independently.
+        let chunk_size = 1_usize << (max_qubit + 1);
+        if self.run_in_parallel() {
+            self.data.par_chunks_mut(chunk_size).for_each(kernel);
+        } else {
+            self.data.chunks_mut(chunk_size).for_each(kernel);
+        }
+    }
+
+    /// The probability of measuring `qubit` in the `|1>` state.
+    pub fn probability_one(&self, qubit: Qubit) -> f64 {
+        let mask = 1_usize << qubit.0;
+        let map_fn = |(i, amp): (usize, &Complex64)| -> f64 {
+            if i & mask != 0 {
+                amp.norm_sqr()
+            } else {
+                0.
+            }
+        };
+        if self.run_in_parallel() {
+            self.data.par_iter().enumerate().map(map_fn).sum()
+        } else {
+            fast_sum(
+                &self
+                    .data
+                    .iter()
+                    .enumerate()
+                    .map(map_fn)
+                    .collect::<Vec<f64>>(),
+            )
+        }
+    }
+
+    /// The probabilities of each computational basis state.
+    pub fn probabilities(&self) -> Vec<f64> {
+        if self.run_in_parallel() {
+            self.data.par_iter().map(|amp| amp.norm_sqr()).collect()
+        } else {
+            self.data.iter().map(|amp| amp.norm_sqr()).collect()
+        }
+    }
+
+    /// Project `qubit` onto the given measurement `outcome` and renormalize, where `probability`
+    /// is the probability of that outcome occurring.
+    pub fn collapse(&mut self, qubit: Qubit, outcome: bool, probability: f64) {
+        let mask = 1_usize << qubit.0;
+        let norm = 1. / probability.sqrt();
+        let map_fn = |(i, amp): (usize, &mut Complex64)| {
+            if (i & mask != 0) == outcome {
+                *amp *= norm;
+            } else {
+                *amp = C_ZERO;
+            }
+        };
+        if self.run_in_parallel() {
+            self.data.par_iter_mut().enumerate().for_each(map_fn);
+        } else {
+            self.data.iter_mut().enumerate().for_each(map_fn);
+        }
+    }
+
+    /// Measure `qubit` in the computational basis, collapsing the state and returning the outcome.
+    pub fn measure<R: Rng>(&mut self, qubit: Qubit, rng: &mut R) -> bool {
+        let p_one = self.probability_one(qubit);
+        let outcome = rng.random::<f64>() < p_one;
+        self.collapse(qubit, outcome, if outcome { p_one } else { 1. - p_one });
+        outcome
+    }
+
+    /// Reset `qubit` to the `|0>` state.
+    pub fn reset<R: Rng>(&mut self, qubit: Qubit, rng: &mut R) {
+        if self.measure(qubit, rng) {
+            let mask = 1_usize << qubit.0;
+            // Every chunk of `2 * mask` amplitudes has its `|1>` half after its `|0>` half.
+            let kernel = |chunk: &mut [Complex64]| {
+                let (zero, one) = chunk.split_at_mut(mask);
+                zero.swap_with_slice(one);
+            };
+            if self.run_in_parallel() {
+                self.data.par_chunks_mut(mask << 1).for_each(kernel);
+            } else {
+                self.data.chunks_mut(mask << 1).for_each(kernel);
+            }
+        }
+    }
+
+    /// The inner product `<self|other>`.
+    ///
+    /// # Panics
+    ///
+    /// If the two statevectors are defined over a different number of qubits.
+    pub fn inner(&self, other: &Statevector) -> Complex64 {
+        assert_eq!(self.num_qubits, other.num_qubits);
+        if self.run_in_parallel() {
+            self.data
+                .par_iter()
+                .zip(other.data.par_iter())
+                .map(|(left, right)| left.conj() * right)
+                .sum()
+        } else {
+            self.data
+                .iter()
+                .zip(other.data.iter())
+                .map(|(left, right)| left.conj() * right)
+                .sum()
+        }
+    }
+
+    /// Whether two normalized statevectors are equal up to a global phase, within the absolute
+    /// tolerance `atol` on the fidelity.
+    pub fn equiv(&self, other: &Statevector, atol: f64) -> bool {
+        self.num_qubits == other.num_qubits && (1. - self.inner(other).norm()).abs() <= atol
+    }
+}
+
+/// Insert zero bits into `value` at each of the sorted bit `positions`.
+#[inline]
+fn insert_zero_bits(mut value: usize, positions: &[u32]) -> usize {
+    for position in positions {
+        let low = value & ((1 << position) - 1);
+        value = ((value ^ low) << 1) | low;
+    }
+    value
+}
+
+/// The result of a single execution of a circuit.
+#[derive(Clone, Debug)]
+pub struct SimulationResult {
+    /// The final state of the qubits.
+    pub statevector: Statevector,
+    /// The final state of the clbits, indexed by clbit.
+    pub clbits: Vec<bool>,
+}
+
+/// The action of a single instruction on the simulator state.
+enum Action<'a> {
+    Unitary(ndarray::Array2<Complex64>, &'a [Qubit]),
+    Measure(Qubit, Clbit),
+    Reset(Qubit),
+    Nothing,
+}
+
+fn instruction_action<'a>(
+    circuit: &'a CircuitData,
+    inst: &'a PackedInstruction,
+) -> Result<Action<'a>, SimulatorError> {
+    let qubits = circuit.get_qargs(inst.qubits);
+    match inst.op.view() {
+        OperationRef::StandardInstruction(instruction) => match instruction {
+            StandardInstruction::Measure => Ok(Action::Measure(
+                qubits[0],
+                circuit.get_cargs(inst.clbits)[0],
+            )),
+            StandardInstruction::Reset => Ok(Action::Reset(qubits[0])),
+            StandardInstruction::Barrier(_) | StandardInstruction::Delay(_) => Ok(Action::Nothing),
+        },
+        op @ (OperationRef::StandardGate(_) | OperationRef::Unitary(_)) => {
+            let params = inst.params_view();
+            if params.iter().any(|param| !matches!(param, Param::Float(_))) {
+                return Err(SimulatorError::ParameterizedCircuit);
+            }
+            let matrix = op
+                .matrix(params)
+                .ok_or_else(|| SimulatorError::UnsupportedOperation(op.name().to_string()))?;
+            Ok(Action::Unitary(matrix, qubits))
+        }
+        op => Err(SimulatorError::UnsupportedOperation(op.name().to_string())),
+    }
+}
+
+fn global_phase_factor(circuit: &CircuitData) -> Result<Complex64, SimulatorError> {
+    match circuit.global_phase() {
+        Param::Float(phase) => Ok(Complex64::from_polar(1., *phase)),
+        _ => Err(SimulatorError::ParameterizedCircuit),
+    }
+}
+
+fn check_num_qubits(circuit: &CircuitData) -> Result<u32, SimulatorError> {
+    let num_qubits = circuit.num_qubits();
+    if num_qubits > MAX_STATEVECTOR_QUBITS as usize {
+        return Err(SimulatorError::TooManyQubits(num_qubits));
+    }
+    Ok(num_qubits as u32)
+}
+
+fn run_once<R: Rng>(
+    circuit: &CircuitData,
+    actions: &[Action],
+    num_qubits: u32,
+    rng: &mut R,
+) -> Result<SimulationResult, SimulatorError> {
+    let mut statevector = Statevector::zero(num_qubits)?;
+    let mut clbits = vec![false; circuit.num_clbits()];
+    for action in actions {
+        match action {
+            Action::Unitary(matrix, qubits) => statevector.apply_matrix(matrix.view(), qubits),
+            Action::Measure(qubit, clbit) => {
+                clbits[clbit.index()] = statevector.measure(*qubit, rng);
+            }
+            Action::Reset(qubit) => statevector.reset(*qubit, rng),
+            Action::Nothing => (),
+        }
+    }
+    statevector.scale(global_phase_factor(circuit)?);
+    Ok(SimulationResult {
+        statevector,
+        clbits,
+    })
+}
+
+fn make_rng(seed: Option<u64>) -> Pcg64Mcg {
+    match seed {
+        Some(seed) => Pcg64Mcg::seed_from_u64(seed),
+        None => Pcg64Mcg::from_os_rng(),
+    }
+}
+
+/// Run a circuit once on the statevector simulator, starting from the all-zeros state.
+///
+/// The circuit may contain standard gates, unitary gates, measurements, resets, barriers and
+/// delays; any other operation causes an error.  Measurements and resets collapse the state
+/// randomly according to the Born rule, using an RNG seeded by `seed` if given.
+pub fn simulate_statevector(
+    circuit: &CircuitData,
+    seed: Option<u64>,
+) -> Result<SimulationResult, SimulatorError> {
+    let num_qubits = check_num_qubits(circuit)?;
+    let actions = circuit
+        .iter()
+        .map(|inst| instruction_action(circuit, inst))
+        .collect::<Result<Vec<_>, _>>()?;
+    run_once(circuit, &actions, num_qubits, &mut make_rng(seed))
+}
+
+/// Sample the final clbit values of a circuit over a number of shots.
+///
+/// Each entry of the returned vector is the clbit values of a single shot, indexed by clbit.  If
+/// every measurement in the circuit is terminal, the quantum part of the circuit is simulated only
+/// once and the shots are sampled from the final probability distribution.  Otherwise, the circuit
+/// is re-run for each shot.
+pub fn sample_memory(
+    circuit: &CircuitData,
+    shots: usize,
+    seed: Option<u64>,
+) -> Result<Vec<Vec<bool>>, SimulatorError> {
+    let num_qubits = check_num_qubits(circuit)?;
+    let actions = circuit
+        .iter()
+        .map(|inst| instruction_action(circuit, inst))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut rng = make_rng(seed);
+
+    let first_measure = actions
+        .iter()
+        .position(|action| matches!(action, Action::Measure(..) | Action::Reset(_)))
+        .unwrap_or(actions.len());
+    let terminal = actions[first_measure..]
+        .iter()
+        .all(|action| matches!(action, Action::Measure(..) | Action::Nothing));
+    if !terminal {
+        return (0..shots)
+            .map(|_| run_once(circuit, &actions, num_qubits, &mut rng).map(|res| res.clbits))
+            .collect();
+    }
+
+    let measurements = actions[first_measure..]
+        .iter()
+        .filter_map(|action| match action {
+            Action::Measure(qubit, clbit) => Some((*qubit, *clbit)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let state = run_once(circuit, &actions[..first_measure], num_qubits, &mut rng)?.statevector;
+    let distribution = WeightedIndex::new(state.probabilities())
+        .expect("a normalized statevector has a valid probability distribution");
+    Ok((0..shots)
+        .map(|_| {
+            let index = distribution.sample(&mut rng);
+            let mut clbits = vec![false; circuit.num_clbits()];
+            for (qubit, clbit) in measurements.iter() {
+                clbits[clbit.index()] = (index >> qubit.0) & 1 == 1;
+            }
+            clbits
+        })
+        .collect())
+}
+
+/// Sample the counts of each clbit outcome of a circuit over a number of shots.
+///
+/// The keys are bitstrings in the same format as Qiskit's ``Counts``, with clbit 0 as the
+/// right-most character.
+pub fn sample_counts(
+    circuit: &CircuitData,
+    shots: usize,
+    seed: Option<u64>,
+) -> Result<HashMap<String, usize>, SimulatorError> {
+    let mut counts = HashMap::new();
+    for clbits in sample_memory(circuit, shots, seed)? {
+        let key = clbits
+            .iter()
+            .rev()
+            .map(|bit| if *bit { '1' } else { '0' })
+            .collect::<String>();
+        *counts.entry(key).or_insert(0) += 1;
+    }
+    Ok(counts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+    use qiskit_circuit::operations::StandardGate;
+    use qiskit_circuit::packed_instruction::PackedOperation;
+    use std::f64::consts::FRAC_1_SQRT_2;
+
+    fn ghz(num_qubits: u32, measure: bool) -> CircuitData {
+        let num_clbits = if measure { num_qubits } else { 0 };
+        let mut circuit =
+            CircuitData::with_capacity(num_qubits, num_clbits, 0, Param::Float(0.)).unwrap();
+        circuit.push_standard_gate(StandardGate::H, &[], &[Qubit(0)]);
+        for i in 1..num_qubits {
+            circuit.push_standard_gate(StandardGate::CX, &[], &[Qubit(0), Qubit(i)]);
+        }
+        for i in 0..num_clbits {
+            circuit.push_packed_operation(
+                PackedOperation::from_standard_instruction(StandardInstruction::Measure),
+                &[],
+                &[Qubit(i)],
+                &[Clbit(i)],
+            );
+        }
+        circuit
+    }
+
+    #[test]
+    fn test_ghz_statevector() {
+        let result = simulate_statevector(&ghz(3, false), None).unwrap();
+        let data = result.statevector.data();
+        assert_abs_diff_eq!(data[0], c64(FRAC_1_SQRT_2, 0.), epsilon = 1e-12);
+        assert_abs_diff_eq!(data[7], c64(FRAC_1_SQRT_2, 0.), epsilon = 1e-12);
+        let rest: f64 = data[1..7].iter().map(|amp| amp.norm()).sum();
+        assert_abs_diff_eq!(rest, 0., epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_qubit_ordering() {
+        // X on qubit 1 of two qubits is the basis state |10>, which is index 2.
+        let mut circuit = CircuitData::with_capacity(2, 0, 1, Param::Float(0.)).unwrap();
+        circuit.push_standard_gate(StandardGate::X, &[], &[Qubit(1)]);
+        let result = simulate_statevector(&circuit, None).unwrap();
+        assert_abs_diff_eq!(result.statevector.data()[2], c64(1., 0.), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_ghz_counts_seeded() {
+        let counts = sample_counts(&ghz(4, true), 1000, Some(2025)).unwrap();
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts["0000"] + counts["1111"], 1000);
+        assert_eq!(
+            counts,
+            sample_counts(&ghz(4, true), 1000, Some(2025)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_too_many_qubits() {
+        let circuit =
+            CircuitData::with_capacity(MAX_STATEVECTOR_QUBITS + 1, 0, 0, Param::Float(0.)).unwrap();
+        assert!(matches!(
+            simulate_statevector(&circuit, None),
+            Err(SimulatorError::TooManyQubits(_))
+        ));
+        assert!(matches!(
+            Statevector::zero(MAX_STATEVECTOR_QUBITS + 1),
+            Err(SimulatorError::TooManyQubits(_))
+        ));
+    }
+}
@@ -13,7 +13,7 @@
 use std::ffi::{c_char, CStr, CString};
 
 use crate::exit_codes::ExitCode;
-use crate::pointers::{const_ptr_as_ref, mut_ptr_as_ref};
+use crate::pointers::{check_ptr, const_ptr_as_ref, mut_ptr_as_ref};
 
 use nalgebra::{Matrix2, Matrix4};
 use ndarray::{Array2, ArrayView2};
@@ -26,6 +26,7 @@ use qiskit_circuit::operations::{
     ArrayType, DelayUnit, Operation, Param, StandardGate, StandardInstruction, UnitaryGate,
 };
 use qiskit_circuit::packed_instruction::PackedOperation;
+use qiskit_circuit::serialization;
 use qiskit_circuit::{Clbit, Qubit};
 
 #[cfg(feature = "python_binding")]
@@ -810,7 +811,8 @@ pub struct CInstruction {
     qubits: *mut u32,
     /// A pointer to an array of clbit indices this instruction operates on.
     clbits: *mut u32,
-    /// A pointer to an array of parameter values for this instruction.
+    /// A pointer to an array of parameter values for this instruction.  Parameters that are
+    /// not bound to a number, such as symbolic parameters, are ``NaN``.
     params: *mut f64,
     /// The number of qubits for this instruction.
     num_qubits: u32,
@@ -824,7 +826,8 @@ pub struct CInstruction {
 /// Return the instruction details for an instruction in the circuit.
 ///
 /// This function is used to get the instruction details for a given instruction in
-/// the circuit.
+/// the circuit. Parameters that are not bound to a number, such as the symbolic parameters
+/// of a circuit loaded with ``qk_circuit_load``, are reported as ``NaN``.
 ///
 /// @param circuit A pointer to the circuit to get the instruction details for.
 /// @param index The instruction index to get the instruction details of.
@@ -867,7 +870,7 @@ pub unsafe extern "C" fn qk_circuit_get_instruction(
         .iter()
         .map(|x| match x {
             Param::Float(val) => *val,
-            _ => unreachable!("Invalid parameter on instruction"),
+            Param::ParameterExpression(_) | Param::Symbolic(_) | Param::Obj(_) => f64::NAN,
         })
         .collect();
     let out_qargs = qargs_vec.as_mut_ptr();
@@ -1069,3 +1072,179 @@ pub unsafe extern "C" fn qk_circuit_delay(
 
     ExitCode::Success
 }
+
+/// @ingroup QkCircuit
+/// Serialize the circuit into a versioned binary format.
+///
+/// The bytes can be loaded back with ``qk_circuit_load``, including by later versions of Qiskit.
+///
+/// @param circuit A pointer to the circuit.
+/// @param bytes A pointer to write the pointer to the serialized bytes to. On success, the bytes
+///     must be freed with ``qk_bytes_free``.
+/// @param len A pointer to write the number of serialized bytes to.
+/// @param error A pointer to a ``char *`` that is set to a description of the failure if the
+///     circuit can't be serialized, or a null pointer if the description is not needed. The
+///     string must be freed with ``qk_str_free``.
+///
+/// @return ``QkExitCode_Success`` on success, or ``QkExitCode_SerializationUnsupported`` if the
+///     circuit contains operations that can't be serialized.
+///
+/// # Example
+///
+///     QkCircuit *qc = qk_circuit_new(2, 0);
+///     uint8_t *bytes;
+///     size_t len;
+///     if (qk_circuit_dump(qc, &bytes, &len, NULL) == QkExitCode_Success) {
+///         qk_bytes_free(bytes, len);
+///     }
+///     qk_circuit_free(qc);
+///
+/// # Safety
+///
+/// Behavior is undefined if ``circuit`` is not a valid, non-null pointer to a ``QkCircuit``, if
+/// ``bytes`` or ``len`` are not valid, non-null pointers to a ``uint8_t *`` and a ``size_t``
+/// respectively, or if ``error`` is neither null nor a valid pointer to a ``char *``.
+#[no_mangle]
+#[cfg(feature = "cbinding")]
+pub unsafe extern "C" fn qk_circuit_dump(
+    circuit: *const CircuitData,
+    bytes: *mut *mut u8,
+    len: *mut usize,
+    error: *mut *mut c_char,
+) -> ExitCode {
+    // SAFETY: Per documentation, the pointers are non-null and aligned.
+    let circuit = unsafe { const_ptr_as_ref(circuit) };
+    let bytes = unsafe { mut_ptr_as_ref(bytes) };
+    let len = unsafe { mut_ptr_as_ref(len) };
+    match serialization::dump(circuit) {
+        Ok(out) => {
+            let out = out.into_boxed_slice();
+            *len = out.len();
+            *bytes = Box::into_raw(out) as *mut u8;
+            ExitCode::Success
+        }
+        Err(err) => {
+            *len = 0;
+            *bytes = ::std::ptr::null_mut();
+            // SAFETY: Per documentation, the pointer is null or valid for writes.
+            unsafe { write_serialization_error(error, &err) }
+        }
+    }
+}
+
+/// @ingroup QkCircuit
+/// Load a circuit serialized by ``qk_circuit_dump``.
+///
+/// Symbolic parameters are loaded without Python. Integer parameters, such as the durations of
+/// delays in ``dt`` of a circuit serialized from Python, are loaded as integers if a Python
+/// interpreter is running, and as floating-point numbers of the same value otherwise.
+///
+/// @param data A pointer to the serialized bytes.
+/// @param len The number of bytes in ``data``.
+/// @param circuit A pointer to write the pointer to the loaded circuit to. On success, the
+///     circuit must be freed with ``qk_circuit_free``.
+/// @param error A pointer to a ``char *`` that is set to a description of the failure if the
+///     data can't be loaded, or a null pointer if the description is not needed. The string
+///     must be freed with ``qk_str_free``.
+///
+/// @return ``QkExitCode_Success`` on success, ``QkExitCode_SerializationMalformed`` if the data
+///     is not a valid serialized circuit, or ``QkExitCode_SerializationUnsupportedVersion`` if
+///     it was written by a newer version of Qiskit.
+///
+/// # Example
+///
+///     QkCircuit *qc = qk_circuit_new(2, 0);
+///     uint8_t *bytes;
+///     size_t len;
+///     qk_circuit_dump(qc, &bytes, &len, NULL);
+///     QkCircuit *loaded;
+///     char *error;
+///     if (qk_circuit_load(bytes, len, &loaded, &error) == QkExitCode_Success) {
+///         qk_circuit_free(loaded);
+///     } else {
+///         printf("%s\n", error);
+///         qk_str_free(error);
+///     }
+///     qk_bytes_free(bytes, len);
+///     qk_circuit_free(qc);
+///
+/// # Safety
+///
+/// Behavior is undefined if ``data`` is not a valid, non-null pointer to ``len`` readable bytes,
+/// if ``circuit`` is not a valid, non-null pointer to a ``QkCircuit *``, or if ``error`` is
+/// neither null nor a valid pointer to a ``char *``.
+#[no_mangle]
+#[cfg(feature = "cbinding")]
+pub unsafe extern "C" fn qk_circuit_load(
+    data: *const u8,
+    len: usize,
+    circuit: *mut *mut CircuitData,
+    error: *mut *mut c_char,
+) -> ExitCode {
+    check_ptr(data).unwrap();
+    // SAFETY: Per documentation, the pointer is valid for reads of `len` bytes.
+    let data = unsafe { std::slice::from_raw_parts(data, len) };
+    // SAFETY: Per documentation, the pointer is non-null and aligned.
+    let circuit = unsafe { mut_ptr_as_ref(circuit) };
+    match serialization::load(data) {
+        Ok(loaded) => {
+            *circuit = Box::into_raw(Box::new(loaded));
+            ExitCode::Success
+        }
+        Err(err) => {
+            *circuit = ::std::ptr::null_mut();
+            // SAFETY: Per documentation, the pointer is null or valid for writes.
+            unsafe { write_serialization_error(error, &err) }
+        }
+    }
+}
+
+/// Write the description of a serialization error to a C error out-parameter, if it's not null,
+/// and return the matching exit code.
+///
+/// # Safety
+///
+/// `error` must be null or valid for writes.
+unsafe fn write_serialization_error(
+    error: *mut *mut c_char,
+    err: &serialization::SerializationError,
+) -> ExitCode {
+    if !error.is_null() {
+        // SAFETY: Per the function documentation, the pointer is valid for writes.
+        unsafe {
+            *error = CString::new(err.to_string()).unwrap().into_raw();
+        }
+    }
+    err.into()
+}
+
+/// @ingroup QkCircuit
+/// Free bytes returned by ``qk_circuit_dump``.
+///
+/// @param bytes A pointer to the bytes to free.
+/// @param len The number of bytes, as written by ``qk_circuit_dump``.
+///
+/// # Example
+///
+///     QkCircuit *qc = qk_circuit_new(2, 0);
+///     uint8_t *bytes;
+///     size_t len;
+///     if (qk_circuit_dump(qc, &bytes, &len, NULL) == QkExitCode_Success) {
+///         qk_bytes_free(bytes, len);
+///     }
+///     qk_circuit_free(qc);
+///
+/// # Safety
+///
+/// Behavior is undefined if ``bytes`` is not either null or a pointer returned by
+/// ``qk_circuit_dump`` together with the length it wrote.
+#[no_mangle]
+#[cfg(feature = "cbinding")]
+pub unsafe extern "C" fn qk_bytes_free(bytes: *mut u8, len: usize) {
+    if !bytes.is_null() {
+        // SAFETY: Per documentation, the pointer and length came from a boxed slice.
+        unsafe {
+            let _ = Box::from_raw(std::ptr::slice_from_raw_parts_mut(bytes, len));
+        }
+    }
+}
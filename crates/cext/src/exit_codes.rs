@@ -10,8 +10,10 @@
 // copyright notice, and modified files need to carry a notice indicating
 // that they have been altered from the originals.
 
-use qiskit_quantum_info::sparse_observable::ArithmeticError;
-use qiskit_transpiler::target::TargetError;
+use qiskit_accelerate::statevector::SimulatorError;
+use qiskit_circuit::serialization::SerializationError;
+use qiskit_quantum_info::sparse_observable::{ArithmeticError, ExpectationError, MatrixError};
+use qiskit_transpiler::target::{TargetError, TargetJsonError};
 use thiserror::Error;
 
 /// Errors related to C input.
@@ -44,6 +46,10 @@ pub enum ExitCode {
     MismatchedQubits = 201,
     /// Matrix is not unitary.
     ExpectedUnitary = 202,
+    /// Too many qubits to represent the result.
+    TooManyQubits = 203,
+    /// An observable acts on a qubit in a different basis to the one it was measured in.
+    IncompatibleBasis = 204,
     /// Target related error
     TargetError = 300,
     /// Instruction already exists in the Target
@@ -54,6 +60,26 @@ pub enum ExitCode {
     TargetInvalidQargsKey = 303,
     /// Querying an operation that doesn't exist in the Target.
     TargetInvalidInstKey = 304,
+    /// The Target can't be represented in JSON, or the JSON document is not a valid Target.
+    TargetJson = 305,
+    /// The circuit contains an operation that the simulator does not support.
+    SimulatorUnsupportedOperation = 401,
+    /// The circuit contains unbound parameters.
+    SimulatorParameterized = 402,
+    /// Transpiler related error
+    TranspilerError = 500,
+    /// Serialization related error
+    SerializationError = 600,
+    /// The circuit contains an operation that can't be serialized.
+    SerializationUnsupported = 601,
+    /// The data is not a valid serialized circuit.
+    SerializationMalformed = 602,
+    /// The data was serialized by a newer version of Qiskit.
+    SerializationUnsupportedVersion = 603,
+    /// The circuit can't be represented in OpenQASM 2.
+    Qasm2ExportError = 700,
+    /// The OpenQASM 2 program is invalid or can't be represented as a circuit.
+    Qasm2LoadError = 701,
 }
 
 impl From<ArithmeticError> for ExitCode {
@@ -64,6 +90,39 @@ impl From<ArithmeticError> for ExitCode {
     }
 }
 
+impl From<MatrixError> for ExitCode {
+    fn from(value: MatrixError) -> Self {
+        match value {
+            MatrixError::TooManyQubits {
+                num_qubits: _,
+                max: _,
+            } => ExitCode::TooManyQubits,
+        }
+    }
+}
+
+impl From<ExpectationError> for ExitCode {
+    fn from(value: ExpectationError) -> Self {
+        match value {
+            ExpectationError::StateSize {
+                num_qubits: _,
+                len: _,
+            }
+            | ExpectationError::BasisSize {
+                num_qubits: _,
+                len: _,
+            } => ExitCode::MismatchedQubits,
+            ExpectationError::IncompatibleBasis { qubit: _ } => ExitCode::IncompatibleBasis,
+            ExpectationError::InvalidBasis(_)
+            | ExpectationError::InvalidBitstring {
+                bitstring: _,
+                num_qubits: _,
+            }
+            | ExpectationError::NoShots => ExitCode::CInputError,
+        }
+    }
+}
+
 impl From<CInputError> for ExitCode {
     fn from(value: CInputError) -> Self {
         match value {
@@ -91,3 +150,35 @@ impl From<TargetError> for ExitCode {
         }
     }
 }
+
+impl From<TargetJsonError> for ExitCode {
+    fn from(value: TargetJsonError) -> Self {
+        match value {
+            TargetJsonError::Target(err) => err.into(),
+            _ => ExitCode::TargetJson,
+        }
+    }
+}
+
+impl From<SimulatorError> for ExitCode {
+    fn from(value: SimulatorError) -> Self {
+        match value {
+            SimulatorError::UnsupportedOperation(_) => ExitCode::SimulatorUnsupportedOperation,
+            SimulatorError::ParameterizedCircuit => ExitCode::SimulatorParameterized,
+            SimulatorError::TooManyQubits(_) => ExitCode::TooManyQubits,
+        }
+    }
+}
+
+impl From<&SerializationError> for ExitCode {
+    fn from(value: &SerializationError) -> Self {
+        match value {
+            SerializationError::BadMagic
+            | SerializationError::UnexpectedEnd
+            | SerializationError::Malformed(_) => ExitCode::SerializationMalformed,
+            SerializationError::UnsupportedVersion(_) => ExitCode::SerializationUnsupportedVersion,
+            SerializationError::Unsupported(_) => ExitCode::SerializationUnsupported,
+            SerializationError::Python(_) => ExitCode::SerializationError,
+        }
+    }
+}
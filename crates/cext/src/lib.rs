@@ -14,5 +14,7 @@ mod pointers;
 
 pub mod circuit;
 pub mod exit_codes;
+pub mod qasm2;
+pub mod simulation;
 pub mod sparse_observable;
 pub mod transpiler;
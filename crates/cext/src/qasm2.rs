@@ -0,0 +1,214 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2025
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+use std::ffi::{c_char, CStr, CString};
+use std::path::PathBuf;
+
+use crate::exit_codes::ExitCode;
+use crate::pointers::{check_ptr, const_ptr_as_ref, mut_ptr_as_ref};
+
+use qiskit_circuit::circuit_data::CircuitData;
+
+/// @ingroup QkCircuit
+/// Export the circuit to an OpenQASM 2 program.
+///
+/// Standard gates defined in ``qelib1.inc`` are called by name, and other operations are
+/// emitted as ``gate`` definitions, or ``opaque`` declarations if they have no definition.
+///
+/// @param circuit A pointer to the circuit.
+/// @param qasm A pointer to a ``char *`` that is set to the nul-terminated program on success.
+///     The string must be freed with ``qk_str_free``.
+/// @param error A pointer to a ``char *`` that is set to a description of the failure if the
+///     circuit cannot be represented in OpenQASM 2, or a null pointer if the description is not
+///     needed. The string must be freed with ``qk_str_free``.
+///
+/// @return ``QkExitCode_Success`` on success, or ``QkExitCode_Qasm2ExportError`` if the circuit
+///     cannot be represented in OpenQASM 2.
+///
+/// # Example
+///
+///     QkCircuit *qc = qk_circuit_new(2, 2);
+///     uint32_t qubits[2] = {0, 1};
+///     qk_circuit_gate(qc, QkGate_H, qubits, NULL);
+///     qk_circuit_gate(qc, QkGate_CX, qubits, NULL);
+///     char *qasm;
+///     char *error;
+///     if (qk_circuit_to_qasm2(qc, &qasm, &error) == QkExitCode_Success) {
+///         printf("%s\n", qasm);
+///         qk_str_free(qasm);
+///     } else {
+///         printf("%s\n", error);
+///         qk_str_free(error);
+///     }
+///     qk_circuit_free(qc);
+///
+/// # Safety
+///
+/// Behavior is undefined if ``circuit`` is not a valid, non-null pointer to a ``QkCircuit``, if
+/// ``qasm`` is not a valid, non-null pointer to a ``char *``, or if ``error`` is neither null nor
+/// a valid pointer to a ``char *``.
+///
+/// The strings must not be freed with the normal C free, you must use ``qk_str_free`` to
+/// free the memory consumed by them.
+#[no_mangle]
+#[cfg(feature = "cbinding")]
+pub unsafe extern "C" fn qk_circuit_to_qasm2(
+    circuit: *const CircuitData,
+    qasm: *mut *mut c_char,
+    error: *mut *mut c_char,
+) -> ExitCode {
+    // SAFETY: Per documentation, the pointers are non-null and aligned.
+    let circuit = unsafe { const_ptr_as_ref(circuit) };
+    let qasm = unsafe { mut_ptr_as_ref(qasm) };
+    match qiskit_qasm2::dumps(circuit) {
+        Ok(out) => {
+            *qasm = CString::new(out).unwrap().into_raw();
+            ExitCode::Success
+        }
+        Err(err) => {
+            *qasm = ::std::ptr::null_mut();
+            if !error.is_null() {
+                // SAFETY: Per documentation, the pointer is non-null and aligned.
+                unsafe {
+                    *error = CString::new(err.to_string()).unwrap().into_raw();
+                }
+            }
+            ExitCode::Qasm2ExportError
+        }
+    }
+}
+
+/// @ingroup QkCircuit
+/// Parse an OpenQASM 2 program into a new circuit.
+///
+/// The gates of ``qelib1.inc`` and the built-in ``U`` and ``CX`` become standard gates.  Gates
+/// defined by ``gate`` statements in the program are expanded into their definitions wherever they
+/// are applied.  Programs that apply ``opaque`` gates or use ``if`` statements cannot be loaded,
+/// since circuits created through the C API cannot contain control flow.
+///
+/// Programs written by ``qk_circuit_to_qasm2`` call standard gates that are not in ``qelib1.inc``,
+/// such as ``sx`` and ``swap``, without defining them.  To load these, enable the legacy custom
+/// instructions, which are the equivalent of ``qiskit.qasm2.LEGACY_CUSTOM_INSTRUCTIONS`` in
+/// Python, except for ``c4x``.  These make ``sx``, ``sxdg``, ``swap``, ``p``, ``cp``, ``u``,
+/// ``u0``, ``cswap``, ``crx``, ``cry``, ``csx``, ``cu``, ``rxx``, ``rzz``, ``rccx``, ``rc3x``,
+/// ``c3x`` and ``c3sqrtx`` available without an ``include``, and map them to standard gates.  They
+/// also make ``id`` the identity gate rather than ``U(0, 0, 0)``, and an ``opaque delay(t) q;``
+/// declaration a delay in units of ``dt``.
+///
+/// @param program A pointer to a nul-terminated string containing the OpenQASM 2 program.
+/// @param strict Whether to parse the program in strict mode, which follows the letter of the
+///     OpenQASM 2 specification.
+/// @param legacy_instructions Whether to enable the legacy custom instructions.
+/// @param include_path A pointer to an array of ``num_include_path`` nul-terminated strings, which
+///     are the directories to search, in order, when resolving ``include`` statements other than
+///     ``qelib1.inc``.  This may be a null pointer if ``num_include_path`` is zero.
+/// @param num_include_path The number of directories in ``include_path``.
+/// @param circuit A pointer to a ``QkCircuit *`` that is set to the new circuit on success, or a
+///     null pointer on failure.
+/// @param error A pointer to a ``char *`` that is set to a description of the failure if the
+///     program cannot be loaded, or a null pointer if the description is not needed.  For parse
+///     errors, this includes the line and column of each problem.  The string must be freed with
+///     ``qk_str_free``.
+///
+/// @return ``QkExitCode_Success`` on success, ``QkExitCode_CInputError`` if the program or an
+///     include directory is not valid UTF-8, or ``QkExitCode_Qasm2LoadError`` if the program is
+///     invalid or cannot be represented.
+///
+/// # Example
+///
+///     const char *program = "OPENQASM 2.0;\n"
+///                           "include \"qelib1.inc\";\n"
+///                           "qreg q[2];\n"
+///                           "h q[0];\n"
+///                           "cx q[0], q[1];\n";
+///     QkCircuit *qc;
+///     char *error;
+///     if (qk_circuit_from_qasm2(program, false, false, NULL, 0, &qc, &error) == QkExitCode_Success) {
+///         qk_circuit_free(qc);
+///     } else {
+///         printf("%s\n", error);
+///         qk_str_free(error);
+///     }
+///
+/// # Safety
+///
+/// Behavior is undefined if ``program`` is not a valid, non-null pointer to a nul-terminated
+/// string, if ``include_path`` is not a valid pointer to ``num_include_path`` valid pointers to
+/// nul-terminated strings, if ``circuit`` is not a valid, non-null pointer to a ``QkCircuit *``,
+/// or if ``error`` is neither null nor a valid pointer to a ``char *``.
+///
+/// The error string must not be freed with the normal C free, you must use ``qk_str_free`` to
+/// free the memory consumed by it.
+#[no_mangle]
+#[cfg(feature = "cbinding")]
+pub unsafe extern "C" fn qk_circuit_from_qasm2(
+    program: *const c_char,
+    strict: bool,
+    legacy_instructions: bool,
+    include_path: *const *const c_char,
+    num_include_path: usize,
+    circuit: *mut *mut CircuitData,
+    error: *mut *mut c_char,
+) -> ExitCode {
+    // SAFETY: Per documentation, the pointer is non-null and aligned.
+    let circuit = unsafe { mut_ptr_as_ref(circuit) };
+    *circuit = ::std::ptr::null_mut();
+    let write_error = |message: String| {
+        if !error.is_null() {
+            // SAFETY: Per documentation, the pointer is non-null and aligned.
+            unsafe {
+                *error = CString::new(message).unwrap().into_raw();
+            }
+        }
+    };
+
+    if let Err(err) = check_ptr(program) {
+        return err.into();
+    }
+    // SAFETY: Per documentation, the pointer is a valid nul-terminated string.
+    let Ok(program) = unsafe { CStr::from_ptr(program) }.to_str() else {
+        write_error("the program is not valid UTF-8".to_string());
+        return ExitCode::CInputError;
+    };
+    let include_path = if num_include_path == 0 {
+        &[]
+    } else {
+        if let Err(err) = check_ptr(include_path) {
+            return err.into();
+        }
+        // SAFETY: Per documentation, the array has ``num_include_path`` elements.
+        unsafe { ::std::slice::from_raw_parts(include_path, num_include_path) }
+    };
+    let mut paths = Vec::with_capacity(include_path.len());
+    for &path in include_path {
+        if let Err(err) = check_ptr(path) {
+            return err.into();
+        }
+        // SAFETY: Per documentation, each path is a valid nul-terminated string.
+        let Ok(path) = unsafe { CStr::from_ptr(path) }.to_str() else {
+            write_error("an include directory is not valid UTF-8".to_string());
+            return ExitCode::CInputError;
+        };
+        paths.push(PathBuf::from(path));
+    }
+
+    match qiskit_qasm2::loads(program.to_owned(), paths, strict, legacy_instructions) {
+        Ok(out) => {
+            *circuit = Box::into_raw(Box::new(out));
+            ExitCode::Success
+        }
+        Err(err) => {
+            write_error(err.to_string());
+            ExitCode::Qasm2LoadError
+        }
+    }
+}
@@ -0,0 +1,158 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2025
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+use crate::exit_codes::{CInputError, ExitCode};
+use crate::pointers::{check_ptr, const_ptr_as_ref};
+
+use num_complex::Complex64;
+
+use qiskit_accelerate::statevector::{sample_memory, simulate_statevector};
+use qiskit_circuit::circuit_data::CircuitData;
+
+/// Read an optional seed from a pointer, where a null pointer means "no seed".
+///
+/// # Safety
+///
+/// ``seed`` must be either null or a valid pointer to a ``u64``.
+unsafe fn optional_seed(seed: *const u64) -> Option<u64> {
+    if seed.is_null() {
+        None
+    } else {
+        // SAFETY: per documentation, the pointer is valid if not null.
+        Some(unsafe { *const_ptr_as_ref(seed) })
+    }
+}
+
+/// @ingroup QkCircuit
+/// Simulate a circuit with the statevector simulator and write out the final statevector.
+///
+/// The circuit is run once starting from the all-zeros state. It may contain standard gates,
+/// unitary gates, measurements, resets, barriers and delays, and all its parameters must be
+/// bound. Measurements and resets collapse the state randomly.
+///
+/// @param circuit A pointer to the circuit to simulate.
+/// @param seed A pointer to the seed for the random number generator used by measurements and
+///     resets. If this is a null pointer, the generator is seeded from the operating system.
+/// @param out A pointer to an array of ``2 ^ num_qubits`` ``QkComplex64`` elements, to which
+///     the statevector is written. Qubit ``i`` corresponds to bit ``i`` of the array index.
+///
+/// @return An exit code. This is ``QkExitCode_SimulatorUnsupportedOperation`` if the circuit
+///     contains an operation the simulator does not support,
+///     ``QkExitCode_SimulatorParameterized`` if it contains unbound parameters, and
+///     ``QkExitCode_TooManyQubits`` if the statevector cannot be allocated.
+///
+/// # Example
+///
+///     QkCircuit *qc = qk_circuit_new(2, 0);
+///     uint32_t qubits[2] = {0, 1};
+///     qk_circuit_gate(qc, QkGate_H, qubits, NULL);
+///     qk_circuit_gate(qc, QkGate_CX, qubits, NULL);
+///     QkComplex64 statevector[4];
+///     qk_circuit_statevector(qc, NULL, statevector);
+///
+/// # Safety
+///
+/// Behavior is undefined if ``circuit`` is not a valid, non-null pointer to a ``QkCircuit``,
+/// ``seed`` is not either null or a valid pointer to a ``uint64_t``, or ``out`` is not a valid,
+/// non-null pointer to ``2 ^ num_qubits`` writable ``QkComplex64`` elements.
+#[no_mangle]
+#[cfg(feature = "cbinding")]
+pub unsafe extern "C" fn qk_circuit_statevector(
+    circuit: *const CircuitData,
+    seed: *const u64,
+    out: *mut Complex64,
+) -> ExitCode {
+    // SAFETY: Per documentation, the pointers are valid.
+    let circuit = unsafe { const_ptr_as_ref(circuit) };
+    let seed = unsafe { optional_seed(seed) };
+    if let Err(err) = check_ptr(out) {
+        return err.into();
+    }
+
+    let result = match simulate_statevector(circuit, seed) {
+        Ok(result) => result,
+        Err(err) => return err.into(),
+    };
+    let data = result.statevector.data();
+    // SAFETY: Per documentation, ``out`` has space for the full statevector.
+    unsafe { ::std::ptr::copy_nonoverlapping(data.as_ptr(), out, data.len()) };
+    ExitCode::Success
+}
+
+/// @ingroup QkCircuit
+/// Sample the classical outcomes of a circuit with the statevector simulator.
+///
+/// The circuit may contain standard gates, unitary gates, measurements, resets, barriers and
+/// delays, and all its parameters must be bound. If all measurements in the circuit are
+/// terminal, the circuit is simulated once and the shots are sampled from the final state,
+/// otherwise the circuit is simulated once per shot.
+///
+/// @param circuit A pointer to the circuit to sample.
+/// @param shots The number of shots to sample.
+/// @param seed A pointer to the seed for the random number generator. If this is a null
+///     pointer, the generator is seeded from the operating system.
+/// @param out A pointer to an array of ``shots`` ``uint64_t`` elements, to which the clbit
+///     values of each shot are written. Clbit ``i`` corresponds to bit ``i`` of each element.
+///
+/// @return An exit code. This is ``QkExitCode_IndexError`` if the circuit has more than 64
+///     clbits, ``QkExitCode_SimulatorUnsupportedOperation`` if the circuit contains an
+///     operation the simulator does not support, ``QkExitCode_SimulatorParameterized`` if it
+///     contains unbound parameters, and ``QkExitCode_TooManyQubits`` if the statevector cannot
+///     be allocated.
+///
+/// # Example
+///
+///     QkCircuit *qc = qk_circuit_new(1, 1);
+///     uint32_t qubit[1] = {0};
+///     qk_circuit_gate(qc, QkGate_H, qubit, NULL);
+///     qk_circuit_measure(qc, 0, 0);
+///     uint64_t seed = 42;
+///     uint64_t memory[100];
+///     qk_circuit_sample_memory(qc, 100, &seed, memory);
+///
+/// # Safety
+///
+/// Behavior is undefined if ``circuit`` is not a valid, non-null pointer to a ``QkCircuit``,
+/// ``seed`` is not either null or a valid pointer to a ``uint64_t``, or ``out`` is not a valid,
+/// non-null pointer to ``shots`` writable ``uint64_t`` elements.
+#[no_mangle]
+#[cfg(feature = "cbinding")]
+pub unsafe extern "C" fn qk_circuit_sample_memory(
+    circuit: *const CircuitData,
+    shots: usize,
+    seed: *const u64,
+    out: *mut u64,
+) -> ExitCode {
+    // SAFETY: Per documentation, the pointers are valid.
+    let circuit = unsafe { const_ptr_as_ref(circuit) };
+    let seed = unsafe { optional_seed(seed) };
+    if let Err(err) = check_ptr(out) {
+        return err.into();
+    }
+    if circuit.num_clbits() > u64::BITS as usize {
+        return CInputError::IndexError.into();
+    }
+
+    let memory = match sample_memory(circuit, shots, seed) {
+        Ok(memory) => memory,
+        Err(err) => return err.into(),
+    };
+    // SAFETY: Per documentation, ``out`` has space for ``shots`` elements.
+    let out = unsafe { ::std::slice::from_raw_parts_mut(out, shots) };
+    for (shot, clbits) in out.iter_mut().zip(memory) {
+        *shot = clbits
+            .iter()
+            .enumerate()
+            .fold(0, |acc, (index, bit)| acc | ((*bit as u64) << index));
+    }
+    ExitCode::Success
+}
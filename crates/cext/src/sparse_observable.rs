@@ -10,13 +10,15 @@
 // copyright notice, and modified files need to carry a notice indicating
 // that they have been altered from the originals.
 
-use std::ffi::{c_char, CString};
+use std::ffi::{c_char, CStr, CString};
 
 use crate::exit_codes::{CInputError, ExitCode};
 use crate::pointers::{check_ptr, const_ptr_as_ref, mut_ptr_as_ref};
 use num_complex::Complex64;
 
-use qiskit_quantum_info::sparse_observable::{BitTerm, SparseObservable, SparseTermView};
+use qiskit_quantum_info::sparse_observable::{
+    BitTerm, SparseObservable, SparseTermView, MAX_DENSE_QUBITS, MAX_SPARSE_QUBITS,
+};
 
 #[cfg(feature = "python_binding")]
 use pyo3::ffi::PyObject;
@@ -702,6 +704,55 @@ pub unsafe extern "C" fn qk_obs_compose_map(
     Box::into_raw(Box::new(result))
 }
 
+/// @ingroup QkObs
+/// Apply a transpiler layout to the observable.
+///
+/// This relabels qubit ``i`` of the observable as qubit ``layout[i]`` of an observable with
+/// ``num_qubits`` qubits.  Use the layout written by ``qk_transpile_layout_final_layout`` to map
+/// an observable defined on the input of the transpiler onto the transpiled circuit.
+///
+/// @param obs A pointer to the observable.
+/// @param layout A pointer to the array of new qubit indices, which has at least
+///     ``qk_obs_num_qubits(obs)`` elements, or a null pointer to only widen the observable to
+///     ``num_qubits`` qubits.
+/// @param num_qubits The number of qubits in the output observable.
+///
+/// @return A pointer to the relabeled observable, or a null pointer if the layout is not valid
+///     for the observable, for example if it contains duplicate indices or indices that are
+///     not smaller than ``num_qubits``.
+///
+/// # Example
+///
+///     QkObs *obs = qk_obs_identity(2);
+///     uint32_t layout[2] = {3, 0};
+///     QkObs *mapped = qk_obs_apply_layout(obs, layout, 5);
+///
+/// # Safety
+///
+/// Behavior is undefined if ``obs`` is not a valid, non-null pointer to a ``QkObs``, or if
+/// ``layout`` is not null and not readable for ``qk_obs_num_qubits(obs)`` elements.
+#[no_mangle]
+#[cfg(feature = "cbinding")]
+pub unsafe extern "C" fn qk_obs_apply_layout(
+    obs: *const SparseObservable,
+    layout: *const u32,
+    num_qubits: u32,
+) -> *mut SparseObservable {
+    // SAFETY: Per documentation, the pointer is non-null and aligned.
+    let obs = unsafe { const_ptr_as_ref(obs) };
+    let layout = if layout.is_null() {
+        None
+    } else {
+        check_ptr(layout).unwrap();
+        // SAFETY: Per documentation, the array is readable for the number of qubits in `obs`.
+        Some(unsafe { ::std::slice::from_raw_parts(layout, obs.num_qubits() as usize) })
+    };
+    match obs.apply_layout(layout, num_qubits) {
+        Ok(out) => Box::into_raw(Box::new(out)),
+        Err(_) => ::std::ptr::null_mut(),
+    }
+}
+
 /// @ingroup QkObs
 /// Calculate the canonical representation of the observable.
 ///
@@ -793,6 +844,363 @@ pub unsafe extern "C" fn qk_obs_equal(
     obs.eq(other)
 }
 
+/// @ingroup QkObs
+/// Write out the dense matrix of an observable.
+///
+/// The projectors are handled directly, without expanding them into Pauli terms.
+///
+/// @param obs A pointer to the observable.
+/// @param out A pointer to an array of ``4 ^ num_qubits`` ``QkComplex64`` elements, to which the
+///     matrix is written in row-major order. Qubit ``i`` corresponds to bit ``i`` of the row and
+///     column indices.
+///
+/// @return An exit code. This is ``QkExitCode_TooManyQubits`` if the observable is defined on too
+///     many qubits for its matrix to be allocated.
+///
+/// # Example
+///
+///     QkObs *obs = qk_obs_identity(2);
+///     QkComplex64 matrix[16];
+///     qk_obs_to_matrix_dense(obs, matrix);
+///
+/// # Safety
+///
+/// Behavior is undefined if ``obs`` is not a valid, non-null pointer to a ``QkObs``, or ``out``
+/// is not a valid, non-null pointer to ``4 ^ num_qubits`` writable ``QkComplex64`` elements.
+#[no_mangle]
+#[cfg(feature = "cbinding")]
+pub unsafe extern "C" fn qk_obs_to_matrix_dense(
+    obs: *const SparseObservable,
+    out: *mut Complex64,
+) -> ExitCode {
+    // SAFETY: Per documentation, the pointer is non-null and aligned.
+    let obs = unsafe { const_ptr_as_ref(obs) };
+    if let Err(err) = check_ptr(out) {
+        return err.into();
+    }
+
+    let matrix = match obs.to_matrix_dense(qiskit_circuit::getenv_use_multiple_threads()) {
+        Ok(matrix) => matrix,
+        Err(err) => return err.into(),
+    };
+    // SAFETY: Per documentation, ``out`` has space for the full matrix.
+    unsafe { ::std::ptr::copy_nonoverlapping(matrix.as_ptr(), out, matrix.len()) };
+    ExitCode::Success
+}
+
+/// A square matrix in compressed sparse row (CSR) format.
+///
+/// The column indices and values of the stored elements of row ``i`` are
+/// ``indices[indptr[i]:indptr[i + 1]]`` and ``data[indptr[i]:indptr[i + 1]]`` respectively.
+#[repr(C)]
+pub struct CsrMatrix {
+    /// The number of rows and columns of the matrix.
+    dim: usize,
+    /// The number of stored elements of the matrix.
+    nnz: usize,
+    /// An array of ``nnz`` values of the stored elements.
+    data: *mut Complex64,
+    /// An array of ``nnz`` column indices of the stored elements, sorted within each row.
+    indices: *mut usize,
+    /// An array of ``dim + 1`` offsets into ``data`` and ``indices`` of the start of each row.
+    indptr: *mut usize,
+}
+
+/// @ingroup QkObs
+/// Build the matrix of an observable in compressed sparse row (CSR) format.
+///
+/// The projectors are handled directly, without expanding them into Pauli terms. Qubit ``i``
+/// corresponds to bit ``i`` of the row and column indices. Elements that cancel to zero within a
+/// row are stored explicitly.
+///
+/// @param obs A pointer to the observable.
+/// @param out A pointer to a ``QkCsrMatrix`` to write the matrix to. On success, the arrays in it
+///     must be freed with ``qk_csrmatrix_free``.
+///
+/// @return An exit code. This is ``QkExitCode_TooManyQubits`` if the observable is defined on too
+///     many qubits for its matrix to be built, in which case nothing is written to ``out``.
+///
+/// # Example
+///
+///     QkObs *obs = qk_obs_identity(2);
+///     QkCsrMatrix matrix;
+///     qk_obs_to_matrix_sparse(obs, &matrix);
+///     qk_csrmatrix_free(matrix);
+///
+/// # Safety
+///
+/// Behavior is undefined if ``obs`` is not a valid, non-null pointer to a ``QkObs``, or ``out``
+/// is not a valid, non-null pointer to a ``QkCsrMatrix``.
+#[no_mangle]
+#[cfg(feature = "cbinding")]
+pub unsafe extern "C" fn qk_obs_to_matrix_sparse(
+    obs: *const SparseObservable,
+    out: *mut CsrMatrix,
+) -> ExitCode {
+    // SAFETY: Per documentation, the pointers are non-null and aligned.
+    let obs = unsafe { const_ptr_as_ref(obs) };
+    let out = unsafe { mut_ptr_as_ref(out) };
+
+    let (data, indices, indptr) =
+        match obs.to_matrix_sparse::<usize>(qiskit_circuit::getenv_use_multiple_threads()) {
+            Ok(csr) => csr,
+            Err(err) => return err.into(),
+        };
+    *out = CsrMatrix {
+        dim: indptr.len() - 1,
+        nnz: data.len(),
+        data: Box::into_raw(data.into_boxed_slice()) as *mut Complex64,
+        indices: Box::into_raw(indices.into_boxed_slice()) as *mut usize,
+        indptr: Box::into_raw(indptr.into_boxed_slice()) as *mut usize,
+    };
+    ExitCode::Success
+}
+
+/// @ingroup QkObs
+/// Free the arrays of a ``QkCsrMatrix``.
+///
+/// @param matrix The matrix to free, as written by ``qk_obs_to_matrix_sparse``.
+///
+/// # Example
+///
+///     QkObs *obs = qk_obs_identity(2);
+///     QkCsrMatrix matrix;
+///     qk_obs_to_matrix_sparse(obs, &matrix);
+///     qk_csrmatrix_free(matrix);
+///
+/// # Safety
+///
+/// Behavior is undefined if ``matrix`` was not written by ``qk_obs_to_matrix_sparse``, or if its
+/// fields have been modified.
+#[no_mangle]
+#[cfg(feature = "cbinding")]
+pub unsafe extern "C" fn qk_csrmatrix_free(matrix: CsrMatrix) {
+    // SAFETY: Per documentation, the arrays came from boxed slices of these lengths.
+    unsafe {
+        let _ = Box::from_raw(::std::ptr::slice_from_raw_parts_mut(
+            matrix.data,
+            matrix.nnz,
+        ));
+        let _ = Box::from_raw(::std::ptr::slice_from_raw_parts_mut(
+            matrix.indices,
+            matrix.nnz,
+        ));
+        let _ = Box::from_raw(::std::ptr::slice_from_raw_parts_mut(
+            matrix.indptr,
+            matrix.dim + 1,
+        ));
+    }
+}
+
+/// @ingroup QkObs
+/// Calculate the expectation value of an observable against a statevector.
+///
+/// The projectors are evaluated directly, without expanding them into Pauli terms.
+///
+/// @param obs A pointer to the observable.
+/// @param state A pointer to an array of ``2 ^ num_qubits`` ``QkComplex64`` elements, which is
+///     the statevector. Qubit ``i`` corresponds to bit ``i`` of the array index. The state does
+///     not need to be normalized.
+/// @param out A pointer to a ``QkComplex64`` to write the expectation value to.
+///
+/// @return An exit code. This is ``QkExitCode_TooManyQubits`` if the observable is defined on too
+///     many qubits for a statevector to be addressable.
+///
+/// # Example
+///
+///     QkObs *obs = qk_obs_identity(1);
+///     QkComplex64 state[2] = {{1.0, 0.0}, {0.0, 0.0}};
+///     QkComplex64 expval;
+///     qk_obs_expectation_value_statevector(obs, state, &expval);
+///
+/// # Safety
+///
+/// Behavior is undefined if ``obs`` is not a valid, non-null pointer to a ``QkObs``, ``state``
+/// is not a valid, non-null pointer to ``2 ^ num_qubits`` readable ``QkComplex64`` elements, or
+/// ``out`` is not a valid, non-null pointer to a ``QkComplex64``.
+#[no_mangle]
+#[cfg(feature = "cbinding")]
+pub unsafe extern "C" fn qk_obs_expectation_value_statevector(
+    obs: *const SparseObservable,
+    state: *const Complex64,
+    out: *mut Complex64,
+) -> ExitCode {
+    // SAFETY: Per documentation, the pointers are non-null and aligned.
+    let obs = unsafe { const_ptr_as_ref(obs) };
+    let out = unsafe { mut_ptr_as_ref(out) };
+    if let Err(err) = check_ptr(state) {
+        return err.into();
+    }
+    // A statevector has as many elements as the side of a matrix, so this limit keeps its size in
+    // bytes within an `isize`.
+    if obs.num_qubits() > MAX_SPARSE_QUBITS {
+        return ExitCode::TooManyQubits;
+    }
+    let len = 1usize << obs.num_qubits();
+    // SAFETY: Per documentation, ``state`` has ``2 ^ num_qubits`` elements.
+    let state = unsafe { ::std::slice::from_raw_parts(state, len) };
+
+    match obs.expectation_value_statevector(state, qiskit_circuit::getenv_use_multiple_threads()) {
+        Ok(value) => {
+            *out = value;
+            ExitCode::Success
+        }
+        Err(err) => err.into(),
+    }
+}
+
+/// @ingroup QkObs
+/// Calculate the expectation value of an observable against a density matrix.
+///
+/// The projectors are evaluated directly, without expanding them into Pauli terms.
+///
+/// @param obs A pointer to the observable.
+/// @param state A pointer to an array of ``4 ^ num_qubits`` ``QkComplex64`` elements, which is
+///     the density matrix in row-major order. Qubit ``i`` corresponds to bit ``i`` of the row and
+///     column indices.
+/// @param out A pointer to a ``QkComplex64`` to write the expectation value to.
+///
+/// @return An exit code. This is ``QkExitCode_TooManyQubits`` if the observable is defined on too
+///     many qubits for a density matrix to be addressable.
+///
+/// # Example
+///
+///     QkObs *obs = qk_obs_identity(1);
+///     QkComplex64 state[4] = {{0.5, 0.0}, {0.0, 0.0}, {0.0, 0.0}, {0.5, 0.0}};
+///     QkComplex64 expval;
+///     qk_obs_expectation_value_density_matrix(obs, state, &expval);
+///
+/// # Safety
+///
+/// Behavior is undefined if ``obs`` is not a valid, non-null pointer to a ``QkObs``, ``state``
+/// is not a valid, non-null pointer to ``4 ^ num_qubits`` readable ``QkComplex64`` elements, or
+/// ``out`` is not a valid, non-null pointer to a ``QkComplex64``.
+#[no_mangle]
+#[cfg(feature = "cbinding")]
+pub unsafe extern "C" fn qk_obs_expectation_value_density_matrix(
+    obs: *const SparseObservable,
+    state: *const Complex64,
+    out: *mut Complex64,
+) -> ExitCode {
+    // SAFETY: Per documentation, the pointers are non-null and aligned.
+    let obs = unsafe { const_ptr_as_ref(obs) };
+    let out = unsafe { mut_ptr_as_ref(out) };
+    if let Err(err) = check_ptr(state) {
+        return err.into();
+    }
+    if obs.num_qubits() > MAX_DENSE_QUBITS {
+        return ExitCode::TooManyQubits;
+    }
+    let len = 1usize << (2 * obs.num_qubits());
+    // SAFETY: Per documentation, ``state`` has ``4 ^ num_qubits`` elements.
+    let state = unsafe { ::std::slice::from_raw_parts(state, len) };
+
+    match obs.expectation_value_density_matrix(state, qiskit_circuit::getenv_use_multiple_threads())
+    {
+        Ok(value) => {
+            *out = value;
+            ExitCode::Success
+        }
+        Err(err) => err.into(),
+    }
+}
+
+/// @ingroup QkObs
+/// Estimate the expectation value of an observable from measurement counts.
+///
+/// Each qubit is taken to have been measured in one of the Pauli bases, given by ``basis``. Every
+/// single-qubit term of the observable must be diagonal in the basis its qubit was measured in,
+/// so, for example, qubits measured in the X basis can only have the terms ``QkBitTerm_X``,
+/// ``QkBitTerm_Plus`` and ``QkBitTerm_Minus`` acting on them. The projectors are evaluated
+/// directly as indicators of the corresponding measurement outcome.
+///
+/// @param obs A pointer to the observable.
+/// @param outcomes A pointer to an array of ``num_outcomes`` nul-terminated bitstrings, which are
+///     the measurement outcomes with qubit 0 as the rightmost character. Spaces are ignored, and
+///     hexadecimal strings prefixed with ``0x`` are also accepted.
+/// @param counts A pointer to an array of ``num_outcomes`` numbers of times each outcome was
+///     observed. These need not be integers.
+/// @param num_outcomes The number of outcomes.
+/// @param basis A pointer to an array of ``num_qubits`` ``QkBitTerm``\ s, each of which is
+///     ``QkBitTerm_X``, ``QkBitTerm_Y`` or ``QkBitTerm_Z``, giving the basis that qubit ``i``
+///     was measured in. If this is a null pointer, all qubits were measured in the Z basis.
+/// @param out A pointer to a ``QkComplex64`` to write the expectation value to.
+///
+/// @return An exit code. This is ``QkExitCode_IncompatibleBasis`` if a term of the observable
+///     acts on a qubit in a different basis to the one it was measured in, and
+///     ``QkExitCode_CInputError`` if an outcome or the basis is invalid or the counts contain no
+///     shots.
+///
+/// # Example
+///
+///     QkObs *obs = qk_obs_zero(2);
+///     QkBitTerm bit_terms[2] = {QkBitTerm_Zero, QkBitTerm_Plus};
+///     uint32_t indices[2] = {0, 1};
+///     QkObsTerm term = {(QkComplex64){1.0, 0.0}, 2, bit_terms, indices, 2};
+///     qk_obs_add_term(obs, &term);
+///
+///     const char *outcomes[2] = {"00", "10"};
+///     double counts[2] = {75.0, 25.0};
+///     QkBitTerm basis[2] = {QkBitTerm_Z, QkBitTerm_X};
+///     QkComplex64 expval;
+///     qk_obs_expectation_value_counts(obs, outcomes, counts, 2, basis, &expval);
+///
+/// # Safety
+///
+/// Behavior is undefined if ``obs`` is not a valid, non-null pointer to a ``QkObs``, ``outcomes``
+/// is not a valid pointer to ``num_outcomes`` valid pointers to nul-terminated strings,
+/// ``counts`` is not a valid pointer to ``num_outcomes`` readable ``double``\ s, ``basis`` is not
+/// either null or a valid pointer to ``num_qubits`` valid ``QkBitTerm``\ s, or ``out`` is not a
+/// valid, non-null pointer to a ``QkComplex64``.
+#[no_mangle]
+#[cfg(feature = "cbinding")]
+pub unsafe extern "C" fn qk_obs_expectation_value_counts(
+    obs: *const SparseObservable,
+    outcomes: *const *const c_char,
+    counts: *const f64,
+    num_outcomes: usize,
+    basis: *const BitTerm,
+    out: *mut Complex64,
+) -> ExitCode {
+    // SAFETY: Per documentation, the pointers are non-null and aligned.
+    let obs = unsafe { const_ptr_as_ref(obs) };
+    let out = unsafe { mut_ptr_as_ref(out) };
+    if let Err(err) = check_ptr(outcomes).and_then(|_| check_ptr(counts)) {
+        return err.into();
+    }
+    // SAFETY: Per documentation, the arrays have ``num_outcomes`` elements.
+    let outcomes = unsafe { ::std::slice::from_raw_parts(outcomes, num_outcomes) };
+    let counts = unsafe { ::std::slice::from_raw_parts(counts, num_outcomes) };
+    let basis = if basis.is_null() {
+        None
+    } else {
+        if let Err(err) = check_ptr(basis) {
+            return err.into();
+        }
+        // SAFETY: Per documentation, the basis has ``num_qubits`` valid elements.
+        Some(unsafe { ::std::slice::from_raw_parts(basis, obs.num_qubits() as usize) })
+    };
+
+    let mut pairs = Vec::with_capacity(num_outcomes);
+    for (&outcome, &count) in outcomes.iter().zip(counts) {
+        if let Err(err) = check_ptr(outcome) {
+            return err.into();
+        }
+        // SAFETY: Per documentation, each outcome is a valid nul-terminated string.
+        let Ok(outcome) = unsafe { CStr::from_ptr(outcome) }.to_str() else {
+            return ExitCode::CInputError;
+        };
+        pairs.push((outcome, count));
+    }
+    match obs.expectation_value_counts(pairs, basis) {
+        Ok(value) => {
+            *out = value;
+            ExitCode::Success
+        }
+        Err(err) => err.into(),
+    }
+}
+
 /// @ingroup QkObs
 /// Return a string representation of a ``QkObs``.
 ///
@@ -0,0 +1,222 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2025
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+use crate::pointers::const_ptr_as_ref;
+use qiskit_circuit::transpile_layout::TranspileLayout;
+use qiskit_circuit::PhysicalQubit;
+
+/// Write `qubits` to the caller-owned array `out`.
+///
+/// # Safety
+///
+/// `out` must be valid for writes of `qubits.len()` elements.
+unsafe fn write_qubits(qubits: &[PhysicalQubit], out: *mut u32) {
+    for (i, qubit) in qubits.iter().enumerate() {
+        // SAFETY: Per documentation, the array is writable for `qubits.len()` elements.
+        unsafe { out.add(i).write(qubit.0) };
+    }
+}
+
+/// @ingroup QkTranspileLayout
+/// Get the number of qubits in the circuit that was transpiled.
+///
+/// @param layout A pointer to the layout.
+///
+/// @return The number of input qubits.
+///
+/// # Example
+///
+///     uint32_t num_input = qk_transpile_layout_num_input_qubits(result.layout);
+///
+/// # Safety
+///
+/// Behavior is undefined if ``layout`` is not a valid, non-null pointer to a
+/// ``QkTranspileLayout``.
+#[no_mangle]
+#[cfg(feature = "cbinding")]
+pub unsafe extern "C" fn qk_transpile_layout_num_input_qubits(
+    layout: *const TranspileLayout,
+) -> u32 {
+    // SAFETY: Per documentation, the pointer is non-null and aligned.
+    let layout = unsafe { const_ptr_as_ref(layout) };
+    layout.num_input_qubits() as u32
+}
+
+/// @ingroup QkTranspileLayout
+/// Get the number of qubits in the transpiled circuit.
+///
+/// This is the number of input qubits plus the number of ancillas added to fill the target.
+///
+/// @param layout A pointer to the layout.
+///
+/// @return The number of output qubits.
+///
+/// # Example
+///
+///     uint32_t num_output = qk_transpile_layout_num_output_qubits(result.layout);
+///
+/// # Safety
+///
+/// Behavior is undefined if ``layout`` is not a valid, non-null pointer to a
+/// ``QkTranspileLayout``.
+#[no_mangle]
+#[cfg(feature = "cbinding")]
+pub unsafe extern "C" fn qk_transpile_layout_num_output_qubits(
+    layout: *const TranspileLayout,
+) -> u32 {
+    // SAFETY: Per documentation, the pointer is non-null and aligned.
+    let layout = unsafe { const_ptr_as_ref(layout) };
+    layout.num_output_qubits() as u32
+}
+
+/// @ingroup QkTranspileLayout
+/// Get the physical qubit that each qubit of the input circuit starts the transpiled circuit on.
+///
+/// @param layout A pointer to the layout.
+/// @param filter_ancillas If ``true``, only write the entries for the input qubits. Otherwise,
+///     the physical qubits of the ancillas follow those of the input qubits.
+/// @param initial_layout A pointer to the array to write to. It must have room for
+///     ``qk_transpile_layout_num_input_qubits`` elements if ``filter_ancillas`` is ``true``, and
+///     for ``qk_transpile_layout_num_output_qubits`` elements otherwise.
+///
+/// # Example
+///
+///     uint32_t num_input = qk_transpile_layout_num_input_qubits(result.layout);
+///     uint32_t *initial = malloc(sizeof(uint32_t) * num_input);
+///     qk_transpile_layout_initial_layout(result.layout, true, initial);
+///
+/// # Safety
+///
+/// Behavior is undefined if ``layout`` is not a valid, non-null pointer to a
+/// ``QkTranspileLayout``, or if ``initial_layout`` is not valid for writes of the number of
+/// elements given above.
+#[no_mangle]
+#[cfg(feature = "cbinding")]
+pub unsafe extern "C" fn qk_transpile_layout_initial_layout(
+    layout: *const TranspileLayout,
+    filter_ancillas: bool,
+    initial_layout: *mut u32,
+) {
+    // SAFETY: Per documentation, the pointer is non-null and aligned.
+    let layout = unsafe { const_ptr_as_ref(layout) };
+    // SAFETY: Per documentation, the output array is large enough.
+    unsafe {
+        write_qubits(
+            &layout.initial_physical_layout(filter_ancillas),
+            initial_layout,
+        )
+    };
+}
+
+/// @ingroup QkTranspileLayout
+/// Get the physical qubit that the state of each qubit of the input circuit ends the transpiled
+/// circuit on.
+///
+/// This is the layout to pass to ``qk_obs_apply_layout`` to map an observable defined on the
+/// input circuit onto the transpiled circuit.
+///
+/// @param layout A pointer to the layout.
+/// @param filter_ancillas If ``true``, only write the entries for the input qubits. Otherwise,
+///     the physical qubits of the ancillas follow those of the input qubits.
+/// @param final_layout A pointer to the array to write to. It must have room for
+///     ``qk_transpile_layout_num_input_qubits`` elements if ``filter_ancillas`` is ``true``, and
+///     for ``qk_transpile_layout_num_output_qubits`` elements otherwise.
+///
+/// # Example
+///
+///     uint32_t num_input = qk_transpile_layout_num_input_qubits(result.layout);
+///     uint32_t num_output = qk_transpile_layout_num_output_qubits(result.layout);
+///     uint32_t *final = malloc(sizeof(uint32_t) * num_input);
+///     qk_transpile_layout_final_layout(result.layout, true, final);
+///     QkObs *mapped = qk_obs_apply_layout(obs, final, num_output);
+///
+/// # Safety
+///
+/// Behavior is undefined if ``layout`` is not a valid, non-null pointer to a
+/// ``QkTranspileLayout``, or if ``final_layout`` is not valid for writes of the number of
+/// elements given above.
+#[no_mangle]
+#[cfg(feature = "cbinding")]
+pub unsafe extern "C" fn qk_transpile_layout_final_layout(
+    layout: *const TranspileLayout,
+    filter_ancillas: bool,
+    final_layout: *mut u32,
+) {
+    // SAFETY: Per documentation, the pointer is non-null and aligned.
+    let layout = unsafe { const_ptr_as_ref(layout) };
+    // SAFETY: Per documentation, the output array is large enough.
+    unsafe { write_qubits(&layout.final_physical_layout(filter_ancillas), final_layout) };
+}
+
+/// @ingroup QkTranspileLayout
+/// Get the permutation of the physical qubits introduced by routing.
+///
+/// Entry ``i`` is the physical qubit that the state that starts the transpiled circuit on
+/// physical qubit ``i`` ends on.
+///
+/// @param layout A pointer to the layout.
+/// @param permutation A pointer to the array to write to. It must have room for
+///     ``qk_transpile_layout_num_output_qubits`` elements.
+///
+/// # Example
+///
+///     uint32_t num_output = qk_transpile_layout_num_output_qubits(result.layout);
+///     uint32_t *permutation = malloc(sizeof(uint32_t) * num_output);
+///     qk_transpile_layout_routing_permutation(result.layout, permutation);
+///
+/// # Safety
+///
+/// Behavior is undefined if ``layout`` is not a valid, non-null pointer to a
+/// ``QkTranspileLayout``, or if ``permutation`` is not valid for writes of
+/// ``qk_transpile_layout_num_output_qubits`` elements.
+#[no_mangle]
+#[cfg(feature = "cbinding")]
+pub unsafe extern "C" fn qk_transpile_layout_routing_permutation(
+    layout: *const TranspileLayout,
+    permutation: *mut u32,
+) {
+    // SAFETY: Per documentation, the pointer is non-null and aligned.
+    let layout = unsafe { const_ptr_as_ref(layout) };
+    // SAFETY: Per documentation, the output array is large enough.
+    unsafe { write_qubits(layout.routing_permutation(), permutation) };
+}
+
+/// @ingroup QkTranspileLayout
+/// Free a layout.
+///
+/// @param layout A pointer to the layout to free.
+///
+/// # Example
+///
+///     QkTranspileLayout *layout = result.layout;
+///     result.layout = NULL;
+///     // ... use the layout ...
+///     qk_transpile_layout_free(layout);
+///
+/// # Safety
+///
+/// Behavior is undefined if ``layout`` is not either null or a valid pointer to a
+/// ``QkTranspileLayout``.
+#[no_mangle]
+#[cfg(feature = "cbinding")]
+pub unsafe extern "C" fn qk_transpile_layout_free(layout: *mut TranspileLayout) {
+    if !layout.is_null() {
+        if !layout.is_aligned() {
+            panic!("Attempted to free a non-aligned pointer.")
+        }
+        // SAFETY: We have verified the pointer is non-null and aligned, so it should be
+        // readable by Box.
+        unsafe {
+            let _ = Box::from_raw(layout);
+        }
+    }
+}
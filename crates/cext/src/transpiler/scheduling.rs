@@ -0,0 +1,230 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2025
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+use std::ffi::{c_char, CString};
+
+use rustworkx_core::petgraph::stable_graph::NodeIndex;
+
+use crate::exit_codes::ExitCode;
+use crate::pointers::const_ptr_as_ref;
+use qiskit_circuit::circuit_data::CircuitData;
+use qiskit_circuit::dag_circuit::DAGCircuit;
+use qiskit_transpiler::passes::{
+    run_alap_schedule_analysis, run_asap_schedule_analysis, run_constrained_reschedule,
+    NodeStartTimes, SchedulingError,
+};
+use qiskit_transpiler::target::Target;
+
+/// Convert a circuit to a DAG, returning the DAG node of each instruction in order.
+fn circuit_to_dag(circuit: &CircuitData) -> (DAGCircuit, Vec<NodeIndex>) {
+    let dag = DAGCircuit::from_circuit_data(circuit.clone(), false)
+        .expect("circuits built through the C API can always be converted to a DAG");
+    // The operation nodes are added in the order of the instructions of the circuit.
+    let nodes = dag.op_nodes(true).map(|(node, _)| node).collect();
+    (dag, nodes)
+}
+
+/// Write the result of a scheduling function to the C outputs.
+///
+/// # Safety
+///
+/// ``start_times`` must be a valid pointer to as many ``double`` elements as ``nodes``, and
+/// ``error`` must be null or a valid pointer.
+unsafe fn write_start_times(
+    result: Result<NodeStartTimes, SchedulingError>,
+    nodes: &[NodeIndex],
+    start_times: *mut f64,
+    error: *mut *mut c_char,
+) -> ExitCode {
+    match result {
+        Ok(node_start_time) => {
+            // SAFETY: Per documentation, ``start_times`` has space for one element per
+            // instruction.
+            let start_times = unsafe { ::std::slice::from_raw_parts_mut(start_times, nodes.len()) };
+            for (time, node) in start_times.iter_mut().zip(nodes) {
+                *time = node_start_time[node];
+            }
+            ExitCode::Success
+        }
+        Err(err) => {
+            if !error.is_null() {
+                // SAFETY: Per documentation, the pointer is non-null and aligned.
+                unsafe {
+                    *error = CString::new(err.to_string()).unwrap().into_raw();
+                }
+            }
+            ExitCode::TranspilerError
+        }
+    }
+}
+
+/// @ingroup QkTranspiler
+/// Schedule a physical circuit as soon as possible.
+///
+/// The instruction durations are read from the target. The start times are in units of ``dt``
+/// if the target has a ``dt``, and in seconds otherwise. If the target has a
+/// ``pulse_alignment`` or ``acquire_alignment`` other than 1, the start times are aligned as
+/// by ``qk_transpiler_constrained_reschedule``.
+///
+/// @param circuit A pointer to the physical circuit to schedule.
+/// @param target A pointer to the target to read the instruction durations from.
+/// @param clbit_write_latency The time after which a measurement starts writing to its
+///     clbits, in the same unit as the start times.
+/// @param start_times A pointer to an array of ``qk_circuit_num_instructions(circuit)``
+///     elements, which is filled with the start time of each instruction of the circuit.
+/// @param error A pointer to a ``char *`` that is set to a description of the failure if the
+///     circuit cannot be scheduled, or a null pointer if the description is not needed. The
+///     string must be freed with ``qk_str_free``.
+///
+/// @return ``QkExitCode_Success`` on success, or ``QkExitCode_TranspilerError`` if the
+///     circuit cannot be scheduled, for example because the target has no duration for one
+///     of its instructions.
+///
+/// # Example
+///
+///     QkCircuit *qc = qk_circuit_new(1, 1);
+///     qk_circuit_gate(qc, QkGate_X, (uint32_t[]){0}, NULL);
+///     qk_circuit_measure(qc, 0, 0);
+///
+///     QkTarget *target = qk_target_new(1);
+///     // ... set dt and add the instructions with their durations ...
+///
+///     double start_times[2];
+///     qk_transpiler_asap_schedule_analysis(qc, target, 0.0, start_times, NULL);
+///
+/// # Safety
+///
+/// Behavior is undefined if ``circuit`` or ``target`` are not valid, non-null pointers to a
+/// ``QkCircuit`` and a ``QkTarget`` respectively, if ``start_times`` is not a valid, non-null
+/// pointer to enough ``double`` elements, or if ``error`` is neither null nor a valid pointer.
+#[no_mangle]
+#[cfg(feature = "cbinding")]
+pub unsafe extern "C" fn qk_transpiler_asap_schedule_analysis(
+    circuit: *const CircuitData,
+    target: *const Target,
+    clbit_write_latency: f64,
+    start_times: *mut f64,
+    error: *mut *mut c_char,
+) -> ExitCode {
+    // SAFETY: Per documentation, the pointers are non-null and aligned.
+    let circuit = unsafe { const_ptr_as_ref(circuit) };
+    let target = unsafe { const_ptr_as_ref(target) };
+    let (dag, nodes) = circuit_to_dag(circuit);
+    let result = run_asap_schedule_analysis(&dag, target, clbit_write_latency);
+    // SAFETY: Per documentation, the output pointers are valid.
+    unsafe { write_start_times(result, &nodes, start_times, error) }
+}
+
+/// @ingroup QkTranspiler
+/// Schedule a physical circuit as late as possible.
+///
+/// The arguments and units are the same as for ``qk_transpiler_asap_schedule_analysis``.
+///
+/// @param circuit A pointer to the physical circuit to schedule.
+/// @param target A pointer to the target to read the instruction durations from.
+/// @param clbit_write_latency The time after which a measurement starts writing to its
+///     clbits, in the same unit as the start times.
+/// @param start_times A pointer to an array of ``qk_circuit_num_instructions(circuit)``
+///     elements, which is filled with the start time of each instruction of the circuit.
+/// @param error A pointer to a ``char *`` that is set to a description of the failure if the
+///     circuit cannot be scheduled, or a null pointer if the description is not needed. The
+///     string must be freed with ``qk_str_free``.
+///
+/// @return ``QkExitCode_Success`` on success, or ``QkExitCode_TranspilerError`` if the
+///     circuit cannot be scheduled.
+///
+/// # Example
+///
+///     double *start_times = malloc(qk_circuit_num_instructions(qc) * sizeof(double));
+///     qk_transpiler_alap_schedule_analysis(qc, target, 0.0, start_times, NULL);
+///
+/// # Safety
+///
+/// Behavior is undefined if ``circuit`` or ``target`` are not valid, non-null pointers to a
+/// ``QkCircuit`` and a ``QkTarget`` respectively, if ``start_times`` is not a valid, non-null
+/// pointer to enough ``double`` elements, or if ``error`` is neither null nor a valid pointer.
+#[no_mangle]
+#[cfg(feature = "cbinding")]
+pub unsafe extern "C" fn qk_transpiler_alap_schedule_analysis(
+    circuit: *const CircuitData,
+    target: *const Target,
+    clbit_write_latency: f64,
+    start_times: *mut f64,
+    error: *mut *mut c_char,
+) -> ExitCode {
+    // SAFETY: Per documentation, the pointers are non-null and aligned.
+    let circuit = unsafe { const_ptr_as_ref(circuit) };
+    let target = unsafe { const_ptr_as_ref(target) };
+    let (dag, nodes) = circuit_to_dag(circuit);
+    let result = run_alap_schedule_analysis(&dag, target, clbit_write_latency);
+    // SAFETY: Per documentation, the output pointers are valid.
+    unsafe { write_start_times(result, &nodes, start_times, error) }
+}
+
+/// @ingroup QkTranspiler
+/// Shift the start times of a scheduled circuit to satisfy the alignment constraints of a
+/// target.
+///
+/// Gates are moved to start at a multiple of the ``pulse_alignment`` of the target, and
+/// measurements and resets at a multiple of its ``acquire_alignment``, pushing back the
+/// instructions that follow them. The target must have a ``dt``.
+///
+/// @param circuit A pointer to the scheduled circuit.
+/// @param target A pointer to the target to read the alignment constraints and instruction
+///     durations from.
+/// @param clbit_write_latency The time after which a measurement starts writing to its
+///     clbits, in ``dt``.
+/// @param start_times A pointer to an array of ``qk_circuit_num_instructions(circuit)``
+///     elements, holding the start time of each instruction of the circuit in ``dt``. It is
+///     updated with the aligned start times on success, and left unchanged on failure.
+/// @param error A pointer to a ``char *`` that is set to a description of the failure if the
+///     start times cannot be aligned, or a null pointer if the description is not needed. The
+///     string must be freed with ``qk_str_free``.
+///
+/// @return ``QkExitCode_Success`` on success, or ``QkExitCode_TranspilerError`` if the
+///     target has no ``dt`` or the circuit contains an instruction that cannot be aligned.
+///
+/// # Example
+///
+///     double start_times[2] = {0.0, 100.0};
+///     qk_target_set_acquire_alignment(target, 16);
+///     qk_transpiler_constrained_reschedule(qc, target, 0.0, start_times, NULL);
+///     // start_times[1] is now 112.0
+///
+/// # Safety
+///
+/// Behavior is undefined if ``circuit`` or ``target`` are not valid, non-null pointers to a
+/// ``QkCircuit`` and a ``QkTarget`` respectively, if ``start_times`` is not a valid, non-null
+/// pointer to enough ``double`` elements, or if ``error`` is neither null nor a valid pointer.
+#[no_mangle]
+#[cfg(feature = "cbinding")]
+pub unsafe extern "C" fn qk_transpiler_constrained_reschedule(
+    circuit: *const CircuitData,
+    target: *const Target,
+    clbit_write_latency: f64,
+    start_times: *mut f64,
+    error: *mut *mut c_char,
+) -> ExitCode {
+    // SAFETY: Per documentation, the pointers are non-null and aligned.
+    let circuit = unsafe { const_ptr_as_ref(circuit) };
+    let target = unsafe { const_ptr_as_ref(target) };
+    let (dag, nodes) = circuit_to_dag(circuit);
+    // SAFETY: Per documentation, ``start_times`` holds one element per instruction.
+    let input = unsafe { ::std::slice::from_raw_parts(start_times, nodes.len()) };
+    let mut node_start_time: NodeStartTimes =
+        nodes.iter().copied().zip(input.iter().copied()).collect();
+    let result =
+        run_constrained_reschedule(&dag, target, &mut node_start_time, clbit_write_latency)
+            .map(|_| node_start_time);
+    // SAFETY: Per documentation, the output pointers are valid.
+    unsafe { write_start_times(result, &nodes, start_times, error) }
+}
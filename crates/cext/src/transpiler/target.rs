@@ -19,6 +19,7 @@ use qiskit_circuit::packed_instruction::PackedOperation;
 use qiskit_circuit::PhysicalQubit;
 use qiskit_transpiler::target::{InstructionProperties, Qargs, Target};
 use smallvec::{smallvec, SmallVec};
+use std::ffi::{c_char, CStr, CString};
 
 /// @ingroup QkTarget
 /// Construct a new ``QkTarget`` with the given number of qubits.
@@ -366,6 +367,149 @@ pub unsafe extern "C" fn qk_target_copy(target: *mut Target) -> *mut Target {
     Box::into_raw(target.clone().into())
 }
 
+/// @ingroup QkTarget
+/// Construct a new ``QkTarget`` from its JSON representation.
+///
+/// The JSON format is the one produced by ``qk_target_to_json``. It covers standard gates
+/// and standard instructions, their per-qargs properties, the qubit properties and the
+/// timing constraints of the ``QkTarget``. Parameters are either fixed numbers or the names
+/// of free parameters, such as the ``"theta"`` of an ``rz`` gate that accepts any angle.
+///
+/// @param json A pointer to a nul-terminated string containing the JSON document.
+/// @param target A pointer to a ``QkTarget *`` that is set to the new ``QkTarget`` on success,
+///     or a null pointer on failure.
+/// @param error A pointer to a ``char *`` that is set to a description of the failure if the
+///     document cannot be loaded, or a null pointer if the description is not needed. The
+///     string must be freed with ``qk_str_free``.
+///
+/// @return ``QkExitCode_Success`` on success, ``QkExitCode_CInputError`` if the document is not
+///     valid UTF-8, or ``QkExitCode_TargetJson`` if the document is malformed or does not
+///     describe a valid ``QkTarget``.
+///
+/// # Example
+///
+///     const char *json = "{\"version\": 1, \"num_qubits\": 2, \"instructions\": []}";
+///     QkTarget *target;
+///     char *error;
+///     if (qk_target_from_json(json, &target, &error) == QkExitCode_Success) {
+///         qk_target_free(target);
+///     } else {
+///         printf("%s\n", error);
+///         qk_str_free(error);
+///     }
+///
+/// # Safety
+///
+/// Behavior is undefined if ``json`` is not a valid, non-null pointer to a nul-terminated
+/// string, if ``target`` is not a valid, non-null pointer to a ``QkTarget *``, or if ``error``
+/// is neither null nor a valid pointer to a ``char *``.
+///
+/// The error string must not be freed with the normal C free, you must use ``qk_str_free`` to
+/// free the memory consumed by it.
+#[no_mangle]
+#[cfg(feature = "cbinding")]
+pub unsafe extern "C" fn qk_target_from_json(
+    json: *const c_char,
+    target: *mut *mut Target,
+    error: *mut *mut c_char,
+) -> ExitCode {
+    // SAFETY: Per documentation, the pointer is non-null and aligned.
+    let target = unsafe { mut_ptr_as_ref(target) };
+    *target = ::std::ptr::null_mut();
+    let write_error = |message: String| {
+        if !error.is_null() {
+            // SAFETY: Per documentation, the pointer is non-null and aligned.
+            unsafe {
+                *error = CString::new(message).unwrap().into_raw();
+            }
+        }
+    };
+
+    if let Err(err) = check_ptr(json) {
+        return err.into();
+    }
+    // SAFETY: Per documentation, the pointer is a valid nul-terminated string.
+    let Ok(json) = unsafe { CStr::from_ptr(json) }.to_str() else {
+        write_error("the document is not valid UTF-8".to_string());
+        return ExitCode::CInputError;
+    };
+    match Target::from_json(json) {
+        Ok(out) => {
+            *target = Box::into_raw(Box::new(out));
+            ExitCode::Success
+        }
+        Err(err) => {
+            write_error(err.to_string());
+            err.into()
+        }
+    }
+}
+
+/// @ingroup QkTarget
+/// Serialize the ``QkTarget`` into JSON.
+///
+/// The document can be loaded back with ``qk_target_from_json``.
+///
+/// @param target A pointer to the ``QkTarget``.
+/// @param json A pointer to a ``char *`` that is set to the nul-terminated JSON document on
+///     success. The string must be freed with ``qk_str_free``.
+/// @param error A pointer to a ``char *`` that is set to a description of the failure if the
+///     ``QkTarget`` cannot be represented in JSON, or a null pointer if the description is not
+///     needed. The string must be freed with ``qk_str_free``.
+///
+/// @return ``QkExitCode_Success`` on success, or ``QkExitCode_TargetJson`` if the ``QkTarget``
+///     contains instructions that can't be represented in JSON.
+///
+/// # Example
+///
+///     QkTarget *target = qk_target_new(5);
+///     char *json;
+///     char *error;
+///     if (qk_target_to_json(target, &json, &error) == QkExitCode_Success) {
+///         printf("%s\n", json);
+///         qk_str_free(json);
+///     } else {
+///         printf("%s\n", error);
+///         qk_str_free(error);
+///     }
+///     qk_target_free(target);
+///
+/// # Safety
+///
+/// Behavior is undefined if ``target`` is not a valid, non-null pointer to a ``QkTarget``, if
+/// ``json`` is not a valid, non-null pointer to a ``char *``, or if ``error`` is neither null
+/// nor a valid pointer to a ``char *``.
+///
+/// The strings must not be freed with the normal C free, you must use ``qk_str_free`` to
+/// free the memory consumed by them.
+#[no_mangle]
+#[cfg(feature = "cbinding")]
+pub unsafe extern "C" fn qk_target_to_json(
+    target: *const Target,
+    json: *mut *mut c_char,
+    error: *mut *mut c_char,
+) -> ExitCode {
+    // SAFETY: Per documentation, the pointers are non-null and aligned.
+    let target = unsafe { const_ptr_as_ref(target) };
+    let json = unsafe { mut_ptr_as_ref(json) };
+    match target.to_json() {
+        Ok(out) => {
+            *json = CString::new(out).unwrap().into_raw();
+            ExitCode::Success
+        }
+        Err(err) => {
+            *json = ::std::ptr::null_mut();
+            if !error.is_null() {
+                // SAFETY: Per documentation, the pointer is non-null and aligned.
+                unsafe {
+                    *error = CString::new(err.to_string()).unwrap().into_raw();
+                }
+            }
+            err.into()
+        }
+    }
+}
+
 /// @ingroup QkTarget
 /// Free the ``QkTarget``.
 ///
@@ -0,0 +1,217 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2025
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+use std::ffi::{c_char, CString};
+
+use crate::exit_codes::ExitCode;
+use crate::pointers::{const_ptr_as_ref, mut_ptr_as_ref};
+use qiskit_circuit::circuit_data::CircuitData;
+use qiskit_circuit::transpile_layout::TranspileLayout;
+use qiskit_transpiler::target::Target;
+use qiskit_transpiler::transpile::{transpile, TranspileOptions};
+
+/// @ingroup QkTranspiler
+/// Options for ``qk_transpile``.
+///
+/// Get the default options with ``qk_transpiler_default_options`` and change the fields you
+/// need.
+#[repr(C)]
+pub struct CTranspileOptions {
+    /// The optimization level, from 0 (no optimization) to 3 (most optimization).
+    pub optimization_level: u8,
+    /// The seed for the stochastic parts of the transpiler, or a negative number to seed
+    /// from system entropy.
+    pub seed: i64,
+    /// The degree of approximation allowed in synthesis, between 0 and 1.  Set to ``NaN`` to
+    /// derive the approximation from the error rates in the target.
+    pub approximation_degree: f64,
+}
+
+/// @ingroup QkTranspiler
+/// The output of ``qk_transpile``.
+#[repr(C)]
+pub struct CTranspileResult {
+    /// The transpiled circuit.
+    pub circuit: *mut CircuitData,
+    /// The layout of the transpiled circuit, which describes where each qubit of the input
+    /// circuit starts and ends the transpiled circuit.
+    pub layout: *mut TranspileLayout,
+}
+
+/// @ingroup QkTranspiler
+/// Get the default transpiler options.
+///
+/// The defaults are optimization level 2, a seed from system entropy and an approximation
+/// degree of 1.0.
+///
+/// @return The default ``QkTranspileOptions``.
+///
+/// # Example
+///
+///     QkTranspileOptions options = qk_transpiler_default_options();
+///     options.optimization_level = 3;
+///     options.seed = 42;
+#[no_mangle]
+#[cfg(feature = "cbinding")]
+pub extern "C" fn qk_transpiler_default_options() -> CTranspileOptions {
+    let defaults = TranspileOptions::default();
+    CTranspileOptions {
+        optimization_level: defaults.optimization_level,
+        seed: -1,
+        approximation_degree: defaults.approximation_degree.unwrap_or(f64::NAN),
+    }
+}
+
+/// @ingroup QkTranspiler
+/// Transpile a circuit for a target.
+///
+/// This runs the preset transpilation pipeline: standard gates on three or more qubits are
+/// unrolled, a layout is chosen and the circuit is routed with Sabre, and the circuit is
+/// translated to the instructions in the target by collecting the single- and two-qubit runs
+/// that the target does not support, or that can be synthesized with fewer two-qubit gates,
+/// into unitaries and resynthesizing them. At optimization level 1 and above, single-qubit
+/// runs are then optimized. Python is not needed.
+///
+/// The circuit can contain standard gates with bound parameters, unitaries on up to two
+/// qubits and standard instructions such as measurements. Any other operation makes the
+/// transpilation fail with a description of the problem. The translation to the target is done
+/// by synthesis only, since the equivalence library of the basis translator is only available
+/// from Python, so the transpilation also fails if the target has no single-qubit Euler basis,
+/// or no two-qubit gate that synthesis can use, on the qubits it needs to translate.
+///
+/// @param circuit A pointer to the circuit to transpile.
+/// @param target A pointer to the target to compile for. It must have a qubit count.
+/// @param options A pointer to the options, or a null pointer to use the defaults.
+/// @param result A pointer to the ``QkTranspileResult`` to write the output to. On success,
+///     the memory owned by it must be freed with ``qk_transpile_result_clear``.
+/// @param error A pointer to a ``char *`` that is set to a description of the failure if the
+///     transpilation fails, or a null pointer if the description is not needed. The string
+///     must be freed with ``qk_str_free``.
+///
+/// @return ``QkExitCode_Success`` on success, or ``QkExitCode_TranspilerError`` if the
+///     transpilation failed.
+///
+/// # Example
+///
+///     QkCircuit *qc = qk_circuit_new(2, 0);
+///     qk_circuit_gate(qc, QkGate_H, (uint32_t[]){0}, NULL);
+///     qk_circuit_gate(qc, QkGate_CX, (uint32_t[]){0, 1}, NULL);
+///
+///     QkTarget *target = qk_target_new(3);
+///     // ... add instructions to the target ...
+///
+///     QkTranspileOptions options = qk_transpiler_default_options();
+///     options.seed = 1234;
+///     QkTranspileResult result;
+///     char *error = NULL;
+///     if (qk_transpile(qc, target, &options, &result, &error) != QkExitCode_Success) {
+///         printf("%s\n", error);
+///         qk_str_free(error);
+///     } else {
+///         // ... use result.circuit and result.layout ...
+///         qk_transpile_result_clear(&result);
+///     }
+///
+/// # Safety
+///
+/// Behavior is undefined if ``circuit``, ``target`` or ``result`` are not valid, non-null
+/// pointers to a ``QkCircuit``, a ``QkTarget`` and a ``QkTranspileResult`` respectively, or if
+/// ``options`` or ``error`` are neither null nor valid pointers.
+#[no_mangle]
+#[cfg(feature = "cbinding")]
+pub unsafe extern "C" fn qk_transpile(
+    circuit: *const CircuitData,
+    target: *const Target,
+    options: *const CTranspileOptions,
+    result: *mut CTranspileResult,
+    error: *mut *mut c_char,
+) -> ExitCode {
+    // SAFETY: Per documentation, the pointers are non-null and aligned.
+    let circuit = unsafe { const_ptr_as_ref(circuit) };
+    let target = unsafe { const_ptr_as_ref(target) };
+    let result = unsafe { mut_ptr_as_ref(result) };
+    let options = if options.is_null() {
+        TranspileOptions::default()
+    } else {
+        // SAFETY: Per documentation, the pointer is non-null and aligned.
+        let options = unsafe { const_ptr_as_ref(options) };
+        TranspileOptions {
+            optimization_level: options.optimization_level,
+            seed: (options.seed >= 0).then_some(options.seed as u64),
+            approximation_degree: (!options.approximation_degree.is_nan())
+                .then_some(options.approximation_degree),
+        }
+    };
+    match transpile(circuit, target, &options) {
+        Ok(mut out) => {
+            let layout = out
+                .set_layout(None)
+                .expect("transpiled circuits always have a layout");
+            *result = CTranspileResult {
+                circuit: Box::into_raw(Box::new(out)),
+                layout: Box::into_raw(Box::new(layout)),
+            };
+            ExitCode::Success
+        }
+        Err(err) => {
+            if !error.is_null() {
+                // SAFETY: Per documentation, the pointer is non-null and aligned.
+                unsafe {
+                    *error = CString::new(err.to_string()).unwrap().into_raw();
+                }
+            }
+            ExitCode::TranspilerError
+        }
+    }
+}
+
+/// @ingroup QkTranspiler
+/// Free the memory owned by a ``QkTranspileResult``.
+///
+/// This frees the circuit and the layout, and sets the pointers to null.  To keep either of
+/// them, move it out of the result and set the corresponding field to a null pointer before
+/// calling this function.
+///
+/// @param result A pointer to the result to clear.
+///
+/// # Example
+///
+///     QkTranspileResult result;
+///     if (qk_transpile(qc, target, NULL, &result, NULL) == QkExitCode_Success) {
+///         QkCircuit *out = result.circuit;
+///         result.circuit = NULL;
+///         qk_transpile_result_clear(&result);
+///         // ... use out, then free it with qk_circuit_free ...
+///     }
+///
+/// # Safety
+///
+/// Behavior is undefined if ``result`` is not a valid, non-null pointer to a
+/// ``QkTranspileResult`` that was filled by ``qk_transpile``.
+#[no_mangle]
+#[cfg(feature = "cbinding")]
+pub unsafe extern "C" fn qk_transpile_result_clear(result: *mut CTranspileResult) {
+    // SAFETY: Per documentation, the pointer is non-null and aligned.
+    let result = unsafe { mut_ptr_as_ref(result) };
+    // SAFETY: The pointers were created by `qk_transpile` from boxes, or were nulled by the
+    // caller.
+    unsafe {
+        if !result.circuit.is_null() {
+            let _ = Box::from_raw(result.circuit);
+        }
+        if !result.layout.is_null() {
+            let _ = Box::from_raw(result.layout);
+        }
+    }
+    result.circuit = std::ptr::null_mut();
+    result.layout = std::ptr::null_mut();
+}
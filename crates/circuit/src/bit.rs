@@ -366,6 +366,18 @@ macro_rules! create_bit_object {
                     subclass: Default::default(),
                 })
             }
+
+            /// The register that owns this bit and the index of the bit within it, or `None` if
+            /// the bit is anonymous.
+            pub fn owning_register(&self) -> Option<($reg_struct, u32)> {
+                match &self.0 {
+                    BitInfo::Owned { register, index } => Some((
+                        $reg_struct(Arc::new(RegisterInfo::Owning(register.clone()))),
+                        *index,
+                    )),
+                    BitInfo::Anonymous { .. } => None,
+                }
+            }
         }
 
         impl ShareableBit for $bit_struct {
@@ -569,6 +581,12 @@ macro_rules! create_bit_object {
                 }))
             }
 
+            /// Does this register own its bits, as opposed to aliasing existing ones?
+            #[inline]
+            pub fn is_owning(&self) -> bool {
+                matches!(*self.0, RegisterInfo::Owning(_))
+            }
+
             /// Get the name of the register.
             #[inline]
             pub fn name(&self) -> &str {
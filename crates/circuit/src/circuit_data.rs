@@ -31,6 +31,7 @@ use crate::packed_instruction::{PackedInstruction, PackedOperation};
 use crate::parameter_table::{ParameterTable, ParameterTableError, ParameterUse, ParameterUuid};
 use crate::register_data::RegisterData;
 use crate::slice::{PySequenceIndex, SequenceIndex};
+use crate::transpile_layout::TranspileLayout;
 use crate::{Clbit, Qubit, Stretch, Var, VarsMode};
 
 use numpy::PyReadonlyArray1;
@@ -151,6 +152,8 @@ pub struct CircuitData {
     param_table: ParameterTable,
     #[pyo3(get)]
     global_phase: Param,
+    /// The layout of the circuit, if it is the output of the transpiler.
+    layout: Option<TranspileLayout>,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -310,6 +313,7 @@ impl CircuitData {
             clbits: clbits_registry,
             param_table: ParameterTable::new(),
             global_phase: Param::Float(0.),
+            layout: None,
             qregs: RegisterData::new(),
             cregs: RegisterData::new(),
             qubit_indices,
@@ -559,7 +563,7 @@ impl CircuitData {
     /// Get a (cached) sorted list of the Python-space `Parameter` instances tracked by this circuit
     /// data's parameter table.
     #[getter]
-    pub fn get_parameters<'py>(&self, py: Python<'py>) -> Bound<'py, PyList> {
+    pub fn get_parameters<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyList>> {
         self.param_table.py_parameters(py)
     }
 
@@ -571,10 +575,15 @@ impl CircuitData {
         self.param_table._py_raw_entry(param)
     }
 
-    pub fn get_parameter_by_name(&self, py: Python, name: PyBackedStr) -> Option<Py<PyAny>> {
-        self.param_table
-            .py_parameter_by_name(&name)
-            .map(|ob| ob.clone_ref(py))
+    pub fn get_parameter_by_name(
+        &self,
+        py: Python,
+        name: PyBackedStr,
+    ) -> PyResult<Option<Py<PyAny>>> {
+        Ok(self
+            .param_table
+            .py_parameter_by_name(py, &name)?
+            .map(Bound::unbind))
     }
 
     /// Return the width of the circuit. This is the number of qubits plus the
@@ -784,6 +793,7 @@ impl CircuitData {
         res.cregs = self.cregs.clone();
         res.qubit_indices = self.qubit_indices.clone();
         res.clbit_indices = self.clbit_indices.clone();
+        res.layout.clone_from(&self.layout);
 
         if let VarsMode::Drop = vars_mode {
             return Ok(res);
@@ -1244,7 +1254,7 @@ impl CircuitData {
                 array
                     .iter()
                     .map(|value| Param::Float(*value))
-                    .zip(old_table.drain_ordered())
+                    .zip(old_table.drain_ordered(sequence.py())?)
                     .map(|(value, (obj, uses))| (obj, value, uses)),
             )
         } else {
@@ -1449,39 +1459,44 @@ impl CircuitData {
     /// by copies or other means, before making the parameter table consistent.
     #[setter]
     pub fn set_global_phase(&mut self, angle: Param) -> PyResult<()> {
-        if let Param::ParameterExpression(expr) = &self.global_phase {
-            Python::with_gil(|py| -> PyResult<()> {
-                for param_ob in expr
-                    .bind(py)
-                    .getattr(intern!(py, "parameters"))?
-                    .try_iter()?
-                {
-                    match self.param_table.remove_use(
-                        ParameterUuid::from_parameter(&param_ob?)?,
-                        ParameterUse::GlobalPhase,
-                    ) {
-                        Ok(_)
-                        | Err(ParameterTableError::ParameterNotTracked(_))
-                        | Err(ParameterTableError::UsageNotTracked(_)) => (),
-                        // Any errors added later might want propagating.
-                    }
+        let untrack = |table: &mut ParameterTable, uuid| -> PyResult<()> {
+            match table.remove_use(uuid, ParameterUse::GlobalPhase) {
+                Ok(_)
+                | Err(ParameterTableError::ParameterNotTracked(_))
+                | Err(ParameterTableError::UsageNotTracked(_)) => Ok(()),
+                Err(err) => Err(err.into()),
+            }
+        };
+        match &self.global_phase {
+            Param::Symbolic(expr) => {
+                for symbol in expr.symbols() {
+                    untrack(&mut self.param_table, ParameterUuid::from_symbol(symbol))?;
                 }
-                Ok(())
-            })?;
+            }
+            Param::ParameterExpression(_) => {
+                Python::with_gil(|py| -> PyResult<()> {
+                    for param_ob in self.global_phase.iter_parameters(py)? {
+                        untrack(
+                            &mut self.param_table,
+                            ParameterUuid::from_parameter(&param_ob?)?,
+                        )?;
+                    }
+                    Ok(())
+                })?;
+            }
+            Param::Float(_) | Param::Obj(_) => (),
         }
         match angle {
             Param::Float(angle) => {
                 self.global_phase = Param::Float(angle.rem_euclid(2. * std::f64::consts::PI));
                 Ok(())
             }
-            Param::ParameterExpression(_) => Python::with_gil(|py| -> PyResult<()> {
-                for param_ob in angle.iter_parameters(py)? {
-                    self.param_table
-                        .track(&param_ob?, Some(ParameterUse::GlobalPhase))?;
-                }
+            Param::ParameterExpression(_) | Param::Symbolic(_) => {
+                self.param_table
+                    .track_param(&angle, ParameterUse::GlobalPhase)?;
                 self.global_phase = angle;
                 Ok(())
-            }),
+            }
             Param::Obj(_) => Err(PyTypeError::new_err("invalid type for global phase")),
         }
     }
@@ -1914,6 +1929,7 @@ impl CircuitData {
             clbits,
             param_table: ParameterTable::new(),
             global_phase: Param::Float(0.0),
+            layout: None,
             qregs,
             cregs,
             qubit_indices,
@@ -2010,6 +2026,7 @@ impl CircuitData {
             clbits: ObjectRegistry::with_capacity(num_clbits as usize),
             param_table: ParameterTable::new(),
             global_phase: Param::Float(0.0),
+            layout: None,
             qregs: RegisterData::new(),
             cregs: RegisterData::new(),
             qubit_indices: BitLocator::with_capacity(num_qubits as usize),
@@ -2087,19 +2104,11 @@ impl CircuitData {
             .iter()
             .enumerate()
         {
-            if matches!(param, Param::Float(_)) {
-                continue;
-            }
             let usage = ParameterUse::Index {
                 instruction: instruction_index,
                 parameter: index as u32,
             };
-            Python::with_gil(|py| -> PyResult<()> {
-                for param_ob in param.iter_parameters(py)? {
-                    self.param_table.track(&param_ob?, Some(usage))?;
-                }
-                Ok(())
-            })?;
+            self.param_table.track_param(param, usage)?;
         }
         Ok(())
     }
@@ -2112,19 +2121,11 @@ impl CircuitData {
             .iter()
             .enumerate()
         {
-            if matches!(param, Param::Float(_)) {
-                continue;
-            }
             let usage = ParameterUse::Index {
                 instruction: instruction_index,
                 parameter: index as u32,
             };
-            Python::with_gil(|py| -> PyResult<()> {
-                for param_ob in param.iter_parameters(py)? {
-                    self.param_table.untrack(&param_ob?, usage)?;
-                }
-                Ok(())
-            })?;
+            self.param_table.untrack_param(param, usage)?;
         }
         Ok(())
     }
@@ -2139,16 +2140,8 @@ impl CircuitData {
         for inst_index in 0..self.data.len() {
             self.track_instruction_parameters(inst_index)?;
         }
-        if matches!(self.global_phase, Param::Float(_)) {
-            return Ok(());
-        }
-        Python::with_gil(|py| -> PyResult<()> {
-            for param_ob in self.global_phase.iter_parameters(py)? {
-                self.param_table
-                    .track(&param_ob?, Some(ParameterUse::GlobalPhase))?;
-            }
-            Ok(())
-        })
+        self.param_table
+            .track_param(&self.global_phase, ParameterUse::GlobalPhase)
     }
 
     /// Native internal driver of `__delitem__` that uses a Rust-space version of the
@@ -2205,7 +2198,7 @@ impl CircuitData {
             py,
             slice
                 .iter()
-                .zip(old_table.drain_ordered())
+                .zip(old_table.drain_ordered(py)?)
                 .map(|(value, (param_ob, uses))| (param_ob, value.clone_ref(py), uses)),
         )
     }
@@ -2221,11 +2214,10 @@ impl CircuitData {
         let mut items = Vec::new();
         for (param_uuid, value) in iter {
             // Assume all the Parameters are already in the circuit
-            let param_obj = self.get_parameter_by_uuid(param_uuid);
+            let param_obj = self.get_parameter_by_uuid(py, param_uuid)?;
             if let Some(param_obj) = param_obj {
-                // Copy or increase ref_count for Parameter, avoid acquiring the GIL.
                 items.push((
-                    param_obj.clone_ref(py),
+                    param_obj.unbind(),
                     value.as_ref().clone_ref(py),
                     self.param_table.pop(param_uuid)?,
                 ));
@@ -2251,6 +2243,16 @@ impl CircuitData {
         &self.global_phase
     }
 
+    /// Returns the [TranspileLayout] of the circuit, if it is the output of the transpiler.
+    pub fn layout(&self) -> Option<&TranspileLayout> {
+        self.layout.as_ref()
+    }
+
+    /// Set the [TranspileLayout] of the circuit, returning the previous one.
+    pub fn set_layout(&mut self, layout: Option<TranspileLayout>) -> Option<TranspileLayout> {
+        ::std::mem::replace(&mut self.layout, layout)
+    }
+
     /// Returns an immutable view of the Qubits registered in the circuit
     pub fn qubits(&self) -> &ObjectRegistry<Qubit, ShareableQubit> {
         &self.qubits
@@ -2310,6 +2312,11 @@ impl CircuitData {
         self.cargs_interner().get(index)
     }
 
+    /// Insert cargs into the interner and return the interned value
+    pub fn add_cargs(&mut self, clbits: &[Clbit]) -> Interned<[Clbit]> {
+        self.cargs_interner.insert(clbits)
+    }
+
     fn assign_parameters_inner<I, T>(&mut self, py: Python, iter: I) -> PyResult<()>
     where
         I: IntoIterator<Item = (Py<PyAny>, T, HashSet<ParameterUse>)>,
@@ -2327,23 +2334,22 @@ impl CircuitData {
         let validate_parameter_attr = intern!(py, "validate_parameter");
 
         // Bind a single `Parameter` into a Python-space `ParameterExpression`.
-        let bind_expr = |expr: Borrowed<PyAny>,
-                         param_ob: &Py<PyAny>,
-                         value: &Param,
-                         coerce: bool|
-         -> PyResult<Param> {
-            let new_expr = expr.call_method1(assign_attr, (param_ob, value.into_py_any(py)?))?;
-            if new_expr.getattr(parameters_attr)?.len()? == 0 {
-                let out = new_expr.call_method0(numeric_attr)?;
-                if coerce {
-                    out.extract()
+        let bind_expr =
+            |expr: &Param, param_ob: &Py<PyAny>, value: &Param, coerce: bool| -> PyResult<Param> {
+                let new_expr = expr
+                    .into_pyobject(py)?
+                    .call_method1(assign_attr, (param_ob, value.into_py_any(py)?))?;
+                if new_expr.getattr(parameters_attr)?.len()? == 0 {
+                    let out = new_expr.call_method0(numeric_attr)?;
+                    if coerce {
+                        out.extract()
+                    } else {
+                        Param::extract_no_coerce(&out)
+                    }
                 } else {
-                    Param::extract_no_coerce(&out)
+                    Ok(Param::ParameterExpression(new_expr.unbind()))
                 }
-            } else {
-                Ok(Param::ParameterExpression(new_expr.unbind()))
-            }
-        };
+            };
 
         let mut user_operations = HashMap::new();
         let mut uuids = Vec::new();
@@ -2356,15 +2362,12 @@ impl CircuitData {
             for usage in uses {
                 match usage {
                     ParameterUse::GlobalPhase => {
-                        let Param::ParameterExpression(expr) = &self.global_phase else {
+                        let expr @ (Param::ParameterExpression(_) | Param::Symbolic(_)) =
+                            &self.global_phase
+                        else {
                             return Err(inconsistent());
                         };
-                        self.set_global_phase(bind_expr(
-                            expr.bind_borrowed(py),
-                            &param_ob,
-                            value.as_ref(),
-                            true,
-                        )?)?;
+                        self.set_global_phase(bind_expr(expr, &param_ob, value.as_ref(), true)?)?;
                     }
                     ParameterUse::Index {
                         instruction,
@@ -2374,11 +2377,12 @@ impl CircuitData {
                         let previous = &mut self.data[instruction];
                         if let Some(standard) = previous.standard_gate() {
                             let params = previous.params_mut();
-                            let Param::ParameterExpression(expr) = &params[parameter] else {
+                            let expr @ (Param::ParameterExpression(_) | Param::Symbolic(_)) =
+                                &params[parameter]
+                            else {
                                 return Err(inconsistent());
                             };
-                            let new_param =
-                                bind_expr(expr.bind_borrowed(py), &param_ob, value.as_ref(), true)?;
+                            let new_param = bind_expr(expr, &param_ob, value.as_ref(), true)?;
                             params[parameter] = match new_param.clone_ref(py) {
                                 Param::Obj(obj) => {
                                     return Err(CircuitError::new_err(format!(
@@ -2416,15 +2420,11 @@ impl CircuitData {
                             let previous_param = &previous.params_view()[parameter];
                             let new_param = match previous_param {
                                 Param::Float(_) => return Err(inconsistent()),
-                                Param::ParameterExpression(expr) => {
+                                expr @ (Param::ParameterExpression(_) | Param::Symbolic(_)) => {
                                     // For user gates, we don't coerce floats to integers in `Param`
                                     // so that users can use them if they choose.
-                                    let new_param = bind_expr(
-                                        expr.bind_borrowed(py),
-                                        &param_ob,
-                                        value.as_ref(),
-                                        false,
-                                    )?;
+                                    let new_param =
+                                        bind_expr(expr, &param_ob, value.as_ref(), false)?;
                                     // Historically, `assign_parameters` called `validate_parameter`
                                     // only when a `ParameterExpression` became fully bound.  Some
                                     // "generalised" (or user) gates fail without this, though
@@ -2518,8 +2518,12 @@ impl CircuitData {
     }
 
     /// Retrieves the python `Param` object based on its `ParameterUuid`.
-    pub fn get_parameter_by_uuid(&self, uuid: ParameterUuid) -> Option<&Py<PyAny>> {
-        self.param_table.py_parameter_by_uuid(uuid)
+    pub fn get_parameter_by_uuid<'py>(
+        &self,
+        py: Python<'py>,
+        uuid: ParameterUuid,
+    ) -> PyResult<Option<Bound<'py, PyAny>>> {
+        self.param_table.py_parameter_by_uuid(py, uuid)
     }
 
     /// Get an immutable view of the instructions in the circuit data
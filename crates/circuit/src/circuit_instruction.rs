@@ -254,7 +254,7 @@ impl CircuitInstruction {
     pub fn is_parameterized(&self) -> bool {
         self.params
             .iter()
-            .any(|x| matches!(x, Param::ParameterExpression(_)))
+            .any(|x| matches!(x, Param::ParameterExpression(_) | Param::Symbolic(_)))
     }
 
     /// Creates a shallow copy with the given fields replaced.
@@ -382,13 +382,16 @@ impl CircuitInstruction {
                         Param::ParameterExpression(right) | Param::Obj(right) => {
                             right.bind(py).eq(left)?
                         }
+                        Param::Symbolic(right) => right.to_py(py)?.eq(left)?,
                     },
                     Param::ParameterExpression(left) | Param::Obj(left) => match right {
                         Param::Float(right) => left.bind(py).eq(right)?,
                         Param::ParameterExpression(right) | Param::Obj(right) => {
                             left.bind(py).eq(right)?
                         }
+                        Param::Symbolic(right) => left.bind(py).eq(right.to_py(py)?)?,
                     },
+                    Param::Symbolic(_) => left.eq(py, right)?,
                 };
                 if !eq {
                     return Ok(false);
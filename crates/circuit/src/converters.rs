@@ -56,7 +56,7 @@ pub fn circuit_to_dag(
 
 #[pyfunction(signature = (dag, copy_operations = true))]
 pub fn dag_to_circuit(dag: &DAGCircuit, copy_operations: bool) -> PyResult<CircuitData> {
-    CircuitData::from_packed_instructions(
+    let mut circuit = CircuitData::from_packed_instructions(
         dag.qubits().clone(),
         dag.clbits().clone(),
         dag.qargs_interner().clone(),
@@ -116,7 +116,9 @@ pub fn dag_to_circuit(dag: &DAGCircuit, copy_operations: bool) -> PyResult<Circu
                 ),
             })
             .collect::<Vec<CircuitVar>>(),
-    )
+    )?;
+    circuit.set_layout(dag.layout().cloned());
+    Ok(circuit)
 }
 
 pub fn converters(m: &Bound<PyModule>) -> PyResult<()> {
@@ -37,6 +37,8 @@ use crate::packed_instruction::{PackedInstruction, PackedOperation};
 use crate::register_data::RegisterData;
 use crate::rustworkx_core_vnext::isomorphism;
 use crate::slice::PySequenceIndex;
+use crate::symbol_expr::{SymbolExpr, Value};
+use crate::transpile_layout::TranspileLayout;
 use crate::variable_mapper::VariableMapper;
 use crate::{imports, Clbit, Qubit, Stretch, TupleLikeArg, Var, VarsMode};
 
@@ -76,6 +78,7 @@ use std::cmp::Ordering;
 use std::collections::{BTreeMap, VecDeque};
 use std::convert::Infallible;
 use std::f64::consts::PI;
+use std::sync::Arc;
 #[cfg(feature = "cache_pygates")]
 use std::sync::OnceLock;
 
@@ -223,6 +226,9 @@ pub struct DAGCircuit {
 
     stretches_capture: HashSet<Stretch>,
     stretches_declare: Vec<Stretch>,
+
+    /// The layout of the circuit, if it has been through layout and routing.
+    layout: Option<TranspileLayout>,
 }
 
 #[derive(Clone, Debug)]
@@ -1032,8 +1038,8 @@ impl DAGCircuit {
             Param::Float(angle) => {
                 self.global_phase = Param::Float(angle.rem_euclid(2. * PI));
             }
-            Param::ParameterExpression(angle) => {
-                self.global_phase = Param::ParameterExpression(angle);
+            angle @ (Param::ParameterExpression(_) | Param::Symbolic(_)) => {
+                self.global_phase = angle;
             }
             Param::Obj(_) => return Err(PyTypeError::new_err("Invalid type for global phase")),
         }
@@ -1884,6 +1890,7 @@ impl DAGCircuit {
             ((self_phase - other_phase + PI).rem_euclid(2. * PI) - PI).abs() <= 1.0e-10
         };
         let normalize_param = |param: &Param| {
+            let param = &param.to_python_expression(py)?;
             if let Param::ParameterExpression(ob) = param {
                 ob.bind(py)
                     .call_method0(intern!(py, "numeric"))
@@ -4397,9 +4404,20 @@ impl DAGCircuit {
             vars_declare: HashSet::new(),
             stretches_capture: HashSet::new(),
             stretches_declare: Vec::new(),
+            layout: None,
         })
     }
 
+    /// Returns the [TranspileLayout] of the DAG, if it has been laid out.
+    pub fn layout(&self) -> Option<&TranspileLayout> {
+        self.layout.as_ref()
+    }
+
+    /// Set the [TranspileLayout] of the DAG, returning the previous one.
+    pub fn set_layout(&mut self, layout: Option<TranspileLayout>) -> Option<TranspileLayout> {
+        ::std::mem::replace(&mut self.layout, layout)
+    }
+
     /// Create an empty DAG, but with all the same qubit data, classical data and metadata
     /// (including global phase).
     ///
@@ -4439,6 +4457,7 @@ impl DAGCircuit {
         // `copy_empty_like` has historically made a strong assumption that the exact same qargs
         // will be used in the output.  Some Qiskit functions rely on this undocumented behaviour.
         out.qargs_interner.clone_from(&self.qargs_interner);
+        out.layout.clone_from(&self.layout);
         Ok(out)
     }
 
@@ -5474,7 +5493,7 @@ impl DAGCircuit {
     }
 
     #[inline]
-    fn has_control_flow(&self) -> bool {
+    pub fn has_control_flow(&self) -> bool {
         CONTROL_FLOW_OP_NAMES
             .iter()
             .any(|x| self.op_names.contains_key(&x.to_string()))
@@ -6415,6 +6434,7 @@ impl DAGCircuit {
             vars_declare: HashSet::new(),
             stretches_capture: HashSet::new(),
             stretches_declare: Vec::new(),
+            layout: None,
         })
     }
 
@@ -6690,12 +6710,14 @@ impl DAGCircuit {
 
         // Assign other necessary data
         new_dag.name = qc.name;
+        new_dag.layout = qc_data.layout().cloned();
 
         // Avoid manually acquiring the GIL.
         new_dag.global_phase = match qc_data.global_phase() {
             // The clone here implicitly requires the gil while ParameterExpression is defined in
             // Python.
             Param::ParameterExpression(exp) => Param::ParameterExpression(exp.clone()),
+            Param::Symbolic(exp) => Param::Symbolic(exp.clone()),
             Param::Float(float) => Param::Float(*float),
             _ => unreachable!("Incorrect parameter assigned for global phase"),
         };
@@ -7618,8 +7640,8 @@ impl ::std::ops::Index<NodeIndex> for DAGCircuit {
     }
 }
 
-/// Add to global phase. Global phase can only be Float or ParameterExpression so this
-/// does not handle the full possibility of parameter values.
+/// Add to global phase. Global phase can only be Float, ParameterExpression or Symbolic, so
+/// this returns an error for [Param::Obj].
 pub(crate) fn add_global_phase(phase: &Param, other: &Param) -> PyResult<Param> {
     Ok(match [phase, other] {
         [Param::Float(a), Param::Float(b)] => Param::Float(a + b),
@@ -7641,7 +7663,27 @@ pub(crate) fn add_global_phase(phase: &Param, other: &Param) -> PyResult<Param>
                     .call_method1(py, intern!(py, "__add__"), (b,))
             })?)
         }
-        _ => panic!("Invalid global phase"),
+        [Param::Float(a), Param::Symbolic(b)] | [Param::Symbolic(b), Param::Float(a)] => {
+            Param::Symbolic(Arc::new(
+                b.unary_op(|b| b + &SymbolExpr::Value(Value::Real(*a))),
+            ))
+        }
+        [Param::Symbolic(a), Param::Symbolic(b)] => Param::Symbolic(Arc::new(
+            a.binary_op(b, |a, b| a + b)
+                .map_err(|err| PyValueError::new_err(err.to_string()))?,
+        )),
+        [Param::Symbolic(_), Param::ParameterExpression(_)]
+        | [Param::ParameterExpression(_), Param::Symbolic(_)] => {
+            Python::with_gil(|py| -> PyResult<Param> {
+                add_global_phase(
+                    &phase.to_python_expression(py)?,
+                    &other.to_python_expression(py)?,
+                )
+            })?
+        }
+        [Param::Obj(_), _] | [_, Param::Obj(_)] => {
+            return Err(PyTypeError::new_err("Invalid type for global phase"))
+        }
     })
 }
 
@@ -191,6 +191,9 @@ impl DAGOpNode {
                         param_a.bind(py).eq(param_b)?
                     }
                     [Param::Obj(param_a), Param::Obj(param_b)] => param_a.bind(py).eq(param_b)?,
+                    [Param::Symbolic(param_a), Param::Symbolic(param_b)] => param_a == param_b,
+                    [Param::Symbolic(_), Param::ParameterExpression(_)]
+                    | [Param::ParameterExpression(_), Param::Symbolic(_)] => a.eq(py, b)?,
                     _ => false,
                 };
                 if !res {
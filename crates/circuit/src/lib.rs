@@ -32,11 +32,14 @@ pub mod object_registry;
 pub mod operations;
 pub mod packed_instruction;
 pub mod parameter_expression;
+pub mod parameter_symbol;
 pub mod parameter_table;
 pub mod register_data;
+pub mod serialization;
 pub mod slice;
 pub mod symbol_expr;
 pub mod symbol_parser;
+pub mod transpile_layout;
 pub mod util;
 
 pub mod rustworkx_core_vnext;
@@ -196,6 +199,17 @@ pub fn getenv_use_multiple_threads() -> bool {
     !parallel_context || force_threads
 }
 
+/// Whether a Python interpreter is running in this process.
+///
+/// Rust-space code that can also be driven without Python, such as through the C API, uses this
+/// to skip the parts of its work that are only implemented in Python.
+#[inline]
+pub fn python_is_initialized() -> bool {
+    // SAFETY: `Py_IsInitialized` can be called at any time, including before the interpreter is
+    // initialized and after it is finalized.
+    unsafe { pyo3::ffi::Py_IsInitialized() != 0 }
+}
+
 pub fn circuit(m: &Bound<PyModule>) -> PyResult<()> {
     m.add_class::<annotation::PyAnnotation>()?;
     m.add_class::<bit::PyBit>()?;
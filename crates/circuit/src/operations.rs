@@ -12,11 +12,14 @@
 
 use approx::relative_eq;
 use std::f64::consts::PI;
+use std::sync::Arc;
 use std::{fmt, vec};
 
 use crate::circuit_data::CircuitData;
 use crate::imports::{get_std_gate_class, BARRIER, DELAY, MEASURE, RESET};
 use crate::imports::{DEEPCOPY, PARAMETER_EXPRESSION, QUANTUM_CIRCUIT, UNITARY_GATE};
+use crate::parameter_symbol::SymbolicExpression;
+use crate::symbol_expr::{SymbolExpr, Value};
 use crate::{gate_matrix, impl_intopyobject_for_copy_pyclass, Qubit};
 
 use nalgebra::{Matrix2, Matrix4};
@@ -33,16 +36,25 @@ use pyo3::prelude::*;
 use pyo3::types::{IntoPyDict, PyDict, PyFloat, PyIterator, PyList, PyTuple};
 use pyo3::{intern, IntoPyObjectExt, Python};
 
-#[derive(Clone, Debug, IntoPyObject, IntoPyObjectRef)]
+#[derive(Clone, Debug)]
 pub enum Param {
     ParameterExpression(PyObject),
     Float(f64),
     Obj(PyObject),
+    /// A symbolic expression that lives in Rust space, and is only converted to a Python-space
+    /// `ParameterExpression` when it is passed to Python.
+    Symbolic(Arc<SymbolicExpression>),
 }
 
 impl Param {
     pub fn eq(&self, py: Python, other: &Param) -> PyResult<bool> {
         match [self, other] {
+            [Self::Symbolic(a), Self::Symbolic(b)] => Ok(a == b),
+            [Self::Symbolic(a), Self::Float(b)] | [Self::Float(b), Self::Symbolic(a)] => {
+                Ok(a.expr().real() == Some(*b) && a.expr().imag() == Some(0.))
+            }
+            [Self::Symbolic(a), b] => a.to_py(py)?.eq(b),
+            [a, Self::Symbolic(b)] => b.to_py(py)?.eq(a),
             [Self::Float(a), Self::Float(b)] => Ok(a == b),
             [Self::Float(a), Self::ParameterExpression(b)] => b.bind(py).eq(a),
             [Self::ParameterExpression(a), Self::Float(b)] => a.bind(py).eq(b),
@@ -63,6 +75,34 @@ impl Param {
     }
 }
 
+impl<'py> IntoPyObject<'py> for &Param {
+    type Target = PyAny;
+    type Output = Bound<'py, PyAny>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Self::Output> {
+        match self {
+            Param::ParameterExpression(ob) | Param::Obj(ob) => Ok(ob.bind(py).clone()),
+            Param::Float(val) => val.into_bound_py_any(py),
+            Param::Symbolic(expr) => expr.to_py(py),
+        }
+    }
+}
+
+impl<'py> IntoPyObject<'py> for Param {
+    type Target = PyAny;
+    type Output = Bound<'py, PyAny>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Self::Output> {
+        match self {
+            Param::ParameterExpression(ob) | Param::Obj(ob) => Ok(ob.into_bound(py)),
+            Param::Float(val) => val.into_bound_py_any(py),
+            Param::Symbolic(expr) => expr.to_py(py),
+        }
+    }
+}
+
 impl<'py> FromPyObject<'py> for Param {
     fn extract_bound(b: &Bound<'py, PyAny>) -> Result<Self, PyErr> {
         Ok(if b.is_instance(PARAMETER_EXPRESSION.get_bound(b.py()))? {
@@ -84,6 +124,15 @@ impl Param {
             Param::ParameterExpression(expr) => Ok(ParamParameterIter(Some(
                 expr.bind(py).getattr(parameters_attr)?.try_iter()?,
             ))),
+            Param::Symbolic(expr) => {
+                let parameters = expr
+                    .symbols()
+                    .map(|symbol| symbol.to_py(py))
+                    .collect::<PyResult<Vec<_>>>()?;
+                Ok(ParamParameterIter(Some(
+                    PyList::new(py, parameters)?.into_any().try_iter()?,
+                )))
+            }
             Param::Obj(obj) => {
                 let obj = obj.bind(py);
                 if obj.is_instance(QUANTUM_CIRCUIT.get_bound(py))? {
@@ -110,12 +159,22 @@ impl Param {
         })
     }
 
+    /// Convert a [Param::Symbolic] into the equivalent Python-space [Param::ParameterExpression].
+    /// Other variants are returned unchanged.
+    pub fn to_python_expression(&self, py: Python) -> PyResult<Self> {
+        match self {
+            Param::Symbolic(expr) => Ok(Param::ParameterExpression(expr.to_py(py)?.unbind())),
+            param => Ok(param.clone_ref(py)),
+        }
+    }
+
     /// Clones the [Param] object safely by reference count or copying.
     pub fn clone_ref(&self, py: Python) -> Self {
         match self {
             Param::ParameterExpression(exp) => Param::ParameterExpression(exp.clone_ref(py)),
             Param::Float(float) => Param::Float(*float),
             Param::Obj(obj) => Param::Obj(obj.clone_ref(py)),
+            Param::Symbolic(expr) => Param::Symbolic(expr.clone()),
         }
     }
 }
@@ -1244,11 +1303,23 @@ impl Operation for StandardGate {
                 )
                 .expect("Unexpected Qiskit python bug"),
             ),
-            Self::R => Python::with_gil(|py| -> Option<CircuitData> {
-                let theta_expr = clone_param(&params[0], py);
-                let phi_expr1 = add_param(&params[1], -PI / 2., py);
-                let phi_expr2 = multiply_param(&phi_expr1, -1.0, py);
-                let defparams = smallvec![theta_expr, phi_expr1, phi_expr2];
+            Self::R => {
+                // Float parameters are handled without the GIL, so the definition is available
+                // when Python is not initialized, such as through the C API.
+                let defparams = if let [Param::Float(theta), Param::Float(phi)] = params {
+                    smallvec![
+                        Param::Float(*theta),
+                        Param::Float(phi - PI / 2.),
+                        Param::Float(PI / 2. - phi),
+                    ]
+                } else {
+                    Python::with_gil(|py| {
+                        let theta_expr = clone_param(&params[0], py);
+                        let phi_expr1 = add_param(&params[1], -PI / 2., py);
+                        let phi_expr2 = multiply_param(&phi_expr1, -1.0, py);
+                        smallvec![theta_expr, phi_expr1, phi_expr2]
+                    })
+                };
                 Some(
                     CircuitData::from_standard_gates(
                         1,
@@ -1257,7 +1328,7 @@ impl Operation for StandardGate {
                     )
                     .expect("Unexpected Qiskit python bug"),
                 )
-            }),
+            }
             Self::RX => {
                 let theta = &params[0];
                 Some(
@@ -1881,36 +1952,40 @@ impl Operation for StandardGate {
                     .expect("Unexpected Qiskit python bug"),
                 )
             }
-            Self::XXMinusYY => Python::with_gil(|py| -> Option<CircuitData> {
+            Self::XXMinusYY => {
                 let q0 = smallvec![Qubit(0)];
                 let q1 = smallvec![Qubit(1)];
                 let q0_1 = smallvec![Qubit(0), Qubit(1)];
                 let theta = &params[0];
                 let beta = &params[1];
+                let (minus_beta, half_theta, minus_half_theta) =
+                    if let (Param::Float(theta), Param::Float(beta)) = (theta, beta) {
+                        (
+                            Param::Float(-beta),
+                            Param::Float(0.5 * theta),
+                            Param::Float(-0.5 * theta),
+                        )
+                    } else {
+                        Python::with_gil(|py| {
+                            (
+                                multiply_param(beta, -1.0, py),
+                                multiply_param(theta, 0.5, py),
+                                multiply_param(theta, -0.5, py),
+                            )
+                        })
+                    };
                 Some(
                     CircuitData::from_standard_gates(
                         2,
                         [
-                            (
-                                Self::RZ,
-                                smallvec![multiply_param(beta, -1.0, py)],
-                                q1.clone(),
-                            ),
+                            (Self::RZ, smallvec![minus_beta], q1.clone()),
                             (Self::Sdg, smallvec![], q0.clone()),
                             (Self::SX, smallvec![], q0.clone()),
                             (Self::S, smallvec![], q0.clone()),
                             (Self::S, smallvec![], q1.clone()),
                             (Self::CX, smallvec![], q0_1.clone()),
-                            (
-                                Self::RY,
-                                smallvec![multiply_param(theta, 0.5, py)],
-                                q0.clone(),
-                            ),
-                            (
-                                Self::RY,
-                                smallvec![multiply_param(theta, -0.5, py)],
-                                q1.clone(),
-                            ),
+                            (Self::RY, smallvec![half_theta], q0.clone()),
+                            (Self::RY, smallvec![minus_half_theta], q1.clone()),
                             (Self::CX, smallvec![], q0_1),
                             (Self::Sdg, smallvec![], q1.clone()),
                             (Self::Sdg, smallvec![], q0.clone()),
@@ -1922,13 +1997,24 @@ impl Operation for StandardGate {
                     )
                     .expect("Unexpected Qiskit python bug"),
                 )
-            }),
-            Self::XXPlusYY => Python::with_gil(|py| -> Option<CircuitData> {
+            }
+            Self::XXPlusYY => {
                 let q0 = smallvec![Qubit(0)];
                 let q1 = smallvec![Qubit(1)];
                 let q1_0 = smallvec![Qubit(1), Qubit(0)];
                 let theta = &params[0];
                 let beta = &params[1];
+                let (minus_half_theta, minus_beta) =
+                    if let (Param::Float(theta), Param::Float(beta)) = (theta, beta) {
+                        (Param::Float(-0.5 * theta), Param::Float(-beta))
+                    } else {
+                        Python::with_gil(|py| {
+                            (
+                                multiply_param(theta, -0.5, py),
+                                multiply_param(beta, -1.0, py),
+                            )
+                        })
+                    };
                 Some(
                     CircuitData::from_standard_gates(
                         2,
@@ -1939,28 +2025,20 @@ impl Operation for StandardGate {
                             (Self::S, smallvec![], q1.clone()),
                             (Self::S, smallvec![], q0.clone()),
                             (Self::CX, smallvec![], q1_0.clone()),
-                            (
-                                Self::RY,
-                                smallvec![multiply_param(theta, -0.5, py)],
-                                q1.clone(),
-                            ),
-                            (
-                                Self::RY,
-                                smallvec![multiply_param(theta, -0.5, py)],
-                                q0.clone(),
-                            ),
+                            (Self::RY, smallvec![minus_half_theta.clone()], q1.clone()),
+                            (Self::RY, smallvec![minus_half_theta], q0.clone()),
                             (Self::CX, smallvec![], q1_0),
                             (Self::Sdg, smallvec![], q0.clone()),
                             (Self::Sdg, smallvec![], q1.clone()),
                             (Self::SXdg, smallvec![], q1.clone()),
                             (Self::S, smallvec![], q1),
-                            (Self::RZ, smallvec![multiply_param(beta, -1.0, py)], q0),
+                            (Self::RZ, smallvec![minus_beta], q0),
                         ],
                         FLOAT_ZERO,
                     )
                     .expect("Unexpected Qiskit python bug"),
                 )
-            }),
+            }
             Self::CCX => {
                 let q0 = smallvec![Qubit(0)];
                 let q1 = smallvec![Qubit(1)];
@@ -2383,6 +2461,7 @@ fn clone_param(param: &Param, py: Python) -> Param {
     match param {
         Param::Float(theta) => Param::Float(*theta),
         Param::ParameterExpression(theta) => Param::ParameterExpression(theta.clone_ref(py)),
+        Param::Symbolic(theta) => Param::Symbolic(theta.clone()),
         Param::Obj(_) => unreachable!(),
     }
 }
@@ -2397,6 +2476,9 @@ pub fn multiply_param(param: &Param, mult: f64, py: Python) -> Param {
                 .call_method1(py, intern!(py, "__rmul__"), (mult,))
                 .expect("Multiplication of Parameter expression by float failed."),
         ),
+        Param::Symbolic(theta) => Param::Symbolic(Arc::new(
+            theta.unary_op(|theta| theta * &SymbolExpr::Value(Value::Real(mult))),
+        )),
         Param::Obj(_) => unreachable!("Unsupported multiplication of a Param::Obj."),
     }
 }
@@ -2414,6 +2496,20 @@ pub fn multiply_params(param1: Param, param2: Param, py: Python) -> Param {
                     .expect("Parameter expression multiplication failed"),
             )
         }
+        (Param::Symbolic(p1), Param::Symbolic(p2)) => Param::Symbolic(Arc::new(
+            p1.binary_op(p2, |p1, p2| p1 * p2)
+                .expect("Symbolic expression multiplication failed"),
+        )),
+        (Param::Symbolic(_), Param::ParameterExpression(_))
+        | (Param::ParameterExpression(_), Param::Symbolic(_)) => multiply_params(
+            param1
+                .to_python_expression(py)
+                .expect("Symbolic expression conversion failed"),
+            param2
+                .to_python_expression(py)
+                .expect("Symbolic expression conversion failed"),
+            py,
+        ),
         _ => unreachable!("Unsupported multiplication."),
     }
 }
@@ -2427,6 +2523,9 @@ pub fn add_param(param: &Param, summand: f64, py: Python) -> Param {
                 .call_method1(py, intern!(py, "__add__"), (summand,))
                 .expect("Sum of Parameter expression and float failed."),
         ),
+        Param::Symbolic(theta) => Param::Symbolic(Arc::new(
+            theta.unary_op(|theta| theta + &SymbolExpr::Value(Value::Real(summand))),
+        )),
         Param::Obj(_) => unreachable!(),
     }
 }
@@ -2434,12 +2533,27 @@ pub fn add_param(param: &Param, summand: f64, py: Python) -> Param {
 pub fn radd_param(param1: Param, param2: Param, py: Python) -> Param {
     match [&param1, &param2] {
         [Param::Float(theta), Param::Float(lambda)] => Param::Float(theta + lambda),
-        [Param::Float(theta), Param::ParameterExpression(_lambda)] => {
+        [Param::Float(theta), Param::ParameterExpression(_) | Param::Symbolic(_)] => {
             add_param(&param2, *theta, py)
         }
-        [Param::ParameterExpression(_theta), Param::Float(lambda)] => {
+        [Param::ParameterExpression(_) | Param::Symbolic(_), Param::Float(lambda)] => {
             add_param(&param1, *lambda, py)
         }
+        [Param::Symbolic(theta), Param::Symbolic(lambda)] => Param::Symbolic(Arc::new(
+            lambda
+                .binary_op(theta, |lambda, theta| lambda + theta)
+                .expect("Symbolic expression addition failed"),
+        )),
+        [Param::Symbolic(_), Param::ParameterExpression(_)]
+        | [Param::ParameterExpression(_), Param::Symbolic(_)] => radd_param(
+            param1
+                .to_python_expression(py)
+                .expect("Symbolic expression conversion failed"),
+            param2
+                .to_python_expression(py)
+                .expect("Symbolic expression conversion failed"),
+            py,
+        ),
         [Param::ParameterExpression(theta), Param::ParameterExpression(lambda)] => {
             Param::ParameterExpression(
                 theta
@@ -672,7 +672,7 @@ impl PackedInstruction {
     pub fn is_parameterized(&self) -> bool {
         self.params_view()
             .iter()
-            .any(|x| matches!(x, Param::ParameterExpression(_)))
+            .any(|x| matches!(x, Param::ParameterExpression(_) | Param::Symbolic(_)))
     }
 
     #[inline]
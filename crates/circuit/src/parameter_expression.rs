@@ -489,6 +489,19 @@ impl Default for ParameterExpression {
     }
 }
 
+impl From<SymbolExpr> for ParameterExpression {
+    fn from(expr: SymbolExpr) -> Self {
+        Self { expr }
+    }
+}
+
+impl ParameterExpression {
+    /// The expression tree.
+    pub fn expr(&self) -> &SymbolExpr {
+        &self.expr
+    }
+}
+
 impl fmt::Display for ParameterExpression {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.expr)
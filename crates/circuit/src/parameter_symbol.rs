@@ -0,0 +1,391 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2025
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Symbolic parameters that live in Rust space.
+//!
+//! A [SymbolicExpression] can be created and used without a Python interpreter, for example when
+//! a [Target](../../qiskit_transpiler/target/struct.Target.html) or a circuit is loaded from the C
+//! API.  It is converted to the equivalent Python-space `Parameter` or `ParameterExpression` only
+//! when it is passed to Python.
+
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, OnceLock};
+
+use hashbrown::HashSet;
+use indexmap::IndexMap;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use pyo3::{intern, IntoPyObjectExt};
+use thiserror::Error;
+
+use crate::imports::{
+    PARAMETER, PARAMETER_EXPRESSION, PARAMETER_VECTOR, PARAMETER_VECTOR_ELEMENT, UUID,
+};
+use crate::parameter_expression::ParameterExpression;
+use crate::symbol_expr::SymbolExpr;
+
+/// Errors that can occur while building a [SymbolicExpression].
+#[derive(Error, Debug)]
+pub enum SymbolicExpressionError {
+    /// Two distinct parameters of the expression have the same name.
+    #[error("distinct parameters of the expression are both named '{0}'")]
+    NameConflict(String),
+    /// The expression refers to a symbol that is not one of its parameters.
+    #[error("the expression refers to '{0}', which is not one of its parameters")]
+    UnknownSymbol(String),
+}
+
+fn py_uuid(py: Python, value: u128) -> PyResult<Bound<PyAny>> {
+    let kwargs = PyDict::new(py);
+    kwargs.set_item(intern!(py, "int"), value)?;
+    UUID.get_bound(py).call((), Some(&kwargs))
+}
+
+/// A parameter vector that Rust-space [ParameterSymbol]s can be elements of.
+pub struct SymbolVector {
+    name: String,
+    root_uuid: u128,
+    len: usize,
+    /// The Python-space `ParameterVector` and the list of its elements, created on first use.
+    py_vector: OnceLock<(Py<PyAny>, Py<PyList>)>,
+}
+
+impl SymbolVector {
+    pub fn new(name: String, root_uuid: u128, len: usize) -> Self {
+        Self {
+            name,
+            root_uuid,
+            len,
+            py_vector: OnceLock::new(),
+        }
+    }
+
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[inline]
+    pub fn root_uuid(&self) -> u128 {
+        self.root_uuid
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get the Python-space `ParameterVector` and its list of elements, creating them if this is
+    /// the first use.
+    fn py_vector<'py>(&self, py: Python<'py>) -> PyResult<(Bound<'py, PyAny>, Bound<'py, PyList>)> {
+        if self.py_vector.get().is_none() {
+            let vector = PARAMETER_VECTOR
+                .get_bound(py)
+                .call1((self.name.as_str(), 0))?;
+            vector.setattr(intern!(py, "_root_uuid"), py_uuid(py, self.root_uuid)?)?;
+            let elements = PyList::empty(py);
+            for index in 0..self.len {
+                elements.append(PARAMETER_VECTOR_ELEMENT.get_bound(py).call1((
+                    &vector,
+                    index,
+                    py_uuid(py, self.root_uuid.wrapping_add(index as u128))?,
+                ))?)?;
+            }
+            vector.setattr(intern!(py, "_params"), &elements)?;
+            // Another thread may have won the race while we held the GIL; either is fine.
+            let _ = self.py_vector.set((vector.unbind(), elements.unbind()));
+        }
+        let (vector, elements) = self.py_vector.get().unwrap();
+        Ok((vector.bind(py).clone(), elements.bind(py).clone()))
+    }
+}
+
+impl fmt::Debug for SymbolVector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SymbolVector")
+            .field("name", &self.name)
+            .field("root_uuid", &self.root_uuid)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+/// A symbolic parameter that lives in Rust space.
+///
+/// Like a Python-space `Parameter`, a symbol is identified by its UUID, not its name.  The
+/// equivalent Python-space object is created the first time it is needed and then reused, so the
+/// symbol always converts to the same `Parameter`.
+pub struct ParameterSymbol {
+    name: String,
+    uuid: u128,
+    vector: Option<(Arc<SymbolVector>, usize)>,
+    py_object: OnceLock<Py<PyAny>>,
+}
+
+impl ParameterSymbol {
+    /// Create a standalone parameter.
+    pub fn new(name: String, uuid: u128) -> Self {
+        Self {
+            name,
+            uuid,
+            vector: None,
+            py_object: OnceLock::new(),
+        }
+    }
+
+    /// Create a standalone parameter with a new random UUID.
+    pub fn new_unique(name: String) -> Self {
+        Self::new(name, uuid::Uuid::new_v4().as_u128())
+    }
+
+    /// Create the element of a parameter vector at `index`.
+    pub fn new_vector_element(vector: Arc<SymbolVector>, index: usize, uuid: u128) -> Self {
+        Self {
+            name: format!("{}[{}]", vector.name, index),
+            uuid,
+            vector: Some((vector, index)),
+            py_object: OnceLock::new(),
+        }
+    }
+
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[inline]
+    pub fn uuid(&self) -> u128 {
+        self.uuid
+    }
+
+    /// The vector this parameter is an element of, and its index in the vector.
+    #[inline]
+    pub fn vector(&self) -> Option<(&Arc<SymbolVector>, usize)> {
+        self.vector.as_ref().map(|(vector, index)| (vector, *index))
+    }
+
+    /// Get the equivalent Python-space `Parameter` (or `ParameterVectorElement`), creating it if
+    /// this is the first use.
+    pub fn to_py<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        if let Some(ob) = self.py_object.get() {
+            return Ok(ob.bind(py).clone());
+        }
+        let ob = match self.vector.as_ref() {
+            Some((vector, index)) => {
+                let (py_vector, elements) = vector.py_vector(py)?;
+                let element = PARAMETER_VECTOR_ELEMENT.get_bound(py).call1((
+                    py_vector,
+                    *index,
+                    py_uuid(py, self.uuid)?,
+                ))?;
+                if *index < vector.len {
+                    elements.set_item(*index, &element)?;
+                }
+                element
+            }
+            None => {
+                let kwargs = PyDict::new(py);
+                kwargs.set_item(intern!(py, "uuid"), py_uuid(py, self.uuid)?)?;
+                PARAMETER
+                    .get_bound(py)
+                    .call((self.name.as_str(),), Some(&kwargs))?
+            }
+        };
+        let _ = self.py_object.set(ob.clone().unbind());
+        Ok(ob)
+    }
+}
+
+impl PartialEq for ParameterSymbol {
+    fn eq(&self, other: &Self) -> bool {
+        self.uuid == other.uuid
+    }
+}
+impl Eq for ParameterSymbol {}
+
+impl Hash for ParameterSymbol {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.uuid.hash(state)
+    }
+}
+
+impl fmt::Debug for ParameterSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParameterSymbol")
+            .field("name", &self.name)
+            .field("uuid", &self.uuid)
+            .field("vector", &self.vector)
+            .finish()
+    }
+}
+
+/// A symbolic parameter expression that lives in Rust space.
+///
+/// As in the Python-space `ParameterExpression`, the expression tree refers to its symbols by
+/// name, and each name is mapped to the [ParameterSymbol] it stands for.  Two distinct parameters
+/// of one expression cannot share a name.
+#[derive(Clone, Debug)]
+pub struct SymbolicExpression {
+    expr: SymbolExpr,
+    symbols: IndexMap<String, Arc<ParameterSymbol>, ::ahash::RandomState>,
+}
+
+impl SymbolicExpression {
+    /// Build an expression from its tree and its parameters.
+    ///
+    /// Every symbol in `expr` must be the name of one of the `symbols`.  A parameter may appear
+    /// more than once in `symbols`, but two distinct parameters may not have the same name.
+    pub fn new(
+        expr: SymbolExpr,
+        symbols: impl IntoIterator<Item = Arc<ParameterSymbol>>,
+    ) -> Result<Self, SymbolicExpressionError> {
+        let mut by_name: IndexMap<String, Arc<ParameterSymbol>, ::ahash::RandomState> =
+            IndexMap::with_hasher(::ahash::RandomState::default());
+        for symbol in symbols {
+            match by_name.entry(symbol.name.clone()) {
+                indexmap::map::Entry::Occupied(entry) => {
+                    if **entry.get() != *symbol {
+                        return Err(SymbolicExpressionError::NameConflict(symbol.name.clone()));
+                    }
+                }
+                indexmap::map::Entry::Vacant(entry) => {
+                    entry.insert(symbol);
+                }
+            }
+        }
+        if let Some(name) = expr
+            .symbols()
+            .into_iter()
+            .find(|name| !by_name.contains_key(name))
+        {
+            return Err(SymbolicExpressionError::UnknownSymbol(name));
+        }
+        Ok(Self {
+            expr,
+            symbols: by_name,
+        })
+    }
+
+    /// Build the expression that is a single parameter.
+    pub fn from_symbol(symbol: Arc<ParameterSymbol>) -> Self {
+        let mut symbols = IndexMap::with_hasher(::ahash::RandomState::default());
+        let expr = SymbolExpr::Symbol(Arc::new(symbol.name.clone()));
+        symbols.insert(symbol.name.clone(), symbol);
+        Self { expr, symbols }
+    }
+
+    /// The expression tree, in which symbols are referred to by name.
+    #[inline]
+    pub fn expr(&self) -> &SymbolExpr {
+        &self.expr
+    }
+
+    /// The parameters of the expression.
+    pub fn symbols(&self) -> impl ExactSizeIterator<Item = &Arc<ParameterSymbol>> {
+        self.symbols.values()
+    }
+
+    /// Look up the parameter that a symbol name in the expression tree stands for.
+    pub fn symbol_by_name(&self, name: &str) -> Option<&Arc<ParameterSymbol>> {
+        self.symbols.get(name)
+    }
+
+    /// If the expression is a single parameter, get it.
+    pub fn as_symbol(&self) -> Option<&Arc<ParameterSymbol>> {
+        match &self.expr {
+            SymbolExpr::Symbol(name) => self.symbols.get(name.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Build the expression `op(self)`.
+    pub fn unary_op(&self, op: impl FnOnce(&SymbolExpr) -> SymbolExpr) -> Self {
+        Self::with_used_symbols(op(&self.expr), self.symbols.values())
+            .expect("symbols of the operand cannot conflict")
+    }
+
+    /// Build the expression `op(self, other)`, whose parameters are those of both operands.
+    ///
+    /// This fails if the operands have distinct parameters with the same name.
+    pub fn binary_op(
+        &self,
+        other: &Self,
+        op: impl FnOnce(&SymbolExpr, &SymbolExpr) -> SymbolExpr,
+    ) -> Result<Self, SymbolicExpressionError> {
+        Self::with_used_symbols(
+            op(&self.expr, &other.expr),
+            self.symbols.values().chain(other.symbols.values()),
+        )
+    }
+
+    /// Build an expression from `expr`, keeping only those of `symbols` that it still refers to.
+    /// Operations can cancel out symbols, and a parameter must not outlive its last use.
+    fn with_used_symbols<'a>(
+        expr: SymbolExpr,
+        symbols: impl Iterator<Item = &'a Arc<ParameterSymbol>>,
+    ) -> Result<Self, SymbolicExpressionError> {
+        let used = expr.symbols();
+        let symbols = symbols
+            .filter(|symbol| used.contains(&symbol.name))
+            .cloned()
+            .collect::<Vec<_>>();
+        Self::new(expr, symbols)
+    }
+
+    /// Get the equivalent Python-space `Parameter` or `ParameterExpression`.
+    pub fn to_py<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        if let Some(symbol) = self.as_symbol() {
+            return symbol.to_py(py);
+        }
+        let symbol_map = PyDict::new(py);
+        for (name, symbol) in self.symbols.iter() {
+            symbol_map.set_item(
+                symbol.to_py(py)?,
+                ParameterExpression::from(SymbolExpr::Symbol(Arc::new(name.clone()))),
+            )?;
+        }
+        PARAMETER_EXPRESSION
+            .get_bound(py)
+            .call1((symbol_map, ParameterExpression::from(self.expr.clone())))
+    }
+}
+
+impl PartialEq for SymbolicExpression {
+    fn eq(&self, other: &Self) -> bool {
+        self.expr == other.expr
+            && self.symbols.len() == other.symbols.len()
+            && self.symbols.values().collect::<HashSet<_>>()
+                == other.symbols.values().collect::<HashSet<_>>()
+    }
+}
+
+impl fmt::Display for SymbolicExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.expr)
+    }
+}
+
+impl<'py> IntoPyObject<'py> for &SymbolicExpression {
+    type Target = PyAny;
+    type Output = Bound<'py, PyAny>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Self::Output> {
+        self.to_py(py)?.into_bound_py_any(py)
+    }
+}
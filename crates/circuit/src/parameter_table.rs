@@ -10,7 +10,7 @@
 // copyright notice, and modified files need to carry a notice indicating
 // that they have been altered from the originals.
 
-use std::sync::OnceLock;
+use std::sync::{Arc, OnceLock};
 
 use hashbrown::hash_map::Entry;
 use hashbrown::{HashMap, HashSet};
@@ -18,11 +18,12 @@ use thiserror::Error;
 
 use pyo3::exceptions::PyTypeError;
 use pyo3::prelude::*;
-use pyo3::pybacked::PyBackedStr;
 use pyo3::types::{PyList, PySet};
 use pyo3::{import_exception, intern, PyTraverseError, PyVisit};
 
 use crate::imports::UUID;
+use crate::operations::Param;
+use crate::parameter_symbol::ParameterSymbol;
 
 import_exception!(qiskit.circuit, CircuitError);
 
@@ -32,6 +33,8 @@ pub enum ParameterTableError {
     ParameterNotTracked(ParameterUuid),
     #[error("usage {0:?} is not tracked by the table")]
     UsageNotTracked(ParameterUse),
+    #[error("name conflict adding parameter '{0}'")]
+    NameConflict(String),
 }
 impl From<ParameterTableError> for PyErr {
     fn from(value: ParameterTableError) -> PyErr {
@@ -54,19 +57,37 @@ struct VectorElement {
     index: usize,
 }
 
+/// The object that a parameter in the table was tracked from.
+#[derive(Clone, Debug)]
+enum ParameterObject {
+    /// A Python-space `Parameter`.
+    Python(Py<PyAny>),
+    /// A Rust-space symbol, which is only converted to a Python-space `Parameter` on request.
+    Symbol(Arc<ParameterSymbol>),
+}
+
+impl ParameterObject {
+    fn to_py<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        match self {
+            Self::Python(ob) => Ok(ob.bind(py).clone()),
+            Self::Symbol(symbol) => symbol.to_py(py),
+        }
+    }
+}
+
 /// Tracked data tied to each parameter's UUID in the table.
 #[derive(Clone, Debug)]
 pub struct ParameterInfo {
     uses: HashSet<ParameterUse>,
-    name: PyBackedStr,
+    name: String,
     element: Option<VectorElement>,
-    object: Py<PyAny>,
+    object: ParameterObject,
 }
 
-/// Rust-space information on a Python `ParameterVector` and its uses in the table.
+/// Rust-space information on a `ParameterVector` and its uses in the table.
 #[derive(Clone, Debug)]
 struct VectorInfo {
-    name: PyBackedStr,
+    name: String,
     /// Number of elements of the vector tracked within the parameter table.
     refcount: usize,
 }
@@ -82,6 +103,12 @@ impl ParameterUuid {
     pub fn from_parameter(ob: &Bound<PyAny>) -> PyResult<Self> {
         ob.getattr(intern!(ob.py(), "_uuid"))?.extract()
     }
+
+    /// Get the UUID of a Rust-space symbol.
+    #[inline]
+    pub fn from_symbol(symbol: &ParameterSymbol) -> Self {
+        Self(symbol.uuid())
+    }
 }
 
 /// This implementation of `FromPyObject` is for the UUID itself, which is what the `ParameterUuid`
@@ -120,9 +147,8 @@ impl<'py> FromPyObject<'py> for VectorUuid {
 pub struct ParameterTable {
     /// Mapping of the parameter key (its UUID) to the information on it tracked by this table.
     by_uuid: HashMap<ParameterUuid, ParameterInfo>,
-    /// Mapping of the parameter names to the UUID that represents them.  Since we always get
-    /// parameters in as Python-space objects, we use the string object extracted from Python space.
-    by_name: HashMap<PyBackedStr, ParameterUuid>,
+    /// Mapping of the parameter names to the UUID that represents them.
+    by_name: HashMap<String, ParameterUuid>,
     /// Additional information on any `ParameterVector` instances that have elements in the circuit.
     vectors: HashMap<VectorUuid, VectorInfo>,
     /// Cache of the sort order of the parameters.  This is lexicographical for most parameters,
@@ -169,12 +195,9 @@ impl ParameterTable {
             }
             Entry::Vacant(entry) => {
                 let py_name_attr = intern!(py, "name");
-                let name = param_ob.getattr(py_name_attr)?.extract::<PyBackedStr>()?;
+                let name = param_ob.getattr(py_name_attr)?.extract::<String>()?;
                 if self.by_name.contains_key(&name) {
-                    return Err(CircuitError::new_err(format!(
-                        "name conflict adding parameter '{}'",
-                        &name
-                    )));
+                    return Err(ParameterTableError::NameConflict(name).into());
                 }
                 let element = if let Ok(vector) = param_ob.getattr(intern!(py, "vector")) {
                     let vector_uuid = VectorUuid::from_vector(&vector)?;
@@ -205,7 +228,51 @@ impl ParameterTable {
                     name,
                     uses,
                     element,
-                    object: param_ob.clone().unbind(),
+                    object: ParameterObject::Python(param_ob.clone().unbind()),
+                });
+                self.invalidate_cache();
+            }
+        }
+        Ok(uuid)
+    }
+
+    /// Add a new usage of a Rust-space symbol, optionally adding a first usage to it.
+    ///
+    /// Unlike [track](Self::track), this does not need Python.  A symbol and a Python-space
+    /// `Parameter` with the same UUID are the same parameter of the table.
+    pub fn track_symbol(
+        &mut self,
+        symbol: &Arc<ParameterSymbol>,
+        usage: Option<ParameterUse>,
+    ) -> Result<ParameterUuid, ParameterTableError> {
+        let uuid = ParameterUuid::from_symbol(symbol);
+        match self.by_uuid.entry(uuid) {
+            Entry::Occupied(mut entry) => {
+                if let Some(usage) = usage {
+                    entry.get_mut().uses.insert(usage);
+                }
+            }
+            Entry::Vacant(entry) => {
+                if self.by_name.contains_key(symbol.name()) {
+                    return Err(ParameterTableError::NameConflict(symbol.name().to_owned()));
+                }
+                let element = symbol.vector().map(|(vector, index)| {
+                    let vector_uuid = VectorUuid(vector.root_uuid());
+                    self.vectors
+                        .entry(vector_uuid)
+                        .and_modify(|info| info.refcount += 1)
+                        .or_insert_with(|| VectorInfo {
+                            name: vector.name().to_owned(),
+                            refcount: 1,
+                        });
+                    VectorElement { vector_uuid, index }
+                });
+                self.by_name.insert(symbol.name().to_owned(), uuid);
+                entry.insert(ParameterInfo {
+                    name: symbol.name().to_owned(),
+                    uses: usage.into_iter().collect(),
+                    element,
+                    object: ParameterObject::Symbol(symbol.clone()),
                 });
                 self.invalidate_cache();
             }
@@ -213,6 +280,46 @@ impl ParameterTable {
         Ok(uuid)
     }
 
+    /// Track a use of every parameter in `param`.
+    ///
+    /// Rust-space symbolic parameters are tracked without Python; Python-space ones need the GIL.
+    pub fn track_param(&mut self, param: &Param, usage: ParameterUse) -> PyResult<()> {
+        match param {
+            Param::Float(_) => Ok(()),
+            Param::Symbolic(expr) => {
+                for symbol in expr.symbols() {
+                    self.track_symbol(symbol, Some(usage))?;
+                }
+                Ok(())
+            }
+            Param::ParameterExpression(_) | Param::Obj(_) => Python::with_gil(|py| {
+                for param_ob in param.iter_parameters(py)? {
+                    self.track(&param_ob?, Some(usage))?;
+                }
+                Ok(())
+            }),
+        }
+    }
+
+    /// Untrack a use of every parameter in `param`; the inverse of [track_param](Self::track_param).
+    pub fn untrack_param(&mut self, param: &Param, usage: ParameterUse) -> PyResult<()> {
+        match param {
+            Param::Float(_) => Ok(()),
+            Param::Symbolic(expr) => {
+                for symbol in expr.symbols() {
+                    self.remove_use(ParameterUuid::from_symbol(symbol), usage)?;
+                }
+                Ok(())
+            }
+            Param::ParameterExpression(_) | Param::Obj(_) => Python::with_gil(|py| {
+                for param_ob in param.iter_parameters(py)? {
+                    self.untrack(&param_ob?, usage)?;
+                }
+                Ok(())
+            }),
+        }
+    }
+
     /// Untrack one use of a single Python-space `Parameter` object from the table, discarding all
     /// other tracking of that `Parameter` if this was the last usage of it.
     pub fn untrack(&mut self, param_ob: &Bound<PyAny>, usage: ParameterUse) -> PyResult<()> {
@@ -221,38 +328,58 @@ impl ParameterTable {
     }
 
     /// Lookup the Python parameter object by name.
-    pub fn py_parameter_by_name(&self, name: &PyBackedStr) -> Option<&Py<PyAny>> {
+    pub fn py_parameter_by_name<'py>(
+        &self,
+        py: Python<'py>,
+        name: &str,
+    ) -> PyResult<Option<Bound<'py, PyAny>>> {
         self.by_name
             .get(name)
-            .map(|uuid| &self.by_uuid[uuid].object)
+            .map(|uuid| self.by_uuid[uuid].object.to_py(py))
+            .transpose()
     }
 
     /// Lookup the Python parameter object by uuid.
-    pub fn py_parameter_by_uuid(&self, uuid: ParameterUuid) -> Option<&Py<PyAny>> {
-        self.by_uuid.get(&uuid).map(|param| &param.object)
+    pub fn py_parameter_by_uuid<'py>(
+        &self,
+        py: Python<'py>,
+        uuid: ParameterUuid,
+    ) -> PyResult<Option<Bound<'py, PyAny>>> {
+        self.by_uuid
+            .get(&uuid)
+            .map(|param| param.object.to_py(py))
+            .transpose()
     }
 
     /// Get the (maybe cached) Python list of the sorted `Parameter` objects.
-    pub fn py_parameters<'py>(&self, py: Python<'py>) -> Bound<'py, PyList> {
-        self.py_parameters_cache
-            .get_or_init(|| {
-                PyList::new(
-                    py,
-                    self.order_cache
-                        .get_or_init(|| self.sorted_order())
-                        .iter()
-                        .map(|uuid| self.by_uuid[uuid].object.bind(py).clone()),
-                )
-                .unwrap()
-                .unbind()
-            })
+    pub fn py_parameters<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyList>> {
+        if let Some(list) = self.py_parameters_cache.get() {
+            return Ok(list.bind(py).clone());
+        }
+        let list = PyList::new(
+            py,
+            self.order_cache
+                .get_or_init(|| self.sorted_order())
+                .iter()
+                .map(|uuid| self.by_uuid[uuid].object.to_py(py))
+                .collect::<PyResult<Vec<_>>>()?,
+        )?;
+        Ok(self
+            .py_parameters_cache
+            .get_or_init(|| list.unbind())
             .bind(py)
-            .clone()
+            .clone())
     }
 
     /// Get a Python set of all tracked `Parameter` objects.
     pub fn py_parameters_unsorted<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PySet>> {
-        PySet::new(py, self.by_uuid.values().map(|info| &info.object))
+        PySet::new(
+            py,
+            self.by_uuid
+                .values()
+                .map(|info| info.object.to_py(py))
+                .collect::<PyResult<Vec<_>>>()?,
+        )
     }
 
     /// Get the sorted order of the `ParameterTable`.  This does not access the cache.
@@ -341,22 +468,30 @@ impl ParameterTable {
 
     /// Clear this table, yielding the Python parameter objects and their uses in sorted order.
     ///
-    /// The clearing effect is eager and not dependent on the iteration.
+    /// The clearing effect is eager and not dependent on the iteration.  Rust-space symbols are
+    /// converted to Python space before anything is yielded.
     pub fn drain_ordered(
         &mut self,
-    ) -> impl ExactSizeIterator<Item = (Py<PyAny>, HashSet<ParameterUse>)> {
+        py: Python,
+    ) -> PyResult<impl ExactSizeIterator<Item = (Py<PyAny>, HashSet<ParameterUse>)>> {
         let order = self
             .order_cache
             .take()
             .unwrap_or_else(|| self.sorted_order());
-        let by_uuid = ::std::mem::take(&mut self.by_uuid);
+        let mut by_uuid = ::std::mem::take(&mut self.by_uuid);
         self.by_name.clear();
         self.vectors.clear();
         self.py_parameters_cache.take();
-        ParameterTableDrain {
-            order: order.into_iter(),
-            by_uuid,
-        }
+        let drained = order
+            .into_iter()
+            .map(|uuid| {
+                let info = by_uuid
+                    .remove(&uuid)
+                    .expect("tracked UUIDs should be consistent");
+                Ok((info.object.to_py(py)?.unbind(), info.uses))
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        Ok(drained.into_iter())
     }
 
     /// Empty this `ParameterTable` of all its contents.  This does not affect the capacities of the
@@ -402,36 +537,14 @@ impl ParameterTable {
     /// This is not a pyclass, so it's up to our owner to delegate their own traversal to us.
     pub fn py_gc_traverse(&self, visit: &PyVisit) -> Result<(), PyTraverseError> {
         for info in self.by_uuid.values() {
-            visit.call(&info.object)?
+            if let ParameterObject::Python(ob) = &info.object {
+                visit.call(ob)?
+            }
         }
-        // We don't need to / can't visit the `PyBackedStr` stores.
+        // Rust-space symbols hold their Python-space `Parameter` in a cache we can't visit.
         if let Some(list) = self.py_parameters_cache.get() {
             visit.call(list)?
         }
         Ok(())
     }
 }
-
-struct ParameterTableDrain {
-    order: ::std::vec::IntoIter<ParameterUuid>,
-    by_uuid: HashMap<ParameterUuid, ParameterInfo>,
-}
-impl Iterator for ParameterTableDrain {
-    type Item = (Py<PyAny>, HashSet<ParameterUse>);
-
-    fn next(&mut self) -> Option<Self::Item> {
-        self.order.next().map(|uuid| {
-            let info = self
-                .by_uuid
-                .remove(&uuid)
-                .expect("tracked UUIDs should be consistent");
-            (info.object, info.uses)
-        })
-    }
-
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        self.order.size_hint()
-    }
-}
-impl ExactSizeIterator for ParameterTableDrain {}
-impl ::std::iter::FusedIterator for ParameterTableDrain {}
@@ -0,0 +1,1567 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2025
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! A versioned binary format for [CircuitData].
+//!
+//! The format is designed to be compact and fast to read and write without Python.  All integers
+//! and floats are little endian, lengths are `u32` (with the exception of the instruction count,
+//! which is a `u64`) and strings are a length followed by UTF-8 bytes.  A document is laid out as:
+//!
+//! 1. the magic bytes `QKCD` and a `u16` format version;
+//! 2. the qubits: the table of registers that own qubits, then the qubits, then the quantum
+//!    registers of the circuit;
+//! 3. the clbits, laid out in the same way as the qubits;
+//! 4. the realtime variables and stretches, in order of their addition to the circuit;
+//! 5. the symbolic parameters: the table of parameter vectors, then the table of parameters;
+//! 6. the global phase;
+//! 7. the instructions;
+//! 8. the [TranspileLayout], if any.
+//!
+//! Operations defined in Python (such as control flow or custom gates) cannot be serialized.
+//! Symbolic parameters are stored as [SymbolExpr] trees over the parameter table, which identifies
+//! each parameter by its UUID.  They are loaded as Rust-space [ParameterSymbol]s, so loading never
+//! needs Python.  Python-space integer parameters, such as the durations of delays in `dt`, are
+//! loaded as integers if Python is running, and as floats otherwise.
+
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use hashbrown::{HashMap, HashSet};
+use indexmap::IndexSet;
+use ndarray::Array2;
+use num_complex::Complex64;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyInt};
+use pyo3::{intern, IntoPyObjectExt, PyErr};
+use smallvec::SmallVec;
+use thiserror::Error;
+
+use crate::bit::{ClassicalRegister, QuantumRegister, Register, ShareableClbit, ShareableQubit};
+use crate::circuit_data::{CircuitData, CircuitIdentifierInfo, CircuitStretchType, CircuitVarType};
+use crate::classical::expr;
+use crate::classical::types::Type;
+use crate::imports::{PARAMETER, PARAMETER_VECTOR_ELEMENT};
+use crate::nlayout::{NLayout, PhysicalQubit, VirtualQubit};
+use crate::operations::{
+    ArrayType, DelayUnit, Operation, OperationRef, Param, StandardGate, StandardInstruction,
+    UnitaryGate,
+};
+use crate::packed_instruction::{PackedInstruction, PackedOperation};
+use crate::parameter_expression::ParameterExpression;
+use crate::parameter_symbol::{ParameterSymbol, SymbolVector, SymbolicExpression};
+use crate::symbol_expr::{BinaryOp, SymbolExpr, UnaryOp, Value};
+use crate::transpile_layout::TranspileLayout;
+use crate::{python_is_initialized, Clbit, Qubit};
+
+const MAGIC: &[u8; 4] = b"QKCD";
+/// The version of the format written by [dump].  [load] accepts this and all earlier versions.
+pub const FORMAT_VERSION: u16 = 1;
+
+#[derive(Error, Debug)]
+pub enum SerializationError {
+    #[error("the data is not a serialized circuit")]
+    BadMagic,
+    #[error("unsupported format version {0}; the newest supported version is {FORMAT_VERSION}")]
+    UnsupportedVersion(u16),
+    #[error("unexpected end of data")]
+    UnexpectedEnd,
+    #[error("malformed data: {0}")]
+    Malformed(String),
+    #[error("cannot serialize {0}")]
+    Unsupported(String),
+    #[error(transparent)]
+    Python(#[from] PyErr),
+}
+impl From<SerializationError> for PyErr {
+    fn from(value: SerializationError) -> PyErr {
+        match value {
+            SerializationError::Python(err) => err,
+            value => PyValueError::new_err(value.to_string()),
+        }
+    }
+}
+
+fn malformed<T>(msg: impl Into<String>) -> Result<T, SerializationError> {
+    Err(SerializationError::Malformed(msg.into()))
+}
+
+/// Serialize a circuit.
+pub fn dump(circuit: &CircuitData) -> Result<Vec<u8>, SerializationError> {
+    let mut encoder = Encoder::default();
+    let mut body = Writer::default();
+    encoder.param(&mut body, circuit.global_phase())?;
+    body.u64(circuit.data().len() as u64);
+    for inst in circuit.data() {
+        encoder.instruction(&mut body, circuit, inst)?;
+    }
+
+    let mut out = Writer::default();
+    out.0.extend_from_slice(MAGIC);
+    out.u16(FORMAT_VERSION);
+    write_bits(&mut out, circuit.qubits().objects(), circuit.qregs())?;
+    write_bits(&mut out, circuit.clbits().objects(), circuit.cregs())?;
+    write_identifiers(&mut out, circuit)?;
+    encoder.parameter_tables(&mut out)?;
+    out.0.extend_from_slice(&body.0);
+    write_layout(&mut out, circuit.layout())?;
+    Ok(out.0)
+}
+
+/// Load a circuit that was serialized by [dump].
+///
+/// The operands of each instruction are checked against its operation, so malformed data is an
+/// error rather than an inconsistent circuit.  Symbolic parameters are loaded as
+/// [Param::Symbolic] values.
+pub fn load(data: &[u8]) -> Result<CircuitData, SerializationError> {
+    let mut reader = Reader { data, pos: 0 };
+    if reader.bytes(MAGIC.len()).ok() != Some(MAGIC.as_slice()) {
+        return Err(SerializationError::BadMagic);
+    }
+    let version = reader.u16()?;
+    if version == 0 || version > FORMAT_VERSION {
+        return Err(SerializationError::UnsupportedVersion(version));
+    }
+    let mut circuit = CircuitData::with_capacity(0, 0, 0, Param::Float(0.0))?;
+    let (qubits, qregs) = read_bits::<ShareableQubit>(&mut reader)?;
+    for qubit in qubits {
+        circuit.add_qubit(qubit, true)?;
+    }
+    for qreg in qregs {
+        circuit.add_qreg(qreg, true)?;
+    }
+    let (clbits, cregs) = read_bits::<ShareableClbit>(&mut reader)?;
+    for clbit in clbits {
+        circuit.add_clbit(clbit, true)?;
+    }
+    for creg in cregs {
+        circuit.add_creg(creg, true)?;
+    }
+    read_identifiers(&mut reader, &mut circuit)?;
+    let mut decoder = Decoder::read_parameter_tables(&mut reader)?;
+    let global_phase = decoder.param(&mut reader)?;
+    circuit.set_global_phase(global_phase)?;
+    let num_instructions = reader.u64()?;
+    for _ in 0..num_instructions {
+        let inst = decoder.instruction(&mut reader, &mut circuit)?;
+        circuit.push(inst)?;
+    }
+    circuit.set_layout(read_layout(&mut reader)?);
+    if reader.pos != data.len() {
+        return malformed(format!(
+            "{} bytes of trailing data",
+            data.len() - reader.pos
+        ));
+    }
+    Ok(circuit)
+}
+
+#[derive(Default)]
+struct Writer(Vec<u8>);
+
+impl Writer {
+    fn u8(&mut self, value: u8) {
+        self.0.push(value);
+    }
+    fn u16(&mut self, value: u16) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+    fn u32(&mut self, value: u32) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+    fn u64(&mut self, value: u64) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+    fn i64(&mut self, value: i64) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+    fn u128(&mut self, value: u128) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+    fn f64(&mut self, value: f64) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+    fn complex(&mut self, value: Complex64) {
+        self.f64(value.re);
+        self.f64(value.im);
+    }
+    fn len(&mut self, len: usize) -> Result<(), SerializationError> {
+        let len = u32::try_from(len)
+            .map_err(|_| SerializationError::Unsupported(format!("a sequence of length {len}")))?;
+        self.u32(len);
+        Ok(())
+    }
+    fn str(&mut self, value: &str) -> Result<(), SerializationError> {
+        self.len(value.len())?;
+        self.0.extend_from_slice(value.as_bytes());
+        Ok(())
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], SerializationError> {
+        let out = self
+            .data
+            .get(self.pos..self.pos.saturating_add(len))
+            .ok_or(SerializationError::UnexpectedEnd)?;
+        self.pos += len;
+        Ok(out)
+    }
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], SerializationError> {
+        Ok(self.bytes(N)?.try_into().unwrap())
+    }
+    fn u8(&mut self) -> Result<u8, SerializationError> {
+        Ok(self.array::<1>()?[0])
+    }
+    fn u16(&mut self) -> Result<u16, SerializationError> {
+        self.array().map(u16::from_le_bytes)
+    }
+    fn u32(&mut self) -> Result<u32, SerializationError> {
+        self.array().map(u32::from_le_bytes)
+    }
+    fn u64(&mut self) -> Result<u64, SerializationError> {
+        self.array().map(u64::from_le_bytes)
+    }
+    fn i64(&mut self) -> Result<i64, SerializationError> {
+        self.array().map(i64::from_le_bytes)
+    }
+    fn u128(&mut self) -> Result<u128, SerializationError> {
+        self.array().map(u128::from_le_bytes)
+    }
+    fn f64(&mut self) -> Result<f64, SerializationError> {
+        self.array().map(f64::from_le_bytes)
+    }
+    fn complex(&mut self) -> Result<Complex64, SerializationError> {
+        Ok(Complex64::new(self.f64()?, self.f64()?))
+    }
+    fn remaining(&self) -> usize {
+        self.data.len().saturating_sub(self.pos)
+    }
+    fn len(&mut self) -> Result<usize, SerializationError> {
+        self.u32().map(|len| len as usize)
+    }
+    fn str(&mut self) -> Result<String, SerializationError> {
+        let len = self.len()?;
+        String::from_utf8(self.bytes(len)?.to_vec())
+            .or_else(|_| malformed("a string is not valid UTF-8"))
+    }
+    fn u32s(&mut self) -> Result<Vec<u32>, SerializationError> {
+        let len = self.len()?;
+        (0..len).map(|_| self.u32()).collect()
+    }
+    /// Read an index into a table of length `len`.
+    fn index(&mut self, len: usize, what: &str) -> Result<usize, SerializationError> {
+        let index = self.u32()? as usize;
+        if index >= len {
+            return malformed(format!("{what} index {index} is out of range"));
+        }
+        Ok(index)
+    }
+}
+
+/// The operations on qubits and clbits needed to serialize them and their registers.
+trait SerializableBit: Clone + Eq + Hash + Debug {
+    type Reg: Register<Bit = Self> + Clone + Eq + Hash + Debug;
+    fn new_anonymous_bit(ancilla: bool) -> Self;
+    fn owning_register(&self) -> Option<(Self::Reg, u32)>;
+    fn is_ancilla_bit(&self) -> bool;
+    fn new_owning_register(name: String, size: u32, ancilla: bool) -> Self::Reg;
+    fn new_alias_register(name: String, bits: Vec<Self>, ancilla: bool) -> Option<Self::Reg>;
+    fn is_owning_register(register: &Self::Reg) -> bool;
+    fn is_ancilla_register(register: &Self::Reg) -> bool;
+}
+
+impl SerializableBit for ShareableQubit {
+    type Reg = QuantumRegister;
+    fn new_anonymous_bit(ancilla: bool) -> Self {
+        if ancilla {
+            ShareableQubit::new_anonymous_ancilla()
+        } else {
+            ShareableQubit::new_anonymous()
+        }
+    }
+    fn owning_register(&self) -> Option<(Self::Reg, u32)> {
+        ShareableQubit::owning_register(self)
+    }
+    fn is_ancilla_bit(&self) -> bool {
+        self.is_ancilla()
+    }
+    fn new_owning_register(name: String, size: u32, ancilla: bool) -> Self::Reg {
+        if ancilla {
+            QuantumRegister::new_ancilla_owning(name, size)
+        } else {
+            QuantumRegister::new_owning(name, size)
+        }
+    }
+    fn new_alias_register(name: String, bits: Vec<Self>, ancilla: bool) -> Option<Self::Reg> {
+        if ancilla {
+            QuantumRegister::new_ancilla_alias(name, bits)
+        } else {
+            Some(QuantumRegister::new_alias(Some(name), bits))
+        }
+    }
+    fn is_owning_register(register: &Self::Reg) -> bool {
+        register.is_owning()
+    }
+    fn is_ancilla_register(register: &Self::Reg) -> bool {
+        register.is_ancilla()
+    }
+}
+
+impl SerializableBit for ShareableClbit {
+    type Reg = ClassicalRegister;
+    fn new_anonymous_bit(_ancilla: bool) -> Self {
+        ShareableClbit::new_anonymous()
+    }
+    fn owning_register(&self) -> Option<(Self::Reg, u32)> {
+        ShareableClbit::owning_register(self)
+    }
+    fn is_ancilla_bit(&self) -> bool {
+        false
+    }
+    fn new_owning_register(name: String, size: u32, _ancilla: bool) -> Self::Reg {
+        ClassicalRegister::new_owning(name, size)
+    }
+    fn new_alias_register(name: String, bits: Vec<Self>, _ancilla: bool) -> Option<Self::Reg> {
+        Some(ClassicalRegister::new_alias(Some(name), bits))
+    }
+    fn is_owning_register(register: &Self::Reg) -> bool {
+        register.is_owning()
+    }
+    fn is_ancilla_register(_register: &Self::Reg) -> bool {
+        false
+    }
+}
+
+const BIT_ANONYMOUS: u8 = 0;
+const BIT_ANONYMOUS_ANCILLA: u8 = 1;
+const BIT_OWNED: u8 = 2;
+const REGISTER_OWNING: u8 = 0;
+const REGISTER_ALIAS: u8 = 1;
+
+fn write_bits<B: SerializableBit>(
+    out: &mut Writer,
+    bits: &[B],
+    registers: &[B::Reg],
+) -> Result<(), SerializationError> {
+    let mut owners = IndexSet::<B::Reg>::new();
+    for bit in bits {
+        if let Some((register, _)) = bit.owning_register() {
+            owners.insert(register);
+        }
+    }
+    for register in registers {
+        if B::is_owning_register(register) {
+            owners.insert(register.clone());
+        }
+    }
+    out.len(owners.len())?;
+    for register in owners.iter() {
+        out.str(register.name())?;
+        out.len(register.len())?;
+        out.u8(B::is_ancilla_register(register) as u8);
+    }
+    out.len(bits.len())?;
+    for bit in bits {
+        match bit.owning_register() {
+            Some((register, index)) => {
+                out.u8(BIT_OWNED);
+                out.u32(owners.get_index_of(&register).unwrap() as u32);
+                out.u32(index);
+            }
+            None if bit.is_ancilla_bit() => out.u8(BIT_ANONYMOUS_ANCILLA),
+            None => out.u8(BIT_ANONYMOUS),
+        }
+    }
+    let bit_indices = bits
+        .iter()
+        .enumerate()
+        .map(|(index, bit)| (bit, index as u32))
+        .collect::<HashMap<_, _>>();
+    out.len(registers.len())?;
+    for register in registers {
+        if B::is_owning_register(register) {
+            out.u8(REGISTER_OWNING);
+            out.u32(owners.get_index_of(register).unwrap() as u32);
+            continue;
+        }
+        out.u8(REGISTER_ALIAS);
+        out.str(register.name())?;
+        out.u8(B::is_ancilla_register(register) as u8);
+        out.len(register.len())?;
+        for bit in register.bits() {
+            let Some(index) = bit_indices.get(&bit) else {
+                return Err(SerializationError::Unsupported(format!(
+                    "register '{}', which contains bits that are not in the circuit",
+                    register.name()
+                )));
+            };
+            out.u32(*index);
+        }
+    }
+    Ok(())
+}
+
+fn read_bits<B: SerializableBit>(
+    reader: &mut Reader,
+) -> Result<(Vec<B>, Vec<B::Reg>), SerializationError> {
+    let num_owners = reader.len()?;
+    let owners = (0..num_owners)
+        .map(|_| -> Result<_, SerializationError> {
+            let name = reader.str()?;
+            let size = reader.u32()?;
+            let ancilla = reader.u8()? != 0;
+            Ok(B::new_owning_register(name, size, ancilla))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let num_bits = reader.len()?;
+    let bits = (0..num_bits)
+        .map(|_| match reader.u8()? {
+            BIT_ANONYMOUS => Ok(B::new_anonymous_bit(false)),
+            BIT_ANONYMOUS_ANCILLA => Ok(B::new_anonymous_bit(true)),
+            BIT_OWNED => {
+                let register = &owners[reader.index(owners.len(), "register")?];
+                let index = reader.u32()?;
+                match register.get(index as usize) {
+                    Some(bit) => Ok(bit),
+                    None => malformed(format!(
+                        "bit {index} is out of range for register '{}'",
+                        register.name()
+                    )),
+                }
+            }
+            tag => malformed(format!("invalid bit tag {tag}")),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let num_registers = reader.len()?;
+    let registers = (0..num_registers)
+        .map(|_| match reader.u8()? {
+            REGISTER_OWNING => Ok(owners[reader.index(owners.len(), "register")?].clone()),
+            REGISTER_ALIAS => {
+                let name = reader.str()?;
+                let ancilla = reader.u8()? != 0;
+                let len = reader.len()?;
+                let members = (0..len)
+                    .map(|_| Ok(bits[reader.index(bits.len(), "bit")?].clone()))
+                    .collect::<Result<Vec<_>, SerializationError>>()?;
+                match B::new_alias_register(name, members, ancilla) {
+                    Some(register) => Ok(register),
+                    None => malformed("an ancilla register contains non-ancilla bits"),
+                }
+            }
+            tag => malformed(format!("invalid register tag {tag}")),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((bits, registers))
+}
+
+const IDENTIFIER_VAR: u8 = 0;
+const IDENTIFIER_STRETCH: u8 = 1;
+
+fn write_type(out: &mut Writer, ty: Type) {
+    match ty {
+        Type::Bool => out.u8(0),
+        Type::Duration => out.u8(1),
+        Type::Float => out.u8(2),
+        Type::Uint(width) => {
+            out.u8(3);
+            out.u16(width);
+        }
+    }
+}
+
+fn read_type(reader: &mut Reader) -> Result<Type, SerializationError> {
+    match reader.u8()? {
+        0 => Ok(Type::Bool),
+        1 => Ok(Type::Duration),
+        2 => Ok(Type::Float),
+        3 => Ok(Type::Uint(reader.u16()?)),
+        tag => malformed(format!("invalid type tag {tag}")),
+    }
+}
+
+fn write_identifiers(out: &mut Writer, circuit: &CircuitData) -> Result<(), SerializationError> {
+    out.len(circuit.identifiers().len())?;
+    for identifier in circuit.identifiers() {
+        match identifier {
+            CircuitIdentifierInfo::Var(info) => {
+                let Some(expr::Var::Standalone { uuid, name, ty }) =
+                    circuit.get_var(info.get_var())
+                else {
+                    return Err(SerializationError::Unsupported(
+                        "a variable that wraps a bit or register".into(),
+                    ));
+                };
+                out.u8(IDENTIFIER_VAR);
+                out.u8(info.get_type() as u8);
+                out.u128(*uuid);
+                out.str(name)?;
+                write_type(out, *ty);
+            }
+            CircuitIdentifierInfo::Stretch(info) => {
+                let stretch = circuit
+                    .get_stretch(info.get_stretch())
+                    .expect("stretches in the identifier table are in the circuit");
+                out.u8(IDENTIFIER_STRETCH);
+                out.u8(info.get_type() as u8);
+                out.u128(stretch.uuid);
+                out.str(&stretch.name)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn read_identifiers(
+    reader: &mut Reader,
+    circuit: &mut CircuitData,
+) -> Result<(), SerializationError> {
+    let num_identifiers = reader.len()?;
+    for _ in 0..num_identifiers {
+        match reader.u8()? {
+            IDENTIFIER_VAR => {
+                let var_type = match reader.u8()? {
+                    0 => CircuitVarType::Input,
+                    1 => CircuitVarType::Capture,
+                    2 => CircuitVarType::Declare,
+                    tag => return malformed(format!("invalid variable kind {tag}")),
+                };
+                let uuid = reader.u128()?;
+                let name = reader.str()?;
+                let ty = read_type(reader)?;
+                circuit.add_var(expr::Var::Standalone { uuid, name, ty }, var_type)?;
+            }
+            IDENTIFIER_STRETCH => {
+                let stretch_type = match reader.u8()? {
+                    0 => CircuitStretchType::Capture,
+                    1 => CircuitStretchType::Declare,
+                    tag => return malformed(format!("invalid stretch kind {tag}")),
+                };
+                let uuid = reader.u128()?;
+                let name = reader.str()?;
+                circuit.add_stretch(expr::Stretch { uuid, name }, stretch_type)?;
+            }
+            tag => return malformed(format!("invalid identifier tag {tag}")),
+        }
+    }
+    Ok(())
+}
+
+const PARAM_FLOAT: u8 = 0;
+const PARAM_INT: u8 = 1;
+const PARAM_PARAMETER: u8 = 2;
+const PARAM_EXPRESSION: u8 = 3;
+
+const OP_STANDARD_GATE: u8 = 0;
+const OP_STANDARD_INSTRUCTION: u8 = 1;
+const OP_UNITARY: u8 = 2;
+
+const INSTRUCTION_BARRIER: u8 = 0;
+const INSTRUCTION_DELAY: u8 = 1;
+const INSTRUCTION_MEASURE: u8 = 2;
+const INSTRUCTION_RESET: u8 = 3;
+
+const SYMBOL_SYMBOL: u8 = 0;
+const SYMBOL_REAL: u8 = 1;
+const SYMBOL_INT: u8 = 2;
+const SYMBOL_COMPLEX: u8 = 3;
+const SYMBOL_UNARY: u8 = 4;
+const SYMBOL_BINARY: u8 = 5;
+
+const UNARY_OPS: [UnaryOp; 12] = [
+    UnaryOp::Abs,
+    UnaryOp::Neg,
+    UnaryOp::Sin,
+    UnaryOp::Asin,
+    UnaryOp::Cos,
+    UnaryOp::Acos,
+    UnaryOp::Tan,
+    UnaryOp::Atan,
+    UnaryOp::Exp,
+    UnaryOp::Log,
+    UnaryOp::Sign,
+    UnaryOp::Conj,
+];
+const BINARY_OPS: [BinaryOp; 5] = [
+    BinaryOp::Add,
+    BinaryOp::Sub,
+    BinaryOp::Mul,
+    BinaryOp::Div,
+    BinaryOp::Pow,
+];
+
+/// A symbolic `Parameter` in the parameter table.
+struct ParameterRecord {
+    name: String,
+    uuid: u128,
+    /// The index of the vector in the vector table and the index of the element in the vector.
+    vector: Option<(u32, u32)>,
+}
+
+/// A `ParameterVector` in the vector table.
+struct VectorRecord {
+    name: String,
+    len: u32,
+    root_uuid: u128,
+}
+
+/// The state needed to serialize the parameters of a circuit, which are collected into tables
+/// as they're encountered.
+#[derive(Default)]
+struct Encoder {
+    parameters: indexmap::IndexMap<u128, ParameterRecord, ::ahash::RandomState>,
+    vectors: indexmap::IndexMap<u128, VectorRecord, ::ahash::RandomState>,
+}
+
+impl Encoder {
+    fn instruction(
+        &mut self,
+        out: &mut Writer,
+        circuit: &CircuitData,
+        inst: &PackedInstruction,
+    ) -> Result<(), SerializationError> {
+        match inst.op.view() {
+            OperationRef::StandardGate(gate) => {
+                out.u8(OP_STANDARD_GATE);
+                out.u8(gate as u8);
+            }
+            OperationRef::StandardInstruction(instruction) => {
+                out.u8(OP_STANDARD_INSTRUCTION);
+                match instruction {
+                    StandardInstruction::Barrier(num_qubits) => {
+                        out.u8(INSTRUCTION_BARRIER);
+                        out.u32(num_qubits);
+                    }
+                    StandardInstruction::Delay(unit) => {
+                        out.u8(INSTRUCTION_DELAY);
+                        out.u8(unit as u8);
+                    }
+                    StandardInstruction::Measure => out.u8(INSTRUCTION_MEASURE),
+                    StandardInstruction::Reset => out.u8(INSTRUCTION_RESET),
+                }
+            }
+            OperationRef::Unitary(unitary) => {
+                let matrix = unitary.matrix(&[]).expect("unitaries always have a matrix");
+                out.u8(OP_UNITARY);
+                out.len(matrix.nrows())?;
+                for value in matrix.iter() {
+                    out.complex(*value);
+                }
+            }
+            OperationRef::Gate(_) | OperationRef::Instruction(_) | OperationRef::Operation(_) => {
+                return Err(SerializationError::Unsupported(format!(
+                    "the Python-space operation '{}'",
+                    inst.op.name()
+                )));
+            }
+        }
+        let qubits = circuit.get_qargs(inst.qubits);
+        out.len(qubits.len())?;
+        for qubit in qubits {
+            out.u32(qubit.0);
+        }
+        let clbits = circuit.get_cargs(inst.clbits);
+        out.len(clbits.len())?;
+        for clbit in clbits {
+            out.u32(clbit.0);
+        }
+        let params = inst.params_view();
+        out.len(params.len())?;
+        for param in params {
+            self.param(out, param)?;
+        }
+        match inst.label.as_deref() {
+            Some(label) => {
+                out.u8(1);
+                out.str(label)?;
+            }
+            None => out.u8(0),
+        }
+        Ok(())
+    }
+
+    fn param(&mut self, out: &mut Writer, param: &Param) -> Result<(), SerializationError> {
+        match param {
+            Param::Float(value) => {
+                out.u8(PARAM_FLOAT);
+                out.f64(*value);
+                Ok(())
+            }
+            Param::Obj(ob) => Python::with_gil(|py| {
+                let ob = ob.bind(py);
+                if !ob.is_exact_instance_of::<PyInt>() {
+                    return Err(SerializationError::Unsupported(format!(
+                        "a parameter of type '{}'",
+                        ob.get_type().name()?
+                    )));
+                }
+                out.u8(PARAM_INT);
+                out.i64(ob.extract()?);
+                Ok(())
+            }),
+            Param::ParameterExpression(ob) => Python::with_gil(|py| {
+                let ob = ob.bind(py);
+                if ob.is_instance(PARAMETER.get_bound(py))? {
+                    out.u8(PARAM_PARAMETER);
+                    out.u32(self.parameter(ob)?);
+                    return Ok(());
+                }
+                let mut symbols = HashMap::new();
+                for parameter in ob
+                    .getattr(intern!(py, "_parameter_symbols"))?
+                    .downcast::<PyDict>()
+                    .map_err(PyErr::from)?
+                    .keys()
+                {
+                    let name = parameter
+                        .getattr(intern!(py, "name"))?
+                        .extract::<String>()?;
+                    symbols.insert(name, self.parameter(&parameter)?);
+                }
+                let expr = ob
+                    .getattr(intern!(py, "_symbol_expr"))?
+                    .extract::<ParameterExpression>()?;
+                out.u8(PARAM_EXPRESSION);
+                write_symbol(out, expr.expr(), &symbols)
+            }),
+            Param::Symbolic(expr) => {
+                if let Some(symbol) = expr.as_symbol() {
+                    out.u8(PARAM_PARAMETER);
+                    out.u32(self.symbol(symbol));
+                    return Ok(());
+                }
+                let symbols = expr
+                    .symbols()
+                    .map(|symbol| (symbol.name().to_owned(), self.symbol(symbol)))
+                    .collect::<HashMap<_, _>>();
+                out.u8(PARAM_EXPRESSION);
+                write_symbol(out, expr.expr(), &symbols)
+            }
+        }
+    }
+
+    /// Get the index of a Rust-space [ParameterSymbol] in the parameter table, adding it if needed.
+    fn symbol(&mut self, symbol: &ParameterSymbol) -> u32 {
+        if let Some(index) = self.parameters.get_index_of(&symbol.uuid()) {
+            return index as u32;
+        }
+        let vector = symbol.vector().map(|(vector, index)| {
+            let entry = self.vectors.entry(vector.root_uuid());
+            let vector_index = entry.index() as u32;
+            entry.or_insert(VectorRecord {
+                name: vector.name().to_owned(),
+                len: vector.len() as u32,
+                root_uuid: vector.root_uuid(),
+            });
+            (vector_index, index as u32)
+        });
+        let (index, _) = self.parameters.insert_full(
+            symbol.uuid(),
+            ParameterRecord {
+                name: symbol.name().to_owned(),
+                uuid: symbol.uuid(),
+                vector,
+            },
+        );
+        index as u32
+    }
+
+    /// Get the index of a Python-space `Parameter` in the parameter table, adding it if needed.
+    fn parameter(&mut self, ob: &Bound<PyAny>) -> Result<u32, SerializationError> {
+        let py = ob.py();
+        let uuid = ob
+            .getattr(intern!(py, "_uuid"))?
+            .getattr(intern!(py, "int"))?
+            .extract::<u128>()?;
+        if let Some(index) = self.parameters.get_index_of(&uuid) {
+            return Ok(index as u32);
+        }
+        let vector = if ob.is_instance(PARAMETER_VECTOR_ELEMENT.get_bound(py))? {
+            let vector = ob.getattr(intern!(py, "vector"))?;
+            let root_uuid = vector
+                .getattr(intern!(py, "_root_uuid"))?
+                .getattr(intern!(py, "int"))?
+                .extract::<u128>()?;
+            let entry = self.vectors.entry(root_uuid);
+            let vector_index = entry.index() as u32;
+            entry.or_insert(VectorRecord {
+                name: vector.getattr(intern!(py, "name"))?.extract()?,
+                len: vector.len()? as u32,
+                root_uuid,
+            });
+            Some((vector_index, ob.getattr(intern!(py, "index"))?.extract()?))
+        } else {
+            None
+        };
+        let (index, _) = self.parameters.insert_full(
+            uuid,
+            ParameterRecord {
+                name: ob.getattr(intern!(py, "name"))?.extract()?,
+                uuid,
+                vector,
+            },
+        );
+        Ok(index as u32)
+    }
+
+    fn parameter_tables(&self, out: &mut Writer) -> Result<(), SerializationError> {
+        out.len(self.vectors.len())?;
+        for vector in self.vectors.values() {
+            out.str(&vector.name)?;
+            out.u32(vector.len);
+            out.u128(vector.root_uuid);
+        }
+        out.len(self.parameters.len())?;
+        for parameter in self.parameters.values() {
+            out.str(&parameter.name)?;
+            out.u128(parameter.uuid);
+            match parameter.vector {
+                Some((vector, index)) => {
+                    out.u8(1);
+                    out.u32(vector);
+                    out.u32(index);
+                }
+                None => out.u8(0),
+            }
+        }
+        Ok(())
+    }
+}
+
+fn write_symbol(
+    out: &mut Writer,
+    expr: &SymbolExpr,
+    symbols: &HashMap<String, u32>,
+) -> Result<(), SerializationError> {
+    match expr {
+        SymbolExpr::Symbol(name) => {
+            let Some(index) = symbols.get(name.as_str()) else {
+                return Err(SerializationError::Unsupported(format!(
+                    "an expression containing the untracked symbol '{name}'"
+                )));
+            };
+            out.u8(SYMBOL_SYMBOL);
+            out.u32(*index);
+        }
+        SymbolExpr::Value(Value::Real(value)) => {
+            out.u8(SYMBOL_REAL);
+            out.f64(*value);
+        }
+        SymbolExpr::Value(Value::Int(value)) => {
+            out.u8(SYMBOL_INT);
+            out.i64(*value);
+        }
+        SymbolExpr::Value(Value::Complex(value)) => {
+            out.u8(SYMBOL_COMPLEX);
+            out.complex(*value);
+        }
+        SymbolExpr::Unary { op, expr } => {
+            out.u8(SYMBOL_UNARY);
+            out.u8(UNARY_OPS.iter().position(|other| other == op).unwrap() as u8);
+            write_symbol(out, expr, symbols)?;
+        }
+        SymbolExpr::Binary { op, lhs, rhs } => {
+            out.u8(SYMBOL_BINARY);
+            out.u8(BINARY_OPS.iter().position(|other| other == op).unwrap() as u8);
+            write_symbol(out, lhs, symbols)?;
+            write_symbol(out, rhs, symbols)?;
+        }
+    }
+    Ok(())
+}
+
+/// The state needed to deserialize the parameters of a circuit.
+struct Decoder {
+    /// The Rust-space symbols, in the order of the parameter table.  Records with the same UUID
+    /// share a single symbol.
+    parameters: Vec<Arc<ParameterSymbol>>,
+}
+
+impl Decoder {
+    fn read_parameter_tables(reader: &mut Reader) -> Result<Self, SerializationError> {
+        let num_vectors = reader.len()?;
+        let vectors = (0..num_vectors)
+            .map(|_| {
+                let name = reader.str()?;
+                let len = reader.u32()?;
+                let root_uuid = reader.u128()?;
+                Ok(Arc::new(SymbolVector::new(name, root_uuid, len as usize)))
+            })
+            .collect::<Result<Vec<_>, SerializationError>>()?;
+        let num_parameters = reader.len()?;
+        let mut by_uuid: HashMap<u128, Arc<ParameterSymbol>> =
+            HashMap::with_capacity(num_parameters);
+        let mut by_name: HashMap<String, u128> = HashMap::with_capacity(num_parameters);
+        let parameters = (0..num_parameters)
+            .map(|_| {
+                let name = reader.str()?;
+                let uuid = reader.u128()?;
+                let symbol = match reader.u8()? {
+                    0 => ParameterSymbol::new(name, uuid),
+                    1 => {
+                        let vector = &vectors[reader.index(vectors.len(), "parameter vector")?];
+                        let index = reader.u32()?;
+                        if index as usize >= vector.len() {
+                            return malformed(format!(
+                                "element {index} is out of range for vector '{}'",
+                                vector.name()
+                            ));
+                        }
+                        ParameterSymbol::new_vector_element(vector.clone(), index as usize, uuid)
+                    }
+                    tag => return malformed(format!("invalid parameter tag {tag}")),
+                };
+                if let Some(existing) = by_uuid.get(&uuid) {
+                    return Ok(existing.clone());
+                }
+                if by_name.insert(symbol.name().to_owned(), uuid).is_some() {
+                    return malformed(format!(
+                        "distinct parameters are both named '{}'",
+                        symbol.name()
+                    ));
+                }
+                let symbol = Arc::new(symbol);
+                by_uuid.insert(uuid, symbol.clone());
+                Ok(symbol)
+            })
+            .collect::<Result<Vec<_>, SerializationError>>()?;
+        Ok(Self { parameters })
+    }
+
+    fn param(&mut self, reader: &mut Reader) -> Result<Param, SerializationError> {
+        match reader.u8()? {
+            PARAM_FLOAT => Ok(Param::Float(reader.f64()?)),
+            // Integers are only written for parameters that were Python-space `int`s, such as
+            // the durations of delays in `dt`.  They can only be loaded as `int`s while Python is
+            // running; otherwise they are loaded as the float of the same value.
+            PARAM_INT => {
+                let value = reader.i64()?;
+                if python_is_initialized() {
+                    Python::with_gil(|py| Ok(Param::Obj(value.into_py_any(py)?)))
+                } else {
+                    Ok(Param::Float(value as f64))
+                }
+            }
+            PARAM_PARAMETER => {
+                let index = reader.index(self.parameters.len(), "parameter")?;
+                Ok(Param::Symbolic(Arc::new(SymbolicExpression::from_symbol(
+                    self.parameters[index].clone(),
+                ))))
+            }
+            PARAM_EXPRESSION => {
+                let mut used = IndexSet::new();
+                let expr = self.symbol(reader, &mut used)?;
+                let symbols = used
+                    .into_iter()
+                    .map(|index| self.parameters[index].clone())
+                    .collect::<Vec<_>>();
+                let expr = SymbolicExpression::new(expr, symbols)
+                    .or_else(|err| malformed(err.to_string()))?;
+                Ok(Param::Symbolic(Arc::new(expr)))
+            }
+            tag => malformed(format!("invalid parameter tag {tag}")),
+        }
+    }
+
+    fn symbol(
+        &self,
+        reader: &mut Reader,
+        used: &mut IndexSet<usize>,
+    ) -> Result<SymbolExpr, SerializationError> {
+        match reader.u8()? {
+            SYMBOL_SYMBOL => {
+                let index = reader.index(self.parameters.len(), "parameter")?;
+                used.insert(index);
+                Ok(SymbolExpr::Symbol(Arc::new(
+                    self.parameters[index].name().to_owned(),
+                )))
+            }
+            SYMBOL_REAL => Ok(SymbolExpr::Value(Value::Real(reader.f64()?))),
+            SYMBOL_INT => Ok(SymbolExpr::Value(Value::Int(reader.i64()?))),
+            SYMBOL_COMPLEX => Ok(SymbolExpr::Value(Value::Complex(reader.complex()?))),
+            SYMBOL_UNARY => {
+                let op = UNARY_OPS
+                    .get(reader.u8()? as usize)
+                    .cloned()
+                    .ok_or_else(|| SerializationError::Malformed("invalid unary op".into()))?;
+                Ok(SymbolExpr::Unary {
+                    op,
+                    expr: Arc::new(self.symbol(reader, used)?),
+                })
+            }
+            SYMBOL_BINARY => {
+                let op = BINARY_OPS
+                    .get(reader.u8()? as usize)
+                    .cloned()
+                    .ok_or_else(|| SerializationError::Malformed("invalid binary op".into()))?;
+                Ok(SymbolExpr::Binary {
+                    op,
+                    lhs: Arc::new(self.symbol(reader, used)?),
+                    rhs: Arc::new(self.symbol(reader, used)?),
+                })
+            }
+            tag => malformed(format!("invalid expression tag {tag}")),
+        }
+    }
+
+    fn instruction(
+        &mut self,
+        reader: &mut Reader,
+        circuit: &mut CircuitData,
+    ) -> Result<PackedInstruction, SerializationError> {
+        let op: PackedOperation = match reader.u8()? {
+            OP_STANDARD_GATE => {
+                let tag = reader.u8()?;
+                match ::bytemuck::checked::try_cast::<u8, StandardGate>(tag) {
+                    Ok(gate) => gate.into(),
+                    Err(_) => return malformed(format!("invalid standard gate {tag}")),
+                }
+            }
+            OP_STANDARD_INSTRUCTION => match reader.u8()? {
+                INSTRUCTION_BARRIER => StandardInstruction::Barrier(reader.u32()?).into(),
+                INSTRUCTION_DELAY => {
+                    let tag = reader.u8()?;
+                    match ::bytemuck::checked::try_cast::<u8, DelayUnit>(tag) {
+                        Ok(unit) => StandardInstruction::Delay(unit).into(),
+                        Err(_) => return malformed(format!("invalid delay unit {tag}")),
+                    }
+                }
+                INSTRUCTION_MEASURE => StandardInstruction::Measure.into(),
+                INSTRUCTION_RESET => StandardInstruction::Reset.into(),
+                tag => return malformed(format!("invalid standard instruction {tag}")),
+            },
+            OP_UNITARY => {
+                let dim = reader.len()?;
+                if dim < 2 || !dim.is_power_of_two() {
+                    return malformed(format!("invalid unitary dimension {dim}"));
+                }
+                // Check the matrix fits in the data before allocating space for it.
+                if dim
+                    .checked_mul(dim)
+                    .and_then(|len| len.checked_mul(16))
+                    .map_or(true, |bytes| bytes > reader.remaining())
+                {
+                    return Err(SerializationError::UnexpectedEnd);
+                }
+                let values = (0..dim * dim)
+                    .map(|_| reader.complex())
+                    .collect::<Result<Vec<_>, _>>()?;
+                let matrix = Array2::from_shape_vec((dim, dim), values).unwrap();
+                let array = match dim {
+                    2 => ArrayType::OneQ(nalgebra::Matrix2::from_fn(|i, j| matrix[[i, j]])),
+                    4 => ArrayType::TwoQ(nalgebra::Matrix4::from_fn(|i, j| matrix[[i, j]])),
+                    _ => ArrayType::NDArray(matrix),
+                };
+                Box::new(UnitaryGate { array }).into()
+            }
+            tag => return malformed(format!("invalid operation tag {tag}")),
+        };
+        let qubits = reader
+            .u32s()?
+            .into_iter()
+            .map(|qubit| {
+                if qubit as usize >= circuit.num_qubits() {
+                    return malformed(format!("qubit {qubit} is out of range"));
+                }
+                Ok(Qubit(qubit))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let clbits = reader
+            .u32s()?
+            .into_iter()
+            .map(|clbit| {
+                if clbit as usize >= circuit.num_clbits() {
+                    return malformed(format!("clbit {clbit} is out of range"));
+                }
+                Ok(Clbit(clbit))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let num_params = reader.len()?;
+        let params = (0..num_params)
+            .map(|_| self.param(reader))
+            .collect::<Result<SmallVec<[Param; 3]>, _>>()?;
+        let label = match reader.u8()? {
+            0 => None,
+            1 => Some(Box::new(reader.str()?)),
+            tag => return malformed(format!("invalid label tag {tag}")),
+        };
+        check_arity(&op, &qubits, clbits.len(), params.len())?;
+        Ok(PackedInstruction {
+            op,
+            qubits: circuit.add_qargs(&qubits),
+            clbits: circuit.add_cargs(&clbits),
+            params: (!params.is_empty()).then(|| Box::new(params)),
+            label,
+            #[cfg(feature = "cache_pygates")]
+            py_op: std::sync::OnceLock::new(),
+        })
+    }
+}
+
+/// Check that the operands of a decoded instruction match what its operation expects, so that
+/// malformed data can't produce an inconsistent circuit.
+fn check_arity(
+    op: &PackedOperation,
+    qubits: &[Qubit],
+    num_clbits: usize,
+    num_params: usize,
+) -> Result<(), SerializationError> {
+    let name = op.name();
+    if op.num_qubits() as usize != qubits.len() {
+        return malformed(format!(
+            "'{name}' acts on {} qubits, but {} were given",
+            op.num_qubits(),
+            qubits.len()
+        ));
+    }
+    if op.num_clbits() as usize != num_clbits {
+        return malformed(format!(
+            "'{name}' acts on {} clbits, but {num_clbits} were given",
+            op.num_clbits()
+        ));
+    }
+    // A delay carries its duration as its only parameter.
+    let expected_params = match op.view() {
+        OperationRef::StandardInstruction(StandardInstruction::Delay(_)) => 1,
+        _ => op.num_params() as usize,
+    };
+    if expected_params != num_params {
+        return malformed(format!(
+            "'{name}' takes {expected_params} parameters, but {num_params} were given"
+        ));
+    }
+    let mut seen = HashSet::with_capacity(qubits.len());
+    if let Some(qubit) = qubits.iter().find(|qubit| !seen.insert(**qubit)) {
+        return malformed(format!("qubit {} is repeated in '{name}'", qubit.0));
+    }
+    Ok(())
+}
+
+fn write_layout(
+    out: &mut Writer,
+    layout: Option<&TranspileLayout>,
+) -> Result<(), SerializationError> {
+    let Some(layout) = layout else {
+        out.u8(0);
+        return Ok(());
+    };
+    out.u8(1);
+    out.len(layout.num_output_qubits())?;
+    for (_, phys) in layout.initial_layout().iter_virtual() {
+        out.u32(phys.0);
+    }
+    out.len(layout.num_input_qubits())?;
+    for virt in layout.input_qubit_mapping() {
+        out.u32(virt.0);
+    }
+    for phys in layout.routing_permutation() {
+        out.u32(phys.0);
+    }
+    Ok(())
+}
+
+fn read_layout(reader: &mut Reader) -> Result<Option<TranspileLayout>, SerializationError> {
+    match reader.u8()? {
+        0 => return Ok(None),
+        1 => (),
+        tag => return malformed(format!("invalid layout tag {tag}")),
+    }
+    let virt_to_phys = reader.u32s()?;
+    let num_qubits = virt_to_phys.len();
+    let mut phys_to_virt = vec![VirtualQubit(u32::MAX); num_qubits];
+    for (virt, phys) in virt_to_phys.iter().enumerate() {
+        match phys_to_virt.get_mut(*phys as usize) {
+            Some(slot) if slot.0 == u32::MAX => *slot = VirtualQubit(virt as u32),
+            _ => return malformed("the initial layout is not a permutation"),
+        }
+    }
+    let initial_layout = NLayout::from_vecs_unchecked(
+        virt_to_phys.into_iter().map(PhysicalQubit).collect(),
+        phys_to_virt,
+    );
+    let input_qubit_mapping = reader.u32s()?.into_iter().map(VirtualQubit).collect();
+    let routing_permutation = (0..num_qubits)
+        .map(|_| reader.u32().map(PhysicalQubit))
+        .collect::<Result<Vec<_>, _>>()?;
+    TranspileLayout::new(
+        initial_layout,
+        input_qubit_mapping,
+        Some(routing_permutation),
+    )
+    .map(Some)
+    .or_else(|err| malformed(err.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bit::ShareableQubit;
+
+    /// Anonymous bits are given fresh identities on load, so compare bits and registers by their
+    /// structure and their positions in the circuit.
+    fn assert_bits_match<B: SerializableBit>(
+        (left, left_regs): (&[B], &[B::Reg]),
+        (right, right_regs): (&[B], &[B::Reg]),
+    ) {
+        assert_eq!(left.len(), right.len());
+        for (left, right) in left.iter().zip(right) {
+            assert_eq!(left.owning_register(), right.owning_register());
+            assert_eq!(left.is_ancilla_bit(), right.is_ancilla_bit());
+        }
+        let positions = |bits: &[B], register: &B::Reg| {
+            register
+                .bits()
+                .map(|bit| bits.iter().position(|other| *other == bit))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(left_regs.len(), right_regs.len());
+        for (l, r) in left_regs.iter().zip(right_regs) {
+            assert_eq!(l.name(), r.name());
+            assert_eq!(B::is_owning_register(l), B::is_owning_register(r));
+            assert_eq!(B::is_ancilla_register(l), B::is_ancilla_register(r));
+            assert_eq!(positions(left, l), positions(right, r));
+        }
+    }
+
+    fn assert_params_eq(left: &[Param], right: &[Param]) {
+        assert_eq!(left.len(), right.len());
+        for (left, right) in left.iter().zip(right) {
+            match (left, right) {
+                (Param::Float(left), Param::Float(right)) => assert_eq!(left, right),
+                (Param::Symbolic(left), Param::Symbolic(right)) => assert_eq!(left, right),
+                _ => panic!("only float and Rust-space symbolic parameters are tested"),
+            }
+        }
+    }
+
+    fn assert_round_trip(circuit: &CircuitData) -> CircuitData {
+        let bytes = dump(circuit).unwrap();
+        let loaded = load(&bytes).unwrap();
+        assert_bits_match(
+            (loaded.qubits().objects(), loaded.qregs()),
+            (circuit.qubits().objects(), circuit.qregs()),
+        );
+        assert_bits_match(
+            (loaded.clbits().objects(), loaded.cregs()),
+            (circuit.clbits().objects(), circuit.cregs()),
+        );
+        assert_eq!(loaded.layout(), circuit.layout());
+        assert_eq!(loaded.data().len(), circuit.data().len());
+        for (left, right) in loaded.data().iter().zip(circuit.data()) {
+            assert_eq!(left.op.name(), right.op.name());
+            assert_eq!(
+                loaded.get_qargs(left.qubits),
+                circuit.get_qargs(right.qubits)
+            );
+            assert_eq!(
+                loaded.get_cargs(left.clbits),
+                circuit.get_cargs(right.clbits)
+            );
+            assert_eq!(left.label, right.label);
+            assert_params_eq(left.params_view(), right.params_view());
+        }
+        assert_params_eq(
+            std::slice::from_ref(loaded.global_phase()),
+            std::slice::from_ref(circuit.global_phase()),
+        );
+        // The format should be deterministic.
+        assert_eq!(dump(&loaded).unwrap(), bytes);
+        loaded
+    }
+
+    #[test]
+    fn test_round_trip_standard_operations() {
+        let mut circuit = CircuitData::with_capacity(3, 2, 0, Param::Float(0.25)).unwrap();
+        circuit.push_standard_gate(StandardGate::H, &[], &[Qubit(0)]);
+        circuit.push_standard_gate(StandardGate::CX, &[], &[Qubit(0), Qubit(2)]);
+        circuit.push_standard_gate(
+            StandardGate::U,
+            &[Param::Float(0.1), Param::Float(0.2), Param::Float(0.3)],
+            &[Qubit(1)],
+        );
+        circuit.push_packed_operation(
+            StandardInstruction::Barrier(3).into(),
+            &[],
+            &[Qubit(0), Qubit(1), Qubit(2)],
+            &[],
+        );
+        circuit.push_packed_operation(
+            StandardInstruction::Delay(DelayUnit::NS).into(),
+            &[Param::Float(100.0)],
+            &[Qubit(1)],
+            &[],
+        );
+        circuit.push_packed_operation(
+            StandardInstruction::Measure.into(),
+            &[],
+            &[Qubit(2)],
+            &[Clbit(1)],
+        );
+        circuit.push_packed_operation(StandardInstruction::Reset.into(), &[], &[Qubit(0)], &[]);
+        let mut labelled = PackedInstruction::from_standard_gate(
+            StandardGate::X,
+            None,
+            circuit.add_qargs(&[Qubit(1)]),
+        );
+        labelled.label = Some(Box::new("flip".to_string()));
+        circuit.push(labelled).unwrap();
+
+        let loaded = assert_round_trip(&circuit);
+        assert!(matches!(loaded.global_phase(), Param::Float(0.25)));
+    }
+
+    #[test]
+    fn test_round_trip_symbolic_parameters() {
+        let theta = Arc::new(ParameterSymbol::new_unique("theta".to_string()));
+        let vector = Arc::new(SymbolVector::new("x".to_string(), 1 << 64, 2));
+        let element = Arc::new(ParameterSymbol::new_vector_element(
+            vector.clone(),
+            1,
+            (1 << 64) + 1,
+        ));
+        let symbol =
+            |symbol: &Arc<ParameterSymbol>| SymbolicExpression::from_symbol(symbol.clone());
+        let expr = symbol(&theta)
+            .binary_op(&symbol(&element), |lhs, rhs| {
+                lhs * &SymbolExpr::Value(Value::Int(2)) + rhs.clone()
+            })
+            .unwrap();
+
+        let mut circuit =
+            CircuitData::with_capacity(2, 0, 0, Param::Symbolic(Arc::new(symbol(&element))))
+                .unwrap();
+        circuit.push_standard_gate(
+            StandardGate::RZ,
+            &[Param::Symbolic(Arc::new(symbol(&theta)))],
+            &[Qubit(0)],
+        );
+        circuit.push_standard_gate(
+            StandardGate::RZZ,
+            &[Param::Symbolic(Arc::new(expr))],
+            &[Qubit(0), Qubit(1)],
+        );
+
+        let loaded = assert_round_trip(&circuit);
+        assert_eq!(loaded.num_parameters(), 2);
+        let Param::Symbolic(phase) = loaded.global_phase() else {
+            panic!("the global phase should be symbolic");
+        };
+        let loaded_element = phase.as_symbol().unwrap();
+        assert_eq!(loaded_element.uuid(), element.uuid());
+        assert_eq!(loaded_element.name(), "x[1]");
+        let (loaded_vector, index) = loaded_element.vector().unwrap();
+        assert_eq!(index, 1);
+        assert_eq!(loaded_vector.name(), "x");
+        assert_eq!(loaded_vector.root_uuid(), vector.root_uuid());
+        assert_eq!(loaded_vector.len(), 2);
+        // Every use of a parameter refers to the one symbol with its UUID.
+        let Param::Symbolic(expr) = &loaded.data()[1].params_view()[0] else {
+            panic!("the parameter should be symbolic");
+        };
+        assert!(expr
+            .symbols()
+            .any(|symbol| Arc::ptr_eq(symbol, loaded_element)));
+    }
+
+    #[test]
+    fn test_round_trip_unitary() {
+        let mut circuit = CircuitData::with_capacity(3, 0, 0, Param::Float(0.0)).unwrap();
+        let i = Complex64::new(0.0, 1.0);
+        let one = Complex64::new(1.0, 0.0);
+        let zero = Complex64::new(0.0, 0.0);
+        circuit.push_packed_operation(
+            Box::new(UnitaryGate {
+                array: ArrayType::OneQ(nalgebra::Matrix2::new(zero, i, i, zero)),
+            })
+            .into(),
+            &[],
+            &[Qubit(0)],
+            &[],
+        );
+        circuit.push_packed_operation(
+            Box::new(UnitaryGate {
+                array: ArrayType::NDArray(Array2::from_diag_elem(8, one)),
+            })
+            .into(),
+            &[],
+            &[Qubit(0), Qubit(1), Qubit(2)],
+            &[],
+        );
+        let loaded = assert_round_trip(&circuit);
+        for (left, right) in loaded.data().iter().zip(circuit.data()) {
+            assert_eq!(left.op.matrix(&[]), right.op.matrix(&[]));
+        }
+    }
+
+    #[test]
+    fn test_round_trip_registers() {
+        let mut circuit = CircuitData::with_capacity(0, 0, 0, Param::Float(0.0)).unwrap();
+        let qr = QuantumRegister::new_owning("qr", 3);
+        let anc = QuantumRegister::new_ancilla_owning("anc".to_string(), 1);
+        let cr = ClassicalRegister::new_owning("cr", 2);
+        circuit.add_qreg(qr.clone(), true).unwrap();
+        circuit.add_qreg(anc, true).unwrap();
+        circuit
+            .add_qubit(ShareableQubit::new_anonymous(), true)
+            .unwrap();
+        circuit.add_creg(cr, true).unwrap();
+        let alias = QuantumRegister::new_alias(
+            Some("alias".to_string()),
+            vec![qr.get(2).unwrap(), circuit.qubits().objects()[4].clone()],
+        );
+        circuit.add_qreg(alias, true).unwrap();
+        // A bit owned by a register that is not itself in the circuit.
+        let loose = QuantumRegister::new_owning("loose", 2);
+        circuit.add_qubit(loose.get(1).unwrap(), true).unwrap();
+        circuit.push_standard_gate(StandardGate::CZ, &[], &[Qubit(3), Qubit(5)]);
+
+        let loaded = assert_round_trip(&circuit);
+        assert!(loaded.qubits().objects()[3].is_ancilla());
+    }
+
+    #[test]
+    fn test_round_trip_identifiers() {
+        let mut circuit = CircuitData::with_capacity(1, 0, 0, Param::Float(0.0)).unwrap();
+        let a = expr::Var::Standalone {
+            uuid: 1,
+            name: "a".to_string(),
+            ty: Type::Uint(8),
+        };
+        let b = expr::Var::Standalone {
+            uuid: 2,
+            name: "b".to_string(),
+            ty: Type::Bool,
+        };
+        let s = expr::Stretch {
+            uuid: 3,
+            name: "s".to_string(),
+        };
+        circuit.add_var(a.clone(), CircuitVarType::Input).unwrap();
+        circuit
+            .add_stretch(s.clone(), CircuitStretchType::Declare)
+            .unwrap();
+        circuit.add_var(b.clone(), CircuitVarType::Declare).unwrap();
+
+        let loaded = assert_round_trip(&circuit);
+        assert_eq!(
+            loaded.identifiers().collect::<Vec<_>>(),
+            circuit.identifiers().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            loaded.get_vars(CircuitVarType::Input).collect::<Vec<_>>(),
+            vec![&a]
+        );
+        assert_eq!(
+            loaded.get_vars(CircuitVarType::Declare).collect::<Vec<_>>(),
+            vec![&b]
+        );
+        assert_eq!(
+            loaded
+                .get_stretches(CircuitStretchType::Declare)
+                .collect::<Vec<_>>(),
+            vec![&s]
+        );
+    }
+
+    #[test]
+    fn test_round_trip_layout() {
+        let mut circuit = CircuitData::with_capacity(3, 0, 0, Param::Float(0.0)).unwrap();
+        circuit.push_standard_gate(StandardGate::Swap, &[], &[Qubit(0), Qubit(1)]);
+        let layout = TranspileLayout::new(
+            NLayout::from_virtual_to_physical([2, 0, 1].into_iter().map(PhysicalQubit).collect())
+                .unwrap(),
+            vec![VirtualQubit(1), VirtualQubit(0)],
+            Some([1, 0, 2].into_iter().map(PhysicalQubit).collect()),
+        )
+        .unwrap();
+        circuit.set_layout(Some(layout));
+        assert_round_trip(&circuit);
+    }
+
+    #[test]
+    fn test_invalid_data() {
+        let circuit = CircuitData::with_capacity(2, 1, 0, Param::Float(0.0)).unwrap();
+        let bytes = dump(&circuit).unwrap();
+        assert!(matches!(load(b"nope"), Err(SerializationError::BadMagic)));
+        let mut future = bytes.clone();
+        future[4..6].copy_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+        assert!(matches!(
+            load(&future),
+            Err(SerializationError::UnsupportedVersion(_))
+        ));
+        assert!(matches!(
+            load(&bytes[..bytes.len() - 1]),
+            Err(SerializationError::UnexpectedEnd)
+        ));
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(matches!(
+            load(&trailing),
+            Err(SerializationError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn test_mismatched_operands() {
+        // `CircuitData` does not validate operands on push, so write inconsistent instructions and
+        // check they're rejected on load.
+        let assert_rejected =
+            |op: PackedOperation, params: &[Param], qubits: &[u32], clbits: &[u32]| {
+                let mut circuit = CircuitData::with_capacity(3, 1, 0, Param::Float(0.0)).unwrap();
+                let qubits = qubits.iter().map(|q| Qubit(*q)).collect::<Vec<_>>();
+                let clbits = clbits.iter().map(|c| Clbit(*c)).collect::<Vec<_>>();
+                circuit.push_packed_operation(op, params, &qubits, &clbits);
+                let bytes = dump(&circuit).unwrap();
+                assert!(matches!(
+                    load(&bytes),
+                    Err(SerializationError::Malformed(_))
+                ));
+            };
+        assert_rejected(StandardGate::CX.into(), &[], &[0], &[]);
+        assert_rejected(StandardGate::H.into(), &[Param::Float(1.0)], &[0], &[]);
+        assert_rejected(StandardGate::RZ.into(), &[], &[0], &[]);
+        assert_rejected(StandardGate::CZ.into(), &[], &[1, 1], &[]);
+        assert_rejected(StandardInstruction::Barrier(3).into(), &[], &[0, 1], &[]);
+        assert_rejected(
+            StandardInstruction::Delay(DelayUnit::DT).into(),
+            &[],
+            &[0],
+            &[],
+        );
+        assert_rejected(StandardInstruction::Measure.into(), &[], &[0], &[]);
+        assert_rejected(StandardInstruction::Reset.into(), &[], &[0], &[0]);
+        assert_rejected(
+            Box::new(UnitaryGate {
+                array: ArrayType::NDArray(Array2::from_diag_elem(4, Complex64::new(1.0, 0.0))),
+            })
+            .into(),
+            &[],
+            &[0, 1, 2],
+            &[],
+        );
+        assert_rejected(
+            Box::new(UnitaryGate {
+                array: ArrayType::NDArray(Array2::from_diag_elem(1, Complex64::new(1.0, 0.0))),
+            })
+            .into(),
+            &[],
+            &[],
+            &[],
+        );
+    }
+}
@@ -0,0 +1,356 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2025
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+use hashbrown::HashSet;
+use pyo3::exceptions::PyValueError;
+use pyo3::PyErr;
+use thiserror::Error;
+
+use crate::nlayout::{NLayout, PhysicalQubit, VirtualQubit};
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum TranspileLayoutError {
+    #[error("input qubit {input} is mapped to virtual qubit {virt}, but the layout only has {num_qubits} qubits")]
+    InputOutOfRange {
+        input: usize,
+        virt: u32,
+        num_qubits: usize,
+    },
+    #[error("virtual qubit {0} is the image of more than one input qubit")]
+    DuplicateInput(u32),
+    #[error("the routing permutation is not a permutation of {0} qubits")]
+    InvalidPermutation(usize),
+    #[error(
+        "cannot compose a layout with {expected} output qubits with one with {actual} input qubits"
+    )]
+    ComposeMismatch { expected: usize, actual: usize },
+}
+impl From<TranspileLayoutError> for PyErr {
+    fn from(value: TranspileLayoutError) -> PyErr {
+        PyValueError::new_err(value.to_string())
+    }
+}
+
+/// The full record of how the transpiler laid out and permuted the qubits of a circuit.
+///
+/// This is the Rust-space counterpart to the Python `TranspileLayout`.  There are three spaces of
+/// qubit indices involved:
+///
+/// * the "input" qubits, which are the qubits of the circuit that was passed to the transpiler;
+/// * the "virtual" qubits, which are the input qubits plus any ancillas that were added to fill
+///   the device, and which are what the [NLayout] maps from;
+/// * the "physical" qubits, which are the qubits of the output circuit.
+///
+/// The routing permutation is expressed on the physical qubits: entry `p` is the physical qubit
+/// that the state that starts the output circuit on physical qubit `p` ends on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TranspileLayout {
+    initial_layout: NLayout,
+    input_qubit_mapping: Vec<VirtualQubit>,
+    routing_permutation: Vec<PhysicalQubit>,
+}
+
+impl TranspileLayout {
+    /// Create a new layout, checking the coherence of the inputs.
+    ///
+    /// # Arguments
+    ///
+    /// * `initial_layout`: the mapping of virtual qubits to physical qubits at the start of the
+    ///   output circuit.
+    /// * `input_qubit_mapping`: the virtual qubit that each qubit of the input circuit became.
+    ///   Virtual qubits that are not the image of an input qubit are ancillas.
+    /// * `routing_permutation`: the permutation of the physical qubits introduced by routing,
+    ///   or `None` if the output circuit does not permute its qubits.
+    pub fn new(
+        initial_layout: NLayout,
+        input_qubit_mapping: Vec<VirtualQubit>,
+        routing_permutation: Option<Vec<PhysicalQubit>>,
+    ) -> Result<Self, TranspileLayoutError> {
+        let num_qubits = initial_layout.num_qubits();
+        let mut seen = HashSet::with_capacity(input_qubit_mapping.len());
+        for (input, virt) in input_qubit_mapping.iter().enumerate() {
+            if virt.index() >= num_qubits {
+                return Err(TranspileLayoutError::InputOutOfRange {
+                    input,
+                    virt: virt.0,
+                    num_qubits,
+                });
+            }
+            if !seen.insert(*virt) {
+                return Err(TranspileLayoutError::DuplicateInput(virt.0));
+            }
+        }
+        let routing_permutation = match routing_permutation {
+            Some(permutation) => {
+                if permutation.len() != num_qubits
+                    || permutation.iter().collect::<HashSet<_>>().len() != num_qubits
+                    || permutation.iter().any(|qubit| qubit.index() >= num_qubits)
+                {
+                    return Err(TranspileLayoutError::InvalidPermutation(num_qubits));
+                }
+                permutation
+            }
+            None => (0..num_qubits as u32).map(PhysicalQubit).collect(),
+        };
+        Ok(Self {
+            initial_layout,
+            input_qubit_mapping,
+            routing_permutation,
+        })
+    }
+
+    /// A layout that places input qubit `i` on physical qubit `i`, with no routing permutation,
+    /// and fills the remaining physical qubits with ancillas.
+    ///
+    /// # Panics
+    ///
+    /// If `num_input_qubits` is greater than `num_physical_qubits`.
+    pub fn trivial(num_input_qubits: u32, num_physical_qubits: u32) -> Self {
+        assert!(num_input_qubits <= num_physical_qubits);
+        Self {
+            initial_layout: NLayout::generate_trivial_layout(num_physical_qubits),
+            input_qubit_mapping: (0..num_input_qubits).map(VirtualQubit).collect(),
+            routing_permutation: (0..num_physical_qubits).map(PhysicalQubit).collect(),
+        }
+    }
+
+    /// The number of qubits in the circuit that was passed to the transpiler.
+    #[inline]
+    pub fn num_input_qubits(&self) -> usize {
+        self.input_qubit_mapping.len()
+    }
+
+    /// The number of qubits in the output circuit.
+    #[inline]
+    pub fn num_output_qubits(&self) -> usize {
+        self.initial_layout.num_qubits()
+    }
+
+    /// The number of ancilla qubits that were added to the input circuit.
+    #[inline]
+    pub fn num_ancillas(&self) -> usize {
+        self.num_output_qubits() - self.num_input_qubits()
+    }
+
+    /// The virtual-to-physical mapping at the start of the output circuit.
+    #[inline]
+    pub fn initial_layout(&self) -> &NLayout {
+        &self.initial_layout
+    }
+
+    /// The virtual qubit that each input qubit became.
+    #[inline]
+    pub fn input_qubit_mapping(&self) -> &[VirtualQubit] {
+        &self.input_qubit_mapping
+    }
+
+    /// The permutation of the physical qubits introduced by routing.
+    #[inline]
+    pub fn routing_permutation(&self) -> &[PhysicalQubit] {
+        &self.routing_permutation
+    }
+
+    /// The virtual qubits in "input order": the images of the input qubits first, followed by the
+    /// ancillas in ascending order.
+    fn virtual_order(&self, filter_ancillas: bool) -> Vec<VirtualQubit> {
+        let mut out = self.input_qubit_mapping.clone();
+        if !filter_ancillas {
+            let inputs = self.input_qubit_mapping.iter().collect::<HashSet<_>>();
+            out.extend(
+                (0..self.num_output_qubits() as u32)
+                    .map(VirtualQubit)
+                    .filter(|virt| !inputs.contains(virt)),
+            );
+        }
+        out
+    }
+
+    /// The physical qubit that each input qubit starts the output circuit on.
+    ///
+    /// If `filter_ancillas` is `false`, the physical qubits of the ancillas follow those of the
+    /// input qubits, so the output has an entry for every qubit in the output circuit.
+    pub fn initial_physical_layout(&self, filter_ancillas: bool) -> Vec<PhysicalQubit> {
+        self.virtual_order(filter_ancillas)
+            .into_iter()
+            .map(|virt| virt.to_phys(&self.initial_layout))
+            .collect()
+    }
+
+    /// The physical qubit that the state of each input qubit ends the output circuit on.
+    ///
+    /// This is the layout to use to map an observable defined on the input circuit onto the
+    /// output circuit.  If `filter_ancillas` is `false`, the ancillas are included in the same
+    /// order as in [initial_physical_layout].
+    pub fn final_physical_layout(&self, filter_ancillas: bool) -> Vec<PhysicalQubit> {
+        self.initial_physical_layout(filter_ancillas)
+            .into_iter()
+            .map(|phys| self.routing_permutation[phys.index()])
+            .collect()
+    }
+
+    /// Apply a further permutation of the physical qubits after the current routing permutation,
+    /// such as one introduced by a later routing or permutation-elision pass.
+    pub fn compose_routing_permutation(
+        &mut self,
+        permutation: &[PhysicalQubit],
+    ) -> Result<(), TranspileLayoutError> {
+        let num_qubits = self.num_output_qubits();
+        if permutation.len() != num_qubits
+            || permutation.iter().collect::<HashSet<_>>().len() != num_qubits
+            || permutation.iter().any(|qubit| qubit.index() >= num_qubits)
+        {
+            return Err(TranspileLayoutError::InvalidPermutation(num_qubits));
+        }
+        for qubit in self.routing_permutation.iter_mut() {
+            *qubit = permutation[qubit.index()];
+        }
+        Ok(())
+    }
+
+    /// Compose this layout with one for a transpilation of the output circuit of `self`.
+    ///
+    /// `other` must treat the output qubits of `self` as its input qubits.  The result describes
+    /// the combined transpilation from the input circuit of `self` to the output circuit of
+    /// `other`; any ancillas added by `other` become additional ancillas of the result.
+    pub fn compose(&self, other: &TranspileLayout) -> Result<Self, TranspileLayoutError> {
+        if other.num_input_qubits() != self.num_output_qubits() {
+            return Err(TranspileLayoutError::ComposeMismatch {
+                expected: self.num_output_qubits(),
+                actual: other.num_input_qubits(),
+            });
+        }
+        // Where each qubit of the intermediate circuit is placed in the final circuit.
+        let relabel = |qubit: PhysicalQubit| {
+            other.input_qubit_mapping[qubit.index()].to_phys(&other.initial_layout)
+        };
+        let mut virt_to_phys = self
+            .initial_layout
+            .iter_virtual()
+            .map(|(_, phys)| relabel(phys))
+            .collect::<Vec<_>>();
+        virt_to_phys.extend(
+            other.virtual_order(false)[other.num_input_qubits()..]
+                .iter()
+                .map(|virt| virt.to_phys(&other.initial_layout)),
+        );
+        let mut routing_permutation = other.routing_permutation.clone();
+        for (start, end) in self.routing_permutation.iter().enumerate() {
+            let start = relabel(PhysicalQubit(start as u32));
+            routing_permutation[start.index()] = other.routing_permutation[relabel(*end).index()];
+        }
+        let mut phys_to_virt = vec![VirtualQubit(u32::MAX); virt_to_phys.len()];
+        for (virt, phys) in virt_to_phys.iter().enumerate() {
+            phys_to_virt[phys.index()] = VirtualQubit(virt as u32);
+        }
+        Ok(Self {
+            initial_layout: NLayout::from_vecs_unchecked(virt_to_phys, phys_to_virt),
+            input_qubit_mapping: self.input_qubit_mapping.clone(),
+            routing_permutation,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn phys(qubits: &[u32]) -> Vec<PhysicalQubit> {
+        qubits.iter().copied().map(PhysicalQubit).collect()
+    }
+
+    fn layout(virt_to_phys: &[u32]) -> NLayout {
+        NLayout::from_virtual_to_physical(phys(virt_to_phys)).unwrap()
+    }
+
+    #[test]
+    fn test_physical_layouts() {
+        // Two input qubits become virtual qubits 2 and 0 on a three-qubit device.
+        let layout = TranspileLayout::new(
+            layout(&[1, 2, 0]),
+            vec![VirtualQubit(2), VirtualQubit(0)],
+            Some(phys(&[2, 0, 1])),
+        )
+        .unwrap();
+        assert_eq!(layout.num_ancillas(), 1);
+        assert_eq!(layout.initial_physical_layout(true), phys(&[0, 1]));
+        assert_eq!(layout.initial_physical_layout(false), phys(&[0, 1, 2]));
+        assert_eq!(layout.final_physical_layout(true), phys(&[2, 0]));
+        assert_eq!(layout.final_physical_layout(false), phys(&[2, 0, 1]));
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert_eq!(
+            TranspileLayout::new(layout(&[0, 1]), vec![VirtualQubit(2)], None),
+            Err(TranspileLayoutError::InputOutOfRange {
+                input: 0,
+                virt: 2,
+                num_qubits: 2
+            })
+        );
+        assert_eq!(
+            TranspileLayout::new(
+                layout(&[0, 1]),
+                vec![VirtualQubit(1), VirtualQubit(1)],
+                None
+            ),
+            Err(TranspileLayoutError::DuplicateInput(1))
+        );
+        assert_eq!(
+            TranspileLayout::new(layout(&[0, 1]), vec![], Some(phys(&[1, 1]))),
+            Err(TranspileLayoutError::InvalidPermutation(2))
+        );
+    }
+
+    #[test]
+    fn test_compose_routing_permutation() {
+        let mut layout = TranspileLayout::trivial(3, 3);
+        layout
+            .compose_routing_permutation(&phys(&[1, 2, 0]))
+            .unwrap();
+        layout
+            .compose_routing_permutation(&phys(&[1, 2, 0]))
+            .unwrap();
+        assert_eq!(layout.final_physical_layout(true), phys(&[2, 0, 1]));
+    }
+
+    #[test]
+    fn test_compose() {
+        let first = TranspileLayout::new(
+            layout(&[1, 0]),
+            vec![VirtualQubit(0), VirtualQubit(1)],
+            Some(phys(&[1, 0])),
+        )
+        .unwrap();
+        let second = TranspileLayout::new(
+            layout(&[2, 0, 1]),
+            vec![VirtualQubit(0), VirtualQubit(1)],
+            Some(phys(&[0, 2, 1])),
+        )
+        .unwrap();
+        let composed = first.compose(&second).unwrap();
+        assert_eq!(composed.num_input_qubits(), 2);
+        assert_eq!(composed.num_ancillas(), 1);
+        // Input qubit 0 starts on intermediate qubit 1, which is final qubit 0.
+        assert_eq!(composed.initial_physical_layout(false), phys(&[0, 2, 1]));
+        // Input qubit 0 ends the first circuit on intermediate qubit 0, which starts the second
+        // on final qubit 2 and ends it on final qubit 1.
+        assert_eq!(composed.final_physical_layout(true), phys(&[1, 0]));
+        assert_eq!(
+            first.compose(&TranspileLayout::trivial(3, 3)),
+            Err(TranspileLayoutError::ComposeMismatch {
+                expected: 2,
+                actual: 3
+            })
+        );
+    }
+}
@@ -0,0 +1,440 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2025
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! A builder of [CircuitData] from the bytecode stream, which does not need Python.
+//!
+//! This is the Rust-space equivalent of `qiskit.qasm2.parse.from_bytecode`.  The gates of the
+//! `qelib1.inc` special include, and the built-in `U` and `CX`, are mapped to [StandardGate]s.
+//! There is no Rust-space object that can hold the definition of a custom gate, so gates defined
+//! by `gate` statements are expanded into their bodies at every place they are applied.  The output
+//! therefore only contains standard gates, measurements, resets, barriers and delays.  Operations
+//! that need Python-space objects to represent them (applications of `opaque` gates and `if`
+//! statements) are errors; there is no Rust-space representation of control flow.
+//!
+//! The legacy custom instructions, the equivalent of Python-space
+//! `qiskit.qasm2.LEGACY_CUSTOM_INSTRUCTIONS`, can be enabled to load programs written by the
+//! exporter, which calls gates such as `sx` and `swap` by name without defining them.
+
+use std::path::PathBuf;
+
+use pyo3::PyErr;
+use thiserror::Error;
+
+use qiskit_circuit::bit::{ClassicalRegister, QuantumRegister};
+use qiskit_circuit::circuit_data::CircuitData;
+use qiskit_circuit::operations::{DelayUnit, Param, StandardGate, StandardInstruction};
+use qiskit_circuit::packed_instruction::PackedOperation;
+use qiskit_circuit::{Clbit, Qubit};
+
+use crate::bytecode::{BytecodeIterator, InternalBytecode};
+use crate::error::ParseError;
+use crate::expr::{Expr, Function};
+use crate::lex::TokenStream;
+use crate::parse::{GateId, QubitId, QELIB1};
+use crate::CustomInstruction;
+
+type LoadResult<T> = Result<T, QASM2LoadError>;
+
+#[derive(Error, Debug)]
+pub enum QASM2LoadError {
+    /// The program is not valid OpenQASM 2.
+    #[error("{0}")]
+    Parse(#[from] ParseError),
+    /// The program is valid, but contains something that can't be represented without Python.
+    #[error("{0}")]
+    Unsupported(String),
+    /// One of the legacy custom instructions was given an argument it cannot accept.
+    #[error("{0}")]
+    InvalidArgument(String),
+    #[error("PyError: {0}")]
+    PyErr(#[from] PyErr),
+}
+
+/// How to apply a gate that is in scope in the program.
+enum GateDefinition {
+    Standard(StandardGate),
+    /// The `id` gate of `qelib1.inc`.  Like in Python space, this is a `U(0, 0, 0)`, since the
+    /// legacy meaning of Qiskit's `IGate` was a single-cycle delay.
+    Identity,
+    /// The legacy `u0` gate, which is a number of `id` gates given by its parameter.
+    U0,
+    /// The legacy `delay` instruction, whose parameter is the duration in `dt`.
+    Delay,
+    /// A gate defined by a `gate` statement, which is expanded into its body when applied.  The
+    /// body contains only `GateInBody` and `Barrier` instructions.
+    Defined(Vec<InternalBytecode>),
+    Opaque(String),
+}
+
+/// The gate of `qelib1.inc` with the given name.
+fn qelib1_gate(name: &str) -> GateDefinition {
+    GateDefinition::Standard(match name {
+        "u3" => StandardGate::U3,
+        "u2" => StandardGate::U2,
+        "u1" => StandardGate::U1,
+        "cx" => StandardGate::CX,
+        "id" => return GateDefinition::Identity,
+        "x" => StandardGate::X,
+        "y" => StandardGate::Y,
+        "z" => StandardGate::Z,
+        "h" => StandardGate::H,
+        "s" => StandardGate::S,
+        "sdg" => StandardGate::Sdg,
+        "t" => StandardGate::T,
+        "tdg" => StandardGate::Tdg,
+        "rx" => StandardGate::RX,
+        "ry" => StandardGate::RY,
+        "rz" => StandardGate::RZ,
+        "cz" => StandardGate::CZ,
+        "cy" => StandardGate::CY,
+        "ch" => StandardGate::CH,
+        "ccx" => StandardGate::CCX,
+        "crz" => StandardGate::CRZ,
+        "cu1" => StandardGate::CU1,
+        "cu3" => StandardGate::CU3,
+        _ => unreachable!("'{name}' is not a gate in qelib1.inc"),
+    })
+}
+
+/// The legacy custom instructions, in the same order as Python-space
+/// `qiskit.qasm2.LEGACY_CUSTOM_INSTRUCTIONS`, as `(name, num_params, num_qubits, builtin)`.
+///
+/// `c4x` is left out, since it has no Rust-space representation; a program can still define it
+/// with a `gate` statement.
+const LEGACY_INSTRUCTIONS: [(&str, usize, usize, bool); 42] = [
+    ("u3", 3, 1, false),
+    ("u2", 2, 1, false),
+    ("u1", 1, 1, false),
+    ("cx", 0, 2, false),
+    ("id", 0, 1, false),
+    ("u0", 1, 1, true),
+    ("u", 3, 1, true),
+    ("p", 1, 1, true),
+    ("x", 0, 1, false),
+    ("y", 0, 1, false),
+    ("z", 0, 1, false),
+    ("h", 0, 1, false),
+    ("s", 0, 1, false),
+    ("sdg", 0, 1, false),
+    ("t", 0, 1, false),
+    ("tdg", 0, 1, false),
+    ("rx", 1, 1, false),
+    ("ry", 1, 1, false),
+    ("rz", 1, 1, false),
+    ("sx", 0, 1, true),
+    ("sxdg", 0, 1, true),
+    ("cz", 0, 2, false),
+    ("cy", 0, 2, false),
+    ("swap", 0, 2, true),
+    ("ch", 0, 2, false),
+    ("ccx", 0, 3, false),
+    ("cswap", 0, 3, true),
+    ("crx", 1, 2, true),
+    ("cry", 1, 2, true),
+    ("crz", 1, 2, false),
+    ("cu1", 1, 2, false),
+    ("cp", 1, 2, true),
+    ("cu3", 3, 2, false),
+    ("csx", 0, 2, true),
+    ("cu", 4, 2, true),
+    ("rxx", 1, 2, true),
+    ("rzz", 1, 2, true),
+    ("rccx", 0, 3, true),
+    ("rc3x", 0, 4, true),
+    ("c3x", 0, 4, true),
+    ("c3sqrtx", 0, 4, true),
+    ("delay", 1, 1, false),
+];
+
+/// The legacy custom instruction with the given name.  Unlike the `id` of `qelib1.inc`, the legacy
+/// `id` is Qiskit's `IGate`, like in Python space.
+fn legacy_gate(name: &str) -> GateDefinition {
+    GateDefinition::Standard(match name {
+        "id" => StandardGate::I,
+        "u0" => return GateDefinition::U0,
+        "u" => StandardGate::U,
+        "p" => StandardGate::Phase,
+        "sx" => StandardGate::SX,
+        "sxdg" => StandardGate::SXdg,
+        "swap" => StandardGate::Swap,
+        "cswap" => StandardGate::CSwap,
+        "crx" => StandardGate::CRX,
+        "cry" => StandardGate::CRY,
+        "cp" => StandardGate::CPhase,
+        "csx" => StandardGate::CSX,
+        "cu" => StandardGate::CU,
+        "rxx" => StandardGate::RXX,
+        "rzz" => StandardGate::RZZ,
+        "rccx" => StandardGate::RCCX,
+        "rc3x" => StandardGate::RC3X,
+        "c3x" => StandardGate::C3X,
+        "c3sqrtx" => StandardGate::C3SX,
+        "delay" => return GateDefinition::Delay,
+        name => return qelib1_gate(name),
+    })
+}
+
+/// Get a parameter of a legacy custom instruction that must be an integer.
+fn integer_argument(name: &str, value: f64) -> LoadResult<f64> {
+    if value.fract() == 0.0 {
+        Ok(value)
+    } else {
+        Err(QASM2LoadError::InvalidArgument(format!(
+            "the legacy '{name}' instruction can only accept an integer parameter"
+        )))
+    }
+}
+
+/// Evaluate an argument of a gate application within a gate body, given the values of the
+/// parameters of the gate.
+fn evaluate(expr: &Expr, params: &[f64]) -> f64 {
+    match expr {
+        Expr::Constant(value) => *value,
+        Expr::Parameter(index) => params[usize::from(*index)],
+        Expr::Negate(expr) => -evaluate(expr, params),
+        Expr::Add(left, right) => evaluate(left, params) + evaluate(right, params),
+        Expr::Subtract(left, right) => evaluate(left, params) - evaluate(right, params),
+        Expr::Multiply(left, right) => evaluate(left, params) * evaluate(right, params),
+        Expr::Divide(left, right) => evaluate(left, params) / evaluate(right, params),
+        Expr::Power(left, right) => evaluate(left, params).powf(evaluate(right, params)),
+        Expr::Function(func, expr) => {
+            let value = evaluate(expr, params);
+            match func {
+                Function::Cos => value.cos(),
+                Function::Exp => value.exp(),
+                Function::Ln => value.ln(),
+                Function::Sin => value.sin(),
+                Function::Sqrt => value.sqrt(),
+                Function::Tan => value.tan(),
+            }
+        }
+        Expr::CustomFunction(..) => {
+            unreachable!("custom classical functions are only available from Python")
+        }
+    }
+}
+
+fn map_qubits(qubits: &[QubitId], scope: &[Qubit]) -> Vec<Qubit> {
+    qubits
+        .iter()
+        .map(|qubit| scope[usize::from(*qubit)])
+        .collect()
+}
+
+fn push_barrier(circuit: &mut CircuitData, qubits: &[Qubit]) {
+    circuit.push_packed_operation(
+        PackedOperation::from_standard_instruction(StandardInstruction::Barrier(
+            qubits.len() as u32
+        )),
+        &[],
+        qubits,
+        &[],
+    );
+}
+
+/// Append the application of the gate `id` to the circuit, recursively expanding any gates that
+/// were defined in the program.
+fn apply_gate(
+    circuit: &mut CircuitData,
+    gates: &[GateDefinition],
+    id: GateId,
+    params: &[f64],
+    qubits: &[Qubit],
+) -> LoadResult<()> {
+    match &gates[usize::from(id)] {
+        GateDefinition::Standard(gate) => {
+            let params = params.iter().map(|x| Param::Float(*x)).collect::<Vec<_>>();
+            circuit.push_standard_gate(*gate, &params, qubits);
+        }
+        GateDefinition::Identity => {
+            let params = [Param::Float(0.), Param::Float(0.), Param::Float(0.)];
+            circuit.push_standard_gate(StandardGate::U, &params, qubits);
+        }
+        GateDefinition::U0 => {
+            for _ in 0..integer_argument("u0", params[0])? as u64 {
+                circuit.push_standard_gate(StandardGate::I, &[], qubits);
+            }
+        }
+        GateDefinition::Delay => circuit.push_packed_operation(
+            PackedOperation::from_standard_instruction(StandardInstruction::Delay(DelayUnit::DT)),
+            &[Param::Float(integer_argument("delay", params[0])?)],
+            qubits,
+            &[],
+        ),
+        GateDefinition::Defined(body) => {
+            for inner in body {
+                match inner {
+                    InternalBytecode::GateInBody {
+                        id,
+                        arguments,
+                        qubits: inner_qubits,
+                    } => {
+                        let arguments = arguments
+                            .iter()
+                            .map(|argument| evaluate(argument, params))
+                            .collect::<Vec<_>>();
+                        let inner_qubits = map_qubits(inner_qubits, qubits);
+                        apply_gate(circuit, gates, *id, &arguments, &inner_qubits)?;
+                    }
+                    InternalBytecode::Barrier {
+                        qubits: inner_qubits,
+                    } => push_barrier(circuit, &map_qubits(inner_qubits, qubits)),
+                    _ => unreachable!("the parser only emits gates and barriers in gate bodies"),
+                }
+            }
+        }
+        GateDefinition::Opaque(name) => {
+            return Err(QASM2LoadError::Unsupported(format!(
+                "the opaque gate '{name}' cannot be represented without Python"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Consume the output of a [BytecodeIterator] to build a circuit.  The iterator must not have been
+/// given any custom classical functions, and must have been given the legacy custom instructions if
+/// and only if `legacy_instructions` is set.
+fn from_bytecode(
+    mut bytecode: BytecodeIterator,
+    legacy_instructions: bool,
+) -> LoadResult<CircuitData> {
+    let mut circuit = CircuitData::new(None, None, None, 0, Param::Float(0.))?;
+    // The order of the gates must match the order the parser assigns them their `GateId`s, which
+    // puts the custom instructions first.
+    let mut gates = if legacy_instructions {
+        LEGACY_INSTRUCTIONS
+            .iter()
+            .map(|(name, ..)| legacy_gate(name))
+            .collect()
+    } else {
+        Vec::new()
+    };
+    gates.extend([
+        GateDefinition::Standard(StandardGate::U),
+        GateDefinition::Standard(StandardGate::CX),
+    ]);
+    let conditional = || {
+        Err(QASM2LoadError::Unsupported(
+            "conditional statements cannot be represented without Python".to_owned(),
+        ))
+    };
+    while let Some(instruction) = bytecode.next_bytecode()? {
+        match instruction {
+            InternalBytecode::Gate {
+                id,
+                arguments,
+                qubits,
+            } => {
+                let qubits = qubits
+                    .into_iter()
+                    .map(|qubit| Qubit::new(usize::from(qubit)))
+                    .collect::<Vec<_>>();
+                apply_gate(&mut circuit, &gates, id, &arguments, &qubits)?;
+            }
+            InternalBytecode::Measure { qubit, clbit } => circuit.push_packed_operation(
+                PackedOperation::from_standard_instruction(StandardInstruction::Measure),
+                &[],
+                &[Qubit::new(usize::from(qubit))],
+                &[Clbit::new(usize::from(clbit))],
+            ),
+            InternalBytecode::Reset { qubit } => circuit.push_packed_operation(
+                PackedOperation::from_standard_instruction(StandardInstruction::Reset),
+                &[],
+                &[Qubit::new(usize::from(qubit))],
+                &[],
+            ),
+            InternalBytecode::Barrier { qubits } => {
+                let qubits = qubits
+                    .into_iter()
+                    .map(|qubit| Qubit::new(usize::from(qubit)))
+                    .collect::<Vec<_>>();
+                push_barrier(&mut circuit, &qubits);
+            }
+            InternalBytecode::DeclareQreg { name, size } => {
+                circuit.add_qreg(QuantumRegister::new_owning(name, size as u32), true)?;
+            }
+            InternalBytecode::DeclareCreg { name, size } => {
+                circuit.add_creg(ClassicalRegister::new_owning(name, size as u32), true)?;
+            }
+            InternalBytecode::SpecialInclude { indices } => {
+                gates.extend(
+                    indices
+                        .into_iter()
+                        .map(|index| qelib1_gate(QELIB1[index].0)),
+                );
+            }
+            InternalBytecode::DeclareGate { .. } => {
+                let mut body = Vec::new();
+                loop {
+                    match bytecode.next_bytecode()? {
+                        Some(InternalBytecode::EndDeclareGate {}) => break,
+                        Some(inner) => body.push(inner),
+                        None => unreachable!("the parser always closes gate definitions"),
+                    }
+                }
+                gates.push(GateDefinition::Defined(body));
+            }
+            InternalBytecode::DeclareOpaque { name, .. } => {
+                gates.push(GateDefinition::Opaque(name));
+            }
+            InternalBytecode::ConditionedGate { .. }
+            | InternalBytecode::ConditionedMeasure { .. }
+            | InternalBytecode::ConditionedReset { .. } => return conditional(),
+            InternalBytecode::GateInBody { .. } | InternalBytecode::EndDeclareGate {} => {
+                unreachable!("the parser only emits these within gate definitions")
+            }
+        }
+    }
+    Ok(circuit)
+}
+
+/// Parse an OpenQASM 2 program from a string into a circuit, without using Python.
+///
+/// `include_path` is the order of directories to search when evaluating `include` statements,
+/// though `qelib1.inc` is always available.  `strict` has the same meaning as in Python-space
+/// `qiskit.qasm2.loads`.  `legacy_instructions` enables the legacy custom instructions, like
+/// passing `custom_instructions=LEGACY_CUSTOM_INSTRUCTIONS` in Python space, except that `c4x` is
+/// not included.  See the module documentation for how the program is represented.
+pub fn loads(
+    program: String,
+    include_path: Vec<PathBuf>,
+    strict: bool,
+    legacy_instructions: bool,
+) -> LoadResult<CircuitData> {
+    let custom_instructions = if legacy_instructions {
+        LEGACY_INSTRUCTIONS
+            .iter()
+            .map(
+                |&(name, num_params, num_qubits, builtin)| CustomInstruction {
+                    name: name.to_owned(),
+                    num_params,
+                    num_qubits,
+                    builtin,
+                },
+            )
+            .collect()
+    } else {
+        Vec::new()
+    };
+    from_bytecode(
+        BytecodeIterator::new(
+            TokenStream::from_string(program, strict),
+            include_path,
+            &custom_instructions,
+            &[],
+            strict,
+            false,
+        )?,
+        legacy_instructions,
+    )
+}
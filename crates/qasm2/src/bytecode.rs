@@ -13,7 +13,7 @@
 use num_bigint::BigUint;
 use pyo3::prelude::*;
 
-use crate::error::QASM2ParseError;
+use crate::error::ParseResult;
 use crate::expr::Expr;
 use crate::lex;
 use crate::parse;
@@ -318,7 +318,8 @@ impl<'py> IntoPyObject<'py> for InternalBytecode {
 
 /// The custom iterator object that is returned up to Python space for iteration through the
 /// bytecode stream.  This is never constructed on the Python side; it is built in Rust space
-/// by Python calls to [bytecode_from_string] and [bytecode_from_file].
+/// by Python calls to [bytecode_from_string] and [bytecode_from_file], and by the Rust-space
+/// circuit builder.
 #[pyclass]
 pub struct BytecodeIterator {
     parser_state: parse::State,
@@ -333,7 +334,8 @@ impl BytecodeIterator {
         custom_instructions: &[CustomInstruction],
         custom_classical: &[CustomClassical],
         strict: bool,
-    ) -> PyResult<Self> {
+        recover: bool,
+    ) -> ParseResult<Self> {
         Ok(BytecodeIterator {
             parser_state: parse::State::new(
                 tokens,
@@ -341,21 +343,16 @@ impl BytecodeIterator {
                 custom_instructions,
                 custom_classical,
                 strict,
-            )
-            .map_err(QASM2ParseError::new_err)?,
+                recover,
+            )?,
             buffer: vec![],
             buffer_used: 0,
         })
     }
-}
 
-#[pymethods]
-impl BytecodeIterator {
-    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
-        slf
-    }
-
-    fn __next__(&mut self, py: Python<'_>) -> PyResult<Option<Bytecode>> {
+    /// Get the next instruction in the bytecode stream, parsing more of the program if necessary.
+    /// This is the Rust-space equivalent of iterating over the object in Python space.
+    pub fn next_bytecode(&mut self) -> ParseResult<Option<InternalBytecode>> {
         if self.buffer_used >= self.buffer.len() {
             self.buffer.clear();
             self.buffer_used = 0;
@@ -365,11 +362,22 @@ impl BytecodeIterator {
             Ok(None)
         } else {
             self.buffer_used += 1;
-            Ok(self.buffer[self.buffer_used - 1]
-                .take()
-                .map(|bytecode| bytecode.into_pyobject(py))
-                .transpose()?
-                .map(|x| x.get().clone()))
+            Ok(self.buffer[self.buffer_used - 1].take())
         }
     }
 }
+
+#[pymethods]
+impl BytecodeIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python<'_>) -> PyResult<Option<Bytecode>> {
+        Ok(self
+            .next_bytecode()?
+            .map(|bytecode| bytecode.into_pyobject(py))
+            .transpose()?
+            .map(|x| x.get().clone()))
+    }
+}
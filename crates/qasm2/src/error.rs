@@ -10,27 +10,45 @@
 // copyright notice, and modified files need to carry a notice indicating
 // that they have been altered from the originals.
 
+use std::ffi::{OsStr, OsString};
+
 use pyo3::import_exception;
+use pyo3::prelude::*;
+use pyo3::types::{IntoPyDict, PyTuple};
 
 use crate::lex::Token;
 
-pub struct Position<'a> {
-    filename: &'a std::ffi::OsStr,
+/// A location in an OpenQASM 2 source file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Position {
+    filename: OsString,
     line: usize,
     col: usize,
 }
 
-impl<'a> Position<'a> {
-    pub fn new(filename: &'a std::ffi::OsStr, line: usize, col: usize) -> Self {
+impl Position {
+    pub fn new(filename: &OsStr, line: usize, col: usize) -> Self {
         Self {
-            filename,
+            filename: filename.to_owned(),
             line,
             col,
         }
     }
+
+    pub fn filename(&self) -> &OsStr {
+        &self.filename
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn col(&self) -> usize {
+        self.col
+    }
 }
 
-impl std::fmt::Display for &Position<'_> {
+impl std::fmt::Display for Position {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
@@ -42,6 +60,166 @@ impl std::fmt::Display for &Position<'_> {
     }
 }
 
+/// A single problem found while lexing or parsing an OpenQASM 2 program.
+#[pyclass(module = "qiskit._accelerate.qasm2", frozen)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    position: Option<Position>,
+    message: String,
+}
+
+impl Diagnostic {
+    pub fn new(position: Option<&Position>, message: &str) -> Self {
+        Self {
+            position: position.cloned(),
+            message: message.to_owned(),
+        }
+    }
+
+    pub fn position(&self) -> Option<&Position> {
+        self.position.as_ref()
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+#[pymethods]
+impl Diagnostic {
+    /// The name of the file containing the problem (``"<input>"`` for programs given as strings),
+    /// or ``None`` if the problem has no location.
+    #[getter]
+    fn file(&self) -> Option<String> {
+        self.position
+            .as_ref()
+            .map(|position| position.filename.to_string_lossy().into_owned())
+    }
+
+    /// The line of the problem, counting from 1, or ``None`` if the problem has no location.
+    #[getter(line)]
+    fn py_line(&self) -> Option<usize> {
+        self.position.as_ref().map(Position::line)
+    }
+
+    /// The column of the problem, or ``None`` if the problem has no location.
+    #[getter]
+    fn column(&self) -> Option<usize> {
+        self.position.as_ref().map(Position::col)
+    }
+
+    /// A human-readable description of the problem, without its location.
+    #[getter(message)]
+    fn py_message(&self) -> &str {
+        &self.message
+    }
+
+    fn __repr__(&self) -> String {
+        match &self.position {
+            Some(position) => format!(
+                "Diagnostic(file={:?}, line={}, column={}, message={:?})",
+                position.filename.to_string_lossy(),
+                position.line,
+                position.col,
+                self.message
+            ),
+            None => format!(
+                "Diagnostic(file=None, line=None, column=None, message={:?})",
+                self.message
+            ),
+        }
+    }
+
+    fn __str__(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            message_generic(self.position.as_ref(), &self.message)
+        )
+    }
+}
+
+/// The error type of the lexer and parser.
+#[derive(Debug)]
+pub enum ParseError {
+    /// A problem with the OpenQASM 2 program itself.
+    Invalid(Diagnostic),
+    /// All the problems found in the program by a parse in recovery mode.
+    Diagnostics(Vec<Diagnostic>),
+    /// An exception raised by Python code that the parser called, such as a custom classical
+    /// function.  These are never recovered from.
+    Python(PyErr),
+}
+
+impl ParseError {
+    /// Create an error for a problem with the program, located at `position`.
+    pub fn new(position: Option<&Position>, message: &str) -> Self {
+        ParseError::Invalid(Diagnostic::new(position, message))
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::Invalid(diagnostic) => write!(f, "{diagnostic}"),
+            ParseError::Diagnostics(diagnostics) => {
+                for (i, diagnostic) in diagnostics.iter().enumerate() {
+                    if i > 0 {
+                        writeln!(f)?;
+                    }
+                    write!(f, "{diagnostic}")?;
+                }
+                Ok(())
+            }
+            ParseError::Python(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<PyErr> for ParseError {
+    fn from(err: PyErr) -> Self {
+        ParseError::Python(err)
+    }
+}
+
+impl From<ParseError> for PyErr {
+    fn from(err: ParseError) -> Self {
+        match err {
+            ParseError::Invalid(diagnostic) => diagnostics_error(vec![diagnostic]),
+            ParseError::Diagnostics(diagnostics) => diagnostics_error(diagnostics),
+            ParseError::Python(err) => err,
+        }
+    }
+}
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+/// Create a Python-space :exc:`.QASM2ParseError` that reports all the given `diagnostics`.
+pub fn diagnostics_error(diagnostics: Vec<Diagnostic>) -> PyErr {
+    let message = diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+    Python::with_gil(|py| {
+        let diagnostics = PyTuple::new(py, diagnostics)?;
+        let kwargs = [("diagnostics", diagnostics)].into_py_dict(py)?;
+        let exc = py
+            .get_type::<QASM2ParseError>()
+            .call((message,), Some(&kwargs))?;
+        Ok(PyErr::from_value(exc))
+    })
+    .unwrap_or_else(|err: PyErr| err)
+}
+
 /// Create an error message that includes span data from the given [token][Token].  The base of the
 /// message is `message`, and `filename` is the file the triggering OpenQASM 2 code came from.  For
 /// string inputs, this can be a placeholder.
@@ -53,14 +231,14 @@ pub fn message_generic(position: Option<&Position>, message: &str) -> String {
     }
 }
 
-/// Shorthand form for creating an error message when a particular type of token was required, but
+/// Shorthand form for creating an error when a particular type of token was required, but
 /// something else was `received`.
-pub fn message_incorrect_requirement(
+pub fn error_incorrect_requirement(
     required: &str,
     received: &Token,
-    filename: &std::ffi::OsStr,
-) -> String {
-    message_generic(
+    filename: &OsStr,
+) -> ParseError {
+    ParseError::new(
         Some(&Position::new(filename, received.line, received.col)),
         &format!(
             "needed {}, but instead saw {}",
@@ -70,13 +248,14 @@ pub fn message_incorrect_requirement(
     )
 }
 
-/// Shorthand form for creating an error message when a particular type of token was required, but
-/// the input ended unexpectedly.
-pub fn message_bad_eof(position: Option<&Position>, required: &str) -> String {
-    message_generic(
+/// Shorthand form for creating an error when a particular type of token was required, but the
+/// input ended unexpectedly.
+pub fn error_bad_eof(position: Option<&Position>, required: &str) -> ParseError {
+    ParseError::new(
         position,
         &format!("unexpected end-of-file when expecting to see {required}"),
     )
 }
 
 import_exception!(qiskit.qasm2.exceptions, QASM2ParseError);
+import_exception!(qiskit.qasm2.exceptions, QASM2ExportError);
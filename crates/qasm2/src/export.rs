@@ -0,0 +1,942 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2025
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! An exporter from [CircuitData] to OpenQASM 2.
+//!
+//! This follows the same conventions as the Python-space exporter `qiskit.qasm2.dumps`, but works
+//! directly from the Rust data model.  Operations that are not in `qelib1.inc` are emitted as
+//! `gate` definitions (if they have one) or `opaque` declarations (if not).  Since OpenQASM 2 gate
+//! definitions can only be parametrized by angles, and the parametric definitions of gates are
+//! only available through Python, definitions are emitted for each distinct set of parameters an
+//! operation is used with, rather than once with formal parameters.
+
+use std::fmt::Write;
+
+use hashbrown::{HashMap, HashSet};
+use indexmap::IndexMap;
+use pyo3::intern;
+use pyo3::prelude::*;
+use thiserror::Error;
+
+use qiskit_circuit::bit::{
+    ClassicalRegister, QuantumRegister, Register, ShareableClbit, ShareableQubit,
+};
+use qiskit_circuit::circuit_data::CircuitData;
+use qiskit_circuit::classical::expr;
+use qiskit_circuit::operations::{
+    Operation, OperationRef, Param, StandardGate, StandardInstruction,
+};
+use qiskit_circuit::packed_instruction::PackedInstruction;
+use qiskit_circuit::parameter_expression::ParameterExpression;
+use qiskit_circuit::symbol_expr::{self, BinaryOp, SymbolExpr, UnaryOp};
+
+use crate::error::QASM2ExportError;
+
+type ExporterResult<T> = Result<T, QASM2ExporterError>;
+
+#[derive(Error, Debug)]
+pub enum QASM2ExporterError {
+    #[error("{0}")]
+    Error(String),
+    #[error("PyError: {0}")]
+    PyErr(PyErr),
+}
+
+impl From<PyErr> for QASM2ExporterError {
+    fn from(err: PyErr) -> Self {
+        QASM2ExporterError::PyErr(err)
+    }
+}
+
+impl From<QASM2ExporterError> for PyErr {
+    fn from(err: QASM2ExporterError) -> Self {
+        match err {
+            QASM2ExporterError::Error(message) => QASM2ExportError::new_err(message),
+            QASM2ExporterError::PyErr(err) => err,
+        }
+    }
+}
+
+fn error<T>(message: String) -> ExporterResult<T> {
+    Err(QASM2ExporterError::Error(message))
+}
+
+/// The names of the gates defined by `qelib1.inc`, as well as the built-in instructions.
+const EXISTING_GATE_NAMES: [&str; 45] = [
+    "barrier", "measure", "reset", "u3", "u2", "u1", "cx", "id", "u0", "u", "p", "x", "y", "z",
+    "h", "s", "sdg", "t", "tdg", "rx", "ry", "rz", "sx", "sxdg", "cz", "cy", "swap", "ch", "ccx",
+    "cswap", "crx", "cry", "crz", "cu1", "cp", "cu3", "csx", "cu", "rxx", "rzz", "rccx", "rc3x",
+    "c3x", "c3sqrtx", "c4x",
+];
+
+const RESERVED: [&str; 12] = [
+    "OPENQASM", "qreg", "creg", "include", "gate", "opaque", "U", "CX", "measure", "reset", "if",
+    "barrier",
+];
+
+/// The name of a standard gate in `qelib1.inc`, if it's defined there.
+fn qelib1_name(gate: StandardGate) -> Option<&'static str> {
+    match gate {
+        // These have different names in Qiskit.
+        StandardGate::C3X => Some("c3x"),
+        StandardGate::C3SX => Some("c3sqrtx"),
+        StandardGate::RC3X => Some("rc3x"),
+        gate => EXISTING_GATE_NAMES
+            .iter()
+            .find(|name| **name == gate.name())
+            .copied(),
+    }
+}
+
+/// Make a valid OpenQASM 2 identifier from `name`, using `prefix` (which must itself be a valid
+/// identifier) to fix it up if necessary.
+fn escape_name(name: &str, prefix: &str) -> String {
+    let escaped = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    if escaped.starts_with(|c: char| c.is_ascii_lowercase()) && !RESERVED.contains(&&*escaped) {
+        escaped
+    } else {
+        format!("{prefix}{escaped}")
+    }
+}
+
+/// Generate a name by suffixing the given stem that is unique within the defined set.
+fn make_unique(name: String, already_defined: &HashSet<String>) -> String {
+    const CHARACTERS: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    if !already_defined.contains(&name) {
+        return name;
+    }
+    for length in 1u32.. {
+        for mut index in 0..CHARACTERS.len().pow(length) {
+            let mut candidate = name.clone();
+            for _ in 0..length {
+                candidate.push(CHARACTERS[index % CHARACTERS.len()] as char);
+                index /= CHARACTERS.len();
+            }
+            if !already_defined.contains(&candidate) {
+                return candidate;
+            }
+        }
+    }
+    unreachable!("the loop over suffix lengths is infinite")
+}
+
+/// The tolerance used when checking whether a float is a simple fraction or multiple of pi.
+const PI_EPS: f64 = 1e-12;
+/// The largest numerator or denominator used when writing a float as a fraction involving pi.
+const MAX_FRAC: u32 = 16;
+
+/// Write a float as an OpenQASM 2 expression, using simple fractions and multiples of pi where
+/// possible.  This is the equivalent of `pi_check` in Python space with `output="qasm"`.
+fn format_float(value: f64) -> ExporterResult<String> {
+    use std::f64::consts::PI;
+
+    if !value.is_finite() {
+        return error(format!("OpenQASM 2 cannot represent the value '{value}'"));
+    }
+    if value.abs() < PI_EPS {
+        return Ok("0".to_owned());
+    }
+    let neg = if value < 0.0 { "-" } else { "" };
+    let abs = value.abs();
+    let is_integer = |x: f64| (x - x.round()).abs() < PI_EPS;
+
+    // Whole multiples of pi.
+    let multiple = abs / PI;
+    if multiple >= 1.0 - PI_EPS && is_integer(multiple) {
+        return Ok(match multiple.round() as u64 {
+            1 => format!("{neg}pi"),
+            multiple => format!("{neg}{multiple}*pi"),
+        });
+    }
+    // No fraction is larger than `MAX_FRAC * pi`, so larger numbers are written out numerically.
+    if abs < MAX_FRAC as f64 * PI {
+        // One times pi over an integer.
+        let denominator = PI / abs;
+        if is_integer(denominator) {
+            return Ok(format!("{neg}pi/{}", denominator.round() as u64));
+        }
+        // Reduced fractions `n*pi/d` and `n/(d*pi)`.
+        for denominator in 1..=MAX_FRAC {
+            for numerator in 1..=MAX_FRAC {
+                let ratio = numerator as f64 / denominator as f64;
+                if (abs - ratio * PI).abs() < PI_EPS {
+                    return Ok(format!("{neg}{numerator}*pi/{denominator}"));
+                }
+            }
+        }
+        for denominator in 1..=MAX_FRAC {
+            for numerator in 1..=MAX_FRAC {
+                let ratio = numerator as f64 / denominator as f64;
+                if (abs - ratio / PI).abs() < PI_EPS {
+                    return Ok(format!("{neg}{numerator}/({denominator}*pi)"));
+                }
+            }
+        }
+    }
+    // OpenQASM 2 requires real literals to contain a decimal point.
+    let mut out = format!("{value:?}");
+    if !out.contains('.') {
+        match out.find('e') {
+            Some(exponent) => out.insert_str(exponent, ".0"),
+            None => out.push_str(".0"),
+        }
+    }
+    Ok(out)
+}
+
+/// Write a symbolic expression as an OpenQASM 2 expression.
+///
+/// OpenQASM 2 has no free parameters outside gate definitions, so the expression must not contain
+/// any symbols.  Operations that OpenQASM 2 has no function for are evaluated numerically.
+fn format_symbol_expr(expr: &SymbolExpr) -> ExporterResult<String> {
+    let value = |expr: &SymbolExpr| -> ExporterResult<String> {
+        match expr.eval(true) {
+            Some(value) => format_symbol_value(&value),
+            None => {
+                error("Cannot represent circuits with unbound parameters in OpenQASM 2.".into())
+            }
+        }
+    };
+    // Binary operations are parenthesised when they're operands, so precedence never matters.
+    let operand = |expr: &SymbolExpr| -> ExporterResult<String> {
+        let out = format_symbol_expr(expr)?;
+        Ok(match expr {
+            SymbolExpr::Binary { .. } => format!("({out})"),
+            _ => out,
+        })
+    };
+    match expr {
+        SymbolExpr::Symbol(name) => error(format!(
+            "Cannot represent circuits with unbound parameters in OpenQASM 2, but found '{name}'."
+        )),
+        SymbolExpr::Value(value) => format_symbol_value(value),
+        SymbolExpr::Unary { op, expr: inner } => match op {
+            UnaryOp::Neg => Ok(format!("-{}", operand(inner)?)),
+            UnaryOp::Sin => Ok(format!("sin({})", format_symbol_expr(inner)?)),
+            UnaryOp::Cos => Ok(format!("cos({})", format_symbol_expr(inner)?)),
+            UnaryOp::Tan => Ok(format!("tan({})", format_symbol_expr(inner)?)),
+            UnaryOp::Exp => Ok(format!("exp({})", format_symbol_expr(inner)?)),
+            UnaryOp::Log => Ok(format!("ln({})", format_symbol_expr(inner)?)),
+            UnaryOp::Abs
+            | UnaryOp::Asin
+            | UnaryOp::Acos
+            | UnaryOp::Atan
+            | UnaryOp::Sign
+            | UnaryOp::Conj => value(expr),
+        },
+        SymbolExpr::Binary { op, lhs, rhs } => {
+            let op = match op {
+                BinaryOp::Add => "+",
+                BinaryOp::Sub => "-",
+                BinaryOp::Mul => "*",
+                BinaryOp::Div => "/",
+                BinaryOp::Pow => "^",
+            };
+            Ok(format!("{}{op}{}", operand(lhs)?, operand(rhs)?))
+        }
+    }
+}
+
+fn format_symbol_value(value: &symbol_expr::Value) -> ExporterResult<String> {
+    match value {
+        symbol_expr::Value::Real(value) => format_float(*value),
+        symbol_expr::Value::Int(value) => Ok(value.to_string()),
+        symbol_expr::Value::Complex(value) if value.im.abs() < PI_EPS => format_float(value.re),
+        symbol_expr::Value::Complex(value) => error(format!(
+            "OpenQASM 2 cannot represent the complex value '{value}'"
+        )),
+    }
+}
+
+fn format_param(param: &Param) -> ExporterResult<String> {
+    match param {
+        Param::Float(value) => format_float(*value),
+        Param::ParameterExpression(ob) => Python::with_gil(|py| {
+            let ob = ob.bind(py);
+            // This is either the Rust-space expression itself, or a Python-space
+            // `ParameterExpression` wrapping one.
+            let expr = match ob.extract::<ParameterExpression>() {
+                Ok(expr) => expr,
+                Err(_) => ob
+                    .getattr(intern!(py, "_symbol_expr"))?
+                    .extract::<ParameterExpression>()?,
+            };
+            format_symbol_expr(expr.expr())
+        }),
+        Param::Symbolic(expr) => format_symbol_expr(expr.expr()),
+        Param::Obj(ob) => Python::with_gil(|py| {
+            let ob = ob.bind(py);
+            match ob.extract::<i64>() {
+                Ok(value) => Ok(value.to_string()),
+                Err(_) => error(format!(
+                    "OpenQASM 2 cannot represent the parameter '{}'",
+                    ob.repr()?
+                )),
+            }
+        }),
+    }
+}
+
+/// The register and value that a legacy OpenQASM 2 `if` statement compares.
+struct Condition {
+    register: ClassicalRegister,
+    value: u64,
+}
+
+/// Strip any casts from an expression, which are irrelevant to the comparisons OpenQASM 2 can
+/// represent.
+fn uncast(expr: &expr::Expr) -> &expr::Expr {
+    match expr {
+        expr::Expr::Cast(cast) => uncast(&cast.operand),
+        expr => expr,
+    }
+}
+
+/// The mapping of the bits in a scope to their OpenQASM 2 names.
+struct Scope<'a> {
+    circuit: &'a CircuitData,
+    qubits: Vec<String>,
+    clbits: Vec<String>,
+}
+
+/// How to call an operation after it's been defined.
+struct CallSite {
+    name: String,
+    /// Whether the call site should include the parameters of the instruction.
+    params: bool,
+}
+
+struct Exporter {
+    /// The `gate` and `opaque` statements needed, keyed by the name they define.
+    definitions: IndexMap<String, String, ::ahash::RandomState>,
+    /// The names already used by a definition for a given operation name and set of parameters.
+    defined: HashMap<(String, Vec<String>), String>,
+    /// All names that can't be used for a new definition.
+    used_names: HashSet<String>,
+    /// The OpenQASM 2 names of the classical registers of the outermost circuit.
+    cregs: HashMap<ClassicalRegister, String>,
+}
+
+impl Exporter {
+    fn new() -> Self {
+        Self {
+            definitions: IndexMap::default(),
+            defined: HashMap::new(),
+            used_names: EXISTING_GATE_NAMES
+                .iter()
+                .chain(RESERVED.iter())
+                .map(|name| name.to_string())
+                .collect(),
+            cregs: HashMap::new(),
+        }
+    }
+
+    /// Build the statements for an instruction.
+    fn statements(
+        &mut self,
+        scope: &Scope,
+        inst: &PackedInstruction,
+        out: &mut Vec<String>,
+    ) -> ExporterResult<()> {
+        let qubits = scope
+            .circuit
+            .get_qargs(inst.qubits)
+            .iter()
+            .map(|q| scope.qubits[q.index()].as_str())
+            .collect::<Vec<_>>();
+        let clbits = scope
+            .circuit
+            .get_cargs(inst.clbits)
+            .iter()
+            .map(|c| {
+                scope
+                    .clbits
+                    .get(c.index())
+                    .map(String::as_str)
+                    .ok_or_else(|| {
+                        QASM2ExporterError::Error(format!(
+                            "OpenQASM 2 cannot represent '{}' inside a gate definition",
+                            inst.op.name()
+                        ))
+                    })
+            })
+            .collect::<ExporterResult<Vec<_>>>()?;
+        match inst.op.view() {
+            OperationRef::StandardInstruction(StandardInstruction::Measure) => {
+                out.push(format!("measure {} -> {};", qubits[0], clbits[0]));
+            }
+            OperationRef::StandardInstruction(StandardInstruction::Reset) => {
+                out.push(format!("reset {};", qubits[0]));
+            }
+            OperationRef::StandardInstruction(StandardInstruction::Barrier(_)) => {
+                // Barriers with no operands are invalid in (strict) OQ2, and the statement would
+                // have no meaning anyway.
+                if !qubits.is_empty() {
+                    out.push(format!("barrier {};", qubits.join(",")));
+                }
+            }
+            OperationRef::Instruction(_) if inst.op.name() == "if_else" => {
+                self.if_statements(scope, inst, out)?;
+            }
+            _ if inst.op.control_flow() => {
+                return error(format!(
+                    "OpenQASM 2 cannot represent the control-flow operation '{}'",
+                    inst.op.name()
+                ));
+            }
+            _ => {
+                let call = self.define(inst)?;
+                let mut statement = call.name;
+                let params = inst.params_view();
+                if call.params && !params.is_empty() {
+                    let params = params
+                        .iter()
+                        .map(format_param)
+                        .collect::<ExporterResult<Vec<_>>>()?;
+                    write!(statement, "({})", params.join(",")).unwrap();
+                }
+                let bits = qubits.iter().chain(clbits.iter()).copied();
+                write!(statement, " {};", bits.collect::<Vec<_>>().join(",")).unwrap();
+                out.push(statement);
+            }
+        }
+        Ok(())
+    }
+
+    /// Build the statements for an `if_else` whose condition can be represented by a legacy
+    /// OpenQASM 2 `if` statement.  Each instruction in the body becomes its own `if` statement.
+    fn if_statements(
+        &mut self,
+        scope: &Scope,
+        inst: &PackedInstruction,
+        out: &mut Vec<String>,
+    ) -> ExporterResult<()> {
+        let condition = self.condition(scope, inst)?;
+        let register_name = self.cregs[&condition.register].clone();
+        let mut blocks = inst.op.blocks();
+        if blocks.len() != 1 {
+            return error("OpenQASM 2 cannot represent an 'if_else' with an 'else' branch".into());
+        }
+        let body = blocks.pop().unwrap();
+        let qubits = scope.circuit.get_qargs(inst.qubits);
+        let clbits = scope.circuit.get_cargs(inst.clbits);
+        let body_scope = Scope {
+            circuit: &body,
+            qubits: qubits
+                .iter()
+                .map(|q| scope.qubits[q.index()].clone())
+                .collect(),
+            clbits: clbits
+                .iter()
+                .map(|c| scope.clbits[c.index()].clone())
+                .collect(),
+        };
+        let prefix = format!("{register_name}[");
+        for body_inst in body.data() {
+            if body_inst.op.control_flow() || body_inst.op.directive() {
+                return error(format!(
+                    "OpenQASM 2 cannot condition the operation '{}'",
+                    body_inst.op.name()
+                ));
+            }
+            let writes_condition = body
+                .get_cargs(body_inst.clbits)
+                .iter()
+                .any(|c| body_scope.clbits[c.index()].starts_with(&prefix));
+            if writes_condition {
+                return error(format!(
+                    "OpenQASM 2 cannot represent a conditional body that writes to the condition \
+                     register '{}'",
+                    condition.register.name()
+                ));
+            }
+            let mut statements = Vec::new();
+            self.statements(&body_scope, body_inst, &mut statements)?;
+            for statement in statements {
+                out.push(format!(
+                    "if({register_name}=={}) {statement}",
+                    condition.value
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the condition of an `if_else` as a comparison of a whole register to an integer.
+    fn condition(&self, scope: &Scope, inst: &PackedInstruction) -> ExporterResult<Condition> {
+        let OperationRef::Instruction(instruction) = inst.op.view() else {
+            unreachable!("'if_else' is always a Python-space instruction")
+        };
+        let unrepresentable =
+            || error("OpenQASM 2 can only represent conditions on whole registers".into());
+        // A condition on a single bit can be represented if the bit is the only bit of a register.
+        let bit_register = |bit: &ShareableClbit| -> Option<ClassicalRegister> {
+            scope
+                .circuit
+                .clbit_indices()
+                .get(bit)?
+                .registers()
+                .iter()
+                .map(|(register, _)| register)
+                .find(|register| register.len() == 1 && self.cregs.contains_key(*register))
+                .cloned()
+        };
+        let (register, value) = Python::with_gil(|py| -> ExporterResult<_> {
+            let condition = instruction
+                .instruction
+                .bind(py)
+                .getattr(intern!(py, "condition"))?;
+            if let Ok((target, value)) = condition.extract::<(Bound<PyAny>, u64)>() {
+                if let Ok(register) = target.extract::<ClassicalRegister>() {
+                    return Ok((Some(register), value));
+                }
+                let bit = target.extract::<ShareableClbit>()?;
+                return Ok((bit_register(&bit), value));
+            }
+            Ok(match uncast(&condition.extract::<expr::Expr>()?) {
+                expr::Expr::Var(expr::Var::Bit { bit }) => (bit_register(bit), 1),
+                expr::Expr::Binary(binary) if binary.op == expr::BinaryOp::Equal => {
+                    match (uncast(&binary.left), uncast(&binary.right)) {
+                        (
+                            expr::Expr::Var(expr::Var::Register { register, .. }),
+                            expr::Expr::Value(expr::Value::Uint { raw, .. }),
+                        )
+                        | (
+                            expr::Expr::Value(expr::Value::Uint { raw, .. }),
+                            expr::Expr::Var(expr::Var::Register { register, .. }),
+                        ) => (Some(register.clone()), *raw),
+                        _ => (None, 0),
+                    }
+                }
+                _ => (None, 0),
+            })
+        })?;
+        match register {
+            Some(register) if self.cregs.contains_key(&register) => {
+                Ok(Condition { register, value })
+            }
+            _ => unrepresentable(),
+        }
+    }
+
+    /// Ensure that the operation of an instruction is defined, and get the name to call it by.
+    fn define(&mut self, inst: &PackedInstruction) -> ExporterResult<CallSite> {
+        let op = &inst.op;
+        if let Some(gate) = op.try_standard_gate() {
+            if let Some(name) = qelib1_name(gate) {
+                return Ok(CallSite {
+                    name: name.to_owned(),
+                    params: true,
+                });
+            }
+        } else if EXISTING_GATE_NAMES.contains(&op.name()) {
+            return Ok(CallSite {
+                name: op.name().to_owned(),
+                params: true,
+            });
+        }
+        if let OperationRef::Unitary(_) = op.view() {
+            return error(
+                "OpenQASM 2 cannot represent 'unitary' without first synthesizing it into gates"
+                    .into(),
+            );
+        }
+        if op.num_qubits() == 0 {
+            return error(format!(
+                "OpenQASM 2 cannot represent '{}', which acts on zero qubits.",
+                op.name()
+            ));
+        }
+        if op.num_clbits() != 0 {
+            return error(format!(
+                "OpenQASM 2 cannot represent '{}', which acts on {} classical bits.",
+                op.name(),
+                op.num_clbits()
+            ));
+        }
+        let params = inst.params_view();
+        let qubits_qasm = (0..op.num_qubits())
+            .map(|i| format!("q{i}"))
+            .collect::<Vec<_>>()
+            .join(",");
+        let Some(definition) = op.definition(params) else {
+            // Opaque operations are declared once with formal parameters.
+            let key = (op.name().to_owned(), vec![params.len().to_string()]);
+            if let Some(name) = self.defined.get(&key) {
+                return Ok(CallSite {
+                    name: name.clone(),
+                    params: true,
+                });
+            }
+            let name = self.new_name(op.name());
+            let params_qasm = if params.is_empty() {
+                String::new()
+            } else {
+                let formal = (0..params.len()).map(|i| format!("param{i}"));
+                format!("({})", formal.collect::<Vec<_>>().join(","))
+            };
+            self.definitions.insert(
+                name.clone(),
+                format!("opaque {name}{params_qasm} {qubits_qasm};"),
+            );
+            self.defined.insert(key, name.clone());
+            return Ok(CallSite { name, params: true });
+        };
+        let key = (
+            op.name().to_owned(),
+            params
+                .iter()
+                .map(format_param)
+                .collect::<ExporterResult<Vec<_>>>()?,
+        );
+        if let Some(name) = self.defined.get(&key) {
+            return Ok(CallSite {
+                name: name.clone(),
+                params: false,
+            });
+        }
+        // The name is reserved before the body is built, so inner operations with the same name
+        // get a different one.
+        let name = self.new_name(op.name());
+        let scope = Scope {
+            circuit: &definition,
+            qubits: (0..definition.num_qubits())
+                .map(|i| format!("q{i}"))
+                .collect(),
+            clbits: Vec::new(),
+        };
+        let mut body = Vec::new();
+        for inst in definition.data() {
+            self.statements(&scope, inst, &mut body)?;
+        }
+        self.definitions.insert(
+            name.clone(),
+            format!("gate {name} {qubits_qasm} {{ {} }}", body.join(" ")),
+        );
+        self.defined.insert(key, name.clone());
+        Ok(CallSite {
+            name,
+            params: false,
+        })
+    }
+
+    fn new_name(&mut self, name: &str) -> String {
+        let name = make_unique(escape_name(name, "gate_"), &self.used_names);
+        self.used_names.insert(name.clone());
+        name
+    }
+}
+
+/// Name the registers of a circuit, adding a dummy register for any loose bits, and get the
+/// OpenQASM 2 label of each bit.
+fn name_registers<B, R>(
+    bits: &[B],
+    registers: &[R],
+    has_register: impl Fn(&B) -> bool,
+    dummy: impl FnOnce(Vec<B>) -> R,
+    used_names: &mut HashSet<String>,
+) -> (Vec<(String, R)>, Vec<String>)
+where
+    B: Clone + Eq + std::hash::Hash,
+    R: Register<Bit = B> + Clone,
+{
+    let loose = bits
+        .iter()
+        .filter(|bit| !has_register(bit))
+        .cloned()
+        .collect::<Vec<_>>();
+    let dummy = (!loose.is_empty()).then(|| dummy(loose));
+    let named = registers
+        .iter()
+        .chain(dummy.iter())
+        .map(|register| {
+            let name = make_unique(escape_name(register.name(), "reg_"), used_names);
+            used_names.insert(name.clone());
+            (name, register.clone())
+        })
+        .collect::<Vec<_>>();
+    let indices = bits
+        .iter()
+        .enumerate()
+        .map(|(index, bit)| (bit, index))
+        .collect::<HashMap<_, _>>();
+    let mut labels = vec![String::new(); bits.len()];
+    for (name, register) in named.iter() {
+        for (i, bit) in register.bits().enumerate() {
+            if let Some(index) = indices.get(&bit) {
+                labels[*index] = format!("{name}[{i}]");
+            }
+        }
+    }
+    (named, labels)
+}
+
+/// Export a circuit to an OpenQASM 2 program.
+///
+/// The circuit must not contain unbound parameters.  The output has no trailing newline.
+///
+/// Circuits made only of Rust-space operations with numeric parameters are exported without
+/// Python.
+pub fn dumps(circuit: &CircuitData) -> ExporterResult<String> {
+    if circuit.num_parameters() > 0 {
+        return error("Cannot represent circuits with unbound parameters in OpenQASM 2.".into());
+    }
+    let mut register_names = HashSet::new();
+    let (qregs, qubit_labels) = name_registers(
+        circuit.qubits().objects(),
+        circuit.qregs(),
+        |bit: &ShareableQubit| {
+            circuit
+                .qubit_indices()
+                .get(bit)
+                .is_some_and(|locations| !locations.registers().is_empty())
+        },
+        |bits| QuantumRegister::new_alias(Some("qregless".to_owned()), bits),
+        &mut register_names,
+    );
+    let (cregs, clbit_labels) = name_registers(
+        circuit.clbits().objects(),
+        circuit.cregs(),
+        |bit: &ShareableClbit| {
+            circuit
+                .clbit_indices()
+                .get(bit)
+                .is_some_and(|locations| !locations.registers().is_empty())
+        },
+        |bits| ClassicalRegister::new_alias(Some("cregless".to_owned()), bits),
+        &mut register_names,
+    );
+
+    let mut exporter = Exporter::new();
+    exporter.cregs = cregs
+        .iter()
+        .map(|(name, register)| (register.clone(), name.clone()))
+        .collect();
+    let scope = Scope {
+        circuit,
+        qubits: qubit_labels,
+        clbits: clbit_labels,
+    };
+    let mut instructions = Vec::new();
+    for inst in circuit.data() {
+        exporter.statements(&scope, inst, &mut instructions)?;
+    }
+
+    let mut out = vec![
+        "OPENQASM 2.0;".to_owned(),
+        "include \"qelib1.inc\";".to_owned(),
+    ];
+    out.extend(exporter.definitions.into_values());
+    out.extend(
+        qregs
+            .iter()
+            .map(|(name, register)| format!("qreg {name}[{}];", register.len())),
+    );
+    out.extend(
+        cregs
+            .iter()
+            .map(|(name, register)| format!("creg {name}[{}];", register.len())),
+    );
+    out.extend(instructions);
+    Ok(out.join("\n"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::Arc;
+
+    use pyo3::types::PyDict;
+    use qiskit_circuit::operations::PyInstruction;
+    use qiskit_circuit::packed_instruction::PackedOperation;
+    use qiskit_circuit::{Clbit, Qubit};
+
+    fn real(value: f64) -> Arc<SymbolExpr> {
+        Arc::new(SymbolExpr::Value(symbol_expr::Value::Real(value)))
+    }
+
+    fn binary(op: BinaryOp, lhs: Arc<SymbolExpr>, rhs: Arc<SymbolExpr>) -> Arc<SymbolExpr> {
+        Arc::new(SymbolExpr::Binary { op, lhs, rhs })
+    }
+
+    fn unary(op: UnaryOp, expr: Arc<SymbolExpr>) -> Arc<SymbolExpr> {
+        Arc::new(SymbolExpr::Unary { op, expr })
+    }
+
+    fn expression(py: Python, expr: Arc<SymbolExpr>) -> Param {
+        let expr = ParameterExpression::from(Arc::unwrap_or_clone(expr));
+        Param::ParameterExpression(Py::new(py, expr).unwrap().into_any())
+    }
+
+    /// A Python-space `if_else` instruction with a legacy `(target, value)` condition.  Only the
+    /// attributes the exporter reads are defined.
+    fn if_else(py: Python, target: Bound<PyAny>, value: u64, body: CircuitData) -> PackedOperation {
+        let (num_qubits, num_clbits) = (body.num_qubits(), body.num_clbits());
+        let locals = PyDict::new(py);
+        locals.set_item("target", target).unwrap();
+        locals.set_item("value", value).unwrap();
+        locals.set_item("body", Py::new(py, body).unwrap()).unwrap();
+        let instruction = py
+            .eval(
+                c"__import__('types').SimpleNamespace(condition=(target, value), \
+                  blocks=(__import__('types').SimpleNamespace(_data=body),))",
+                None,
+                Some(&locals),
+            )
+            .unwrap()
+            .unbind();
+        Box::new(PyInstruction {
+            qubits: num_qubits as u32,
+            clbits: num_clbits as u32,
+            params: 0,
+            op_name: "if_else".to_owned(),
+            control_flow: true,
+            instruction,
+        })
+        .into()
+    }
+
+    #[test]
+    fn test_symbolic_parameters() {
+        Python::with_gil(|py| {
+            use std::f64::consts::PI;
+
+            let mut circuit = CircuitData::with_capacity(0, 0, 0, Param::Float(0.0)).unwrap();
+            circuit
+                .add_qreg(QuantumRegister::new_owning("q", 1), true)
+                .unwrap();
+            let params = [
+                binary(BinaryOp::Mul, real(2.0), unary(UnaryOp::Cos, real(0.5))),
+                unary(
+                    UnaryOp::Neg,
+                    binary(BinaryOp::Add, real(PI / 2.0), real(1.0)),
+                ),
+                binary(
+                    BinaryOp::Div,
+                    unary(UnaryOp::Exp, real(1.0)),
+                    binary(BinaryOp::Pow, real(2.0), real(3.0)),
+                ),
+                // There's no OpenQASM 2 function for `abs`, so it's evaluated.
+                unary(UnaryOp::Abs, real(-3.0 * PI / 4.0)),
+            ];
+            for param in params {
+                circuit.push_standard_gate(StandardGate::RZ, &[expression(py, param)], &[Qubit(0)]);
+            }
+            assert_eq!(
+                dumps(&circuit).unwrap(),
+                [
+                    "OPENQASM 2.0;",
+                    "include \"qelib1.inc\";",
+                    "qreg q[1];",
+                    "rz(2.0*cos(0.5)) q[0];",
+                    "rz(-(pi/2+1.0)) q[0];",
+                    "rz(exp(1.0)/(2.0^3.0)) q[0];",
+                    "rz(3*pi/4) q[0];",
+                ]
+                .join("\n")
+            );
+        });
+    }
+
+    #[test]
+    fn test_symbol_in_expression_is_an_error() {
+        Python::with_gil(|py| {
+            let mut circuit = CircuitData::with_capacity(0, 0, 0, Param::Float(0.0)).unwrap();
+            circuit
+                .add_qreg(QuantumRegister::new_owning("q", 1), true)
+                .unwrap();
+            let symbol = Arc::new(SymbolExpr::Symbol(Arc::new("theta".to_owned())));
+            circuit.push_standard_gate(
+                StandardGate::RZ,
+                &[expression(py, binary(BinaryOp::Mul, real(2.0), symbol))],
+                &[Qubit(0)],
+            );
+            let Err(QASM2ExporterError::Error(message)) = dumps(&circuit) else {
+                panic!("exporting an unbound parameter should fail");
+            };
+            assert!(message.contains("unbound parameters"), "{message}");
+        });
+    }
+
+    #[test]
+    fn test_legacy_if_conditions() {
+        Python::with_gil(|py| {
+            let mut circuit = CircuitData::with_capacity(0, 0, 0, Param::Float(0.0)).unwrap();
+            circuit
+                .add_qreg(QuantumRegister::new_owning("q", 2), true)
+                .unwrap();
+            let cr = ClassicalRegister::new_owning("cr", 2);
+            let flag = ClassicalRegister::new_owning("flag", 1);
+            circuit.add_creg(cr.clone(), true).unwrap();
+            circuit.add_creg(flag.clone(), true).unwrap();
+
+            // A condition on a whole register, with a body of several instructions.
+            let mut body = CircuitData::with_capacity(2, 2, 0, Param::Float(0.0)).unwrap();
+            body.push_standard_gate(StandardGate::X, &[], &[Qubit(0)]);
+            body.push_standard_gate(StandardGate::CX, &[], &[Qubit(0), Qubit(1)]);
+            let op = if_else(py, cr.into_pyobject(py).unwrap().into_any(), 2, body);
+            circuit.push_packed_operation(op, &[], &[Qubit(0), Qubit(1)], &[Clbit(0), Clbit(1)]);
+
+            // A condition on the only bit of a register.
+            let mut body = CircuitData::with_capacity(1, 1, 0, Param::Float(0.0)).unwrap();
+            body.push_standard_gate(StandardGate::RZ, &[Param::Float(0.25)], &[Qubit(0)]);
+            let bit = flag.get(0).unwrap();
+            let op = if_else(py, bit.into_pyobject(py).unwrap().into_any(), 1, body);
+            circuit.push_packed_operation(op, &[], &[Qubit(1)], &[Clbit(2)]);
+
+            assert_eq!(
+                dumps(&circuit).unwrap(),
+                [
+                    "OPENQASM 2.0;",
+                    "include \"qelib1.inc\";",
+                    "qreg q[2];",
+                    "creg cr[2];",
+                    "creg flag[1];",
+                    "if(cr==2) x q[0];",
+                    "if(cr==2) cx q[0],q[1];",
+                    "if(flag==1) rz(0.25) q[1];",
+                ]
+                .join("\n")
+            );
+        });
+    }
+
+    #[test]
+    fn test_legacy_if_condition_on_partial_register() {
+        Python::with_gil(|py| {
+            let mut circuit = CircuitData::with_capacity(0, 0, 0, Param::Float(0.0)).unwrap();
+            circuit
+                .add_qreg(QuantumRegister::new_owning("q", 1), true)
+                .unwrap();
+            let cr = ClassicalRegister::new_owning("cr", 2);
+            circuit.add_creg(cr.clone(), true).unwrap();
+            let mut body = CircuitData::with_capacity(1, 1, 0, Param::Float(0.0)).unwrap();
+            body.push_standard_gate(StandardGate::X, &[], &[Qubit(0)]);
+            let bit = cr.get(1).unwrap();
+            let op = if_else(py, bit.into_pyobject(py).unwrap().into_any(), 1, body);
+            circuit.push_packed_operation(op, &[], &[Qubit(0)], &[Clbit(1)]);
+            let Err(QASM2ExporterError::Error(message)) = dumps(&circuit) else {
+                panic!("a condition on part of a register can't be represented");
+            };
+            assert!(message.contains("whole registers"), "{message}");
+        });
+    }
+}
@@ -21,9 +21,7 @@ use pyo3::prelude::*;
 use pyo3::types::PyTuple;
 
 use crate::bytecode;
-use crate::error::{
-    message_bad_eof, message_generic, message_incorrect_requirement, Position, QASM2ParseError,
-};
+use crate::error::{error_bad_eof, error_incorrect_requirement, ParseError, ParseResult, Position};
 use crate::lex::{Token, TokenContext, TokenStream, TokenType};
 use crate::parse::{GateSymbol, GlobalSymbol, ParamId};
 
@@ -265,7 +263,7 @@ impl ExprParser<'_> {
     /// Get the next token available in the stack of token streams, popping and removing any
     /// complete streams, except the base case.  Will only return `None` once all streams are
     /// exhausted.
-    fn next_token(&mut self) -> PyResult<Option<Token>> {
+    fn next_token(&mut self) -> ParseResult<Option<Token>> {
         let mut pointer = self.tokens.len() - 1;
         while pointer > 1 {
             let out = self.tokens[pointer].next(self.context)?;
@@ -280,7 +278,7 @@ impl ExprParser<'_> {
 
     /// Peek the next token in the stack of token streams.  This does not remove any complete
     /// streams yet.  Will only return `None` once all streams are exhausted.
-    fn peek_token(&mut self) -> PyResult<Option<&Token>> {
+    fn peek_token(&mut self) -> ParseResult<Option<&Token>> {
         let mut pointer = self.tokens.len() - 1;
         while pointer > 1 && self.tokens[pointer].peek(self.context)?.is_none() {
             pointer -= 1;
@@ -296,34 +294,34 @@ impl ExprParser<'_> {
     /// Expect a token of the correct [TokenType].  This is a direct analogue of
     /// [parse::State::expect].  The error variant of the result contains a suitable error message
     /// if the expectation is violated.
-    fn expect(&mut self, expected: TokenType, required: &str, cause: &Token) -> PyResult<Token> {
+    fn expect(&mut self, expected: TokenType, required: &str, cause: &Token) -> ParseResult<Token> {
         let token = match self.next_token()? {
             None => {
-                return Err(QASM2ParseError::new_err(message_bad_eof(
+                return Err(error_bad_eof(
                     Some(&Position::new(
                         self.current_filename(),
                         cause.line,
                         cause.col,
                     )),
                     required,
-                )))
+                ))
             }
             Some(token) => token,
         };
         if token.ttype == expected {
             Ok(token)
         } else {
-            Err(QASM2ParseError::new_err(message_incorrect_requirement(
+            Err(error_incorrect_requirement(
                 required,
                 &token,
                 self.current_filename(),
-            )))
+            ))
         }
     }
 
     /// Peek the next token from the stream, and consume and return it only if it has the correct
     /// type.
-    fn accept(&mut self, acceptable: TokenType) -> PyResult<Option<Token>> {
+    fn accept(&mut self, acceptable: TokenType) -> ParseResult<Option<Token>> {
         match self.peek_token()? {
             Some(Token { ttype, .. }) if *ttype == acceptable => self.next_token(),
             _ => Ok(None),
@@ -333,7 +331,7 @@ impl ExprParser<'_> {
     /// Apply a prefix [Op] to the current [expression][Expr].  If the current expression is a
     /// constant floating-point value the application will be eagerly constant-folded, otherwise
     /// the resulting [Expr] will have a tree structure.
-    fn apply_prefix(&mut self, prefix: Op, expr: Expr) -> PyResult<Expr> {
+    fn apply_prefix(&mut self, prefix: Op, expr: Expr) -> ParseResult<Expr> {
         match prefix {
             Op::Plus => Ok(expr),
             Op::Minus => match expr {
@@ -347,17 +345,23 @@ impl ExprParser<'_> {
     /// Apply a binary infix [Op] to the current [expression][Expr].  If both operands have
     /// constant floating-point values the application will be eagerly constant-folded, otherwise
     /// the resulting [Expr] will have a tree structure.
-    fn apply_infix(&mut self, infix: Op, lhs: Expr, rhs: Expr, op_token: &Token) -> PyResult<Expr> {
+    fn apply_infix(
+        &mut self,
+        infix: Op,
+        lhs: Expr,
+        rhs: Expr,
+        op_token: &Token,
+    ) -> ParseResult<Expr> {
         if let (Expr::Constant(val), Op::Divide) = (&rhs, infix) {
             if *val == 0.0 {
-                return Err(QASM2ParseError::new_err(message_generic(
+                return Err(ParseError::new(
                     Some(&Position::new(
                         self.current_filename(),
                         op_token.line,
                         op_token.col,
                     )),
                     "cannot divide by zero",
-                )));
+                ));
             }
         };
         if let (Expr::Constant(val_l), Expr::Constant(val_r)) = (&lhs, &rhs) {
@@ -386,7 +390,7 @@ impl ExprParser<'_> {
     /// Apply a "scientific calculator" built-in function to an [expression][Expr].  If the operand
     /// is a constant, the function will be constant-folded to produce a new constant expression,
     /// otherwise a tree-form [Expr] is returned.
-    fn apply_function(&mut self, func: Function, expr: Expr, token: &Token) -> PyResult<Expr> {
+    fn apply_function(&mut self, func: Function, expr: Expr, token: &Token) -> ParseResult<Expr> {
         match expr {
             Expr::Constant(val) => match func {
                 Function::Cos => Ok(Expr::Constant(val.cos())),
@@ -395,7 +399,7 @@ impl ExprParser<'_> {
                     if val > 0.0 {
                         Ok(Expr::Constant(val.ln()))
                     } else {
-                        Err(QASM2ParseError::new_err(message_generic(
+                        Err(ParseError::new(
                             Some(&Position::new(
                                 self.current_filename(),
                                 token.line,
@@ -404,7 +408,7 @@ impl ExprParser<'_> {
                             &format!(
                                 "failure in constant folding: cannot take ln of non-positive {val}"
                             ),
-                        )))
+                        ))
                     }
                 }
                 Function::Sin => Ok(Expr::Constant(val.sin())),
@@ -412,7 +416,7 @@ impl ExprParser<'_> {
                     if val >= 0.0 {
                         Ok(Expr::Constant(val.sqrt()))
                     } else {
-                        Err(QASM2ParseError::new_err(message_generic(
+                        Err(ParseError::new(
                             Some(&Position::new(
                                 self.current_filename(),
                                 token.line,
@@ -421,7 +425,7 @@ impl ExprParser<'_> {
                             &format!(
                                 "failure in constant folding: cannot take sqrt of negative {val}"
                             ),
-                        )))
+                        ))
                     }
                 }
                 Function::Tan => Ok(Expr::Constant(val.tan())),
@@ -435,7 +439,7 @@ impl ExprParser<'_> {
         callable: PyObject,
         exprs: Vec<Expr>,
         token: &Token,
-    ) -> PyResult<Expr> {
+    ) -> ParseResult<Expr> {
         if exprs.iter().all(|x| matches!(x, Expr::Constant(_))) {
             // We can still do constant folding with custom user classical functions, we're just
             // going to have to acquire the GIL and call the Python object the user gave us right
@@ -456,17 +460,17 @@ impl ExprParser<'_> {
                         match retval.extract::<f64>(py) {
                             Ok(fval) => Ok(Expr::Constant(fval)),
                             Err(inner) => {
-                                let error = QASM2ParseError::new_err(message_generic(
+                                let error = PyErr::from(ParseError::new(
                                 Some(&Position::new(self.current_filename(), token.line, token.col)),
                                 "user-defined function returned non-float during constant folding",
                             ));
                                 error.set_cause(py, Some(inner));
-                                Err(error)
+                                Err(error.into())
                             }
                         }
                     }
                     Err(inner) => {
-                        let error = QASM2ParseError::new_err(message_generic(
+                        let error = PyErr::from(ParseError::new(
                             Some(&Position::new(
                                 self.current_filename(),
                                 token.line,
@@ -475,7 +479,7 @@ impl ExprParser<'_> {
                             "caught exception when constant folding with user-defined function",
                         ));
                         error.set_cause(py, Some(inner));
-                        Err(error)
+                        Err(error.into())
                     }
                 }
             })
@@ -485,16 +489,16 @@ impl ExprParser<'_> {
     }
 
     /// If in `strict` mode, and we have a trailing comma, emit a suitable error message.
-    fn check_trailing_comma(&self, comma: Option<&Token>) -> PyResult<()> {
+    fn check_trailing_comma(&self, comma: Option<&Token>) -> ParseResult<()> {
         match (self.strict, comma) {
-            (true, Some(token)) => Err(QASM2ParseError::new_err(message_generic(
+            (true, Some(token)) => Err(ParseError::new(
                 Some(&Position::new(
                     self.current_filename(),
                     token.line,
                     token.col,
                 )),
                 "[strict] trailing commas in parameter and qubit lists are forbidden",
-            ))),
+            )),
             _ => Ok(()),
         }
     }
@@ -503,7 +507,7 @@ impl ExprParser<'_> {
     /// Not all [Token]s have a corresponding [Atom]; if this is the case, the return value is
     /// `Ok(None)`.  The error variant is returned if the next token is grammatically valid, but
     /// not semantically, such as an identifier for a value of an incorrect type.
-    fn try_atom_from_token(&self, token: &Token) -> PyResult<Option<Atom>> {
+    fn try_atom_from_token(&self, token: &Token) -> ParseResult<Option<Atom>> {
         match token.ttype {
             TokenType::LParen => Ok(Some(Atom::LParen)),
             TokenType::RParen => Ok(Some(Atom::RParen)),
@@ -531,10 +535,10 @@ impl ExprParser<'_> {
                 match self.gate_symbols.get(id) {
                     Some(GateSymbol::Parameter { index }) => Ok(Some(Atom::Parameter(*index))),
                     Some(GateSymbol::Qubit { .. }) => {
-                        Err(QASM2ParseError::new_err(message_generic(
+                        Err(ParseError::new(
                             Some(&Position::new(self.current_filename(), token.line, token.col)),
                             &format!("'{id}' is a gate qubit, not a parameter"),
-                        )))
+                        ))
                     }
                     None => {
                         match self.global_symbols.get(id) {
@@ -542,11 +546,11 @@ impl ExprParser<'_> {
                                 Ok(Some(Atom::CustomFunction(callable.clone(), *num_params)))
                             }
                             _ =>  {
-                            Err(QASM2ParseError::new_err(message_generic(
+                            Err(ParseError::new(
                             Some(&Position::new(self.current_filename(), token.line, token.col)),
                             &format!(
                                 "'{id}' is not a parameter or custom instruction defined in this scope",
-                            ))))
+                            )))
                             }
                     }
                 }
@@ -559,7 +563,7 @@ impl ExprParser<'_> {
     /// Peek at the next [Atom] (and backing [Token]) if the next token exists and can be converted
     /// into a valid [Atom].  If it can't, or if we are at the end of the input, the `None` variant
     /// is returned.
-    fn peek_atom(&mut self) -> PyResult<Option<(Atom, Token)>> {
+    fn peek_atom(&mut self) -> ParseResult<Option<(Atom, Token)>> {
         if let Some(&token) = self.peek_token()? {
             if let Ok(Some(atom)) = self.try_atom_from_token(&token) {
                 Ok(Some((atom, token)))
@@ -578,9 +582,9 @@ impl ExprParser<'_> {
     /// expression), then as many `*` and `^` operations as appear would be evaluated by this loop,
     /// and its parsing would finish when it saw the next `+` binary operation.  For initial entry,
     /// the `power_min` should be zero.
-    fn eval_expression(&mut self, power_min: u8, cause: &Token) -> PyResult<Expr> {
+    fn eval_expression(&mut self, power_min: u8, cause: &Token) -> ParseResult<Expr> {
         let token = self.next_token()?.ok_or_else(|| {
-            QASM2ParseError::new_err(message_bad_eof(
+            error_bad_eof(
                 Some(&Position::new(
                     self.current_filename(),
                     cause.line,
@@ -591,10 +595,10 @@ impl ExprParser<'_> {
                 } else {
                     "a missing operand"
                 },
-            ))
+            )
         })?;
         let atom = self.try_atom_from_token(&token)?.ok_or_else(|| {
-            QASM2ParseError::new_err(message_incorrect_requirement(
+            error_incorrect_requirement(
                 if power_min == 0 {
                     "an expression"
                 } else {
@@ -602,7 +606,7 @@ impl ExprParser<'_> {
                 },
                 &token,
                 self.current_filename(),
-            ))
+            )
         })?;
         // First evaluate the "left-hand side" of a (potential) sequence of binary infix operators.
         // This might be a simple value, a unary operator acting on a value, or a bracketed
@@ -618,23 +622,23 @@ impl ExprParser<'_> {
             }
             Atom::RParen => {
                 if power_min == 0 {
-                    Err(QASM2ParseError::new_err(message_generic(
+                    Err(ParseError::new(
                         Some(&Position::new(
                             self.current_filename(),
                             token.line,
                             token.col,
                         )),
                         "did not find an expected expression",
-                    )))
+                    ))
                 } else {
-                    Err(QASM2ParseError::new_err(message_generic(
+                    Err(ParseError::new(
                         Some(&Position::new(
                             self.current_filename(),
                             token.line,
                             token.col,
                         )),
                         "the parenthesis closed, but there was a missing operand",
-                    )))
+                    ))
                 }
             }
             Atom::Function(func) => {
@@ -670,7 +674,7 @@ impl ExprParser<'_> {
                 if arguments.len() == num_params {
                     Ok(self.apply_custom_function(callable, arguments, &token)?)
                 } else {
-                    Err(QASM2ParseError::new_err(message_generic(
+                    Err(ParseError::new(
                         Some(&Position::new(
                             self.current_filename(),
                             token.line,
@@ -681,7 +685,7 @@ impl ExprParser<'_> {
                             num_params,
                             arguments.len(),
                         ),
-                    )))
+                    ))
                 }
             }
             Atom::Op(op) => match prefix_power(op) {
@@ -689,14 +693,14 @@ impl ExprParser<'_> {
                     let expr = self.eval_expression(power, &token)?;
                     Ok(self.apply_prefix(op, expr)?)
                 }
-                None => Err(QASM2ParseError::new_err(message_generic(
+                None => Err(ParseError::new(
                     Some(&Position::new(
                         self.current_filename(),
                         token.line,
                         token.col,
                     )),
                     &format!("'{}' is not a valid unary operator", op.text()),
-                ))),
+                )),
             },
             Atom::Const(val) => Ok(Expr::Constant(val)),
             Atom::Parameter(val) => Ok(Expr::Parameter(val)),
@@ -724,7 +728,7 @@ impl ExprParser<'_> {
     ///
     ///     This evaluates in a floating-point context, including evaluating integer tokens, since
     ///     the only places that expressions are valid in OpenQASM 2 is during gate applications.
-    pub fn parse_expression(&mut self, cause: &Token) -> PyResult<Expr> {
+    pub fn parse_expression(&mut self, cause: &Token) -> ParseResult<Expr> {
         self.eval_expression(0, cause)
     }
 }
@@ -25,11 +25,10 @@
 
 use hashbrown::HashMap;
 use num_bigint::BigUint;
-use pyo3::prelude::PyResult;
 
 use std::path::Path;
 
-use crate::error::{message_generic, Position, QASM2ParseError};
+use crate::error::{ParseError, ParseResult, Position};
 
 /// Tokenized version information data.  This is more structured than the real number suggested by
 /// the specification.
@@ -393,7 +392,7 @@ impl TokenStream {
 
     /// Read the next line into the managed buffer in the struct, updating the tracking information
     /// of the position, and the `done` state of the iterator.
-    fn advance_line(&mut self) -> PyResult<usize> {
+    fn advance_line(&mut self) -> ParseResult<usize> {
         if self.done {
             Ok(0)
         } else {
@@ -411,10 +410,10 @@ impl TokenStream {
                 }
                 Err(err) => {
                     self.done = true;
-                    Err(QASM2ParseError::new_err(message_generic(
+                    Err(ParseError::new(
                         Some(&Position::new(&self.filename, self.line, self.col)),
                         &format!("lexer failed to read stream: {err}"),
-                    )))
+                    ))
                 }
             }
         }
@@ -422,7 +421,7 @@ impl TokenStream {
 
     /// Get the next character in the stream.  This updates the line and column information for the
     /// current byte as well.
-    fn next_byte(&mut self) -> PyResult<Option<u8>> {
+    fn next_byte(&mut self) -> ParseResult<Option<u8>> {
         if self.col >= self.line_buffer.len() && self.advance_line()? == 0 {
             return Ok(None);
         }
@@ -430,11 +429,15 @@ impl TokenStream {
         self.col += 1;
         match out {
             b @ 0x80..=0xff => {
+                let position = Position::new(&self.filename, self.line, self.col);
+                // A non-ASCII byte most likely means this isn't an OpenQASM 2 file at all, so we
+                // don't try to lex any further.
                 self.done = true;
-                Err(QASM2ParseError::new_err(message_generic(
-                    Some(&Position::new(&self.filename, self.line, self.col)),
+                self.col = self.line_buffer.len();
+                Err(ParseError::new(
+                    Some(&position),
                     &format!("encountered a non-ASCII byte: {b:02X?}"),
-                )))
+                ))
             }
             b => Ok(Some(b)),
         }
@@ -443,17 +446,21 @@ impl TokenStream {
     /// Peek at the next byte in the stream without consuming it.  This still returns an error if
     /// the next byte isn't in the valid range for OpenQASM 2, or if the file/stream has failed to
     /// read into the buffer for some reason.
-    fn peek_byte(&mut self) -> PyResult<Option<u8>> {
+    fn peek_byte(&mut self) -> ParseResult<Option<u8>> {
         if self.col >= self.line_buffer.len() && self.advance_line()? == 0 {
             return Ok(None);
         }
         match self.line_buffer[self.col] {
             b @ 0x80..=0xff => {
+                let position = Position::new(&self.filename, self.line, self.col);
+                // A non-ASCII byte most likely means this isn't an OpenQASM 2 file at all, so we
+                // don't try to lex any further.
                 self.done = true;
-                Err(QASM2ParseError::new_err(message_generic(
-                    Some(&Position::new(&self.filename, self.line, self.col)),
+                self.col = self.line_buffer.len();
+                Err(ParseError::new(
+                    Some(&position),
                     &format!("encountered a non-ASCII byte: {b:02X?}"),
-                )))
+                ))
             }
             b => Ok(Some(b)),
         }
@@ -461,17 +468,15 @@ impl TokenStream {
 
     /// Expect that the next byte is not a word continuation, providing a suitable error message if
     /// it is.
-    fn expect_word_boundary(&mut self, after: &str, start_col: usize) -> PyResult<()> {
+    fn expect_word_boundary(&mut self, after: &str, start_col: usize) -> ParseResult<()> {
         match self.peek_byte()? {
-            Some(c @ (b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'_')) => {
-                Err(QASM2ParseError::new_err(message_generic(
-                    Some(&Position::new(&self.filename, self.line, start_col)),
-                    &format!(
-                        "expected a word boundary after {}, but saw '{}'",
-                        after, c as char
-                    ),
-                )))
-            }
+            Some(c @ (b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'_')) => Err(ParseError::new(
+                Some(&Position::new(&self.filename, self.line, start_col)),
+                &format!(
+                    "expected a word boundary after {}, but saw '{}'",
+                    after, c as char
+                ),
+            )),
             _ => Ok(()),
         }
     }
@@ -479,7 +484,7 @@ impl TokenStream {
     /// Complete the lexing of a floating-point value from the position of maybe accepting an
     /// exponent.  The previous part of the token must be a valid stand-alone float, or the next
     /// byte must already have been peeked and known to be `b'e' | b'E'`.
-    fn lex_float_exponent(&mut self, start_col: usize) -> PyResult<TokenType> {
+    fn lex_float_exponent(&mut self, start_col: usize) -> ParseResult<TokenType> {
         if !matches!(self.peek_byte()?, Some(b'e' | b'E')) {
             self.expect_word_boundary("a float", start_col)?;
             return Ok(TokenType::Real);
@@ -491,10 +496,10 @@ impl TokenStream {
         }
         // Exponents must have at least one digit in them.
         if !matches!(self.peek_byte()?, Some(b'0'..=b'9')) {
-            return Err(QASM2ParseError::new_err(message_generic(
+            return Err(ParseError::new(
                 Some(&Position::new(&self.filename, self.line, start_col)),
                 "needed to see an integer exponent for this float",
-            )));
+            ));
         }
         while let Some(b'0'..=b'9') = self.peek_byte()? {
             self.next_byte()?;
@@ -506,7 +511,7 @@ impl TokenStream {
     /// Lex a numeric token completely.  This can return a successful integer or a real number; the
     /// function distinguishes based on what it sees.  If `self.try_version`, this can also be a
     /// version identifier (will take precedence over either other type, if possible).
-    fn lex_numeric(&mut self, start_col: usize) -> PyResult<TokenType> {
+    fn lex_numeric(&mut self, start_col: usize) -> ParseResult<TokenType> {
         let first = self.line_buffer[start_col];
         if first == b'.' {
             return match self.next_byte()? {
@@ -518,10 +523,10 @@ impl TokenStream {
                     }
                     self.lex_float_exponent(start_col)
                 }
-                _ => Err(QASM2ParseError::new_err(message_generic(
+                _ => Err(ParseError::new(
                     Some(&Position::new(&self.filename, self.line, start_col)),
                     "expected a numeric fractional part after the bare decimal point",
-                ))),
+                )),
             };
         }
         while let Some(b'0'..=b'9') = self.peek_byte()? {
@@ -551,10 +556,10 @@ impl TokenStream {
             // languages will happily spit out something like `5e-5` when formatting floats.
             Some(b'e' | b'E') => {
                 return if self.strict {
-                    Err(QASM2ParseError::new_err(message_generic(
+                    Err(ParseError::new(
                         Some(&Position::new(&self.filename, self.line, start_col)),
                         "[strict] all floats must include a decimal point",
-                    )))
+                    ))
                 } else {
                     self.lex_float_exponent(start_col)
                 }
@@ -564,10 +569,10 @@ impl TokenStream {
         if first == b'0' && self.col - start_col > 1 {
             // Integers can't start with a leading zero unless they are only the single '0', but we
             // didn't see a decimal point.
-            Err(QASM2ParseError::new_err(message_generic(
+            Err(ParseError::new(
                 Some(&Position::new(&self.filename, self.line, start_col)),
                 "integers cannot have leading zeroes",
-            )))
+            ))
         } else if self.try_version {
             self.expect_word_boundary("a version identifier", start_col)?;
             Ok(TokenType::Version)
@@ -579,7 +584,7 @@ impl TokenStream {
 
     /// Lex a text-like token into a complete token.  This can return any of the keyword-like
     /// tokens (e.g. [TokenType::Pi]), or a [TokenType::Id] if the token is not a built-in keyword.
-    fn lex_textlike(&mut self, start_col: usize) -> PyResult<TokenType> {
+    fn lex_textlike(&mut self, start_col: usize) -> ParseResult<TokenType> {
         let first = self.line_buffer[start_col];
         while let Some(b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'_') = self.peek_byte()? {
             self.next_byte()?;
@@ -590,9 +595,9 @@ impl TokenStream {
             match text {
                 b"OPENQASM" => Ok(TokenType::OpenQASM),
                 b"U" | b"CX" => Ok(TokenType::Id),
-                _ => Err(QASM2ParseError::new_err(message_generic(
+                _ => Err(ParseError::new(
                         Some(&Position::new(&self.filename, self.line, start_col)),
-                        "identifiers cannot start with capital letters except for the builtins 'U' and 'CX'"))),
+                        "identifiers cannot start with capital letters except for the builtins 'U' and 'CX'")),
             }
         } else {
             match text {
@@ -619,20 +624,20 @@ impl TokenStream {
 
     /// Lex a filename token completely.  This is always triggered by seeing a `b'"'` byte in the
     /// input stream.
-    fn lex_filename(&mut self, terminator: u8, start_col: usize) -> PyResult<TokenType> {
+    fn lex_filename(&mut self, terminator: u8, start_col: usize) -> ParseResult<TokenType> {
         loop {
             match self.next_byte()? {
                 None => {
-                    return Err(QASM2ParseError::new_err(message_generic(
+                    return Err(ParseError::new(
                         Some(&Position::new(&self.filename, self.line, start_col)),
                         "unexpected end-of-file while lexing string literal",
-                    )))
+                    ))
                 }
                 Some(b'\n' | b'\r') => {
-                    return Err(QASM2ParseError::new_err(message_generic(
+                    return Err(ParseError::new(
                         Some(&Position::new(&self.filename, self.line, start_col)),
                         "unexpected line break while lexing string literal",
-                    )))
+                    ))
                 }
                 Some(c) if c == terminator => {
                     return Ok(TokenType::Filename);
@@ -646,7 +651,7 @@ impl TokenStream {
     /// until a complete [Token] has been constructed, or the end of the iterator is reached.  This
     /// returns `Some` for all tokens, including the error token, and only returns `None` if there
     /// are no more tokens left to take.
-    fn next_inner(&mut self, context: &mut TokenContext) -> PyResult<Option<Token>> {
+    fn next_inner(&mut self, context: &mut TokenContext) -> ParseResult<Option<Token>> {
         // Consume preceding whitespace.  Beware that this can still exhaust the underlying stream,
         // or scan through an invalid token in the encoding.
         loop {
@@ -697,8 +702,9 @@ impl TokenStream {
                     self.col += 1;
                     TokenType::Equals
                 } else {
-                    return Err(QASM2ParseError::new_err(
-                        "single equals '=' is never valid".to_owned(),
+                    return Err(ParseError::new(
+                        Some(&Position::new(&self.filename, self.line, start_col)),
+                        "single equals '=' is never valid",
                     ));
                 }
             }
@@ -706,23 +712,23 @@ impl TokenStream {
             b'a'..=b'z' | b'A'..=b'Z' => self.lex_textlike(start_col)?,
             c @ (b'"' | b'\'') => {
                 if self.strict && c != b'"' {
-                    return Err(QASM2ParseError::new_err(message_generic(
+                    return Err(ParseError::new(
                         Some(&Position::new(&self.filename, self.line, start_col)),
                         "[strict] paths must be in double quotes (\"\")",
-                    )));
+                    ));
                 } else {
                     self.lex_filename(c, start_col)?
                 }
             }
             c => {
-                return Err(QASM2ParseError::new_err(message_generic(
+                return Err(ParseError::new(
                     Some(&Position::new(&self.filename, self.line, start_col)),
                     &format!(
                         "encountered '{}', which doesn't match any valid tokens",
                         // Non-ASCII bytes should already have been rejected by `next_byte()`.
                         c as char,
                     ),
-                )));
+                ));
             }
         };
         self.try_version = ttype == TokenType::OpenQASM;
@@ -742,14 +748,14 @@ impl TokenStream {
     /// This is a direct analogue of the same method on the [std::iter::Peekable] struct, except it
     /// is manually defined here to avoid hiding the rest of the public fields of the [TokenStream]
     /// struct itself.
-    pub fn peek(&mut self, context: &mut TokenContext) -> PyResult<Option<&Token>> {
+    pub fn peek(&mut self, context: &mut TokenContext) -> ParseResult<Option<&Token>> {
         if self.peeked.is_none() {
             self.peeked = Some(self.next_inner(context)?);
         }
         Ok(self.peeked.as_ref().unwrap().as_ref())
     }
 
-    pub fn next(&mut self, context: &mut TokenContext) -> PyResult<Option<Token>> {
+    pub fn next(&mut self, context: &mut TokenContext) -> ParseResult<Option<Token>> {
         match self.peeked.take() {
             Some(token) => Ok(token),
             None => self.next_inner(context),
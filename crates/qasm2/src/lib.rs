@@ -15,12 +15,18 @@ use pyo3::Python;
 
 use crate::error::QASM2ParseError;
 
+mod build;
 mod bytecode;
 mod error;
+mod export;
 mod expr;
 mod lex;
 mod parse;
 
+pub use build::{loads, QASM2LoadError};
+pub use error::{Diagnostic, ParseError, Position};
+pub use export::{dumps, QASM2ExporterError};
+
 /// Information about a custom instruction that Python space is able to construct to pass down to
 /// us.
 #[pyclass]
@@ -76,12 +82,14 @@ impl CustomClassical {
 /// lex and parse the source lazily; evaluating OpenQASM 2 statements as required, without loading
 /// the entire token and parse tree into memory at once.
 #[pyfunction]
+#[pyo3(signature = (string, include_path, custom_instructions, custom_classical, strict, recover=false))]
 fn bytecode_from_string(
     string: String,
     include_path: Vec<std::path::PathBuf>,
     custom_instructions: Vec<CustomInstruction>,
     custom_classical: Vec<CustomClassical>,
     strict: bool,
+    recover: bool,
 ) -> PyResult<bytecode::BytecodeIterator> {
     bytecode::BytecodeIterator::new(
         lex::TokenStream::from_string(string, strict),
@@ -89,13 +97,16 @@ fn bytecode_from_string(
         &custom_instructions,
         &custom_classical,
         strict,
+        recover,
     )
+    .map_err(PyErr::from)
 }
 
 /// Create a bytecode iterable from a path to a file containing an OpenQASM 2 program.  The
 /// iterable will lex and parse the source lazily; evaluating OpenQASM 2 statements as required,
 /// without loading the entire token and parse tree into memory at once.
 #[pyfunction]
+#[pyo3(signature = (path, include_path, custom_instructions, custom_classical, strict, recover=false))]
 fn bytecode_from_file(
     py: Python<'_>,
     path: std::ffi::OsString,
@@ -103,6 +114,7 @@ fn bytecode_from_file(
     custom_instructions: Vec<CustomInstruction>,
     custom_classical: Vec<CustomClassical>,
     strict: bool,
+    recover: bool,
 ) -> PyResult<bytecode::BytecodeIterator> {
     bytecode::BytecodeIterator::new(
         lex::TokenStream::from_path(&path, strict).map_err(|err| {
@@ -117,7 +129,9 @@ fn bytecode_from_file(
         &custom_instructions,
         &custom_classical,
         strict,
+        recover,
     )
+    .map_err(PyErr::from)
 }
 
 /// An interface to the Rust components of the parser stack, and the types it uses to represent the
@@ -135,6 +149,7 @@ pub fn qasm2(module: &Bound<PyModule>) -> PyResult<()> {
     module.add_class::<bytecode::ExprCustom>()?;
     module.add_class::<CustomInstruction>()?;
     module.add_class::<CustomClassical>()?;
+    module.add_class::<error::Diagnostic>()?;
     module.add_function(wrap_pyfunction!(bytecode_from_string, module)?)?;
     module.add_function(wrap_pyfunction!(bytecode_from_file, module)?)?;
     Ok(())
@@ -21,7 +21,7 @@ use pyo3::prelude::*;
 
 use crate::bytecode::InternalBytecode;
 use crate::error::{
-    message_bad_eof, message_generic, message_incorrect_requirement, Position, QASM2ParseError,
+    error_bad_eof, error_incorrect_requirement, Diagnostic, ParseError, ParseResult, Position,
 };
 use crate::expr::{Expr, ExprParser};
 use crate::lex::{Token, TokenContext, TokenStream, TokenType, Version};
@@ -31,7 +31,7 @@ use crate::{CustomClassical, CustomInstruction};
 const N_BUILTIN_GATES: usize = 2;
 /// The "qelib1.inc" special include.  The elements of the tuple are the gate name, the number of
 /// parameters it takes, and the number of qubits it acts on.
-const QELIB1: [(&str, usize, usize); 23] = [
+pub const QELIB1: [(&str, usize, usize); 23] = [
     ("u3", 3, 1),
     ("u2", 2, 1),
     ("u1", 1, 1),
@@ -60,8 +60,9 @@ const QELIB1: [(&str, usize, usize); 23] = [
 const BUILTIN_CLASSICAL: [&str; 6] = ["cos", "exp", "ln", "sin", "sqrt", "tan"];
 
 /// Define a simple newtype that just has a single non-public `usize` field, has a `new`
-/// constructor, and implements `Copy` and `IntoPy`.  The first argument is the name of the type,
-/// the second is whether to also define addition to make offsetting the newtype easier.
+/// constructor, and implements `Copy`, `IntoPy` and conversion back to `usize`.  The first argument
+/// is the name of the type, the second is whether to also define addition to make offsetting the
+/// newtype easier.
 macro_rules! newtype_id {
     ($id:ident, false) => {
         #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, IntoPyObject, IntoPyObjectRef)]
@@ -72,6 +73,12 @@ macro_rules! newtype_id {
                 Self(value)
             }
         }
+
+        impl From<$id> for usize {
+            fn from(value: $id) -> usize {
+                value.0
+            }
+        }
     };
 
     ($id:ident, true) => {
@@ -238,6 +245,16 @@ pub struct State {
     allow_version: bool,
     /// Whether we're in strict mode or (the default) more permissive parse.
     strict: bool,
+    /// Whether to continue parsing after an invalid statement, so that all the problems in the
+    /// program can be reported together.
+    recover: bool,
+    /// The problems found so far in recovery mode.  Once this is non-empty, no more bytecode is
+    /// emitted.
+    diagnostics: Vec<Diagnostic>,
+    /// The number of unclosed braces consumed from the token stream.
+    brace_depth: usize,
+    /// The type of the last token consumed from the token stream, if any.
+    last_consumed: Option<TokenType>,
 }
 
 impl State {
@@ -248,7 +265,8 @@ impl State {
         custom_instructions: &[CustomInstruction],
         custom_classical: &[CustomClassical],
         strict: bool,
-    ) -> PyResult<Self> {
+        recover: bool,
+    ) -> ParseResult<Self> {
         let mut state = State {
             tokens: vec![tokens],
             context: TokenContext::new(),
@@ -268,15 +286,19 @@ impl State {
             num_gates: 0,
             allow_version: true,
             strict,
+            recover,
+            diagnostics: vec![],
+            brace_depth: 0,
+            last_consumed: None,
         };
         for inst in custom_instructions {
             if state.symbols.contains_key(&inst.name)
                 || state.overridable_gates.contains_key(&inst.name)
             {
-                return Err(QASM2ParseError::new_err(message_generic(
+                return Err(ParseError::new(
                     None,
                     &format!("duplicate custom instruction '{}'", inst.name),
-                )));
+                ));
             }
             state.overridable_gates.insert(
                 inst.name.clone(),
@@ -302,13 +324,13 @@ impl State {
         state.define_gate(None, "CX".to_owned(), 0, 2)?;
         for classical in custom_classical {
             if BUILTIN_CLASSICAL.contains(&&*classical.name) {
-                return Err(QASM2ParseError::new_err(message_generic(
+                return Err(ParseError::new(
                     None,
                     &format!(
                         "cannot override builtin classical function '{}'",
                         &classical.name
                     ),
-                )));
+                ));
             }
             match state.symbols.insert(
                 classical.name.clone(),
@@ -328,13 +350,13 @@ impl State {
                             &classical.name,
                         ),
                     };
-                    return Err(QASM2ParseError::new_err(message_generic(None, &message)));
+                    return Err(ParseError::new(None, &message));
                 }
                 Some(GlobalSymbol::Classical { .. }) => {
-                    return Err(QASM2ParseError::new_err(message_generic(
+                    return Err(ParseError::new(
                         None,
                         &format!("duplicate custom classical function '{}'", &classical.name,),
-                    )));
+                    ));
                 }
                 _ => (),
             }
@@ -345,7 +367,20 @@ impl State {
     /// Get the next token available in the stack of token streams, popping and removing any
     /// complete streams, except the base case.  Will only return `None` once all streams are
     /// exhausted.
-    fn next_token(&mut self) -> PyResult<Option<Token>> {
+    fn next_token(&mut self) -> ParseResult<Option<Token>> {
+        let out = self.next_token_inner()?;
+        if let Some(token) = out.as_ref() {
+            match token.ttype {
+                TokenType::LBrace => self.brace_depth += 1,
+                TokenType::RBrace => self.brace_depth = self.brace_depth.saturating_sub(1),
+                _ => (),
+            }
+            self.last_consumed = Some(token.ttype);
+        }
+        Ok(out)
+    }
+
+    fn next_token_inner(&mut self) -> ParseResult<Option<Token>> {
         let mut pointer = self.tokens.len() - 1;
         while pointer > 0 {
             let out = self.tokens[pointer].next(&mut self.context)?;
@@ -360,7 +395,7 @@ impl State {
 
     /// Peek the next token in the stack of token streams.  This does not remove any complete
     /// streams yet.  Will only return `None` once all streams are exhausted.
-    fn peek_token(&mut self) -> PyResult<Option<&Token>> {
+    fn peek_token(&mut self) -> ParseResult<Option<&Token>> {
         let mut pointer = self.tokens.len() - 1;
         while pointer > 0 && self.tokens[pointer].peek(&mut self.context)?.is_none() {
             pointer -= 1;
@@ -391,34 +426,34 @@ impl State {
     /// is required to be in order for the input program to be valid OpenQASM 2.  This returns the
     /// token if successful, and a suitable error message if the token type is incorrect, or the
     /// end of the file is reached.
-    fn expect(&mut self, expected: TokenType, required: &str, cause: &Token) -> PyResult<Token> {
+    fn expect(&mut self, expected: TokenType, required: &str, cause: &Token) -> ParseResult<Token> {
         let token = match self.next_token()? {
             None => {
-                return Err(QASM2ParseError::new_err(message_bad_eof(
+                return Err(error_bad_eof(
                     Some(&Position::new(
                         self.current_filename(),
                         cause.line,
                         cause.col,
                     )),
                     required,
-                )))
+                ))
             }
             Some(token) => token,
         };
         if token.ttype == expected {
             Ok(token)
         } else {
-            Err(QASM2ParseError::new_err(message_incorrect_requirement(
+            Err(error_incorrect_requirement(
                 required,
                 &token,
                 self.current_filename(),
-            )))
+            ))
         }
     }
 
     /// Take the next token from the stream, if it is of the correct type.  Returns `None` and
     /// leaves the next token in the underlying iterator if it does not match.
-    fn accept(&mut self, expected: TokenType) -> PyResult<Option<Token>> {
+    fn accept(&mut self, expected: TokenType) -> ParseResult<Option<Token>> {
         let peeked = self.peek_token()?;
         if peeked.is_some() && peeked.unwrap().ttype == expected {
             self.next_token()
@@ -428,22 +463,22 @@ impl State {
     }
 
     /// True if the next token in the stream matches the given type, and false if it doesn't.
-    fn next_is(&mut self, expected: TokenType) -> PyResult<bool> {
+    fn next_is(&mut self, expected: TokenType) -> ParseResult<bool> {
         let peeked = self.peek_token()?;
         Ok(peeked.is_some() && peeked.unwrap().ttype == expected)
     }
 
     /// If in `strict` mode, and we have a trailing comma, emit a suitable error message.
-    fn check_trailing_comma(&self, comma: Option<&Token>) -> PyResult<()> {
+    fn check_trailing_comma(&self, comma: Option<&Token>) -> ParseResult<()> {
         match (self.strict, comma) {
-            (true, Some(token)) => Err(QASM2ParseError::new_err(message_generic(
+            (true, Some(token)) => Err(ParseError::new(
                 Some(&Position::new(
                     self.current_filename(),
                     token.line,
                     token.col,
                 )),
                 "[strict] trailing commas in parameter and qubit lists are forbidden",
-            ))),
+            )),
             _ => Ok(()),
         }
     }
@@ -454,7 +489,7 @@ impl State {
     /// register, or isn't defined.  This can also be an error if the subscript is opened, but
     /// cannot be completely resolved due to a typing error or other invalid parse.  `Ok(None)` is
     /// returned if the next token in the stream does not match a possible quantum argument.
-    fn accept_qarg(&mut self) -> PyResult<Option<Operand<QubitId>>> {
+    fn accept_qarg(&mut self) -> ParseResult<Option<Operand<QubitId>>> {
         let (name, name_token) = match self.accept(TokenType::Id)? {
             None => return Ok(None),
             Some(token) => (token.id(&self.context), token),
@@ -462,7 +497,7 @@ impl State {
         let (register_size, register_start) = match self.symbols.get(&name) {
             Some(GlobalSymbol::Qreg { size, start }) => (*size, *start),
             Some(symbol) => {
-                return Err(QASM2ParseError::new_err(message_generic(
+                return Err(ParseError::new(
                     Some(&Position::new(
                         self.current_filename(),
                         name_token.line,
@@ -473,17 +508,17 @@ impl State {
                         name,
                         symbol.describe()
                     ),
-                )))
+                ))
             }
             None => {
-                return Err(QASM2ParseError::new_err(message_generic(
+                return Err(ParseError::new(
                     Some(&Position::new(
                         self.current_filename(),
                         name_token.line,
                         name_token.col,
                     )),
                     &format!("'{name}' is not defined in this scope"),
-                )))
+                ))
             }
         };
         self.complete_operand(&name, register_size, register_start)
@@ -492,40 +527,40 @@ impl State {
 
     /// Take a complete quantum argument from the stream, if it matches.  This is for use within
     /// gates, and so the only valid type of quantum argument is a single qubit.
-    fn accept_qarg_gate(&mut self) -> PyResult<Option<Operand<QubitId>>> {
+    fn accept_qarg_gate(&mut self) -> ParseResult<Option<Operand<QubitId>>> {
         let (name, name_token) = match self.accept(TokenType::Id)? {
             None => return Ok(None),
             Some(token) => (token.id(&self.context), token),
         };
         match self.gate_symbols.get(&name) {
             Some(GateSymbol::Qubit { index }) => Ok(Some(Operand::Single(*index))),
-            Some(GateSymbol::Parameter { .. }) => Err(QASM2ParseError::new_err(message_generic(
+            Some(GateSymbol::Parameter { .. }) => Err(ParseError::new(
                 Some(&Position::new(
                     self.current_filename(),
                     name_token.line,
                     name_token.col,
                 )),
                 &format!("'{name}' is a parameter, not a qubit"),
-            ))),
+            )),
             None => {
                 if let Some(symbol) = self.symbols.get(&name) {
-                    Err(QASM2ParseError::new_err(message_generic(
+                    Err(ParseError::new(
                         Some(&Position::new(
                             self.current_filename(),
                             name_token.line,
                             name_token.col,
                         )),
                         &format!("'{}' is {}, not a qubit", name, symbol.describe()),
-                    )))
+                    ))
                 } else {
-                    Err(QASM2ParseError::new_err(message_generic(
+                    Err(ParseError::new(
                         Some(&Position::new(
                             self.current_filename(),
                             name_token.line,
                             name_token.col,
                         )),
                         &format!("'{name}' is not defined in this scope"),
-                    )))
+                    ))
                 }
             }
         }
@@ -533,25 +568,25 @@ impl State {
 
     /// Take a complete quantum argument from the token stream, returning an error message if one
     /// is not present.
-    fn require_qarg(&mut self, instruction: &Token) -> PyResult<Operand<QubitId>> {
+    fn require_qarg(&mut self, instruction: &Token) -> ParseResult<Operand<QubitId>> {
         match self.peek_token()?.map(|tok| tok.ttype) {
             Some(TokenType::Id) => self.accept_qarg().map(Option::unwrap),
             Some(_) => {
                 let token = self.next_token()?;
-                Err(QASM2ParseError::new_err(message_incorrect_requirement(
+                Err(error_incorrect_requirement(
                     "a quantum argument",
                     &token.unwrap(),
                     self.current_filename(),
-                )))
+                ))
             }
-            None => Err(QASM2ParseError::new_err(message_bad_eof(
+            None => Err(error_bad_eof(
                 Some(&Position::new(
                     self.current_filename(),
                     instruction.line,
                     instruction.col,
                 )),
                 "a quantum argument",
-            ))),
+            )),
         }
     }
 
@@ -562,7 +597,7 @@ impl State {
     /// opened, but cannot be completely resolved due to a typing error or other invalid parse.
     /// `Ok(None)` is returned if the next token in the stream does not match a possible classical
     /// argument.
-    fn accept_carg(&mut self) -> PyResult<Option<Operand<ClbitId>>> {
+    fn accept_carg(&mut self) -> ParseResult<Option<Operand<ClbitId>>> {
         let (name, name_token) = match self.accept(TokenType::Id)? {
             None => return Ok(None),
             Some(token) => (token.id(&self.context), token),
@@ -570,7 +605,7 @@ impl State {
         let (register_size, register_start) = match self.symbols.get(&name) {
             Some(GlobalSymbol::Creg { size, start, .. }) => (*size, *start),
             Some(symbol) => {
-                return Err(QASM2ParseError::new_err(message_generic(
+                return Err(ParseError::new(
                     Some(&Position::new(
                         self.current_filename(),
                         name_token.line,
@@ -581,17 +616,17 @@ impl State {
                         name,
                         symbol.describe()
                     ),
-                )))
+                ))
             }
             None => {
-                return Err(QASM2ParseError::new_err(message_generic(
+                return Err(ParseError::new(
                     Some(&Position::new(
                         self.current_filename(),
                         name_token.line,
                         name_token.col,
                     )),
                     &format!("'{name}' is not defined in this scope"),
-                )))
+                ))
             }
         };
         self.complete_operand(&name, register_size, register_start)
@@ -600,25 +635,25 @@ impl State {
 
     /// Take a complete classical argument from the token stream, returning an error message if one
     /// is not present.
-    fn require_carg(&mut self, instruction: &Token) -> PyResult<Operand<ClbitId>> {
+    fn require_carg(&mut self, instruction: &Token) -> ParseResult<Operand<ClbitId>> {
         match self.peek_token()?.map(|tok| tok.ttype) {
             Some(TokenType::Id) => self.accept_carg().map(Option::unwrap),
             Some(_) => {
                 let token = self.next_token()?;
-                Err(QASM2ParseError::new_err(message_incorrect_requirement(
+                Err(error_incorrect_requirement(
                     "a classical argument",
                     &token.unwrap(),
                     self.current_filename(),
-                )))
+                ))
             }
-            None => Err(QASM2ParseError::new_err(message_bad_eof(
+            None => Err(error_bad_eof(
                 Some(&Position::new(
                     self.current_filename(),
                     instruction.line,
                     instruction.col,
                 )),
                 "a classical argument",
-            ))),
+            )),
         }
     }
 
@@ -630,7 +665,7 @@ impl State {
         name: &str,
         register_size: usize,
         register_start: T,
-    ) -> PyResult<Operand<T>>
+    ) -> ParseResult<Operand<T>>
     where
         T: std::ops::Add<usize, Output = T>,
     {
@@ -644,7 +679,7 @@ impl State {
         if index < register_size {
             Ok(Operand::Single(register_start + index))
         } else {
-            Err(QASM2ParseError::new_err(message_generic(
+            Err(ParseError::new(
                 Some(&Position::new(
                     self.current_filename(),
                     index_token.line,
@@ -653,7 +688,7 @@ impl State {
                 &format!(
                     "index {index} is out-of-range for register '{name}' of size {register_size}"
                 ),
-            )))
+            ))
         }
     }
 
@@ -662,7 +697,7 @@ impl State {
     /// to care about.  We simply error if the version supplied by the file is not the version of
     /// OpenQASM that we are able to support.  This assumes that the `OPENQASM` token is still in
     /// the stream.
-    fn parse_version(&mut self) -> PyResult<usize> {
+    fn parse_version(&mut self) -> ParseResult<usize> {
         let openqasm_token = self.expect_known(TokenType::OpenQASM);
         let version_token = self.expect(TokenType::Version, "version number", &openqasm_token)?;
         match version_token.version(&self.context) {
@@ -670,7 +705,7 @@ impl State {
                 major: 2,
                 minor: Some(0) | None,
             } => Ok(()),
-            _ => Err(QASM2ParseError::new_err(message_generic(
+            _ => Err(ParseError::new(
                 Some(&Position::new(
                     self.current_filename(),
                     version_token.line,
@@ -680,7 +715,7 @@ impl State {
                     "can only handle OpenQASM 2.0, but given {}",
                     version_token.text(&self.context),
                 ),
-            ))),
+            )),
         }?;
         self.expect(TokenType::Semicolon, ";", &openqasm_token)?;
         Ok(0)
@@ -690,7 +725,10 @@ impl State {
     /// the `gate` token is still in the scheme.  This function will likely result in many
     /// instructions being pushed onto the bytecode stream; one for the start and end of the gate
     /// definition, and then one instruction each for the gate applications in the body.
-    fn parse_gate_definition(&mut self, bc: &mut Vec<Option<InternalBytecode>>) -> PyResult<usize> {
+    fn parse_gate_definition(
+        &mut self,
+        bc: &mut Vec<Option<InternalBytecode>>,
+    ) -> ParseResult<usize> {
         let gate_token = self.expect_known(TokenType::Gate);
         let name_token = self.expect(TokenType::Id, "an identifier", &gate_token)?;
         let name = name_token.id(&self.context);
@@ -706,7 +744,7 @@ impl State {
                         index: ParamId::new(num_params),
                     },
                 ) {
-                    return Err(QASM2ParseError::new_err(message_generic(
+                    return Err(ParseError::new(
                         Some(&Position::new(
                             self.current_filename(),
                             param_token.line,
@@ -717,7 +755,7 @@ impl State {
                             param_name,
                             symbol.describe()
                         ),
-                    )));
+                    ));
                 }
                 num_params += 1;
                 comma = self.accept(TokenType::Comma)?;
@@ -739,7 +777,7 @@ impl State {
                     index: QubitId::new(num_qubits),
                 },
             ) {
-                return Err(QASM2ParseError::new_err(message_generic(
+                return Err(ParseError::new(
                     Some(&Position::new(
                         self.current_filename(),
                         qubit_token.line,
@@ -750,7 +788,7 @@ impl State {
                         qubit_name,
                         symbol.describe()
                     ),
-                )));
+                ));
             }
             num_qubits += 1;
             comma = self.accept(TokenType::Comma)?;
@@ -763,15 +801,12 @@ impl State {
             let eof = self.peek_token()?.is_none();
             let position = Position::new(self.current_filename(), gate_token.line, gate_token.col);
             return if eof {
-                Err(QASM2ParseError::new_err(message_bad_eof(
-                    Some(&position),
-                    "a qubit identifier",
-                )))
+                Err(error_bad_eof(Some(&position), "a qubit identifier"))
             } else {
-                Err(QASM2ParseError::new_err(message_generic(
+                Err(ParseError::new(
                     Some(&position),
                     "gates must act on at least one qubit",
-                )))
+                ))
             };
         }
         let lbrace_token = self.expect(TokenType::LBrace, "a gate body", &gate_token)?;
@@ -794,7 +829,7 @@ impl State {
                 }
                 Some(_) => {
                     let token = self.next_token()?.unwrap();
-                    return Err(QASM2ParseError::new_err(message_generic(
+                    return Err(ParseError::new(
                         Some(&Position::new(
                             self.current_filename(),
                             token.line,
@@ -804,17 +839,17 @@ impl State {
                             "only gate applications are valid within a 'gate' body, but saw {}",
                             token.text(&self.context)
                         ),
-                    )));
+                    ));
                 }
                 None => {
-                    return Err(QASM2ParseError::new_err(message_bad_eof(
+                    return Err(error_bad_eof(
                         Some(&Position::new(
                             self.current_filename(),
                             lbrace_token.line,
                             lbrace_token.col,
                         )),
                         "a closing brace '}' of the gate body",
-                    )))
+                    ))
                 }
             }
         }
@@ -837,7 +872,7 @@ impl State {
     fn parse_opaque_definition(
         &mut self,
         bc: &mut Vec<Option<InternalBytecode>>,
-    ) -> PyResult<usize> {
+    ) -> ParseResult<usize> {
         let opaque_token = self.expect_known(TokenType::Opaque);
         let name = self
             .expect(TokenType::Id, "an identifier", &opaque_token)?
@@ -868,14 +903,14 @@ impl State {
         self.check_trailing_comma(comma.as_ref())?;
         self.expect(TokenType::Semicolon, ";", &opaque_token)?;
         if num_qubits == 0 {
-            return Err(QASM2ParseError::new_err(message_generic(
+            return Err(ParseError::new(
                 Some(&Position::new(
                     self.current_filename(),
                     opaque_token.line,
                     opaque_token.col,
                 )),
                 "gates must act on at least one qubit",
-            )));
+            ));
         }
         bc.push(Some(InternalBytecode::DeclareOpaque {
             name: name.clone(),
@@ -894,7 +929,7 @@ impl State {
         bc: &mut Vec<Option<InternalBytecode>>,
         condition: Option<Condition>,
         in_gate: bool,
-    ) -> PyResult<usize> {
+    ) -> ParseResult<usize> {
         let name_token = self.expect_known(TokenType::Id);
         let name = name_token.id(&self.context);
         let (index, num_params, num_qubits) = match self.symbols.get(&name) {
@@ -903,14 +938,14 @@ impl State {
                 num_qubits,
                 index,
             }) => Ok((*index, *num_params, *num_qubits)),
-            Some(symbol) => Err(QASM2ParseError::new_err(message_generic(
+            Some(symbol) => Err(ParseError::new(
                 Some(&Position::new(
                     self.current_filename(),
                     name_token.line,
                     name_token.col,
                 )),
                 &format!("'{}' is {}, not a gate", name, symbol.describe()),
-            ))),
+            )),
             None => {
                 let pos = Position::new(self.current_filename(), name_token.line, name_token.col);
                 let message = if self.overridable_gates.contains_key(&name) {
@@ -918,10 +953,7 @@ impl State {
                 } else {
                     format!("'{name}' is not defined in this scope")
                 };
-                Err(QASM2ParseError::new_err(message_generic(
-                    Some(&pos),
-                    &message,
-                )))
+                Err(ParseError::new(Some(&pos), &message))
             }
         }?;
         let parameters = self.expect_gate_parameters(&name_token, num_params, in_gate)?;
@@ -947,7 +979,7 @@ impl State {
         self.check_trailing_comma(comma.as_ref())?;
         if qargs.len() != num_qubits {
             return match self.peek_token()?.map(|tok| tok.ttype) {
-                Some(TokenType::Semicolon) => Err(QASM2ParseError::new_err(message_generic(
+                Some(TokenType::Semicolon) => Err(ParseError::new(
                     Some(&Position::new(
                         self.current_filename(),
                         name_token.line,
@@ -960,20 +992,20 @@ impl State {
                         if num_qubits == 1 { "" } else { "s" },
                         qargs.len()
                     ),
-                ))),
-                Some(_) => Err(QASM2ParseError::new_err(message_incorrect_requirement(
+                )),
+                Some(_) => Err(error_incorrect_requirement(
                     "the end of the argument list",
                     &name_token,
                     self.current_filename(),
-                ))),
-                None => Err(QASM2ParseError::new_err(message_bad_eof(
+                )),
+                None => Err(error_bad_eof(
                     Some(&Position::new(
                         self.current_filename(),
                         name_token.line,
                         name_token.col,
                     )),
                     "the end of the argument list",
-                ))),
+                )),
             };
         }
         self.expect(TokenType::Semicolon, "';'", &name_token)?;
@@ -986,7 +1018,7 @@ impl State {
         name_token: &Token,
         num_params: usize,
         in_gate: bool,
-    ) -> PyResult<GateParameters> {
+    ) -> ParseResult<GateParameters> {
         let lparen_token = match self.accept(TokenType::LParen)? {
             Some(lparen_token) => lparen_token,
             None => {
@@ -1034,14 +1066,14 @@ impl State {
                 match expr_parser.parse_expression(&lparen_token)? {
                     Expr::Constant(value) => parameters.push(value),
                     _ => {
-                        return Err(QASM2ParseError::new_err(message_generic(
+                        return Err(ParseError::new(
                             Some(&Position::new(
                                 self.current_filename(),
                                 lparen_token.line,
                                 lparen_token.col,
                             )),
                             "non-constant expression in program body",
-                        )))
+                        ))
                     }
                 }
                 seen_params += 1;
@@ -1055,7 +1087,7 @@ impl State {
         };
         self.check_trailing_comma(comma.as_ref())?;
         if seen_params != num_params {
-            return Err(QASM2ParseError::new_err(message_generic(
+            return Err(ParseError::new(
                 Some(&Position::new(
                     self.current_filename(),
                     name_token.line,
@@ -1068,7 +1100,7 @@ impl State {
                     if num_params == 1 { "" } else { "s" },
                     seen_params
                 ),
-            )));
+            ));
         }
         Ok(parameters)
     }
@@ -1083,20 +1115,20 @@ impl State {
         parameters: GateParameters,
         qargs: &[Operand<QubitId>],
         condition: Option<Condition>,
-    ) -> PyResult<usize> {
+    ) -> ParseResult<usize> {
         // Fast path for most common gate patterns that don't need broadcasting.
         if let Some(qubits) = match qargs {
             [Operand::Single(index)] => Some(vec![*index]),
             [Operand::Single(left), Operand::Single(right)] => {
                 if *left == *right {
-                    return Err(QASM2ParseError::new_err(message_generic(
+                    return Err(ParseError::new(
                         Some(&Position::new(
                             self.current_filename(),
                             instruction.line,
                             instruction.col,
                         )),
                         "duplicate qubits in gate application",
-                    )));
+                    ));
                 }
                 Some(vec![*left, *right])
             }
@@ -1120,37 +1152,37 @@ impl State {
             match qarg {
                 Operand::Single(index) => {
                     if !qubits.insert(*index) {
-                        return Err(QASM2ParseError::new_err(message_generic(
+                        return Err(ParseError::new(
                             Some(&Position::new(
                                 self.current_filename(),
                                 instruction.line,
                                 instruction.col,
                             )),
                             "duplicate qubits in gate application",
-                        )));
+                        ));
                     }
                 }
                 Operand::Range(size, start) => {
                     if broadcast_length != 0 && broadcast_length != *size {
-                        return Err(QASM2ParseError::new_err(message_generic(
+                        return Err(ParseError::new(
                             Some(&Position::new(
                                 self.current_filename(),
                                 instruction.line,
                                 instruction.col,
                             )),
                             "cannot resolve broadcast in gate application",
-                        )));
+                        ));
                     }
                     for offset in 0..*size {
                         if !qubits.insert(*start + offset) {
-                            return Err(QASM2ParseError::new_err(message_generic(
+                            return Err(ParseError::new(
                                 Some(&Position::new(
                                     self.current_filename(),
                                     instruction.line,
                                     instruction.col,
                                 )),
                                 "duplicate qubits in gate application",
-                            )));
+                            ));
                         }
                     }
                     broadcast_length = *size;
@@ -1216,7 +1248,7 @@ impl State {
         arguments: Vec<f64>,
         qubits: Vec<QubitId>,
         condition: Option<Condition>,
-    ) -> PyResult<usize> {
+    ) -> ParseResult<usize> {
         if let Some(condition) = condition {
             bc.push(Some(InternalBytecode::ConditionedGate {
                 id: gate_id,
@@ -1243,7 +1275,7 @@ impl State {
         gate_id: GateId,
         arguments: Vec<Expr>,
         qubits: Vec<QubitId>,
-    ) -> PyResult<usize> {
+    ) -> ParseResult<usize> {
         bc.push(Some(InternalBytecode::GateInBody {
             id: gate_id,
             arguments,
@@ -1255,7 +1287,7 @@ impl State {
     /// Parse a complete conditional statement, including the operation that follows the condition
     /// (though this work is delegated to the requisite other grammar rule).  This assumes that the
     /// `if` token is still on the token stream.
-    fn parse_conditional(&mut self, bc: &mut Vec<Option<InternalBytecode>>) -> PyResult<usize> {
+    fn parse_conditional(&mut self, bc: &mut Vec<Option<InternalBytecode>>) -> ParseResult<usize> {
         let if_token = self.expect_known(TokenType::If);
         let lparen_token = self.expect(TokenType::LParen, "'('", &if_token)?;
         let name_token = self.expect(TokenType::Id, "classical register", &if_token)?;
@@ -1267,7 +1299,7 @@ impl State {
         let name = name_token.id(&self.context);
         let creg = match self.symbols.get(&name) {
             Some(GlobalSymbol::Creg { index, .. }) => Ok(*index),
-            Some(symbol) => Err(QASM2ParseError::new_err(message_generic(
+            Some(symbol) => Err(ParseError::new(
                 Some(&Position::new(
                     self.current_filename(),
                     name_token.line,
@@ -1278,15 +1310,15 @@ impl State {
                     name,
                     symbol.describe()
                 ),
-            ))),
-            None => Err(QASM2ParseError::new_err(message_generic(
+            )),
+            None => Err(ParseError::new(
                 Some(&Position::new(
                     self.current_filename(),
                     name_token.line,
                     name_token.col,
                 )),
                 &format!("'{name}' is not defined in this scope"),
-            ))),
+            )),
         }?;
         let condition = Some(Condition { creg, value });
         match self.peek_token()?.map(|tok| tok.ttype) {
@@ -1295,20 +1327,20 @@ impl State {
             Some(TokenType::Reset) => self.parse_reset(bc, condition),
             Some(_) => {
                 let token = self.next_token()?;
-                Err(QASM2ParseError::new_err(message_incorrect_requirement(
+                Err(error_incorrect_requirement(
                     "a gate application, measurement or reset",
                     &token.unwrap(),
                     self.current_filename(),
-                )))
+                ))
             }
-            None => Err(QASM2ParseError::new_err(message_bad_eof(
+            None => Err(error_bad_eof(
                 Some(&Position::new(
                     self.current_filename(),
                     if_token.line,
                     if_token.col,
                 )),
                 "a gate, measurement or reset to condition",
-            ))),
+            )),
         }
     }
 
@@ -1318,7 +1350,7 @@ impl State {
         &mut self,
         bc: &mut Vec<Option<InternalBytecode>>,
         num_gate_qubits: Option<usize>,
-    ) -> PyResult<usize> {
+    ) -> ParseResult<usize> {
         let barrier_token = self.expect_known(TokenType::Barrier);
         let qubits = if !self.next_is(TokenType::Semicolon)? {
             let mut qubits = Vec::new();
@@ -1352,14 +1384,14 @@ impl State {
             self.check_trailing_comma(comma.as_ref())?;
             qubits
         } else if self.strict {
-            return Err(QASM2ParseError::new_err(message_generic(
+            return Err(ParseError::new(
                 Some(&Position::new(
                     self.current_filename(),
                     barrier_token.line,
                     barrier_token.col,
                 )),
                 "[strict] barrier statements must have at least one argument",
-            )));
+            ));
         } else if let Some(num_gate_qubits) = num_gate_qubits {
             (0..num_gate_qubits).map(QubitId::new).collect::<Vec<_>>()
         } else {
@@ -1383,7 +1415,7 @@ impl State {
         &mut self,
         bc: &mut Vec<Option<InternalBytecode>>,
         condition: Option<Condition>,
-    ) -> PyResult<usize> {
+    ) -> ParseResult<usize> {
         let measure_token = self.expect_known(TokenType::Measure);
         let qarg = self.require_qarg(&measure_token)?;
         self.expect(TokenType::Arrow, "'->'", &measure_token)?;
@@ -1413,14 +1445,14 @@ impl State {
                     }));
                     Ok(q_size)
                 }
-                _ => Err(QASM2ParseError::new_err(message_generic(
+                _ => Err(ParseError::new(
                     Some(&Position::new(
                         self.current_filename(),
                         measure_token.line,
                         measure_token.col,
                     )),
                     "cannot resolve broadcast in measurement",
-                ))),
+                )),
             }
         } else {
             match (qarg, carg) {
@@ -1439,14 +1471,14 @@ impl State {
                     }));
                     Ok(q_size)
                 }
-                _ => Err(QASM2ParseError::new_err(message_generic(
+                _ => Err(ParseError::new(
                     Some(&Position::new(
                         self.current_filename(),
                         measure_token.line,
                         measure_token.col,
                     )),
                     "cannot resolve broadcast in measurement",
-                ))),
+                )),
             }
         }
     }
@@ -1458,7 +1490,7 @@ impl State {
         &mut self,
         bc: &mut Vec<Option<InternalBytecode>>,
         condition: Option<Condition>,
-    ) -> PyResult<usize> {
+    ) -> ParseResult<usize> {
         let reset_token = self.expect_known(TokenType::Reset);
         let qarg = self.require_qarg(&reset_token)?;
         self.expect(TokenType::Semicolon, "';'", &reset_token)?;
@@ -1504,7 +1536,7 @@ impl State {
     /// Parse a declaration of a classical register, emitting the relevant bytecode and adding the
     /// definition to the relevant parts of the internal symbol tables in the parser state.  This
     /// assumes that the `creg` token is still in the token stream.
-    fn parse_creg(&mut self, bc: &mut Vec<Option<InternalBytecode>>) -> PyResult<usize> {
+    fn parse_creg(&mut self, bc: &mut Vec<Option<InternalBytecode>>) -> ParseResult<usize> {
         let creg_token = self.expect_known(TokenType::Creg);
         let name_token = self.expect(TokenType::Id, "a valid identifier", &creg_token)?;
         let name = name_token.id(&self.context);
@@ -1525,21 +1557,21 @@ impl State {
             bc.push(Some(InternalBytecode::DeclareCreg { name, size }));
             Ok(1)
         } else {
-            Err(QASM2ParseError::new_err(message_generic(
+            Err(ParseError::new(
                 Some(&Position::new(
                     self.current_filename(),
                     name_token.line,
                     name_token.col,
                 )),
                 &format!("'{}' is already defined", name_token.id(&self.context)),
-            )))
+            ))
         }
     }
 
     /// Parse a declaration of a quantum register, emitting the relevant bytecode and adding the
     /// definition to the relevant parts of the internal symbol tables in the parser state.  This
     /// assumes that the `qreg` token is still in the token stream.
-    fn parse_qreg(&mut self, bc: &mut Vec<Option<InternalBytecode>>) -> PyResult<usize> {
+    fn parse_qreg(&mut self, bc: &mut Vec<Option<InternalBytecode>>) -> ParseResult<usize> {
         let qreg_token = self.expect_known(TokenType::Qreg);
         let name_token = self.expect(TokenType::Id, "a valid identifier", &qreg_token)?;
         let name = name_token.id(&self.context);
@@ -1558,14 +1590,14 @@ impl State {
             bc.push(Some(InternalBytecode::DeclareQreg { name, size }));
             Ok(1)
         } else {
-            Err(QASM2ParseError::new_err(message_generic(
+            Err(ParseError::new(
                 Some(&Position::new(
                     self.current_filename(),
                     name_token.line,
                     name_token.col,
                 )),
                 &format!("'{}' is already defined", name_token.id(&self.context)),
-            )))
+            ))
         }
     }
 
@@ -1574,7 +1606,7 @@ impl State {
     /// updates its state with (and the Python side of the parser does the same) rather than
     /// re-parsing the same file every time.  This assumes that the `include` token is still in the
     /// token stream.
-    fn parse_include(&mut self, bc: &mut Vec<Option<InternalBytecode>>) -> PyResult<usize> {
+    fn parse_include(&mut self, bc: &mut Vec<Option<InternalBytecode>>) -> ParseResult<usize> {
         let include_token = self.expect_known(TokenType::Include);
         let filename_token =
             self.expect(TokenType::Filename, "a filename string", &include_token)?;
@@ -1599,7 +1631,7 @@ impl State {
             let base_filename = std::path::PathBuf::from(&filename);
             let absolute_filename = find_include_path(&base_filename, &self.include_path)
                 .ok_or_else(|| {
-                    QASM2ParseError::new_err(message_generic(
+                    ParseError::new(
                         Some(&Position::new(
                             self.current_filename(),
                             filename_token.line,
@@ -1609,18 +1641,18 @@ impl State {
                             "unable to find '{}' in the include search path",
                             base_filename.display()
                         ),
-                    ))
+                    )
                 })?;
             let new_stream =
                 TokenStream::from_path(absolute_filename, self.strict).map_err(|err| {
-                    QASM2ParseError::new_err(message_generic(
+                    ParseError::new(
                         Some(&Position::new(
                             self.current_filename(),
                             filename_token.line,
                             filename_token.col,
                         )),
                         &format!("unable to open file '{}' for reading: {}", &filename, err),
-                    ))
+                    )
                 })?;
             self.tokens.push(new_stream);
             self.allow_version = true;
@@ -1641,13 +1673,13 @@ impl State {
         name: String,
         num_params: usize,
         num_qubits: usize,
-    ) -> PyResult<bool> {
+    ) -> ParseResult<bool> {
         let already_defined = |state: &Self, name: String| {
             let pos = owner.map(|tok| Position::new(state.current_filename(), tok.line, tok.col));
-            Err(QASM2ParseError::new_err(message_generic(
+            Err(ParseError::new(
                 pos.as_ref(),
                 &format!("'{name}' is already defined"),
-            )))
+            ))
         };
         let mismatched_definitions = |state: &Self, name: String, previous: OverridableGate| {
             let plural = |count: usize, singular: &str| {
@@ -1668,7 +1700,7 @@ impl State {
                 plural(num_qubits, "qubit")
             );
             let pos = owner.map(|tok| Position::new(state.current_filename(), tok.line, tok.col));
-            Err(QASM2ParseError::new_err(message_generic(
+            Err(ParseError::new(
                 pos.as_ref(),
                 &format!(
                     concat!(
@@ -1677,7 +1709,7 @@ impl State {
                     ),
                     name, from_program, from_custom
                 ),
-            )))
+            ))
         };
 
         if let Some(symbol) = self.overridable_gates.remove(&name) {
@@ -1722,30 +1754,96 @@ impl State {
     /// this function that returns `Some` will always have pushed at least one instruction to the
     /// bytecode stream (the number is included).  A return of `None` signals the end of the
     /// iterator.
+    ///
+    /// In recovery mode, an invalid statement does not immediately end the parse.  Instead, its
+    /// diagnostic is recorded, the parser skips to the end of the statement, and parsing resumes
+    /// from there, but no further bytecode is emitted.  Once the token stream is exhausted, a
+    /// single error is returned that reports every diagnostic.  Exceptions raised by Python code
+    /// (such as custom classical functions) are never recovered from.
     pub fn parse_next(
         &mut self,
         bc: &mut Vec<Option<InternalBytecode>>,
-    ) -> PyResult<Option<usize>> {
+    ) -> ParseResult<Option<usize>> {
+        if !self.recover {
+            return self.parse_statements(bc);
+        }
+        loop {
+            let start = bc.len();
+            match self.parse_statements(bc) {
+                Ok(Some(emitted)) if self.diagnostics.is_empty() => return Ok(Some(emitted)),
+                Ok(Some(_)) => bc.truncate(start),
+                Ok(None) if self.diagnostics.is_empty() => return Ok(None),
+                Ok(None) => {
+                    return Err(ParseError::Diagnostics(std::mem::take(
+                        &mut self.diagnostics,
+                    )));
+                }
+                Err(ParseError::Invalid(diagnostic)) => {
+                    bc.truncate(start);
+                    self.diagnostics.push(diagnostic);
+                    self.synchronize()?;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Skip tokens until the end of the statement that was being parsed when an error occurred,
+    /// which is either the next top-level semicolon, or the closing brace of a gate body.  Lexing
+    /// errors seen while skipping are recorded as further diagnostics.
+    fn synchronize(&mut self) -> ParseResult<()> {
+        self.gate_symbols.clear();
+        if self.brace_depth == 0
+            && matches!(
+                self.last_consumed,
+                None | Some(TokenType::Semicolon | TokenType::RBrace)
+            )
+        {
+            return Ok(());
+        }
+        loop {
+            match self.next_token() {
+                Ok(Some(token)) => {
+                    if self.brace_depth == 0
+                        && matches!(token.ttype, TokenType::Semicolon | TokenType::RBrace)
+                    {
+                        return Ok(());
+                    }
+                }
+                Ok(None) => return Ok(()),
+                Err(ParseError::Invalid(diagnostic)) => self.diagnostics.push(diagnostic),
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Parse statements until at least one bytecode instruction is emitted, or the token stream
+    /// is exhausted.
+    fn parse_statements(
+        &mut self,
+        bc: &mut Vec<Option<InternalBytecode>>,
+    ) -> ParseResult<Option<usize>> {
         if self.strict && self.allow_version {
-            match self.peek_token()?.map(|tok| tok.ttype) {
+            let version = match self.peek_token()?.map(|tok| tok.ttype) {
                 Some(TokenType::OpenQASM) => self.parse_version(),
                 Some(_) => {
-                    let token = self.next_token()?.unwrap();
-                    Err(QASM2ParseError::new_err(message_generic(
-                        Some(&Position::new(
-                            self.current_filename(),
-                            token.line,
-                            token.col,
-                        )),
+                    // Only peek the token, so a recovering parse can still use the statement.
+                    let (line, col) = self
+                        .peek_token()?
+                        .map(|token| (token.line, token.col))
+                        .unwrap();
+                    Err(ParseError::new(
+                        Some(&Position::new(self.current_filename(), line, col)),
                         "[strict] the first statement must be 'OPENQASM 2.0;'",
-                    )))
+                    ))
                 }
-                None => Err(QASM2ParseError::new_err(message_generic(
+                None => Err(ParseError::new(
                     None,
                     "[strict] saw an empty token stream, but needed a version statement",
-                ))),
-            }?;
+                )),
+            };
             self.allow_version = false;
+            version?;
         }
         let allow_version = self.allow_version;
         self.allow_version = false;
@@ -1766,34 +1864,34 @@ impl State {
                         self.parse_version()?
                     } else {
                         let token = self.next_token()?.unwrap();
-                        return Err(QASM2ParseError::new_err(message_generic(
+                        return Err(ParseError::new(
                             Some(&Position::new(
                                 self.current_filename(),
                                 token.line,
                                 token.col,
                             )),
                             "only the first statement may be a version declaration",
-                        )));
+                        ));
                     }
                 }
                 TokenType::Semicolon => {
                     let token = self.next_token()?.unwrap();
                     if self.strict {
-                        return Err(QASM2ParseError::new_err(message_generic(
+                        return Err(ParseError::new(
                             Some(&Position::new(
                                 self.current_filename(),
                                 token.line,
                                 token.col,
                             )),
                             "[strict] empty statements and/or extra semicolons are forbidden",
-                        )));
+                        ));
                     } else {
                         0
                     }
                 }
                 _ => {
                     let token = self.next_token()?.unwrap();
-                    return Err(QASM2ParseError::new_err(message_generic(
+                    return Err(ParseError::new(
                         Some(&Position::new(
                             self.current_filename(),
                             token.line,
@@ -1803,7 +1901,7 @@ impl State {
                             "needed a start-of-statement token, but instead got {}",
                             token.text(&self.context)
                         ),
-                    )));
+                    ));
                 }
             };
             if emitted > 0 {
@@ -0,0 +1,86 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2025
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+use hashbrown::HashMap;
+
+use pyo3::intern;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+/// Iterate over all the namespaces that can be used to look up a handler for `namespace`.  This is
+/// the namespace itself, then each of its parents in turn, ending at the root empty-string
+/// namespace.  This matches `qiskit.circuit.annotation.iter_namespaces`.
+pub fn iter_namespaces(namespace: &str) -> impl Iterator<Item = &str> {
+    let mut next = Some(namespace);
+    ::std::iter::from_fn(move || {
+        let current = next?;
+        next = match current.rsplit_once('.') {
+            Some((parent, _)) => Some(parent),
+            None if current.is_empty() => None,
+            None => Some(""),
+        };
+        Some(current)
+    })
+}
+
+/// A registry of serializers for :class:`.Annotation` objects, keyed by the namespace they handle.
+///
+/// Each handler is a Python object satisfying the :class:`.annotation.OpenQASM3Serializer`
+/// interface.  A handler registered for a namespace is also tried for all its child namespaces, if
+/// no more specific handler claims the annotation first.
+#[derive(Debug, Default)]
+pub struct AnnotationHandlers {
+    handlers: HashMap<String, Py<PyAny>>,
+}
+
+impl AnnotationHandlers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `handler` for `namespace` and its children, replacing any previous handler for
+    /// exactly that namespace.
+    pub fn insert(&mut self, namespace: String, handler: Py<PyAny>) {
+        self.handlers.insert(namespace, handler);
+    }
+
+    /// Build the registry from a Python mapping of namespaces to handlers.
+    pub fn from_dict(handlers: &Bound<PyDict>) -> PyResult<Self> {
+        let mut out = Self::new();
+        for (namespace, handler) in handlers.iter() {
+            out.insert(namespace.extract()?, handler.unbind());
+        }
+        Ok(out)
+    }
+
+    /// The handlers that might handle `namespace`, from most to least specific.
+    fn candidates<'a>(&'a self, namespace: &'a str) -> impl Iterator<Item = &'a Py<PyAny>> {
+        iter_namespaces(namespace).filter_map(|namespace| self.handlers.get(namespace))
+    }
+
+    /// Serialize `annotation` with the most specific handler that accepts it, returning its
+    /// namespace and payload, or `None` if no handler could serialize it.
+    pub fn dump(&self, annotation: &Bound<PyAny>) -> PyResult<Option<(String, String)>> {
+        let py = annotation.py();
+        let namespace: String = annotation.getattr(intern!(py, "namespace"))?.extract()?;
+        let not_implemented = py.NotImplemented();
+        for handler in self.candidates(&namespace) {
+            let payload = handler
+                .bind(py)
+                .call_method1(intern!(py, "dump"), (annotation,))?;
+            if !payload.is(&not_implemented) {
+                return Ok(Some((namespace.clone(), payload.extract()?)));
+            }
+        }
+        Ok(None)
+    }
+}
@@ -67,6 +67,7 @@ pub enum Expression {
     Cast(Cast),
     Index(Index),
     IndexSet(IndexSet),
+    FunctionCall(FunctionCall),
 }
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
@@ -106,7 +107,7 @@ pub struct SubscriptedIdentifier {
 }
 
 #[derive(Debug, Clone)]
-pub struct IntegerLiteral(pub(crate) i32);
+pub struct IntegerLiteral(pub(crate) i64);
 
 #[derive(Debug, Clone)]
 pub struct BooleanLiteral(pub(crate) bool);
@@ -138,7 +139,7 @@ impl Display for DurationUnit {
         let unit_str = match self {
             DurationUnit::Nanosecond => "ns",
             DurationUnit::Microsecond => "us",
-            DurationUnit::Millisecond => "us",
+            DurationUnit::Millisecond => "ms",
             DurationUnit::Second => "s",
             DurationUnit::Sample => "dt",
         };
@@ -157,6 +158,7 @@ pub struct Unary {
 pub enum UnaryOp {
     LogicNot,
     BitNot,
+    Negate,
     Default,
 }
 
@@ -165,6 +167,7 @@ impl Display for UnaryOp {
         let op_str = match self {
             UnaryOp::LogicNot => "!",
             UnaryOp::BitNot => "~",
+            UnaryOp::Negate => "-",
             UnaryOp::Default => "",
         };
         write!(f, "{op_str}")
@@ -193,6 +196,11 @@ pub enum BinaryOp {
     NotEqual,
     ShiftLeft,
     ShiftRight,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Pow,
 }
 
 impl Display for BinaryOp {
@@ -211,6 +219,11 @@ impl Display for BinaryOp {
             BinaryOp::NotEqual => "!=",
             BinaryOp::ShiftLeft => "<<",
             BinaryOp::ShiftRight => ">>",
+            BinaryOp::Add => "+",
+            BinaryOp::Sub => "-",
+            BinaryOp::Mul => "*",
+            BinaryOp::Div => "/",
+            BinaryOp::Pow => "**",
         };
         write!(f, "{op_str}")
     }
@@ -231,6 +244,7 @@ pub enum ClassicalType {
     Uint(Uint),
     Bit,
     BitArray(BitArray),
+    Duration,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -288,6 +302,12 @@ pub struct Index {
     pub index: Box<Expression>,
 }
 
+#[derive(Debug, Clone)]
+pub struct FunctionCall {
+    pub name: Identifier,
+    pub arguments: Vec<Expression>,
+}
+
 #[derive(Debug, Clone)]
 pub struct IndexSet {
     pub values: Vec<Expression>,
@@ -327,6 +347,7 @@ pub enum QuantumGateModifierName {
 pub enum Statement {
     QuantumDeclaration(QuantumDeclaration),
     ClassicalDeclaration(ClassicalDeclaration),
+    StretchDeclaration(StretchDeclaration),
     IODeclaration(IODeclaration),
     QuantumInstruction(QuantumInstruction),
     QuantumMeasurementAssignment(QuantumMeasurementAssignment),
@@ -335,6 +356,12 @@ pub enum Statement {
     Alias(Alias),
     Break(Break),
     Continue(Continue),
+    Branching(BranchingStatement),
+    WhileLoop(WhileLoopStatement),
+    ForLoop(ForLoopStatement),
+    Switch(SwitchStatement),
+    Box(BoxStatement),
+    Instruction(InstructionStatements),
 }
 
 #[derive(Debug, Clone)]
@@ -354,6 +381,11 @@ pub struct ClassicalDeclaration {
     pub identifier: Identifier,
 }
 
+#[derive(Debug, Clone)]
+pub struct StretchDeclaration {
+    pub identifier: Identifier,
+}
+
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub struct IODeclaration {
@@ -398,7 +430,7 @@ pub struct Barrier {
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub struct Delay {
-    pub duration: DurationLiteral,
+    pub duration: Expression,
     pub qubits: Vec<IdentifierOrSubscripted>,
 }
 
@@ -410,8 +442,8 @@ pub struct QuantumMeasurementAssignment {
 
 #[derive(Debug, Clone)]
 pub struct Assignment {
-    pub lvalue: Identifier,
-    pub rvalue: Vec<Identifier>,
+    pub lvalue: IdentifierOrSubscripted,
+    pub rvalue: Expression,
 }
 
 #[derive(Debug, Clone)]
@@ -439,6 +471,66 @@ pub struct Break {}
 #[derive(Debug, Clone)]
 pub struct Continue {}
 
+#[derive(Debug, Clone)]
+pub struct BranchingStatement {
+    pub condition: Expression,
+    pub true_body: QuantumBlock,
+    pub false_body: Option<QuantumBlock>,
+}
+
+#[derive(Debug, Clone)]
+pub struct WhileLoopStatement {
+    pub condition: Expression,
+    pub body: QuantumBlock,
+}
+
+/// The set of values a ``for`` loop iterates over; either an inclusive [Range] (printed in
+/// square brackets) or an explicit [IndexSet].
+#[derive(Debug, Clone)]
+pub enum ForLoopIndexSet {
+    Range(Range),
+    IndexSet(IndexSet),
+}
+
+#[derive(Debug, Clone)]
+pub struct ForLoopStatement {
+    pub indexset: ForLoopIndexSet,
+    pub parameter: Identifier,
+    pub body: QuantumBlock,
+}
+
+#[derive(Debug, Clone)]
+pub struct SwitchStatement {
+    pub target: Expression,
+    pub cases: Vec<(Vec<Expression>, QuantumBlock)>,
+    pub default: Option<QuantumBlock>,
+}
+
+/// The statements built from a single instruction of a circuit.  This is transparent in the
+/// output, except that the printer can write the label of the instruction as a comment, and record
+/// the lines that the instruction was written to.
+#[derive(Debug, Clone)]
+pub struct InstructionStatements {
+    /// The index of the instruction in the data of the circuit (or block) it came from.
+    pub index: usize,
+    pub label: Option<String>,
+    pub statements: Vec<Statement>,
+}
+
+/// An annotation on a statement, written ``@namespace payload``.
+#[derive(Debug, Clone)]
+pub struct Annotation {
+    pub namespace: String,
+    pub payload: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct BoxStatement {
+    pub annotations: Vec<Annotation>,
+    pub duration: Option<Expression>,
+    pub body: QuantumBlock,
+}
+
 #[derive(Debug, Hash, Eq, PartialEq)]
 pub enum OP<'a> {
     UnaryOp(&'a UnaryOp),
@@ -10,8 +10,11 @@
 // copyright notice, and modified files need to carry a notice indicating
 // that they have been altered from the originals.
 
+use std::cmp::Ordering;
+
 use pyo3::prelude::*;
-use pyo3::types::{PySequence, PyTuple};
+use pyo3::types::{PyRange, PySequence, PyTuple};
+use pyo3::IntoPyObjectExt;
 
 use ahash::RandomState;
 
@@ -19,8 +22,14 @@ use hashbrown::HashMap;
 use indexmap::IndexMap;
 
 use oq3_semantics::asg;
-use oq3_semantics::symbols::{SymbolId, SymbolTable, SymbolType};
-use oq3_semantics::types::{ArrayDims, Type};
+use oq3_semantics::symbols::{SymbolId, SymbolIdResult, SymbolTable, SymbolType};
+use oq3_semantics::types::{ArrayDims, IsConst, Type};
+
+use qiskit_circuit::classical::expr::{Expr, Stretch, Value, Var};
+use qiskit_circuit::classical::types::Type as ClassicalType;
+use qiskit_circuit::duration::Duration;
+use qiskit_circuit::imports::CASE_DEFAULT;
+use uuid::Uuid;
 
 use crate::circuit::{PyCircuit, PyCircuitModule, PyClassicalRegister, PyGate, PyQuantumRegister};
 use crate::error::QASM3ImporterError;
@@ -39,6 +48,18 @@ pub struct PySymbolTable {
     pub qregs: HashMap<SymbolId, PyQuantumRegister>,
     /// `ClassicalRegister` objects.
     pub cregs: HashMap<SymbolId, PyClassicalRegister>,
+    /// Standalone classical variables.
+    pub vars: HashMap<SymbolId, Var>,
+    /// Unsized `int` and `uint` variables that have been declared but not yet assigned to, and the
+    /// control-flow depth they were declared at.  Qiskit has no unsized integers, so these take the
+    /// type of the first value assigned to them.
+    pub untyped_vars: HashMap<SymbolId, usize>,
+    /// `Stretch` variables.
+    pub stretches: HashMap<SymbolId, Stretch>,
+    /// The values of compile-time constants, which are inlined wherever they are used.
+    pub consts: HashMap<SymbolId, Expr>,
+    /// `Parameter` objects, from `input float` declarations and `for`-loop variables.
+    pub params: HashMap<SymbolId, Py<PyAny>>,
 }
 
 struct BuilderState {
@@ -51,53 +72,262 @@ struct BuilderState {
     module: PyCircuitModule,
     /// Constructors for gate objects.
     pygates: HashMap<String, PyGate>,
+    /// How many control-flow builder scopes of the circuit we're currently within.
+    control_flow_depth: usize,
+}
+
+fn symbol_id(symbol: &SymbolIdResult) -> PyResult<&SymbolId> {
+    symbol
+        .as_ref()
+        .map_err(|err| QASM3ImporterError::new_err(format!("internal error: {err:?}")))
+}
+
+fn is_const_type(ty: &Type) -> bool {
+    match ty {
+        Type::Bit(is_const)
+        | Type::Int(_, is_const)
+        | Type::UInt(_, is_const)
+        | Type::Float(_, is_const)
+        | Type::Angle(_, is_const)
+        | Type::Complex(_, is_const)
+        | Type::Bool(is_const)
+        | Type::Duration(is_const)
+        | Type::BitArray(_, is_const) => *is_const == IsConst::True,
+        _ => false,
+    }
+}
+
+fn zero_value(ty: ClassicalType) -> Expr {
+    Expr::Value(match ty {
+        ClassicalType::Bool | ClassicalType::Uint(_) => Value::Uint { raw: 0, ty },
+        ClassicalType::Float => Value::Float { raw: 0.0, ty },
+        ClassicalType::Duration => Value::Duration(Duration::dt(0)),
+    })
+}
+
+/// Coerce the initial value of a variable to the variable's type.
+fn coerce_to(value: Expr, var: &Var) -> PyResult<Expr> {
+    let (from, to) = (value.ty(), var.ty());
+    expr::coerce_lossless(value, to).ok_or_else(|| {
+        QASM3ImporterError::new_err(format!(
+            "cannot initialize a variable of type '{to:?}' with a value of type '{from:?}'"
+        ))
+    })
+}
+
+fn new_var(name: &str, ty: ClassicalType) -> Var {
+    Var::Standalone {
+        uuid: Uuid::new_v4().as_u128(),
+        name: name.to_owned(),
+        ty,
+    }
 }
 
 impl BuilderState {
+    /// Append an instruction to the circuit, or to the innermost control-flow scope if we're
+    /// building one.
+    fn append<'py>(&'py self, py: Python<'py>, instruction: Py<PyAny>) -> PyResult<()> {
+        if self.control_flow_depth == 0 {
+            self.qc.append(py, instruction)
+        } else {
+            self.qc.append_in_scope(py, instruction)
+        }
+    }
+
     fn declare_classical(
         &mut self,
         py: Python,
         ast_symbols: &SymbolTable,
         decl: &asg::DeclareClassical,
     ) -> PyResult<()> {
-        let name_id = decl
-            .name()
-            .as_ref()
-            .map_err(|err| QASM3ImporterError::new_err(format!("internal error: {err:?}")))?;
+        let name_id = symbol_id(decl.name())?;
         let name_symbol = &ast_symbols[name_id];
-        match name_symbol.symbol_type() {
-            Type::Bit(is_const) => {
-                if is_const.clone().into() {
-                    Err(QASM3ImporterError::new_err("cannot handle consts"))
-                } else if decl.initializer().is_some() {
-                    Err(QASM3ImporterError::new_err(
-                        "cannot handle initialized bits",
+        let ty = name_symbol.symbol_type();
+        if is_const_type(ty) {
+            return self.declare_const(py, ast_symbols, name_id, decl.initializer());
+        }
+        match ty {
+            Type::Bit(_) => self.add_clbit(py, name_id.clone())?,
+            Type::BitArray(dims, _) => match dims {
+                ArrayDims::D1(size) => {
+                    self.add_creg(py, name_id.clone(), name_symbol.name(), *size)?
+                }
+                _ => {
+                    return Err(QASM3ImporterError::new_err(
+                        "cannot handle classical registers with more than one dimension",
                     ))
-                } else {
-                    self.add_clbit(py, name_id.clone())
+                }
+            },
+            Type::Stretch(_) => {
+                let stretch = Stretch {
+                    uuid: Uuid::new_v4().as_u128(),
+                    name: name_symbol.name().to_owned(),
+                };
+                self.qc.add_stretch(py, stretch.clone())?;
+                self.symbols.stretches.insert(name_id.clone(), stretch);
+                return Ok(());
+            }
+            Type::Int(None, _) | Type::UInt(None, _) => {
+                return match decl.initializer() {
+                    Some(initializer) => {
+                        let value =
+                            expr::eval_classical(py, &self.symbols, ast_symbols, initializer)?;
+                        self.declare_inferred_var(py, ast_symbols, name_id, value)
+                    }
+                    None => {
+                        self.symbols
+                            .untyped_vars
+                            .insert(name_id.clone(), self.control_flow_depth);
+                        Ok(())
+                    }
+                };
+            }
+            Type::Int(Some(_), _) => {
+                return Err(QASM3ImporterError::new_err(format!(
+                    "cannot declare '{}': Qiskit does not support signed integers",
+                    name_symbol.name()
+                )))
+            }
+            ty => {
+                let classical = expr::classical_type(ty).ok_or_else(|| {
+                    QASM3ImporterError::new_err(format!("unhandled classical type: {ty:?}"))
+                })?;
+                let var = new_var(name_symbol.name(), classical);
+                match decl.initializer() {
+                    Some(initializer) => {
+                        let value =
+                            expr::eval_classical(py, &self.symbols, ast_symbols, initializer)?;
+                        let value = coerce_to(value, &var)?;
+                        self.qc.add_var(py, var.clone(), value)?;
+                    }
+                    None if self.control_flow_depth == 0 => {
+                        self.qc.add_uninitialized_var(py, var.clone())?
+                    }
+                    // Qiskit can't represent uninitialized variables in control-flow scopes, but
+                    // the value of an uninitialized variable is undefined anyway.
+                    None => self.qc.add_var(py, var.clone(), zero_value(classical))?,
+                }
+                self.symbols.vars.insert(name_id.clone(), var);
+                return Ok(());
+            }
+        }
+        // Only bits and registers reach here; they're initialized by a regular assignment.
+        match decl.initializer() {
+            Some(initializer) => self.store(
+                py,
+                ast_symbols,
+                &asg::LValue::Identifier(Ok(name_id.clone())),
+                initializer,
+            ),
+            None => Ok(()),
+        }
+    }
+
+    fn declare_const(
+        &mut self,
+        py: Python,
+        ast_symbols: &SymbolTable,
+        name_id: &SymbolId,
+        initializer: Option<&asg::TExpr>,
+    ) -> PyResult<()> {
+        let name_symbol = &ast_symbols[name_id];
+        let initializer = initializer.ok_or_else(|| {
+            QASM3ImporterError::new_err(format!(
+                "constant '{}' has no initializer",
+                name_symbol.name()
+            ))
+        })?;
+        let value = match name_symbol.symbol_type() {
+            // Real-number constants are folded, so they can be used in gate parameters.
+            Type::Float(_, _) => Expr::Value(Value::Float {
+                raw: expr::eval_const_float(py, &self.symbols, ast_symbols, initializer)?,
+                ty: ClassicalType::Float,
+            }),
+            ty => {
+                let value = expr::eval_classical(py, &self.symbols, ast_symbols, initializer)?;
+                if !value.is_const() {
+                    return Err(QASM3ImporterError::new_err(format!(
+                        "the initializer of constant '{}' is not a constant expression",
+                        name_symbol.name()
+                    )));
+                }
+                match expr::classical_type(ty) {
+                    Some(classical) => {
+                        expr::coerce_lossless(value, classical).ok_or_else(|| {
+                            QASM3ImporterError::new_err(format!(
+                                "cannot initialize constant '{}' of type '{classical:?}'",
+                                name_symbol.name()
+                            ))
+                        })?
+                    }
+                    None => value,
                 }
             }
-            Type::BitArray(dims, is_const) => {
-                if is_const.clone().into() {
-                    Err(QASM3ImporterError::new_err("cannot handle consts"))
-                } else if decl.initializer().is_some() {
-                    Err(QASM3ImporterError::new_err(
-                        "cannot handle initialized registers",
+        };
+        self.symbols.consts.insert(name_id.clone(), value);
+        Ok(())
+    }
+
+    /// Declare a variable of an unsized integer type, using the type of its first value.
+    fn declare_inferred_var(
+        &mut self,
+        py: Python,
+        ast_symbols: &SymbolTable,
+        name_id: &SymbolId,
+        value: Expr,
+    ) -> PyResult<()> {
+        let name = ast_symbols[name_id].name();
+        let ClassicalType::Uint(_) = value.ty() else {
+            return Err(QASM3ImporterError::new_err(format!(
+                "cannot initialize integer '{name}' with a value of type '{:?}'",
+                value.ty()
+            )));
+        };
+        let var = new_var(name, value.ty());
+        self.qc.add_var(py, var.clone(), value)?;
+        self.symbols.untyped_vars.remove(name_id);
+        self.symbols.vars.insert(name_id.clone(), var);
+        Ok(())
+    }
+
+    fn declare_io(
+        &mut self,
+        py: Python,
+        ast_symbols: &SymbolTable,
+        name: &SymbolIdResult,
+        is_input: bool,
+    ) -> PyResult<()> {
+        let name_id = symbol_id(name)?;
+        let name_symbol = &ast_symbols[name_id];
+        match name_symbol.symbol_type() {
+            // Real-valued inputs are the OpenQASM 3 representation of circuit parameters.
+            Type::Float(_, _) | Type::Angle(_, _) if is_input => {
+                let parameter = self.module.new_parameter(py, name_symbol.name())?;
+                self.symbols
+                    .params
+                    .insert(name_id.clone(), parameter.unbind());
+                Ok(())
+            }
+            Type::Bit(_) if !is_input => self.add_clbit(py, name_id.clone()),
+            Type::BitArray(ArrayDims::D1(size), _) if !is_input => {
+                self.add_creg(py, name_id.clone(), name_symbol.name(), *size)
+            }
+            ty => {
+                let classical = expr::classical_type(ty).ok_or_else(|| {
+                    QASM3ImporterError::new_err(format!(
+                        "unhandled type for {} declaration: {ty:?}",
+                        if is_input { "input" } else { "output" }
                     ))
+                })?;
+                let var = new_var(name_symbol.name(), classical);
+                if is_input {
+                    self.qc.add_input(py, var.clone())?;
                 } else {
-                    match dims {
-                        ArrayDims::D1(size) => {
-                            self.add_creg(py, name_id.clone(), name_symbol.name(), *size)
-                        }
-                        _ => Err(QASM3ImporterError::new_err(
-                            "cannot handle classical registers with more than one dimension",
-                        )),
-                    }
+                    self.qc.add_uninitialized_var(py, var.clone())?;
                 }
+                self.symbols.vars.insert(name_id.clone(), var);
+                Ok(())
             }
-            ty => Err(QASM3ImporterError::new_err(format!(
-                "unhandled classical type: {ty:?}",
-            ))),
         }
     }
 
@@ -173,7 +403,7 @@ impl BuilderState {
         }
         let gate_instance = gate.construct(py, params)?;
         for qubits in expr::broadcast_qubits(py, &self.symbols, ast_symbols, qargs)? {
-            self.qc.append(
+            self.append(
                 py,
                 self.module
                     .new_instruction(py, gate_instance.clone_ref(py), qubits, ())?,
@@ -225,7 +455,7 @@ impl BuilderState {
             qubits,
             (),
         )?;
-        self.qc.append(py, instruction).map(|_| ())
+        self.append(py, instruction)
     }
 
     // Map gates in the symbol table to Qiskit gates in the standard library.
@@ -263,29 +493,352 @@ impl BuilderState {
         ast_symbols: &SymbolTable,
         assignment: &asg::Assignment,
     ) -> PyResult<()> {
-        // Only handling measurements in this first pass.
-        let qarg = match assignment.rvalue().expression() {
-            asg::Expr::MeasureExpression(target) => expr::eval_qarg(
+        match assignment.rvalue().expression() {
+            asg::Expr::MeasureExpression(target) => {
+                let qarg = expr::eval_qarg(
+                    py,
+                    &self.symbols,
+                    ast_symbols,
+                    expr::expect_gate_operand(target.operand())?,
+                )?;
+                let carg =
+                    expr::eval_measure_carg(py, &self.symbols, ast_symbols, assignment.lvalue())?;
+                for (qubits, clbits) in expr::broadcast_measure(py, &qarg, &carg)? {
+                    self.append(
+                        py,
+                        self.module
+                            .new_instruction(py, self.module.measure(py), qubits, clbits)?,
+                    )?
+                }
+                Ok(())
+            }
+            _ => self.store(py, ast_symbols, assignment.lvalue(), assignment.rvalue()),
+        }
+    }
+
+    fn store(
+        &mut self,
+        py: Python,
+        ast_symbols: &SymbolTable,
+        lvalue: &asg::LValue,
+        rvalue: &asg::TExpr,
+    ) -> PyResult<()> {
+        let rvalue = expr::eval_classical(py, &self.symbols, ast_symbols, rvalue)?;
+        if let asg::LValue::Identifier(name) = lvalue {
+            let name_id = symbol_id(name)?;
+            if let Some(depth) = self.symbols.untyped_vars.get(name_id) {
+                if *depth != self.control_flow_depth {
+                    return Err(QASM3ImporterError::new_err(format!(
+                        "cannot infer the width of '{}' from an assignment in a different scope \
+                         to its declaration",
+                        ast_symbols[name_id].name()
+                    )));
+                }
+                return self.declare_inferred_var(py, ast_symbols, name_id, rvalue);
+            }
+        }
+        let lvalue = expr::eval_lvalue(py, &self.symbols, ast_symbols, lvalue)?;
+        let (from, to) = (rvalue.ty(), lvalue.ty());
+        let rvalue = expr::coerce_lossless(rvalue, to).ok_or_else(|| {
+            QASM3ImporterError::new_err(format!(
+                "cannot assign a value of type '{from:?}' to a target of type '{to:?}'"
+            ))
+        })?;
+        self.qc.store(py, lvalue, rvalue)
+    }
+
+    fn reset(&mut self, py: Python, ast_symbols: &SymbolTable, reset: &asg::Reset) -> PyResult<()> {
+        let qarg = expr::expect_gate_operand(reset.gate_operand())?;
+        let qubits = match expr::eval_qarg(py, &self.symbols, ast_symbols, qarg)? {
+            expr::BroadcastItem::Bit(bit) => vec![bit],
+            expr::BroadcastItem::Register(register) => register,
+        };
+        for qubit in qubits {
+            self.append(
+                py,
+                self.module
+                    .new_instruction(py, self.module.reset(py), (qubit,), ())?,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn delay(
+        &mut self,
+        py: Python,
+        ast_symbols: &SymbolTable,
+        delay: &asg::DelayStmt,
+    ) -> PyResult<()> {
+        let mut qubits = Vec::new();
+        for qarg in delay.qubits() {
+            match expr::eval_qarg(
                 py,
                 &self.symbols,
                 ast_symbols,
-                expr::expect_gate_operand(target.operand())?,
-            ),
-            expr => Err(QASM3ImporterError::new_err(format!(
-                "only measurement assignments are currently supported, not {expr:?}",
+                expr::expect_gate_operand(qarg)?,
+            )? {
+                expr::BroadcastItem::Bit(bit) => qubits.push(bit),
+                expr::BroadcastItem::Register(register) => qubits.extend(register),
+            }
+        }
+        // A delay with no operands applies to all qubits.
+        let qubits = (!qubits.is_empty())
+            .then(|| PyTuple::new(py, qubits))
+            .transpose()?;
+        match expr::eval_classical(py, &self.symbols, ast_symbols, delay.duration())? {
+            Expr::Value(Value::Duration(duration)) => {
+                let (value, unit) = match duration {
+                    Duration::dt(value) => (value.into_bound_py_any(py)?, "dt"),
+                    Duration::ns(value) => (value.into_bound_py_any(py)?, "ns"),
+                    Duration::us(value) => (value.into_bound_py_any(py)?, "us"),
+                    Duration::ms(value) => (value.into_bound_py_any(py)?, "ms"),
+                    Duration::s(value) => (value.into_bound_py_any(py)?, "s"),
+                };
+                self.qc.delay(py, value, qubits, Some(unit))
+            }
+            duration if duration.ty() == ClassicalType::Duration => {
+                self.qc
+                    .delay(py, duration.into_bound_py_any(py)?, qubits, None)
+            }
+            duration => Err(QASM3ImporterError::new_err(format!(
+                "expected a duration in a delay, but found a value of type '{:?}'",
+                duration.ty()
             ))),
-        }?;
-        let carg = expr::eval_measure_carg(py, &self.symbols, ast_symbols, assignment.lvalue())?;
-        for (qubits, clbits) in expr::broadcast_measure(py, &qarg, &carg)? {
-            self.qc.append(
+        }
+    }
+
+    /// Enter a control-flow builder context of the circuit, build its body with `build`, and
+    /// exit it again.  This returns whatever object the context returned on entry.
+    fn build_in_context<'py, F>(
+        &mut self,
+        py: Python<'py>,
+        context: &Bound<'py, PyAny>,
+        build: F,
+    ) -> PyResult<Bound<'py, PyAny>>
+    where
+        F: FnOnce(&mut Self, &Bound<'py, PyAny>) -> PyResult<()>,
+    {
+        let entered = context.call_method0("__enter__")?;
+        self.control_flow_depth += 1;
+        let result = build(self, &entered);
+        self.control_flow_depth -= 1;
+        match result {
+            Ok(()) => {
+                context.call_method1("__exit__", (py.None(), py.None(), py.None()))?;
+                Ok(entered)
+            }
+            Err(err) => {
+                context.call_method1(
+                    "__exit__",
+                    (err.get_type(py), err.value(py), err.traceback(py)),
+                )?;
+                Err(err)
+            }
+        }
+    }
+
+    fn condition(
+        &self,
+        py: Python,
+        ast_symbols: &SymbolTable,
+        condition: &asg::TExpr,
+    ) -> PyResult<Expr> {
+        let condition = expr::eval_classical(py, &self.symbols, ast_symbols, condition)?;
+        let ty = condition.ty();
+        expr::coerce_lossless(condition, ClassicalType::Bool).ok_or_else(|| {
+            QASM3ImporterError::new_err(format!(
+                "expected a boolean condition, but found a value of type '{ty:?}'"
+            ))
+        })
+    }
+
+    fn build_if(
+        &mut self,
+        py: Python,
+        ast_symbols: &SymbolTable,
+        if_stmt: &asg::If,
+    ) -> PyResult<()> {
+        let condition = self.condition(py, ast_symbols, if_stmt.condition())?;
+        let context = self.qc.control_flow(py, "if_test", (condition,))?;
+        let else_context = self.build_in_context(py, &context, |state, _| {
+            state.build_block(py, ast_symbols, if_stmt.then_branch().statements())
+        })?;
+        if let Some(else_branch) = if_stmt.else_branch() {
+            self.build_in_context(py, &else_context, |state, _| {
+                state.build_block(py, ast_symbols, else_branch.statements())
+            })?;
+        }
+        Ok(())
+    }
+
+    fn build_while(
+        &mut self,
+        py: Python,
+        ast_symbols: &SymbolTable,
+        while_stmt: &asg::While,
+    ) -> PyResult<()> {
+        let condition = self.condition(py, ast_symbols, while_stmt.condition())?;
+        let context = self.qc.control_flow(py, "while_loop", (condition,))?;
+        self.build_in_context(py, &context, |state, _| {
+            state.build_block(py, ast_symbols, while_stmt.loop_body().statements())
+        })?;
+        Ok(())
+    }
+
+    fn build_for(
+        &mut self,
+        py: Python,
+        ast_symbols: &SymbolTable,
+        for_stmt: &asg::ForStmt,
+    ) -> PyResult<()> {
+        let indexset = match for_stmt.iterable() {
+            asg::ForIterable::RangeExpression(range) => {
+                let start = expr::eval_const_int(py, ast_symbols, range.start())?;
+                let stop = expr::eval_const_int(py, ast_symbols, range.stop())?;
+                let step = range
+                    .step()
+                    .map(|step| expr::eval_const_int(py, ast_symbols, step))
+                    .transpose()?
+                    .unwrap_or(1);
+                // OpenQASM 3 ranges include their end point, unlike Python's.
+                let stop = match step.cmp(&0) {
+                    Ordering::Greater => stop + 1,
+                    Ordering::Less => stop - 1,
+                    Ordering::Equal => {
+                        return Err(QASM3ImporterError::new_err(
+                            "for-loop ranges cannot have a step of zero",
+                        ))
+                    }
+                };
+                PyRange::new_with_step(py, start, stop, step)?.into_any()
+            }
+            asg::ForIterable::SetExpression(set) => PyTuple::new(
                 py,
-                self.module
-                    .new_instruction(py, self.module.measure(py), qubits, clbits)?,
+                set.expressions()
+                    .iter()
+                    .map(|value| expr::eval_const_int(py, ast_symbols, value))
+                    .collect::<PyResult<Vec<_>>>()?,
             )?
-        }
+            .into_any(),
+            asg::ForIterable::Expr(_) => {
+                return Err(QASM3ImporterError::new_err(
+                    "for loops can only iterate over ranges or sets of constant integers",
+                ))
+            }
+        };
+        let loop_id = symbol_id(for_stmt.loop_var())?;
+        let parameter = self.module.new_parameter(py, ast_symbols[loop_id].name())?;
+        self.symbols
+            .params
+            .insert(loop_id.clone(), parameter.clone().unbind());
+        let context = self
+            .qc
+            .control_flow(py, "for_loop", (indexset, parameter))?;
+        self.build_in_context(py, &context, |state, _| {
+            state.build_block(py, ast_symbols, for_stmt.loop_body().statements())
+        })?;
         Ok(())
     }
 
+    fn build_switch(
+        &mut self,
+        py: Python,
+        ast_symbols: &SymbolTable,
+        switch: &asg::SwitchCaseStmt,
+    ) -> PyResult<()> {
+        let target = expr::eval_classical(py, &self.symbols, ast_symbols, switch.control())?;
+        let context = self.qc.control_flow(py, "switch", (target,))?;
+        self.build_in_context(py, &context, |state, case| {
+            for case_stmt in switch.cases() {
+                let values = case_stmt
+                    .control_values()
+                    .iter()
+                    .map(|value| expr::eval_const_int(py, ast_symbols, value))
+                    .collect::<PyResult<Vec<_>>>()?;
+                let case_context = case.call1(PyTuple::new(py, values)?)?;
+                state.build_in_context(py, &case_context, |state, _| {
+                    state.build_block(py, ast_symbols, case_stmt.statements())
+                })?;
+            }
+            if let Some(default) = switch.default_block() {
+                let case_context = case.call1((CASE_DEFAULT.get_bound(py),))?;
+                state.build_in_context(py, &case_context, |state, _| {
+                    state.build_block(py, ast_symbols, default)
+                })?;
+            }
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    fn build_block(
+        &mut self,
+        py: Python,
+        ast_symbols: &SymbolTable,
+        statements: &[asg::Stmt],
+    ) -> PyResult<()> {
+        statements
+            .iter()
+            .try_for_each(|statement| self.build_stmt(py, ast_symbols, statement))
+    }
+
+    fn build_stmt(
+        &mut self,
+        py: Python,
+        ast_symbols: &SymbolTable,
+        statement: &asg::Stmt,
+    ) -> PyResult<()> {
+        match statement {
+            asg::Stmt::GateCall(call) => self.call_gate(py, ast_symbols, call),
+            asg::Stmt::DeclareClassical(decl) => self.declare_classical(py, ast_symbols, decl),
+            asg::Stmt::DeclareQuantum(decl) => self.declare_quantum(py, ast_symbols, decl),
+            asg::Stmt::InputDeclaration(decl) => {
+                self.declare_io(py, ast_symbols, decl.name(), true)
+            }
+            asg::Stmt::OutputDeclaration(decl) => {
+                self.declare_io(py, ast_symbols, decl.name(), false)
+            }
+            // We ignore gate definitions because the only information we can currently use
+            // from them is extracted with `SymbolTable::gates` via `map_gate_ids`.
+            asg::Stmt::GateDefinition(_) => Ok(()),
+            asg::Stmt::Barrier(barrier) => self.apply_barrier(py, ast_symbols, barrier),
+            asg::Stmt::Assignment(assignment) => self.assign(py, ast_symbols, assignment),
+            asg::Stmt::Reset(reset) => self.reset(py, ast_symbols, reset),
+            asg::Stmt::Delay(delay) => self.delay(py, ast_symbols, delay),
+            asg::Stmt::If(if_stmt) => self.build_if(py, ast_symbols, if_stmt),
+            asg::Stmt::While(while_stmt) => self.build_while(py, ast_symbols, while_stmt),
+            asg::Stmt::ForStmt(for_stmt) => self.build_for(py, ast_symbols, for_stmt),
+            asg::Stmt::SwitchCaseStmt(switch) => self.build_switch(py, ast_symbols, switch),
+            asg::Stmt::Break => self.qc.break_loop(py),
+            asg::Stmt::Continue => self.qc.continue_loop(py),
+            asg::Stmt::Block(block) => self.build_block(py, ast_symbols, block.statements()),
+            asg::Stmt::NullStmt => Ok(()),
+            asg::Stmt::Box => Err(QASM3ImporterError::new_err(
+                "'box' statements cannot be imported: the OpenQASM 3 parser in use does not \
+                 represent their contents",
+            )),
+            asg::Stmt::AnnotatedStmt(_) => Err(QASM3ImporterError::new_err(
+                "annotations cannot be imported: they only apply to 'box' statements, which the \
+                 OpenQASM 3 parser in use does not represent",
+            )),
+            asg::Stmt::Alias(_)
+            | asg::Stmt::Cal
+            | asg::Stmt::DeclareHardwareQubit(_)
+            | asg::Stmt::DefCal
+            | asg::Stmt::DefStmt(_)
+            | asg::Stmt::End
+            | asg::Stmt::ExprStmt(_)
+            | asg::Stmt::Extern
+            | asg::Stmt::GPhaseCall(_)
+            | asg::Stmt::Include(_)
+            | asg::Stmt::ModifiedGPhaseCall(_)
+            | asg::Stmt::OldStyleDeclaration
+            | asg::Stmt::Pragma(_) => Err(QASM3ImporterError::new_err(format!(
+                "this statement is not yet handled during OpenQASM 3 import: {statement:?}"
+            ))),
+        }
+    }
+
     fn add_qubit(&mut self, py: Python, ast_symbol: SymbolId) -> PyResult<()> {
         let qubit = self.module.new_qubit(py)?;
         if self
@@ -367,52 +920,10 @@ pub fn convert_asg(
         symbols: Default::default(),
         pygates: gate_constructors,
         module,
+        control_flow_depth: 0,
     };
 
     state.map_gate_ids(py, ast_symbols)?;
-
-    for statement in program.stmts().iter() {
-        match statement {
-            asg::Stmt::GateCall(call) => state.call_gate(py, ast_symbols, call)?,
-            asg::Stmt::DeclareClassical(decl) => state.declare_classical(py, ast_symbols, decl)?,
-            asg::Stmt::DeclareQuantum(decl) => state.declare_quantum(py, ast_symbols, decl)?,
-            // We ignore gate definitions because the only information we can currently use
-            // from them is extracted with `SymbolTable::gates` via `map_gate_ids`.
-            asg::Stmt::GateDefinition(_) => (),
-            asg::Stmt::Barrier(barrier) => state.apply_barrier(py, ast_symbols, barrier)?,
-            asg::Stmt::Assignment(assignment) => state.assign(py, ast_symbols, assignment)?,
-            asg::Stmt::Alias(_)
-            | asg::Stmt::AnnotatedStmt(_)
-            | asg::Stmt::Block(_)
-            | asg::Stmt::Box
-            | asg::Stmt::Break
-            | asg::Stmt::Cal
-            | asg::Stmt::Continue
-            | asg::Stmt::DeclareHardwareQubit(_)
-            | asg::Stmt::DefCal
-            | asg::Stmt::DefStmt(_)
-            | asg::Stmt::Delay(_)
-            | asg::Stmt::End
-            | asg::Stmt::ExprStmt(_)
-            | asg::Stmt::Extern
-            | asg::Stmt::ForStmt(_)
-            | asg::Stmt::GPhaseCall(_)
-            | asg::Stmt::If(_)
-            | asg::Stmt::Include(_)
-            | asg::Stmt::InputDeclaration(_)
-            | asg::Stmt::ModifiedGPhaseCall(_)
-            | asg::Stmt::NullStmt
-            | asg::Stmt::OldStyleDeclaration
-            | asg::Stmt::OutputDeclaration(_)
-            | asg::Stmt::Pragma(_)
-            | asg::Stmt::Reset(_)
-            | asg::Stmt::SwitchCaseStmt(_)
-            | asg::Stmt::While(_) => {
-                return Err(QASM3ImporterError::new_err(format!(
-                    "this statement is not yet handled during OpenQASM 3 import: {statement:?}"
-                )));
-            }
-        }
-    }
+    state.build_block(py, ast_symbols, program.stmts())?;
     Ok(state.qc)
 }
@@ -11,9 +11,11 @@
 // that they have been altered from the originals.
 
 use pyo3::prelude::*;
-use pyo3::types::{PyAny, PyList, PyString, PyTuple, PyType};
+use pyo3::types::{IntoPyDict, PyAny, PyList, PyString, PyTuple, PyType};
 use pyo3::{IntoPyObjectExt, PyTypeInfo};
 
+use qiskit_circuit::classical::expr::{Expr, Stretch, Var};
+
 use crate::error::QASM3ImporterError;
 
 pub trait PyRegister {
@@ -187,8 +189,10 @@ pub struct PyCircuitModule {
     clbit: Py<PyType>,
     circuit_instruction: Py<PyType>,
     barrier: Py<PyType>,
-    // The singleton object.
+    parameter: Py<PyType>,
+    // The singleton objects.
     measure: Py<PyAny>,
+    reset: Py<PyAny>,
 }
 
 impl PyCircuitModule {
@@ -218,8 +222,13 @@ impl PyCircuitModule {
                 .getattr("Barrier")?
                 .downcast_into::<PyType>()?
                 .unbind(),
-            // Measure is a singleton, so just store the object.
+            parameter: module
+                .getattr("Parameter")?
+                .downcast_into::<PyType>()?
+                .unbind(),
+            // Measure and Reset are singletons, so just store the objects.
             measure: module.getattr("Measure")?.call0()?.into_py_any(py)?,
+            reset: module.getattr("Reset")?.call0()?.into_py_any(py)?,
         })
     }
 
@@ -297,9 +306,17 @@ impl PyCircuitModule {
             .map(|x| x.into_pyobject(py).unwrap().unbind())
     }
 
+    pub fn new_parameter<'py>(&self, py: Python<'py>, name: &str) -> PyResult<Bound<'py, PyAny>> {
+        self.parameter.bind(py).call1((name,))
+    }
+
     pub fn measure(&self, py: Python) -> Py<PyAny> {
         self.measure.clone_ref(py)
     }
+
+    pub fn reset(&self, py: Python) -> Py<PyAny> {
+        self.reset.clone_ref(py)
+    }
 }
 
 /// Circuit construction context object to provide an easier Rust-space interface for us to
@@ -353,4 +370,86 @@ impl PyCircuit {
             .call_method1("_append", (instruction.into_pyobject(py)?,))
             .map(|_| ())
     }
+
+    /// Append an instruction to the innermost control-flow builder scope of the circuit.  This
+    /// goes through the full checked `QuantumCircuit.append`, unlike [append](Self::append).
+    pub fn append_in_scope<'py, T: IntoPyObject<'py>>(
+        &'py self,
+        py: Python<'py>,
+        instruction: T,
+    ) -> PyResult<()>
+    where
+        <T as pyo3::IntoPyObject<'py>>::Output: pyo3::IntoPyObject<'py>,
+        PyErr: From<<T as pyo3::IntoPyObject<'py>>::Error>,
+    {
+        let kwargs = [("copy", false)].into_py_dict(py)?;
+        self.inner(py)
+            .call_method("append", (instruction.into_pyobject(py)?,), Some(&kwargs))
+            .map(|_| ())
+    }
+
+    pub fn add_var(&self, py: Python, var: Var, initial: Expr) -> PyResult<()> {
+        self.inner(py)
+            .call_method1("add_var", (var, initial))
+            .map(|_| ())
+    }
+
+    pub fn add_uninitialized_var(&self, py: Python, var: Var) -> PyResult<()> {
+        self.inner(py)
+            .call_method1("add_uninitialized_var", (var,))
+            .map(|_| ())
+    }
+
+    pub fn add_input(&self, py: Python, var: Var) -> PyResult<()> {
+        self.inner(py).call_method1("add_input", (var,)).map(|_| ())
+    }
+
+    pub fn add_stretch(&self, py: Python, stretch: Stretch) -> PyResult<()> {
+        self.inner(py)
+            .call_method1("add_stretch", (stretch,))
+            .map(|_| ())
+    }
+
+    pub fn store(&self, py: Python, lvalue: Expr, rvalue: Expr) -> PyResult<()> {
+        self.inner(py)
+            .call_method1("store", (lvalue, rvalue))
+            .map(|_| ())
+    }
+
+    pub fn delay<'py>(
+        &self,
+        py: Python<'py>,
+        duration: Bound<'py, PyAny>,
+        qubits: Option<Bound<'py, PyTuple>>,
+        unit: Option<&str>,
+    ) -> PyResult<()> {
+        self.inner(py)
+            .call_method1("delay", (duration, qubits, unit))
+            .map(|_| ())
+    }
+
+    /// Get the control-flow builder context manager returned by calling the circuit method
+    /// `method` with the given arguments.  The circuit is built up through these contexts in the
+    /// same way as Python-space users would use a `with` statement.
+    pub fn control_flow<'py, A>(
+        &self,
+        py: Python<'py>,
+        method: &str,
+        args: A,
+    ) -> PyResult<Bound<'py, PyAny>>
+    where
+        A: IntoPyObject<'py, Target = PyTuple, Output = Bound<'py, PyTuple>>,
+    {
+        self.0
+            .bind(py)
+            .call_method1(method, args.into_pyobject_or_pyerr(py)?)
+    }
+
+    pub fn break_loop(&self, py: Python) -> PyResult<()> {
+        self.inner(py).call_method0("break_loop").map(|_| ())
+    }
+
+    pub fn continue_loop(&self, py: Python) -> PyResult<()> {
+        self.inner(py).call_method0("continue_loop").map(|_| ())
+    }
 }
@@ -10,6 +10,305 @@
 // copyright notice, and modified files need to carry a notice indicating
 // that they have been altered from the originals.
 
+use std::fmt;
+use std::path::Path;
+
 use pyo3::import_exception;
+use pyo3::prelude::*;
+use pyo3::types::{IntoPyDict, PyTuple};
+use thiserror::Error;
+
+use oq3_semantics::semantic_error::{SemanticErrorKind, SemanticErrorList};
+use oq3_semantics::TextRange;
+use oq3_source_file::{SourceFile, SourceString, SourceTrait};
+use oq3_syntax::{AstNode, SyntaxKind};
 
 import_exception!(qiskit.qasm3.exceptions, QASM3ImporterError);
+
+/// How serious a :class:`.qasm3.Diagnostic` is.
+#[pyclass(
+    module = "qiskit._accelerate.qasm3",
+    frozen,
+    eq,
+    eq_int,
+    name = "DiagnosticSeverity"
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    /// The program is invalid, or cannot be imported.
+    #[pyo3(name = "ERROR")]
+    Error,
+    /// A problem reported by the analysis of the program that does not stop it being imported.
+    #[pyo3(name = "WARNING")]
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A single problem found while parsing an OpenQASM 3 program, along with its location.
+#[pyclass(module = "qiskit._accelerate.qasm3", frozen, get_all)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The path to the file containing the problem, or ``None`` if the problem is in a program
+    /// that was given directly as a string.
+    pub file: Option<String>,
+    /// The line of the start of the problem, counting from 1.
+    pub line: usize,
+    /// The column of the start of the problem, counting characters from 1.
+    pub column: usize,
+    /// The half-open ``(start, end)`` range of byte offsets of the problem in its source.
+    pub span: (usize, usize),
+    /// How serious the problem is.
+    pub severity: Severity,
+    /// A human-readable description of the problem.
+    pub message: String,
+}
+
+#[pymethods]
+impl Diagnostic {
+    fn __repr__(&self) -> String {
+        format!(
+            "Diagnostic(file={}, line={}, column={}, span={:?}, severity={}, message={:?})",
+            self.file
+                .as_ref()
+                .map_or_else(|| "None".to_owned(), |file| format!("{file:?}")),
+            self.line,
+            self.column,
+            self.span,
+            match self.severity {
+                Severity::Error => "DiagnosticSeverity.ERROR",
+                Severity::Warning => "DiagnosticSeverity.WARNING",
+            },
+            self.message,
+        )
+    }
+
+    fn __str__(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: {}: {}",
+            self.file.as_deref().unwrap_or("<input>"),
+            self.line,
+            self.column,
+            self.severity,
+            self.message
+        )
+    }
+}
+
+/// Errors from parsing and analyzing an OpenQASM 3 program, before it is converted into a circuit.
+#[derive(Debug, Error)]
+pub enum ImporterError {
+    /// The program has syntax errors, or failed semantic analysis.
+    #[error("errors during parsing:\n{}", format_diagnostics(.0))]
+    Invalid(Vec<Diagnostic>),
+    /// The parser failed on a construct that it does not support.
+    #[error("the OpenQASM 3 parser does not support a construct used in this program")]
+    Unsupported,
+}
+
+impl ImporterError {
+    /// All the diagnostics associated with the error, including warnings.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        match self {
+            ImporterError::Invalid(diagnostics) => diagnostics,
+            ImporterError::Unsupported => &[],
+        }
+    }
+}
+
+impl From<ImporterError> for PyErr {
+    fn from(err: ImporterError) -> Self {
+        Python::with_gil(|py| {
+            let diagnostics = PyTuple::new(py, err.diagnostics().iter().cloned())?;
+            let kwargs = [("diagnostics", diagnostics)].into_py_dict(py)?;
+            let exc = py
+                .get_type::<QASM3ImporterError>()
+                .call((err.to_string(),), Some(&kwargs))?;
+            Ok(PyErr::from_value(exc))
+        })
+        .unwrap_or_else(|err: PyErr| err)
+    }
+}
+
+fn format_diagnostics(diagnostics: &[Diagnostic]) -> String {
+    diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The text of a source file, for resolving ranges in it into diagnostics.
+struct Source<'a> {
+    file: Option<String>,
+    text: &'a str,
+}
+
+impl Source<'_> {
+    fn diagnostic(&self, range: TextRange, severity: Severity, message: String) -> Diagnostic {
+        let (start, end) = (usize::from(range.start()), usize::from(range.end()));
+        let before = self.text.get(..start).unwrap_or(self.text);
+        let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+        Diagnostic {
+            file: self.file.clone(),
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            span: (start, end),
+            severity,
+            message,
+        }
+    }
+
+    /// The first line of the source text in `range`, for use in messages.
+    fn snippet(&self, range: TextRange) -> &str {
+        let (start, end) = (usize::from(range.start()), usize::from(range.end()));
+        let text = self.text.get(start..end).unwrap_or_default().trim();
+        text.lines().next().unwrap_or_default()
+    }
+}
+
+fn path_string(path: &Path) -> String {
+    path.to_string_lossy().into_owned()
+}
+
+fn included_syntax_diagnostics(included: &[SourceFile], out: &mut Vec<Diagnostic>) {
+    for source_file in included {
+        let text = ::std::fs::read_to_string(source_file.file_path()).unwrap_or_default();
+        let source = Source {
+            file: Some(path_string(source_file.file_path())),
+            text: &text,
+        };
+        out.extend(
+            source_file.syntax_ast().errors().iter().map(|err| {
+                source.diagnostic(err.range(), Severity::Error, err.message().to_string())
+            }),
+        );
+        included_syntax_diagnostics(source_file.included(), out);
+    }
+}
+
+/// Collect the diagnostics from the lexing and parsing of a program and all the files it
+/// includes.  The program source is `text`, which was read from `file`, if given.
+pub fn syntax_diagnostics(
+    file: Option<&Path>,
+    text: &str,
+    parsed: &SourceString,
+) -> Vec<Diagnostic> {
+    let source = Source {
+        file: file.map(path_string),
+        text,
+    };
+    let mut out = parsed
+        .syntax_ast()
+        .errors()
+        .iter()
+        .map(|err| source.diagnostic(err.range(), Severity::Error, err.message().to_string()))
+        .collect::<Vec<_>>();
+    included_syntax_diagnostics(parsed.included(), &mut out);
+    out
+}
+
+/// Collect a diagnostic for each `box` statement in a program, which cannot be imported.  The
+/// program source is `text`, which was read from `file`, if given.
+///
+/// The parser has no representation of `box` statements: it reports spurious syntax errors for
+/// them, or panics, so they are found in the syntax tree before the full parse.
+pub fn box_diagnostics(file: Option<&Path>, text: &str) -> Vec<Diagnostic> {
+    let source = Source {
+        file: file.map(path_string),
+        text,
+    };
+    oq3_syntax::SourceFile::parse(text)
+        .tree()
+        .syntax()
+        .descendants()
+        .filter(|node| node.kind() == SyntaxKind::BOX_EXPR)
+        .map(|node| {
+            source.diagnostic(
+                node.text_range(),
+                Severity::Error,
+                "'box' statements cannot be imported: the OpenQASM 3 parser in use does not \
+                 represent them"
+                    .to_owned(),
+            )
+        })
+        .collect()
+}
+
+fn describe_semantic_error(kind: &SemanticErrorKind) -> String {
+    match kind {
+        SemanticErrorKind::UndefVarError => "undefined identifier".to_owned(),
+        SemanticErrorKind::UndefGateError => "undefined gate".to_owned(),
+        SemanticErrorKind::RedeclarationError(name) => format!("redeclaration of '{name}'"),
+        SemanticErrorKind::ConstIntegerError => "expected a constant integer".to_owned(),
+        SemanticErrorKind::IncompatibleTypesError => "incompatible types".to_owned(),
+        SemanticErrorKind::IncompatibleDimensionError => "incompatible dimensions".to_owned(),
+        SemanticErrorKind::TooManyIndexes => "too many indices".to_owned(),
+        SemanticErrorKind::CastError => "invalid cast".to_owned(),
+        SemanticErrorKind::MutateConstError => "cannot modify a constant".to_owned(),
+        SemanticErrorKind::NotInGlobalScopeError => {
+            "this statement is only allowed in the global scope".to_owned()
+        }
+        SemanticErrorKind::IncludeNotInGlobalScopeError => {
+            "'include' is only allowed in the global scope".to_owned()
+        }
+        SemanticErrorKind::ReturnInGlobalScopeError => {
+            "'return' is not allowed in the global scope".to_owned()
+        }
+        SemanticErrorKind::NumGateParamsError => "wrong number of gate parameters".to_owned(),
+        SemanticErrorKind::NumGateQubitsError => "wrong number of gate qubits".to_owned(),
+    }
+}
+
+fn semantic_diagnostics_for(
+    source: &Source,
+    errors: &SemanticErrorList,
+    out: &mut Vec<Diagnostic>,
+) {
+    out.extend(errors.iter().map(|err| {
+        let description = describe_semantic_error(err.kind());
+        let message = match source.snippet(err.range()) {
+            "" => description,
+            snippet => format!("{description}: '{snippet}'"),
+        };
+        source.diagnostic(err.range(), Severity::Error, message)
+    }));
+    for included in errors.include_errors() {
+        let text = ::std::fs::read_to_string(included.source_file_path()).unwrap_or_default();
+        let source = Source {
+            file: Some(path_string(included.source_file_path())),
+            text: &text,
+        };
+        semantic_diagnostics_for(&source, included, out);
+    }
+}
+
+/// Collect the diagnostics from the semantic analysis of a program and all the files it
+/// includes.  The program source is `text`, which was read from `file`, if given.
+pub fn semantic_diagnostics(
+    file: Option<&Path>,
+    text: &str,
+    errors: &SemanticErrorList,
+) -> Vec<Diagnostic> {
+    let source = Source {
+        file: file.map(path_string),
+        text,
+    };
+    let mut out = Vec::new();
+    semantic_diagnostics_for(&source, errors, &mut out);
+    out
+}
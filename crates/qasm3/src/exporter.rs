@@ -11,27 +11,38 @@
 // that they have been altered from the originals.
 
 use crate::ast::{
-    Alias, Barrier, BitArray, Break, ClassicalDeclaration, ClassicalType, Continue, Delay,
-    Designator, DurationLiteral, DurationUnit, Expression, Float, GateCall, Header, IODeclaration,
-    IOModifier, Identifier, IdentifierOrSubscripted, Include, IndexSet, IntegerLiteral, Node,
-    Parameter, Program, QuantumBlock, QuantumDeclaration, QuantumGateDefinition,
+    Alias, Annotation, Assignment, Barrier, Binary, BinaryOp, BitArray, BooleanLiteral,
+    BoxStatement, BranchingStatement, Break, Cast, ClassicalDeclaration, ClassicalType, Constant,
+    Continue, Delay, Designator, DurationLiteral, DurationUnit, Expression, Float, ForLoopIndexSet,
+    ForLoopStatement, FunctionCall, GateCall, Header, IODeclaration, IOModifier, Identifier,
+    IdentifierOrSubscripted, Include, Index, IndexSet, InstructionStatements, Int, IntegerLiteral,
+    Node, Parameter, Program, QuantumBlock, QuantumDeclaration, QuantumGateDefinition,
     QuantumGateSignature, QuantumInstruction, QuantumMeasurement, QuantumMeasurementAssignment,
-    Reset, Statement, SubscriptedIdentifier, Version,
+    Range, Reset, Statement, StretchDeclaration, SubscriptedIdentifier, SwitchStatement, Uint,
+    Unary, UnaryOp, Version, WhileLoopStatement,
 };
-use std::io::Write;
 
-use crate::printer::BasicPrinter;
+use crate::annotation::AnnotationHandlers;
+use crate::printer::{BasicPrinter, PrinterOptions, SourceMap};
 use hashbrown::{HashMap, HashSet};
 use indexmap::IndexMap;
+use pyo3::intern;
 use pyo3::prelude::*;
+use pyo3::types::{PyRange, PyRangeMethods};
 use pyo3::Python;
 use qiskit_circuit::bit::{
     ClassicalRegister, QuantumRegister, Register, ShareableClbit, ShareableQubit,
 };
-use qiskit_circuit::circuit_data::CircuitData;
-use qiskit_circuit::operations::{DelayUnit, StandardInstruction};
+use qiskit_circuit::circuit_data::{CircuitData, CircuitStretchType, CircuitVarType};
+use qiskit_circuit::classical::expr::{self, Expr};
+use qiskit_circuit::classical::types::Type;
+use qiskit_circuit::duration::Duration;
+use qiskit_circuit::imports::CASE_DEFAULT;
+use qiskit_circuit::operations::{DelayUnit, OperationRef, StandardInstruction};
 use qiskit_circuit::operations::{Operation, Param};
 use qiskit_circuit::packed_instruction::PackedInstruction;
+use qiskit_circuit::parameter_expression::ParameterExpression;
+use qiskit_circuit::symbol_expr::{self, SymbolExpr};
 use thiserror::Error;
 
 use lazy_static::lazy_static;
@@ -39,6 +50,10 @@ use regex::Regex;
 
 type ExporterResult<T> = Result<T, QASM3ExporterError>;
 
+// The tolerance and largest numerator and denominator used when recognising multiples of pi.
+const PI_EPS: f64 = 1e-12;
+const MAX_FRAC: u64 = 16;
+
 // These are the prefixes used for the loose qubit and bit names.
 lazy_static! {
     static ref BIT_PREFIX: &'static str = "_bit";
@@ -245,6 +260,8 @@ struct SymbolTable {
     symbols: Vec<HashMap<String, Identifier>>,
     bitinfo: Vec<HashMap<BitType, IdentifierOrSubscripted>>,
     reginfo: Vec<HashMap<RegisterType, IdentifierOrSubscripted>>,
+    // Standalone variables and stretches, keyed by their UUIDs.
+    identinfo: Vec<HashMap<u128, Identifier>>,
     gates: IndexMap<String, QuantumGateDefinition>,
     stdgates: HashSet<String>,
     _counter: Counter,
@@ -255,10 +272,12 @@ impl SymbolTable {
         let symbols = vec![HashMap::new()];
         let bitinfo = vec![HashMap::new()];
         let reginfo = vec![HashMap::new()];
+        let identinfo = vec![HashMap::new()];
         Self {
             symbols,
             bitinfo,
             reginfo,
+            identinfo,
             gates: IndexMap::new(),
             stdgates: HashSet::new(),
             _counter: Counter::new(),
@@ -279,6 +298,15 @@ impl SymbolTable {
         Ok(())
     }
 
+    fn bind_global(&mut self, name: &str) {
+        let id = Identifier {
+            string: name.to_string(),
+        };
+        if let Some(first) = self.symbols.first_mut() {
+            first.insert(name.to_string(), id);
+        }
+    }
+
     fn bind_no_check(&mut self, name: &str) {
         let id = Identifier {
             string: name.to_string(),
@@ -400,6 +428,19 @@ impl SymbolTable {
         }
     }
 
+    fn get_reginfo(&self, reg: &RegisterType) -> Option<&IdentifierOrSubscripted> {
+        for info in self.reginfo.iter().rev() {
+            if let Some(id) = info.get(reg) {
+                return Some(id);
+            }
+        }
+        None
+    }
+
+    fn get_identinfo(&self, uuid: u128) -> Option<&Identifier> {
+        self.identinfo.iter().rev().find_map(|info| info.get(&uuid))
+    }
+
     fn register_gate(
         &mut self,
         op_name: String,
@@ -477,16 +518,31 @@ impl SymbolTable {
         Ok(identifier)
     }
 
+    /// Declare a standalone variable or stretch in the current scope.
+    fn register_identifier(&mut self, name: String, uuid: u128) -> ExporterResult<Identifier> {
+        let name = self.escaped_declarable_name(name, true, false)?;
+        let identifier = Identifier {
+            string: name.clone(),
+        };
+        let _ = self.bind(&name);
+        if let Some(last) = self.identinfo.last_mut() {
+            last.insert(uuid, identifier.clone());
+        }
+        Ok(identifier)
+    }
+
     fn push_scope(&mut self) {
         self.symbols.push(HashMap::new());
         self.bitinfo.push(HashMap::new());
         self.reginfo.push(HashMap::new());
+        self.identinfo.push(HashMap::new());
     }
 
     fn pop_scope(&mut self) {
         self.symbols.pop();
         self.bitinfo.pop();
         self.reginfo.pop();
+        self.identinfo.pop();
     }
 
     fn new_context(&mut self) -> Self {
@@ -502,7 +558,8 @@ pub struct Exporter {
     basis_gates: Vec<String>,
     disable_constants: bool,
     allow_aliasing: bool,
-    indent: String,
+    printer_options: PrinterOptions,
+    annotation_handlers: AnnotationHandlers,
 }
 
 impl Exporter {
@@ -511,43 +568,31 @@ impl Exporter {
         basis_gates: Vec<String>,
         disable_constants: bool,
         allow_aliasing: bool,
-        indent: String,
+        printer_options: PrinterOptions,
+        annotation_handlers: AnnotationHandlers,
     ) -> Self {
         Self {
             includes,
             basis_gates,
             disable_constants,
             allow_aliasing,
-            indent,
+            printer_options,
+            annotation_handlers,
         }
     }
 
     pub fn dumps(&self, circuit_data: &CircuitData, islayout: bool) -> ExporterResult<String> {
-        let mut builder = QASM3Builder::new(
-            circuit_data,
-            islayout,
-            self.includes.clone(),
-            self.basis_gates.clone(),
-            self.disable_constants,
-            self.allow_aliasing,
-        );
-        match builder.build_program() {
-            Ok(program) => {
-                let mut output = String::new();
-                BasicPrinter::new(&mut output, self.indent.to_string(), false)
-                    .visit(&Node::Program(&program));
-                Ok(output)
-            }
-            Err(e) => Err(QASM3ExporterError::Error(e.to_string())),
-        }
+        self.dumps_with_source_map(circuit_data, islayout)
+            .map(|(output, _)| output)
     }
 
-    pub fn dump<W: Write>(
+    /// Export the circuit, also returning the lines of the output that each of its top-level
+    /// instructions was written to.
+    pub fn dumps_with_source_map(
         &self,
         circuit_data: &CircuitData,
         islayout: bool,
-        writer: &mut W,
-    ) -> ExporterResult<()> {
+    ) -> ExporterResult<(String, SourceMap)> {
         let mut builder = QASM3Builder::new(
             circuit_data,
             islayout,
@@ -555,23 +600,22 @@ impl Exporter {
             self.basis_gates.clone(),
             self.disable_constants,
             self.allow_aliasing,
+            &self.annotation_handlers,
         );
-
         match builder.build_program() {
             Ok(program) => {
                 let mut output = String::new();
-                let mut printer = BasicPrinter::new(&mut output, self.indent.to_string(), false);
+                let mut printer = BasicPrinter::new(&mut output, self.printer_options.clone());
                 printer.visit(&Node::Program(&program));
-                drop(printer);
-                let _ = writer.write_all(output.as_bytes());
-                Ok(())
+                let source_map = printer.into_source_map();
+                Ok((output, source_map))
             }
             Err(e) => Err(QASM3ExporterError::Error(e.to_string())),
         }
     }
 }
 
-pub struct QASM3Builder {
+pub struct QASM3Builder<'h> {
     _builtin_instr: HashSet<&'static str>,
     loose_bit_prefix: &'static str,
     loose_qubit_prefix: &'static str,
@@ -581,13 +625,17 @@ pub struct QASM3Builder {
     is_layout: bool,
     symbol_table: SymbolTable,
     global_io_decls: Vec<IODeclaration>,
+    global_classical_decls: Vec<ClassicalDeclaration>,
+    // The identifiers that Qiskit parameters (by name) are referred to by in the current context.
+    parameter_map: HashMap<String, Identifier>,
     includes: Vec<String>,
     basis_gates: Vec<String>,
     disable_constants: bool,
     allow_aliasing: bool,
+    annotation_handlers: &'h AnnotationHandlers,
 }
 
-impl<'a> QASM3Builder {
+impl<'a, 'h> QASM3Builder<'h> {
     pub fn new(
         circuit_data: &'a CircuitData,
         is_layout: bool,
@@ -595,6 +643,7 @@ impl<'a> QASM3Builder {
         basis_gates: Vec<String>,
         disable_constants: bool,
         allow_aliasing: bool,
+        annotation_handlers: &'h AnnotationHandlers,
     ) -> Self {
         Self {
             _builtin_instr: [
@@ -620,23 +669,25 @@ impl<'a> QASM3Builder {
             is_layout,
             symbol_table: SymbolTable::new(),
             global_io_decls: Vec::new(),
+            global_classical_decls: Vec::new(),
+            parameter_map: HashMap::new(),
             includes,
             basis_gates,
             disable_constants,
             allow_aliasing,
+            annotation_handlers,
         }
     }
 
-    #[allow(dead_code)]
-    fn new_scope<F>(
+    fn new_scope<F, T>(
         &mut self,
         circuit_data: &CircuitData,
         qubits: Vec<BitType>,
         clbits: Vec<BitType>,
         f: F,
-    ) -> ExporterResult<()>
+    ) -> ExporterResult<T>
     where
-        F: FnOnce(&mut QASM3Builder) -> ExporterResult<()>,
+        F: FnOnce(&mut QASM3Builder<'h>) -> ExporterResult<T>,
     {
         let current_bitmap = &self.circuit_scope.bit_map;
         let new_qubits: Vec<BitType> = qubits.iter().map(|q| current_bitmap[q].clone()).collect();
@@ -660,17 +711,11 @@ impl<'a> QASM3Builder {
 
         let mut new_bit_map = HashMap::new();
 
-        for q in circuit_data.qubits().objects().iter() {
-            new_bit_map.insert(
-                BitType::ShareableQubit(q.clone()),
-                BitType::ShareableQubit(q.clone()),
-            );
+        for (q, outer) in circuit_data.qubits().objects().iter().zip(new_qubits) {
+            new_bit_map.insert(BitType::ShareableQubit(q.clone()), outer);
         }
-        for c in circuit_data.clbits().objects().iter() {
-            new_bit_map.insert(
-                BitType::ShareableClbit(c.clone()),
-                BitType::ShareableClbit(c.clone()),
-            );
+        for (c, outer) in circuit_data.clbits().objects().iter().zip(new_clbits) {
+            new_bit_map.insert(BitType::ShareableClbit(c.clone()), outer);
         }
 
         self.symbol_table.push_scope();
@@ -689,7 +734,7 @@ impl<'a> QASM3Builder {
 
     fn new_context<F>(&mut self, body: &'a CircuitData, f: F) -> ExporterResult<QuantumBlock>
     where
-        F: FnOnce(&mut QASM3Builder) -> QuantumBlock,
+        F: FnOnce(&mut QASM3Builder<'h>) -> ExporterResult<QuantumBlock>,
     {
         let mut bit_map = HashMap::new();
 
@@ -712,12 +757,15 @@ impl<'a> QASM3Builder {
             &mut self.circuit_scope,
             BuildScope::with_mappings(body.clone(), bit_map),
         );
+        // A new context can't see the parameters of its enclosing one.
+        let mut old_parameter_map = std::mem::take(&mut self.parameter_map);
         let result = f(self);
         std::mem::swap(&mut self.circuit_scope, &mut old_scope);
         std::mem::swap(&mut self.symbol_table, &mut old_symbol_table);
+        std::mem::swap(&mut self.parameter_map, &mut old_parameter_map);
         self.symbol_table.gates = old_symbol_table.gates;
 
-        Ok(result)
+        result
     }
 
     fn lookup_bit(&self, bit: &BitType) -> ExporterResult<&IdentifierOrSubscripted> {
@@ -736,9 +784,10 @@ impl<'a> QASM3Builder {
         let header = self.build_header();
 
         self.hoist_global_params()?;
+        self.hoist_io_vars()?;
         let classical_decls = self.hoist_classical_bits()?;
         let qubit_decls = self.build_qubit_decls()?;
-        let main_stmts = self.build_top_level_stmts()?;
+        let main_stmts = self.build_current_scope()?;
 
         let mut all_stmts = Vec::new();
         for decl in &self.global_io_decls {
@@ -750,6 +799,9 @@ impl<'a> QASM3Builder {
         for decl in classical_decls {
             all_stmts.push(decl);
         }
+        for decl in &self.global_classical_decls {
+            all_stmts.push(Statement::ClassicalDeclaration(decl.clone()));
+        }
         for decl in qubit_decls {
             all_stmts.push(decl);
         }
@@ -790,18 +842,20 @@ impl<'a> QASM3Builder {
 
     fn hoist_global_params(&mut self) -> ExporterResult<()> {
         Python::with_gil(|py| {
-            for param in self.circuit_scope.circuit_data.get_parameters(py) {
-                let raw_name: String = match param.getattr("name") {
-                    Ok(attr) => match attr.extract() {
-                        Ok(name) => name,
-                        Err(err) => return Err(QASM3ExporterError::PyErr(err)),
-                    },
-                    Err(err) => return Err(QASM3ExporterError::PyErr(err)),
-                };
-                let identifier = Identifier {
-                    string: raw_name.clone(),
-                };
-                let _ = self.symbol_table.bind(&raw_name);
+            let mut loop_parameters = HashSet::new();
+            collect_loop_parameters(py, &self.circuit_scope.circuit_data, &mut loop_parameters)?;
+            for param in self.circuit_scope.circuit_data.get_parameters(py)? {
+                let raw_name: String = param.getattr("name")?.extract()?;
+                // Loop variables are declared implicitly by their `for` loops.
+                if loop_parameters.contains(&raw_name) {
+                    continue;
+                }
+                let name =
+                    self.symbol_table
+                        .escaped_declarable_name(raw_name.clone(), true, false)?;
+                let identifier = Identifier { string: name };
+                self.symbol_table.bind(&identifier.string)?;
+                self.parameter_map.insert(raw_name, identifier.clone());
                 self.global_io_decls.push(IODeclaration {
                     modifier: IOModifier::Input,
                     type_: ClassicalType::Float(Float::Double),
@@ -812,6 +866,28 @@ impl<'a> QASM3Builder {
         })
     }
 
+    /// Declare the input variables of the circuit.  Only the outermost circuit can have them.
+    fn hoist_io_vars(&mut self) -> ExporterResult<()> {
+        let vars = self
+            .circuit_scope
+            .circuit_data
+            .get_vars(CircuitVarType::Input)
+            .cloned()
+            .collect::<Vec<_>>();
+        for var in vars {
+            let expr::Var::Standalone { uuid, name, ty } = var else {
+                unreachable!("input variables are always standalone");
+            };
+            let identifier = self.symbol_table.register_identifier(name, uuid)?;
+            self.global_io_decls.push(IODeclaration {
+                modifier: IOModifier::Input,
+                type_: build_classical_type(ty),
+                identifier,
+            });
+        }
+        Ok(())
+    }
+
     fn hoist_classical_bits(&mut self) -> ExporterResult<Vec<Statement>> {
         let clbit_indices = self.circuit_scope.circuit_data.clbit_indices();
         let clbits = self.circuit_scope.circuit_data.clbits().objects();
@@ -880,7 +956,7 @@ impl<'a> QASM3Builder {
                 self.symbol_table.set_bitinfo(
                     IdentifierOrSubscripted::Subscripted(SubscriptedIdentifier {
                         string: identifier.string.to_string(),
-                        subscript: Box::new(Expression::IntegerLiteral(IntegerLiteral(i as i32))),
+                        subscript: Box::new(Expression::IntegerLiteral(IntegerLiteral(i as i64))),
                     }),
                     BitType::ShareableClbit(clbit),
                 )
@@ -976,7 +1052,7 @@ impl<'a> QASM3Builder {
                 self.symbol_table.set_bitinfo(
                     IdentifierOrSubscripted::Subscripted(SubscriptedIdentifier {
                         string: identifier.string.to_string(),
-                        subscript: Box::new(Expression::IntegerLiteral(IntegerLiteral(i as i32))),
+                        subscript: Box::new(Expression::IntegerLiteral(IntegerLiteral(i as i64))),
                     }),
                     BitType::ShareableQubit(qubit),
                 )
@@ -984,7 +1060,7 @@ impl<'a> QASM3Builder {
             decls.push(Statement::QuantumDeclaration(QuantumDeclaration {
                 identifier,
                 designator: Some(Designator {
-                    expression: Expression::IntegerLiteral(IntegerLiteral(qreg.len() as i32)),
+                    expression: Expression::IntegerLiteral(IntegerLiteral(qreg.len() as i64)),
                 }),
             }))
         }
@@ -1003,7 +1079,7 @@ impl<'a> QASM3Builder {
             };
             let id2 = IdentifierOrSubscripted::Subscripted(SubscriptedIdentifier {
                 string: name.string.clone(),
-                subscript: Box::new(Expression::IntegerLiteral(IntegerLiteral(i as i32))),
+                subscript: Box::new(Expression::IntegerLiteral(IntegerLiteral(i as i64))),
             });
             self.symbol_table.set_bitinfo(id2, bit.clone());
             elements.push(Expression::IdentifierOrSubscripted(id.clone()));
@@ -1014,11 +1090,49 @@ impl<'a> QASM3Builder {
         })
     }
 
-    fn build_top_level_stmts(&mut self) -> ExporterResult<Vec<Statement>> {
-        let mut stmts = Vec::new();
+    fn build_current_scope(&mut self) -> ExporterResult<Vec<Statement>> {
+        let mut stmts = self.build_local_declarations()?;
         let data = self.circuit_scope.circuit_data.data().to_vec();
-        for instr in data {
-            self.build_instruction(&instr, &mut stmts)?;
+        for (index, instr) in data.iter().enumerate() {
+            let mut statements = Vec::new();
+            self.build_instruction(instr, &mut statements)?;
+            stmts.push(Statement::Instruction(InstructionStatements {
+                index,
+                label: instr.label().map(str::to_owned),
+                statements,
+            }));
+        }
+        Ok(stmts)
+    }
+
+    /// Declare the local variables and stretches of the current scope.  Variables are declared
+    /// uninitialised at the top of the scope, and their initial values are written by `store`s.
+    fn build_local_declarations(&mut self) -> ExporterResult<Vec<Statement>> {
+        let circuit_data = &self.circuit_scope.circuit_data;
+        let vars = circuit_data
+            .get_vars(CircuitVarType::Declare)
+            .cloned()
+            .collect::<Vec<_>>();
+        let stretches = circuit_data
+            .get_stretches(CircuitStretchType::Declare)
+            .cloned()
+            .collect::<Vec<_>>();
+        let mut stmts = Vec::with_capacity(vars.len() + stretches.len());
+        for var in vars {
+            let expr::Var::Standalone { uuid, name, ty } = var else {
+                unreachable!("declared variables are always standalone");
+            };
+            stmts.push(Statement::ClassicalDeclaration(ClassicalDeclaration {
+                type_: build_classical_type(ty),
+                identifier: self.symbol_table.register_identifier(name, uuid)?,
+            }));
+        }
+        for stretch in stretches {
+            stmts.push(Statement::StretchDeclaration(StretchDeclaration {
+                identifier: self
+                    .symbol_table
+                    .register_identifier(stretch.name, stretch.uuid)?,
+            }));
         }
         Ok(stmts)
     }
@@ -1031,9 +1145,7 @@ impl<'a> QASM3Builder {
         let name = instruction.op.name();
 
         if instruction.op.control_flow() {
-            Err(QASM3ExporterError::Error(format!(
-                "Control flow {name} is not supported"
-            )))
+            self.build_control_flow(instruction, stmts)
         } else {
             match name {
                 "barrier" => self.handle_barrier(instruction, stmts),
@@ -1048,9 +1160,7 @@ impl<'a> QASM3Builder {
                     stmts.push(Statement::Continue(Continue {}));
                     Ok(())
                 }
-                "store" => {
-                    panic!("Store is not yet supported");
-                }
+                "store" => self.handle_store(instruction, stmts),
                 _ => {
                     let gate_call = self.build_gate_call(instruction)?;
                     stmts.push(Statement::QuantumInstruction(QuantumInstruction::GateCall(
@@ -1062,6 +1172,403 @@ impl<'a> QASM3Builder {
         }
     }
 
+    fn build_control_flow(
+        &mut self,
+        instruction: &PackedInstruction,
+        stmts: &mut Vec<Statement>,
+    ) -> ExporterResult<()> {
+        let name = instruction.op.name();
+        let OperationRef::Instruction(py_inst) = instruction.op.view() else {
+            return Err(QASM3ExporterError::Error(format!(
+                "Control flow {name} is not supported"
+            )));
+        };
+        let operation = Python::with_gil(|py| py_inst.instruction.clone_ref(py));
+        let blocks = instruction.op.blocks();
+        match name {
+            "if_else" => {
+                let condition = Python::with_gil(|py| {
+                    self.build_condition(&operation.bind(py).getattr("condition")?)
+                })?;
+                let true_body = self.build_block(instruction, &blocks[0])?;
+                let false_body = blocks
+                    .get(1)
+                    .map(|block| self.build_block(instruction, block))
+                    .transpose()?;
+                stmts.push(Statement::Branching(BranchingStatement {
+                    condition,
+                    true_body,
+                    false_body,
+                }));
+            }
+            "while_loop" => {
+                let condition = Python::with_gil(|py| {
+                    self.build_condition(&operation.bind(py).getattr("condition")?)
+                })?;
+                let body = self.build_block(instruction, &blocks[0])?;
+                stmts.push(Statement::WhileLoop(WhileLoopStatement { condition, body }));
+            }
+            "for_loop" => {
+                let (indexset, loop_parameter) = Python::with_gil(|py| {
+                    let params = operation.bind(py).getattr("params")?;
+                    let indexset = self.build_for_loop_indexset(&params.get_item(0)?)?;
+                    let loop_parameter = params.get_item(1)?;
+                    let loop_parameter: Option<String> = if loop_parameter.is_none() {
+                        None
+                    } else {
+                        Some(loop_parameter.getattr("name")?.extract()?)
+                    };
+                    Ok::<_, QASM3ExporterError>((indexset, loop_parameter))
+                })?;
+                let (qubits, clbits) = self.instruction_bits(instruction);
+                let (parameter, body) = self.new_scope(&blocks[0], qubits, clbits, |builder| {
+                    let name = builder.symbol_table.escaped_declarable_name(
+                        loop_parameter.clone().unwrap_or_else(|| "_".to_string()),
+                        true,
+                        false,
+                    )?;
+                    builder.symbol_table.bind(&name)?;
+                    let parameter = Identifier { string: name };
+                    let outer_parameter_map = builder.parameter_map.clone();
+                    if let Some(raw_name) = loop_parameter {
+                        builder.parameter_map.insert(raw_name, parameter.clone());
+                    }
+                    let statements = builder.build_current_scope();
+                    builder.parameter_map = outer_parameter_map;
+                    Ok((
+                        parameter,
+                        QuantumBlock {
+                            statements: statements?,
+                        },
+                    ))
+                })?;
+                stmts.push(Statement::ForLoop(ForLoopStatement {
+                    indexset,
+                    parameter,
+                    body,
+                }));
+            }
+            "switch_case" => {
+                let (real_target, cases) = Python::with_gil(|py| {
+                    let operation = operation.bind(py);
+                    let real_target = self.build_target(&operation.getattr("target")?)?;
+                    let case_default = CASE_DEFAULT.get_bound(py);
+                    let mut cases = Vec::new();
+                    for case in operation.call_method0("cases_specifier")?.try_iter()? {
+                        let (values, block): (Bound<PyAny>, Bound<PyAny>) = case?.extract()?;
+                        let mut labels = Vec::new();
+                        let mut is_default = false;
+                        for value in values.try_iter()? {
+                            let value = value?;
+                            if value.is(case_default) {
+                                is_default = true;
+                            } else {
+                                labels.push(self.build_integer(&value)?);
+                            }
+                        }
+                        let block: CircuitData = block.getattr("_data")?.extract()?;
+                        cases.push((labels, is_default, block));
+                    }
+                    Ok::<_, QASM3ExporterError>((real_target, cases))
+                })?;
+                let target_name = self.symbol_table.escaped_declarable_name(
+                    "switch_dummy".to_string(),
+                    true,
+                    true,
+                )?;
+                self.symbol_table.bind_global(&target_name);
+                let target = Identifier {
+                    string: target_name,
+                };
+                self.global_classical_decls.push(ClassicalDeclaration {
+                    type_: ClassicalType::Int(Int { size: None }),
+                    identifier: target.clone(),
+                });
+                let mut switch_cases = Vec::new();
+                let mut default = None;
+                for (labels, is_default, block) in cases {
+                    let body = self.build_block(instruction, &block)?;
+                    // Even if the default is mixed in with other labels, only emitting the
+                    // `default` is equivalent, since evaluating case labels has no side effects.
+                    if is_default {
+                        default = Some(body);
+                    } else {
+                        switch_cases.push((labels, body));
+                    }
+                }
+                stmts.push(Statement::Assignment(Assignment {
+                    lvalue: IdentifierOrSubscripted::Identifier(target.clone()),
+                    rvalue: real_target,
+                }));
+                stmts.push(Statement::Switch(SwitchStatement {
+                    target: Expression::IdentifierOrSubscripted(
+                        IdentifierOrSubscripted::Identifier(target),
+                    ),
+                    cases: switch_cases,
+                    default,
+                }));
+            }
+            "box" => {
+                let (annotations, duration) = Python::with_gil(|py| {
+                    let operation = operation.bind(py);
+                    let annotations = operation
+                        .getattr(intern!(py, "annotations"))?
+                        .try_iter()?
+                        .map(|annotation| self.build_annotation(&annotation?))
+                        .collect::<ExporterResult<Vec<_>>>()?;
+                    let duration = operation.getattr(intern!(py, "duration"))?;
+                    let duration = if duration.is_none() {
+                        None
+                    } else {
+                        let unit = operation.getattr(intern!(py, "unit"))?.extract()?;
+                        Some(self.build_duration(&duration, unit)?)
+                    };
+                    Ok::<_, QASM3ExporterError>((annotations, duration))
+                })?;
+                let body = self.build_block(instruction, &blocks[0])?;
+                stmts.push(Statement::Box(BoxStatement {
+                    annotations,
+                    duration,
+                    body,
+                }));
+            }
+            _ => {
+                return Err(QASM3ExporterError::Error(format!(
+                    "Control flow {name} is not supported"
+                )))
+            }
+        }
+        Ok(())
+    }
+
+    fn instruction_bits(&self, instruction: &PackedInstruction) -> (Vec<BitType>, Vec<BitType>) {
+        let circuit_data = &self.circuit_scope.circuit_data;
+        let qubits = circuit_data
+            .qargs_interner()
+            .get(instruction.qubits)
+            .iter()
+            .map(|q| BitType::ShareableQubit(circuit_data.qubits().get(*q).unwrap().clone()))
+            .collect();
+        let clbits = circuit_data
+            .cargs_interner()
+            .get(instruction.clbits)
+            .iter()
+            .map(|c| BitType::ShareableClbit(circuit_data.clbits().get(*c).unwrap().clone()))
+            .collect();
+        (qubits, clbits)
+    }
+
+    fn build_block(
+        &mut self,
+        instruction: &PackedInstruction,
+        block: &CircuitData,
+    ) -> ExporterResult<QuantumBlock> {
+        let (qubits, clbits) = self.instruction_bits(instruction);
+        self.new_scope(block, qubits, clbits, |builder| {
+            Ok(QuantumBlock {
+                statements: builder.build_current_scope()?,
+            })
+        })
+    }
+
+    fn build_integer(&self, value: &Bound<PyAny>) -> ExporterResult<Expression> {
+        let value: i64 = value
+            .extract()
+            .map_err(|_| QASM3ExporterError::Error(format!("'{value}' is not an integer")))?;
+        Ok(Expression::IntegerLiteral(IntegerLiteral(value)))
+    }
+
+    fn build_for_loop_indexset(&self, indexset: &Bound<PyAny>) -> ExporterResult<ForLoopIndexSet> {
+        if let Ok(range) = indexset.downcast::<PyRange>() {
+            let (start, stop, step) = (range.start()?, range.stop()?, range.step()?);
+            // OpenQASM 3 ranges are inclusive at both ends, unlike Python's.
+            let len = range.len()? as isize;
+            let end = if len > 0 {
+                start + step * (len - 1)
+            } else {
+                stop - 1
+            };
+            let integer = |value: isize| {
+                Some(Box::new(Expression::IntegerLiteral(IntegerLiteral(
+                    value as i64,
+                ))))
+            };
+            return Ok(ForLoopIndexSet::Range(Range {
+                start: integer(start),
+                end: integer(end),
+                step: if step != 1 { integer(step) } else { None },
+            }));
+        }
+        let values = indexset
+            .try_iter()?
+            .map(|value| self.build_integer(&value?))
+            .collect::<ExporterResult<Vec<_>>>()
+            .map_err(|_| {
+                QASM3ExporterError::Error(format!(
+                    "The values in OpenQASM 3 'for' loops must all be integers, but received '{indexset}'."
+                ))
+            })?;
+        Ok(ForLoopIndexSet::IndexSet(IndexSet { values }))
+    }
+
+    /// Build the condition of an `if_else` or `while_loop`, which is either a legacy
+    /// `(bit, value)` or `(register, value)` tuple, or a classical expression.
+    fn build_condition(&self, condition: &Bound<PyAny>) -> ExporterResult<Expression> {
+        if let Ok((bit, value)) = condition.extract::<(ShareableClbit, usize)>() {
+            let bit = self.build_bit_expression(bit)?;
+            return Ok(if value != 0 {
+                bit
+            } else {
+                Expression::Unary(Unary {
+                    op: UnaryOp::LogicNot,
+                    operand: Box::new(bit),
+                })
+            });
+        }
+        if let Ok((register, value)) = condition.extract::<(ClassicalRegister, u64)>() {
+            return Ok(Expression::Binary(Binary {
+                op: BinaryOp::Equal,
+                left: Box::new(self.build_register_expression(register)?),
+                right: Box::new(Self::build_uint_literal(value)?),
+            }));
+        }
+        self.build_expression(&condition.extract()?)
+    }
+
+    /// Build the target of a `switch_case`, which is either a bit, a register, or a classical
+    /// expression.
+    fn build_target(&self, target: &Bound<PyAny>) -> ExporterResult<Expression> {
+        if let Ok(bit) = target.extract::<ShareableClbit>() {
+            return self.build_bit_expression(bit);
+        }
+        if let Ok(register) = target.extract::<ClassicalRegister>() {
+            return self.build_register_expression(register);
+        }
+        self.build_expression(&target.extract()?)
+    }
+
+    fn build_bit_expression(&self, bit: ShareableClbit) -> ExporterResult<Expression> {
+        Ok(Expression::IdentifierOrSubscripted(
+            self.lookup_bit(&BitType::ShareableClbit(bit))?.clone(),
+        ))
+    }
+
+    fn build_register_expression(&self, register: ClassicalRegister) -> ExporterResult<Expression> {
+        let register = RegisterType::ClassicalRegister(register);
+        let id = self.symbol_table.get_reginfo(&register).ok_or_else(|| {
+            QASM3ExporterError::Error(format!("Register not found: {}", register.name()))
+        })?;
+        Ok(Expression::IdentifierOrSubscripted(id.clone()))
+    }
+
+    fn build_identifier_expression(&self, uuid: u128, name: &str) -> ExporterResult<Expression> {
+        let id = self.symbol_table.get_identinfo(uuid).ok_or_else(|| {
+            QASM3ExporterError::Error(format!("'{name}' is not declared in this scope"))
+        })?;
+        Ok(Expression::IdentifierOrSubscripted(
+            IdentifierOrSubscripted::Identifier(id.clone()),
+        ))
+    }
+
+    fn build_uint_literal(value: u64) -> ExporterResult<Expression> {
+        let value = i64::try_from(value).map_err(|_| {
+            QASM3ExporterError::Error(format!(
+                "Integer {value} is too large to be represented in OpenQASM 3"
+            ))
+        })?;
+        Ok(Expression::IntegerLiteral(IntegerLiteral(value)))
+    }
+
+    fn build_expression(&self, node: &Expr) -> ExporterResult<Expression> {
+        match node {
+            Expr::Unary(unary) => Ok(Expression::Unary(Unary {
+                op: match unary.op {
+                    expr::UnaryOp::BitNot => UnaryOp::BitNot,
+                    expr::UnaryOp::LogicNot => UnaryOp::LogicNot,
+                },
+                operand: Box::new(self.build_expression(&unary.operand)?),
+            })),
+            Expr::Binary(binary) => Ok(Expression::Binary(Binary {
+                op: match binary.op {
+                    expr::BinaryOp::BitAnd => BinaryOp::BitAnd,
+                    expr::BinaryOp::BitOr => BinaryOp::BitOr,
+                    expr::BinaryOp::BitXor => BinaryOp::BitXor,
+                    expr::BinaryOp::LogicAnd => BinaryOp::LogicAnd,
+                    expr::BinaryOp::LogicOr => BinaryOp::LogicOr,
+                    expr::BinaryOp::Equal => BinaryOp::Equal,
+                    expr::BinaryOp::NotEqual => BinaryOp::NotEqual,
+                    expr::BinaryOp::Less => BinaryOp::Less,
+                    expr::BinaryOp::LessEqual => BinaryOp::LessEqual,
+                    expr::BinaryOp::Greater => BinaryOp::Greater,
+                    expr::BinaryOp::GreaterEqual => BinaryOp::GreaterEqual,
+                    expr::BinaryOp::ShiftLeft => BinaryOp::ShiftLeft,
+                    expr::BinaryOp::ShiftRight => BinaryOp::ShiftRight,
+                    expr::BinaryOp::Add => BinaryOp::Add,
+                    expr::BinaryOp::Sub => BinaryOp::Sub,
+                    expr::BinaryOp::Mul => BinaryOp::Mul,
+                    expr::BinaryOp::Div => BinaryOp::Div,
+                },
+                left: Box::new(self.build_expression(&binary.left)?),
+                right: Box::new(self.build_expression(&binary.right)?),
+            })),
+            Expr::Cast(cast) => {
+                if cast.implicit {
+                    return self.build_expression(&cast.operand);
+                }
+                let type_ = match cast.ty {
+                    Type::Bool => ClassicalType::Bool,
+                    Type::Uint(width) => ClassicalType::Uint(Uint {
+                        size: Some(width as u32),
+                    }),
+                    Type::Float => ClassicalType::Float(Float::Double),
+                    Type::Duration => {
+                        return Err(QASM3ExporterError::Error(
+                            "Casts to duration are not supported".to_string(),
+                        ))
+                    }
+                };
+                Ok(Expression::Cast(Cast {
+                    type_,
+                    operand: Box::new(self.build_expression(&cast.operand)?),
+                }))
+            }
+            Expr::Value(value) => match value {
+                expr::Value::Uint {
+                    raw,
+                    ty: Type::Bool,
+                } => Ok(Expression::BooleanLiteral(BooleanLiteral(*raw != 0))),
+                expr::Value::Uint { raw, .. } => Self::build_uint_literal(*raw),
+                expr::Value::Float { raw, .. } => Ok(Expression::Parameter(Parameter {
+                    obj: format!("{raw:?}"),
+                })),
+                expr::Value::Duration(duration) => {
+                    let (value, unit) = match *duration {
+                        Duration::dt(value) => (value as f64, DurationUnit::Sample),
+                        Duration::ns(value) => (value, DurationUnit::Nanosecond),
+                        Duration::us(value) => (value, DurationUnit::Microsecond),
+                        Duration::ms(value) => (value, DurationUnit::Millisecond),
+                        Duration::s(value) => (value, DurationUnit::Second),
+                    };
+                    Ok(Expression::DurationLiteral(DurationLiteral { value, unit }))
+                }
+            },
+            Expr::Var(var) => match var {
+                expr::Var::Bit { bit } => self.build_bit_expression(bit.clone()),
+                expr::Var::Register { register, .. } => {
+                    self.build_register_expression(register.clone())
+                }
+                expr::Var::Standalone { uuid, name, .. } => {
+                    self.build_identifier_expression(*uuid, name)
+                }
+            },
+            Expr::Stretch(stretch) => self.build_identifier_expression(stretch.uuid, &stretch.name),
+            Expr::Index(index) => Ok(Expression::Index(Index {
+                target: Box::new(self.build_expression(&index.target)?),
+                index: Box::new(self.build_expression(&index.index)?),
+            })),
+        }
+    }
+
     fn handle_barrier(
         &mut self,
         instr: &PackedInstruction,
@@ -1156,6 +1663,51 @@ impl<'a> QASM3Builder {
         Ok(())
     }
 
+    fn handle_store(
+        &mut self,
+        instr: &PackedInstruction,
+        stmts: &mut Vec<Statement>,
+    ) -> ExporterResult<()> {
+        let OperationRef::Instruction(py_inst) = instr.op.view() else {
+            return Err(QASM3ExporterError::Error(
+                "Store is not a Python-space instruction".to_string(),
+            ));
+        };
+        let (lvalue, rvalue) = Python::with_gil(|py| -> PyResult<(Expr, Expr)> {
+            let store = py_inst.instruction.bind(py);
+            Ok((
+                store.getattr(intern!(py, "lvalue"))?.extract()?,
+                store.getattr(intern!(py, "rvalue"))?.extract()?,
+            ))
+        })?;
+        let lvalue = match self.build_expression(&lvalue)? {
+            Expression::IdentifierOrSubscripted(id) => id,
+            Expression::Index(Index { target, index }) => match *target {
+                Expression::IdentifierOrSubscripted(IdentifierOrSubscripted::Identifier(id)) => {
+                    IdentifierOrSubscripted::Subscripted(SubscriptedIdentifier {
+                        string: id.string,
+                        subscript: index,
+                    })
+                }
+                _ => {
+                    return Err(QASM3ExporterError::Error(
+                        "Cannot store to a nested index".to_string(),
+                    ))
+                }
+            },
+            _ => {
+                return Err(QASM3ExporterError::Error(
+                    "The target of a store must be a variable".to_string(),
+                ))
+            }
+        };
+        stmts.push(Statement::Assignment(Assignment {
+            lvalue,
+            rvalue: self.build_expression(&rvalue)?,
+        }));
+        Ok(())
+    }
+
     fn handle_delay(
         &self,
         instr: &PackedInstruction,
@@ -1182,58 +1734,31 @@ impl<'a> QASM3Builder {
                 "Expected Delay instruction, but got wrong instruction".to_string(),
             ));
         };
-        let param = &instr.params_view()[0];
-        let duration: f64 = Python::with_gil(|py| match param {
-            Param::Float(val) => *val,
-            Param::ParameterExpression(p) => {
-                let py_obj = p.bind(py);
-                let py_str = py_obj.str().expect("Failed to call str() on Parameter");
-                let name = py_str
-                    .str()
-                    .expect("Failed to convert PyString to &str")
-                    .to_string();
-                match name.parse::<f64>() {
-                    Ok(val) => val,
-                    Err(_) => panic!("Failed to parse parameter value"),
-                }
-            }
-            Param::Obj(obj) => {
-                let py_obj = obj.bind(py);
-                let py_str = py_obj.str().expect("Failed to call str() on Parameter");
-                let name = py_str
-                    .str()
-                    .expect("Failed to convert PyString to &str")
-                    .to_string();
-                match name.parse::<f64>() {
-                    Ok(val) => val,
-                    Err(_) => panic!("Failed to parse parameter value"),
-                }
+        let duration = match &instr.params_view()[0] {
+            Param::Float(value) => {
+                Expression::DurationLiteral(Self::build_duration_literal(*value, delay_unit)?)
             }
-        });
-
-        let mut map = HashMap::new();
-        map.insert(DelayUnit::NS, DurationUnit::Nanosecond);
-        map.insert(DelayUnit::US, DurationUnit::Microsecond);
-        map.insert(DelayUnit::MS, DurationUnit::Millisecond);
-        map.insert(DelayUnit::S, DurationUnit::Second);
-        map.insert(DelayUnit::DT, DurationUnit::Sample);
-
-        let duration_literal: DurationLiteral = match map.get(&delay_unit) {
-            Some(found) => DurationLiteral {
-                value: duration,
-                unit: found.clone(),
-            },
-            None => {
-                if delay_unit == DelayUnit::PS {
-                    DurationLiteral {
-                        value: duration * 1000.0,
-                        unit: DurationUnit::Nanosecond,
-                    }
-                } else {
-                    return Err(QASM3ExporterError::Error(format!(
-                        "Unknown delay unit: {delay_unit}"
-                    )));
+            Param::Obj(ob) => Python::with_gil(|py| {
+                let ob = ob.bind(py);
+                match ob.extract::<f64>() {
+                    Ok(value) => Ok(Expression::DurationLiteral(Self::build_duration_literal(
+                        value, delay_unit,
+                    )?)),
+                    Err(_) => Err(QASM3ExporterError::Error(format!(
+                        "Cannot export the delay duration '{}'",
+                        ob.repr()?
+                    ))),
                 }
+            })?,
+            // A symbolic duration is a float, so it is scaled by one unit of the delay.
+            param @ (Param::ParameterExpression(_) | Param::Symbolic(_)) => {
+                Expression::Binary(Binary {
+                    op: BinaryOp::Mul,
+                    left: Box::new(self.build_param(param)?),
+                    right: Box::new(Expression::DurationLiteral(Self::build_duration_literal(
+                        1.0, delay_unit,
+                    )?)),
+                })
             }
         };
 
@@ -1251,10 +1776,220 @@ impl<'a> QASM3Builder {
             ))?;
             qubits.push(id.to_owned());
         }
-        Ok(Delay {
-            duration: duration_literal,
-            qubits,
-        })
+        Ok(Delay { duration, qubits })
+    }
+
+    fn build_duration_literal(value: f64, unit: DelayUnit) -> ExporterResult<DurationLiteral> {
+        let (value, unit) = match unit {
+            DelayUnit::NS => (value, DurationUnit::Nanosecond),
+            DelayUnit::US => (value, DurationUnit::Microsecond),
+            DelayUnit::MS => (value, DurationUnit::Millisecond),
+            DelayUnit::S => (value, DurationUnit::Second),
+            DelayUnit::DT => (value, DurationUnit::Sample),
+            DelayUnit::PS => (value * 1000.0, DurationUnit::Nanosecond),
+            DelayUnit::EXPR => {
+                return Err(QASM3ExporterError::Error(format!(
+                    "Unknown delay unit: {unit}"
+                )))
+            }
+        };
+        Ok(DurationLiteral { value, unit })
+    }
+
+    /// Build the duration of a `box`, which is either a literal with a unit, or an expression.
+    fn build_duration(
+        &self,
+        duration: &Bound<PyAny>,
+        unit: DelayUnit,
+    ) -> ExporterResult<Expression> {
+        if unit == DelayUnit::EXPR {
+            return self.build_expression(&duration.extract()?);
+        }
+        Ok(Expression::DurationLiteral(Self::build_duration_literal(
+            duration.extract()?,
+            unit,
+        )?))
+    }
+
+    /// Serialize an annotation using the configured handlers for its namespace or its parents.
+    fn build_annotation(&self, annotation: &Bound<PyAny>) -> ExporterResult<Annotation> {
+        match self.annotation_handlers.dump(annotation)? {
+            Some((namespace, payload)) => Ok(Annotation { namespace, payload }),
+            None => Err(QASM3ExporterError::Error(format!(
+                "No configured annotation serializer could handle {annotation}"
+            ))),
+        }
+    }
+
+    fn build_param(&self, param: &Param) -> ExporterResult<Expression> {
+        match param {
+            Param::Float(value) => Ok(self.build_float(*value)),
+            Param::ParameterExpression(ob) => Python::with_gil(|py| {
+                let expr = ob
+                    .bind(py)
+                    .getattr(intern!(py, "_symbol_expr"))?
+                    .extract::<ParameterExpression>()?;
+                self.build_symbol_expr(expr.expr())
+            }),
+            Param::Symbolic(expr) => self.build_symbol_expr(expr.expr()),
+            Param::Obj(ob) => Python::with_gil(|py| {
+                let ob = ob.bind(py);
+                match ob.extract::<f64>() {
+                    Ok(value) => Ok(self.build_float(value)),
+                    Err(_) => Err(QASM3ExporterError::Error(format!(
+                        "Cannot export the parameter '{}'",
+                        ob.repr()?
+                    ))),
+                }
+            }),
+        }
+    }
+
+    /// Build a float, written in terms of `pi` if it's a simple multiple or fraction of it, unless
+    /// constants are disabled.
+    fn build_float(&self, value: f64) -> Expression {
+        let literal = |value: f64| {
+            Expression::Parameter(Parameter {
+                obj: value.to_string(),
+            })
+        };
+        if self.disable_constants || !value.is_finite() || value.abs() < PI_EPS {
+            return literal(value);
+        }
+        let is_integer = |x: f64| (x - x.round()).abs() < PI_EPS;
+        let multiple = value.abs() / std::f64::consts::PI;
+        let (numerator, denominator) = if multiple >= 1.0 - PI_EPS && is_integer(multiple) {
+            (multiple.round() as u64, 1)
+        } else {
+            let fraction = (2..=MAX_FRAC).find_map(|denominator| {
+                let numerator = multiple * denominator as f64;
+                (numerator >= 1.0 - PI_EPS
+                    && numerator.round() as u64 <= MAX_FRAC
+                    && is_integer(numerator))
+                .then(|| (numerator.round() as u64, denominator))
+            });
+            match fraction {
+                Some(fraction) => fraction,
+                None => return literal(value),
+            }
+        };
+        let integer = |value: u64| Expression::IntegerLiteral(IntegerLiteral(value as i64));
+        // The sign goes on the leading factor, so `-3*pi/4` doesn't need parentheses.
+        let signed = |expression: Expression| {
+            if value < 0.0 {
+                Expression::Unary(Unary {
+                    op: UnaryOp::Negate,
+                    operand: Box::new(expression),
+                })
+            } else {
+                expression
+            }
+        };
+        let mut out = if numerator == 1 {
+            signed(Expression::Constant(Constant::PI))
+        } else {
+            Expression::Binary(Binary {
+                op: BinaryOp::Mul,
+                left: Box::new(signed(integer(numerator))),
+                right: Box::new(Expression::Constant(Constant::PI)),
+            })
+        };
+        if denominator != 1 {
+            out = Expression::Binary(Binary {
+                op: BinaryOp::Div,
+                left: Box::new(out),
+                right: Box::new(integer(denominator)),
+            });
+        }
+        out
+    }
+
+    fn build_symbol_value(&self, value: &symbol_expr::Value) -> ExporterResult<Expression> {
+        match value {
+            symbol_expr::Value::Real(value) => Ok(self.build_float(*value)),
+            symbol_expr::Value::Int(value) => {
+                let literal =
+                    Expression::IntegerLiteral(IntegerLiteral(value.unsigned_abs() as i64));
+                Ok(if *value < 0 {
+                    Expression::Unary(Unary {
+                        op: UnaryOp::Negate,
+                        operand: Box::new(literal),
+                    })
+                } else {
+                    literal
+                })
+            }
+            symbol_expr::Value::Complex(value) => {
+                if value.im.abs() < PI_EPS {
+                    Ok(self.build_float(value.re))
+                } else {
+                    Err(QASM3ExporterError::Error(format!(
+                        "Cannot export the complex value '{value}'"
+                    )))
+                }
+            }
+        }
+    }
+
+    fn build_symbol_expr(&self, expr: &SymbolExpr) -> ExporterResult<Expression> {
+        match expr {
+            SymbolExpr::Symbol(name) => match self.parameter_map.get(name.as_str()) {
+                Some(identifier) => Ok(Expression::IdentifierOrSubscripted(
+                    IdentifierOrSubscripted::Identifier(identifier.clone()),
+                )),
+                None => Err(QASM3ExporterError::Error(format!(
+                    "Parameter '{name}' is not defined in this scope"
+                ))),
+            },
+            SymbolExpr::Value(value) => self.build_symbol_value(value),
+            SymbolExpr::Unary { op, expr: operand } => {
+                let function = match op {
+                    symbol_expr::UnaryOp::Neg => {
+                        return Ok(Expression::Unary(Unary {
+                            op: UnaryOp::Negate,
+                            operand: Box::new(self.build_symbol_expr(operand)?),
+                        }))
+                    }
+                    symbol_expr::UnaryOp::Sin => "sin",
+                    symbol_expr::UnaryOp::Cos => "cos",
+                    symbol_expr::UnaryOp::Tan => "tan",
+                    symbol_expr::UnaryOp::Asin => "arcsin",
+                    symbol_expr::UnaryOp::Acos => "arccos",
+                    symbol_expr::UnaryOp::Atan => "arctan",
+                    symbol_expr::UnaryOp::Exp => "exp",
+                    symbol_expr::UnaryOp::Log => "log",
+                    // OpenQASM 3 has no built-in functions for these, so they can only be exported
+                    // if they evaluate to a number.
+                    symbol_expr::UnaryOp::Abs
+                    | symbol_expr::UnaryOp::Sign
+                    | symbol_expr::UnaryOp::Conj => {
+                        return match expr.eval(true) {
+                            Some(value) => self.build_symbol_value(&value),
+                            None => Err(QASM3ExporterError::Error(format!(
+                                "Cannot export '{expr}', which has no OpenQASM 3 equivalent"
+                            ))),
+                        }
+                    }
+                };
+                Ok(Expression::FunctionCall(FunctionCall {
+                    name: Identifier {
+                        string: function.to_string(),
+                    },
+                    arguments: vec![self.build_symbol_expr(operand)?],
+                }))
+            }
+            SymbolExpr::Binary { op, lhs, rhs } => Ok(Expression::Binary(Binary {
+                op: match op {
+                    symbol_expr::BinaryOp::Add => BinaryOp::Add,
+                    symbol_expr::BinaryOp::Sub => BinaryOp::Sub,
+                    symbol_expr::BinaryOp::Mul => BinaryOp::Mul,
+                    symbol_expr::BinaryOp::Div => BinaryOp::Div,
+                    symbol_expr::BinaryOp::Pow => BinaryOp::Pow,
+                },
+                left: Box::new(self.build_symbol_expr(lhs)?),
+                right: Box::new(self.build_symbol_expr(rhs)?),
+            })),
+        }
     }
 
     fn build_gate_call(&mut self, instr: &PackedInstruction) -> ExporterResult<GateCall> {
@@ -1267,36 +2002,11 @@ impl<'a> QASM3Builder {
         {
             self.define_gate(instr)?;
         }
-        let params = if self.disable_constants {
-            Python::with_gil(|_py| {
-                instr
-                    .params_view()
-                    .iter()
-                    .map(|param| match param {
-                        Param::Float(val) => Expression::Parameter(Parameter {
-                            obj: val.to_string(),
-                        }),
-                        Param::ParameterExpression(p) => {
-                            let name = Python::with_gil(|py| {
-                                let py_obj = p.bind(py);
-                                let py_str =
-                                    py_obj.str().expect("Failed to call str() on Parameter");
-                                py_str
-                                    .str()
-                                    .expect("Failed to convert PyString to &str")
-                                    .to_string()
-                            });
-                            Expression::Parameter(Parameter { obj: name })
-                        }
-                        Param::Obj(_) => panic!("Objects not supported yet"),
-                    })
-                    .collect::<Vec<_>>()
-            })
-        } else {
-            return Err(QASM3ExporterError::Error(
-                "Constant parameters not supported yet".to_string(),
-            ));
-        };
+        let params = instr
+            .params_view()
+            .iter()
+            .map(|param| self.build_param(param))
+            .collect::<ExporterResult<Vec<_>>>()?;
 
         let qargs = self
             .circuit_scope
@@ -1363,7 +2073,10 @@ impl<'a> QASM3Builder {
 
             let body = self.new_context(&instruction, |builder| {
                 for param in &params_def {
-                    let _ = builder.symbol_table.bind(&param.string);
+                    builder.symbol_table.bind(&param.string)?;
+                    builder
+                        .parameter_map
+                        .insert(param.string.clone(), param.clone());
                 }
                 for (i, q) in instruction.qubits().objects().iter().enumerate() {
                     let name = format!("{}_{}", builder._gate_qubit_prefix, i);
@@ -1379,11 +2092,11 @@ impl<'a> QASM3Builder {
 
                 let mut stmts_tmp = Vec::new();
                 for instr in instruction.data() {
-                    let _ = builder.build_instruction(instr, &mut stmts_tmp);
+                    builder.build_instruction(instr, &mut stmts_tmp)?;
                 }
-                QuantumBlock {
+                Ok(QuantumBlock {
                     statements: stmts_tmp,
-                }
+                })
             })?;
 
             let _ = self.symbol_table.register_gate(
@@ -1401,3 +2114,43 @@ impl<'a> QASM3Builder {
         }
     }
 }
+
+fn build_classical_type(ty: Type) -> ClassicalType {
+    match ty {
+        Type::Bool => ClassicalType::Bool,
+        Type::Uint(width) => ClassicalType::Uint(Uint {
+            size: Some(width as u32),
+        }),
+        Type::Float => ClassicalType::Float(Float::Double),
+        Type::Duration => ClassicalType::Duration,
+    }
+}
+
+/// Collect the names of the parameters used as `for` loop variables anywhere in a circuit.
+fn collect_loop_parameters(
+    py: Python,
+    circuit_data: &CircuitData,
+    names: &mut HashSet<String>,
+) -> ExporterResult<()> {
+    for instruction in circuit_data.data() {
+        if !instruction.op.control_flow() {
+            continue;
+        }
+        if let OperationRef::Instruction(py_inst) = instruction.op.view() {
+            if py_inst.op_name == "for_loop" {
+                let loop_parameter = py_inst
+                    .instruction
+                    .bind(py)
+                    .getattr("params")?
+                    .get_item(1)?;
+                if !loop_parameter.is_none() {
+                    names.insert(loop_parameter.getattr("name")?.extract()?);
+                }
+            }
+        }
+        for block in instruction.op.blocks() {
+            collect_loop_parameters(py, &block, names)?;
+        }
+    }
+    Ok(())
+}
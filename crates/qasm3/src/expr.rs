@@ -10,64 +10,208 @@
 // copyright notice, and modified files need to carry a notice indicating
 // that they have been altered from the originals.
 
+use std::cmp::Ordering;
+
 use pyo3::prelude::*;
-use pyo3::types::PyTuple;
+use pyo3::types::{PyFloat, PyTuple};
 use pyo3::IntoPyObjectExt;
 
 use hashbrown::HashMap;
 
 use oq3_semantics::asg;
-use oq3_semantics::symbols::{SymbolId, SymbolTable};
-use oq3_semantics::types::Type;
+use oq3_semantics::symbols::{SymbolId, SymbolTable, SymbolType};
+use oq3_semantics::types::{ArrayDims, IsConst, Type};
+
+use qiskit_circuit::classical::expr::{
+    Binary, BinaryOp, Cast, Expr, Index, Unary, UnaryOp, Value, Var,
+};
+use qiskit_circuit::classical::types::Type as ClassicalType;
+use qiskit_circuit::duration::Duration;
 
 use crate::build::PySymbolTable;
 use crate::circuit::PyRegister;
 use crate::error::QASM3ImporterError;
 
-pub fn eval_gate_param(
-    _py: Python,
-    _our_symbols: &PySymbolTable,
-    _ast_symbols: &SymbolTable,
-    param: &asg::TExpr,
-) -> PyResult<f64> {
-    // Only handling float parameters in this first pass of the importer.
-    match param.get_type() {
-        Type::Float(_, is_const) => {
-            if is_const.clone().into() {
-                match param.expression() {
-                    asg::Expr::Literal(asg::Literal::Float(lit)) => {
-                        lit.value().parse().map_err(|_| {
-                            QASM3ImporterError::new_err(format!(
-                                "invalid float literal: '{}'",
-                                lit.value()
-                            ))
-                        })
-                    }
-                    expr => Err(QASM3ImporterError::new_err(format!(
-                        "unhandled expression for floating-point constant: {expr:?}"
-                    ))),
+/// A gate parameter part-way through evaluation.  Constant values stay in Rust space for as long
+/// as possible, and we only fall back to Python-space arithmetic once a `Parameter` is involved.
+enum GateParam<'py> {
+    Float(f64),
+    Object(Bound<'py, PyAny>),
+}
+
+impl<'py> GateParam<'py> {
+    fn into_object(self, py: Python<'py>) -> Bound<'py, PyAny> {
+        match self {
+            GateParam::Float(value) => PyFloat::new(py, value).into_any(),
+            GateParam::Object(obj) => obj,
+        }
+    }
+
+    fn neg(self) -> PyResult<Self> {
+        match self {
+            GateParam::Float(value) => Ok(GateParam::Float(-value)),
+            GateParam::Object(obj) => obj.neg().map(GateParam::Object),
+        }
+    }
+
+    fn arith(self, py: Python<'py>, op: &asg::ArithOp, other: Self) -> PyResult<Self> {
+        if let (GateParam::Float(left), GateParam::Float(right)) = (&self, &other) {
+            let out = match op {
+                asg::ArithOp::Add => left + right,
+                asg::ArithOp::Sub => left - right,
+                asg::ArithOp::Mul => left * right,
+                asg::ArithOp::Div => left / right,
+                op => {
+                    return Err(QASM3ImporterError::new_err(format!(
+                        "unhandled operator in gate parameter: {op:?}"
+                    )))
                 }
-            } else {
-                Err(QASM3ImporterError::new_err(format!(
-                    "expected a constant float, but found a runtime value: {param:?}"
-                )))
+            };
+            return Ok(GateParam::Float(out));
+        }
+        let left = self.into_object(py);
+        let right = other.into_object(py);
+        match op {
+            asg::ArithOp::Add => left.add(right),
+            asg::ArithOp::Sub => left.sub(right),
+            asg::ArithOp::Mul => left.mul(right),
+            asg::ArithOp::Div => left.div(right),
+            op => Err(QASM3ImporterError::new_err(format!(
+                "unhandled operator in gate parameter: {op:?}"
+            ))),
+        }
+        .map(GateParam::Object)
+    }
+}
+
+/// Get the value of one of the built-in OpenQASM 3 constants, if `symbol_id` refers to one.
+fn builtin_constant(ast_symbols: &SymbolTable, symbol_id: &SymbolId) -> Option<f64> {
+    let symbol = &ast_symbols[symbol_id];
+    if !matches!(symbol.symbol_type(), Type::Float(_, IsConst::True)) {
+        return None;
+    }
+    match symbol.name() {
+        "pi" | "π" => Some(::std::f64::consts::PI),
+        "tau" | "τ" => Some(::std::f64::consts::TAU),
+        "euler" | "ℇ" => Some(::std::f64::consts::E),
+        _ => None,
+    }
+}
+
+fn eval_gate_param_inner<'py>(
+    py: Python<'py>,
+    our_symbols: &PySymbolTable,
+    ast_symbols: &SymbolTable,
+    param: &asg::TExpr,
+) -> PyResult<GateParam<'py>> {
+    match param.expression() {
+        asg::Expr::Literal(asg::Literal::Float(lit)) => {
+            lit.value().parse().map(GateParam::Float).map_err(|_| {
+                QASM3ImporterError::new_err(format!("invalid float literal: '{}'", lit.value()))
+            })
+        }
+        asg::Expr::Literal(asg::Literal::Int(lit)) => {
+            let value = *lit.value() as f64;
+            Ok(GateParam::Float(if *lit.sign() { value } else { -value }))
+        }
+        // Any casts inserted by the semantic analysis are between real-number types, which are all
+        // the same to a Qiskit gate parameter.
+        asg::Expr::Cast(cast) => {
+            eval_gate_param_inner(py, our_symbols, ast_symbols, cast.operand())
+        }
+        asg::Expr::UnaryExpr(unary) => match unary.op() {
+            asg::UnaryOp::Minus => {
+                eval_gate_param_inner(py, our_symbols, ast_symbols, unary.operand())?.neg()
+            }
+            op => Err(QASM3ImporterError::new_err(format!(
+                "unhandled operator in gate parameter: {op:?}"
+            ))),
+        },
+        asg::Expr::BinaryExpr(binary) => match binary.op() {
+            asg::BinaryOp::ArithOp(op) => {
+                let left = eval_gate_param_inner(py, our_symbols, ast_symbols, binary.left())?;
+                let right = eval_gate_param_inner(py, our_symbols, ast_symbols, binary.right())?;
+                left.arith(py, op, right)
+            }
+            op => Err(QASM3ImporterError::new_err(format!(
+                "unhandled operator in gate parameter: {op:?}"
+            ))),
+        },
+        asg::Expr::Identifier(symbol_id) => {
+            let symbol_id = symbol_id
+                .as_ref()
+                .map_err(|err| QASM3ImporterError::new_err(format!("internal error: {err:?}")))?;
+            if let Some(parameter) = our_symbols.params.get(symbol_id) {
+                return Ok(GateParam::Object(parameter.bind(py).clone()));
             }
+            match our_symbols.consts.get(symbol_id) {
+                Some(Expr::Value(Value::Float { raw, .. })) => return Ok(GateParam::Float(*raw)),
+                Some(Expr::Value(Value::Uint {
+                    raw,
+                    ty: ClassicalType::Uint(_),
+                })) => return Ok(GateParam::Float(*raw as f64)),
+                _ => (),
+            }
+            builtin_constant(ast_symbols, symbol_id)
+                .map(GateParam::Float)
+                .ok_or_else(|| {
+                    QASM3ImporterError::new_err(format!(
+                        "'{}' cannot be used as a gate parameter",
+                        ast_symbols[symbol_id].name()
+                    ))
+                })
         }
-        Type::Angle(_, _) => Err(QASM3ImporterError::new_err(
+        expr => Err(QASM3ImporterError::new_err(format!(
+            "unhandled expression in gate parameter: {expr:?}"
+        ))),
+    }
+}
+
+/// Evaluate a gate parameter into a Python-space object.  This is a `float` if the parameter is
+/// constant, or a `ParameterExpression` if it depends on an `input` or a loop variable.
+pub fn eval_gate_param<'py>(
+    py: Python<'py>,
+    our_symbols: &PySymbolTable,
+    ast_symbols: &SymbolTable,
+    param: &asg::TExpr,
+) -> PyResult<Bound<'py, PyAny>> {
+    if let Type::Angle(_, _) = param.get_type() {
+        return Err(QASM3ImporterError::new_err(
             "the OpenQASM 3 'angle' type is not yet supported",
-        )),
-        ty => Err(QASM3ImporterError::new_err(format!(
-            "expected an angle-like type, but saw {ty:?}"
+        ));
+    }
+    eval_gate_param_inner(py, our_symbols, ast_symbols, param).map(|param| param.into_object(py))
+}
+
+/// Evaluate a compile-time constant real-number expression.
+pub fn eval_const_float(
+    py: Python,
+    our_symbols: &PySymbolTable,
+    ast_symbols: &SymbolTable,
+    expr: &asg::TExpr,
+) -> PyResult<f64> {
+    match eval_gate_param_inner(py, our_symbols, ast_symbols, expr)? {
+        GateParam::Float(value) => Ok(value),
+        GateParam::Object(_) => Err(QASM3ImporterError::new_err(format!(
+            "expected a constant real number, but found a parameterized value: {expr:?}"
         ))),
     }
 }
 
-fn eval_const_int(_py: Python, _ast_symbols: &SymbolTable, expr: &asg::TExpr) -> PyResult<isize> {
+pub fn eval_const_int(
+    _py: Python,
+    _ast_symbols: &SymbolTable,
+    expr: &asg::TExpr,
+) -> PyResult<isize> {
     match expr.get_type() {
         Type::Int(_, is_const) | Type::UInt(_, is_const) => {
             if is_const.clone().into() {
                 match expr.expression() {
-                    asg::Expr::Literal(asg::Literal::Int(lit)) => Ok(*lit.value() as isize),
+                    asg::Expr::Literal(asg::Literal::Int(lit)) => {
+                        let value = *lit.value() as isize;
+                        Ok(if *lit.sign() { value } else { -value })
+                    }
+                    asg::Expr::Cast(cast) => eval_const_int(_py, _ast_symbols, cast.operand()),
                     expr => Err(QASM3ImporterError::new_err(format!(
                         "unhandled expression type for constant-integer evaluation: {expr:?}"
                     ))),
@@ -382,3 +526,570 @@ where
         carg,
     })
 }
+
+/// The kind of cast needed to convert a value of one classical type to another.  This mirrors
+/// `qiskit.circuit.classical.types.CastKind`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CastKind {
+    Equal,
+    Implicit,
+    Lossless,
+    Dangerous,
+    None,
+}
+
+fn cast_kind(from: ClassicalType, to: ClassicalType) -> CastKind {
+    match (from, to) {
+        (ClassicalType::Bool, ClassicalType::Bool)
+        | (ClassicalType::Float, ClassicalType::Float)
+        | (ClassicalType::Duration, ClassicalType::Duration) => CastKind::Equal,
+        (ClassicalType::Bool, ClassicalType::Uint(_) | ClassicalType::Float) => CastKind::Lossless,
+        (ClassicalType::Uint(_), ClassicalType::Bool) => CastKind::Implicit,
+        (ClassicalType::Uint(from), ClassicalType::Uint(to)) => match from.cmp(&to) {
+            Ordering::Equal => CastKind::Equal,
+            Ordering::Less => CastKind::Lossless,
+            Ordering::Greater => CastKind::Dangerous,
+        },
+        (ClassicalType::Uint(_), ClassicalType::Float)
+        | (ClassicalType::Float, ClassicalType::Uint(_) | ClassicalType::Bool) => {
+            CastKind::Dangerous
+        }
+        _ => CastKind::None,
+    }
+}
+
+/// The greater of two types, if they're ordered.  This mirrors
+/// `qiskit.circuit.classical.types.greater`.
+fn greater_type(left: ClassicalType, right: ClassicalType) -> Option<ClassicalType> {
+    match (left, right) {
+        (ClassicalType::Uint(left), ClassicalType::Uint(right)) => {
+            Some(ClassicalType::Uint(left.max(right)))
+        }
+        (left, right) if left == right => Some(left),
+        _ => None,
+    }
+}
+
+fn same_kind(left: ClassicalType, right: ClassicalType) -> bool {
+    ::std::mem::discriminant(&left) == ::std::mem::discriminant(&right)
+}
+
+/// The Qiskit classical type that represents an OpenQASM 3 type, if there is one.
+pub fn classical_type(ty: &Type) -> Option<ClassicalType> {
+    let width = |width: usize| u16::try_from(width).ok().map(ClassicalType::Uint);
+    match ty {
+        Type::Bit(_) | Type::Bool(_) => Some(ClassicalType::Bool),
+        Type::BitArray(ArrayDims::D1(size), _) => width(*size),
+        Type::UInt(Some(size), _) => width(*size as usize),
+        Type::Float(None | Some(64), _) => Some(ClassicalType::Float),
+        Type::Duration(_) | Type::Stretch(_) => Some(ClassicalType::Duration),
+        _ => None,
+    }
+}
+
+/// The minimum number of bits needed to represent an integer literal, in the same way as
+/// `qiskit.circuit.classical.expr.lift`.
+fn literal_width(value: u64) -> u16 {
+    (u64::BITS - value.leading_zeros()).max(1) as u16
+}
+
+fn uint_literal(expr: &Expr) -> Option<u64> {
+    match expr {
+        Expr::Value(Value::Uint {
+            raw,
+            ty: ClassicalType::Uint(_),
+        }) => Some(*raw),
+        _ => None,
+    }
+}
+
+fn bool_value(value: bool) -> Expr {
+    Expr::Value(Value::Uint {
+        raw: value as u64,
+        ty: ClassicalType::Bool,
+    })
+}
+
+/// Coerce `expr` to `ty` by inserting a suitable cast node, if the cast is lossless.  This
+/// mirrors `qiskit.circuit.classical.expr._coerce_lossless`.
+pub fn coerce_lossless(expr: Expr, ty: ClassicalType) -> Option<Expr> {
+    // Integer literals can just take on the type they're being coerced to, if they fit.
+    if let (Some(raw), ClassicalType::Uint(width)) = (uint_literal(&expr), ty) {
+        if literal_width(raw) <= width {
+            return Some(Expr::Value(Value::Uint { raw, ty }));
+        }
+    }
+    let implicit = match cast_kind(expr.ty(), ty) {
+        CastKind::Equal => return Some(expr),
+        CastKind::Implicit => true,
+        CastKind::Lossless => false,
+        CastKind::Dangerous | CastKind::None => return None,
+    };
+    Some(cast(expr, ty, implicit))
+}
+
+fn cast(operand: Expr, ty: ClassicalType, implicit: bool) -> Expr {
+    let constant = operand.is_const();
+    Cast {
+        operand,
+        ty,
+        constant,
+        implicit,
+    }
+    .into()
+}
+
+fn binary(op: BinaryOp, left: Expr, right: Expr, ty: ClassicalType) -> Expr {
+    let constant = left.is_const() && right.is_const();
+    Binary {
+        op,
+        left,
+        right,
+        ty,
+        constant,
+    }
+    .into()
+}
+
+fn invalid_operands(op: BinaryOp, left: &Expr, right: &Expr) -> PyErr {
+    QASM3ImporterError::new_err(format!(
+        "invalid types for '{op:?}': '{:?}' and '{:?}'",
+        left.ty(),
+        right.ty()
+    ))
+}
+
+/// Infer the widths of integer literals in either operand of a binary operation to match the
+/// other operand.  This mirrors `qiskit.circuit.classical.expr._lift_binary_operands`.
+fn lift_binary_operands(left: Expr, right: Expr) -> PyResult<(Expr, Expr)> {
+    let retype = |raw: u64, other: &Expr| match other.ty() {
+        ClassicalType::Uint(width) => {
+            if literal_width(raw) > width {
+                Err(QASM3ImporterError::new_err(format!(
+                    "integer literal '{raw}' is wider than the other operand '{other:?}'"
+                )))
+            } else {
+                Ok(Expr::Value(Value::Uint {
+                    raw,
+                    ty: ClassicalType::Uint(width),
+                }))
+            }
+        }
+        _ => Ok(Expr::Value(Value::Uint {
+            raw,
+            ty: ClassicalType::Uint(literal_width(raw)),
+        })),
+    };
+    match (uint_literal(&left), uint_literal(&right)) {
+        (Some(left_raw), Some(right_raw)) => {
+            let ty = ClassicalType::Uint(literal_width(left_raw).max(literal_width(right_raw)));
+            Ok((
+                Expr::Value(Value::Uint { raw: left_raw, ty }),
+                Expr::Value(Value::Uint { raw: right_raw, ty }),
+            ))
+        }
+        (Some(raw), None) => Ok((retype(raw, &right)?, right)),
+        (None, Some(raw)) => {
+            let right = retype(raw, &left)?;
+            Ok((left, right))
+        }
+        (None, None) => Ok((left, right)),
+    }
+}
+
+/// Build a binary expression node, resolving implicit casts in the same way as the Python-space
+/// constructor functions in `qiskit.circuit.classical.expr`.
+fn build_binary(op: BinaryOp, left: Expr, right: Expr) -> PyResult<Expr> {
+    let (left, right) = lift_binary_operands(left, right)?;
+    let (left_ty, right_ty) = (left.ty(), right.ty());
+    // Coerce both operands to the greater of their two types, and return that type.
+    let promote = |left: Expr, right: Expr| -> PyResult<(Expr, Expr, ClassicalType)> {
+        let ty =
+            greater_type(left_ty, right_ty).ok_or_else(|| invalid_operands(op, &left, &right))?;
+        match (coerce_lossless(left, ty), coerce_lossless(right, ty)) {
+            (Some(left), Some(right)) => Ok((left, right, ty)),
+            _ => Err(QASM3ImporterError::new_err(format!(
+                "invalid types for '{op:?}': '{left_ty:?}' and '{right_ty:?}'"
+            ))),
+        }
+    };
+    let numeric = |ty: ClassicalType| matches!(ty, ClassicalType::Uint(_) | ClassicalType::Float);
+    match op {
+        BinaryOp::BitAnd | BinaryOp::BitOr | BinaryOp::BitXor
+            if same_kind(left_ty, right_ty)
+                && matches!(left_ty, ClassicalType::Bool | ClassicalType::Uint(_)) =>
+        {
+            let (left, right, ty) = promote(left, right)?;
+            Ok(binary(op, left, right, ty))
+        }
+        BinaryOp::Equal | BinaryOp::NotEqual if same_kind(left_ty, right_ty) => {
+            let (left, right, _) = promote(left, right)?;
+            Ok(binary(op, left, right, ClassicalType::Bool))
+        }
+        BinaryOp::Add | BinaryOp::Sub
+            if same_kind(left_ty, right_ty) && left_ty != ClassicalType::Bool =>
+        {
+            let (left, right, ty) = promote(left, right)?;
+            Ok(binary(op, left, right, ty))
+        }
+        BinaryOp::Mul => match (left_ty, right_ty) {
+            (ClassicalType::Duration, ClassicalType::Duration) => {
+                Err(QASM3ImporterError::new_err("cannot multiply two durations"))
+            }
+            (left_ty, right_ty) if same_kind(left_ty, right_ty) && numeric(left_ty) => {
+                let (left, right, ty) = promote(left, right)?;
+                Ok(binary(op, left, right, ty))
+            }
+            (ClassicalType::Duration, ty) | (ty, ClassicalType::Duration) if numeric(ty) => {
+                Ok(binary(op, left, right, ClassicalType::Duration))
+            }
+            _ => Err(invalid_operands(op, &left, &right)),
+        },
+        BinaryOp::Div => match (left_ty, right_ty) {
+            (ClassicalType::Duration, ClassicalType::Duration) => {
+                Ok(binary(op, left, right, ClassicalType::Float))
+            }
+            (left_ty, right_ty) if same_kind(left_ty, right_ty) && numeric(left_ty) => {
+                let (left, right, ty) = promote(left, right)?;
+                Ok(binary(op, left, right, ty))
+            }
+            (ClassicalType::Duration, ty) if numeric(ty) => {
+                Ok(binary(op, left, right, ClassicalType::Duration))
+            }
+            _ => Err(invalid_operands(op, &left, &right)),
+        },
+        _ => Err(invalid_operands(op, &left, &right)),
+    }
+}
+
+/// Cast `operand` to the OpenQASM 3 type `ty`.  Casts of literals are folded into the literal.
+fn build_cast(operand: Expr, ty: &Type) -> PyResult<Expr> {
+    let Some(ty) = classical_type(ty) else {
+        // The semantic analysis inserts casts to types that Qiskit doesn't represent, such as
+        // unsized integers or types that failed to infer.  We handle the typing ourselves, so we
+        // can ignore these.
+        return Ok(operand);
+    };
+    let folded = match (&operand, ty) {
+        (Expr::Value(Value::Uint { raw, .. }), ClassicalType::Bool) => Some(bool_value(*raw != 0)),
+        (Expr::Value(Value::Uint { raw, .. }), ClassicalType::Float) => {
+            Some(Expr::Value(Value::Float {
+                raw: *raw as f64,
+                ty,
+            }))
+        }
+        (Expr::Value(Value::Uint { raw, .. }), ClassicalType::Uint(width))
+            if literal_width(*raw) <= width =>
+        {
+            Some(Expr::Value(Value::Uint { raw: *raw, ty }))
+        }
+        _ => None,
+    };
+    if let Some(folded) = folded {
+        return Ok(folded);
+    }
+    match cast_kind(operand.ty(), ty) {
+        CastKind::Equal => Ok(operand),
+        CastKind::Implicit => Ok(cast(operand, ty, true)),
+        CastKind::Lossless | CastKind::Dangerous => Ok(cast(operand, ty, false)),
+        CastKind::None => Err(QASM3ImporterError::new_err(format!(
+            "cannot cast a value of type '{:?}' to '{ty:?}'",
+            operand.ty()
+        ))),
+    }
+}
+
+fn eval_duration(value: f64, unit: &asg::TimeUnit) -> PyResult<Duration> {
+    match unit {
+        asg::TimeUnit::Cycle => {
+            if value.fract() == 0.0 {
+                Ok(Duration::dt(value as i64))
+            } else {
+                Err(QASM3ImporterError::new_err(format!(
+                    "durations in 'dt' must be integers, but found '{value}'"
+                )))
+            }
+        }
+        asg::TimeUnit::NanoSecond => Ok(Duration::ns(value)),
+        asg::TimeUnit::MicroSecond => Ok(Duration::us(value)),
+        asg::TimeUnit::MilliSecond => Ok(Duration::ms(value)),
+        asg::TimeUnit::Second => Ok(Duration::s(value)),
+    }
+}
+
+fn eval_literal(literal: &asg::Literal) -> PyResult<Expr> {
+    let signed = |value: f64, sign: bool| if sign { value } else { -value };
+    match literal {
+        asg::Literal::Bool(lit) => Ok(bool_value(*lit.value())),
+        asg::Literal::Int(lit) => {
+            let raw = u64::try_from(*lit.value())
+                .ok()
+                .filter(|_| *lit.sign())
+                .ok_or_else(|| {
+                    QASM3ImporterError::new_err(format!(
+                        "integer literal '{}' cannot be represented as an unsigned 64-bit integer",
+                        signed(*lit.value() as f64, *lit.sign())
+                    ))
+                })?;
+            Ok(Expr::Value(Value::Uint {
+                raw,
+                ty: ClassicalType::Uint(literal_width(raw)),
+            }))
+        }
+        asg::Literal::Float(lit) => lit
+            .value()
+            .parse()
+            .map(|raw| {
+                Expr::Value(Value::Float {
+                    raw,
+                    ty: ClassicalType::Float,
+                })
+            })
+            .map_err(|_| {
+                QASM3ImporterError::new_err(format!("invalid float literal: '{}'", lit.value()))
+            }),
+        asg::Literal::BitString(lit) => {
+            let bits = lit.value().replace('_', "");
+            let raw = u64::from_str_radix(&bits, 2).map_err(|_| {
+                QASM3ImporterError::new_err(format!(
+                    "bit-string literal '{}' cannot be represented as an unsigned 64-bit integer",
+                    lit.value()
+                ))
+            })?;
+            Ok(Expr::Value(Value::Uint {
+                raw,
+                ty: ClassicalType::Uint(bits.len() as u16),
+            }))
+        }
+        asg::Literal::TimingIntLiteral(lit) => {
+            eval_duration(signed(*lit.value() as f64, *lit.sign()), lit.time_unit())
+                .map(|duration| Expr::Value(Value::Duration(duration)))
+        }
+        asg::Literal::TimingFloatLiteral(lit) => {
+            eval_duration(signed(*lit.value(), *lit.sign()), lit.time_unit())
+                .map(|duration| Expr::Value(Value::Duration(duration)))
+        }
+        literal => Err(QASM3ImporterError::new_err(format!(
+            "unhandled literal in classical expression: {literal:?}"
+        ))),
+    }
+}
+
+fn eval_identifier(
+    py: Python,
+    our_symbols: &PySymbolTable,
+    ast_symbols: &SymbolTable,
+    symbol_id: &SymbolId,
+) -> PyResult<Expr> {
+    if let Some(bit) = our_symbols.clbits.get(symbol_id) {
+        return Ok(Var::Bit {
+            bit: bit.extract(py)?,
+        }
+        .into());
+    }
+    if let Some(register) = our_symbols.cregs.get(symbol_id) {
+        let size = register.bit_list(py).len();
+        return Ok(Var::Register {
+            register: register.clone().into_pyobject(py)?.extract()?,
+            ty: ClassicalType::Uint(size as u16),
+        }
+        .into());
+    }
+    if let Some(var) = our_symbols.vars.get(symbol_id) {
+        return Ok(var.clone().into());
+    }
+    if let Some(stretch) = our_symbols.stretches.get(symbol_id) {
+        return Ok(stretch.clone().into());
+    }
+    if let Some(value) = our_symbols.consts.get(symbol_id) {
+        return Ok(value.clone());
+    }
+    if let Some(value) = builtin_constant(ast_symbols, symbol_id) {
+        return Ok(Expr::Value(Value::Float {
+            raw: value,
+            ty: ClassicalType::Float,
+        }));
+    }
+    let name = ast_symbols[symbol_id].name();
+    if our_symbols.params.contains_key(symbol_id) {
+        Err(QASM3ImporterError::new_err(format!(
+            "'{name}' can only be used in gate parameters"
+        )))
+    } else if our_symbols.untyped_vars.contains_key(symbol_id) {
+        Err(QASM3ImporterError::new_err(format!(
+            "cannot infer the width of '{name}' before it is assigned to"
+        )))
+    } else {
+        Err(QASM3ImporterError::new_err(format!(
+            "'{name}' cannot be used in a classical expression"
+        )))
+    }
+}
+
+fn eval_indexed_identifier(
+    py: Python,
+    our_symbols: &PySymbolTable,
+    ast_symbols: &SymbolTable,
+    indexed: &asg::IndexedIdentifier,
+) -> PyResult<Expr> {
+    let symbol_id = indexed
+        .identifier()
+        .as_ref()
+        .map_err(|err| QASM3ImporterError::new_err(format!("internal error: {err:?}")))?;
+    let index = match indexed.indexes() {
+        [asg::IndexOperator::ExpressionList(list)] if list.len() == 1 => &list.expressions[0],
+        _ => {
+            return Err(QASM3ImporterError::new_err(
+                "only single indices into classical values are supported",
+            ))
+        }
+    };
+    // Indexing into a register is resolved to the individual bit, as it would be in Qiskit.
+    if our_symbols.cregs.contains_key(symbol_id) {
+        let register =
+            broadcast_bits_for_identifier(py, &our_symbols.clbits, &our_symbols.cregs, symbol_id)?;
+        return match broadcast_apply_index(py, ast_symbols, register, &indexed.indexes()[0])? {
+            BroadcastItem::Bit(bit) => Ok(Var::Bit {
+                bit: bit.extract(py)?,
+            }
+            .into()),
+            BroadcastItem::Register(_) => Err(QASM3ImporterError::new_err(
+                "internal error: register index did not produce a bit",
+            )),
+        };
+    }
+    let target = eval_identifier(py, our_symbols, ast_symbols, symbol_id)?;
+    let index = eval_classical(py, our_symbols, ast_symbols, index)?;
+    match (target.ty(), index.ty()) {
+        (ClassicalType::Uint(_), ClassicalType::Uint(_)) => {
+            let constant = target.is_const() && index.is_const();
+            Ok(Index {
+                target,
+                index,
+                ty: ClassicalType::Bool,
+                constant,
+            }
+            .into())
+        }
+        (target_ty, index_ty) => Err(QASM3ImporterError::new_err(format!(
+            "invalid types for indexing: '{target_ty:?}' and '{index_ty:?}'"
+        ))),
+    }
+}
+
+/// Evaluate an OpenQASM 3 classical expression into a Qiskit classical expression.
+///
+/// The semantic analysis does not yet resolve the types of all classical expressions, so we
+/// infer types ourselves using the same rules as the Python-space constructors in
+/// `qiskit.circuit.classical.expr`, and only use the OpenQASM 3 types to resolve explicit casts.
+pub fn eval_classical(
+    py: Python,
+    our_symbols: &PySymbolTable,
+    ast_symbols: &SymbolTable,
+    expr: &asg::TExpr,
+) -> PyResult<Expr> {
+    match expr.expression() {
+        asg::Expr::Literal(literal) => eval_literal(literal),
+        asg::Expr::Identifier(symbol_id) => {
+            let symbol_id = symbol_id
+                .as_ref()
+                .map_err(|err| QASM3ImporterError::new_err(format!("internal error: {err:?}")))?;
+            eval_identifier(py, our_symbols, ast_symbols, symbol_id)
+        }
+        asg::Expr::IndexedIdentifier(indexed) => {
+            eval_indexed_identifier(py, our_symbols, ast_symbols, indexed)
+        }
+        asg::Expr::Cast(cast) => build_cast(
+            eval_classical(py, our_symbols, ast_symbols, cast.operand())?,
+            cast.get_type(),
+        ),
+        asg::Expr::UnaryExpr(unary) => {
+            let operand = eval_classical(py, our_symbols, ast_symbols, unary.operand())?;
+            let (op, operand) = match unary.op() {
+                asg::UnaryOp::Minus => {
+                    // Qiskit has no negation operator, but we can still negate constants.
+                    return match operand {
+                        Expr::Value(Value::Float { raw, ty }) => {
+                            Ok(Expr::Value(Value::Float { raw: -raw, ty }))
+                        }
+                        operand => Err(QASM3ImporterError::new_err(format!(
+                            "cannot negate a value of type '{:?}'",
+                            operand.ty()
+                        ))),
+                    };
+                }
+                asg::UnaryOp::Not => (
+                    UnaryOp::LogicNot,
+                    coerce_lossless(operand, ClassicalType::Bool)
+                        .ok_or_else(|| QASM3ImporterError::new_err("invalid operand for '!'"))?,
+                ),
+                asg::UnaryOp::BitNot => match operand.ty() {
+                    ClassicalType::Bool | ClassicalType::Uint(_) => (UnaryOp::BitNot, operand),
+                    ty => {
+                        return Err(QASM3ImporterError::new_err(format!(
+                            "cannot apply '~' to type '{ty:?}'"
+                        )))
+                    }
+                },
+            };
+            let (ty, constant) = (operand.ty(), operand.is_const());
+            Ok(Unary {
+                op,
+                operand,
+                ty,
+                constant,
+            }
+            .into())
+        }
+        asg::Expr::BinaryExpr(binary) => {
+            let op = match binary.op() {
+                asg::BinaryOp::ArithOp(asg::ArithOp::Add) => BinaryOp::Add,
+                asg::BinaryOp::ArithOp(asg::ArithOp::Sub) => BinaryOp::Sub,
+                asg::BinaryOp::ArithOp(asg::ArithOp::Mul) => BinaryOp::Mul,
+                asg::BinaryOp::ArithOp(asg::ArithOp::Div) => BinaryOp::Div,
+                asg::BinaryOp::ArithOp(asg::ArithOp::BitAnd) => BinaryOp::BitAnd,
+                asg::BinaryOp::ArithOp(asg::ArithOp::BitXOr) => BinaryOp::BitXor,
+                asg::BinaryOp::CmpOp(asg::CmpOp::Eq) => BinaryOp::Equal,
+                asg::BinaryOp::CmpOp(asg::CmpOp::Neq) => BinaryOp::NotEqual,
+                op => {
+                    return Err(QASM3ImporterError::new_err(format!(
+                        "unhandled binary operator: {op:?}"
+                    )))
+                }
+            };
+            let left = eval_classical(py, our_symbols, ast_symbols, binary.left())?;
+            let right = eval_classical(py, our_symbols, ast_symbols, binary.right())?;
+            build_binary(op, left, right)
+        }
+        expr => Err(QASM3ImporterError::new_err(format!(
+            "unhandled classical expression: {expr:?}"
+        ))),
+    }
+}
+
+/// Evaluate the target of a classical assignment into a Qiskit classical expression.
+pub fn eval_lvalue(
+    py: Python,
+    our_symbols: &PySymbolTable,
+    ast_symbols: &SymbolTable,
+    lvalue: &asg::LValue,
+) -> PyResult<Expr> {
+    let target = match lvalue {
+        asg::LValue::Identifier(symbol_id) => {
+            let symbol_id = symbol_id
+                .as_ref()
+                .map_err(|err| QASM3ImporterError::new_err(format!("internal error: {err:?}")))?;
+            eval_identifier(py, our_symbols, ast_symbols, symbol_id)?
+        }
+        asg::LValue::IndexedIdentifier(indexed) => {
+            eval_indexed_identifier(py, our_symbols, ast_symbols, indexed)?
+        }
+    };
+    match &target {
+        Expr::Var(_) => Ok(target),
+        Expr::Index(index) if matches!(index.target, Expr::Var(_)) => Ok(target),
+        _ => Err(QASM3ImporterError::new_err(format!(
+            "cannot assign to '{target:?}'"
+        ))),
+    }
+}
@@ -10,6 +10,7 @@
 // copyright notice, and modified files need to carry a notice indicating
 // that they have been altered from the originals.
 
+mod annotation;
 mod ast;
 mod build;
 mod circuit;
@@ -20,18 +21,64 @@ mod printer;
 
 use std::ffi::OsString;
 use std::ops::Deref;
+use std::panic::RefUnwindSafe;
 use std::path::{Path, PathBuf};
 
 use hashbrown::HashMap;
 
 use pyo3::prelude::*;
-use pyo3::types::{PyAny, PyDict, PyModule};
+use pyo3::types::{PyAny, PyDict, PyModule, PyRange};
 
+use oq3_semantics::context::Context;
+use oq3_semantics::semantic_error::{SemanticErrorKind, SemanticErrorList};
 use oq3_semantics::syntax_to_semantics::parse_source_string;
 use pyo3::pybacked::PyBackedStr;
 use qiskit_circuit::circuit_data::CircuitData;
 
+use crate::annotation::AnnotationHandlers;
 use crate::error::QASM3ImporterError;
+pub use crate::error::{Diagnostic, ImporterError, Severity};
+use crate::printer::PrinterOptions;
+
+/// Whether the semantic analysis produced any errors that should stop the import.
+///
+/// The semantic analysis does not yet correctly type all classical expressions, and reports
+/// incompatible types in many valid programs.  We resolve the types of classical expressions
+/// ourselves during the build, so these particular errors are not fatal.
+fn any_fatal_errors(errors: &SemanticErrorList) -> bool {
+    errors
+        .iter()
+        .any(|err| !matches!(err.kind(), SemanticErrorKind::IncompatibleTypesError))
+        || errors.include_errors().iter().any(any_fatal_errors)
+}
+
+/// Parse an OpenQASM 3 program and run the semantic analysis on it.  `file` is the path the source
+/// was read from, if any, which is only used to report the locations of problems.
+///
+/// On failure, the returned error contains structured diagnostics for every problem found.
+pub fn parse<P: AsRef<Path> + RefUnwindSafe>(
+    source: &str,
+    file: Option<&Path>,
+    include_path: &[P],
+) -> Result<Context, ImporterError> {
+    let boxes = error::box_diagnostics(file, source);
+    if !boxes.is_empty() {
+        return Err(ImporterError::Invalid(boxes));
+    }
+    // The parser panics on some valid constructs that it does not yet support.
+    let result =
+        ::std::panic::catch_unwind(|| parse_source_string(source, None, Some(include_path)))
+            .map_err(|_| ImporterError::Unsupported)?;
+    let mut diagnostics = error::syntax_diagnostics(file, source, result.syntax_result());
+    let context = result.take_context();
+    let fatal = !diagnostics.is_empty() || any_fatal_errors(context.errors());
+    diagnostics.extend(error::semantic_diagnostics(file, source, context.errors()));
+    if fatal {
+        Err(ImporterError::Invalid(diagnostics))
+    } else {
+        Ok(context)
+    }
+}
 
 /// Load an OpenQASM 3 program from a string into a :class:`.QuantumCircuit`.
 ///
@@ -55,8 +102,8 @@ use crate::error::QASM3ImporterError;
 ///
 /// Raises:
 ///     :exc:`.QASM3ImporterError`: if an error occurred during parsing or semantic analysis.
-///         In the case of a parsing error, most of the error messages are printed to the terminal
-///         and formatted, for better legibility.
+///         In the case of a parsing error, the ``diagnostics`` attribute of the exception contains
+///         a :class:`.qasm3.Diagnostic` with the location of each problem in the source.
 #[pyfunction]
 #[pyo3(signature = (source, /, *, custom_gates=None, include_path=None))]
 pub fn loads(
@@ -64,6 +111,16 @@ pub fn loads(
     source: String,
     custom_gates: Option<Vec<circuit::PyGate>>,
     include_path: Option<Vec<OsString>>,
+) -> PyResult<circuit::PyCircuit> {
+    load_source(py, &source, None, custom_gates, include_path)
+}
+
+fn load_source(
+    py: Python,
+    source: &str,
+    file: Option<&Path>,
+    custom_gates: Option<Vec<circuit::PyGate>>,
+    include_path: Option<Vec<OsString>>,
 ) -> PyResult<circuit::PyCircuit> {
     let default_include_path = || -> PyResult<Vec<OsString>> {
         let filename: PyBackedStr = py.import("qiskit")?.filename()?.try_into()?;
@@ -74,13 +131,7 @@ pub fn loads(
             .into_os_string()])
     };
     let include_path = include_path.map(Ok).unwrap_or_else(default_include_path)?;
-    let result = parse_source_string(source, None, Some(&include_path));
-    if result.any_errors() {
-        result.print_errors();
-        return Err(QASM3ImporterError::new_err(
-            "errors during parsing; see printed errors",
-        ));
-    }
+    let context = parse(source, file, &include_path)?;
     let gates = match custom_gates {
         Some(gates) => gates
             .into_iter()
@@ -96,7 +147,7 @@ pub fn loads(
             })
             .collect::<PyResult<HashMap<_, _>>>()?,
     };
-    crate::build::convert_asg(py, result.program(), result.symbol_table(), gates)
+    crate::build::convert_asg(py, context.program(), context.symbol_table(), gates)
 }
 
 /// Load an OpenQASM 3 program from a source file into a :class:`.QuantumCircuit`.
@@ -125,8 +176,8 @@ pub fn loads(
 ///
 /// Raises:
 ///     :exc:`.QASM3ImporterError`: if an error occurred during parsing or semantic analysis.
-///         In the case of a parsing error, most of the error messages are printed to the terminal
-///         and formatted, for better legibility.
+///         In the case of a parsing error, the ``diagnostics`` attribute of the exception contains
+///         a :class:`.qasm3.Diagnostic` with the location of each problem in the source.
 #[pyfunction]
 #[pyo3(
     signature = (pathlike_or_filelike, /, *, custom_gates=None, include_path=None),
@@ -137,30 +188,32 @@ pub fn load(
     custom_gates: Option<Vec<circuit::PyGate>>,
     include_path: Option<Vec<OsString>>,
 ) -> PyResult<circuit::PyCircuit> {
-    let source =
-        if pathlike_or_filelike.is_instance(&PyModule::import(py, "io")?.getattr("TextIOBase")?)? {
-            pathlike_or_filelike
-                .call_method0("read")?
-                .extract::<String>()?
-        } else {
-            let path = PyModule::import(py, "os")?
-                .getattr("fspath")?
-                .call1((pathlike_or_filelike,))?
-                .extract::<OsString>()?;
-            ::std::fs::read_to_string(&path).map_err(|err| {
-                QASM3ImporterError::new_err(format!("failed to read file '{:?}': {:?}", &path, err))
-            })?
-        };
-    loads(py, source, custom_gates, include_path)
+    if pathlike_or_filelike.is_instance(&PyModule::import(py, "io")?.getattr("TextIOBase")?)? {
+        let source = pathlike_or_filelike
+            .call_method0("read")?
+            .extract::<String>()?;
+        load_source(py, &source, None, custom_gates, include_path)
+    } else {
+        let path = PyModule::import(py, "os")?
+            .getattr("fspath")?
+            .call1((pathlike_or_filelike,))?
+            .extract::<PathBuf>()?;
+        let source = ::std::fs::read_to_string(&path).map_err(|err| {
+            QASM3ImporterError::new_err(format!("failed to read file '{:?}': {:?}", &path, err))
+        })?;
+        load_source(py, &source, Some(&path), custom_gates, include_path)
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 struct DumpOptions {
     includes: Vec<String>,
     basis_gates: Vec<String>,
     disable_constants: bool,
     allow_aliasing: bool,
-    indent: String,
+    printer_options: PrinterOptions,
+    annotation_handlers: AnnotationHandlers,
+    source_map: Option<Py<PyDict>>,
 }
 
 impl Default for DumpOptions {
@@ -170,37 +223,63 @@ impl Default for DumpOptions {
             basis_gates: vec![],
             disable_constants: true,
             allow_aliasing: false,
-            indent: "  ".to_string(),
+            printer_options: PrinterOptions::default(),
+            annotation_handlers: AnnotationHandlers::new(),
+            source_map: None,
         }
     }
 }
 
-#[pyfunction]
-#[pyo3(signature = (circuit, /, kwargs=None))]
-pub fn dumps(
-    _py: Python,
-    circuit: &Bound<PyAny>,
-    kwargs: Option<&Bound<PyDict>>,
-) -> PyResult<String> {
-    let mut options = DumpOptions::default();
-
-    if let Some(kw) = kwargs {
-        if let Some(val) = kw.get_item("includes")? {
-            options.includes = val.extract::<Vec<String>>()?;
-        }
-        if let Some(val) = kw.get_item("basis_gates")? {
-            options.basis_gates = val.extract::<Vec<String>>()?;
-        }
-        if let Some(val) = kw.get_item("disable_constants")? {
-            options.disable_constants = val.extract::<bool>()?;
-        }
-        if let Some(val) = kw.get_item("allow_aliasing")? {
-            options.allow_aliasing = val.extract::<bool>()?;
-        }
-        if let Some(val) = kw.get_item("indent")? {
-            options.indent = val.extract::<String>()?;
+impl DumpOptions {
+    fn from_kwargs(kwargs: Option<&Bound<PyDict>>) -> PyResult<Self> {
+        let mut options = DumpOptions::default();
+        if let Some(kw) = kwargs {
+            if let Some(val) = kw.get_item("includes")? {
+                options.includes = val.extract::<Vec<String>>()?;
+            }
+            if let Some(val) = kw.get_item("basis_gates")? {
+                options.basis_gates = val.extract::<Vec<String>>()?;
+            }
+            if let Some(val) = kw.get_item("disable_constants")? {
+                options.disable_constants = val.extract::<bool>()?;
+            }
+            if let Some(val) = kw.get_item("allow_aliasing")? {
+                options.allow_aliasing = val.extract::<bool>()?;
+            }
+            if let Some(val) = kw.get_item("indent")? {
+                options.printer_options.indent = val.extract::<String>()?;
+            }
+            if let Some(val) = kw.get_item("chain_else_if")? {
+                options.printer_options.chain_else_if = val.extract::<bool>()?;
+            }
+            if let Some(val) = kw.get_item("max_line_width")? {
+                options.printer_options.max_line_width = val.extract::<Option<usize>>()?;
+            }
+            if let Some(val) = kw.get_item("label_comments")? {
+                options.printer_options.label_comments = val.extract::<bool>()?;
+            }
+            if let Some(val) = kw.get_item("source_map")? {
+                options.source_map = val.extract::<Option<Py<PyDict>>>()?;
+            }
+            if let Some(val) = kw.get_item("annotation_handlers")? {
+                if !val.is_none() {
+                    options.annotation_handlers =
+                        AnnotationHandlers::from_dict(val.downcast::<PyDict>()?)?;
+                }
+            }
         }
+        Ok(options)
     }
+}
+
+/// Export `circuit` with the given options.  `function` is the name of the public function doing
+/// the export, for error messages.
+fn export_circuit(
+    py: Python,
+    circuit: &Bound<PyAny>,
+    options: DumpOptions,
+    function: &str,
+) -> PyResult<String> {
     let circuit_data = circuit
         .getattr("_data")?
         .downcast::<CircuitData>()?
@@ -213,74 +292,65 @@ pub fn dumps(
         options.basis_gates,
         options.disable_constants,
         options.allow_aliasing,
-        options.indent,
+        options.printer_options,
+        options.annotation_handlers,
     );
 
-    let stream = exporter.dumps(&circuit_data, islayout).map_err(|err| {
+    let to_py_err = |err| {
         QASM3ImporterError::new_err(format!(
-            "failed to export circuit using qasm3.dumps_experimental: {err:?}"
+            "failed to export circuit using qasm3.{function}: {err:?}"
         ))
-    })?;
-
+    };
+    let Some(py_source_map) = options.source_map else {
+        return exporter.dumps(&circuit_data, islayout).map_err(to_py_err);
+    };
+    let (stream, source_map) = exporter
+        .dumps_with_source_map(&circuit_data, islayout)
+        .map_err(to_py_err)?;
+    // The source map is written into the given dictionary as a mapping of instruction indices to
+    // `range` objects of the (zero-based) lines that each instruction was written to.
+    let py_source_map = py_source_map.bind(py);
+    for (index, lines) in source_map {
+        py_source_map.set_item(
+            index,
+            PyRange::new(py, lines.start as isize, lines.end as isize)?,
+        )?;
+    }
     Ok(stream)
 }
 
+/// Export a :class:`.QuantumCircuit` to an OpenQASM 3 program in a string.
+///
+/// If the ``source_map`` option is a dictionary, it is filled with a mapping from the index of each
+/// top-level instruction in :attr:`.QuantumCircuit.data` to a :class:`range` of the (zero-based)
+/// lines of the program that the instruction was written to.  Instructions nested in the blocks of
+/// control-flow operations do not have their own entries; they are covered by the range of the
+/// control-flow instruction that contains them.
+#[pyfunction]
+#[pyo3(signature = (circuit, /, kwargs=None))]
+pub fn dumps(
+    py: Python,
+    circuit: &Bound<PyAny>,
+    kwargs: Option<&Bound<PyDict>>,
+) -> PyResult<String> {
+    let options = DumpOptions::from_kwargs(kwargs)?;
+    export_circuit(py, circuit, options, "dumps_experimental")
+}
+
+/// Export a :class:`.QuantumCircuit` to an OpenQASM 3 program, writing it to a text stream.
+///
+/// The ``source_map`` option has the same meaning as for :func:`.qasm3.dumps_experimental`.
 #[pyfunction]
 #[pyo3(signature = (circuit,stream, /, kwargs=None))]
 pub fn dump(
-    _py: Python,
+    py: Python,
     circuit: &Bound<PyAny>,
     stream: &Bound<PyAny>,
     kwargs: Option<&Bound<PyDict>>,
 ) -> PyResult<()> {
-    let mut options = DumpOptions::default();
-
-    if let Some(kw) = kwargs {
-        if let Some(val) = kw.get_item("includes")? {
-            options.includes = val.extract::<Vec<String>>()?;
-        }
-        if let Some(val) = kw.get_item("basis_gates")? {
-            options.basis_gates = val.extract::<Vec<String>>()?;
-        }
-        if let Some(val) = kw.get_item("disable_constants")? {
-            options.disable_constants = val.extract::<bool>()?;
-        }
-        if let Some(val) = kw.get_item("allow_aliasing")? {
-            options.allow_aliasing = val.extract::<bool>()?;
-        }
-        if let Some(val) = kw.get_item("indent")? {
-            options.indent = val.extract::<String>()?;
-        }
-    }
-    let circuit_data = circuit
-        .getattr("_data")?
-        .downcast::<CircuitData>()?
-        .borrow();
-
-    let islayout = !circuit.getattr("layout")?.is_none();
-
-    let exporter = exporter::Exporter::new(
-        options.includes,
-        options.basis_gates,
-        options.disable_constants,
-        options.allow_aliasing,
-        options.indent,
-    );
-
-    let mut output = Vec::new();
-    exporter
-        .dump(&circuit_data, islayout, &mut output)
-        .map_err(|err| {
-            QASM3ImporterError::new_err(format!(
-                "failed to export circuit using qasm3.dump_experimental: {err:?}"
-            ))
-        })?;
-
-    let output_str = String::from_utf8(output)
-        .map_err(|e| QASM3ImporterError::new_err(format!("invalid utf-8 output: {e:?}")))?;
-
-    stream.call_method1("write", (output_str,))?;
-
+    let options = DumpOptions::from_kwargs(kwargs)?;
+    let output = export_circuit(py, circuit, options, "dump_experimental")?;
+    stream.call_method1("write", (output,))?;
     Ok(())
 }
 
@@ -292,5 +362,7 @@ pub fn qasm3(module: &Bound<PyModule>) -> PyResult<()> {
     module.add_function(wrap_pyfunction!(dumps, module)?)?;
     module.add_function(wrap_pyfunction!(dump, module)?)?;
     module.add_class::<circuit::PyGate>()?;
+    module.add_class::<error::Diagnostic>()?;
+    module.add_class::<error::Severity>()?;
     Ok(())
 }
@@ -11,17 +11,21 @@
 // that they have been altered from the originals.
 
 use hashbrown::HashMap;
+use indexmap::IndexMap;
 
 use std::fmt::Write;
+use std::ops::Range as LineRange;
 
 use crate::ast::{
-    Alias, Assignment, Barrier, Binary, BinaryOp, BitArray, BooleanLiteral, Cast,
-    ClassicalDeclaration, ClassicalType, Constant, Delay, DurationLiteral, Expression, Float,
-    GateCall, Header, Identifier, IdentifierOrSubscripted, Include, Index, IndexSet, Int,
-    IntegerLiteral, Node, Parameter, Program, ProgramBlock, QuantumBlock, QuantumDeclaration,
-    QuantumGateDefinition, QuantumGateModifier, QuantumGateModifierName, QuantumGateSignature,
-    QuantumInstruction, QuantumMeasurement, QuantumMeasurementAssignment, Range, Reset, Statement,
-    SubscriptedIdentifier, Uint, Unary, UnaryOp, Version, OP,
+    Alias, Annotation, Assignment, Barrier, Binary, BinaryOp, BitArray, BooleanLiteral,
+    BoxStatement, BranchingStatement, Cast, ClassicalDeclaration, ClassicalType, Constant, Delay,
+    DurationLiteral, Expression, Float, ForLoopIndexSet, ForLoopStatement, FunctionCall, GateCall,
+    Header, IODeclaration, IOModifier, Identifier, IdentifierOrSubscripted, Include, Index,
+    IndexSet, InstructionStatements, Int, IntegerLiteral, Node, Parameter, Program, ProgramBlock,
+    QuantumBlock, QuantumDeclaration, QuantumGateDefinition, QuantumGateModifier,
+    QuantumGateModifierName, QuantumGateSignature, QuantumInstruction, QuantumMeasurement,
+    QuantumMeasurementAssignment, Range, Reset, Statement, StretchDeclaration,
+    SubscriptedIdentifier, SwitchStatement, Uint, Unary, UnaryOp, Version, WhileLoopStatement, OP,
 };
 
 #[derive(Debug)]
@@ -36,11 +40,45 @@ impl BindingPower {
     }
 }
 
+/// Options controlling how a [BasicPrinter] formats its output.
+#[derive(Debug, Clone)]
+pub struct PrinterOptions {
+    /// The string to use as a single indentation level.
+    pub indent: String,
+    /// Whether to collapse an ``else`` block that contains only an ``if`` statement into an
+    /// ``else if``.  This flatter form may have less support on backends.
+    pub chain_else_if: bool,
+    /// The width in characters beyond which the arguments and operands of a statement are wrapped
+    /// onto continuation lines.  Lines are only broken between arguments, so a single long
+    /// argument can still make a line longer than this.
+    pub max_line_width: Option<usize>,
+    /// Whether to write the label of each labelled instruction in a comment before it.
+    pub label_comments: bool,
+}
+
+impl Default for PrinterOptions {
+    fn default() -> Self {
+        Self {
+            indent: "  ".to_string(),
+            chain_else_if: false,
+            max_line_width: None,
+            label_comments: false,
+        }
+    }
+}
+
+/// A map from the index of each top-level instruction of a circuit to the half-open range of
+/// (zero-based) lines of the output that it was written to.
+pub type SourceMap = IndexMap<usize, LineRange<usize>>;
+
 pub struct BasicPrinter<'a> {
     stream: &'a mut String,
-    indent: String,
+    options: PrinterOptions,
     current_indent: usize,
-    _chain_else_if: bool,
+    source_map: SourceMap,
+    // The number of complete lines in `stream[..counted_bytes]`, for building the source map.
+    counted_lines: usize,
+    counted_bytes: usize,
     constant_lookup: HashMap<Constant, &'static str>,
     modifier_lookup: HashMap<QuantumGateModifierName, &'static str>,
     float_width_lookup: HashMap<Float, String>,
@@ -48,7 +86,7 @@ pub struct BasicPrinter<'a> {
 }
 
 impl<'a> BasicPrinter<'a> {
-    pub fn new(stream: &'a mut String, indent: String, _chain_else_if: bool) -> Self {
+    pub fn new(stream: &'a mut String, options: PrinterOptions) -> Self {
         let mut constant_lookup = HashMap::new();
         constant_lookup.insert(Constant::PI, "pi");
         constant_lookup.insert(Constant::Euler, "euler");
@@ -65,6 +103,12 @@ impl<'a> BasicPrinter<'a> {
         let mut binding_power = HashMap::new();
         binding_power.insert(OP::UnaryOp(&UnaryOp::LogicNot), BindingPower::new(0, 22));
         binding_power.insert(OP::UnaryOp(&UnaryOp::BitNot), BindingPower::new(0, 22));
+        binding_power.insert(OP::UnaryOp(&UnaryOp::Negate), BindingPower::new(0, 22));
+        binding_power.insert(OP::BinaryOp(&BinaryOp::Pow), BindingPower::new(24, 23));
+        binding_power.insert(OP::BinaryOp(&BinaryOp::Mul), BindingPower::new(19, 20));
+        binding_power.insert(OP::BinaryOp(&BinaryOp::Div), BindingPower::new(19, 20));
+        binding_power.insert(OP::BinaryOp(&BinaryOp::Add), BindingPower::new(17, 18));
+        binding_power.insert(OP::BinaryOp(&BinaryOp::Sub), BindingPower::new(17, 18));
         binding_power.insert(
             OP::BinaryOp(&BinaryOp::ShiftLeft),
             BindingPower::new(15, 16),
@@ -93,9 +137,11 @@ impl<'a> BasicPrinter<'a> {
 
         BasicPrinter {
             stream,
-            indent,
+            options,
             current_indent: 0,
-            _chain_else_if,
+            source_map: SourceMap::new(),
+            counted_lines: 0,
+            counted_bytes: 0,
             constant_lookup,
             modifier_lookup,
             float_width_lookup,
@@ -121,8 +167,34 @@ impl<'a> BasicPrinter<'a> {
         }
     }
 
+    /// The source map of the top-level instructions that have been printed.
+    pub fn into_source_map(self) -> SourceMap {
+        self.source_map
+    }
+
     fn start_line(&mut self) {
-        write!(self.stream, "{}", self.indent.repeat(self.current_indent)).unwrap();
+        write!(
+            self.stream,
+            "{}",
+            self.options.indent.repeat(self.current_indent)
+        )
+        .unwrap();
+    }
+
+    /// The zero-based index of the line currently being written.
+    fn current_line(&mut self) -> usize {
+        let start = self.counted_bytes.min(self.stream.len());
+        self.counted_lines += self.stream[start..].matches('\n').count();
+        self.counted_bytes = self.stream.len();
+        self.counted_lines
+    }
+
+    /// Whether the line currently being written is wider than the configured maximum.
+    fn line_too_long(&self) -> bool {
+        self.options.max_line_width.is_some_and(|width| {
+            let line_start = self.stream.rfind('\n').map_or(0, |newline| newline + 1);
+            self.stream[line_start..].chars().count() > width
+        })
     }
 
     fn end_statement(&mut self) {
@@ -187,6 +259,7 @@ impl<'a> BasicPrinter<'a> {
             Expression::Cast(expression) => self.visit_cast(expression),
             Expression::Index(expression) => self.visit_index(expression),
             Expression::IndexSet(index_set) => self.visit_index_set(index_set),
+            Expression::FunctionCall(expression) => self.visit_function_call(expression),
         }
     }
 
@@ -262,47 +335,52 @@ impl<'a> BasicPrinter<'a> {
         write!(self.stream, "{}{}", expression.value, expression.unit).unwrap();
     }
 
-    fn visit_unary(&mut self, expression: &Unary) {
-        write!(self.stream, "{}", expression.op).unwrap();
-        let op = OP::UnaryOp(&expression.op);
-        if matches!(
-            *expression.operand,
-            Expression::Unary(_) | Expression::Binary(_)
-        ) && self.binding_power[&op].left < self.binding_power[&op].right
-        {
-            write!(self.stream, "(").unwrap();
-            self.visit_expression(&expression.operand);
-            write!(self.stream, ")").unwrap();
-        } else {
-            self.visit_expression(&expression.operand);
-        }
+    fn operator_binding_power(&self, expression: &Expression) -> Option<(u8, u8)> {
+        let power = match expression {
+            Expression::Unary(unary) => &self.binding_power[&OP::UnaryOp(&unary.op)],
+            Expression::Binary(binary) => &self.binding_power[&OP::BinaryOp(&binary.op)],
+            _ => return None,
+        };
+        Some((power.left, power.right))
     }
 
-    fn visit_binary(&mut self, expression: &Binary) {
-        let op = OP::BinaryOp(&expression.op);
-        if matches!(
-            *expression.left,
-            Expression::Unary(_) | Expression::Binary(_)
-        ) && self.binding_power[&op].left < self.binding_power[&op].right
-        {
+    fn visit_operand(&mut self, operand: &Expression, needs_parens: bool) {
+        if needs_parens {
             write!(self.stream, "(").unwrap();
-            self.visit_expression(&expression.left);
+            self.visit_expression(operand);
             write!(self.stream, ")").unwrap();
         } else {
-            self.visit_expression(&expression.left);
+            self.visit_expression(operand);
         }
+    }
+
+    fn visit_unary(&mut self, expression: &Unary) {
         write!(self.stream, "{}", expression.op).unwrap();
-        if matches!(
-            *expression.right,
-            Expression::Unary(_) | Expression::Binary(_)
-        ) && self.binding_power[&op].left < self.binding_power[&op].right
-        {
-            write!(self.stream, "(").unwrap();
-            self.visit_expression(&expression.right);
-            write!(self.stream, ")").unwrap();
+        let right = self.binding_power[&OP::UnaryOp(&expression.op)].right;
+        let needs_parens = self
+            .operator_binding_power(&expression.operand)
+            .is_some_and(|(inner_left, _)| inner_left < right);
+        self.visit_operand(&expression.operand, needs_parens);
+    }
+
+    fn visit_binary(&mut self, expression: &Binary) {
+        let power = &self.binding_power[&OP::BinaryOp(&expression.op)];
+        let (left, right) = (power.left, power.right);
+        let needs_parens = self
+            .operator_binding_power(&expression.left)
+            .is_some_and(|(_, inner_right)| inner_right < left);
+        self.visit_operand(&expression.left, needs_parens);
+        // Multiples and fractions of constants are written compactly, like `-3*pi/4`, to match
+        // the output of the Python exporter.
+        if is_constant_product(expression) {
+            write!(self.stream, "{}", expression.op).unwrap();
         } else {
-            self.visit_expression(&expression.right);
+            write!(self.stream, " {} ", expression.op).unwrap();
         }
+        let needs_parens = self
+            .operator_binding_power(&expression.right)
+            .is_some_and(|(inner_left, _)| inner_left < right);
+        self.visit_operand(&expression.right, needs_parens);
     }
 
     fn visit_cast(&mut self, expression: &Cast) {
@@ -328,6 +406,15 @@ impl<'a> BasicPrinter<'a> {
         write!(self.stream, "]").unwrap();
     }
 
+    fn visit_function_call(&mut self, expression: &FunctionCall) {
+        self.visit_identifier(&expression.name);
+        write!(self.stream, "(").unwrap();
+        if !expression.arguments.is_empty() {
+            self.visit_expression_sequence(&expression.arguments, "", "", ", ");
+        }
+        write!(self.stream, ")").unwrap();
+    }
+
     fn visit_index_set(&mut self, node: &IndexSet) {
         self.visit_expression_sequence(&node.values, "{", "}", ", ");
     }
@@ -376,12 +463,24 @@ impl<'a> BasicPrinter<'a> {
         if !start.is_empty() {
             write!(self.stream, "{start}").unwrap();
         }
-        for node in nodes.iter().take(nodes.len() - 1) {
-            self.visit_expression(node);
+        for (i, node) in nodes.iter().enumerate() {
+            if i == 0 {
+                self.visit_expression(node);
+                continue;
+            }
+            let before_separator = self.stream.len();
             write!(self.stream, "{separator}").unwrap();
-        }
-        if let Some(last) = nodes.last() {
-            self.visit_expression(last);
+            self.visit_expression(node);
+            if self.line_too_long() {
+                // Try again on a continuation line, indented one level deeper than the statement.
+                self.stream.truncate(before_separator);
+                write!(self.stream, "{}", separator.trim_end()).unwrap();
+                self.end_line();
+                self.current_indent += 1;
+                self.start_line();
+                self.current_indent -= 1;
+                self.visit_expression(node);
+            }
         }
         if !end.is_empty() {
             write!(self.stream, "{end}").unwrap();
@@ -425,6 +524,7 @@ impl<'a> BasicPrinter<'a> {
             ClassicalType::Uint(type_) => self.visit_uint_type(type_),
             ClassicalType::Bit => self.visit_bit_type(),
             ClassicalType::BitArray(type_) => self.visit_bit_array_type(type_),
+            ClassicalType::Duration => self.visit_duration_type(),
         }
     }
 
@@ -458,13 +558,18 @@ impl<'a> BasicPrinter<'a> {
         write!(self.stream, "bit[{}]", type_.0).unwrap()
     }
 
+    fn visit_duration_type(&mut self) {
+        write!(self.stream, "duration").unwrap()
+    }
+
     fn visit_statement(&mut self, statement: &Statement) {
         match statement {
             Statement::QuantumDeclaration(statement) => self.visit_quantum_declaration(statement),
             Statement::ClassicalDeclaration(statement) => {
                 self.visit_classical_declaration(statement)
             }
-            Statement::IODeclaration(_iodeclaration) => todo!(),
+            Statement::StretchDeclaration(statement) => self.visit_stretch_declaration(statement),
+            Statement::IODeclaration(statement) => self.visit_io_declaration(statement),
             Statement::QuantumInstruction(statement) => self.visit_quantum_instruction(statement),
             Statement::QuantumMeasurementAssignment(statement) => {
                 self.visit_quantum_measurement_assignment(statement)
@@ -476,6 +581,12 @@ impl<'a> BasicPrinter<'a> {
             Statement::Alias(statement) => self.visit_alias_statement(statement),
             Statement::Break(_) => self.visit_break_statement(),
             Statement::Continue(_) => self.visit_continue_statement(),
+            Statement::Branching(statement) => self.visit_branching_statement(statement, false),
+            Statement::WhileLoop(statement) => self.visit_while_loop_statement(statement),
+            Statement::ForLoop(statement) => self.visit_for_loop_statement(statement),
+            Statement::Switch(statement) => self.visit_switch_statement(statement),
+            Statement::Box(statement) => self.visit_box_statement(statement),
+            Statement::Instruction(statement) => self.visit_instruction_statements(statement),
         }
     }
 
@@ -500,6 +611,25 @@ impl<'a> BasicPrinter<'a> {
         self.end_statement();
     }
 
+    fn visit_stretch_declaration(&mut self, statement: &StretchDeclaration) {
+        self.start_line();
+        write!(self.stream, "stretch ").unwrap();
+        self.visit_identifier(&statement.identifier);
+        self.end_statement();
+    }
+
+    fn visit_io_declaration(&mut self, statement: &IODeclaration) {
+        self.start_line();
+        match statement.modifier {
+            IOModifier::Input => write!(self.stream, "input ").unwrap(),
+            IOModifier::Output => write!(self.stream, "output ").unwrap(),
+        }
+        self.visit_classical_type(&statement.type_);
+        write!(self.stream, " ").unwrap();
+        self.visit_identifier(&statement.identifier);
+        self.end_statement();
+    }
+
     fn visit_quantum_instruction(&mut self, instruction: &QuantumInstruction) {
         match instruction {
             QuantumInstruction::GateCall(instruction) => self.visit_quantum_gate_call(instruction),
@@ -556,7 +686,7 @@ impl<'a> BasicPrinter<'a> {
     fn visit_quantum_delay(&mut self, instruction: &Delay) {
         self.start_line();
         write!(self.stream, "delay[").unwrap();
-        self.visit_duration_literal(&instruction.duration);
+        self.visit_expression(&instruction.duration);
         write!(self.stream, "] ").unwrap();
         for qubit in &instruction.qubits {
             match qubit {
@@ -584,9 +714,12 @@ impl<'a> BasicPrinter<'a> {
 
     fn visit_assignment_statement(&mut self, statement: &Assignment) {
         self.start_line();
-        self.visit_identifier(&statement.lvalue);
+        match &statement.lvalue {
+            IdentifierOrSubscripted::Identifier(id) => self.visit_identifier(id),
+            IdentifierOrSubscripted::Subscripted(sub_id) => self.visit_subscript_identifier(sub_id),
+        }
         write!(self.stream, " = ").unwrap();
-        self.visit_identifier_for_switch(&statement.rvalue);
+        self.visit_expression(&statement.rvalue);
         self.end_statement();
     }
 
@@ -616,16 +749,175 @@ impl<'a> BasicPrinter<'a> {
         self.write_statement("continue");
     }
 
-    fn visit_identifier_for_switch(&mut self, identifiers: &Vec<Identifier>) {
-        if identifiers.len() > 1 {
-            write!(self.stream, "[").unwrap();
+    /// The `if` statement that is the only content of `block`, if it should be chained onto the
+    /// `else` of an enclosing `if` statement.
+    fn chained_branch<'b>(&self, block: &'b QuantumBlock) -> Option<&'b BranchingStatement> {
+        if !self.options.chain_else_if {
+            return None;
+        }
+        match block.statements.as_slice() {
+            [Statement::Branching(statement)] => Some(statement),
+            // A label comment needs a line of its own, so we can't chain through one.
+            [Statement::Instruction(instruction)]
+                if !(self.options.label_comments && instruction.label.is_some()) =>
+            {
+                match instruction.statements.as_slice() {
+                    [Statement::Branching(statement)] => Some(statement),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn visit_branching_statement(&mut self, statement: &BranchingStatement, chained: bool) {
+        if !chained {
+            self.start_line();
+        }
+        write!(self.stream, "if (").unwrap();
+        self.visit_expression(&statement.condition);
+        write!(self.stream, ") ").unwrap();
+        self.visit_quantum_block(&statement.true_body);
+        if let Some(false_body) = &statement.false_body {
+            write!(self.stream, " else ").unwrap();
+            match self.chained_branch(false_body) {
+                Some(branch) => self.visit_branching_statement(branch, true),
+                None => self.visit_quantum_block(false_body),
+            }
         }
-        for identifier in identifiers {
-            self.visit_identifier(identifier);
-            write!(self.stream, ",").unwrap();
+        if !chained {
+            // The visitor to the first `if` ends the line.
+            self.end_line();
         }
-        if identifiers.len() > 1 {
+    }
+
+    fn visit_while_loop_statement(&mut self, statement: &WhileLoopStatement) {
+        self.start_line();
+        write!(self.stream, "while (").unwrap();
+        self.visit_expression(&statement.condition);
+        write!(self.stream, ") ").unwrap();
+        self.visit_quantum_block(&statement.body);
+        self.end_line();
+    }
+
+    fn visit_for_loop_statement(&mut self, statement: &ForLoopStatement) {
+        self.start_line();
+        write!(self.stream, "for ").unwrap();
+        self.visit_identifier(&statement.parameter);
+        write!(self.stream, " in ").unwrap();
+        match &statement.indexset {
+            ForLoopIndexSet::Range(range) => {
+                write!(self.stream, "[").unwrap();
+                self.visit_range(range);
+                write!(self.stream, "]").unwrap();
+            }
+            ForLoopIndexSet::IndexSet(index_set) => self.visit_index_set(index_set),
+        }
+        write!(self.stream, " ").unwrap();
+        self.visit_quantum_block(&statement.body);
+        self.end_line();
+    }
+
+    fn visit_switch_statement(&mut self, statement: &SwitchStatement) {
+        self.start_line();
+        write!(self.stream, "switch (").unwrap();
+        self.visit_expression(&statement.target);
+        write!(self.stream, ") {{").unwrap();
+        self.end_line();
+        self.current_indent += 1;
+        for (labels, body) in &statement.cases {
+            if labels.is_empty() {
+                continue;
+            }
+            self.start_line();
+            write!(self.stream, "case ").unwrap();
+            self.visit_expression_sequence(labels, "", "", ", ");
+            write!(self.stream, " ").unwrap();
+            self.visit_quantum_block(body);
+            self.end_line();
+        }
+        if let Some(default) = &statement.default {
+            self.start_line();
+            write!(self.stream, "default ").unwrap();
+            self.visit_quantum_block(default);
+            self.end_line();
+        }
+        self.current_indent -= 1;
+        self.start_line();
+        write!(self.stream, "}}").unwrap();
+        self.end_line();
+    }
+
+    fn visit_annotation(&mut self, annotation: &Annotation) {
+        self.start_line();
+        write!(self.stream, "@{}", annotation.namespace).unwrap();
+        if !annotation.payload.is_empty() {
+            write!(self.stream, " {}", annotation.payload).unwrap();
+        }
+        self.end_line();
+    }
+
+    fn visit_box_statement(&mut self, statement: &BoxStatement) {
+        // The OpenQASM 3 spec doesn't specify any ordering between annotations.  Like the Python
+        // exporter, we write them like Python decorators, where the "first" annotation is written
+        // closest to the box itself.
+        for annotation in statement.annotations.iter().rev() {
+            self.visit_annotation(annotation);
+        }
+        self.start_line();
+        write!(self.stream, "box").unwrap();
+        if let Some(duration) = &statement.duration {
+            write!(self.stream, "[").unwrap();
+            self.visit_expression(duration);
             write!(self.stream, "]").unwrap();
         }
+        write!(self.stream, " ").unwrap();
+        self.visit_quantum_block(&statement.body);
+        self.end_line();
+    }
+
+    fn visit_instruction_statements(&mut self, statement: &InstructionStatements) {
+        // Only instructions of the outermost scope go into the source map; nested ones are
+        // covered by the range of the control-flow instruction that contains them.
+        let top_level = self.current_indent == 0;
+        let start = if top_level { self.current_line() } else { 0 };
+        if let (true, Some(label)) = (self.options.label_comments, &statement.label) {
+            self.start_line();
+            write!(self.stream, "// {}", label.replace(['\r', '\n'], " ")).unwrap();
+            self.end_line();
+        }
+        for inner in &statement.statements {
+            self.visit_statement(inner);
+        }
+        if top_level {
+            let end = self.current_line();
+            self.source_map.insert(statement.index, start..end);
+        }
     }
 }
+
+/// Whether a binary expression is a product or quotient of integers and constants that involves at
+/// least one constant, such as `2*pi` or `-3*pi/4`.
+fn is_constant_product(expression: &Binary) -> bool {
+    /// Whether every leaf of the expression is an integer or a constant, and if so, whether any
+    /// are constants.
+    fn leaves(expression: &Expression) -> Option<bool> {
+        match expression {
+            Expression::Constant(_) => Some(true),
+            Expression::IntegerLiteral(_) => Some(false),
+            Expression::Unary(Unary {
+                op: UnaryOp::Negate,
+                operand,
+            }) => leaves(operand),
+            Expression::Binary(binary) => product_leaves(binary),
+            _ => None,
+        }
+    }
+    fn product_leaves(binary: &Binary) -> Option<bool> {
+        if !matches!(binary.op, BinaryOp::Mul | BinaryOp::Div) {
+            return None;
+        }
+        Some(leaves(&binary.left)? | leaves(&binary.right)?)
+    }
+    product_leaves(expression) == Some(true)
+}
@@ -12,6 +12,7 @@
 
 pub mod convert_2q_block_matrix;
 pub mod pauli_lindblad_map;
+pub mod sparse_fermion_op;
 pub mod sparse_observable;
 pub mod sparse_pauli_op;
 pub mod unitary_compose;
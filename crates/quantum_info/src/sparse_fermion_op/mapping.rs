@@ -0,0 +1,198 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2025
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Fermion-to-qubit mappings of [SparseFermionOp] into [SparseObservable].
+//!
+//! All the supported mappings are described by three sets of qubits for each mode `j` (see Seeley,
+//! Richard and Love, "The Bravyi-Kitaev transformation for quantum computation of electronic
+//! structure", J. Chem. Phys. 137, 224109 (2012)):
+//!
+//! * the _update_ set: the qubits whose value depends on the occupation of mode `j`;
+//! * the _parity_ set: the qubits that together store the parity of the modes below `j`;
+//! * the _occupation_ set: the qubits that together store the occupation of mode `j`.
+//!
+//! The ladder operators are then `a_j^dagger = (P_x - i P_y) / 2` and `a_j = (P_x + i P_y) / 2`,
+//! where `P_x` is X on the update set and Z on the parity set, and `P_y` is Y on qubit `j`, X on
+//! the rest of the update set, and Z on the symmetric difference of the parity and occupation sets
+//! (excluding qubit `j`).  For the Jordan-Wigner mapping, these reduce to the familiar Z strings.
+
+use std::collections::BTreeMap;
+
+use num_complex::Complex64;
+
+use super::{FermionAction, SparseFermionOp};
+use crate::sparse_observable::{BitTerm, SparseObservable, SparseTermView};
+
+/// The available mappings of fermionic modes to qubits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FermionMapping {
+    /// Qubit `j` stores the occupation of mode `j`.
+    JordanWigner,
+    /// Qubit `j` stores the parity of the occupations of modes `0..=j`.
+    Parity,
+    /// Qubit `j` stores the parity of a range of modes ending at `j`, as in a Fenwick tree.
+    BravyiKitaev,
+}
+
+/// A Pauli string, sorted by qubit index, with no identities.
+type PauliString = Vec<(u32, BitTerm)>;
+
+/// The two Pauli strings `(P_x, P_y)` that make up the ladder operators of a mode.
+fn majorana_strings(
+    mapping: FermionMapping,
+    mode: u32,
+    num_modes: u32,
+) -> (PauliString, PauliString) {
+    let (update, parity, occupation): (Vec<u32>, Vec<u32>, Vec<u32>) = match mapping {
+        FermionMapping::JordanWigner => (vec![mode], (0..mode).collect(), vec![mode]),
+        FermionMapping::Parity => (
+            (mode..num_modes).collect(),
+            mode.checked_sub(1).into_iter().collect(),
+            mode.checked_sub(1).into_iter().chain([mode]).collect(),
+        ),
+        FermionMapping::BravyiKitaev => {
+            // These use the one-based indexing of Fenwick trees internally.
+            let mut update = Vec::new();
+            let mut index = mode + 1;
+            while index <= num_modes {
+                update.push(index - 1);
+                index += index & index.wrapping_neg();
+            }
+            let mut parity = Vec::new();
+            let mut index = mode;
+            while index > 0 {
+                parity.push(index - 1);
+                index &= index - 1;
+            }
+            let mut occupation = vec![mode];
+            let parent = (mode + 1) & mode;
+            let mut index = mode;
+            while index != parent {
+                occupation.push(index - 1);
+                index &= index - 1;
+            }
+            (update, parity, occupation)
+        }
+    };
+
+    let mut x_string = BTreeMap::new();
+    x_string.extend(update.iter().map(|qubit| (*qubit, BitTerm::X)));
+    x_string.extend(parity.iter().map(|qubit| (*qubit, BitTerm::Z)));
+
+    let mut y_string = BTreeMap::new();
+    for qubit in parity.iter().chain(occupation.iter()) {
+        // Toggling each qubit in both sets gives the symmetric difference.
+        if y_string.remove(qubit).is_none() {
+            y_string.insert(*qubit, BitTerm::Z);
+        }
+    }
+    y_string.extend(update.iter().map(|qubit| (*qubit, BitTerm::X)));
+    y_string.insert(mode, BitTerm::Y);
+    (
+        x_string.into_iter().collect(),
+        y_string.into_iter().collect(),
+    )
+}
+
+/// Multiply two single-qubit Paulis, returning the power of `i` in the phase and the product,
+/// which is `None` for the identity.
+fn multiply_single(left: BitTerm, right: BitTerm) -> (u8, Option<BitTerm>) {
+    match (left, right) {
+        (BitTerm::X, BitTerm::Y) => (1, Some(BitTerm::Z)),
+        (BitTerm::Y, BitTerm::Z) => (1, Some(BitTerm::X)),
+        (BitTerm::Z, BitTerm::X) => (1, Some(BitTerm::Y)),
+        (BitTerm::Y, BitTerm::X) => (3, Some(BitTerm::Z)),
+        (BitTerm::Z, BitTerm::Y) => (3, Some(BitTerm::X)),
+        (BitTerm::X, BitTerm::Z) => (3, Some(BitTerm::Y)),
+        (left, right) if left == right => (0, None),
+        _ => unreachable!("the mappings only produce Pauli operators"),
+    }
+}
+
+/// Multiply two Pauli strings, returning the power of `i` in the phase and the product.
+fn multiply(left: &[(u32, BitTerm)], right: &[(u32, BitTerm)]) -> (u8, PauliString) {
+    let mut phase = 0;
+    let mut out = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.iter().peekable();
+    let mut right = right.iter().peekable();
+    loop {
+        match (left.peek(), right.peek()) {
+            (Some(l), Some(r)) if l.0 == r.0 => {
+                let (power, term) = multiply_single(l.1, r.1);
+                phase = (phase + power) % 4;
+                if let Some(term) = term {
+                    out.push((l.0, term));
+                }
+                left.next();
+                right.next();
+            }
+            (Some(l), Some(r)) if l.0 < r.0 => out.push(*left.next().unwrap()),
+            (Some(_), Some(_)) | (None, Some(_)) => out.push(*right.next().unwrap()),
+            (Some(_), None) => out.push(*left.next().unwrap()),
+            (None, None) => break,
+        }
+    }
+    (phase, out)
+}
+
+const I_POWERS: [Complex64; 4] = [
+    Complex64::new(1.0, 0.0),
+    Complex64::new(0.0, 1.0),
+    Complex64::new(-1.0, 0.0),
+    Complex64::new(0.0, -1.0),
+];
+
+impl SparseFermionOp {
+    /// Map the operator to a qubit observable, with mode `j` corresponding to qubit `j`.
+    ///
+    /// Each product of `k` ladder operators expands to (up to) `2^k` Pauli terms.  Like terms are
+    /// summed in the output, which is in the canonical order of [SparseObservable::canonicalize],
+    /// and any terms that cancel exactly are removed.
+    pub fn to_sparse_observable(&self, mapping: FermionMapping) -> SparseObservable {
+        let strings = (0..self.num_modes)
+            .map(|mode| majorana_strings(mapping, mode, self.num_modes))
+            .collect::<Vec<_>>();
+        let mut out = SparseObservable::zero(self.num_modes);
+        let mut products = Vec::new();
+        let mut next = Vec::new();
+        for term in self.iter() {
+            products.clear();
+            products.push((term.coeff, PauliString::new()));
+            for (action, mode) in term.actions.iter().zip(term.indices) {
+                let (x_string, y_string) = &strings[*mode as usize];
+                let y_coeff = match action {
+                    FermionAction::Create => Complex64::new(0.0, -0.5),
+                    FermionAction::Annihilate => Complex64::new(0.0, 0.5),
+                };
+                next.clear();
+                for (coeff, product) in products.iter() {
+                    let (phase, x_product) = multiply(product, x_string);
+                    next.push((coeff * 0.5 * I_POWERS[phase as usize], x_product));
+                    let (phase, y_product) = multiply(product, y_string);
+                    next.push((coeff * y_coeff * I_POWERS[phase as usize], y_product));
+                }
+                ::std::mem::swap(&mut products, &mut next);
+            }
+            for (coeff, product) in products.drain(..) {
+                let (indices, bit_terms): (Vec<u32>, Vec<BitTerm>) = product.into_iter().unzip();
+                out.add_term(SparseTermView {
+                    num_qubits: self.num_modes,
+                    coeff,
+                    bit_terms: &bit_terms,
+                    indices: &indices,
+                })
+                .expect("the output has the same number of qubits as there are modes");
+            }
+        }
+        out.canonicalize(0.0)
+    }
+}
@@ -0,0 +1,1113 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2025
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+mod mapping;
+
+pub use mapping::FermionMapping;
+
+use std::cmp::{Ordering, Reverse};
+
+use indexmap::IndexMap;
+use num_complex::Complex64;
+use numpy::{PyArray1, PyReadonlyArray1};
+use pyo3::{
+    exceptions::{PyTypeError, PyValueError},
+    intern,
+    prelude::*,
+    types::{PyList, PyTuple},
+    IntoPyObjectExt, PyErr,
+};
+use thiserror::Error;
+
+use crate::sparse_observable::SparseObservable;
+
+/// A single fermionic ladder operator.
+///
+/// The `u8` representation is part of the Python API, where the actions of a [SparseFermionOp]
+/// are exposed as an array of `uint8`.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FermionAction {
+    /// The annihilation operator, `a_j`.
+    Annihilate = 0,
+    /// The creation operator, `a_j^dagger`.
+    Create = 1,
+}
+impl FermionAction {
+    /// The label of this action in the Python-space sparse-list format.
+    pub fn py_label(&self) -> &'static str {
+        match self {
+            Self::Annihilate => "-",
+            Self::Create => "+",
+        }
+    }
+
+    /// The adjoint of this ladder operator.
+    pub fn flip(&self) -> Self {
+        match self {
+            Self::Annihilate => Self::Create,
+            Self::Create => Self::Annihilate,
+        }
+    }
+
+    fn try_from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::Annihilate),
+            1 => Some(Self::Create),
+            _ => None,
+        }
+    }
+
+    fn try_from_label(value: u8) -> Option<Self> {
+        match value {
+            b'-' => Some(Self::Annihilate),
+            b'+' => Some(Self::Create),
+            _ => None,
+        }
+    }
+}
+
+/// An error related to the coherence of the raw data of a [SparseFermionOp].
+#[derive(Error, Debug)]
+pub enum CoherenceError {
+    #[error("`boundaries` ({boundaries}) must be one element longer than `coeffs` ({coeffs})")]
+    MismatchedTermCount { coeffs: usize, boundaries: usize },
+    #[error("`actions` ({actions}) and `indices` ({indices}) must be the same length")]
+    MismatchedItemCount { actions: usize, indices: usize },
+    #[error("the first item of `boundaries` ({0}) must be 0")]
+    BadInitialBoundary(usize),
+    #[error("the last item of `boundaries` ({last}) must match the length of `actions` and `indices` ({items})")]
+    BadFinalBoundary { last: usize, items: usize },
+    #[error("all mode indices must be less than the number of modes")]
+    ModeIndexTooHigh,
+    #[error("the values in `boundaries` include backwards slices")]
+    DecreasingBoundaries,
+}
+
+/// An error related to processing of a sparse-list label.
+#[derive(Error, Debug)]
+pub enum LabelError {
+    #[error("label with length {label} does not match indices of length {indices}")]
+    WrongLengthIndices { label: usize, indices: usize },
+    #[error("index {index} is out of range for a {num_modes}-mode operator")]
+    BadIndex { index: u32, num_modes: u32 },
+    #[error("labels must only contain letters from the alphabet '+-'")]
+    OutsideAlphabet,
+}
+
+#[derive(Error, Debug)]
+pub enum ArithmeticError {
+    #[error("mismatched numbers of modes: {left}, {right}")]
+    MismatchedModes { left: u32, right: u32 },
+}
+
+impl From<CoherenceError> for PyErr {
+    fn from(value: CoherenceError) -> PyErr {
+        PyValueError::new_err(value.to_string())
+    }
+}
+impl From<LabelError> for PyErr {
+    fn from(value: LabelError) -> PyErr {
+        PyValueError::new_err(value.to_string())
+    }
+}
+impl From<ArithmeticError> for PyErr {
+    fn from(value: ArithmeticError) -> PyErr {
+        PyValueError::new_err(value.to_string())
+    }
+}
+
+/// A sum of products of fermionic ladder operators, stored in a mode-sparse format.
+///
+/// This uses the same CSR-like layout as [SparseObservable]: each term is a complex coefficient
+/// multiplied by a product of ladder operators, and the products are stored flat, with
+/// `boundaries` denoting the slices that make up each term.  Unlike the single-qubit terms of a
+/// [SparseObservable], the ladder operators in a product do not commute, so the order of
+/// `actions` and `indices` within a term is the order of the product (the left-most operator acts
+/// last), and a mode can appear several times in the same term.  A term with no ladder operators
+/// is the identity.
+///
+/// See [PySparseFermionOp] for the Python-space documentation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SparseFermionOp {
+    /// The number of fermionic modes the operator acts on.
+    num_modes: u32,
+    /// The coefficients of each term in the sum.
+    coeffs: Vec<Complex64>,
+    /// A flat list of the ladder operators in each product, denoted by `boundaries`.
+    actions: Vec<FermionAction>,
+    /// A flat list of the modes that the corresponding entries in `actions` act on.
+    indices: Vec<u32>,
+    /// Indices that partition `actions` and `indices` into the products of each term.
+    /// `boundaries[0]..boundaries[1]` is the range of the first term.
+    boundaries: Vec<usize>,
+}
+
+/// A view object onto a single term of a [SparseFermionOp].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SparseFermionTermView<'a> {
+    pub num_modes: u32,
+    pub coeff: Complex64,
+    pub actions: &'a [FermionAction],
+    pub indices: &'a [u32],
+}
+impl SparseFermionTermView<'_> {
+    /// Get the sparse string representation of this term, like `+_1 -_0`.
+    pub fn to_sparse_str(self) -> String {
+        let coeff = format!("{}", self.coeff).replace('i', "j");
+        let ladders = self
+            .actions
+            .iter()
+            .zip(self.indices)
+            .map(|(action, index)| format!("{}_{}", action.py_label(), index))
+            .collect::<Vec<_>>()
+            .join(" ");
+        format!("({coeff})({ladders})")
+    }
+}
+
+/// The order of ladder operators in a normal-ordered product: creation operators before
+/// annihilation operators, with the creation operators in decreasing order of mode and the
+/// annihilation operators in increasing order of mode.
+fn normal_order_key(action: FermionAction, index: u32) -> (bool, Reverse<u32>, u32) {
+    match action {
+        FermionAction::Create => (false, Reverse(index), 0),
+        FermionAction::Annihilate => (true, Reverse(0), index),
+    }
+}
+
+impl SparseFermionOp {
+    /// Create a new operator from the raw components that make it up.
+    ///
+    /// This checks the input values for data coherence on entry.
+    pub fn new(
+        num_modes: u32,
+        coeffs: Vec<Complex64>,
+        actions: Vec<FermionAction>,
+        indices: Vec<u32>,
+        boundaries: Vec<usize>,
+    ) -> Result<Self, CoherenceError> {
+        if coeffs.len() + 1 != boundaries.len() {
+            return Err(CoherenceError::MismatchedTermCount {
+                coeffs: coeffs.len(),
+                boundaries: boundaries.len(),
+            });
+        }
+        if actions.len() != indices.len() {
+            return Err(CoherenceError::MismatchedItemCount {
+                actions: actions.len(),
+                indices: indices.len(),
+            });
+        }
+        // We already checked that `boundaries` is at least length 1.
+        if boundaries[0] != 0 {
+            return Err(CoherenceError::BadInitialBoundary(boundaries[0]));
+        }
+        if *boundaries.last().unwrap() != indices.len() {
+            return Err(CoherenceError::BadFinalBoundary {
+                last: *boundaries.last().unwrap(),
+                items: indices.len(),
+            });
+        }
+        if boundaries
+            .iter()
+            .zip(&boundaries[1..])
+            .any(|(left, right)| right < left)
+        {
+            return Err(CoherenceError::DecreasingBoundaries);
+        }
+        if indices.iter().any(|index| *index >= num_modes) {
+            return Err(CoherenceError::ModeIndexTooHigh);
+        }
+        Ok(Self {
+            num_modes,
+            coeffs,
+            actions,
+            indices,
+            boundaries,
+        })
+    }
+
+    /// Create a zero operator with pre-allocated space for the given number of terms and ladder
+    /// operators.
+    pub fn with_capacity(num_modes: u32, num_terms: usize, num_actions: usize) -> Self {
+        let mut boundaries = Vec::with_capacity(num_terms + 1);
+        boundaries.push(0);
+        Self {
+            num_modes,
+            coeffs: Vec::with_capacity(num_terms),
+            actions: Vec::with_capacity(num_actions),
+            indices: Vec::with_capacity(num_actions),
+            boundaries,
+        }
+    }
+
+    /// Get the zero operator over the given number of modes.
+    pub fn zero(num_modes: u32) -> Self {
+        Self::with_capacity(num_modes, 0, 0)
+    }
+
+    /// Get the identity operator over the given number of modes.
+    pub fn identity(num_modes: u32) -> Self {
+        Self {
+            num_modes,
+            coeffs: vec![Complex64::new(1.0, 0.0)],
+            actions: vec![],
+            indices: vec![],
+            boundaries: vec![0, 0],
+        }
+    }
+
+    pub fn num_modes(&self) -> u32 {
+        self.num_modes
+    }
+
+    pub fn num_terms(&self) -> usize {
+        self.coeffs.len()
+    }
+
+    pub fn coeffs(&self) -> &[Complex64] {
+        &self.coeffs
+    }
+
+    pub fn actions(&self) -> &[FermionAction] {
+        &self.actions
+    }
+
+    pub fn indices(&self) -> &[u32] {
+        &self.indices
+    }
+
+    pub fn boundaries(&self) -> &[usize] {
+        &self.boundaries
+    }
+
+    /// Get a view onto a single term.
+    pub fn term(&self, index: usize) -> SparseFermionTermView<'_> {
+        let start = self.boundaries[index];
+        let end = self.boundaries[index + 1];
+        SparseFermionTermView {
+            num_modes: self.num_modes,
+            coeff: self.coeffs[index],
+            actions: &self.actions[start..end],
+            indices: &self.indices[start..end],
+        }
+    }
+
+    /// Get an iterator over the terms of the operator.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = SparseFermionTermView<'_>> + '_ {
+        (0..self.num_terms()).map(|i| self.term(i))
+    }
+
+    /// Add a single term to this operator.
+    pub fn add_term(&mut self, term: SparseFermionTermView) -> Result<(), ArithmeticError> {
+        self.check_equal_modes(term.num_modes)?;
+        self.push_term(term.coeff, term.actions, term.indices);
+        Ok(())
+    }
+
+    fn push_term(&mut self, coeff: Complex64, actions: &[FermionAction], indices: &[u32]) {
+        self.coeffs.push(coeff);
+        self.actions.extend_from_slice(actions);
+        self.indices.extend_from_slice(indices);
+        self.boundaries.push(self.indices.len());
+    }
+
+    /// Check that `other` has the same number of modes as `self`.
+    pub fn check_equal_modes(&self, other: u32) -> Result<(), ArithmeticError> {
+        if self.num_modes != other {
+            return Err(ArithmeticError::MismatchedModes {
+                left: self.num_modes,
+                right: other,
+            });
+        }
+        Ok(())
+    }
+
+    /// Get the Hermitian adjoint of the operator.
+    ///
+    /// The product in each term is reversed, each ladder operator is replaced by its adjoint, and
+    /// the coefficients are conjugated.
+    pub fn adjoint(&self) -> Self {
+        let mut out = Self::with_capacity(self.num_modes, self.num_terms(), self.indices.len());
+        for term in self.iter() {
+            out.coeffs.push(term.coeff.conj());
+            out.actions
+                .extend(term.actions.iter().rev().map(FermionAction::flip));
+            out.indices.extend(term.indices.iter().rev());
+            out.boundaries.push(out.indices.len());
+        }
+        out
+    }
+
+    /// Sum terms whose products of ladder operators are identical, removing them if the final
+    /// coefficient's absolute value is less than or equal to the tolerance.
+    ///
+    /// The terms are kept in order of their first appearance.  This does not use the
+    /// anticommutation relations to identify products that are equal but written differently; see
+    /// [SparseFermionOp::normal_order] for that.
+    pub fn simplify(&self, tol: f64) -> Self {
+        let mut terms = IndexMap::<_, Complex64, ::ahash::RandomState>::default();
+        for term in self.iter() {
+            *terms
+                .entry((term.actions, term.indices))
+                .or_insert(Complex64::new(0.0, 0.0)) += term.coeff;
+        }
+        let mut out = Self::zero(self.num_modes);
+        for ((actions, indices), coeff) in terms {
+            if coeff.norm_sqr() <= tol * tol {
+                continue;
+            }
+            out.push_term(coeff, actions, indices);
+        }
+        out
+    }
+
+    /// Rewrite the operator in normal order, using the canonical anticommutation relations.
+    ///
+    /// In each output term, all the creation operators are to the left of all the annihilation
+    /// operators.  The creation operators are in decreasing order of mode, and the annihilation
+    /// operators in increasing order, so the adjoint of a normal-ordered term is also normal
+    /// ordered.  Products that contain the same ladder operator twice are zero and are removed,
+    /// and like terms are summed, with any that exactly cancel removed.  Two operators are equal
+    /// if and only if their normal-ordered forms are equal up to floating-point tolerance and term
+    /// order.
+    ///
+    /// Swapping an annihilation operator past a creation operator on the same mode introduces an
+    /// extra term, so the number of terms in the output can be exponential in the number of ladder
+    /// operators in each term.
+    pub fn normal_order(&self) -> Self {
+        let mut out = Self::zero(self.num_modes);
+        let mut stack = Vec::new();
+        for term in self.iter() {
+            stack.push((
+                term.coeff,
+                term.actions
+                    .iter()
+                    .copied()
+                    .zip(term.indices.iter().copied())
+                    .collect::<Vec<_>>(),
+            ));
+            'stack: while let Some((mut coeff, mut ops)) = stack.pop() {
+                // Bubble sort, since we need to know every transposition to track the sign and
+                // the contractions.  The products are short, so this is not a bottleneck.
+                let mut swapped = true;
+                while swapped {
+                    swapped = false;
+                    for i in 1..ops.len() {
+                        let (left, right) = (ops[i - 1], ops[i]);
+                        match normal_order_key(left.0, left.1)
+                            .cmp(&normal_order_key(right.0, right.1))
+                        {
+                            Ordering::Less => (),
+                            // The same ladder operator twice in a row is zero.
+                            Ordering::Equal => continue 'stack,
+                            Ordering::Greater => {
+                                if left.1 == right.1
+                                    && left.0 == FermionAction::Annihilate
+                                    && right.0 == FermionAction::Create
+                                {
+                                    // a_j a_j^dagger = 1 - a_j^dagger a_j
+                                    let mut contracted = ops.clone();
+                                    contracted.drain(i - 1..=i);
+                                    stack.push((coeff, contracted));
+                                }
+                                ops.swap(i - 1, i);
+                                coeff = -coeff;
+                                swapped = true;
+                            }
+                        }
+                    }
+                }
+                out.coeffs.push(coeff);
+                out.actions.extend(ops.iter().map(|(action, _)| *action));
+                out.indices.extend(ops.iter().map(|(_, index)| *index));
+                out.boundaries.push(out.indices.len());
+            }
+        }
+        out.simplify(0.0)
+    }
+}
+
+impl ::std::ops::Add for &SparseFermionOp {
+    type Output = SparseFermionOp;
+
+    fn add(self, rhs: &SparseFermionOp) -> SparseFermionOp {
+        let mut out = self.clone();
+        for term in rhs.iter() {
+            out.push_term(term.coeff, term.actions, term.indices);
+        }
+        out
+    }
+}
+impl ::std::ops::Neg for &SparseFermionOp {
+    type Output = SparseFermionOp;
+
+    fn neg(self) -> SparseFermionOp {
+        self * Complex64::new(-1.0, 0.0)
+    }
+}
+impl ::std::ops::Sub for &SparseFermionOp {
+    type Output = SparseFermionOp;
+
+    fn sub(self, rhs: &SparseFermionOp) -> SparseFermionOp {
+        self + &(-rhs)
+    }
+}
+impl ::std::ops::Mul<Complex64> for &SparseFermionOp {
+    type Output = SparseFermionOp;
+
+    fn mul(self, rhs: Complex64) -> SparseFermionOp {
+        let mut out = self.clone();
+        out.coeffs.iter_mut().for_each(|coeff| *coeff *= rhs);
+        out
+    }
+}
+
+/// An operator on fermionic modes, stored as a sparse sum of products of ladder operators.
+///
+/// Each term is a complex coefficient multiplied by a product of creation (:math:`a^\dagger_j`)
+/// and annihilation (:math:`a_j`) operators.  The operator is stored in the same CSR-like format as
+/// :class:`SparseObservable`, with the flat arrays :attr:`coeffs`, :attr:`actions`,
+/// :attr:`indices` and :attr:`boundaries`.  ``actions[i]`` is ``1`` for a creation operator and
+/// ``0`` for an annihilation operator, acting on mode ``indices[i]``.  The products are in the
+/// order given, so ``actions[boundaries[k]]`` is the left-most operator of term ``k``, and a mode
+/// can appear several times in the same term.  A term with no ladder operators is the identity.
+///
+/// The most convenient way to build an operator is from a sparse list, like
+/// :meth:`SparseObservable.from_sparse_list`, where ``"+"`` is a creation operator and ``"-"`` is
+/// an annihilation operator.  For example, the hopping term :math:`a^\dagger_1 a_0 + a^\dagger_0
+/// a_1` on four modes is::
+///
+///     >>> SparseFermionOp.from_sparse_list([("+-", (1, 0), 1.0), ("+-", (0, 1), 1.0)], num_modes=4)
+///     <SparseFermionOp with 2 terms on 4 modes: (1+0j)(+_1 -_0) + (1+0j)(+_0 -_1)>
+///
+/// Like :class:`SparseObservable`, addition stacks the terms without simplification, and
+/// comparison with ``==`` is structural.  Use :meth:`normal_order` to get a canonical form.
+///
+/// The operator is mapped to a qubit :class:`SparseObservable` with :meth:`to_sparse_observable`,
+/// using the Jordan--Wigner, parity or Bravyi--Kitaev transformation.  Mode :math:`j` maps to
+/// qubit :math:`j`, and in the Jordan--Wigner transformation, an occupied mode is the qubit state
+/// :math:`\lvert1\rangle`.
+///
+/// .. table:: Methods and attributes of :class:`SparseFermionOp`.
+///
+///   ==============================  ==============================================================
+///   Method                          Summary
+///   ==============================  ==============================================================
+///   :meth:`from_sparse_list`        Construct from a list of ``(actions, indices, coeff)``.
+///   :meth:`from_raw_parts`          Construct from the flat arrays.
+///   :meth:`zero`                    The zero operator.
+///   :meth:`identity`                The identity operator.
+///   :meth:`to_sparse_list`          Express the operator as a list of ``(actions, indices,
+///                                   coeff)``.
+///   :meth:`adjoint`                 The Hermitian adjoint of the operator.
+///   :meth:`simplify`                Sum terms with identical products.
+///   :meth:`normal_order`            Rewrite the operator in normal order.
+///   :meth:`to_sparse_observable`    Map the operator to a :class:`SparseObservable` on qubits.
+///   ==============================  ==============================================================
+#[pyclass(
+    name = "SparseFermionOp",
+    frozen,
+    module = "qiskit.quantum_info",
+    sequence
+)]
+#[derive(Clone, Debug)]
+pub struct PySparseFermionOp {
+    inner: SparseFermionOp,
+}
+impl PySparseFermionOp {
+    pub fn inner(&self) -> &SparseFermionOp {
+        &self.inner
+    }
+}
+impl From<SparseFermionOp> for PySparseFermionOp {
+    fn from(val: SparseFermionOp) -> Self {
+        Self { inner: val }
+    }
+}
+
+#[pymethods]
+impl PySparseFermionOp {
+    #[pyo3(signature = (data, /, num_modes=None))]
+    #[new]
+    fn py_new(data: &Bound<PyAny>, num_modes: Option<u32>) -> PyResult<Self> {
+        if let Ok(op) = data.downcast_exact::<Self>() {
+            let op = op.get();
+            if let Some(num_modes) = num_modes {
+                op.inner.check_equal_modes(num_modes)?;
+            }
+            return Ok(op.clone());
+        }
+        if let Ok(vec) = data.extract() {
+            let Some(num_modes) = num_modes else {
+                return Err(PyValueError::new_err(
+                    "if using the sparse-list form, 'num_modes' must be provided",
+                ));
+            };
+            return Self::from_sparse_list(vec, num_modes);
+        }
+        Err(PyTypeError::new_err(format!(
+            "unknown input format for 'SparseFermionOp': {}",
+            data.get_type().repr()?,
+        )))
+    }
+
+    /// The number of fermionic modes the operator acts on.
+    #[getter]
+    fn num_modes(&self) -> u32 {
+        self.inner.num_modes()
+    }
+
+    /// The number of terms in the sum this operator is tracking.
+    #[getter]
+    fn num_terms(&self) -> usize {
+        self.inner.num_terms()
+    }
+
+    /// A copy of the coefficients of each term.
+    #[getter]
+    fn coeffs<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<Complex64>> {
+        PyArray1::from_slice(py, self.inner.coeffs())
+    }
+
+    /// A copy of the flat list of ladder operators, where ``1`` is a creation operator and ``0`` is
+    /// an annihilation operator.
+    #[getter]
+    fn actions<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<u8>> {
+        PyArray1::from_iter(py, self.inner.actions().iter().map(|action| *action as u8))
+    }
+
+    /// A copy of the flat list of the modes each ladder operator acts on.
+    #[getter]
+    fn indices<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<u32>> {
+        PyArray1::from_slice(py, self.inner.indices())
+    }
+
+    /// A copy of the indices that partition :attr:`actions` and :attr:`indices` into terms.
+    #[getter]
+    fn boundaries<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<usize>> {
+        PyArray1::from_slice(py, self.inner.boundaries())
+    }
+
+    /// Get the zero operator over the given number of modes.
+    ///
+    /// Examples:
+    ///
+    ///     .. code-block:: python
+    ///
+    ///         >>> SparseFermionOp.zero(4)
+    ///         <SparseFermionOp with 0 terms on 4 modes: 0.0>
+    #[staticmethod]
+    #[pyo3(signature = (/, num_modes))]
+    fn zero(num_modes: u32) -> Self {
+        SparseFermionOp::zero(num_modes).into()
+    }
+
+    /// Get the identity operator over the given number of modes.
+    ///
+    /// Examples:
+    ///
+    ///     .. code-block:: python
+    ///
+    ///         >>> SparseFermionOp.identity(4)
+    ///         <SparseFermionOp with 1 term on 4 modes: (1+0j)()>
+    #[staticmethod]
+    #[pyo3(signature = (/, num_modes))]
+    fn identity(num_modes: u32) -> Self {
+        SparseFermionOp::identity(num_modes).into()
+    }
+
+    /// Construct an operator from a list of products of ladder operators.
+    ///
+    /// Each element of the list is a 3-tuple of a label, the modes the label applies to, and the
+    /// coefficient.  The label is a string of ``"+"`` (creation) and ``"-"`` (annihilation), and
+    /// the product is in the order given, so ``("+-", (1, 0), 0.5)`` is :math:`\frac12 a^\dagger_1
+    /// a_0`.  A mode can appear more than once in the same term.
+    ///
+    /// Args:
+    ///     iter (list[tuple[str, Sequence[int], complex]]): the terms.
+    ///     num_modes (int): the number of modes the operator acts on.
+    ///
+    /// Examples:
+    ///
+    ///     .. code-block:: python
+    ///
+    ///         >>> SparseFermionOp.from_sparse_list([("+-", (0, 0), 1.0), ("", (), -0.5)], 2)
+    ///         <SparseFermionOp with 2 terms on 2 modes: (1+0j)(+_0 -_0) + (-0.5+0j)()>
+    #[staticmethod]
+    #[pyo3(signature = (iter, /, num_modes))]
+    fn from_sparse_list(
+        iter: Vec<(String, Vec<u32>, Complex64)>,
+        num_modes: u32,
+    ) -> PyResult<Self> {
+        let mut out = SparseFermionOp::with_capacity(num_modes, iter.len(), 0);
+        for (label, modes, coeff) in iter {
+            if label.len() != modes.len() {
+                return Err(LabelError::WrongLengthIndices {
+                    label: label.len(),
+                    indices: modes.len(),
+                }
+                .into());
+            }
+            if let Some(index) = modes.iter().find(|index| **index >= num_modes) {
+                return Err(LabelError::BadIndex {
+                    index: *index,
+                    num_modes,
+                }
+                .into());
+            }
+            let actions = label
+                .bytes()
+                .map(FermionAction::try_from_label)
+                .collect::<Option<Vec<_>>>()
+                .ok_or(LabelError::OutsideAlphabet)?;
+            out.push_term(coeff, &actions, &modes);
+        }
+        Ok(out.into())
+    }
+
+    /// Construct an operator from the flat arrays that make it up.
+    ///
+    /// The inputs are the same as the attributes of the same names, and are checked for coherence.
+    ///
+    /// Args:
+    ///     num_modes (int): the number of modes the operator acts on.
+    ///     coeffs: the complex coefficients of each term.
+    ///     actions: the flat list of ladder operators, where ``1`` is a creation operator and
+    ///         ``0`` is an annihilation operator.
+    ///     indices: the flat list of the modes each ladder operator acts on.
+    ///     boundaries: the indices that partition ``actions`` and ``indices`` into terms.
+    #[staticmethod]
+    #[pyo3(signature = (/, num_modes, coeffs, actions, indices, boundaries))]
+    fn from_raw_parts(
+        num_modes: u32,
+        coeffs: Vec<Complex64>,
+        actions: PyReadonlyArray1<u8>,
+        indices: Vec<u32>,
+        boundaries: Vec<usize>,
+    ) -> PyResult<Self> {
+        let actions = actions
+            .as_array()
+            .iter()
+            .map(|value| FermionAction::try_from_u8(*value))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| PyValueError::new_err("`actions` must all be 0 or 1"))?;
+        Ok(SparseFermionOp::new(num_modes, coeffs, actions, indices, boundaries)?.into())
+    }
+
+    /// Express the operator as a list of ``(label, indices, coeff)`` tuples, in the same format as
+    /// accepted by :meth:`from_sparse_list`.
+    fn to_sparse_list<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyList>> {
+        let terms = self
+            .inner
+            .iter()
+            .map(|term| {
+                let label = term
+                    .actions
+                    .iter()
+                    .map(FermionAction::py_label)
+                    .collect::<String>();
+                (label, PyList::new(py, term.indices)?, term.coeff).into_pyobject(py)
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        PyList::new(py, terms)
+    }
+
+    /// Get the Hermitian adjoint of the operator.
+    ///
+    /// Each product is reversed, with each ladder operator replaced by its adjoint, and the
+    /// coefficients are conjugated.
+    ///
+    /// Examples:
+    ///
+    ///     .. code-block:: python
+    ///
+    ///         >>> SparseFermionOp.from_sparse_list([("++-", (2, 1, 0), 1j)], 3).adjoint()
+    ///         <SparseFermionOp with 1 term on 3 modes: (0-1j)(+_0 -_1 -_2)>
+    fn adjoint(&self) -> Self {
+        self.inner.adjoint().into()
+    }
+
+    /// Sum terms whose products of ladder operators are written identically.
+    ///
+    /// This does not use the anticommutation relations, so products that are equal but written
+    /// in a different order are not combined; use :meth:`normal_order` for that.
+    ///
+    /// Args:
+    ///     tol (float): after summing like terms, any coefficients whose absolute value is less
+    ///         than or equal to the given absolute tolerance are removed.
+    #[pyo3(signature = (/, tol=1e-8))]
+    fn simplify(&self, tol: f64) -> Self {
+        self.inner.simplify(tol).into()
+    }
+
+    /// Rewrite the operator in normal order, using the canonical anticommutation relations.
+    ///
+    /// In each output term, all creation operators are to the left of all annihilation operators.
+    /// The creation operators are in decreasing order of mode, and the annihilation operators are
+    /// in increasing order of mode.  Products that contain the same ladder operator twice are
+    /// zero and are removed, and like terms are summed.  Two operators are equal if their
+    /// normal-ordered forms are equal (up to floating-point tolerance and the order of terms).
+    ///
+    /// .. warning::
+    ///
+    ///     Each swap of an annihilation operator with a creation operator on the same mode
+    ///     introduces an extra term, so the number of output terms can grow exponentially with the
+    ///     number of ladder operators in each input term.
+    ///
+    /// Examples:
+    ///
+    ///     .. code-block:: python
+    ///
+    ///         >>> op = SparseFermionOp.from_sparse_list([("-+", (0, 0), 1.0)], 1)
+    ///         >>> op.normal_order()
+    ///         <SparseFermionOp with 2 terms on 1 mode: (-1+0j)(+_0 -_0) + (1+0j)()>
+    fn normal_order(&self) -> Self {
+        self.inner.normal_order().into()
+    }
+
+    /// Map the operator to a qubit :class:`SparseObservable`.
+    ///
+    /// Mode :math:`j` corresponds to qubit :math:`j` of the output, which acts on
+    /// :attr:`num_modes` qubits.  The available mappings are:
+    ///
+    /// ``"jordan_wigner"``
+    ///     Qubit :math:`j` stores the occupation of mode :math:`j`, and each ladder operator has a
+    ///     string of Pauli Z on all lower qubits to track the parity.
+    ///
+    /// ``"parity"``
+    ///     Qubit :math:`j` stores the parity of the occupations of modes :math:`0` to :math:`j`.
+    ///
+    /// ``"bravyi_kitaev"``
+    ///     The qubits store partial sums of the occupations in a binary-tree (Fenwick tree)
+    ///     arrangement, so each ladder operator acts on :math:`O(\log n)` qubits.  Any number of
+    ///     modes is supported, not just powers of two.
+    ///
+    /// Like terms are summed in the output, and any that cancel exactly are removed.
+    ///
+    /// Args:
+    ///     mapping (str): the fermion-to-qubit mapping to use.
+    ///
+    /// Returns:
+    ///     SparseObservable: the mapped operator.
+    ///
+    /// Examples:
+    ///
+    ///     .. code-block:: python
+    ///
+    ///         >>> number = SparseFermionOp.from_sparse_list([("+-", (1, 1), 1.0)], 2)
+    ///         >>> number.to_sparse_observable()
+    ///         <SparseObservable with 2 terms on 2 qubits: (0.5+0j)() + (-0.5+0j)(Z_1)>
+    #[pyo3(signature = (mapping="jordan_wigner"))]
+    fn to_sparse_observable(&self, mapping: &str) -> PyResult<SparseObservable> {
+        let mapping = match mapping {
+            "jordan_wigner" => FermionMapping::JordanWigner,
+            "parity" => FermionMapping::Parity,
+            "bravyi_kitaev" => FermionMapping::BravyiKitaev,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "unknown fermion-to-qubit mapping: '{mapping}'"
+                )))
+            }
+        };
+        Ok(self.inner.to_sparse_observable(mapping))
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.num_terms()
+    }
+
+    fn __eq__(&self, other: &Bound<PyAny>) -> bool {
+        other
+            .downcast::<Self>()
+            .is_ok_and(|other| self.inner == other.get().inner)
+    }
+
+    fn __repr__(&self) -> String {
+        let num_terms = self.inner.num_terms();
+        let num_modes = self.inner.num_modes();
+        let str_terms = if num_terms == 0 {
+            "0.0".to_owned()
+        } else {
+            self.inner
+                .iter()
+                .map(SparseFermionTermView::to_sparse_str)
+                .collect::<Vec<_>>()
+                .join(" + ")
+        };
+        format!(
+            "<SparseFermionOp with {} term{} on {} mode{}: {}>",
+            num_terms,
+            if num_terms == 1 { "" } else { "s" },
+            num_modes,
+            if num_modes == 1 { "" } else { "s" },
+            str_terms,
+        )
+    }
+
+    fn __reduce__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyTuple>> {
+        (
+            py.get_type::<Self>()
+                .getattr(intern!(py, "from_raw_parts"))?,
+            (
+                self.inner.num_modes(),
+                self.coeffs(py),
+                self.actions(py),
+                self.indices(py),
+                self.boundaries(py),
+            ),
+        )
+            .into_pyobject(py)
+    }
+
+    fn __add__<'py>(&self, other: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+        let py = other.py();
+        let Ok(other) = other.downcast::<Self>() else {
+            return Ok(py.NotImplemented().into_bound(py));
+        };
+        let other = &other.get().inner;
+        self.inner.check_equal_modes(other.num_modes())?;
+        Self::from(&self.inner + other).into_bound_py_any(py)
+    }
+
+    fn __sub__<'py>(&self, other: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+        let py = other.py();
+        let Ok(other) = other.downcast::<Self>() else {
+            return Ok(py.NotImplemented().into_bound(py));
+        };
+        let other = &other.get().inner;
+        self.inner.check_equal_modes(other.num_modes())?;
+        Self::from(&self.inner - other).into_bound_py_any(py)
+    }
+
+    fn __neg__(&self) -> Self {
+        (-&self.inner).into()
+    }
+
+    fn __pos__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __mul__(&self, other: Complex64) -> Self {
+        (&self.inner * other).into()
+    }
+
+    fn __rmul__(&self, other: Complex64) -> Self {
+        self.__mul__(other)
+    }
+}
+
+pub fn sparse_fermion_op(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_class::<PySparseFermionOp>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sparse_observable::BitTerm;
+
+    const MAPPINGS: [FermionMapping; 3] = [
+        FermionMapping::JordanWigner,
+        FermionMapping::Parity,
+        FermionMapping::BravyiKitaev,
+    ];
+
+    fn op(num_modes: u32, terms: &[(&str, &[u32], Complex64)]) -> SparseFermionOp {
+        let mut out = SparseFermionOp::zero(num_modes);
+        for (label, indices, coeff) in terms {
+            let actions = label
+                .bytes()
+                .map(|letter| FermionAction::try_from_label(letter).unwrap())
+                .collect::<Vec<_>>();
+            out.push_term(*coeff, &actions, indices);
+        }
+        out
+    }
+
+    fn c(re: f64, im: f64) -> Complex64 {
+        Complex64::new(re, im)
+    }
+
+    fn assert_matrices_close(left: &[Complex64], right: &[Complex64]) {
+        assert_eq!(left.len(), right.len());
+        for (l, r) in left.iter().zip(right) {
+            assert!((l - r).norm() < 1e-12, "{l} != {r}");
+        }
+    }
+
+    fn mapped_matrix(op: &SparseFermionOp, mapping: FermionMapping) -> Vec<Complex64> {
+        op.to_sparse_observable(mapping)
+            .to_matrix_dense(false)
+            .unwrap()
+    }
+
+    #[test]
+    fn normal_order_contracts_and_swaps() {
+        let base = op(2, &[("-+", &[0, 0], c(1.0, 0.0))]);
+        let expected = op(2, &[("+-", &[0, 0], c(-1.0, 0.0)), ("", &[], c(1.0, 0.0))]);
+        assert_eq!(base.normal_order(), expected);
+
+        let base = op(
+            2,
+            &[("++", &[0, 1], c(2.0, 0.0)), ("--", &[1, 0], c(0.0, 1.0))],
+        );
+        let expected = op(
+            2,
+            &[("++", &[1, 0], c(-2.0, 0.0)), ("--", &[0, 1], c(0.0, -1.0))],
+        );
+        assert_eq!(base.normal_order(), expected);
+
+        // Repeated ladder operators are zero, even if they are not adjacent to start with, and
+        // terms that become equal are summed.
+        let base = op(
+            3,
+            &[
+                ("+-+", &[0, 1, 0], c(1.0, 0.0)),
+                ("+-", &[2, 1], c(1.0, 0.0)),
+                ("-+", &[1, 2], c(1.0, 0.0)),
+            ],
+        );
+        assert_eq!(base.normal_order(), SparseFermionOp::zero(3));
+    }
+
+    #[test]
+    fn normal_order_is_idempotent() {
+        let base = op(
+            4,
+            &[
+                ("-+-+", &[0, 0, 3, 1], c(1.0, 0.5)),
+                ("+--+", &[2, 1, 3, 2], c(-0.25, 0.0)),
+                ("-+", &[3, 3], c(0.0, 2.0)),
+            ],
+        );
+        let ordered = base.normal_order();
+        assert_eq!(ordered.normal_order(), ordered);
+        for term in ordered.iter() {
+            let keys = term
+                .actions
+                .iter()
+                .zip(term.indices)
+                .map(|(action, index)| normal_order_key(*action, *index))
+                .collect::<Vec<_>>();
+            assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+        }
+        for mapping in MAPPINGS {
+            assert_matrices_close(
+                &mapped_matrix(&base, mapping),
+                &mapped_matrix(&ordered, mapping),
+            );
+        }
+    }
+
+    #[test]
+    fn adjoint_and_simplify() {
+        let base = op(
+            3,
+            &[
+                ("++-", &[2, 1, 0], c(0.0, 1.0)),
+                ("+-", &[1, 0], c(1.0, 0.0)),
+                ("+-", &[1, 0], c(-1.0, 0.0)),
+                ("", &[], c(0.5, 0.0)),
+            ],
+        );
+        let adjoint = base.adjoint();
+        assert_eq!(
+            adjoint.term(0),
+            op(3, &[("+--", &[0, 1, 2], c(0.0, -1.0))]).term(0)
+        );
+        assert_eq!(adjoint.adjoint(), base);
+        assert_eq!(
+            base.simplify(1e-8),
+            op(
+                3,
+                &[("++-", &[2, 1, 0], c(0.0, 1.0)), ("", &[], c(0.5, 0.0))]
+            )
+        );
+        for mapping in MAPPINGS {
+            let matrix = mapped_matrix(&base, mapping);
+            let side = 1 << 3;
+            let conj_transpose = (0..side * side)
+                .map(|i| matrix[(i % side) * side + i / side].conj())
+                .collect::<Vec<_>>();
+            assert_matrices_close(&mapped_matrix(&adjoint, mapping), &conj_transpose);
+        }
+    }
+
+    #[test]
+    fn mappings_satisfy_anticommutation_relations() {
+        let num_modes = 5;
+        let side = 1 << num_modes;
+        let identity = (0..side * side)
+            .map(|i| {
+                if i % (side + 1) == 0 {
+                    c(1.0, 0.0)
+                } else {
+                    c(0.0, 0.0)
+                }
+            })
+            .collect::<Vec<_>>();
+        let zero = vec![c(0.0, 0.0); side * side];
+        for mapping in MAPPINGS {
+            for i in 0..num_modes {
+                for j in 0..num_modes {
+                    let one = c(1.0, 0.0);
+                    let mixed = op(num_modes, &[("-+", &[i, j], one), ("+-", &[j, i], one)]);
+                    let expected = if i == j { &identity } else { &zero };
+                    assert_matrices_close(&mapped_matrix(&mixed, mapping), expected);
+                    let same = op(num_modes, &[("--", &[i, j], one), ("--", &[j, i], one)]);
+                    assert_matrices_close(&mapped_matrix(&same, mapping), &zero);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn number_operator_mappings() {
+        let number = op(4, &[("+-", &[1, 1], c(1.0, 0.0))]);
+        let expected = |z_indices: Vec<u32>| {
+            let num_z = z_indices.len();
+            SparseObservable::new(
+                4,
+                vec![c(0.5, 0.0), c(-0.5, 0.0)],
+                vec![BitTerm::Z; num_z],
+                z_indices,
+                vec![0, 0, num_z],
+            )
+            .unwrap()
+        };
+        assert_eq!(
+            number.to_sparse_observable(FermionMapping::JordanWigner),
+            expected(vec![1])
+        );
+        assert_eq!(
+            number.to_sparse_observable(FermionMapping::Parity),
+            expected(vec![0, 1])
+        );
+        assert_eq!(
+            number.to_sparse_observable(FermionMapping::BravyiKitaev),
+            expected(vec![0, 1])
+        );
+        // In Bravyi-Kitaev, mode 2 is stored alone, but mode 3 is the parity of all the modes.
+        let number = op(4, &[("+-", &[2, 2], c(1.0, 0.0))]);
+        assert_eq!(
+            number.to_sparse_observable(FermionMapping::BravyiKitaev),
+            expected(vec![2])
+        );
+        let number = op(4, &[("+-", &[3, 3], c(1.0, 0.0))]);
+        assert_eq!(
+            number.to_sparse_observable(FermionMapping::BravyiKitaev),
+            expected(vec![1, 2, 3])
+        );
+    }
+}
@@ -0,0 +1,169 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2025
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Commutators and anticommutators of [SparseObservable]s.
+//!
+//! The (anti)commutator of two observables is the sum of the (anti)commutators of each pair of
+//! terms.  Two single-qubit terms that are diagonal in the same Pauli basis commute, and two
+//! different Paulis anticommute, so for most pairs of terms we can tell up front that the
+//! (anti)commutator is either zero or twice the product, and we never need to build the reversed
+//! product.  Only pairs where a projector meets a term in a different basis need both products.
+
+use num_complex::Complex64;
+
+use super::grouping::basis_differences;
+use super::{compose, SparseObservable, SparseTermView};
+
+/// The relationship between two terms under multiplication.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Commutation {
+    /// `AB = BA`.
+    Commute,
+    /// `AB = -BA`.
+    Anticommute,
+    /// Neither of the above; the two products have to be calculated separately.
+    Neither,
+}
+
+/// Determine how two terms commute with each other.
+fn commutation(left: &SparseTermView, right: &SparseTermView) -> Commutation {
+    let mut anticommutes = false;
+    for (left_term, right_term) in basis_differences(left, right) {
+        if left_term.is_projector() || right_term.is_projector() {
+            return Commutation::Neither;
+        }
+        anticommutes = !anticommutes;
+    }
+    if anticommutes {
+        Commutation::Anticommute
+    } else {
+        Commutation::Commute
+    }
+}
+
+/// Add the terms of the matrix product `coeff * left @ right` to `out`.
+fn add_product(
+    out: &mut SparseObservable,
+    term_state: &mut compose::Iter,
+    coeff: Complex64,
+    left: &SparseTermView,
+    right: &SparseTermView,
+) {
+    term_state.load_from(
+        coeff * left.coeff * right.coeff,
+        left.indices
+            .iter()
+            .copied()
+            .zip(left.bit_terms.iter().copied()),
+        right
+            .indices
+            .iter()
+            .copied()
+            .zip(right.bit_terms.iter().copied()),
+    );
+    out.boundaries.reserve(term_state.num_terms());
+    out.coeffs.reserve(term_state.num_terms());
+    out.indices
+        .reserve(term_state.num_terms() * term_state.term_len());
+    out.bit_terms
+        .reserve(term_state.num_terms() * term_state.term_len());
+    while let Some(term) = term_state.next() {
+        out.add_term(term)
+            .expect("qubit counts were checked during initialisation");
+    }
+}
+
+impl SparseObservable {
+    /// Calculate `A @ B + sign * B @ A`, where `A` is `self` and `B` is `other`.
+    fn signed_commutator(&self, other: &SparseObservable, anti: bool) -> SparseObservable {
+        if other.num_qubits != self.num_qubits {
+            panic!(
+                "operand ({}) has a different number of qubits to the base ({})",
+                other.num_qubits, self.num_qubits
+            );
+        }
+        let (skip, sign) = if anti {
+            (Commutation::Anticommute, 1.0)
+        } else {
+            (Commutation::Commute, -1.0)
+        };
+        let mut out = SparseObservable::zero(self.num_qubits);
+        let mut term_state = compose::Iter::new(self.num_qubits);
+        for left in self.iter() {
+            for right in other.iter() {
+                match commutation(&left, &right) {
+                    relation if relation == skip => (),
+                    Commutation::Neither => {
+                        add_product(&mut out, &mut term_state, 1.0.into(), &left, &right);
+                        add_product(&mut out, &mut term_state, sign.into(), &right, &left);
+                    }
+                    _ => add_product(&mut out, &mut term_state, 2.0.into(), &left, &right),
+                }
+            }
+        }
+        out
+    }
+
+    /// Calculate the commutator `[A, B] = A @ B - B @ A`, where `A` is `self` and `B` is `other`.
+    ///
+    /// Pairs of terms that commute are skipped entirely, rather than producing two products that
+    /// cancel.  Like [SparseObservable::compose], the output is not simplified; pairs of terms
+    /// that do not themselves commute can still produce duplicate terms, or terms that cancel with
+    /// those from other pairs.
+    ///
+    /// # Panics
+    ///
+    /// If `self` and `other` have different numbers of qubits.
+    pub fn commutator(&self, other: &SparseObservable) -> SparseObservable {
+        self.signed_commutator(other, false)
+    }
+
+    /// Calculate the anticommutator `{A, B} = A @ B + B @ A`, where `A` is `self` and `B` is
+    /// `other`.
+    ///
+    /// Pairs of terms that anticommute are skipped entirely.  See [SparseObservable::commutator]
+    /// for more detail.
+    ///
+    /// # Panics
+    ///
+    /// If `self` and `other` have different numbers of qubits.
+    pub fn anticommutator(&self, other: &SparseObservable) -> SparseObservable {
+        self.signed_commutator(other, true)
+    }
+
+    /// Calculate the nested commutators `[A, [A, ..., [A, B]]]` of `A = self` and `B = other`, for
+    /// every nesting depth up to and including `order`.
+    ///
+    /// The output has `order + 1` entries, where entry `k` has `k` commutators, so the first entry
+    /// is a copy of `other`.  Each subsequent entry is canonicalized (with a tolerance of zero)
+    /// before being used in the next commutator, so that like terms are only carried forwards
+    /// once.
+    ///
+    /// # Panics
+    ///
+    /// If `self` and `other` have different numbers of qubits.
+    pub fn nested_commutators(
+        &self,
+        other: &SparseObservable,
+        order: usize,
+    ) -> Vec<SparseObservable> {
+        let mut out = Vec::with_capacity(order + 1);
+        out.push(other.clone());
+        for _ in 0..order {
+            let next = self
+                .commutator(out.last().expect("the output is never empty"))
+                .canonicalize(0.0);
+            out.push(next);
+        }
+        out
+    }
+}
@@ -0,0 +1,258 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2025
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Expectation values of [SparseObservable] against quantum states and measurement counts.
+//!
+//! The state-based calculations use the same row-wise matrix elements as the matrix conversions in
+//! [super::matrix], so the projectors are never expanded into Paulis, and the matrix of the
+//! observable is never built.
+
+use num_complex::Complex64;
+use rayon::prelude::*;
+use thiserror::Error;
+
+use qiskit_circuit::util::C_ZERO;
+
+use super::matrix::matrix_compress;
+use super::{BitTerm, SparseObservable};
+
+/// The minimum number of qubits for which the state-based calculations are done in parallel, if
+/// parallelism is requested.  This matches the threshold of the Pauli-based calculations.
+const PARALLEL_THRESHOLD: u32 = 19;
+
+#[derive(Error, Debug)]
+pub enum ExpectationError {
+    #[error(
+        "a state with {len} elements is not compatible with an observable on {num_qubits} qubits"
+    )]
+    StateSize { num_qubits: u32, len: usize },
+    #[error("the measurement basis has {len} qubits, but the observable has {num_qubits}")]
+    BasisSize { num_qubits: u32, len: usize },
+    #[error("measurement bases must be 'X', 'Y' or 'Z', not '{}'", .0.py_label())]
+    InvalidBasis(BitTerm),
+    #[error("a term acts on qubit {qubit} in a different basis to the one it was measured in")]
+    IncompatibleBasis { qubit: u32 },
+    #[error("'{bitstring}' is not a valid measurement outcome on {num_qubits} qubits")]
+    InvalidBitstring { bitstring: String, num_qubits: u32 },
+    #[error("the counts contain no shots")]
+    NoShots,
+}
+
+/// How a single-qubit term contributes to a term's value on a measurement outcome in the term's
+/// basis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Diagonal {
+    /// A Pauli: the eigenvalue is -1 if the outcome bit is set.
+    Pauli,
+    /// A projector onto the positive eigenstate: the term is zero if the outcome bit is set.
+    Positive,
+    /// A projector onto the negative eigenstate: the term is zero unless the outcome bit is set.
+    Negative,
+}
+
+/// The Pauli basis that a single-qubit term is diagonal in, and how it acts in that basis.
+fn diagonal_in_basis(bit_term: BitTerm) -> (BitTerm, Diagonal) {
+    match bit_term {
+        BitTerm::X => (BitTerm::X, Diagonal::Pauli),
+        BitTerm::Plus => (BitTerm::X, Diagonal::Positive),
+        BitTerm::Minus => (BitTerm::X, Diagonal::Negative),
+        BitTerm::Y => (BitTerm::Y, Diagonal::Pauli),
+        BitTerm::Right => (BitTerm::Y, Diagonal::Positive),
+        BitTerm::Left => (BitTerm::Y, Diagonal::Negative),
+        BitTerm::Z => (BitTerm::Z, Diagonal::Pauli),
+        BitTerm::Zero => (BitTerm::Z, Diagonal::Positive),
+        BitTerm::One => (BitTerm::Z, Diagonal::Negative),
+    }
+}
+
+/// Parse a measurement outcome into its bits, where bit `i` of the output corresponds to qubit
+/// `i`.  The outcome is either a bitstring (with qubit 0 on the right, and whitespace ignored), or
+/// a hexadecimal string prefixed with `0x`.  Returns `None` if the outcome is not valid.
+fn parse_outcome(outcome: &str, num_qubits: u32, out: &mut Vec<bool>) -> Option<()> {
+    out.clear();
+    if let Some(hex) = outcome.strip_prefix("0x") {
+        for digit in hex.chars().rev() {
+            let digit = digit.to_digit(16)?;
+            out.extend((0..4).map(|bit| digit & (1 << bit) != 0));
+        }
+        // Leading zeros can take the hex string past the number of qubits.
+        if out.iter().skip(num_qubits as usize).any(|bit| *bit) {
+            return None;
+        }
+        out.resize(num_qubits as usize, false);
+        return Some(());
+    }
+    for bit in outcome.chars().rev().filter(|c| !c.is_whitespace()) {
+        match bit {
+            '0' => out.push(false),
+            '1' => out.push(true),
+            _ => return None,
+        }
+    }
+    (out.len() == num_qubits as usize).then_some(())
+}
+
+/// The number of elements on each side of a matrix acting on `num_qubits` qubits, if it can be
+/// represented.
+fn side_length(num_qubits: u32) -> Option<usize> {
+    1usize.checked_shl(num_qubits)
+}
+
+impl SparseObservable {
+    /// Calculate the expectation value `<psi|O|psi>` of the observable against a statevector.
+    ///
+    /// Qubit `i` corresponds to bit `i` of the index into `state`.  The state is not required to be
+    /// normalized.  The expectation value is only guaranteed to be real if the observable is
+    /// Hermitian.
+    pub fn expectation_value_statevector(
+        &self,
+        state: &[Complex64],
+        parallel: bool,
+    ) -> Result<Complex64, ExpectationError> {
+        let side = side_length(self.num_qubits())
+            .filter(|side| *side == state.len())
+            .ok_or(ExpectationError::StateSize {
+                num_qubits: self.num_qubits(),
+                len: state.len(),
+            })?;
+        let terms = matrix_compress(self);
+        let row_value = |row: usize| {
+            let mut acc = C_ZERO;
+            for term in terms.iter() {
+                term.for_each_in_row(row, |col, value| acc += value * state[col]);
+            }
+            state[row].conj() * acc
+        };
+        if parallel && self.num_qubits() >= PARALLEL_THRESHOLD {
+            Ok((0..side).into_par_iter().map(row_value).sum())
+        } else {
+            Ok((0..side).map(row_value).sum())
+        }
+    }
+
+    /// Calculate the expectation value `Tr(O rho)` of the observable against a density matrix.
+    ///
+    /// The density matrix is given as a C-ordered flattening of the 2D matrix, and qubit `i`
+    /// corresponds to bit `i` of the row and column indices.  The expectation value is only
+    /// guaranteed to be real if both the observable and the density matrix are Hermitian.
+    pub fn expectation_value_density_matrix(
+        &self,
+        state: &[Complex64],
+        parallel: bool,
+    ) -> Result<Complex64, ExpectationError> {
+        let side = side_length(self.num_qubits())
+            .filter(|side| side.checked_mul(*side) == Some(state.len()))
+            .ok_or(ExpectationError::StateSize {
+                num_qubits: self.num_qubits(),
+                len: state.len(),
+            })?;
+        let terms = matrix_compress(self);
+        let row_value = |row: usize| {
+            let mut acc = C_ZERO;
+            for term in terms.iter() {
+                term.for_each_in_row(row, |col, value| acc += value * state[col * side + row]);
+            }
+            acc
+        };
+        if parallel && self.num_qubits() >= PARALLEL_THRESHOLD {
+            Ok((0..side).into_par_iter().map(row_value).sum())
+        } else {
+            Ok((0..side).map(row_value).sum())
+        }
+    }
+
+    /// Estimate the expectation value of the observable from the counts of measurement outcomes.
+    ///
+    /// Each qubit is measured in the Pauli basis given by the corresponding element of `basis`,
+    /// which must be one of [BitTerm::X], [BitTerm::Y] or [BitTerm::Z].  If `basis` is `None`, all
+    /// qubits are measured in the Z basis.  Every single-qubit term in the observable must be
+    /// diagonal in the basis its qubit was measured in; the projectors are evaluated directly as
+    /// indicators of the corresponding outcome.
+    ///
+    /// The outcomes are bitstrings with qubit 0 on the right (ignoring any whitespace), or
+    /// hexadecimal strings prefixed with `0x`, and are paired with how many times they were
+    /// observed.  The counts need not be integers, so quasi-probabilities are also accepted.
+    pub fn expectation_value_counts<'a, I>(
+        &self,
+        counts: I,
+        basis: Option<&[BitTerm]>,
+    ) -> Result<Complex64, ExpectationError>
+    where
+        I: IntoIterator<Item = (&'a str, f64)>,
+    {
+        if let Some(basis) = basis {
+            if basis.len() != self.num_qubits() as usize {
+                return Err(ExpectationError::BasisSize {
+                    num_qubits: self.num_qubits(),
+                    len: basis.len(),
+                });
+            }
+            if let Some(bad) = basis.iter().find(|bit_term| bit_term.is_projector()) {
+                return Err(ExpectationError::InvalidBasis(*bad));
+            }
+        }
+        let terms = self
+            .iter()
+            .map(|view| {
+                view.bit_terms
+                    .iter()
+                    .zip(view.indices)
+                    .map(|(bit_term, &index)| {
+                        let (term_basis, diagonal) = diagonal_in_basis(*bit_term);
+                        let measured = basis.map_or(BitTerm::Z, |basis| basis[index as usize]);
+                        if term_basis == measured {
+                            Ok((index as usize, diagonal))
+                        } else {
+                            Err(ExpectationError::IncompatibleBasis { qubit: index })
+                        }
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut term_sums = vec![0.0; terms.len()];
+        let mut shots = 0.0;
+        let mut bits = Vec::with_capacity(self.num_qubits() as usize);
+        for (outcome, count) in counts {
+            parse_outcome(outcome, self.num_qubits(), &mut bits).ok_or_else(|| {
+                ExpectationError::InvalidBitstring {
+                    bitstring: outcome.to_owned(),
+                    num_qubits: self.num_qubits(),
+                }
+            })?;
+            shots += count;
+            for (term, sum) in terms.iter().zip(term_sums.iter_mut()) {
+                let mut value = 1.0;
+                for &(index, diagonal) in term {
+                    match (diagonal, bits[index]) {
+                        (Diagonal::Pauli, true) => value = -value,
+                        (Diagonal::Positive, true) | (Diagonal::Negative, false) => {
+                            value = 0.0;
+                            break;
+                        }
+                        _ => (),
+                    }
+                }
+                *sum += count * value;
+            }
+        }
+        if shots == 0.0 {
+            return Err(ExpectationError::NoShots);
+        }
+        Ok(self
+            .coeffs()
+            .iter()
+            .zip(term_sums)
+            .map(|(coeff, sum)| coeff * (sum / shots))
+            .sum())
+    }
+}
@@ -0,0 +1,201 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2025
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Partitioning of the terms of a [SparseObservable] into groups that can be measured
+//! simultaneously.
+//!
+//! This is the same problem as `PauliList.group_commuting` solves in Python space: we build the
+//! graph with a node for each term and an edge between any two terms that can't be measured
+//! together, and then colour it.  Each colour is a group.
+//!
+//! Projectors need more care than Paulis.  A projector onto an eigenstate of `P` can only be
+//! measured by measuring `P` on that qubit by itself, so two terms acting on the same qubit with a
+//! projector are only compatible if they act in the same basis there, even in the general (not
+//! qubit-wise) mode.  On qubits where both terms are Paulis, the general mode only requires that
+//! there are an even number of anticommuting positions, exactly like `QubitSparsePauli::commutes`.
+
+use std::cmp::Ordering;
+
+use rayon::prelude::*;
+use rustworkx_core::coloring::greedy_node_color;
+use rustworkx_core::petgraph::graph::{NodeIndex, UnGraph};
+
+use super::{BitTerm, SparseObservable, SparseTerm, SparseTermView};
+
+/// The minimum number of terms for which the non-commutation graph is built in parallel, if
+/// parallelism is requested.
+const PARALLEL_THRESHOLD: usize = 256;
+
+/// The Pauli basis a single-qubit term is diagonal in.  This is the symplectic representation in
+/// the low two bits of [BitTerm].
+#[inline]
+pub(super) fn basis(bit_term: BitTerm) -> u8 {
+    (bit_term as u8) & 0b11
+}
+
+/// The Pauli operator for a basis returned by [basis].
+fn pauli_of_basis(basis: u8) -> BitTerm {
+    match basis {
+        0b10 => BitTerm::X,
+        0b11 => BitTerm::Y,
+        0b01 => BitTerm::Z,
+        _ => unreachable!("{basis:#04b} is not a Pauli basis"),
+    }
+}
+
+/// The pairs of single-qubit terms that two terms apply to the same qubit in different Pauli
+/// bases, in order of qubit.  Qubits that only one of the terms acts on are skipped.
+///
+/// This is lazy, so callers can stop at the first pair that decides their question.
+pub(super) fn basis_differences<'a>(
+    left: &SparseTermView<'a>,
+    right: &SparseTermView<'a>,
+) -> impl Iterator<Item = (BitTerm, BitTerm)> + 'a {
+    let (left_indices, left_bit_terms) = (left.indices, left.bit_terms);
+    let (right_indices, right_bit_terms) = (right.indices, right.bit_terms);
+    let mut left_idx = 0;
+    let mut right_idx = 0;
+    ::std::iter::from_fn(move || {
+        while left_idx < left_indices.len() && right_idx < right_indices.len() {
+            match left_indices[left_idx].cmp(&right_indices[right_idx]) {
+                Ordering::Less => left_idx += 1,
+                Ordering::Greater => right_idx += 1,
+                Ordering::Equal => {
+                    let left_term = left_bit_terms[left_idx];
+                    let right_term = right_bit_terms[right_idx];
+                    left_idx += 1;
+                    right_idx += 1;
+                    if basis(left_term) != basis(right_term) {
+                        return Some((left_term, right_term));
+                    }
+                }
+            }
+        }
+        None
+    })
+}
+
+/// Can the two terms be measured simultaneously?
+fn compatible(left: &SparseTermView, right: &SparseTermView, qubit_wise: bool) -> bool {
+    let mut anticommutes = false;
+    for (left_term, right_term) in basis_differences(left, right) {
+        if qubit_wise || left_term.is_projector() || right_term.is_projector() {
+            return false;
+        }
+        anticommutes = !anticommutes;
+    }
+    !anticommutes
+}
+
+impl SparseObservable {
+    /// Partition the terms of the observable into groups that can be measured simultaneously,
+    /// returning the indices of the terms in each group.
+    ///
+    /// If `qubit_wise`, the terms in each group act in the same Pauli basis on every qubit they
+    /// share, so each group can be measured with single-qubit rotations (see
+    /// [SparseObservable::pauli_base]).  Otherwise, the terms in each group commute, treating each
+    /// projector as needing its own single-qubit measurement.
+    ///
+    /// The groups are found by greedy graph colouring, so are not guaranteed to be the minimum
+    /// number possible.  The groups are ordered by their first term, and within each group, the
+    /// term indices are in increasing order.
+    pub fn commuting_groups(&self, qubit_wise: bool, parallel: bool) -> Vec<Vec<usize>> {
+        let terms = self.iter().collect::<Vec<_>>();
+        let num_terms = terms.len();
+        let edges_from = |left: usize| {
+            let terms = &terms;
+            (left + 1..num_terms)
+                .filter(move |right| !compatible(&terms[left], &terms[*right], qubit_wise))
+                .map(move |right| (left as u32, right as u32))
+        };
+        let edges = if parallel && num_terms >= PARALLEL_THRESHOLD {
+            (0..num_terms)
+                .into_par_iter()
+                .flat_map_iter(edges_from)
+                .collect::<Vec<_>>()
+        } else {
+            (0..num_terms).flat_map(edges_from).collect::<Vec<_>>()
+        };
+        let mut graph = UnGraph::<(), ()>::with_capacity(num_terms, edges.len());
+        for _ in 0..num_terms {
+            graph.add_node(());
+        }
+        graph.extend_with_edges(edges);
+
+        let colors = greedy_node_color(&graph);
+        // The colours are assigned in order of node degree; we order the groups by their first
+        // term instead, so the output is independent of the colouring order.
+        let mut group_of_color = vec![None; colors.len()];
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        for term in 0..num_terms {
+            let group = *group_of_color[colors[&NodeIndex::new(term)]].get_or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[group].push(term);
+        }
+        groups
+    }
+
+    /// Partition the observable into observables whose terms can be measured simultaneously.
+    ///
+    /// The sum of the output is the input.  See [SparseObservable::commuting_groups] for the
+    /// meaning of `qubit_wise`.
+    pub fn group_commuting(&self, qubit_wise: bool, parallel: bool) -> Vec<SparseObservable> {
+        self.commuting_groups(qubit_wise, parallel)
+            .into_iter()
+            .map(|group| {
+                let num_bit_terms = group.iter().map(|i| self.term(*i).indices.len()).sum();
+                let mut out =
+                    SparseObservable::with_capacity(self.num_qubits, group.len(), num_bit_terms);
+                for i in group {
+                    out.add_term(self.term(i))
+                        .expect("the terms come from an observable of the same size");
+                }
+                out
+            })
+            .collect()
+    }
+
+    /// Get the single Pauli basis that every term in the observable can be measured in, if the
+    /// terms are all qubit-wise compatible.
+    ///
+    /// The output is a term with unit coefficient containing the Pauli to measure on each qubit
+    /// that at least one term acts on.  Returns `None` if two terms act on the same qubit in
+    /// different bases.
+    pub fn pauli_base(&self) -> Option<SparseTerm> {
+        let mut bases = vec![0u8; self.num_qubits as usize];
+        for (bit_term, index) in self.bit_terms.iter().zip(self.indices.iter()) {
+            let existing = &mut bases[*index as usize];
+            match *existing {
+                0 => *existing = basis(*bit_term),
+                existing if existing == basis(*bit_term) => (),
+                _ => return None,
+            }
+        }
+        let (indices, bit_terms): (Vec<u32>, Vec<BitTerm>) = bases
+            .into_iter()
+            .enumerate()
+            .filter(|(_, basis)| *basis != 0)
+            .map(|(index, basis)| (index as u32, pauli_of_basis(basis)))
+            .unzip();
+        Some(
+            SparseTerm::new(
+                self.num_qubits,
+                1.0.into(),
+                bit_terms.into_boxed_slice(),
+                indices.into_boxed_slice(),
+            )
+            .expect("the indices are in range by construction"),
+        )
+    }
+}
@@ -0,0 +1,328 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2025
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Conversion of [SparseObservable] to dense and CSR matrices.
+//!
+//! This works directly on the projectors, rather than going through
+//! [SparseObservable::as_paulis].  Each term is a tensor product of single-qubit operators, so each
+//! row of its matrix is found by considering the row bit of each qubit independently.  The Paulis
+//! and the Z-basis projectors have exactly one non-zero in each row of their matrix (or none, for
+//! the projectors), so a term made only of those has at most one non-zero per row, just like in
+//! the Pauli-only case.  The X- and Y-basis projectors have two non-zeros in every row, so a term
+//! with `k` of them has `2^k` non-zeros per row, which is still exponentially fewer operations
+//! than the `as_paulis` form, which also expands the Z-basis projectors and visits every row once
+//! per Pauli term.
+
+use num_complex::Complex64;
+use num_traits::Zero;
+use rayon::prelude::*;
+use thiserror::Error;
+
+use qiskit_circuit::util::{c64, C_ZERO};
+
+use super::{BitTerm, SparseObservable};
+use crate::sparse_pauli_op::copy_flat_parallel;
+
+/// The powers of the imaginary unit, indexed by the exponent modulo 4.
+const I_POWERS: [Complex64; 4] = [c64(1., 0.), c64(0., 1.), c64(-1., 0.), c64(0., -1.)];
+
+/// The three arrays `(data, indices, indptr)` of a CSR matrix.
+pub type CSRData<T> = (Vec<Complex64>, Vec<T>, Vec<T>);
+
+#[derive(Error, Debug)]
+pub enum MatrixError {
+    #[error("cannot build a matrix on {num_qubits} qubits; the maximum is {max}")]
+    TooManyQubits { num_qubits: u32, max: u32 },
+}
+
+/// The largest number of qubits for which a dense matrix can be allocated.  This ensures that the
+/// number of bytes in the matrix fits in an `isize`.
+pub const MAX_DENSE_QUBITS: u32 = (usize::BITS - 6) / 2;
+/// The largest number of qubits for which a CSR matrix can be built.  The limiting factor is the
+/// side length of the matrix, which must fit in a `u64` bitmask and leave room for the bytes of
+/// the `indptr` array.
+pub const MAX_SPARSE_QUBITS: u32 = usize::BITS - 6;
+
+/// A single term of a [SparseObservable], compressed into bitmasks over the qubits so that the
+/// non-zero elements of a row of its matrix can be calculated directly from the row index.
+///
+/// In all the masks, bit `i` corresponds to qubit `i`.  Each matrix element is the coefficient
+/// multiplied by a power of the imaginary unit, which is calculated from the masks.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct MatrixCompressedTerm {
+    /// The coefficient of the term, including the factor of a half from each X- and Y-basis
+    /// projector, and the factor `-i` from each Pauli Y.
+    coeff: Complex64,
+    /// The qubits whose column bit is always the flip of the row bit (Pauli X and Y).
+    x_like: u64,
+    /// The qubits that contribute a factor of -1 when the row bit is set (Pauli Z and Y).
+    z_like: u64,
+    /// The qubits with a Z-basis projector.
+    z_projector: u64,
+    /// The qubits with a `One` projector; rows whose bits on `z_projector` do not match this are
+    /// all zero.
+    one: u64,
+    /// The qubits with an X- or Y-basis projector, for which the column bit can take either value.
+    free: u64,
+    /// The qubits of `free` whose off-diagonal element is -1 (`Minus`).
+    minus: u64,
+    /// The qubits of `free` whose off-diagonal element is `-i` in the row with the bit unset
+    /// (`Right`).
+    right: u64,
+    /// The qubits of `free` whose off-diagonal element is `+i` in the row with the bit unset
+    /// (`Left`).
+    left: u64,
+}
+
+impl MatrixCompressedTerm {
+    /// Call `f` with the column index and value of each non-zero element in row `row`.
+    #[inline]
+    pub(super) fn for_each_in_row(&self, row: usize, mut f: impl FnMut(usize, Complex64)) {
+        let row = row as u64;
+        if row & self.z_projector != self.one {
+            return;
+        }
+        let base_col = row ^ self.x_like;
+        let base_power = 2 * (row & self.z_like).count_ones();
+        // The off-diagonal elements of both Y-basis projectors pick up a sign from the row bit.
+        let y_projector_row = row & (self.right | self.left);
+        // Iterate through all subsets of `free`, starting from the empty set; these are the
+        // qubits on which the column bit is the flip of the row bit.
+        let mut flipped = 0u64;
+        loop {
+            let power = base_power
+                + 2 * (flipped & self.minus).count_ones()
+                + 3 * (flipped & self.right).count_ones()
+                + (flipped & self.left).count_ones()
+                + 2 * (flipped & y_projector_row).count_ones();
+            f(
+                (base_col ^ flipped) as usize,
+                self.coeff * I_POWERS[(power % 4) as usize],
+            );
+            flipped = flipped.wrapping_sub(self.free) & self.free;
+            if flipped == 0 {
+                break;
+            }
+        }
+    }
+}
+
+/// Compress all the terms of the observable with non-zero coefficients.
+pub(super) fn matrix_compress(obs: &SparseObservable) -> Vec<MatrixCompressedTerm> {
+    obs.iter()
+        .filter(|view| !view.coeff.is_zero())
+        .map(|view| {
+            let mut term = MatrixCompressedTerm {
+                coeff: view.coeff,
+                x_like: 0,
+                z_like: 0,
+                z_projector: 0,
+                one: 0,
+                free: 0,
+                minus: 0,
+                right: 0,
+                left: 0,
+            };
+            for (bit_term, &index) in view.bit_terms.iter().zip(view.indices) {
+                let mask = 1u64 << index;
+                match bit_term {
+                    BitTerm::X => term.x_like |= mask,
+                    BitTerm::Y => {
+                        term.x_like |= mask;
+                        term.z_like |= mask;
+                        term.coeff *= c64(0., -1.);
+                    }
+                    BitTerm::Z => term.z_like |= mask,
+                    BitTerm::Zero => term.z_projector |= mask,
+                    BitTerm::One => {
+                        term.z_projector |= mask;
+                        term.one |= mask;
+                    }
+                    BitTerm::Plus | BitTerm::Minus | BitTerm::Right | BitTerm::Left => {
+                        term.free |= mask;
+                        term.coeff *= 0.5;
+                        match bit_term {
+                            BitTerm::Minus => term.minus |= mask,
+                            BitTerm::Right => term.right |= mask,
+                            BitTerm::Left => term.left |= mask,
+                            _ => (),
+                        }
+                    }
+                }
+            }
+            term
+        })
+        .collect()
+}
+
+/// Fill `scratch` with the sorted, summed `(column, value)` pairs of row `row` of the matrix.
+fn sparse_row(terms: &[MatrixCompressedTerm], row: usize, scratch: &mut Vec<(usize, Complex64)>) {
+    scratch.clear();
+    for term in terms {
+        term.for_each_in_row(row, |col, value| scratch.push((col, value)));
+    }
+    scratch.sort_unstable_by_key(|(col, _)| *col);
+    scratch.dedup_by(|(col, value), (prev_col, prev_value)| {
+        if col == prev_col {
+            *prev_value += *value;
+            true
+        } else {
+            false
+        }
+    });
+}
+
+/// Convert a `usize` that is known to be in range to the index type of a CSR matrix.
+#[inline]
+fn to_index<T: TryFrom<usize>>(value: usize) -> T {
+    T::try_from(value)
+        .ok()
+        .expect("the caller should have checked the index type is large enough")
+}
+
+fn to_matrix_sparse_serial<T>(terms: &[MatrixCompressedTerm], side: usize) -> CSRData<T>
+where
+    T: TryFrom<usize> + Copy,
+{
+    let mut values = Vec::<Complex64>::with_capacity(side);
+    let mut indices = Vec::<T>::with_capacity(side);
+    let mut indptr = Vec::<T>::with_capacity(side + 1);
+    indptr.push(to_index(0));
+    let mut scratch = Vec::new();
+    for row in 0..side {
+        sparse_row(terms, row, &mut scratch);
+        for &(col, value) in scratch.iter() {
+            values.push(value);
+            indices.push(to_index(col));
+        }
+        indptr.push(to_index(values.len()));
+    }
+    (values, indices, indptr)
+}
+
+fn to_matrix_sparse_parallel<T>(terms: &[MatrixCompressedTerm], side: usize) -> CSRData<T>
+where
+    T: TryFrom<usize> + Copy + Send + Sync,
+{
+    // As in the Pauli-only form, we trade off some of Rayon's ability to balance the load for
+    // less overhead by splitting the rows into exactly one chunk per thread.
+    let num_threads = rayon::current_num_threads();
+    let chunk_size = side.div_ceil(num_threads);
+    let chunks = (0..side.div_ceil(chunk_size))
+        .into_par_iter()
+        .map(|chunk| {
+            let start = chunk * chunk_size;
+            let end = (start + chunk_size).min(side);
+            let mut values = Vec::<Complex64>::with_capacity(end - start);
+            let mut indices = Vec::<T>::with_capacity(end - start);
+            // The cumulative number of non-zeros _within the chunk_ at the end of each row.
+            let mut nnz = Vec::<usize>::with_capacity(end - start);
+            let mut scratch = Vec::new();
+            for row in start..end {
+                sparse_row(terms, row, &mut scratch);
+                for &(col, value) in scratch.iter() {
+                    values.push(value);
+                    indices.push(to_index(col));
+                }
+                nnz.push(values.len());
+            }
+            (values, indices, nnz)
+        })
+        .collect::<Vec<_>>();
+    // Turn the chunkwise non-zero counts into absolute counts.
+    let mut indptr = Vec::<T>::with_capacity(side + 1);
+    indptr.push(to_index(0));
+    let mut start_nnz = 0;
+    for (values, _, nnz) in chunks.iter() {
+        indptr.extend(nnz.iter().map(|nnz| to_index::<T>(start_nnz + nnz)));
+        start_nnz += values.len();
+    }
+    let (values_chunks, indices_chunks): (Vec<_>, Vec<_>) = chunks
+        .into_iter()
+        .map(|(values, indices, _)| (values, indices))
+        .unzip();
+    let values = copy_flat_parallel(&values_chunks);
+    let indices = copy_flat_parallel(&indices_chunks);
+    (values, indices, indptr)
+}
+
+impl SparseObservable {
+    /// Build the dense matrix of the observable, as a C-ordered [Vec] of the 2D matrix.
+    ///
+    /// Qubit `i` corresponds to bit `i` of the row and column indices.
+    pub fn to_matrix_dense(&self, parallel: bool) -> Result<Vec<Complex64>, MatrixError> {
+        if self.num_qubits() > MAX_DENSE_QUBITS {
+            return Err(MatrixError::TooManyQubits {
+                num_qubits: self.num_qubits(),
+                max: MAX_DENSE_QUBITS,
+            });
+        }
+        let terms = matrix_compress(self);
+        let side = 1usize << self.num_qubits();
+        let mut out = vec![C_ZERO; side * side];
+        let write_row = |(row, out_row): (usize, &mut [Complex64])| {
+            for term in terms.iter() {
+                term.for_each_in_row(row, |col, value| out_row[col] += value);
+            }
+        };
+        if parallel {
+            out.par_chunks_mut(side).enumerate().for_each(write_row);
+        } else {
+            out.chunks_mut(side).enumerate().for_each(write_row);
+        }
+        Ok(out)
+    }
+
+    /// Build the three-array CSR form of the matrix of the observable, with column indices sorted
+    /// within each row.
+    ///
+    /// Qubit `i` corresponds to bit `i` of the row and column indices.  Elements that cancel to
+    /// zero within a row are stored explicitly.  The caller is responsible for choosing an index
+    /// type `T` that can hold the number of stored elements; see [Self::max_matrix_nnz].
+    pub fn to_matrix_sparse<T>(&self, parallel: bool) -> Result<CSRData<T>, MatrixError>
+    where
+        T: TryFrom<usize> + Copy + Send + Sync,
+    {
+        if self.num_qubits() > MAX_SPARSE_QUBITS {
+            return Err(MatrixError::TooManyQubits {
+                num_qubits: self.num_qubits(),
+                max: MAX_SPARSE_QUBITS,
+            });
+        }
+        let terms = matrix_compress(self);
+        let side = 1usize << self.num_qubits();
+        if parallel {
+            Ok(to_matrix_sparse_parallel(&terms, side))
+        } else {
+            Ok(to_matrix_sparse_serial(&terms, side))
+        }
+    }
+
+    /// A pessimistic upper bound on the number of stored elements in the CSR form of the matrix of
+    /// this observable, for use in choosing an index type for [Self::to_matrix_sparse].
+    pub fn max_matrix_nnz(&self) -> u64 {
+        let side = 1u64.checked_shl(self.num_qubits()).unwrap_or(u64::MAX);
+        let max_entries_per_row = self
+            .iter()
+            .map(|view| {
+                let free = view
+                    .bit_terms
+                    .iter()
+                    .filter(|bit_term| bit_term.is_projector() && bit_term.has_x_component())
+                    .count();
+                1u64.checked_shl(free as u32).unwrap_or(u64::MAX)
+            })
+            .fold(0u64, u64::saturating_add)
+            .min(side);
+        max_entries_per_row.saturating_mul(side)
+    }
+}
@@ -10,7 +10,14 @@
 // copyright notice, and modified files need to carry a notice indicating
 // that they have been altered from the originals.
 
+mod commutator;
+mod expectation;
+mod grouping;
 mod lookup;
+mod matrix;
+
+pub use expectation::ExpectationError;
+pub use matrix::{CSRData, MatrixError, MAX_DENSE_QUBITS, MAX_SPARSE_QUBITS};
 
 use hashbrown::HashSet;
 use indexmap::IndexSet;
@@ -19,8 +26,8 @@ use ndarray::Array2;
 use num_complex::Complex64;
 use num_traits::Zero;
 use numpy::{
-    PyArray1, PyArray2, PyArrayDescr, PyArrayDescrMethods, PyArrayLike1, PyArrayMethods,
-    PyReadonlyArray1, PyReadonlyArray2, PyUntypedArrayMethods,
+    AllowTypeChange, PyArray1, PyArray2, PyArrayDescr, PyArrayDescrMethods, PyArrayLike1,
+    PyArrayLike2, PyArrayMethods, PyReadonlyArray1, PyReadonlyArray2, PyUntypedArrayMethods,
 };
 use pyo3::{
     exceptions::{PyRuntimeError, PyTypeError, PyValueError, PyZeroDivisionError},
@@ -47,6 +54,9 @@ static PAULI_TYPE: ImportOnceCell = ImportOnceCell::new("qiskit.quantum_info", "
 static PAULI_LIST_TYPE: ImportOnceCell = ImportOnceCell::new("qiskit.quantum_info", "PauliList");
 static SPARSE_PAULI_OP_TYPE: ImportOnceCell =
     ImportOnceCell::new("qiskit.quantum_info", "SparsePauliOp");
+static CSR_MATRIX_TYPE: ImportOnceCell = ImportOnceCell::new("scipy.sparse", "csr_matrix");
+static QUANTUM_STATE_TYPE: ImportOnceCell =
+    ImportOnceCell::new("qiskit.quantum_info.states.quantum_state", "QuantumState");
 static BIT_TERM_PY_ENUM: GILOnceCell<Py<PyType>> = GILOnceCell::new();
 static BIT_TERM_INTO_PY: GILOnceCell<[Option<Py<PyAny>>; 16]> = GILOnceCell::new();
 
@@ -1647,6 +1657,16 @@ impl From<ArithmeticError> for PyErr {
         PyValueError::new_err(value.to_string())
     }
 }
+impl From<MatrixError> for PyErr {
+    fn from(value: MatrixError) -> PyErr {
+        PyValueError::new_err(value.to_string())
+    }
+}
+impl From<ExpectationError> for PyErr {
+    fn from(value: ExpectationError) -> PyErr {
+        PyValueError::new_err(value.to_string())
+    }
+}
 
 /// The single-character string label used to represent this term in the :class:`SparseObservable`
 /// alphabet.
@@ -2912,6 +2932,219 @@ impl PySparseObservable {
         Ok(inner.as_paulis().into())
     }
 
+    /// Build the matrix form of this observable.
+    ///
+    /// The projectors are handled directly, without expanding them into Pauli terms, so this is
+    /// typically much faster than converting the output of :meth:`as_paulis`.  The matrix uses the
+    /// same little-endian convention as the rest of Qiskit: qubit :math:`i` corresponds to bit
+    /// :math:`i` of the row and column indices.
+    ///
+    /// .. warning::
+    ///
+    ///     The matrix has :math:`2^{2n}` elements for an observable on :math:`n` qubits, so even
+    ///     the sparse form is only suitable for small numbers of qubits.
+    ///
+    /// Args:
+    ///     sparse (bool): if ``True``, return a Scipy sparse matrix in CSR format, otherwise return
+    ///         a dense Numpy array.
+    ///     force_serial (bool): if ``True``, use an unthreaded implementation, regardless of the
+    ///         state of the `Qiskit threading-control environment variables
+    ///         <https://quantum.cloud.ibm.com/docs/guides/configure-qiskit-local#environment-variables>`__.
+    ///         By default, this will use threaded parallelism over the available CPUs.
+    ///
+    /// Returns:
+    ///     numpy.ndarray | scipy.sparse.csr_matrix: the matrix form of the observable.
+    ///
+    /// Examples:
+    ///
+    ///     The matrix of a projector is not proportional to a single Pauli::
+    ///
+    ///         >>> SparseObservable("+").to_matrix()
+    ///         array([[0.5+0.j, 0.5+0.j],
+    ///                [0.5+0.j, 0.5+0.j]])
+    ///
+    ///     The matrix is the same as that of the equivalent Pauli-only form::
+    ///
+    ///         >>> obs = SparseObservable.from_list([("+r", 1.5), ("1Z", -0.5j)])
+    ///         >>> np.testing.assert_allclose(
+    ///         ...     obs.to_matrix(), SparsePauliOp.from_sparse_observable(obs).to_matrix()
+    ///         ... )
+    #[pyo3(signature = (/, sparse=false, force_serial=false))]
+    fn to_matrix<'py>(
+        &self,
+        py: Python<'py>,
+        sparse: bool,
+        force_serial: bool,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.read().map_err(|_| InnerReadError)?;
+        let parallel = !force_serial && qiskit_circuit::getenv_use_multiple_threads();
+        let side = 1usize << inner.num_qubits().min(usize::BITS - 1);
+        if !sparse {
+            let out = inner.to_matrix_dense(parallel)?;
+            return Ok(PyArray1::from_vec(py, out)
+                .reshape([side, side])?
+                .into_any());
+        }
+
+        // Like `SparsePauliOp`, we use `i32` indices if they're guaranteed to be large enough,
+        // because Scipy will downcast to them on `csr_matrix` construction anyway.
+        let (data, indices, indptr) = if inner.max_matrix_nnz() <= (i32::MAX as u64) {
+            let (data, indices, indptr) = inner.to_matrix_sparse::<i32>(parallel)?;
+            (
+                PyArray1::from_vec(py, data).into_any(),
+                PyArray1::from_vec(py, indices).into_any(),
+                PyArray1::from_vec(py, indptr).into_any(),
+            )
+        } else {
+            let (data, indices, indptr) = inner.to_matrix_sparse::<i64>(parallel)?;
+            (
+                PyArray1::from_vec(py, data).into_any(),
+                PyArray1::from_vec(py, indices).into_any(),
+                PyArray1::from_vec(py, indptr).into_any(),
+            )
+        };
+        CSR_MATRIX_TYPE
+            .get_bound(py)
+            .call1(((data, indices, indptr), (side, side)))
+    }
+
+    /// Calculate the expectation value of this observable against a quantum state.
+    ///
+    /// The projectors are evaluated directly, without expanding them into Pauli terms, and the
+    /// matrix of the observable is never built.  The state uses the same little-endian convention
+    /// as the rest of Qiskit: qubit :math:`i` corresponds to bit :math:`i` of the index.
+    ///
+    /// Args:
+    ///     state: the state to evaluate the observable against.  This can be a
+    ///         :class:`.Statevector` or a :class:`.DensityMatrix`, or a 1D or 2D array, which is
+    ///         interpreted as a statevector or a density matrix respectively.  The state must be on
+    ///         the same number of qubits as the observable.
+    ///
+    /// Returns:
+    ///     complex: the expectation value.  This is only guaranteed to be real if the observable
+    ///     (and the density matrix, if given) is Hermitian.
+    ///
+    /// Examples:
+    ///
+    ///     Evaluate a projector-heavy observable against a Bell state::
+    ///
+    ///         >>> from qiskit.quantum_info import Statevector
+    ///         >>> bell = Statevector([1, 0, 0, 1]) / np.sqrt(2)
+    ///         >>> obs = SparseObservable.from_list([("00", 1.0), ("11", 1.0), ("++", -0.5)])
+    ///         >>> obs.expectation_value(bell)
+    ///         (0.75+0j)
+    ///
+    /// See also:
+    ///     :meth:`expectation_value_counts`
+    ///         The estimate of the expectation value from measurement counts.
+    #[pyo3(signature = (state, /))]
+    fn expectation_value(&self, state: &Bound<PyAny>) -> PyResult<Complex64> {
+        let py = state.py();
+        let inner = self.inner.read().map_err(|_| InnerReadError)?;
+        let state = if state.is_instance(QUANTUM_STATE_TYPE.get_bound(py))? {
+            state.getattr(intern!(py, "data"))?
+        } else {
+            state.clone()
+        };
+        let parallel = qiskit_circuit::getenv_use_multiple_threads();
+        if let Ok(statevector) = state.extract::<PyArrayLike1<Complex64, AllowTypeChange>>() {
+            let statevector = statevector.as_array();
+            let statevector = statevector.as_standard_layout();
+            let statevector = statevector
+                .as_slice()
+                .expect("standard-layout arrays are contiguous");
+            return Ok(inner.expectation_value_statevector(statevector, parallel)?);
+        }
+        if let Ok(density_matrix) = state.extract::<PyArrayLike2<Complex64, AllowTypeChange>>() {
+            let density_matrix = density_matrix.as_array();
+            if density_matrix.nrows() != density_matrix.ncols() {
+                return Err(PyValueError::new_err("a density matrix must be square"));
+            }
+            let density_matrix = density_matrix.as_standard_layout();
+            let density_matrix = density_matrix
+                .as_slice()
+                .expect("standard-layout arrays are contiguous");
+            return Ok(inner.expectation_value_density_matrix(density_matrix, parallel)?);
+        }
+        Err(PyTypeError::new_err(format!(
+            "cannot interpret {} as a statevector or density matrix",
+            state.get_type().repr()?
+        )))
+    }
+
+    /// Estimate the expectation value of this observable from measurement counts.
+    ///
+    /// Each qubit is taken to have been measured in one of the Pauli bases, as given by ``basis``.
+    /// Every single-qubit term of the observable must be diagonal in the basis its qubit was
+    /// measured in, so, for example, qubits measured in the X basis can only have the terms ``X``,
+    /// ``+`` and ``-`` acting on them.  The projectors are evaluated directly as indicators of the
+    /// corresponding measurement outcome, without expanding them into Pauli terms.
+    ///
+    /// Args:
+    ///     counts: a mapping of measurement outcomes to the number of times they were observed.
+    ///         The outcomes are bitstrings with qubit 0 on the right (any spaces are ignored), or
+    ///         hexadecimal strings prefixed with ``0x``.  The numbers need not be integers, so a
+    ///         :class:`.Counts`, :class:`.ProbDistribution` or :class:`.QuasiDistribution` are all
+    ///         accepted.
+    ///     basis (str | None): the Pauli basis each qubit was measured in, as a string of the
+    ///         letters ``X``, ``Y`` and ``Z`` with qubit 0 on the right.  If not given, all qubits
+    ///         are taken to have been measured in the Z basis.
+    ///
+    /// Returns:
+    ///     complex: the estimated expectation value.
+    ///
+    /// Raises:
+    ///     ValueError: if a term acts on a qubit in a different basis to the one it was measured
+    ///         in, or if the outcomes are not valid for the number of qubits.
+    ///
+    /// Examples:
+    ///
+    ///     Estimate an observable from counts with qubit 1 measured in the X basis::
+    ///
+    ///         >>> obs = SparseObservable.from_list([("+0", 1.0), ("XZ", 0.5)])
+    ///         >>> obs.expectation_value_counts({"00": 60, "01": 20, "10": 20}, basis="XZ")
+    ///         (0.7+0j)
+    #[pyo3(signature = (counts, /, basis=None))]
+    fn expectation_value_counts(
+        &self,
+        counts: &Bound<PyAny>,
+        basis: Option<&str>,
+    ) -> PyResult<Complex64> {
+        let py = counts.py();
+        let inner = self.inner.read().map_err(|_| InnerReadError)?;
+        let basis = basis
+            .map(|basis| {
+                basis
+                    .bytes()
+                    .rev()
+                    .map(|letter| match BitTerm::try_from_u8(letter) {
+                        Ok(Some(bit_term)) => Ok(bit_term),
+                        _ => Err(PyValueError::new_err(format!(
+                            "invalid measurement basis '{basis}'"
+                        ))),
+                    })
+                    .collect::<PyResult<Vec<_>>>()
+            })
+            .transpose()?;
+        // The probability distributions have integer keys; we need them as bitstrings.
+        let counts = if counts.hasattr(intern!(py, "binary_probabilities"))? {
+            counts.call_method1(intern!(py, "binary_probabilities"), (inner.num_qubits(),))?
+        } else {
+            counts.clone()
+        };
+        let counts = counts
+            .call_method0(intern!(py, "items"))?
+            .try_iter()?
+            .map(|item| item?.extract::<(String, f64)>())
+            .collect::<PyResult<Vec<_>>>()?;
+        Ok(inner.expectation_value_counts(
+            counts
+                .iter()
+                .map(|(outcome, count)| (outcome.as_str(), *count)),
+            basis.as_deref(),
+        )?)
+    }
+
     /// Express the observable in terms of a sparse list format.
     ///
     /// This can be seen as counter-operation of :meth:`.SparseObservable.from_sparse_list`, however
@@ -3489,6 +3722,143 @@ impl PySparseObservable {
         }
     }
 
+    /// Calculate the commutator of this observable with another.
+    ///
+    /// The commutator is $[A, B] = AB - BA$, where $A$ is ``self`` and $B$ is ``other``.  In terms
+    /// of :meth:`compose`, this is ``other.compose(self) - self.compose(other)``, but pairs of terms
+    /// that commute are skipped rather than having their two products calculated and cancelled.
+    /// Pairs of Pauli terms always either commute or anticommute, so for observables without
+    /// projectors, every pair of terms contributes at most one term to the output.
+    ///
+    /// The output is not simplified; use :meth:`simplify` to sum duplicate terms.
+    ///
+    /// Examples:
+    ///
+    ///     .. code-block:: python
+    ///
+    ///         >>> x = SparseObservable("X")
+    ///         >>> y = SparseObservable("Y")
+    ///         >>> x.commutator(y)
+    ///         <SparseObservable with 1 term on 1 qubit: (0+2j)(Z_0)>
+    ///         >>> x.commutator(x)
+    ///         <SparseObservable with 0 terms on 1 qubit: 0.0>
+    ///
+    /// Args:
+    ///     other: the observable $B$ in the commutator.  This must have the same number of qubits
+    ///         as ``self``.
+    ///
+    /// See also:
+    ///     :meth:`anticommutator`
+    ///         The anticommutator $AB + BA$.
+    ///
+    ///     :meth:`nested_commutators`
+    ///         Repeated commutators with the same left-hand side.
+    #[pyo3(signature = (other, /))]
+    fn commutator<'py>(
+        &self,
+        other: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PySparseObservable>> {
+        let py = other.py();
+        let Some(other) = coerce_to_observable(other)? else {
+            return Err(PyTypeError::new_err(format!(
+                "unknown type for commutator: {}",
+                other.get_type().repr()?
+            )));
+        };
+        let other = other.borrow();
+        let inner = self.inner.read().map_err(|_| InnerReadError)?;
+        let other_inner = other.inner.read().map_err(|_| InnerReadError)?;
+        inner.check_equal_qubits(&other_inner)?;
+        inner.commutator(&other_inner).into_pyobject(py)
+    }
+
+    /// Calculate the anticommutator of this observable with another.
+    ///
+    /// The anticommutator is $\{A, B\} = AB + BA$, where $A$ is ``self`` and $B$ is ``other``.
+    /// Pairs of terms that anticommute are skipped, in the same way that :meth:`commutator` skips
+    /// pairs of terms that commute.
+    ///
+    /// The output is not simplified; use :meth:`simplify` to sum duplicate terms.
+    ///
+    /// Examples:
+    ///
+    ///     .. code-block:: python
+    ///
+    ///         >>> x = SparseObservable("X")
+    ///         >>> x.anticommutator(x)
+    ///         <SparseObservable with 1 term on 1 qubit: (2+0j)()>
+    ///         >>> x.anticommutator(SparseObservable("Z"))
+    ///         <SparseObservable with 0 terms on 1 qubit: 0.0>
+    ///
+    /// Args:
+    ///     other: the observable $B$ in the anticommutator.  This must have the same number of
+    ///         qubits as ``self``.
+    #[pyo3(signature = (other, /))]
+    fn anticommutator<'py>(
+        &self,
+        other: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PySparseObservable>> {
+        let py = other.py();
+        let Some(other) = coerce_to_observable(other)? else {
+            return Err(PyTypeError::new_err(format!(
+                "unknown type for anticommutator: {}",
+                other.get_type().repr()?
+            )));
+        };
+        let other = other.borrow();
+        let inner = self.inner.read().map_err(|_| InnerReadError)?;
+        let other_inner = other.inner.read().map_err(|_| InnerReadError)?;
+        inner.check_equal_qubits(&other_inner)?;
+        inner.anticommutator(&other_inner).into_pyobject(py)
+    }
+
+    /// Calculate the nested commutators $[A, [A, \dots, [A, B]]]$ up to a given depth.
+    ///
+    /// Here $A$ is ``self`` and $B$ is ``other``.  These are the terms that appear in the
+    /// Baker-Campbell-Hausdorff expansion of $e^{A} B e^{-A}$, and in the construction of Krylov
+    /// subspaces.
+    ///
+    /// Each nesting level is simplified (without any tolerance) before being used in the next
+    /// commutator, so that repeated terms are not carried forwards.
+    ///
+    /// Examples:
+    ///
+    ///     .. code-block:: python
+    ///
+    ///         >>> x = SparseObservable("X")
+    ///         >>> z = SparseObservable("Z")
+    ///         >>> x.nested_commutators(z, 2)
+    ///         [<SparseObservable with 1 term on 1 qubit: (1+0j)(Z_0)>,
+    ///          <SparseObservable with 1 term on 1 qubit: (0-2j)(Y_0)>,
+    ///          <SparseObservable with 1 term on 1 qubit: (4+0j)(Z_0)>]
+    ///
+    /// Args:
+    ///     other: the observable $B$ at the core of the nested commutators.  This must have the
+    ///         same number of qubits as ``self``.
+    ///     order (int): the maximum number of nested commutators.
+    ///
+    /// Returns:
+    ///     list[SparseObservable]: a list of length ``order + 1``, where the entry at index ``k``
+    ///     has ``k`` nested commutators.  The first entry is a copy of ``other``.
+    #[pyo3(signature = (other, /, order))]
+    fn nested_commutators(&self, other: &Bound<PyAny>, order: usize) -> PyResult<Vec<Self>> {
+        let Some(other) = coerce_to_observable(other)? else {
+            return Err(PyTypeError::new_err(format!(
+                "unknown type for nested_commutators: {}",
+                other.get_type().repr()?
+            )));
+        };
+        let other = other.borrow();
+        let inner = self.inner.read().map_err(|_| InnerReadError)?;
+        let other_inner = other.inner.read().map_err(|_| InnerReadError)?;
+        inner.check_equal_qubits(&other_inner)?;
+        Ok(inner
+            .nested_commutators(&other_inner, order)
+            .into_iter()
+            .map(Self::from)
+            .collect())
+    }
+
     /// Apply a transpiler layout to this :class:`SparseObservable`.
     ///
     /// Typically you will have defined your observable in terms of the virtual qubits of the
@@ -3589,6 +3959,86 @@ impl PySparseObservable {
             ))
     }
 
+    /// Get a :class:`~.quantum_info.Pauli` object that represents a single measurement basis for
+    /// every term in this observable simultaneously.
+    ///
+    /// This combines the individual bases of :meth:`pauli_bases` into one, which is possible if the
+    /// terms are qubit-wise compatible: every pair of terms that act on the same qubit do so in
+    /// the same basis.  For example, the observable ``0XI + 1IX + IX+`` has the basis ``ZXX``.
+    /// This is always the case for the groups returned by :meth:`group_commuting` with
+    /// ``qubit_wise=True``.  An identity in the Pauli output does not require a concrete
+    /// measurement.
+    ///
+    /// Returns:
+    ///     :class:`~.quantum_info.Pauli`: the Pauli operator representing the necessary measurement
+    ///     basis.
+    ///
+    /// Raises:
+    ///     ValueError: if two terms act on the same qubit in different bases.
+    ///
+    /// See also:
+    ///     :meth:`SparseTerm.pauli_base`
+    ///         The same method for a single term.
+    fn pauli_base<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.read().map_err(|_| InnerReadError)?;
+        let base = inner.pauli_base().ok_or_else(|| {
+            PyValueError::new_err("the terms of this observable have no common measurement basis")
+        })?;
+        let mut x = vec![false; inner.num_qubits() as usize];
+        let mut z = vec![false; inner.num_qubits() as usize];
+        for (bit_term, index) in base.bit_terms().iter().zip(base.indices()) {
+            x[*index as usize] = bit_term.has_x_component();
+            z[*index as usize] = bit_term.has_z_component();
+        }
+        PAULI_TYPE
+            .get_bound(py)
+            .call1(((PyArray1::from_vec(py, z), PyArray1::from_vec(py, x)),))
+    }
+
+    /// Partition the observable into groups of terms that can be measured simultaneously.
+    ///
+    /// This builds a graph with a node for each term, and an edge between any two terms that
+    /// cannot be measured together, and then partitions the terms with a greedy colouring of the
+    /// graph.  The result is not guaranteed to use the minimum number of groups.
+    ///
+    /// With ``qubit_wise=True``, two terms are compatible if they act in the same Pauli basis on
+    /// every qubit they both act on, so each group can be measured using only single-qubit
+    /// rotations into the basis given by its :meth:`pauli_base`.  Otherwise, two terms are
+    /// compatible if they commute, though since a projector needs its qubit to be measured by
+    /// itself, terms can only act in different bases on qubits where they both have Pauli
+    /// operators.  If there are no projectors, this is the same partition as
+    /// :meth:`.SparsePauliOp.group_commuting`.
+    ///
+    /// Examples:
+    ///
+    ///     .. code-block:: python
+    ///
+    ///         >>> obs = SparseObservable.from_list([("XX", 1), ("YY", 2), ("IZ", 3), ("0Z", 4)])
+    ///         >>> obs.group_commuting()
+    ///         [<SparseObservable with 2 terms on 2 qubits: (1+0j)(X_1 X_0) + (2+0j)(Y_1 Y_0)>,
+    ///          <SparseObservable with 2 terms on 2 qubits: (3+0j)(Z_0) + (4+0j)(0_1 Z_0)>]
+    ///         >>> [group.pauli_base() for group in obs.group_commuting(qubit_wise=True)]
+    ///         [Pauli('XX'), Pauli('YY'), Pauli('ZZ')]
+    ///
+    /// Args:
+    ///     qubit_wise (bool): whether terms must be compatible on every qubit individually, rather
+    ///         than only needing to commute.
+    ///
+    /// Returns:
+    ///     list[SparseObservable]: the groups, whose sum is this observable.  Each term appears in
+    ///     exactly one group, and the terms in each group are in the same order as in this
+    ///     observable.
+    #[pyo3(signature = (qubit_wise=false))]
+    fn group_commuting(&self, qubit_wise: bool) -> PyResult<Vec<Self>> {
+        let inner = self.inner.read().map_err(|_| InnerReadError)?;
+        let parallel = qiskit_circuit::getenv_use_multiple_threads();
+        Ok(inner
+            .group_commuting(qubit_wise, parallel)
+            .into_iter()
+            .map(Self::from)
+            .collect())
+    }
+
     fn __len__(&self) -> PyResult<usize> {
         self.num_terms()
     }
@@ -4164,6 +4614,7 @@ pub fn sparse_observable(m: &Bound<PyModule>) -> PyResult<()> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::test::in_scoped_thread_pool;
 
     #[test]
     fn test_error_safe_add_dense_label() {
@@ -4176,4 +4627,380 @@ mod test {
         // `modified` should have been left in a valid state.
         assert_eq!(base, modified);
     }
+
+    fn example_observable() -> SparseObservable {
+        let mut obs = SparseObservable::zero(4);
+        for (label, coeff) in [
+            ("IXYZ", Complex64::new(1.5, -0.5)),
+            ("+-rl", Complex64::new(-0.25, 2.0)),
+            ("01IZ", Complex64::new(0.75, 0.75)),
+            ("r+1X", Complex64::new(0.0, 1.0)),
+            ("IIII", Complex64::new(-1.0, 0.0)),
+            ("-lY0", Complex64::new(0.5, 0.0)),
+            ("IXYZ", Complex64::new(-0.5, 0.5)),
+        ] {
+            obs.add_dense_label(label, coeff).unwrap();
+        }
+        obs
+    }
+
+    fn csr_to_dense(side: usize, (data, indices, indptr): CSRData<i64>) -> Vec<Complex64> {
+        let mut out = vec![Complex64::zero(); side * side];
+        for row in 0..side {
+            for i in indptr[row] as usize..indptr[row + 1] as usize {
+                out[row * side + indices[i] as usize] += data[i];
+            }
+        }
+        out
+    }
+
+    fn assert_matrices_close(left: &[Complex64], right: &[Complex64]) {
+        assert_eq!(left.len(), right.len());
+        for (i, (left, right)) in left.iter().zip(right).enumerate() {
+            assert!((left - right).norm() < 1e-12, "{left} != {right} at {i}");
+        }
+    }
+
+    #[test]
+    fn to_matrix_dense_single_bit_terms() {
+        let half = Complex64::new(0.5, 0.0);
+        let half_i = Complex64::new(0.0, 0.5);
+        let one = Complex64::new(1.0, 0.0);
+        let i = Complex64::new(0.0, 1.0);
+        let zero = Complex64::zero();
+        let cases = [
+            ("X", [zero, one, one, zero]),
+            ("Y", [zero, -i, i, zero]),
+            ("Z", [one, zero, zero, -one]),
+            ("+", [half, half, half, half]),
+            ("-", [half, -half, -half, half]),
+            ("r", [half, -half_i, half_i, half]),
+            ("l", [half, half_i, -half_i, half]),
+            ("0", [one, zero, zero, zero]),
+            ("1", [zero, zero, zero, one]),
+        ];
+        for (label, expected) in cases {
+            let mut obs = SparseObservable::zero(1);
+            obs.add_dense_label(label, one).unwrap();
+            assert_eq!(obs.to_matrix_dense(false).unwrap(), expected, "{label}");
+        }
+    }
+
+    #[test]
+    fn to_matrix_dense_matches_paulis() {
+        let obs = example_observable();
+        let direct = obs.to_matrix_dense(false).unwrap();
+        let paulis = obs.as_paulis().to_matrix_dense(false).unwrap();
+        assert_matrices_close(&direct, &paulis);
+    }
+
+    #[test]
+    fn to_matrix_sparse_matches_dense() {
+        let obs = example_observable();
+        let dense = obs.to_matrix_dense(false).unwrap();
+        let sparse = obs.to_matrix_sparse::<i64>(false).unwrap();
+        assert!(sparse.2.len() == 17 && sparse.1.len() as u64 <= obs.max_matrix_nnz());
+        assert_matrices_close(&csr_to_dense(16, sparse), &dense);
+    }
+
+    #[test]
+    fn to_matrix_zero_observable() {
+        let obs = SparseObservable::zero(2);
+        assert_eq!(
+            obs.to_matrix_dense(false).unwrap(),
+            vec![Complex64::zero(); 16]
+        );
+        let (data, indices, indptr) = obs.to_matrix_sparse::<i32>(false).unwrap();
+        assert!(data.is_empty() && indices.is_empty());
+        assert_eq!(indptr, vec![0; 5]);
+    }
+
+    #[test]
+    fn to_matrix_too_many_qubits() {
+        let obs = SparseObservable::identity(100);
+        assert!(matches!(
+            obs.to_matrix_dense(false),
+            Err(MatrixError::TooManyQubits { .. })
+        ));
+        assert!(matches!(
+            obs.to_matrix_sparse::<i64>(false),
+            Err(MatrixError::TooManyQubits { .. })
+        ));
+    }
+
+    fn example_state(num_qubits: u32) -> Vec<Complex64> {
+        (0..1usize << num_qubits)
+            .map(|i| Complex64::new((i as f64 * 0.7).sin(), (i as f64 * 1.3).cos()))
+            .collect()
+    }
+
+    #[test]
+    fn expectation_value_statevector_matches_matrix() {
+        let obs = example_observable();
+        let state = example_state(4);
+        let matrix = obs.to_matrix_dense(false).unwrap();
+        let expected = (0..16)
+            .flat_map(|row| (0..16).map(move |col| (row, col)))
+            .map(|(row, col)| state[row].conj() * matrix[row * 16 + col] * state[col])
+            .sum::<Complex64>();
+        let actual = obs.expectation_value_statevector(&state, false).unwrap();
+        assert!((actual - expected).norm() < 1e-12, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn expectation_value_density_matrix_matches_matrix() {
+        let obs = example_observable();
+        let state = example_state(4);
+        // A mixed state: an outer product of a non-normalised vector, plus a diagonal.
+        let rho = (0..16)
+            .flat_map(|row| (0..16).map(move |col| (row, col)))
+            .map(|(row, col)| {
+                let diagonal = if row == col { row as f64 } else { 0.0 };
+                state[row] * state[col].conj() + diagonal
+            })
+            .collect::<Vec<_>>();
+        let matrix = obs.to_matrix_dense(false).unwrap();
+        let expected = (0..16)
+            .flat_map(|row| (0..16).map(move |col| (row, col)))
+            .map(|(row, col)| matrix[row * 16 + col] * rho[col * 16 + row])
+            .sum::<Complex64>();
+        let actual = obs.expectation_value_density_matrix(&rho, false).unwrap();
+        assert!((actual - expected).norm() < 1e-12, "{actual} != {expected}");
+        assert!(matches!(
+            obs.expectation_value_density_matrix(&state, false),
+            Err(ExpectationError::StateSize { .. })
+        ));
+    }
+
+    #[test]
+    fn expectation_value_counts_projectors() {
+        let mut obs = SparseObservable::zero(2);
+        obs.add_dense_label("+0", Complex64::new(1.0, 0.0)).unwrap();
+        obs.add_dense_label("XZ", Complex64::new(0.5, 0.0)).unwrap();
+        obs.add_dense_label("I1", Complex64::new(0.0, 2.0)).unwrap();
+        let counts = [("00", 60.0), ("0 1", 20.0), ("0x2", 20.0)];
+        let basis = [BitTerm::Z, BitTerm::X];
+        let actual = obs.expectation_value_counts(counts, Some(&basis)).unwrap();
+        assert_eq!(actual, Complex64::new(0.6 + 0.1, 2.0 * 0.2));
+        assert!(matches!(
+            obs.expectation_value_counts(counts, None),
+            Err(ExpectationError::IncompatibleBasis { qubit: 1 })
+        ));
+        assert!(matches!(
+            obs.expectation_value_counts([("010", 1.0)], Some(&basis)),
+            Err(ExpectationError::InvalidBitstring { .. })
+        ));
+        assert!(matches!(
+            obs.expectation_value_counts([], Some(&basis)),
+            Err(ExpectationError::NoShots)
+        ));
+    }
+
+    #[test]
+    fn expectation_value_threaded_and_serial_equal() {
+        let obs = SparseObservable::identity(20)
+            + &SparseObservable::new(
+                20,
+                vec![Complex64::new(0.5, -1.0)],
+                vec![BitTerm::Plus, BitTerm::One, BitTerm::Y],
+                vec![0, 7, 19],
+                vec![0, 3],
+            )
+            .unwrap();
+        let state = example_state(20);
+        let parallel =
+            in_scoped_thread_pool(|| obs.expectation_value_statevector(&state, true).unwrap())
+                .unwrap();
+        let serial = obs.expectation_value_statevector(&state, false).unwrap();
+        assert!((parallel - serial).norm() < 1e-8 * serial.norm());
+    }
+
+    #[test]
+    fn to_matrix_threaded_and_serial_equal() {
+        let obs = example_observable();
+        let parallel = in_scoped_thread_pool(|| obs.to_matrix_dense(true).unwrap()).unwrap();
+        assert_eq!(parallel, obs.to_matrix_dense(false).unwrap());
+        let parallel =
+            in_scoped_thread_pool(|| obs.to_matrix_sparse::<i32>(true).unwrap()).unwrap();
+        assert_eq!(parallel, obs.to_matrix_sparse::<i32>(false).unwrap());
+    }
+
+    fn observable_from_labels(labels: &[&str]) -> SparseObservable {
+        let mut obs = SparseObservable::zero(labels[0].len() as u32);
+        for (i, label) in labels.iter().enumerate() {
+            obs.add_dense_label(label, Complex64::new((i + 1) as f64, 0.0))
+                .unwrap();
+        }
+        obs
+    }
+
+    #[test]
+    fn group_commuting_paulis() {
+        let obs = observable_from_labels(&["XX", "YY", "IZ", "ZZ"]);
+        assert_eq!(
+            obs.commuting_groups(false, false),
+            vec![vec![0, 1], vec![2, 3]]
+        );
+        assert_eq!(
+            obs.commuting_groups(true, false),
+            vec![vec![0], vec![1], vec![2, 3]]
+        );
+    }
+
+    #[test]
+    fn group_commuting_projectors() {
+        // `ZX` and `XZ` commute, but `0I` needs qubit 1 measured in Z by itself, which is not
+        // compatible with `XZ`, even though `ZI` would be.
+        let obs = observable_from_labels(&["ZX", "XZ", "0I", "XI"]);
+        let mut groups = obs.commuting_groups(false, false);
+        groups.sort();
+        assert_eq!(groups, vec![vec![0, 2], vec![1, 3]]);
+        let mut groups = obs.commuting_groups(true, false);
+        groups.sort();
+        assert_eq!(groups, vec![vec![0, 2], vec![1, 3]]);
+
+        let obs = observable_from_labels(&["0Z", "ZZ", "1I", "+X"]);
+        assert_eq!(
+            obs.commuting_groups(false, false),
+            vec![vec![0, 1, 2], vec![3]]
+        );
+    }
+
+    #[test]
+    fn group_commuting_sums_to_input() {
+        let obs = example_observable();
+        for qubit_wise in [true, false] {
+            let groups = obs.group_commuting(qubit_wise, false);
+            assert_eq!(
+                groups.iter().map(|group| group.num_terms()).sum::<usize>(),
+                obs.num_terms()
+            );
+            let sum = groups
+                .iter()
+                .fold(SparseObservable::zero(obs.num_qubits()), |acc, group| {
+                    acc + group
+                });
+            assert_matrices_close(
+                &sum.to_matrix_dense(false).unwrap(),
+                &obs.to_matrix_dense(false).unwrap(),
+            );
+        }
+    }
+
+    #[test]
+    fn pauli_base_combines_terms() {
+        let obs = observable_from_labels(&["0XI", "1IX", "IX+"]);
+        let base = obs.pauli_base().unwrap();
+        assert_eq!(base.indices(), &[0, 1, 2]);
+        assert_eq!(base.bit_terms(), &[BitTerm::X, BitTerm::X, BitTerm::Z]);
+        assert_eq!(base.coeff(), Complex64::new(1.0, 0.0));
+        assert!(observable_from_labels(&["XI", "ZI"]).pauli_base().is_none());
+        assert!(SparseObservable::identity(3)
+            .pauli_base()
+            .unwrap()
+            .indices()
+            .is_empty());
+    }
+
+    #[test]
+    fn group_commuting_threaded_and_serial_equal() {
+        let alphabet = ["I", "X", "Y", "Z", "+", "-", "r", "l", "0", "1"];
+        let labels = alphabet
+            .iter()
+            .cartesian_product(alphabet.iter())
+            .cartesian_product(alphabet.iter())
+            .map(|((a, b), c)| format!("{a}{b}{c}"))
+            .collect::<Vec<_>>();
+        let obs = observable_from_labels(&labels.iter().map(|l| l.as_str()).collect::<Vec<_>>());
+        for qubit_wise in [true, false] {
+            let parallel =
+                in_scoped_thread_pool(|| obs.commuting_groups(qubit_wise, true)).unwrap();
+            assert_eq!(parallel, obs.commuting_groups(qubit_wise, false));
+        }
+        for group in obs.group_commuting(true, true) {
+            assert!(group.pauli_base().is_some());
+        }
+    }
+
+    /// The matrices of `A @ B` and `B @ A`, where `A` is `left` and `B` is `right`.
+    fn products_dense(
+        left: &SparseObservable,
+        right: &SparseObservable,
+    ) -> (Vec<Complex64>, Vec<Complex64>) {
+        (
+            right.compose(left).to_matrix_dense(false).unwrap(),
+            left.compose(right).to_matrix_dense(false).unwrap(),
+        )
+    }
+
+    #[test]
+    fn commutator_matches_products() {
+        let left = example_observable();
+        let right = observable_from_labels(&["XYZ+", "I0lZ", "YYII", "-r1X"]);
+        let (forwards, backwards) = products_dense(&left, &right);
+        let expected = forwards
+            .iter()
+            .zip(&backwards)
+            .map(|(f, b)| f - b)
+            .collect::<Vec<_>>();
+        assert_matrices_close(
+            &left.commutator(&right).to_matrix_dense(false).unwrap(),
+            &expected,
+        );
+        let expected = forwards
+            .iter()
+            .zip(&backwards)
+            .map(|(f, b)| f + b)
+            .collect::<Vec<_>>();
+        assert_matrices_close(
+            &left.anticommutator(&right).to_matrix_dense(false).unwrap(),
+            &expected,
+        );
+    }
+
+    #[test]
+    fn commutator_skips_cancelling_terms() {
+        let left = observable_from_labels(&["XX", "ZI"]);
+        let right = observable_from_labels(&["YY", "0Z", "IX"]);
+        // Only `(XX, 0Z)` and `(ZI, YY)` fail to commute.  `X @ 0` is a sum of two terms, and the
+        // projector means both orders of product are needed, so that pair produces four terms.
+        assert_eq!(left.commutator(&right).num_terms(), 5);
+        // Only `(ZI, YY)` anticommutes, and `(XX, 0Z)` still needs both products.
+        assert_eq!(left.anticommutator(&right).num_terms(), 8);
+        assert_eq!(
+            SparseObservable::identity(2).commutator(&right).num_terms(),
+            0
+        );
+        assert_eq!(
+            observable_from_labels(&["XI"])
+                .anticommutator(&observable_from_labels(&["ZI"]))
+                .num_terms(),
+            0
+        );
+    }
+
+    #[test]
+    fn nested_commutators_recurse() {
+        let left = observable_from_labels(&["XI", "ZZ"]);
+        let right = observable_from_labels(&["Z+", "IY"]);
+        let nested = left.nested_commutators(&right, 3);
+        assert_eq!(nested.len(), 4);
+        assert_eq!(nested[0], right);
+        for k in 1..nested.len() {
+            assert_matrices_close(
+                &nested[k].to_matrix_dense(false).unwrap(),
+                &left
+                    .commutator(&nested[k - 1])
+                    .to_matrix_dense(false)
+                    .unwrap(),
+            );
+        }
+        // For Paulis, `[P, [P, Q]] = 4 Q` if `P` and `Q` anticommute.
+        let x = observable_from_labels(&["X"]);
+        let z = observable_from_labels(&["Z"]);
+        let nested = x.nested_commutators(&z, 2);
+        assert_eq!(nested[1].num_terms(), 1);
+        assert_eq!(nested[2], &z * Complex64::new(4.0, 0.0));
+        assert_eq!(x.nested_commutators(&x, 1)[1].num_terms(), 0);
+    }
 }
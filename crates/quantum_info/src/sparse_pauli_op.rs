@@ -1059,7 +1059,7 @@ pub fn to_matrix_sparse(
 
 /// Copy several slices into a single flat vec, in parallel.  Allocates a temporary `Vec<usize>` of
 /// the same length as the input slice to track the chunking.
-fn copy_flat_parallel<T, U>(slices: &[U]) -> Vec<T>
+pub(crate) fn copy_flat_parallel<T, U>(slices: &[U]) -> Vec<T>
 where
     T: Copy + Send + Sync,
     U: AsRef<[T]> + Sync,
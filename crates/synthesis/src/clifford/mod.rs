@@ -13,6 +13,7 @@
 mod bm_synthesis;
 pub(crate) mod greedy_synthesis;
 mod random_clifford;
+pub mod stabilizer_simulator;
 pub(crate) mod utils;
 
 use crate::clifford::bm_synthesis::synth_clifford_bm_inner;
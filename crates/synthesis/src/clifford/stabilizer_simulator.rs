@@ -0,0 +1,408 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2025
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+use hashbrown::HashMap;
+use ndarray::Array2;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_distr::{Binomial, Distribution};
+use rand_pcg::Pcg64Mcg;
+use thiserror::Error;
+
+use qiskit_circuit::circuit_data::CircuitData;
+use qiskit_circuit::operations::{
+    Operation, OperationRef, Param, StandardGate, StandardInstruction,
+};
+use qiskit_circuit::{Clbit, Qubit};
+
+use crate::clifford::utils::Clifford;
+
+/// Errors that can occur while simulating a circuit with the stabilizer simulator.
+#[derive(Error, Debug)]
+pub enum StabilizerSimulatorError {
+    /// The circuit contains an operation that is not a supported Clifford operation.
+    #[error("Operation '{name}' is not supported by the stabilizer simulator: {reason}")]
+    UnsupportedOperation { name: String, reason: String },
+}
+
+/// The result of a single execution of a circuit on the stabilizer simulator.
+#[derive(Clone)]
+pub struct StabilizerResult {
+    /// The stabilizer tableau of the final state of the qubits, with dimensions
+    /// `(2 * num_qubits) x (2 * num_qubits + 1)`, in the same layout as Qiskit's `Clifford`.
+    pub tableau: Array2<bool>,
+    /// The final state of the clbits, indexed by clbit.
+    pub clbits: Vec<bool>,
+}
+
+/// A single instruction of the circuit, as seen by the stabilizer simulator.
+enum Step<'a> {
+    Gate(StandardGate, &'a [Param], &'a [Qubit]),
+    Measure(Qubit, Clbit),
+    Reset(Qubit),
+}
+
+/// Convert the circuit to a sequence of simulator steps.  Directives and delays are dropped.
+fn to_steps(circuit: &CircuitData) -> Result<Vec<Step<'_>>, StabilizerSimulatorError> {
+    let mut steps = Vec::with_capacity(circuit.data().len());
+    for inst in circuit.iter() {
+        let qubits = circuit.get_qargs(inst.qubits);
+        match inst.op.view() {
+            OperationRef::StandardGate(gate) => {
+                steps.push(Step::Gate(gate, inst.params_view(), qubits))
+            }
+            OperationRef::StandardInstruction(StandardInstruction::Measure) => {
+                steps.push(Step::Measure(qubits[0], circuit.get_cargs(inst.clbits)[0]))
+            }
+            OperationRef::StandardInstruction(StandardInstruction::Reset) => {
+                steps.push(Step::Reset(qubits[0]))
+            }
+            OperationRef::StandardInstruction(
+                StandardInstruction::Barrier(_) | StandardInstruction::Delay(_),
+            ) => (),
+            op => {
+                return Err(StabilizerSimulatorError::UnsupportedOperation {
+                    name: op.name().to_string(),
+                    reason: "only standard gates, measurements and resets are supported"
+                        .to_string(),
+                })
+            }
+        }
+    }
+    Ok(steps)
+}
+
+/// A group of shots that have had identical measurement outcomes so far, and so share a state.
+#[derive(Clone)]
+struct Branch {
+    clifford: Clifford,
+    clbits: Vec<bool>,
+    shots: u64,
+}
+
+/// Run the steps on the branch, splitting it in two whenever a measurement (or the measurement
+/// implied by a reset) has a random outcome and the branch's shots are divided between both
+/// outcomes.  Each branch that reaches the end of the circuit is passed to `finish`.
+///
+/// Gates are applied once per branch rather than once per shot, so in the common case of terminal
+/// measurements each gate is only simulated once.  The branches are explored depth-first: when a
+/// branch splits, the `1` outcome is set aside until the `0` outcome has run to the end of the
+/// circuit.  At most one branch is set aside per random measurement on the current path, and each
+/// holds at least one shot, so the number of live tableaux is bounded by both the number of random
+/// measurements and the number of shots, rather than growing with the number of distinct
+/// measurement histories.
+fn run_branches(
+    steps: &[Step],
+    initial: Branch,
+    rng: &mut Pcg64Mcg,
+    mut finish: impl FnMut(Branch),
+) -> Result<(), StabilizerSimulatorError> {
+    // The branches that have been set aside, with the index of the next step to run on each.
+    let mut pending = vec![(0, initial)];
+    while let Some((start, mut branch)) = pending.pop() {
+        for (index, step) in steps.iter().enumerate().skip(start) {
+            let (qubit, clbit) = match step {
+                Step::Gate(gate, params, qubits) => {
+                    branch
+                        .clifford
+                        .append_standard_gate(*gate, params, qubits)
+                        .map_err(|reason| StabilizerSimulatorError::UnsupportedOperation {
+                            name: gate.name().to_string(),
+                            reason,
+                        })?;
+                    continue;
+                }
+                Step::Measure(qubit, clbit) => (qubit.index(), Some(clbit.index())),
+                Step::Reset(qubit) => (qubit.index(), None),
+            };
+            let ones = if branch.clifford.is_measurement_deterministic(qubit) {
+                0
+            } else {
+                Binomial::new(branch.shots, 0.5)
+                    .expect("0.5 is a valid probability")
+                    .sample(rng)
+            };
+            let outcome = if ones == 0 || ones == branch.shots {
+                ones > 0
+            } else {
+                let mut other = branch.clone();
+                other.shots = ones;
+                branch.shots -= ones;
+                apply_collapse(&mut other, qubit, clbit, true);
+                pending.push((index + 1, other));
+                false
+            };
+            apply_collapse(&mut branch, qubit, clbit, outcome);
+        }
+        finish(branch);
+    }
+    Ok(())
+}
+
+/// Collapse `qubit` of the branch, choosing `outcome` if the measurement is random, and either
+/// store the outcome in `clbit` or, if there is no clbit, reset the qubit.
+fn apply_collapse(branch: &mut Branch, qubit: usize, clbit: Option<usize>, outcome: bool) {
+    match clbit {
+        Some(clbit) => branch.clbits[clbit] = branch.clifford.measure(qubit, || outcome),
+        None => branch.clifford.reset(qubit, || outcome),
+    }
+}
+
+fn make_rng(seed: Option<u64>) -> Pcg64Mcg {
+    match seed {
+        Some(seed) => Pcg64Mcg::seed_from_u64(seed),
+        None => Pcg64Mcg::from_os_rng(),
+    }
+}
+
+/// Run the circuit for `shots` shots, starting from the all-zeros state, and pass each final
+/// branch to `finish`.
+fn run_circuit(
+    circuit: &CircuitData,
+    shots: u64,
+    rng: &mut Pcg64Mcg,
+    finish: impl FnMut(Branch),
+) -> Result<(), StabilizerSimulatorError> {
+    let steps = to_steps(circuit)?;
+    if shots == 0 {
+        return Ok(());
+    }
+    let initial = Branch {
+        clifford: Clifford::identity(circuit.num_qubits()),
+        clbits: vec![false; circuit.num_clbits()],
+        shots,
+    };
+    run_branches(&steps, initial, rng, finish)
+}
+
+/// Run a Clifford circuit once on the stabilizer simulator, starting from the all-zeros state.
+///
+/// The circuit may contain Clifford standard gates (including rotations by multiples of
+/// ``pi / 2``), measurements, resets, barriers and delays.  Measurements with random outcomes
+/// draw from an RNG seeded by `seed` if given.
+pub fn simulate_stabilizer(
+    circuit: &CircuitData,
+    seed: Option<u64>,
+) -> Result<StabilizerResult, StabilizerSimulatorError> {
+    let mut result = None;
+    run_circuit(circuit, 1, &mut make_rng(seed), |branch| {
+        result = Some(branch)
+    })?;
+    let branch = result.expect("a single shot always has exactly one branch");
+    Ok(StabilizerResult {
+        tableau: branch.clifford.tableau,
+        clbits: branch.clbits,
+    })
+}
+
+/// Sample the final clbit values of a Clifford circuit over a number of shots.
+///
+/// Each entry of the returned vector is the clbit values of a single shot, indexed by clbit.
+/// All shots share a single tableau until a measurement or reset has a random outcome, at which
+/// point the shots are divided between the two outcomes, so the gates are simulated once per
+/// distinct measurement history rather than once per shot.  The histories are simulated one after
+/// another, so only the tableaux along the current history are held in memory.  The shots are
+/// returned in a random order.
+pub fn sample_stabilizer_memory(
+    circuit: &CircuitData,
+    shots: usize,
+    seed: Option<u64>,
+) -> Result<Vec<Vec<bool>>, StabilizerSimulatorError> {
+    let mut rng = make_rng(seed);
+    let mut memory = Vec::with_capacity(shots);
+    run_circuit(circuit, shots as u64, &mut rng, |branch| {
+        memory.extend(::std::iter::repeat(branch.clbits).take(branch.shots as usize));
+    })?;
+    memory.shuffle(&mut rng);
+    Ok(memory)
+}
+
+/// Sample the counts of each clbit outcome of a Clifford circuit over a number of shots.
+///
+/// The keys are bitstrings in the same format as Qiskit's ``Counts``, with clbit 0 as the
+/// right-most character.
+pub fn sample_stabilizer_counts(
+    circuit: &CircuitData,
+    shots: usize,
+    seed: Option<u64>,
+) -> Result<HashMap<String, usize>, StabilizerSimulatorError> {
+    let mut counts = HashMap::new();
+    for clbits in sample_stabilizer_memory(circuit, shots, seed)? {
+        let key = clbits
+            .iter()
+            .rev()
+            .map(|bit| if *bit { '1' } else { '0' })
+            .collect::<String>();
+        *counts.entry(key).or_insert(0) += 1;
+    }
+    Ok(counts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qiskit_circuit::packed_instruction::PackedOperation;
+    use std::f64::consts::FRAC_PI_2;
+
+    fn measure_all(circuit: &mut CircuitData) {
+        for i in 0..circuit.num_qubits() as u32 {
+            circuit.push_packed_operation(
+                PackedOperation::from_standard_instruction(StandardInstruction::Measure),
+                &[],
+                &[Qubit(i)],
+                &[Clbit(i)],
+            );
+        }
+    }
+
+    #[test]
+    fn test_ghz_counts() {
+        let num_qubits = 100;
+        let mut circuit =
+            CircuitData::with_capacity(num_qubits, num_qubits, 0, Param::Float(0.)).unwrap();
+        circuit.push_standard_gate(StandardGate::H, &[], &[Qubit(0)]);
+        for i in 1..num_qubits {
+            circuit.push_standard_gate(StandardGate::CX, &[], &[Qubit(i - 1), Qubit(i)]);
+        }
+        measure_all(&mut circuit);
+        let counts = sample_stabilizer_counts(&circuit, 20, Some(7)).unwrap();
+        let zeros = "0".repeat(num_qubits as usize);
+        let ones = "1".repeat(num_qubits as usize);
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts[&zeros] + counts[&ones], 20);
+    }
+
+    #[test]
+    fn test_branches_only_at_random_measurements() {
+        // Only the first measurement of a GHZ state is random, so however many shots there are,
+        // they are split between exactly two tableaux.
+        let num_qubits = 50;
+        let mut circuit =
+            CircuitData::with_capacity(num_qubits, num_qubits, 0, Param::Float(0.)).unwrap();
+        circuit.push_standard_gate(StandardGate::H, &[], &[Qubit(0)]);
+        for i in 1..num_qubits {
+            circuit.push_standard_gate(StandardGate::CX, &[], &[Qubit(i - 1), Qubit(i)]);
+        }
+        measure_all(&mut circuit);
+        let mut branches = Vec::new();
+        run_circuit(&circuit, 10_000, &mut make_rng(Some(11)), |b| {
+            branches.push(b)
+        })
+        .unwrap();
+        assert_eq!(branches.len(), 2);
+        assert_eq!(branches.iter().map(|b| b.shots).sum::<u64>(), 10_000);
+        assert!(branches
+            .iter()
+            .all(|b| b.clbits.iter().all(|bit| *bit == b.clbits[0])));
+
+        // Independent random measurements branch up to the number of shots.
+        let mut circuit = CircuitData::with_capacity(8, 8, 0, Param::Float(0.)).unwrap();
+        for i in 0..8 {
+            circuit.push_standard_gate(StandardGate::H, &[], &[Qubit(i)]);
+        }
+        measure_all(&mut circuit);
+        let mut branches = Vec::new();
+        run_circuit(&circuit, 5, &mut make_rng(Some(11)), |b| branches.push(b)).unwrap();
+        assert!(branches.len() <= 5);
+        assert_eq!(branches.iter().map(|b| b.shots).sum::<u64>(), 5);
+    }
+
+    #[test]
+    fn test_repeated_mid_circuit_measurements() {
+        // Repeatedly measuring a qubit in superposition has a random outcome every time, so there
+        // are far more measurement histories than shots.  Each shot must still be returned once,
+        // and the second qubit (a copy of each outcome) must agree with the first.
+        let rounds = 200;
+        let mut circuit = CircuitData::with_capacity(2, 2 * rounds, 0, Param::Float(0.)).unwrap();
+        for round in 0..rounds {
+            circuit.push_packed_operation(
+                PackedOperation::from_standard_instruction(StandardInstruction::Reset),
+                &[],
+                &[Qubit(1)],
+                &[],
+            );
+            circuit.push_standard_gate(StandardGate::H, &[], &[Qubit(0)]);
+            circuit.push_standard_gate(StandardGate::CX, &[], &[Qubit(0), Qubit(1)]);
+            for qubit in 0..2 {
+                circuit.push_packed_operation(
+                    PackedOperation::from_standard_instruction(StandardInstruction::Measure),
+                    &[],
+                    &[Qubit(qubit)],
+                    &[Clbit(2 * round + qubit)],
+                );
+            }
+        }
+        let memory = sample_stabilizer_memory(&circuit, 1000, Some(5)).unwrap();
+        assert_eq!(memory.len(), 1000);
+        assert!(memory
+            .iter()
+            .all(|shot| shot.chunks(2).all(|pair| pair[0] == pair[1])));
+    }
+
+    #[test]
+    fn test_deterministic_outcomes() {
+        // X, then Y, then RX(pi) and H-S-S-H (= X) leave the qubits in |1>, |1>, |1> and |1>.
+        let mut circuit = CircuitData::with_capacity(4, 4, 0, Param::Float(0.)).unwrap();
+        circuit.push_standard_gate(StandardGate::X, &[], &[Qubit(0)]);
+        circuit.push_standard_gate(StandardGate::Y, &[], &[Qubit(1)]);
+        circuit.push_standard_gate(
+            StandardGate::RX,
+            &[Param::Float(2. * FRAC_PI_2)],
+            &[Qubit(2)],
+        );
+        for gate in [
+            StandardGate::H,
+            StandardGate::S,
+            StandardGate::S,
+            StandardGate::H,
+        ] {
+            circuit.push_standard_gate(gate, &[], &[Qubit(3)]);
+        }
+        measure_all(&mut circuit);
+        let counts = sample_stabilizer_counts(&circuit, 10, None).unwrap();
+        assert_eq!(counts["1111"], 10);
+    }
+
+    #[test]
+    fn test_mid_circuit_measure_and_reset() {
+        // Measure a Bell pair mid-circuit, then reset qubit 0 and measure both again.  The
+        // second measurement of qubit 1 must agree with the first.
+        let mut circuit = CircuitData::with_capacity(2, 4, 0, Param::Float(0.)).unwrap();
+        circuit.push_standard_gate(StandardGate::H, &[], &[Qubit(0)]);
+        circuit.push_standard_gate(StandardGate::CX, &[], &[Qubit(0), Qubit(1)]);
+        measure_all(&mut circuit);
+        circuit.push_packed_operation(
+            PackedOperation::from_standard_instruction(StandardInstruction::Reset),
+            &[],
+            &[Qubit(0)],
+            &[],
+        );
+        for (qubit, clbit) in [(0, 2), (1, 3)] {
+            circuit.push_packed_operation(
+                PackedOperation::from_standard_instruction(StandardInstruction::Measure),
+                &[],
+                &[Qubit(qubit)],
+                &[Clbit(clbit)],
+            );
+        }
+        let counts = sample_stabilizer_counts(&circuit, 100, Some(3)).unwrap();
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts["0000"] + counts["1011"], 100);
+    }
+
+    #[test]
+    fn test_non_clifford_error() {
+        let mut circuit = CircuitData::with_capacity(1, 0, 0, Param::Float(0.)).unwrap();
+        circuit.push_standard_gate(StandardGate::T, &[], &[Qubit(0)]);
+        assert!(simulate_stabilizer(&circuit, None).is_err());
+    }
+}
@@ -11,10 +11,20 @@
 // that they have been altered from the originals.
 
 use crate::linear::utils::calc_inverse_matrix_inner;
-use ndarray::{azip, s, Array1, Array2, ArrayView2};
+use ndarray::{azip, s, Array1, Array2, ArrayView1, ArrayView2, ArrayViewMut1, Axis};
+use qiskit_circuit::getenv_use_multiple_threads;
 use qiskit_circuit::operations::{Param, StandardGate};
 use qiskit_circuit::Qubit;
+use rayon::prelude::*;
 use smallvec::{smallvec, SmallVec};
+use std::f64::consts::FRAC_PI_2;
+
+/// The absolute tolerance for a rotation angle to be considered a multiple of ``pi / 2``.
+const QUARTER_TURN_TOLERANCE: f64 = 1e-10;
+
+/// The number of qubits above which the rows of the tableau are updated in parallel during
+/// measurements.
+const PARALLEL_THRESHOLD: usize = 128;
 
 /// Symplectic matrix.
 /// Currently this class is internal to the synthesis library.
@@ -26,9 +36,10 @@ pub struct SymplecticMatrix {
 }
 
 /// Clifford.
-/// Currently this class is internal to the synthesis library and
-/// has a very different functionality from Qiskit's python-based
-/// Clifford class.
+/// This class has a very different functionality from Qiskit's python-based
+/// Clifford class. Its tableau is also the stabilizer tableau of the state
+/// obtained by applying the Clifford to ``|0...0>``, which is how it is used
+/// by the stabilizer simulator.
 #[derive(Clone)]
 pub struct Clifford {
     /// Number of qubits.
@@ -124,6 +135,44 @@ impl SymplecticMatrix {
 }
 
 impl Clifford {
+    /// Creates the identity Clifford on the given number of qubits.  As a stabilizer tableau,
+    /// this is the state ``|0...0>``.
+    pub fn identity(num_qubits: usize) -> Clifford {
+        Clifford {
+            num_qubits,
+            tableau: Array2::from_shape_fn((2 * num_qubits, 2 * num_qubits + 1), |(i, j)| i == j),
+        }
+    }
+
+    /// Modifies the tableau in-place by appending X-gate
+    pub fn append_x(&mut self, qubit: usize) {
+        let (z, mut p) = self
+            .tableau
+            .multi_slice_mut((s![.., self.num_qubits + qubit], s![.., 2 * self.num_qubits]));
+
+        azip!((p in &mut p, &z in &z)  *p ^= z);
+    }
+
+    /// Modifies the tableau in-place by appending Y-gate
+    pub fn append_y(&mut self, qubit: usize) {
+        let (x, z, mut p) = self.tableau.multi_slice_mut((
+            s![.., qubit],
+            s![.., self.num_qubits + qubit],
+            s![.., 2 * self.num_qubits],
+        ));
+
+        azip!((p in &mut p, &x in &x, &z in &z)  *p ^= x ^ z);
+    }
+
+    /// Modifies the tableau in-place by appending Z-gate
+    pub fn append_z(&mut self, qubit: usize) {
+        let (x, mut p) = self
+            .tableau
+            .multi_slice_mut((s![.., qubit], s![.., 2 * self.num_qubits]));
+
+        azip!((p in &mut p, &x in &x)  *p ^= x);
+    }
+
     /// Modifies the tableau in-place by appending S-gate
     pub fn append_s(&mut self, qubit: usize) {
         let (x, mut z, mut p) = self.tableau.multi_slice_mut((
@@ -137,7 +186,6 @@ impl Clifford {
     }
 
     /// Modifies the tableau in-place by appending Sdg-gate
-    #[allow(dead_code)]
     pub fn append_sdg(&mut self, qubit: usize) {
         let (x, mut z, mut p) = self.tableau.multi_slice_mut((
             s![.., qubit],
@@ -211,6 +259,20 @@ impl Clifford {
         azip!((z0 in &mut z0, &z1 in &z1) *z0 ^= z1);
     }
 
+    /// Modifies the tableau in-place by appending CZ-gate
+    pub fn append_cz(&mut self, qubit0: usize, qubit1: usize) {
+        self.append_h(qubit1);
+        self.append_cx(qubit0, qubit1);
+        self.append_h(qubit1);
+    }
+
+    /// Modifies the tableau in-place by appending CY-gate
+    pub fn append_cy(&mut self, qubit0: usize, qubit1: usize) {
+        self.append_sdg(qubit1);
+        self.append_cx(qubit0, qubit1);
+        self.append_s(qubit1);
+    }
+
     /// Modifies the tableau in-place by appending W-gate.
     /// This is equivalent to an Sdg gate followed by an H gate.
     pub fn append_v(&mut self, qubit: usize) {
@@ -231,6 +293,95 @@ impl Clifford {
         azip!((x in &mut x, z in &mut z)  (*x, *z) = (*z, *x ^ *z));
     }
 
+    /// Modifies the tableau in-place by appending a Z-rotation by ``quarter_turns`` multiples
+    /// of ``pi / 2``, up to a global phase.
+    fn append_quarter_turns_z(&mut self, qubit: usize, quarter_turns: u8) {
+        match quarter_turns % 4 {
+            1 => self.append_s(qubit),
+            2 => self.append_z(qubit),
+            3 => self.append_sdg(qubit),
+            _ => (),
+        }
+    }
+
+    /// Modifies the tableau in-place by appending a Clifford standard gate.  Returns an error if
+    /// the gate is not Clifford, including rotation gates whose angles are not a multiple of
+    /// ``pi / 2``.
+    pub fn append_standard_gate(
+        &mut self,
+        gate: StandardGate,
+        params: &[Param],
+        qubits: &[Qubit],
+    ) -> Result<(), String> {
+        let quarter_turns = || -> Result<u8, String> {
+            let Some(Param::Float(angle)) = params.first() else {
+                return Err(format!("Unsupported parameters for gate {gate:?}"));
+            };
+            let turns = angle / FRAC_PI_2;
+            if (turns - turns.round()).abs() > QUARTER_TURN_TOLERANCE {
+                return Err(format!(
+                    "Unsupported non-Clifford angle {angle} for gate {gate:?}"
+                ));
+            }
+            Ok(turns.round().rem_euclid(4.) as u8)
+        };
+        match gate {
+            StandardGate::RZ | StandardGate::Phase | StandardGate::U1 => {
+                self.append_quarter_turns_z(qubits[0].index(), quarter_turns()?)
+            }
+            StandardGate::RX => {
+                let turns = quarter_turns()?;
+                self.append_h(qubits[0].index());
+                self.append_quarter_turns_z(qubits[0].index(), turns);
+                self.append_h(qubits[0].index());
+            }
+            StandardGate::RY => {
+                let turns = quarter_turns()?;
+                self.append_sdg(qubits[0].index());
+                self.append_h(qubits[0].index());
+                self.append_quarter_turns_z(qubits[0].index(), turns);
+                self.append_h(qubits[0].index());
+                self.append_s(qubits[0].index());
+            }
+            StandardGate::GlobalPhase | StandardGate::I => (),
+            StandardGate::X => self.append_x(qubits[0].index()),
+            StandardGate::Y => self.append_y(qubits[0].index()),
+            StandardGate::Z => self.append_z(qubits[0].index()),
+            StandardGate::S => self.append_s(qubits[0].index()),
+            StandardGate::Sdg => self.append_sdg(qubits[0].index()),
+            StandardGate::SX => self.append_sx(qubits[0].index()),
+            StandardGate::SXdg => self.append_sxdg(qubits[0].index()),
+            StandardGate::H => self.append_h(qubits[0].index()),
+            StandardGate::CX => self.append_cx(qubits[0].index(), qubits[1].index()),
+            StandardGate::CY => self.append_cy(qubits[0].index(), qubits[1].index()),
+            StandardGate::CZ => self.append_cz(qubits[0].index(), qubits[1].index()),
+            StandardGate::Swap => self.append_swap(qubits[0].index(), qubits[1].index()),
+            StandardGate::ISwap => {
+                let (q0, q1) = (qubits[0].index(), qubits[1].index());
+                self.append_s(q0);
+                self.append_s(q1);
+                self.append_h(q0);
+                self.append_cx(q0, q1);
+                self.append_cx(q1, q0);
+                self.append_h(q1);
+            }
+            StandardGate::DCX => {
+                let (q0, q1) = (qubits[0].index(), qubits[1].index());
+                self.append_cx(q0, q1);
+                self.append_cx(q1, q0);
+            }
+            StandardGate::ECR => {
+                let (q0, q1) = (qubits[0].index(), qubits[1].index());
+                self.append_s(q0);
+                self.append_sx(q1);
+                self.append_cx(q0, q1);
+                self.append_x(q0);
+            }
+            _ => return Err(format!("Unsupported gate {gate:?}")),
+        }
+        Ok(())
+    }
+
     /// Creates a Clifford from a given sequence of Clifford gates.
     /// In essence, starts from the identity tableau and modifies it
     /// based on the gates in the sequence.
@@ -238,46 +389,95 @@ impl Clifford {
         gate_seq: &CliffordGatesVec,
         num_qubits: usize,
     ) -> Result<Clifford, String> {
-        // create the identity
-        let mut clifford = Clifford {
-            num_qubits,
-            tableau: Array2::from_shape_fn((2 * num_qubits, 2 * num_qubits + 1), |(i, j)| i == j),
-        };
+        let mut clifford = Clifford::identity(num_qubits);
+        gate_seq.iter().try_for_each(|(gate, params, qubits)| {
+            clifford.append_standard_gate(*gate, params, qubits)
+        })?;
+        Ok(clifford)
+    }
 
-        gate_seq
-            .iter()
-            .try_for_each(|(gate, _params, qubits)| match *gate {
-                StandardGate::S => {
-                    clifford.append_s(qubits[0].index());
-                    Ok(())
-                }
-                StandardGate::Sdg => {
-                    clifford.append_sdg(qubits[0].0 as usize);
-                    Ok(())
-                }
-                StandardGate::SX => {
-                    clifford.append_sx(qubits[0].0 as usize);
-                    Ok(())
-                }
-                StandardGate::SXdg => {
-                    clifford.append_sxdg(qubits[0].0 as usize);
-                    Ok(())
-                }
-                StandardGate::H => {
-                    clifford.append_h(qubits[0].index());
-                    Ok(())
-                }
-                StandardGate::CX => {
-                    clifford.append_cx(qubits[0].index(), qubits[1].index());
-                    Ok(())
+    /// Multiplies the Pauli in row ``source`` of the tableau into the Pauli ``target``, which is
+    /// a row of ``2 * num_qubits + 1`` entries laid out like the tableau, tracking the phase.
+    ///
+    /// This is the ``rowsum`` operation of "Improved simulation of stabilizer circuits" by
+    /// S. Aaronson and D. Gottesman (2004), `<https://arxiv.org/abs/quant-ph/0406196>`__.
+    fn rowsum(num_qubits: usize, mut target: ArrayViewMut1<bool>, source: ArrayView1<bool>) {
+        // The exponent of ``i`` in the product, modulo 4.
+        let mut exponent: i64 = 2 * (target[2 * num_qubits] as i64 + source[2 * num_qubits] as i64);
+        for qubit in 0..num_qubits {
+            let (x1, z1) = (source[qubit], source[num_qubits + qubit]);
+            let (x2, z2) = (target[qubit], target[num_qubits + qubit]);
+            exponent += match (x1, z1) {
+                (false, false) => 0,
+                (true, true) => z2 as i64 - x2 as i64,
+                (true, false) => z2 as i64 * (2 * x2 as i64 - 1),
+                (false, true) => x2 as i64 * (1 - 2 * z2 as i64),
+            };
+            target[qubit] = x1 ^ x2;
+            target[num_qubits + qubit] = z1 ^ z2;
+        }
+        target[2 * num_qubits] = exponent.rem_euclid(4) == 2;
+    }
+
+    /// Whether a Z-basis measurement of ``qubit`` has a deterministic outcome, i.e. whether no
+    /// stabilizer of the state anticommutes with Z on ``qubit``.
+    pub fn is_measurement_deterministic(&self, qubit: usize) -> bool {
+        let n = self.num_qubits;
+        !(n..2 * n).any(|row| self.tableau[[row, qubit]])
+    }
+
+    /// Measures the stabilizer state represented by the tableau on ``qubit`` in the Z basis,
+    /// collapsing it and returning the outcome.  If the outcome is not deterministic, it is the
+    /// value returned by ``choose``, which is not called otherwise.
+    pub fn measure(&mut self, qubit: usize, choose: impl FnOnce() -> bool) -> bool {
+        let n = self.num_qubits;
+        let anticommuting = (n..2 * n).find(|&row| self.tableau[[row, qubit]]);
+        match anticommuting {
+            Some(pivot) => {
+                let pivot_row = self.tableau.row(pivot).to_owned();
+                let update = |(index, row): (usize, ArrayViewMut1<bool>)| {
+                    if index != pivot && row[qubit] {
+                        Self::rowsum(n, row, pivot_row.view());
+                    }
+                };
+                if n >= PARALLEL_THRESHOLD && getenv_use_multiple_threads() {
+                    self.tableau
+                        .axis_iter_mut(Axis(0))
+                        .into_par_iter()
+                        .enumerate()
+                        .for_each(update);
+                } else {
+                    self.tableau
+                        .axis_iter_mut(Axis(0))
+                        .enumerate()
+                        .for_each(update);
                 }
-                StandardGate::Swap => {
-                    clifford.append_swap(qubits[0].index(), qubits[1].index());
-                    Ok(())
+                let outcome = choose();
+                self.tableau.row_mut(pivot - n).assign(&pivot_row);
+                let mut measured = self.tableau.row_mut(pivot);
+                measured.fill(false);
+                measured[n + qubit] = true;
+                measured[2 * n] = outcome;
+                outcome
+            }
+            None => {
+                let mut scratch = Array1::from_elem(2 * n + 1, false);
+                for row in 0..n {
+                    if self.tableau[[row, qubit]] {
+                        Self::rowsum(n, scratch.view_mut(), self.tableau.row(n + row));
+                    }
                 }
-                _ => Err(format!("Unsupported gate {gate:?}")),
-            })?;
-        Ok(clifford)
+                scratch[2 * n]
+            }
+        }
+    }
+
+    /// Resets ``qubit`` of the stabilizer state represented by the tableau to ``|0>``, using
+    /// ``choose`` for the outcome of the implied measurement as in [Clifford::measure].
+    pub fn reset(&mut self, qubit: usize, choose: impl FnOnce() -> bool) {
+        if self.measure(qubit, choose) {
+            self.append_x(qubit);
+        }
     }
 }
 
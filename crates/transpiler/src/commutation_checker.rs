@@ -617,7 +617,7 @@ fn matrix_via_operator(py: Python, py_obj: &PyObject) -> PyResult<Array2<Complex
 fn is_parameterized(params: &[Param]) -> bool {
     params
         .iter()
-        .any(|x| matches!(x, Param::ParameterExpression(_)))
+        .any(|x| matches!(x, Param::ParameterExpression(_) | Param::Symbolic(_)))
 }
 
 /// Check if a given operation can be mapped onto a generator.
@@ -718,7 +718,7 @@ fn raise_if_param_mismatch(
         py,
         gate_params
             .iter()
-            .filter(|param| matches!(param, Param::ParameterExpression(_))),
+            .filter(|param| matches!(param, Param::ParameterExpression(_) | Param::Symbolic(_))),
     )?;
     if !gate_params_obj.eq(&circuit_parameters)? {
         return Err(CircuitError::new_err(format!(
@@ -763,6 +763,9 @@ fn rebind_equiv(
                     let param_uuid = ParameterUuid::from_parameter(param.bind(py));
                     Some(param_uuid.map(|uuid| (uuid, param_y)))
                 }
+                Param::Symbolic(expr) => expr
+                    .as_symbol()
+                    .map(|symbol| Ok((ParameterUuid::from_symbol(symbol), param_y))),
                 _ => None,
             })
             .collect();
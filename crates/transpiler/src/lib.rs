@@ -14,6 +14,10 @@ pub mod commutation_checker;
 pub mod equivalence;
 pub mod passes;
 pub mod target;
+pub mod transpile;
+
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
 
 mod gate_metrics;
 
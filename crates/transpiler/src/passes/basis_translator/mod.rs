@@ -683,10 +683,12 @@ fn replace_node(
             if inner_node
                 .params_view()
                 .iter()
-                .any(|param| matches!(param, Param::ParameterExpression(_)))
+                .any(|param| matches!(param, Param::ParameterExpression(_) | Param::Symbolic(_)))
             {
                 new_params = SmallVec::new();
                 for param in inner_node.params_view() {
+                    // Rust-space symbols are bound through their Python-space equivalents.
+                    let param = &param.to_python_expression(py)?;
                     if let Param::ParameterExpression(param_obj) = param {
                         let bound_param = param_obj.bind(py);
                         let exp_params = param.iter_parameters(py)?;
@@ -754,7 +756,7 @@ fn replace_node(
             )?;
         }
 
-        match target_dag.global_phase() {
+        match target_dag.global_phase().to_python_expression(py)? {
             Param::ParameterExpression(old_phase) => {
                 let bound_old_phase = old_phase.bind(py);
                 let bind_dict = PyDict::new(py);
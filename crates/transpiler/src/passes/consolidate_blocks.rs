@@ -22,7 +22,7 @@ use qiskit_circuit::dag_circuit::DAGCircuit;
 use qiskit_circuit::gate_matrix::{ONE_QUBIT_IDENTITY, TWO_QUBIT_IDENTITY};
 use qiskit_circuit::imports::{QI_OPERATOR, QUANTUM_CIRCUIT};
 use qiskit_circuit::operations::{ArrayType, Operation, Param, UnitaryGate};
-use qiskit_circuit::packed_instruction::PackedOperation;
+use qiskit_circuit::packed_instruction::{PackedInstruction, PackedOperation};
 use qiskit_circuit::Qubit;
 use rustworkx_core::petgraph::stable_graph::NodeIndex;
 use smallvec::smallvec;
@@ -47,16 +47,20 @@ pub enum DecomposerType {
 fn is_supported(
     target: Option<&Target>,
     basis_gates: Option<&HashSet<String>>,
-    name: &str,
+    inst: &PackedInstruction,
     qargs: &[Qubit],
 ) -> bool {
     match target {
         Some(target) => {
             let physical_qargs: Qargs = qargs.iter().map(|bit| PhysicalQubit(bit.0)).collect();
-            target.instruction_supported(name, &physical_qargs)
+            target.instruction_supported_with_params(
+                inst.op.name(),
+                &physical_qargs,
+                inst.params_view(),
+            )
         }
         None => match basis_gates {
-            Some(basis_gates) => basis_gates.contains(name),
+            Some(basis_gates) => basis_gates.contains(inst.op.name()),
             None => true,
         },
     }
@@ -69,7 +73,6 @@ const MAX_2Q_DEPTH: usize = 20;
 #[pyfunction]
 #[pyo3(name = "consolidate_blocks", signature = (dag, decomposer, basis_gate_name, force_consolidate, target=None, basis_gates=None, blocks=None, runs=None))]
 pub fn run_consolidate_blocks(
-    py: Python,
     dag: &mut DAGCircuit,
     decomposer: DecomposerType,
     basis_gate_name: &str,
@@ -117,11 +120,11 @@ pub fn run_consolidate_blocks(
             if !is_supported(
                 target,
                 basis_gates.as_ref(),
-                inst.op.name(),
+                inst,
                 dag.get_qargs(inst.qubits),
             ) {
                 all_block_gates.insert(inst_node);
-                let matrix = match get_matrix_from_inst(py, inst) {
+                let matrix = match get_matrix_from_inst(inst) {
                     Ok(mat) => mat,
                     Err(_) => continue,
                 };
@@ -151,7 +154,7 @@ pub fn run_consolidate_blocks(
             if !is_supported(
                 target,
                 basis_gates.as_ref(),
-                inst.op.name(),
+                inst,
                 dag.get_qargs(inst.qubits),
             ) {
                 outside_basis = true;
@@ -183,22 +186,25 @@ pub fn run_consolidate_blocks(
                 }),
                 Param::Float(0.),
             )?;
-            let circuit = QUANTUM_CIRCUIT
-                .get_bound(py)
-                .call_method1(intern!(py, "_from_circuit_data"), (circuit_data,))?;
-            let array = QI_OPERATOR
-                .get_bound(py)
-                .call1((circuit,))?
-                .getattr(intern!(py, "data"))?
-                .extract::<PyReadonlyArray2<Complex64>>()?;
-            let matrix = array.as_array();
+            // Blocks on more than two qubits are only ever given to us from Python space.
+            let matrix = Python::with_gil(|py| -> PyResult<Array2<Complex64>> {
+                let circuit = QUANTUM_CIRCUIT
+                    .get_bound(py)
+                    .call_method1(intern!(py, "_from_circuit_data"), (circuit_data,))?;
+                Ok(QI_OPERATOR
+                    .get_bound(py)
+                    .call1((circuit,))?
+                    .getattr(intern!(py, "data"))?
+                    .extract::<PyReadonlyArray2<Complex64>>()?
+                    .as_array()
+                    .to_owned())
+            })?;
             let identity: Array2<Complex64> = Array2::eye(2usize.pow(block_qargs.len() as u32));
             if approx::abs_diff_eq!(identity, matrix) {
                 for node in block {
                     dag.remove_op_node(node);
                 }
             } else {
-                let matrix = array.as_array().to_owned();
                 let unitary_gate = UnitaryGate {
                     array: ArrayType::NDArray(matrix),
                 };
@@ -218,7 +224,7 @@ pub fn run_consolidate_blocks(
                 *block_qargs.iter().min().unwrap(),
                 *block_qargs.iter().max().unwrap(),
             ];
-            let matrix = blocks_to_matrix(py, dag, &block, block_index_map).ok();
+            let matrix = blocks_to_matrix(dag, &block, block_index_map).ok();
             if let Some(matrix) = matrix {
                 let num_basis_gates = match decomposer {
                     DecomposerType::TwoQubitBasis(ref decomp) => {
@@ -275,14 +281,9 @@ pub fn run_consolidate_blocks(
             let first_qubits = dag.get_qargs(first_inst.qubits);
 
             if run.len() == 1
-                && !is_supported(
-                    target,
-                    basis_gates.as_ref(),
-                    first_inst.op.name(),
-                    first_qubits,
-                )
+                && !is_supported(target, basis_gates.as_ref(), first_inst, first_qubits)
             {
-                let matrix = match get_matrix_from_inst(py, first_inst) {
+                let matrix = match get_matrix_from_inst(first_inst) {
                     Ok(mat) => mat,
                     Err(_) => continue,
                 };
@@ -306,7 +307,7 @@ pub fn run_consolidate_blocks(
                     already_in_block = true;
                 }
                 let gate = dag[*node].unwrap_operation();
-                let operator = match get_matrix_from_inst(py, gate) {
+                let operator = match get_matrix_from_inst(gate) {
                     Ok(mat) => mat,
                     Err(_) => {
                         // Set this to skip this run because we can't compute the matrix of the
@@ -10,6 +10,8 @@
 // copyright notice, and modified files need to carry a notice indicating
 // that they have been altered from the originals.
 
+use std::borrow::Cow;
+
 use hashbrown::HashMap;
 use hashbrown::HashSet;
 use ndarray::prelude::*;
@@ -31,11 +33,11 @@ use qiskit_circuit::gate_matrix::CX_GATE;
 use qiskit_circuit::imports::{HLS_SYNTHESIZE_OP_USING_PLUGINS, QS_DECOMPOSITION, QUANTUM_CIRCUIT};
 use qiskit_circuit::operations::Operation;
 use qiskit_circuit::operations::OperationRef;
+use qiskit_circuit::operations::Param;
 use qiskit_circuit::operations::StandardGate;
-use qiskit_circuit::operations::{radd_param, Param};
 use qiskit_circuit::packed_instruction::PackedInstruction;
 use qiskit_circuit::packed_instruction::PackedOperation;
-use qiskit_circuit::{Clbit, Qubit, VarsMode};
+use qiskit_circuit::{python_is_initialized, Clbit, Qubit, VarsMode};
 use smallvec::SmallVec;
 
 use crate::equivalence::EquivalenceLibrary;
@@ -325,52 +327,101 @@ impl HighLevelSynthesisData {
     }
 }
 
+impl HighLevelSynthesisData {
+    /// Call `f` with the Rust-space view of this data.
+    fn with_context<R>(
+        slf: &Bound<HighLevelSynthesisData>,
+        f: impl FnOnce(&HighLevelSynthesisContext) -> R,
+    ) -> R {
+        let py = slf.py();
+        let data = slf.borrow();
+        let target = data.target.as_ref().map(|target| target.borrow(py));
+        let equivalence_library = data
+            .equivalence_library
+            .as_ref()
+            .map(|equivalence_library| equivalence_library.borrow(py));
+        f(&HighLevelSynthesisContext {
+            hls_op_names: &data.hls_op_names,
+            target: target.as_deref(),
+            equivalence_library: equivalence_library.as_deref(),
+            device_insts: &data.device_insts,
+            use_physical_indices: data.use_physical_indices,
+            min_qubits: data.min_qubits,
+            unroll_definitions: data.unroll_definitions,
+            py_data: Some(slf.as_unbound()),
+        })
+    }
+}
+
+/// The data required by the HighLevelSynthesis transpiler pass, as seen from Rust.
+///
+/// When the pass is run from Python, this borrows from a [HighLevelSynthesisData].  When it is
+/// run from Rust without a Python interpreter, `py_data` is `None`: no synthesis plugins are
+/// called, and operations are synthesized through their definitions only.
+pub struct HighLevelSynthesisContext<'a> {
+    /// The names of high-level objects with available synthesis plugins.
+    pub hls_op_names: &'a HashSet<String>,
+    /// Optional, the backend target to use for this pass.
+    pub target: Option<&'a Target>,
+    /// The equivalence library used (instructions in this library will not be unrolled).
+    pub equivalence_library: Option<&'a EquivalenceLibrary>,
+    /// Supported instructions in case that target is not specified.
+    pub device_insts: &'a HashSet<String>,
+    /// Whether the qubit indices of the circuit correspond to qubit indices on the target.
+    pub use_physical_indices: bool,
+    /// The minimum number of qubits for operations in the input dag to translate.
+    pub min_qubits: usize,
+    /// Whether to use custom definitions.
+    pub unroll_definitions: bool,
+    /// The Python-space data that the synthesis plugins are called with.
+    pub py_data: Option<&'a Py<HighLevelSynthesisData>>,
+}
+
 /// A super-fast check whether all operations in `op_names` are natively supported.
 /// This check is based only on the names of the operations in the circuit.
 fn all_instructions_supported(
-    py: Python,
-    data: &Bound<HighLevelSynthesisData>,
+    context: &HighLevelSynthesisContext,
     dag: &DAGCircuit,
 ) -> PyResult<bool> {
-    let ops = dag.count_ops(py, true)?;
+    // Control-flow operations are Python objects, so we can only recurse into them when there is
+    // an interpreter.
+    let ops = if dag.has_control_flow() {
+        Cow::Owned(Python::with_gil(|py| dag.count_ops(py, true))?)
+    } else {
+        Cow::Borrowed(dag.get_op_counts())
+    };
     let mut op_keys = ops.keys();
 
-    let borrowed_data = data.borrow();
-
-    match &borrowed_data.target {
+    match context.target {
         Some(target) => {
-            let target = target.borrow(py);
             if target.num_qubits.is_some() {
                 // If we have the target and HighLevelSynthesis runs pre-routing,
                 // we check whether every operation name in op_names is supported
                 // by the target.
-                if borrowed_data.use_physical_indices {
+                if context.use_physical_indices {
                     return Ok(false);
                 }
                 Ok(op_keys.all(|name| target.instruction_supported(name, &Qargs::Global)))
             } else {
                 // If we do not have the target, we check whether every operation
                 // in op_names is inside the basis gates.
-                Ok(op_keys.all(|name| borrowed_data.device_insts.contains(name)))
+                Ok(op_keys.all(|name| context.device_insts.contains(name)))
             }
         }
-        None => Ok(op_keys.all(|name| borrowed_data.device_insts.contains(name))),
+        None => Ok(op_keys.all(|name| context.device_insts.contains(name))),
     }
 }
 
 /// Check whether an operation is natively supported.
 fn instruction_supported(
-    py: Python,
-    data: &Bound<HighLevelSynthesisData>,
+    context: &HighLevelSynthesisContext,
     name: &str,
     qubits: &[Qubit],
 ) -> bool {
-    let borrowed_data = data.borrow();
-    match &borrowed_data.target {
+    match context.target {
         Some(target) => {
-            let target = target.borrow(py);
             if target.num_qubits.is_some() {
-                if borrowed_data.use_physical_indices {
+                if context.use_physical_indices {
                     let physical_qubits: Qargs =
                         qubits.iter().map(|q| PhysicalQubit(q.0)).collect();
                     target.instruction_supported(name, &physical_qubits)
@@ -378,23 +429,20 @@ fn instruction_supported(
                     target.instruction_supported(name, &Qargs::Global)
                 }
             } else {
-                borrowed_data.device_insts.contains(name)
+                context.device_insts.contains(name)
             }
         }
-        None => borrowed_data.device_insts.contains(name),
+        None => context.device_insts.contains(name),
     }
 }
 
 /// Check whether an operation does not need to be synthesized.
 fn definitely_skip_op(
-    py: Python,
-    data: &Bound<HighLevelSynthesisData>,
+    context: &HighLevelSynthesisContext,
     op: &PackedOperation,
     qubits: &[Qubit],
 ) -> bool {
-    let borrowed_data: PyRef<'_, HighLevelSynthesisData> = data.borrow();
-
-    if qubits.len() < borrowed_data.min_qubits {
+    if qubits.len() < context.min_qubits {
         return true;
     }
 
@@ -407,18 +455,18 @@ fn definitely_skip_op(
     }
 
     // If the operation is natively supported, we can skip it.
-    if instruction_supported(py, data, op.name(), qubits) {
+    if instruction_supported(context, op.name(), qubits) {
         return true;
     }
 
     // If there are available plugins for this operation, we should try them
     // before checking the equivalence library.
-    if borrowed_data.hls_op_names.iter().any(|s| s == op.name()) {
+    if context.py_data.is_some() && context.hls_op_names.iter().any(|s| s == op.name()) {
         return false;
     }
 
-    if let Some(equiv_lib) = &borrowed_data.equivalence_library {
-        if equiv_lib.borrow(py).has_entry(op) {
+    if let Some(equiv_lib) = context.equivalence_library {
+        if equiv_lib.has_entry(op) {
             return true;
         }
     }
@@ -439,10 +487,9 @@ fn definitely_skip_op(
 /// The function also updates in-place the qubit tracker, which keeps track of the
 /// state of each global qubits (whether it's clean, dirty, or cannot be used).
 fn run_on_circuitdata(
-    py: Python,
+    context: &HighLevelSynthesisContext,
     input_circuit: &CircuitData,
     input_qubits: &[usize],
-    data: &Bound<HighLevelSynthesisData>,
     tracker: &mut QubitTracker,
 ) -> PyResult<(CircuitData, Vec<usize>)> {
     if input_circuit.num_qubits() != input_qubits.len() {
@@ -499,7 +546,7 @@ fn run_on_circuitdata(
 
         // Check if synthesis for this operation can be skipped
         let op_qargs: Vec<Qubit> = op_qubits.iter().map(|q| Qubit::new(*q)).collect();
-        if definitely_skip_op(py, data, &inst.op, &op_qargs) {
+        if definitely_skip_op(context, &inst.op, &op_qargs) {
             output_circuit.push(inst.clone())?;
             tracker.set_dirty(op_qubits);
             continue;
@@ -511,50 +558,52 @@ fn run_on_circuitdata(
         // that different subcircuits may choose to use different auxiliary global qubits, and to
         // avoid complications related to tracking qubit status for while- loops.
         // In the future, this handling can potentially be improved.
+        // Control-flow operations are Python objects, so there is an interpreter to use here.
         if inst.op.control_flow() {
-            let quantum_circuit_cls = QUANTUM_CIRCUIT.get_bound(py);
             if let OperationRef::Instruction(py_inst) = inst.op.view() {
-                let old_blocks_as_bound_obj = py_inst.instruction.bind(py);
-
-                // old_blocks_py keeps the original QuantumCircuit's appearing within control-flow ops
-                // new_blocks_py keeps the recursively synthesized circuits
-                let old_blocks_py = old_blocks_as_bound_obj.getattr(intern!(py, "blocks"))?;
-                let old_blocks_py = old_blocks_py.downcast::<PyTuple>()?;
-                let mut new_blocks_py: Vec<Bound<PyAny>> = Vec::with_capacity(old_blocks_py.len());
-
-                // We do not allow using any additional qubits outside of the block.
-                let mut block_tracker = tracker.clone();
-                let to_disable: Vec<usize> = (0..tracker.num_qubits())
-                    .filter(|q| !op_qubits.contains(q))
-                    .collect();
-                block_tracker.disable(to_disable);
-                block_tracker.set_dirty(op_qubits.clone());
-
-                for block_py in old_blocks_py {
-                    let old_block_py: QuantumCircuitData = block_py.extract()?;
-                    let (new_block, _) = run_on_circuitdata(
-                        py,
-                        &old_block_py.data,
-                        &op_qubits,
-                        data,
-                        &mut block_tracker,
-                    )?;
-                    let new_block = new_block.into_bound_py_any(py)?;
-
-                    // We create the new quantum circuit by calling copy_empty_like on the old quantum circuit
-                    // and manually set the circuit data to the (recursively synthesized) data.
-                    // This makes sure that all the python-space information (qregs, cregs, input variables)
-                    // get copied correctly.
-                    let new_block_py: Bound<'_, PyAny> = quantum_circuit_cls
-                        .call_method1(intern!(py, "copy_empty_like"), (block_py,))?;
-                    new_block_py.setattr(intern!(py, "_data"), &new_block)?;
-                    new_blocks_py.push(new_block_py);
-                }
-
-                let replaced_blocks = old_blocks_as_bound_obj
-                    .call_method1(intern!(py, "replace_blocks"), (new_blocks_py,))?;
+                let synthesized_op = Python::with_gil(|py| -> PyResult<OperationFromPython> {
+                    let quantum_circuit_cls = QUANTUM_CIRCUIT.get_bound(py);
+                    let old_blocks_as_bound_obj = py_inst.instruction.bind(py);
+
+                    // old_blocks_py keeps the original QuantumCircuit's appearing within control-flow ops
+                    // new_blocks_py keeps the recursively synthesized circuits
+                    let old_blocks_py = old_blocks_as_bound_obj.getattr(intern!(py, "blocks"))?;
+                    let old_blocks_py = old_blocks_py.downcast::<PyTuple>()?;
+                    let mut new_blocks_py: Vec<Bound<PyAny>> =
+                        Vec::with_capacity(old_blocks_py.len());
+
+                    // We do not allow using any additional qubits outside of the block.
+                    let mut block_tracker = tracker.clone();
+                    let to_disable: Vec<usize> = (0..tracker.num_qubits())
+                        .filter(|q| !op_qubits.contains(q))
+                        .collect();
+                    block_tracker.disable(to_disable);
+                    block_tracker.set_dirty(op_qubits.clone());
+
+                    for block_py in old_blocks_py {
+                        let old_block_py: QuantumCircuitData = block_py.extract()?;
+                        let (new_block, _) = run_on_circuitdata(
+                            context,
+                            &old_block_py.data,
+                            &op_qubits,
+                            &mut block_tracker,
+                        )?;
+                        let new_block = new_block.into_bound_py_any(py)?;
+
+                        // We create the new quantum circuit by calling copy_empty_like on the old quantum circuit
+                        // and manually set the circuit data to the (recursively synthesized) data.
+                        // This makes sure that all the python-space information (qregs, cregs, input variables)
+                        // get copied correctly.
+                        let new_block_py: Bound<'_, PyAny> = quantum_circuit_cls
+                            .call_method1(intern!(py, "copy_empty_like"), (block_py,))?;
+                        new_block_py.setattr(intern!(py, "_data"), &new_block)?;
+                        new_blocks_py.push(new_block_py);
+                    }
 
-                let synthesized_op: OperationFromPython = replaced_blocks.extract()?;
+                    let replaced_blocks = old_blocks_as_bound_obj
+                        .call_method1(intern!(py, "replace_blocks"), (new_blocks_py,))?;
+                    replaced_blocks.extract()
+                })?;
                 let packed_instruction = PackedInstruction {
                     op: synthesized_op.operation,
                     qubits: inst.qubits,
@@ -576,8 +625,7 @@ fn run_on_circuitdata(
         // circuit is defined. Note that the synthesized circuit may involve auxiliary
         // global qubits not used by the input circuit.
         let synthesize_operation_result = synthesize_operation(
-            py,
-            data,
+            context,
             tracker,
             &op_qubits,
             &inst.op,
@@ -645,12 +693,7 @@ fn run_on_circuitdata(
                     );
                 }
 
-                let updated_global_phase = radd_param(
-                    output_circuit.global_phase().clone(),
-                    synthesized_circuit.global_phase().clone(),
-                    py,
-                );
-                output_circuit.set_global_phase(updated_global_phase)?;
+                output_circuit.add_global_phase(synthesized_circuit.global_phase())?;
             }
         }
     }
@@ -672,11 +715,7 @@ fn run_on_circuitdata(
 /// Essentially this function constructs a default definition for a unitary gate, in which case
 /// ``op.definition`` purposefully returns ``None``.
 /// For all other operation types, it simply calls ``op.definition``.
-fn extract_definition(
-    py: Python,
-    op: &PackedOperation,
-    params: &[Param],
-) -> PyResult<Option<CircuitData>> {
+fn extract_definition(op: &PackedOperation, params: &[Param]) -> PyResult<Option<CircuitData>> {
     match op.view() {
         OperationRef::Unitary(unitary) => {
             let unitary: Array<Complex<f64>, Dim<[usize; 2]>> = match unitary.matrix(&[]) {
@@ -724,13 +763,21 @@ fn extract_definition(
                     )?;
                     Ok(Some(circuit_data))
                 }
-                // Run 3q+ synthesis
+                // Run 3q+ synthesis, which is only available in Python.
                 _ => {
-                    let qs_decomposition: &Bound<'_, PyAny> = QS_DECOMPOSITION.get_bound(py);
-                    let synthesized_circuit_py =
-                        qs_decomposition.call1((unitary.into_pyarray(py),))?;
-                    let circuit_data: QuantumCircuitData = synthesized_circuit_py.extract()?;
-                    Ok(Some(circuit_data.data))
+                    if !python_is_initialized() {
+                        return Err(TranspilerError::new_err(format!(
+                            "HighLevelSynthesis cannot synthesize a {}-qubit unitary without Python",
+                            unitary.shape()[0].ilog2()
+                        )));
+                    }
+                    Python::with_gil(|py| {
+                        let qs_decomposition: &Bound<'_, PyAny> = QS_DECOMPOSITION.get_bound(py);
+                        let synthesized_circuit_py =
+                            qs_decomposition.call1((unitary.into_pyarray(py),))?;
+                        let circuit_data: QuantumCircuitData = synthesized_circuit_py.extract()?;
+                        Ok(Some(circuit_data.data))
+                    })
                 }
             }
         }
@@ -752,8 +799,7 @@ fn extract_definition(
 /// The function also updates in-place the qubit tracker which keeps track of the state of
 /// each global qubit (whether it's clean, dirty, or cannot be used).
 fn synthesize_operation(
-    py: Python,
-    data: &Bound<HighLevelSynthesisData>,
+    context: &HighLevelSynthesisContext,
     tracker: &mut QubitTracker,
     input_qubits: &[usize],
     op: &PackedOperation,
@@ -768,8 +814,6 @@ fn synthesize_operation(
         )));
     }
 
-    let borrowed_data: PyRef<'_, HighLevelSynthesisData> = data.borrow();
-
     let mut output_circuit_and_qubits: Option<(CircuitData, Vec<usize>)> = None;
 
     // If this function is called, the operation is not supported by the target, however may have
@@ -784,30 +828,34 @@ fn synthesize_operation(
     // change, we return None.
 
     // Try to synthesize using plugins.
-    if borrowed_data.hls_op_names.iter().any(|s| s == op.name()) {
-        output_circuit_and_qubits = synthesize_op_using_plugins(
-            py,
-            data,
-            tracker,
-            input_qubits,
-            &op.view(),
-            params,
-            label,
-        )?;
+    if let Some(py_data) = context.py_data {
+        if context.hls_op_names.iter().any(|s| s == op.name()) {
+            output_circuit_and_qubits = Python::with_gil(|py| {
+                synthesize_op_using_plugins(
+                    py,
+                    py_data.bind(py),
+                    tracker,
+                    input_qubits,
+                    &op.view(),
+                    params,
+                    label,
+                )
+            })?;
+        }
     }
 
     // Check if present in the equivalent library.
     if output_circuit_and_qubits.is_none() {
-        if let Some(equiv_lib) = &borrowed_data.equivalence_library {
-            if equiv_lib.borrow(py).has_entry(op) {
+        if let Some(equiv_lib) = context.equivalence_library {
+            if equiv_lib.has_entry(op) {
                 return Ok(None);
             }
         }
     }
 
     // Extract definition.
-    if output_circuit_and_qubits.is_none() && borrowed_data.unroll_definitions {
-        let definition_circuit = extract_definition(py, op, params)?;
+    if output_circuit_and_qubits.is_none() && context.unroll_definitions {
+        let definition_circuit = extract_definition(op, params)?;
         match definition_circuit {
             Some(definition_circuit) => {
                 output_circuit_and_qubits = Some((definition_circuit, input_qubits.to_vec()));
@@ -831,7 +879,7 @@ fn synthesize_operation(
     if let Some((current_circuit, current_qubits)) = output_circuit_and_qubits {
         let saved_tracker = tracker.copy();
         let (synthesized_circuit, synthesized_qubits) =
-            run_on_circuitdata(py, &current_circuit, &current_qubits, data, tracker)?;
+            run_on_circuitdata(context, &current_circuit, &current_qubits, tracker)?;
 
         if synthesized_qubits.len() > input_qubits.len() {
             let qubits_to_replace: Vec<usize> =
@@ -901,7 +949,6 @@ fn synthesize_op_using_plugins(
 #[pyfunction]
 #[pyo3(name = "synthesize_operation", signature = (py_op, input_qubits, data, tracker))]
 fn py_synthesize_operation(
-    py: Python,
     py_op: Bound<PyAny>,
     input_qubits: Vec<usize>,
     data: &Bound<HighLevelSynthesisData>,
@@ -909,28 +956,28 @@ fn py_synthesize_operation(
 ) -> PyResult<Option<(CircuitData, Vec<usize>)>> {
     let op: OperationFromPython = py_op.extract()?;
 
-    // Check if the operation can be skipped.
-    if definitely_skip_op(
-        py,
-        data,
-        &op.operation,
-        &input_qubits
-            .iter()
-            .map(|q| Qubit::new(*q))
-            .collect::<Vec<Qubit>>(),
-    ) {
-        return Ok(None);
-    }
+    HighLevelSynthesisData::with_context(data, |context| {
+        // Check if the operation can be skipped.
+        if definitely_skip_op(
+            context,
+            &op.operation,
+            &input_qubits
+                .iter()
+                .map(|q| Qubit::new(*q))
+                .collect::<Vec<Qubit>>(),
+        ) {
+            return Ok(None);
+        }
 
-    synthesize_operation(
-        py,
-        data,
-        tracker,
-        &input_qubits,
-        &op.operation,
-        &op.params,
-        op.label.as_ref().map(|x| x.as_str()),
-    )
+        synthesize_operation(
+            context,
+            tracker,
+            &input_qubits,
+            &op.operation,
+            &op.params,
+            op.label.as_ref().map(|x| x.as_str()),
+        )
+    })
 }
 
 /// Runs HighLevelSynthesis transpiler pass.
@@ -940,18 +987,31 @@ fn py_synthesize_operation(
 /// Otherwise, the new DAG is returned.
 #[pyfunction]
 #[pyo3(name = "run_on_dag", signature = (dag, data, qubits_initially_zero))]
-pub fn run_high_level_synthesis(
-    py: Python,
+fn py_run_high_level_synthesis(
     dag: &DAGCircuit,
     data: &Bound<HighLevelSynthesisData>,
     qubits_initially_zero: bool,
+) -> PyResult<Option<DAGCircuit>> {
+    HighLevelSynthesisData::with_context(data, |context| {
+        run_high_level_synthesis(dag, context, qubits_initially_zero)
+    })
+}
+
+/// Runs the HighLevelSynthesis transpiler pass with the given [HighLevelSynthesisContext].
+///
+/// If the pass does not need to do anything, it returns None, meaning that the DAG should
+/// remain unchanged.  Otherwise, the new DAG is returned.
+pub fn run_high_level_synthesis(
+    dag: &DAGCircuit,
+    context: &HighLevelSynthesisContext,
+    qubits_initially_zero: bool,
 ) -> PyResult<Option<DAGCircuit>> {
     // Fast-path: check if HighLevelSynthesis can be skipped altogether. This is only
     // done at the top-level since this does not track the qubit states.
 
     // First, we apply a super-fast (but incomplete) check to see if all the operations
     // present in the circuit are suported by the target / are in the basis.
-    if all_instructions_supported(py, data, dag)? {
+    if all_instructions_supported(context, dag)? {
         return Ok(None);
     }
 
@@ -961,7 +1021,7 @@ pub fn run_high_level_synthesis(
 
     for (_, inst) in dag.op_nodes(false) {
         let qubits = dag.get_qargs(inst.qubits);
-        if !definitely_skip_op(py, data, &inst.op, qubits) {
+        if !definitely_skip_op(context, &inst.op, qubits) {
             fast_path = false;
             break;
         }
@@ -980,26 +1040,22 @@ pub fn run_high_level_synthesis(
         let mut tracker = QubitTracker::new(num_qubits, qubits_initially_zero);
 
         let (output_circuit, _) =
-            run_on_circuitdata(py, &circuit, &input_qubits, data, &mut tracker)?;
-
-        // Using this constructor so name and metadata are not lost
-        let new_dag = DAGCircuit::from_circuit(
-            QuantumCircuitData {
-                data: output_circuit,
-                name: dag.get_name().cloned(),
-                metadata: dag.get_metadata().map(|m| m.bind(py)).cloned(),
-            },
-            false,
-            None,
-            None,
-        )?;
+            run_on_circuitdata(context, &circuit, &input_qubits, &mut tracker)?;
+
+        // Copy over the name and metadata, so they are not lost.
+        let mut new_dag = DAGCircuit::from_circuit_data(output_circuit, false)?;
+        new_dag.name.clone_from(&dag.name);
+        new_dag.metadata = dag
+            .metadata
+            .as_ref()
+            .map(|metadata| Python::with_gil(|py| metadata.clone_ref(py)));
 
         Ok(Some(new_dag))
     }
 }
 
 pub fn high_level_synthesis_mod(m: &Bound<PyModule>) -> PyResult<()> {
-    m.add_wrapped(wrap_pyfunction!(run_high_level_synthesis))?;
+    m.add_wrapped(wrap_pyfunction!(py_run_high_level_synthesis))?;
     m.add_wrapped(wrap_pyfunction!(py_synthesize_operation))?;
 
     m.add_class::<QubitTracker>()?;
@@ -39,6 +39,7 @@ mod optimize_1q_gates_decomposition;
 mod remove_diagonal_gates_before_measure;
 mod remove_identity_equiv;
 pub mod sabre;
+mod scheduling;
 mod split_2q_unitaries;
 mod unitary_synthesis;
 mod vf2;
@@ -61,7 +62,8 @@ pub use gate_direction::{
 };
 pub use gates_in_basis::{gates_in_basis_mod, gates_missing_from_basis, gates_missing_from_target};
 pub use high_level_synthesis::{
-    high_level_synthesis_mod, run_high_level_synthesis, HighLevelSynthesisData,
+    high_level_synthesis_mod, run_high_level_synthesis, HighLevelSynthesisContext,
+    HighLevelSynthesisData,
 };
 pub use inverse_cancellation::{inverse_cancellation_mod, run_inverse_cancellation};
 pub use optimize_1q_gates_decomposition::{
@@ -71,6 +73,11 @@ pub use remove_diagonal_gates_before_measure::{
     remove_diagonal_gates_before_measure_mod, run_remove_diagonal_before_measure,
 };
 pub use remove_identity_equiv::{remove_identity_equiv_mod, run_remove_identity_equiv};
+pub use scheduling::{
+    instruction_duration, run_alap_schedule_analysis, run_asap_schedule_analysis,
+    run_constrained_reschedule, scheduling_mod, NodeStartTimes, SchedulingError,
+};
 pub use split_2q_unitaries::{run_split_2q_unitaries, split_2q_unitaries_mod};
+pub(crate) use unitary_synthesis::get_target_basis_set;
 pub use unitary_synthesis::{run_unitary_synthesis, unitary_synthesis_mod};
 pub use vf2::{error_map_mod, score_layout, vf2_layout_mod, vf2_layout_pass, ErrorMap};
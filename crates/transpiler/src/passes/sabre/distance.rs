@@ -10,6 +10,9 @@
 // copyright notice, and modified files need to carry a notice indicating
 // that they have been altered from the originals.
 
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
 use fixedbitset::FixedBitSet;
 use ndarray::{Array2, ArrayViewMut1, Axis};
 use rayon_cond::CondIterator;
@@ -74,3 +77,71 @@ where
         .for_each(|(index, row)| bfs_traversal(index, row));
     out
 }
+
+/// Entry in the priority queue of [weighted_distance_matrix]'s Dijkstra traversals.  The ordering
+/// is reversed so that the standard max-heap pops the closest node first.
+#[derive(Clone, Copy, PartialEq)]
+struct QueueItem(f64, usize);
+impl Eq for QueueItem {}
+impl PartialOrd for QueueItem {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for QueueItem {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .0
+            .total_cmp(&self.0)
+            .then_with(|| other.1.cmp(&self.1))
+    }
+}
+
+/// Calculate the all-pairs shortest-path distances in a graph whose edges have non-negative
+/// weights given by `weight_fn`.
+///
+/// `weight_fn` is called with the compact indices of the two ends of each edge, and should be
+/// symmetric.  Pairs of nodes with no path between them are set to `null_value`.
+pub fn weighted_distance_matrix<G>(
+    graph: G,
+    weight_fn: impl Fn(usize, usize) -> f64,
+    parallel_threshold: usize,
+    null_value: f64,
+) -> Array2<f64>
+where
+    G: NodeCompactIndexable + IntoNeighbors,
+{
+    let n = graph.node_count();
+    let adjacency = (0..n)
+        .map(|index| {
+            graph
+                .neighbors(graph.from_index(index))
+                .map(|neighbor| {
+                    let neighbor = graph.to_index(neighbor);
+                    (neighbor, weight_fn(index, neighbor))
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let dijkstra = |start: usize, mut row: ArrayViewMut1<f64>| {
+        let mut done = FixedBitSet::with_capacity(n);
+        let mut heap = BinaryHeap::new();
+        heap.push(QueueItem(0.0, start));
+        while let Some(QueueItem(distance, node)) = heap.pop() {
+            if done.put(node) {
+                continue;
+            }
+            row[[node]] = distance;
+            for &(neighbor, weight) in adjacency[node].iter() {
+                if !done.contains(neighbor) {
+                    heap.push(QueueItem(distance + weight, neighbor));
+                }
+            }
+        }
+    };
+    let mut out = Array2::from_elem((n, n), null_value);
+    CondIterator::new(out.axis_iter_mut(Axis(0)), n >= parallel_threshold)
+        .enumerate()
+        .for_each(|(index, row)| dijkstra(index, row));
+    out
+}
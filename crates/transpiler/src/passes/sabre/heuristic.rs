@@ -30,6 +30,22 @@ pub enum SetScaling {
     Size,
 }
 impl_intopyobject_for_copy_pyclass!(SetScaling);
+impl SetScaling {
+    /// Apply the dynamic scaling to `weight` for a set that contains `len` nodes.
+    #[inline]
+    pub fn scale(self, weight: f64, len: usize) -> f64 {
+        match self {
+            SetScaling::Constant => weight,
+            SetScaling::Size => {
+                if len == 0 {
+                    0.0
+                } else {
+                    weight / (len as f64)
+                }
+            }
+        }
+    }
+}
 #[pymethods]
 impl SetScaling {
     pub fn __reduce__(&self, py: Python) -> PyResult<Py<PyAny>> {
@@ -172,6 +188,94 @@ impl DecayHeuristic {
     }
 }
 
+/// Define the characteristics of the fidelity heuristic.  This is a sum of the error-weighted
+/// distances of every gate in the front layer, where each edge of the coupling graph costs
+/// ``-log(1 - error)`` of the best two-qubit gate on it (normalized so the mean edge costs 1.0).
+/// This steers swaps away from the edges with the worst error rates.
+///
+/// This component has no effect if the target does not contain any two-qubit gate errors.
+#[pyclass]
+#[pyo3(module = "qiskit._accelerate.sabre", frozen)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct FidelityHeuristic {
+    /// The relative weight of this heuristic.  Typically this is defined relative to the
+    /// :class:`.BasicHeuristic`, which generally has its weight set to 1.0.
+    pub weight: f64,
+    /// Set the dynamic scaling of the weight based on the layer it is applying to.
+    pub scale: SetScaling,
+}
+impl_intopyobject_for_copy_pyclass!(FidelityHeuristic);
+#[pymethods]
+impl FidelityHeuristic {
+    #[new]
+    pub fn new(weight: f64, scale: SetScaling) -> Self {
+        Self { weight, scale }
+    }
+
+    pub fn __getnewargs__(&self, py: Python) -> PyResult<Py<PyAny>> {
+        (self.weight, self.scale).into_py_any(py)
+    }
+
+    pub fn __eq__(&self, py: Python, other: Py<PyAny>) -> bool {
+        if let Ok(other) = other.extract::<Self>(py) {
+            self == &other
+        } else {
+            false
+        }
+    }
+
+    pub fn __repr__(&self, py: Python) -> PyResult<Py<PyAny>> {
+        let fmt = "FidelityHeuristic(weight={!r}, scale={!r})";
+        PyString::new(py, fmt)
+            .call_method1("format", (self.weight, self.scale))?
+            .into_py_any(py)
+    }
+}
+
+/// Define the characteristics of the bridge alternative to swaps.  When a CX gate in the front
+/// layer is between two qubits at distance two, Sabre may implement it as a four-CX "bridge"
+/// through the middle qubit instead of inserting a swap.  A bridge costs the same number of CX
+/// gates as a swap followed by the gate, but leaves the layout unchanged.
+///
+/// A bridge is chosen if every swap that would make the gate routable changes the score of the
+/// other gates (using the basic, lookahead and fidelity components) by more than :attr:`penalty`.
+#[pyclass]
+#[pyo3(module = "qiskit._accelerate.sabre", frozen)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct BridgeHeuristic {
+    /// The additional score a bridge must overcome to be chosen over a swap.  With ``0.0``, a
+    /// bridge is only used if all the relevant swaps make the rest of the routing worse; negative
+    /// values make bridges more favourable.
+    pub penalty: f64,
+}
+impl_intopyobject_for_copy_pyclass!(BridgeHeuristic);
+#[pymethods]
+impl BridgeHeuristic {
+    #[new]
+    pub fn new(penalty: f64) -> Self {
+        Self { penalty }
+    }
+
+    pub fn __getnewargs__(&self, py: Python) -> PyResult<Py<PyAny>> {
+        (self.penalty,).into_py_any(py)
+    }
+
+    pub fn __eq__(&self, py: Python, other: Py<PyAny>) -> bool {
+        if let Ok(other) = other.extract::<Self>(py) {
+            self == &other
+        } else {
+            false
+        }
+    }
+
+    pub fn __repr__(&self, py: Python) -> PyResult<Py<PyAny>> {
+        let fmt = "BridgeHeuristic(penalty={!r})";
+        PyString::new(py, fmt)
+            .call_method1("format", (self.penalty,))?
+            .into_py_any(py)
+    }
+}
+
 /// A complete description of the heuristic that Sabre will use.  See the individual elements for a
 /// greater description.
 #[pyclass]
@@ -181,6 +285,8 @@ pub struct Heuristic {
     pub basic: Option<BasicHeuristic>,
     pub lookahead: Option<LookaheadHeuristic>,
     pub decay: Option<DecayHeuristic>,
+    pub fidelity: Option<FidelityHeuristic>,
+    pub bridge: Option<BridgeHeuristic>,
     pub best_epsilon: f64,
     pub attempt_limit: usize,
 }
@@ -201,18 +307,22 @@ impl Heuristic {
     ///     best_epsilon (float): the floating-point epsilon to use when comparing scores to find
     ///         the best value.
     #[new]
-    #[pyo3(signature = (basic=None, lookahead=None, decay=None, attempt_limit=1000, best_epsilon=1e-10))]
+    #[pyo3(signature = (basic=None, lookahead=None, decay=None, attempt_limit=1000, best_epsilon=1e-10, fidelity=None, bridge=None))]
     pub fn new(
         basic: Option<BasicHeuristic>,
         lookahead: Option<LookaheadHeuristic>,
         decay: Option<DecayHeuristic>,
         attempt_limit: Option<usize>,
         best_epsilon: f64,
+        fidelity: Option<FidelityHeuristic>,
+        bridge: Option<BridgeHeuristic>,
     ) -> Self {
         Self {
             basic,
             lookahead,
             decay,
+            fidelity,
+            bridge,
             best_epsilon,
             attempt_limit: attempt_limit.unwrap_or(usize::MAX),
         }
@@ -225,6 +335,8 @@ impl Heuristic {
             self.decay,
             self.attempt_limit,
             self.best_epsilon,
+            self.fidelity,
+            self.bridge,
         )
             .into_py_any(py)
     }
@@ -265,6 +377,28 @@ impl Heuristic {
         }
     }
 
+    /// Set the weight of the ``fidelity`` heuristic (the sum of error-weighted distances of gates
+    /// in the front layer).  The error-weighted distances are normalized so that the average edge
+    /// of the coupling graph costs ``1.0``, so a weight of around ``0.5`` relative to a ``basic``
+    /// weight of ``1.0`` trades off a few extra swaps against avoiding the worst edges.
+    pub fn with_fidelity(&self, weight: f64, scale: SetScaling) -> Self {
+        Self {
+            fidelity: Some(FidelityHeuristic { weight, scale }),
+            ..self.clone()
+        }
+    }
+
+    /// Allow distance-two CX gates to be routed with a bridge instead of a swap, when that leaves
+    /// the rest of the routing problem in a better state.  See :class:`BridgeHeuristic` for the
+    /// meaning of ``penalty``.
+    #[pyo3(signature = (penalty=0.0))]
+    pub fn with_bridge(&self, penalty: f64) -> Self {
+        Self {
+            bridge: Some(BridgeHeuristic { penalty }),
+            ..self.clone()
+        }
+    }
+
     pub fn __eq__(&self, py: Python, other: Py<PyAny>) -> bool {
         if let Ok(other) = other.extract::<Self>(py) {
             self == &other
@@ -274,7 +408,7 @@ impl Heuristic {
     }
 
     pub fn __repr__(&self, py: Python) -> PyResult<Py<PyAny>> {
-        let fmt = "Heuristic(basic={!r}, lookahead={!r}, decay={!r}, attempt_limit={!r}, best_epsilon={!r})";
+        let fmt = "Heuristic(basic={!r}, lookahead={!r}, decay={!r}, attempt_limit={!r}, best_epsilon={!r}, fidelity={!r}, bridge={!r})";
         PyString::new(py, fmt)
             .call_method1(
                 "format",
@@ -284,6 +418,8 @@ impl Heuristic {
                     self.decay,
                     self.attempt_limit,
                     self.best_epsilon,
+                    self.fidelity,
+                    self.bridge,
                 ),
             )?
             .into_py_any(py)
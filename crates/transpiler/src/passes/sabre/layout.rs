@@ -36,9 +36,16 @@ use super::heuristic::Heuristic;
 use super::neighbors::Neighbors;
 use super::route::{swap_map, swap_map_trial, RoutingProblem, RoutingResult, RoutingTarget};
 
+/// Run Sabre layout and routing on a circuit.
+///
+/// Returns the routed DAG, and the initial and final layouts as an ``(initial, final)`` pair of
+/// virtual-to-physical mappings.  If ``absorb_final_permutation`` is true, the terminal
+/// measurements are moved to the end of the routed circuit, after every swap, so each measures
+/// its qubit at its final physical position in the returned final layout.  No swaps are added to
+/// restore the initial layout.
 #[allow(clippy::too_many_arguments)]
 #[pyfunction]
-#[pyo3(signature = (dag, target, heuristic, max_iterations, num_swap_trials, num_random_trials, seed=None, partial_layouts=vec![], skip_routing=false))]
+#[pyo3(signature = (dag, target, heuristic, max_iterations, num_swap_trials, num_random_trials, seed=None, partial_layouts=vec![], skip_routing=false, absorb_final_permutation=false))]
 pub fn sabre_layout_and_routing(
     dag: &mut DAGCircuit,
     target: &Target,
@@ -49,6 +56,7 @@ pub fn sabre_layout_and_routing(
     seed: Option<u64>,
     partial_layouts: Vec<Vec<Option<PhysicalQubit>>>,
     skip_routing: bool,
+    absorb_final_permutation: bool,
 ) -> PyResult<(DAGCircuit, NLayout, NLayout)> {
     let Some(num_physical_qubits) = target.num_qubits else {
         return Err(TranspilerError::new_err(
@@ -137,9 +145,16 @@ pub fn sabre_layout_and_routing(
                 }
                 None => Neighbors::from_coupling(&coupling),
             };
-            let target = RoutingTarget::from_neighbors(neighbors);
+            let routing_target = RoutingTarget::from_neighbors(neighbors);
+            let routing_target = if heuristic.fidelity.is_some() {
+                routing_target.with_error_distance(target, |q| {
+                    subset.as_deref().map_or(q, |subset| subset[q.index()])
+                })
+            } else {
+                routing_target
+            };
             let problem = RoutingProblem {
-                target: &target,
+                target: &routing_target,
                 sabre: &sabre_full,
                 dag,
                 heuristic,
@@ -147,7 +162,7 @@ pub fn sabre_layout_and_routing(
             starting_layouts.extend(partial_layouts);
             add_heuristic_layouts(&mut starting_layouts, problem, allow_parallel);
             let num_layout_trials = starting_layouts.len();
-            let (_, result) = CondIterator::new(
+            let (_, mut result) = CondIterator::new(
                 seeds(num_layout_trials),
                 allow_parallel && num_layout_trials > 1,
             )
@@ -165,13 +180,17 @@ pub fn sabre_layout_and_routing(
                     ),
                 )
             })
-            .min_by_key(|(index, result)| (result.swap_count(), *index))
+            .min_by_key(|(index, result)| (result.routing_cost(), *index))
             .expect("should have at least one layout trial");
+            if absorb_final_permutation && !skip_routing {
+                result.absorb_final_permutation();
+            }
             let num_swaps = result.swap_count();
+            let num_bridges = result.bridge_count();
             let out = dag.physical_empty_like_with_capacity(
                 num_physical_qubits,
-                dag.num_ops() + num_swaps,
-                dag.dag().edge_count() + 2 * num_swaps,
+                dag.num_ops() + num_swaps + 3 * num_bridges,
+                dag.dag().edge_count() + 2 * num_swaps + 6 * num_bridges,
             )?;
             let qubit_fn = |q: PhysicalQubit| {
                 subset
@@ -201,14 +220,20 @@ pub fn sabre_layout_and_routing(
             let mut sub_from_full = vec![PhysicalQubit::new(u32::MAX); num_physical_qubits];
             for component in &components {
                 let sabre = SabreDAG::from_dag(&component.sub_dag)?;
-                let target =
+                let routing_target =
                     RoutingTarget::from_neighbors(Neighbors::from_coupling_subset_with_map(
                         &coupling,
                         &component.physical_qubits,
                         |q| NodeIndex::new(q.index()),
                     ));
+                let routing_target = if heuristic.fidelity.is_some() {
+                    routing_target
+                        .with_error_distance(target, |q| component.physical_qubits[q.index()])
+                } else {
+                    routing_target
+                };
                 let sub_problem = RoutingProblem {
-                    target: &target,
+                    target: &routing_target,
                     sabre: &sabre,
                     dag: &component.sub_dag,
                     heuristic,
@@ -270,7 +295,7 @@ pub fn sabre_layout_and_routing(
                         ),
                     )
                 })
-                .min_by_key(|(index, result)| (result.swap_count(), *index))
+                .min_by_key(|(index, result)| (result.routing_cost(), *index))
                 .expect("should have at least one layout trial");
                 for ((_, sub_phys), virt) in result
                     .initial_layout
@@ -303,9 +328,14 @@ pub fn sabre_layout_and_routing(
                 // ...and assign them to the unassigned physical qubits in increasing order of both.
                 .zip(initial_physical.iter_mut().filter(|v| **v == max_virt))
                 .for_each(|(v, slot)| *slot = v);
-            let target = RoutingTarget::from_neighbors(Neighbors::from_coupling(&coupling));
+            let routing_target = RoutingTarget::from_neighbors(Neighbors::from_coupling(&coupling));
+            let routing_target = if heuristic.fidelity.is_some() {
+                routing_target.with_error_distance(target, |q| q)
+            } else {
+                routing_target
+            };
             let problem = RoutingProblem {
-                target: &target,
+                target: &routing_target,
                 sabre: &sabre_full,
                 dag,
                 heuristic,
@@ -319,13 +349,16 @@ pub fn sabre_layout_and_routing(
                     initial_layout,
                 ))
             } else {
-                let result = swap_map(
+                let mut result = swap_map(
                     problem,
                     &initial_layout,
                     seed,
                     num_swap_trials,
                     Some(allow_parallel),
                 );
+                if absorb_final_permutation {
+                    result.absorb_final_permutation();
+                }
                 Ok((
                     result.rebuild()?,
                     result.initial_layout,
@@ -18,6 +18,9 @@ mod layout;
 mod neighbors;
 mod route;
 
+pub use heuristic::{BasicHeuristic, DecayHeuristic, Heuristic, LookaheadHeuristic, SetScaling};
+pub use layout::sabre_layout_and_routing;
+
 use pyo3::prelude::*;
 use pyo3::wrap_pyfunction;
 
@@ -30,5 +33,7 @@ pub fn sabre(m: &Bound<PyModule>) -> PyResult<()> {
     m.add_class::<heuristic::BasicHeuristic>()?;
     m.add_class::<heuristic::LookaheadHeuristic>()?;
     m.add_class::<heuristic::DecayHeuristic>()?;
+    m.add_class::<heuristic::FidelityHeuristic>()?;
+    m.add_class::<heuristic::BridgeHeuristic>()?;
     Ok(())
 }
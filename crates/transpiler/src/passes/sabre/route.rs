@@ -14,14 +14,15 @@ use std::cmp::Ordering;
 use std::collections::VecDeque;
 use std::convert::Infallible;
 use std::num::NonZero;
+use std::sync::OnceLock;
 
 use numpy::{PyArray2, ToPyArray};
 use pyo3::prelude::*;
 use pyo3::Python;
 
-use hashbrown::HashSet;
+use hashbrown::{HashMap, HashSet};
 use indexmap::IndexMap;
-use ndarray::Array2;
+use ndarray::{Array2, ArrayView2};
 use rand::prelude::*;
 use rand_pcg::Pcg64Mcg;
 use rayon_cond::CondIterator;
@@ -35,7 +36,7 @@ use smallvec::{smallvec, SmallVec};
 use qiskit_circuit::circuit_instruction::OperationFromPython;
 use qiskit_circuit::dag_circuit::{DAGCircuit, DAGCircuitBuilder, NodeType, Wire};
 use qiskit_circuit::nlayout::NLayout;
-use qiskit_circuit::operations::{OperationRef, StandardGate};
+use qiskit_circuit::operations::{OperationRef, StandardGate, StandardInstruction};
 use qiskit_circuit::packed_instruction::PackedInstruction;
 use qiskit_circuit::{getenv_use_multiple_threads, imports, PhysicalQubit, Qubit, VirtualQubit};
 
@@ -43,8 +44,11 @@ use crate::target::{Target, TargetCouplingError};
 use crate::TranspilerError;
 
 use super::dag::{InteractionKind, SabreDAG};
-use super::distance::distance_matrix;
-use super::heuristic::{BasicHeuristic, DecayHeuristic, Heuristic, LookaheadHeuristic, SetScaling};
+use super::distance::{distance_matrix, weighted_distance_matrix};
+use super::heuristic::{
+    BasicHeuristic, BridgeHeuristic, DecayHeuristic, FidelityHeuristic, Heuristic,
+    LookaheadHeuristic,
+};
 use super::layer::{ExtendedSet, FrontLayer};
 use super::neighbors::Neighbors;
 
@@ -82,6 +86,10 @@ enum RoutedItemKind {
     /// out-of-band of the item kind because control-flow is expected to be very uncommon, and we
     /// don't want to needless increase the size of the enum.
     ControlFlow(ControlFlowBlockCount),
+    /// A distance-two CX gate implemented as a four-CX bridge through a middle qubit, which
+    /// leaves the layout unchanged.  The middle qubit is the next one out of
+    /// [RoutingResult::bridges]; like control flow, it's stored out-of-band to keep the enum small.
+    Bridge,
 }
 struct RoutedItem {
     initial_swaps: Option<Box<[[PhysicalQubit; 2]]>>,
@@ -103,6 +111,7 @@ pub struct RoutingResult<'a> {
     order: Vec<RoutedItem>,
     final_swaps: Vec<[PhysicalQubit; 2]>,
     control_flow: Vec<RoutingResult<'a>>,
+    bridges: Vec<PhysicalQubit>,
 }
 impl RoutingResult<'_> {
     /// Count the number of swaps inserted at the top level (i.e. without recursing into
@@ -114,10 +123,63 @@ impl RoutingResult<'_> {
             .sum()
     }
 
+    /// Count the number of gates routed by bridges at the top level (i.e. without recursing into
+    /// control-flow operations).
+    pub fn bridge_count(&self) -> usize {
+        self.bridges.len()
+    }
+
+    /// The cost of the routing in units of swaps, for comparing trials.  A bridge adds three CX
+    /// gates over the gate it replaces, just like a swap, so each counts once.
+    pub fn routing_cost(&self) -> usize {
+        self.swap_count() + self.bridge_count()
+    }
+
     fn num_qubits(&self) -> usize {
         self.initial_layout.num_qubits()
     }
 
+    /// Is this node of the DAG a measurement with nothing after it on any of its wires?
+    fn is_terminal_measure(&self, index: NodeIndex) -> bool {
+        let NodeType::Operation(inst) = &self.dag[index] else {
+            return false;
+        };
+        matches!(
+            inst.op.view(),
+            OperationRef::StandardInstruction(StandardInstruction::Measure)
+        ) && self
+            .dag
+            .dag()
+            .neighbors_directed(index, Direction::Outgoing)
+            .all(|node| {
+                matches!(
+                    self.dag[node],
+                    NodeType::QubitOut(_) | NodeType::ClbitOut(_)
+                )
+            })
+    }
+
+    /// Finish the routing by pushing the remaining permutation into the terminal measurements,
+    /// rather than leaving it implicit in [final_layout].
+    ///
+    /// The terminal measurements are moved to the end of the circuit, after every swap, so each
+    /// measures its virtual qubit at that qubit's physical position in [final_layout], and still
+    /// writes it to the same clbit.  No swaps are added; the permutation of the measured qubits is
+    /// absorbed by where they are measured, and [final_layout] reports the full permutation.
+    ///
+    /// This should only be called on a top-level result, after the choice of trial is made.
+    pub fn absorb_final_permutation(&mut self) {
+        let (terminal, mut order): (Vec<_>, Vec<_>) = ::std::mem::take(&mut self.order)
+            .into_iter()
+            .partition(|item| {
+                matches!(item.kind, RoutedItemKind::Simple)
+                    && self.is_terminal_measure(self.sabre.dag[item.node].index)
+            });
+        debug_assert!(terminal.iter().all(|item| item.initial_swaps.is_none()));
+        order.extend(terminal);
+        self.order = order;
+    }
+
     /// Rebuild the physical circuit from the virtual DAG, using the natural width of the target of
     /// this component.
     ///
@@ -126,10 +188,11 @@ impl RoutingResult<'_> {
     /// suitable mappings back to the full-width [PhysicalQubit] instances instead.
     pub fn rebuild(&self) -> PyResult<DAGCircuit> {
         let num_swaps = self.swap_count();
+        let num_bridges = self.bridge_count();
         let dag = self.dag.physical_empty_like_with_capacity(
             self.num_qubits(),
-            self.dag.num_ops() + num_swaps,
-            self.dag.dag().edge_count() + 2 * num_swaps,
+            self.dag.num_ops() + num_swaps + 3 * num_bridges,
+            self.dag.dag().edge_count() + 2 * num_swaps + 6 * num_bridges,
         )?;
         self.rebuild_onto(dag, |q| q)
     }
@@ -177,9 +240,37 @@ impl RoutingResult<'_> {
             dag.push_back(new_inst)
         };
 
+        let apply_bridge = |inst: &PackedInstruction,
+                            middle: PhysicalQubit,
+                            layout: &NLayout,
+                            dag: &mut DAGCircuitBuilder|
+         -> PyResult<NodeIndex> {
+            // CX(c, t) == CX(c, m) CX(m, t) CX(c, m) CX(m, t) for any state of the middle qubit.
+            let qubits = self.dag.get_qargs(inst.qubits);
+            let [control, middle, target] = [
+                VirtualQubit(qubits[0].0).to_phys(layout),
+                middle,
+                VirtualQubit(qubits[1].0).to_phys(layout),
+            ]
+            .map(|q| Qubit(map_fn(q).0));
+            let mut push_cx = |qargs: [Qubit; 2]| {
+                let cx = PackedInstruction::from_standard_gate(
+                    StandardGate::CX,
+                    None,
+                    dag.insert_qargs(&qargs),
+                );
+                dag.push_back(cx)
+            };
+            push_cx([control, middle])?;
+            push_cx([middle, target])?;
+            push_cx([control, middle])?;
+            push_cx([middle, target])
+        };
+
         let mut dag = dag.into_builder();
         let mut layout = self.initial_layout.clone();
         let mut blocks = self.control_flow.iter();
+        let mut bridges = self.bridges.iter();
         for node in &self.sabre.initial {
             let NodeType::Operation(inst) = &self.dag[*node] else {
                 panic!("Sabre DAG should only contain op nodes");
@@ -196,6 +287,10 @@ impl RoutingResult<'_> {
             };
             match item.kind {
                 RoutedItemKind::Simple => apply_op(inst, &layout, &mut dag)?,
+                RoutedItemKind::Bridge => {
+                    let middle = bridges.next().expect("every bridge has a middle qubit");
+                    apply_bridge(inst, *middle, &layout, &mut dag)?
+                }
                 RoutedItemKind::ControlFlow(num_blocks) => {
                     let blocks = blocks
                         .by_ref()
@@ -276,15 +371,102 @@ impl RoutingResult<'_> {
 pub struct RoutingTarget {
     pub neighbors: Neighbors,
     pub distance: Array2<f64>,
+    /// The weight of each edge (keyed with the lower qubit first) from its two-qubit gate error,
+    /// normalized so the mean edge weight is 1.0.  This is `None` if the target had no two-qubit
+    /// error information, in which case the fidelity heuristic has no effect.
+    error_weights: Option<HashMap<[PhysicalQubit; 2], f64>>,
+    /// The shortest-path distances over the `error_weights`.  This is an all-pairs calculation,
+    /// so is only done the first time a heuristic that uses it asks for it.
+    error_distance: OnceLock<Option<Array2<f64>>>,
 }
 impl RoutingTarget {
     pub fn from_neighbors(neighbors: Neighbors) -> Self {
         Self {
             distance: distance_matrix(&neighbors, usize::MAX, f64::NAN),
             neighbors,
+            error_weights: None,
+            error_distance: OnceLock::new(),
         }
     }
 
+    /// Set the error weights of the edges from the two-qubit gate errors in `target`.
+    ///
+    /// `qubit_fn` maps the physical qubits of this routing target onto the qubits of `target`,
+    /// for cases where the routing target is a subset of the full device.
+    ///
+    /// Each edge is weighted by `-log(1 - error)` of the lowest-error operation defined on it in
+    /// either direction.  Edges with no error information are assigned the mean weight of the
+    /// edges that have it.  Errors are clamped below 1 so that weights are always finite.  The
+    /// error-weighted distance matrix itself is only calculated on first use by
+    /// [RoutingTarget::error_distance].
+    pub fn with_error_distance(
+        mut self,
+        target: &Target,
+        qubit_fn: impl Fn(PhysicalQubit) -> PhysicalQubit,
+    ) -> Self {
+        const MAX_ERROR: f64 = 1.0 - 1e-6;
+        let edge_error = |a: PhysicalQubit, b: PhysicalQubit| -> Option<f64> {
+            [[qubit_fn(a), qubit_fn(b)], [qubit_fn(b), qubit_fn(a)]]
+                .into_iter()
+                .flat_map(|qargs| {
+                    target
+                        .operation_names_for_qargs(&qargs)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter_map(move |name| target.get_error(name, &qargs))
+                })
+                .min_by(f64::total_cmp)
+        };
+        let mut weights = HashMap::new();
+        for a in 0..self.num_qubits() {
+            let a = PhysicalQubit::new(a as u32);
+            for &b in self.neighbors[a].iter().filter(|b| **b > a) {
+                if let Some(error) = edge_error(a, b) {
+                    weights.insert([a, b], -(-error.clamp(0.0, MAX_ERROR)).ln_1p());
+                }
+            }
+        }
+        self.error_distance = OnceLock::new();
+        self.error_weights = None;
+        if weights.is_empty() {
+            return self;
+        }
+        let mean = weights.values().sum::<f64>() / (weights.len() as f64);
+        if mean <= 0.0 {
+            // Every known error is zero, so there's nothing to distinguish the edges.
+            return self;
+        }
+        weights.values_mut().for_each(|weight| *weight /= mean);
+        self.error_weights = Some(weights);
+        self
+    }
+
+    /// Shortest-path distances where each edge is weighted by the error of its best two-qubit
+    /// gate, normalized so the mean edge weight is 1.0.  This is `None` if the target had no
+    /// two-qubit error information.
+    ///
+    /// The matrix is calculated the first time this is called.
+    pub fn error_distance(&self) -> Option<&Array2<f64>> {
+        self.error_distance
+            .get_or_init(|| {
+                let weights = self.error_weights.as_ref()?;
+                let weight_fn = |a: usize, b: usize| {
+                    let (a, b) = (a.min(b) as u32, a.max(b) as u32);
+                    weights
+                        .get(&[PhysicalQubit::new(a), PhysicalQubit::new(b)])
+                        .copied()
+                        .unwrap_or(1.0)
+                };
+                Some(weighted_distance_matrix(
+                    &self.neighbors,
+                    weight_fn,
+                    usize::MAX,
+                    f64::NAN,
+                ))
+            })
+            .as_ref()
+    }
+
     #[inline]
     pub fn num_qubits(&self) -> usize {
         self.neighbors.num_qubits()
@@ -309,9 +491,10 @@ impl PyRoutingTarget {
                 return Err(TranspilerError::new_err(e.to_string()))
             }
         };
-        Ok(Self(Some(RoutingTarget::from_neighbors(
-            Neighbors::from_coupling(&coupling),
-        ))))
+        Ok(Self(Some(
+            RoutingTarget::from_neighbors(Neighbors::from_coupling(&coupling))
+                .with_error_distance(target, |q| q),
+        )))
     }
 
     fn coupling_list(&self) -> Option<Vec<[PhysicalQubit; 2]>> {
@@ -328,6 +511,13 @@ impl PyRoutingTarget {
     fn distance_matrix<'py>(&self, py: Python<'py>) -> Option<Bound<'py, PyArray2<f64>>> {
         self.0.as_ref().map(|target| target.distance.to_pyarray(py))
     }
+
+    fn error_distance_matrix<'py>(&self, py: Python<'py>) -> Option<Bound<'py, PyArray2<f64>>> {
+        self.0
+            .as_ref()
+            .and_then(|target| target.error_distance())
+            .map(|distance| distance.to_pyarray(py))
+    }
 }
 
 /// Helper record struct for a Sabre routing problem.
@@ -362,6 +552,7 @@ struct RoutingState<'a> {
     layout: NLayout,
     order: Vec<RoutedItem>,
     control_flow: Vec<RoutingResult<'a>>,
+    bridges: Vec<PhysicalQubit>,
     decay: Vec<f64>,
     /// How many predecessors still need to be satisfied for each node index before it is at the
     /// front of the topological iteration through the nodes as they're routed.
@@ -654,6 +845,114 @@ impl<'a> RoutingState<'a> {
         }
     }
 
+    /// Score a swap by its effect on every gate in the heuristic sets except the front-layer gate
+    /// currently on `qubits`.  This only uses the set-based components of the heuristic (basic,
+    /// lookahead and fidelity), not the decay.
+    fn score_swap_excluding(&self, swap: [PhysicalQubit; 2], qubits: [PhysicalQubit; 2]) -> f64 {
+        let swapped = |q: PhysicalQubit| {
+            if q == swap[0] {
+                swap[1]
+            } else if q == swap[1] {
+                swap[0]
+            } else {
+                q
+            }
+        };
+        let [a, b] = qubits;
+        let (new_a, new_b) = (swapped(a), swapped(b));
+        let front_score = |dist: &ArrayView2<f64>| {
+            self.front_layer.score(swap, dist) - dist[[new_a.index(), new_b.index()]]
+                + dist[[a.index(), b.index()]]
+        };
+        let dist = &self.target.distance.view();
+        let mut score = 0.0;
+        if let Some(BasicHeuristic { weight, scale }) = self.heuristic.basic {
+            score += scale.scale(weight, self.front_layer.len()) * front_score(dist);
+        }
+        if let Some(LookaheadHeuristic { weight, scale, .. }) = self.heuristic.lookahead {
+            score +=
+                scale.scale(weight, self.extended_set.len()) * self.extended_set.score(swap, dist);
+        }
+        if let (Some(FidelityHeuristic { weight, scale }), Some(error_dist)) =
+            (self.heuristic.fidelity, self.target.error_distance())
+        {
+            score += scale.scale(weight, self.front_layer.len()) * front_score(&error_dist.view());
+        }
+        score
+    }
+
+    /// Find the best front-layer gate, if any, to route with a bridge rather than a swap, and the
+    /// middle qubit to bridge it through.
+    ///
+    /// Only CX gates between qubits at distance two are candidates.  A gate is bridged if every
+    /// swap that would make it routable worsens the score of the rest of the heuristic sets by more
+    /// than the bridge penalty.  If there are several such gates, the one whose swaps would be the
+    /// worst is chosen.
+    fn choose_bridge(&self) -> Option<(NodeIndex, PhysicalQubit)> {
+        let BridgeHeuristic { penalty } = self.heuristic.bridge?;
+        let dist = &self.target.distance;
+        let mut best = None;
+        let mut best_margin = self.heuristic.best_epsilon;
+        for (&node, &[a, b]) in self.front_layer.iter() {
+            if dist[[a.index(), b.index()]] != 2.0 {
+                continue;
+            }
+            let NodeType::Operation(inst) = &self.dag[self.sabre.dag[node].index] else {
+                panic!("Sabre DAG should only contain op nodes");
+            };
+            if inst.op.try_standard_gate() != Some(StandardGate::CX) {
+                continue;
+            }
+            // If we know the gate errors, bridge through the middle qubit with the best edges.
+            let bridge_cost = |middle: PhysicalQubit| {
+                self.target.error_distance().map_or(0.0, |error_dist| {
+                    error_dist[[a.index(), middle.index()]]
+                        + error_dist[[middle.index(), b.index()]]
+                })
+            };
+            let mut middle: Option<PhysicalQubit> = None;
+            let mut best_swap_score = f64::INFINITY;
+            for &candidate in self.target.neighbors[a].iter() {
+                if !self.target.neighbors.contains_edge(candidate, b) {
+                    continue;
+                }
+                if middle.map_or(true, |middle| bridge_cost(candidate) < bridge_cost(middle)) {
+                    middle = Some(candidate);
+                }
+                for swap in [[a, candidate], [b, candidate]] {
+                    best_swap_score = best_swap_score.min(self.score_swap_excluding(swap, [a, b]));
+                }
+            }
+            let margin = best_swap_score - penalty;
+            if margin > best_margin {
+                best_margin = margin;
+                best = middle.map(|middle| (node, middle));
+            }
+        }
+        best
+    }
+
+    /// Route a front-layer node with a bridge through `middle`, then route everything that
+    /// becomes routable as a result.
+    fn apply_bridge(&mut self, node: NodeIndex, middle: PhysicalQubit) {
+        self.front_layer.remove(&node);
+        self.bridges.push(middle);
+        self.order.push(RoutedItem {
+            initial_swaps: None,
+            node,
+            kind: RoutedItemKind::Bridge,
+        });
+        let mut ready = Vec::new();
+        for edge in self.sabre.dag.edges_directed(node, Direction::Outgoing) {
+            let successor_index = edge.target().index();
+            self.required_predecessors[successor_index] -= 1;
+            if self.required_predecessors[successor_index] == 0 {
+                ready.push(edge.target());
+            }
+        }
+        self.update_route(&ready, None);
+    }
+
     /// Return the swap of two virtual qubits that produces the best score of all possible swaps.
     fn choose_best_swap(&mut self) -> [PhysicalQubit; 2] {
         // Obtain all candidate swaps from the front layer.  A coupling-map edge is a candidate
@@ -673,16 +972,7 @@ impl<'a> RoutingState<'a> {
         let mut absolute_score = 0.0;
 
         if let Some(BasicHeuristic { weight, scale }) = self.heuristic.basic {
-            let weight = match scale {
-                SetScaling::Constant => weight,
-                SetScaling::Size => {
-                    if self.front_layer.is_empty() {
-                        0.0
-                    } else {
-                        weight / (self.front_layer.len() as f64)
-                    }
-                }
-            };
+            let weight = scale.scale(weight, self.front_layer.len());
             absolute_score += weight * self.front_layer.total_score(dist);
             for (swap, score) in self.swap_scores.iter_mut() {
                 *score += weight * self.front_layer.score(*swap, dist);
@@ -690,22 +980,24 @@ impl<'a> RoutingState<'a> {
         }
 
         if let Some(LookaheadHeuristic { weight, scale, .. }) = self.heuristic.lookahead {
-            let weight = match scale {
-                SetScaling::Constant => weight,
-                SetScaling::Size => {
-                    if self.extended_set.is_empty() {
-                        0.0
-                    } else {
-                        weight / (self.extended_set.len() as f64)
-                    }
-                }
-            };
+            let weight = scale.scale(weight, self.extended_set.len());
             absolute_score += weight * self.extended_set.total_score(dist);
             for (swap, score) in self.swap_scores.iter_mut() {
                 *score += weight * self.extended_set.score(*swap, dist);
             }
         }
 
+        if let (Some(FidelityHeuristic { weight, scale }), Some(error_dist)) =
+            (self.heuristic.fidelity, self.target.error_distance())
+        {
+            let weight = scale.scale(weight, self.front_layer.len());
+            let error_dist = &error_dist.view();
+            absolute_score += weight * self.front_layer.total_score(error_dist);
+            for (swap, score) in self.swap_scores.iter_mut() {
+                *score += weight * self.front_layer.score(*swap, error_dist);
+            }
+        }
+
         if let Some(DecayHeuristic { .. }) = self.heuristic.decay {
             for (swap, score) in self.swap_scores.iter_mut() {
                 *score = (absolute_score + *score)
@@ -730,11 +1022,16 @@ impl<'a> RoutingState<'a> {
 
 /// Run Sabre swap on a circuit
 ///
+/// If ``absorb_final_permutation`` is true, the terminal measurements are moved to the end of the
+/// routed circuit, after every swap, so each measures its qubit at its final physical position.
+/// No swaps are added to restore the initial layout.
+///
 /// Returns:
 ///     A two-tuple of the newly routed :class:`.DAGCircuit`, and the layout that maps virtual
 ///     qubits to their assigned physical qubits at the *end* of the circuit execution.
+#[allow(clippy::too_many_arguments)]
 #[pyfunction]
-#[pyo3(signature=(dag, target, heuristic, initial_layout, num_trials, seed=None, run_in_parallel=None))]
+#[pyo3(signature=(dag, target, heuristic, initial_layout, num_trials, seed=None, run_in_parallel=None, absorb_final_permutation=false))]
 pub fn sabre_routing(
     dag: &DAGCircuit,
     target: &PyRoutingTarget,
@@ -743,13 +1040,14 @@ pub fn sabre_routing(
     num_trials: usize,
     seed: Option<u64>,
     run_in_parallel: Option<bool>,
+    absorb_final_permutation: bool,
 ) -> PyResult<(DAGCircuit, NLayout)> {
     let Some(target) = target.0.as_ref() else {
         // All-to-all coupling.
         return Ok((dag.clone(), initial_layout.clone()));
     };
     let sabre = SabreDAG::from_dag(dag)?;
-    let result = swap_map(
+    let mut result = swap_map(
         RoutingProblem {
             target,
             sabre: &sabre,
@@ -761,6 +1059,9 @@ pub fn sabre_routing(
         num_trials,
         run_in_parallel,
     );
+    if absorb_final_permutation {
+        result.absorb_final_permutation();
+    }
     result.rebuild().map(|dag| (dag, result.final_layout))
 }
 
@@ -788,7 +1089,7 @@ pub fn swap_map<'a>(
     )
     .map(|seed| swap_map_trial(problem, initial_layout, seed))
     .enumerate()
-    .min_by_key(|(index, result)| (result.swap_count(), *index))
+    .min_by_key(|(index, result)| (result.routing_cost(), *index))
     .map(|(_, result)| result)
     .expect("must have at least one trial")
 }
@@ -813,6 +1114,7 @@ pub fn swap_map_trial<'a>(
         heuristic,
         order: Vec::with_capacity(problem.sabre.dag.node_count()),
         control_flow: Vec::new(),
+        bridges: Vec::new(),
         front_layer: FrontLayer::new(num_qubits),
         extended_set: ExtendedSet::new(num_qubits),
         decay: vec![1.; num_qubits as usize],
@@ -837,6 +1139,12 @@ pub fn swap_map_trial<'a>(
     let mut num_search_steps = 0;
 
     while !state.front_layer.is_empty() {
+        if let Some((node, middle)) = state.choose_bridge() {
+            state.apply_bridge(node, middle);
+            state.extended_set.clear();
+            state.populate_extended_set();
+            continue;
+        }
         let mut current_swaps: Vec<[PhysicalQubit; 2]> = Vec::new();
         // Swap-mapping loop.  This is the main part of the algorithm, which we repeat until we
         // either successfully route a node, or exceed the maximum number of attempts.
@@ -895,5 +1203,6 @@ pub fn swap_map_trial<'a>(
         order: state.order,
         control_flow: state.control_flow,
         final_swaps: Vec::new(),
+        bridges: state.bridges,
     }
 }
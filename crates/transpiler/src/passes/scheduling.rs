@@ -0,0 +1,621 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2025
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+use hashbrown::HashMap;
+use itertools::Itertools;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use pyo3::wrap_pyfunction;
+use rustworkx_core::petgraph::prelude::*;
+use thiserror::Error;
+
+use qiskit_circuit::dag_circuit::{DAGCircuit, NodeType};
+use qiskit_circuit::dag_node::DAGNode;
+use qiskit_circuit::operations::{DelayUnit, Operation, OperationRef, Param, StandardInstruction};
+use qiskit_circuit::packed_instruction::PackedInstruction;
+use qiskit_circuit::PhysicalQubit;
+
+use crate::target::Target;
+use crate::TranspilerError;
+
+/// The start time of every operation node of a scheduled circuit.
+///
+/// Times are in units of ``dt`` if the target specifies ``dt``, in which case they are always
+/// integral, and in seconds otherwise.
+pub type NodeStartTimes = HashMap<NodeIndex, f64>;
+
+/// The errors that the scheduling passes can return.
+///
+/// Apart from [SchedulingError::Python], these carry their own message, so they can be reported
+/// without a Python interpreter.
+#[derive(Debug, Error)]
+pub enum SchedulingError {
+    #[error("Circuit contains parameterized delays, which cannot be scheduled")]
+    ParameterizedDelay,
+    #[error("Circuit contains delays in dt but the target doesn't specify dt")]
+    DelayInDtWithoutDt,
+    #[error("Scheduling cannot run on circuits with stretch durations.")]
+    Stretch,
+    #[error("Duration of {name} on qubits {qubits:?} is not found.")]
+    MissingDuration { name: String, qubits: Vec<u32> },
+    #[error("Alignment constraints can only be applied if the target specifies dt")]
+    AlignmentWithoutDt,
+    #[error(
+        "Start time of node {0} is not found. This node is likely added after this circuit is \
+        scheduled. Run scheduler again."
+    )]
+    MissingStartTime(usize),
+    #[error("Unknown operation type for {0}.")]
+    UnknownOperation(String),
+    /// A delay duration stored as a Python object could not be read.
+    #[error("Python error: {0}")]
+    Python(#[from] PyErr),
+}
+
+impl From<SchedulingError> for PyErr {
+    fn from(err: SchedulingError) -> PyErr {
+        match err {
+            SchedulingError::Python(err) => err,
+            err => TranspilerError::new_err(err.to_string()),
+        }
+    }
+}
+
+/// How an instruction takes part in the schedule.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum InstructionKind {
+    /// Gates, which only occupy their qubits.
+    Gate,
+    /// Delays, which only occupy their qubits and have no alignment constraint.
+    Delay,
+    /// Measurements, whose clbit write can be delayed by the clbit write latency.
+    Measure,
+    /// Resets, which occupy their qubits and clbits.
+    Reset,
+    /// Compiler directives such as barriers, which take no time.
+    Directive,
+    /// Any other instruction, which occupies its qubits and clbits.
+    Other,
+}
+
+fn instruction_kind(inst: &PackedInstruction) -> InstructionKind {
+    match inst.op.view() {
+        OperationRef::StandardGate(_) | OperationRef::Gate(_) | OperationRef::Unitary(_) => {
+            InstructionKind::Gate
+        }
+        OperationRef::StandardInstruction(StandardInstruction::Delay(_)) => InstructionKind::Delay,
+        OperationRef::StandardInstruction(StandardInstruction::Measure) => InstructionKind::Measure,
+        OperationRef::StandardInstruction(StandardInstruction::Reset) => InstructionKind::Reset,
+        _ if inst.op.directive() => InstructionKind::Directive,
+        _ => InstructionKind::Other,
+    }
+}
+
+/// Scale factor from a delay's unit to seconds, or ``None`` for the non-SI units.
+fn seconds_per_unit(unit: DelayUnit) -> Option<f64> {
+    match unit {
+        DelayUnit::S => Some(1.),
+        DelayUnit::MS => Some(1e-3),
+        DelayUnit::US => Some(1e-6),
+        DelayUnit::NS => Some(1e-9),
+        DelayUnit::PS => Some(1e-12),
+        DelayUnit::DT | DelayUnit::EXPR => None,
+    }
+}
+
+/// Get the duration of a delay, in ``dt`` if the target has ``dt`` and in seconds otherwise.
+fn delay_duration(
+    inst: &PackedInstruction,
+    unit: DelayUnit,
+    dt: Option<f64>,
+) -> Result<f64, SchedulingError> {
+    let value = match &inst.params_view()[0] {
+        Param::Float(value) => *value,
+        Param::Obj(value) => Python::with_gil(|py| value.extract::<f64>(py))?,
+        Param::ParameterExpression(_) | Param::Symbolic(_) => {
+            return Err(SchedulingError::ParameterizedDelay)
+        }
+    };
+    match (unit, dt) {
+        (DelayUnit::DT, Some(_)) => Ok(value),
+        (DelayUnit::DT, None) => Err(SchedulingError::DelayInDtWithoutDt),
+        (DelayUnit::EXPR, _) => Err(SchedulingError::Stretch),
+        (unit, Some(dt)) => Ok((value * seconds_per_unit(unit).unwrap() / dt).round()),
+        (unit, None) => Ok(value * seconds_per_unit(unit).unwrap()),
+    }
+}
+
+/// Get the duration of an instruction in a DAG from the target, in ``dt`` if the target has
+/// ``dt`` and in seconds otherwise.
+///
+/// Delays take their own duration and directives take no time.  If the target has no duration
+/// for any other instruction, this returns ``Ok(None)``.
+pub fn instruction_duration(
+    dag: &DAGCircuit,
+    target: &Target,
+    inst: &PackedInstruction,
+) -> Result<Option<f64>, SchedulingError> {
+    if let OperationRef::StandardInstruction(StandardInstruction::Delay(unit)) = inst.op.view() {
+        return delay_duration(inst, unit, target.dt).map(Some);
+    }
+    if inst.op.directive() {
+        return Ok(Some(0.));
+    }
+    let qubits = dag
+        .get_qargs(inst.qubits)
+        .iter()
+        .map(|q| PhysicalQubit::new(q.0))
+        .collect::<Vec<_>>();
+    Ok(target
+        .get_duration(inst.op.name(), &qubits)
+        .map(|duration| match target.dt {
+            Some(dt) => (duration / dt).round(),
+            None => duration,
+        }))
+}
+
+/// Like [instruction_duration], but fail if the target does not specify the duration.
+fn required_instruction_duration(
+    dag: &DAGCircuit,
+    target: &Target,
+    inst: &PackedInstruction,
+) -> Result<f64, SchedulingError> {
+    instruction_duration(dag, target, inst)?.ok_or_else(|| SchedulingError::MissingDuration {
+        name: inst.op.name().to_string(),
+        qubits: dag.get_qargs(inst.qubits).iter().map(|q| q.0).collect(),
+    })
+}
+
+fn operation(dag: &DAGCircuit, node: NodeIndex) -> &PackedInstruction {
+    match &dag[node] {
+        NodeType::Operation(inst) => inst,
+        _ => unreachable!("op node iterators only yield operation nodes"),
+    }
+}
+
+/// Schedule the operation nodes of a physical circuit as soon as possible.
+///
+/// Instruction durations are read from ``target``, and delays in SI units are converted to
+/// ``dt`` if the target specifies it.  ``clbit_write_latency`` is the time, in the same unit as
+/// the returned start times, after which a measurement starts writing to its clbits.  If the
+/// target has a ``pulse_alignment`` or ``acquire_alignment`` other than 1, the start times are
+/// then adjusted by [run_constrained_reschedule].
+pub fn run_asap_schedule_analysis(
+    dag: &DAGCircuit,
+    target: &Target,
+    clbit_write_latency: f64,
+) -> Result<NodeStartTimes, SchedulingError> {
+    let mut node_start_time = NodeStartTimes::with_capacity(dag.num_ops());
+    let mut qubit_idle_after = vec![0.; dag.num_qubits()];
+    let mut clbit_idle_after = vec![0.; dag.num_clbits()];
+    for node in dag
+        .topological_op_nodes()
+        .expect("Unexpected error in dag.topological_op_nodes()")
+    {
+        let inst = operation(dag, node);
+        let duration = required_instruction_duration(dag, target, inst)?;
+        let qubits = dag.get_qargs(inst.qubits);
+        let clbits = dag.get_cargs(inst.clbits);
+        let t0q = qubits
+            .iter()
+            .map(|q| qubit_idle_after[q.index()])
+            .fold(0., f64::max);
+        let t0c = clbits
+            .iter()
+            .map(|c| clbit_idle_after[c.index()])
+            .fold(0., f64::max);
+        let t0 = match instruction_kind(inst) {
+            InstructionKind::Gate | InstructionKind::Delay => t0q,
+            // The clbits are only written to after the clbit write latency, so the measurement
+            // can start before the clbits are free.
+            InstructionKind::Measure => {
+                let t0 = t0q.max(t0c - clbit_write_latency);
+                for clbit in clbits {
+                    clbit_idle_after[clbit.index()] = t0 + duration;
+                }
+                t0
+            }
+            InstructionKind::Reset | InstructionKind::Directive | InstructionKind::Other => {
+                t0q.max(t0c)
+            }
+        };
+        for qubit in qubits {
+            qubit_idle_after[qubit.index()] = t0 + duration;
+        }
+        node_start_time.insert(node, t0);
+    }
+    apply_alignment(dag, target, &mut node_start_time, clbit_write_latency)?;
+    Ok(node_start_time)
+}
+
+/// Schedule the operation nodes of a physical circuit as late as possible.
+///
+/// The arguments and units are the same as for [run_asap_schedule_analysis].
+pub fn run_alap_schedule_analysis(
+    dag: &DAGCircuit,
+    target: &Target,
+    clbit_write_latency: f64,
+) -> Result<NodeStartTimes, SchedulingError> {
+    // The nodes are packed against the end of the circuit by walking it in reverse, so the
+    // times computed in the loop are measured backwards from the end of the circuit.
+    let mut node_stop_time = NodeStartTimes::with_capacity(dag.num_ops());
+    let mut qubit_idle_before = vec![0.; dag.num_qubits()];
+    let mut clbit_idle_before = vec![0.; dag.num_clbits()];
+    let nodes = dag
+        .topological_op_nodes()
+        .expect("Unexpected error in dag.topological_op_nodes()")
+        .collect::<Vec<_>>();
+    for node in nodes.into_iter().rev() {
+        let inst = operation(dag, node);
+        let duration = required_instruction_duration(dag, target, inst)?;
+        let qubits = dag.get_qargs(inst.qubits);
+        let clbits = dag.get_cargs(inst.clbits);
+        let t0q = qubits
+            .iter()
+            .map(|q| qubit_idle_before[q.index()])
+            .fold(0., f64::max);
+        let t0c = clbits
+            .iter()
+            .map(|c| clbit_idle_before[c.index()])
+            .fold(0., f64::max);
+        let t0 = match instruction_kind(inst) {
+            InstructionKind::Gate | InstructionKind::Delay => t0q,
+            // Clbit access is always right-justified within the measurement.
+            InstructionKind::Measure => {
+                let t0 = t0q.max(t0c);
+                for clbit in clbits {
+                    clbit_idle_before[clbit.index()] = t0 + duration - clbit_write_latency;
+                }
+                t0
+            }
+            InstructionKind::Reset | InstructionKind::Directive | InstructionKind::Other => {
+                t0q.max(t0c)
+            }
+        };
+        for qubit in qubits {
+            qubit_idle_before[qubit.index()] = t0 + duration;
+        }
+        node_stop_time.insert(node, t0 + duration);
+    }
+    let circuit_duration = qubit_idle_before
+        .iter()
+        .chain(clbit_idle_before.iter())
+        .copied()
+        .fold(0., f64::max);
+    let mut node_start_time = node_stop_time
+        .into_iter()
+        .map(|(node, t1)| (node, circuit_duration - t1))
+        .collect();
+    apply_alignment(dag, target, &mut node_start_time, clbit_write_latency)?;
+    Ok(node_start_time)
+}
+
+/// Apply the alignment constraints of the target to a schedule, if it has any.
+fn apply_alignment(
+    dag: &DAGCircuit,
+    target: &Target,
+    node_start_time: &mut NodeStartTimes,
+    clbit_write_latency: f64,
+) -> Result<(), SchedulingError> {
+    if target.pulse_alignment == 1 && target.acquire_alignment == 1 {
+        return Ok(());
+    }
+    run_constrained_reschedule(dag, target, node_start_time, clbit_write_latency)
+}
+
+/// Shift the start times of a scheduled circuit so that they satisfy the ``pulse_alignment``
+/// and ``acquire_alignment`` constraints of the target.
+///
+/// Gates must start at a multiple of the pulse alignment, and measurements and resets at a
+/// multiple of the acquire alignment; delays and directives may start at any time.  Nodes are
+/// visited in topological order, and each node that is moved pushes back any of its immediate
+/// successors that it would now overlap.  The alignments are in units of ``dt``, so the target
+/// must specify ``dt``.
+pub fn run_constrained_reschedule(
+    dag: &DAGCircuit,
+    target: &Target,
+    node_start_time: &mut NodeStartTimes,
+    clbit_write_latency: f64,
+) -> Result<(), SchedulingError> {
+    if target.dt.is_none() {
+        return Err(SchedulingError::AlignmentWithoutDt);
+    }
+    let pulse_alignment = target.pulse_alignment as f64;
+    let acquire_alignment = target.acquire_alignment as f64;
+    for node in dag
+        .topological_op_nodes()
+        .expect("Unexpected error in dag.topological_op_nodes()")
+    {
+        let Some(&start_time) = node_start_time.get(&node) else {
+            return Err(SchedulingError::MissingStartTime(node.index()));
+        };
+        // Every instruction can start at t=0.
+        if start_time == 0. {
+            continue;
+        }
+        let inst = operation(dag, node);
+        let kind = instruction_kind(inst);
+        let alignment = match kind {
+            InstructionKind::Gate => Some(pulse_alignment),
+            InstructionKind::Measure | InstructionKind::Reset => Some(acquire_alignment),
+            InstructionKind::Delay | InstructionKind::Directive => None,
+            InstructionKind::Other => {
+                return Err(SchedulingError::UnknownOperation(
+                    inst.op.name().to_string(),
+                ))
+            }
+        };
+        let mut this_t0 = start_time;
+        if let Some(alignment) = alignment {
+            let misalignment = this_t0 % alignment;
+            if misalignment != 0. {
+                this_t0 += alignment - misalignment;
+                node_start_time.insert(node, this_t0);
+            }
+        }
+        let this_t1q = this_t0 + instruction_duration(dag, target, inst)?.unwrap_or(0.);
+        // Only measurements and resets have clbit accesses that end with the instruction.
+        let this_clbits = if matches!(kind, InstructionKind::Measure | InstructionKind::Reset) {
+            dag.get_cargs(inst.clbits)
+        } else {
+            &[]
+        };
+        let this_qubits = dag.get_qargs(inst.qubits);
+
+        for next_node in dag.dag().neighbors_directed(node, Outgoing).unique() {
+            let NodeType::Operation(next_inst) = &dag[next_node] else {
+                continue;
+            };
+            let next_t0q = node_start_time[&next_node];
+            let qreg_overlap = if dag
+                .get_qargs(next_inst.qubits)
+                .iter()
+                .any(|q| this_qubits.contains(q))
+            {
+                this_t1q - next_t0q
+            } else {
+                0.
+            };
+            // Clbit access of the successor starts after the write latency.
+            let creg_overlap = if matches!(
+                instruction_kind(next_inst),
+                InstructionKind::Measure | InstructionKind::Reset
+            ) && dag
+                .get_cargs(next_inst.clbits)
+                .iter()
+                .any(|c| this_clbits.contains(c))
+            {
+                this_t1q - (next_t0q + clbit_write_latency)
+            } else {
+                0.
+            };
+            let overlap = qreg_overlap.max(creg_overlap);
+            if overlap > 0. {
+                node_start_time.insert(next_node, next_t0q + overlap);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn start_times_to_py(
+    py: Python,
+    dag: &DAGCircuit,
+    target: &Target,
+    node_start_time: NodeStartTimes,
+) -> PyResult<Py<PyDict>> {
+    let out_dict = PyDict::new(py);
+    for (node, time) in node_start_time {
+        let py_node = dag.get_node(py, node)?;
+        if target.dt.is_some() {
+            out_dict.set_item(py_node, time as i64)?;
+        } else {
+            out_dict.set_item(py_node, time)?;
+        }
+    }
+    Ok(out_dict.unbind())
+}
+
+/// Schedule a physical circuit as soon as possible, with durations from the target.
+///
+/// Args:
+///     dag (DAGCircuit): the circuit to schedule.
+///     target (Target): the target to get instruction durations from.
+///     clbit_write_latency (int): the latency of clbit writes by measurements.
+///
+/// Returns:
+///     dict: a mapping of each :class:`.DAGOpNode` to its start time, in ``dt`` if the target
+///     specifies ``dt`` and in seconds otherwise.
+#[pyfunction]
+#[pyo3(name = "asap_schedule_analysis", signature = (dag, target, clbit_write_latency=0.))]
+pub fn py_run_asap_schedule_analysis(
+    py: Python,
+    dag: &DAGCircuit,
+    target: &Target,
+    clbit_write_latency: f64,
+) -> PyResult<Py<PyDict>> {
+    let node_start_time = run_asap_schedule_analysis(dag, target, clbit_write_latency)?;
+    start_times_to_py(py, dag, target, node_start_time)
+}
+
+/// Schedule a physical circuit as late as possible, with durations from the target.
+///
+/// Args:
+///     dag (DAGCircuit): the circuit to schedule.
+///     target (Target): the target to get instruction durations from.
+///     clbit_write_latency (int): the latency of clbit writes by measurements.
+///
+/// Returns:
+///     dict: a mapping of each :class:`.DAGOpNode` to its start time, in ``dt`` if the target
+///     specifies ``dt`` and in seconds otherwise.
+#[pyfunction]
+#[pyo3(name = "alap_schedule_analysis", signature = (dag, target, clbit_write_latency=0.))]
+pub fn py_run_alap_schedule_analysis(
+    py: Python,
+    dag: &DAGCircuit,
+    target: &Target,
+    clbit_write_latency: f64,
+) -> PyResult<Py<PyDict>> {
+    let node_start_time = run_alap_schedule_analysis(dag, target, clbit_write_latency)?;
+    start_times_to_py(py, dag, target, node_start_time)
+}
+
+/// Shift the start times of a scheduled circuit to satisfy the alignment constraints of the
+/// target, in place.
+///
+/// Args:
+///     dag (DAGCircuit): the scheduled circuit.
+///     target (Target): the target to get the alignment constraints and instruction durations
+///         from.  It must specify ``dt``.
+///     node_start_time (dict): a mapping of each :class:`.DAGOpNode` to its start time in
+///         ``dt``, as produced by the scheduling passes.  It is updated with the new start
+///         times.
+///     clbit_write_latency (int): the latency of clbit writes by measurements.
+#[pyfunction]
+#[pyo3(name = "constrained_reschedule", signature = (dag, target, node_start_time, clbit_write_latency=0.))]
+pub fn py_run_constrained_reschedule(
+    dag: &DAGCircuit,
+    target: &Target,
+    node_start_time: &Bound<PyDict>,
+    clbit_write_latency: f64,
+) -> PyResult<()> {
+    let mut py_nodes = HashMap::with_capacity(node_start_time.len());
+    let mut start_times = NodeStartTimes::with_capacity(node_start_time.len());
+    for (py_node, time) in node_start_time.iter() {
+        let node = py_node.downcast::<DAGNode>()?.borrow().node.unwrap();
+        start_times.insert(node, time.extract::<f64>()?);
+        py_nodes.insert(node, py_node);
+    }
+    run_constrained_reschedule(dag, target, &mut start_times, clbit_write_latency)?;
+    for (node, time) in start_times {
+        node_start_time.set_item(&py_nodes[&node], time as i64)?;
+    }
+    Ok(())
+}
+
+pub fn scheduling_mod(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(py_run_asap_schedule_analysis))?;
+    m.add_wrapped(wrap_pyfunction!(py_run_alap_schedule_analysis))?;
+    m.add_wrapped(wrap_pyfunction!(py_run_constrained_reschedule))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qiskit_circuit::circuit_data::CircuitData;
+    use qiskit_circuit::operations::StandardGate;
+    use qiskit_circuit::packed_instruction::PackedOperation;
+    use qiskit_circuit::{Clbit, Qubit};
+
+    use crate::test_utils::scheduling_target;
+
+    fn target(alignment: u32) -> Target {
+        scheduling_target(1, alignment)
+    }
+
+    fn measure(circuit: &mut CircuitData, qubit: u32, clbit: u32) {
+        circuit.push_packed_operation(
+            PackedOperation::from_standard_instruction(StandardInstruction::Measure),
+            &[],
+            &[Qubit(qubit)],
+            &[Clbit(clbit)],
+        );
+    }
+
+    /// The start times of the nodes, in the (deterministic) topological order of the DAG.
+    fn start_times(dag: &DAGCircuit, node_start_time: &NodeStartTimes) -> Vec<f64> {
+        dag.topological_op_nodes()
+            .unwrap()
+            .map(|node| node_start_time[&node])
+            .collect()
+    }
+
+    /// `X(0); CX(0, 1); measure(1, 0); X(0)`.  The final `X` comes before the measurement in the
+    /// topological order.
+    fn circuit() -> DAGCircuit {
+        let mut circuit = CircuitData::with_capacity(2, 1, 0, Param::Float(0.)).unwrap();
+        circuit.push_standard_gate(StandardGate::X, &[], &[Qubit(0)]);
+        circuit.push_standard_gate(StandardGate::CX, &[], &[Qubit(0), Qubit(1)]);
+        measure(&mut circuit, 1, 0);
+        circuit.push_standard_gate(StandardGate::X, &[], &[Qubit(0)]);
+        DAGCircuit::from_circuit_data(circuit, false).unwrap()
+    }
+
+    #[test]
+    fn test_asap_start_times() {
+        let dag = circuit();
+        let times = run_asap_schedule_analysis(&dag, &target(1), 0.).unwrap();
+        assert_eq!(start_times(&dag, &times), [0., 160., 660., 660.]);
+    }
+
+    #[test]
+    fn test_alap_start_times() {
+        let dag = circuit();
+        let times = run_alap_schedule_analysis(&dag, &target(1), 0.).unwrap();
+        assert_eq!(start_times(&dag, &times), [0., 160., 1500., 660.]);
+    }
+
+    #[test]
+    fn test_clbit_write_latency() {
+        // Two measurements write to the same clbit; the second can start before the first has
+        // finished writing, by the clbit write latency.
+        let mut circuit = CircuitData::with_capacity(2, 1, 0, Param::Float(0.)).unwrap();
+        measure(&mut circuit, 0, 0);
+        measure(&mut circuit, 1, 0);
+        let dag = DAGCircuit::from_circuit_data(circuit, false).unwrap();
+        let times = run_asap_schedule_analysis(&dag, &target(1), 100.).unwrap();
+        assert_eq!(start_times(&dag, &times), [0., 900.]);
+        let times = run_alap_schedule_analysis(&dag, &target(1), 100.).unwrap();
+        assert_eq!(start_times(&dag, &times), [0., 900.]);
+    }
+
+    #[test]
+    fn test_respect_target_alignment() {
+        // `X(0); delay(100) (0); measure(0, 0); X(0)`.
+        let mut circuit = CircuitData::with_capacity(1, 1, 0, Param::Float(0.)).unwrap();
+        circuit.push_standard_gate(StandardGate::X, &[], &[Qubit(0)]);
+        circuit.push_packed_operation(
+            PackedOperation::from_standard_instruction(StandardInstruction::Delay(DelayUnit::DT)),
+            &[Param::Float(100.)],
+            &[Qubit(0)],
+            &[],
+        );
+        measure(&mut circuit, 0, 0);
+        circuit.push_standard_gate(StandardGate::X, &[], &[Qubit(0)]);
+        let dag = DAGCircuit::from_circuit_data(circuit, false).unwrap();
+        let target = target(16);
+
+        // The measurement is pushed back to the acquire alignment, and the final gate to the
+        // pulse alignment.
+        let times = run_asap_schedule_analysis(&dag, &target, 0.).unwrap();
+        assert_eq!(start_times(&dag, &times), [0., 160., 272., 1280.]);
+        let times = run_alap_schedule_analysis(&dag, &target, 0.).unwrap();
+        assert_eq!(start_times(&dag, &times), [0., 160., 272., 1280.]);
+        // Rescheduling an aligned schedule moves nothing.
+        let mut rescheduled = times.clone();
+        run_constrained_reschedule(&dag, &target, &mut rescheduled, 0.).unwrap();
+        assert_eq!(rescheduled, times);
+    }
+
+    #[test]
+    fn test_constrained_reschedule() {
+        let dag = circuit();
+        let mut times = run_asap_schedule_analysis(&dag, &target(1), 0.).unwrap();
+        run_constrained_reschedule(&dag, &target(16), &mut times, 0.).unwrap();
+        assert_eq!(start_times(&dag, &times), [0., 160., 672., 672.]);
+
+        let mut times = NodeStartTimes::new();
+        let err = run_constrained_reschedule(&dag, &target(16), &mut times, 0.).unwrap_err();
+        assert!(matches!(err, SchedulingError::MissingStartTime(_)));
+    }
+}
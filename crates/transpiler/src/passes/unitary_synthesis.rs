@@ -33,7 +33,7 @@ use qiskit_circuit::converters::{circuit_to_dag, QuantumCircuitData};
 use qiskit_circuit::dag_circuit::{DAGCircuit, DAGCircuitBuilder, NodeType};
 use qiskit_circuit::operations::{Operation, OperationRef, Param, PythonOperation, StandardGate};
 use qiskit_circuit::packed_instruction::{PackedInstruction, PackedOperation};
-use qiskit_circuit::{imports, Qubit, VarsMode};
+use qiskit_circuit::{imports, python_is_initialized, Qubit, VarsMode};
 
 use crate::target::{NormalOperation, Target, TargetOperation};
 use crate::target::{Qargs, QargsRef};
@@ -110,7 +110,7 @@ fn get_euler_basis_set(basis_list: IndexSet<&str, ::ahash::RandomState>) -> Eule
 
 /// Given a `Target`, find an euler basis that is supported for a specific `PhysicalQubit`.
 /// This will determine the available 1q synthesis basis for different decomposers.
-fn get_target_basis_set(target: &Target, qubit: PhysicalQubit) -> EulerBasisSet {
+pub(crate) fn get_target_basis_set(target: &Target, qubit: PhysicalQubit) -> EulerBasisSet {
     let mut target_basis_set: EulerBasisSet = EulerBasisSet::new();
     let target_basis_list = target.operation_names_for_qargs(&[qubit]);
     match target_basis_list {
@@ -154,7 +154,6 @@ fn apply_synth_dag(
 /// and the `qubit_ids` are relative to the subgraph size/orientation,
 /// so `out_qargs` is used to track the final qubit ids where they should be applied.
 fn apply_synth_sequence(
-    py: Python<'_>,
     out_dag: &mut DAGCircuitBuilder,
     out_qargs: &[Qubit],
     sequence: &TwoQubitUnitarySequence,
@@ -179,7 +178,8 @@ fn apply_synth_sequence(
         };
 
         let new_op: PackedOperation = match packed_op.view() {
-            OperationRef::Gate(gate) => {
+            // Python gates only exist while the interpreter is running.
+            OperationRef::Gate(gate) => Python::with_gil(|py| -> PyResult<PackedOperation> {
                 let new_gate = gate.py_copy(py)?;
                 new_gate.gate.setattr(
                     py,
@@ -191,8 +191,8 @@ fn apply_synth_sequence(
                         .map(|param| param.clone_ref(py))
                         .collect::<SmallVec<[Param; 3]>>(),
                 )?;
-                Box::new(new_gate).into()
-            }
+                Ok(Box::new(new_gate).into())
+            })?,
             OperationRef::StandardGate(_) => packed_op.clone(),
             _ => {
                 return Err(QiskitError::new_err(
@@ -226,7 +226,6 @@ fn apply_synth_sequence(
 #[pyfunction]
 #[pyo3(name = "run_main_loop", signature=(dag, qubit_indices, min_qubits, target, basis_gates, synth_gates, coupling_edges, approximation_degree=None, natural_direction=None, pulse_optimize=None))]
 pub fn run_unitary_synthesis(
-    py: Python,
     dag: &mut DAGCircuit,
     qubit_indices: Vec<usize>,
     min_qubits: usize,
@@ -238,10 +237,6 @@ pub fn run_unitary_synthesis(
     natural_direction: Option<bool>,
     pulse_optimize: Option<bool>,
 ) -> PyResult<DAGCircuit> {
-    // We need to use the python converter because the currently available Rust conversion
-    // is lossy. We need `QuantumCircuit` instances to be used in `replace_blocks`.
-    let dag_to_circuit = imports::DAG_TO_CIRCUIT.get_bound(py);
-
     let out_dag = dag.copy_empty_like(VarsMode::Alike)?;
     let mut out_dag = out_dag.into_builder();
 
@@ -249,57 +244,63 @@ pub fn run_unitary_synthesis(
     for node in dag.topological_op_nodes()? {
         let mut packed_instr = dag[node].unwrap_operation().clone();
 
+        // Control-flow operations are Python objects, so there is an interpreter to use here.
         if packed_instr.op.control_flow() {
             let OperationRef::Instruction(py_instr) = packed_instr.op.view() else {
                 unreachable!("Control flow op must be an instruction")
             };
-            let raw_blocks: Vec<PyResult<Bound<PyAny>>> = py_instr
-                .instruction
-                .getattr(py, "blocks")?
-                .bind(py)
-                .try_iter()?
-                .collect();
-            let mut new_blocks = Vec::with_capacity(raw_blocks.len());
-            for raw_block in raw_blocks {
-                let new_ids = dag
-                    .get_qargs(packed_instr.qubits)
-                    .iter()
-                    .map(|qarg| qubit_indices[qarg.0 as usize])
-                    .collect_vec();
-                let res = run_unitary_synthesis(
-                    py,
-                    &mut circuit_to_dag(
-                        QuantumCircuitData::extract_bound(&raw_block?)?,
-                        false,
-                        None,
-                        None,
-                    )?,
-                    new_ids,
-                    min_qubits,
-                    target,
-                    basis_gates.clone(),
-                    synth_gates.clone(),
-                    coupling_edges.clone(),
-                    approximation_degree,
-                    natural_direction,
-                    pulse_optimize,
-                )?;
-                new_blocks.push(dag_to_circuit.call1((res,))?);
-            }
-            let new_node = py_instr
-                .instruction
-                .bind(py)
-                .call_method1("replace_blocks", (new_blocks,))?;
-            let new_node_op: OperationFromPython = new_node.extract()?;
-            packed_instr = PackedInstruction {
-                op: new_node_op.operation,
-                qubits: packed_instr.qubits,
-                clbits: packed_instr.clbits,
-                params: (!new_node_op.params.is_empty()).then(|| Box::new(new_node_op.params)),
-                label: new_node_op.label,
-                #[cfg(feature = "cache_pygates")]
-                py_op: new_node.unbind().into(),
-            };
+            packed_instr = Python::with_gil(|py| -> PyResult<PackedInstruction> {
+                // We need to use the python converter because the currently available Rust
+                // conversion is lossy. We need `QuantumCircuit` instances to be used in
+                // `replace_blocks`.
+                let dag_to_circuit = imports::DAG_TO_CIRCUIT.get_bound(py);
+                let raw_blocks: Vec<PyResult<Bound<PyAny>>> = py_instr
+                    .instruction
+                    .getattr(py, "blocks")?
+                    .bind(py)
+                    .try_iter()?
+                    .collect();
+                let mut new_blocks = Vec::with_capacity(raw_blocks.len());
+                for raw_block in raw_blocks {
+                    let new_ids = dag
+                        .get_qargs(packed_instr.qubits)
+                        .iter()
+                        .map(|qarg| qubit_indices[qarg.0 as usize])
+                        .collect_vec();
+                    let res = run_unitary_synthesis(
+                        &mut circuit_to_dag(
+                            QuantumCircuitData::extract_bound(&raw_block?)?,
+                            false,
+                            None,
+                            None,
+                        )?,
+                        new_ids,
+                        min_qubits,
+                        target,
+                        basis_gates.clone(),
+                        synth_gates.clone(),
+                        coupling_edges.clone(),
+                        approximation_degree,
+                        natural_direction,
+                        pulse_optimize,
+                    )?;
+                    new_blocks.push(dag_to_circuit.call1((res,))?);
+                }
+                let new_node = py_instr
+                    .instruction
+                    .bind(py)
+                    .call_method1("replace_blocks", (new_blocks,))?;
+                let new_node_op: OperationFromPython = new_node.extract()?;
+                Ok(PackedInstruction {
+                    op: new_node_op.operation,
+                    qubits: packed_instr.qubits,
+                    clbits: packed_instr.clbits,
+                    params: (!new_node_op.params.is_empty()).then(|| Box::new(new_node_op.params)),
+                    label: new_node_op.label,
+                    #[cfg(feature = "cache_pygates")]
+                    py_op: new_node.unbind().into(),
+                })
+            })?;
         }
         if !(synth_gates.contains(packed_instr.op.name())
             && packed_instr.op.num_qubits() >= min_qubits as u32)
@@ -378,7 +379,6 @@ pub fn run_unitary_synthesis(
                 match packed_instr.op.view() {
                     OperationRef::Unitary(gate) => {
                         run_2q_unitary_synthesis(
-                            py,
                             gate.matrix_view(),
                             ref_qubits,
                             &coupling_edges,
@@ -395,7 +395,6 @@ pub fn run_unitary_synthesis(
                     _ => match packed_instr.op.matrix(packed_instr.params_view()) {
                         Some(matrix) => {
                             run_2q_unitary_synthesis(
-                                py,
                                 matrix.view(),
                                 ref_qubits,
                                 &coupling_edges,
@@ -413,28 +412,38 @@ pub fn run_unitary_synthesis(
                     },
                 }
             }
-            // Run 3q+ synthesis
+            // Run 3q+ synthesis, which is only available in Python.
             _ => {
                 if basis_gates.is_empty() && target.is_none() {
                     out_dag.push_back(packed_instr.clone())?;
                 } else {
-                    let qs_decomposition: &Bound<'_, PyAny> =
-                        imports::QS_DECOMPOSITION.get_bound(py);
-                    let synth_circ = match packed_instr.op.view() {
-                        OperationRef::Unitary(gate) => {
-                            qs_decomposition.call1((gate.matrix_view().to_pyarray(py),))?
-                        }
-                        _ => match packed_instr.op.matrix(packed_instr.params_view()) {
-                            Some(matrix) => qs_decomposition.call1((matrix.into_pyarray(py),))?,
-                            _ => return Err(QiskitError::new_err("Unitary not found")),
-                        },
-                    };
-                    let synth_dag = circuit_to_dag(
-                        QuantumCircuitData::extract_bound(&synth_circ)?,
-                        false,
-                        None,
-                        None,
-                    )?;
+                    if !python_is_initialized() {
+                        return Err(QiskitError::new_err(format!(
+                            "UnitarySynthesis cannot synthesize a {}-qubit unitary without Python",
+                            packed_instr.op.num_qubits()
+                        )));
+                    }
+                    let synth_dag = Python::with_gil(|py| -> PyResult<DAGCircuit> {
+                        let qs_decomposition: &Bound<'_, PyAny> =
+                            imports::QS_DECOMPOSITION.get_bound(py);
+                        let synth_circ = match packed_instr.op.view() {
+                            OperationRef::Unitary(gate) => {
+                                qs_decomposition.call1((gate.matrix_view().to_pyarray(py),))?
+                            }
+                            _ => match packed_instr.op.matrix(packed_instr.params_view()) {
+                                Some(matrix) => {
+                                    qs_decomposition.call1((matrix.into_pyarray(py),))?
+                                }
+                                _ => return Err(QiskitError::new_err("Unitary not found")),
+                            },
+                        };
+                        circuit_to_dag(
+                            QuantumCircuitData::extract_bound(&synth_circ)?,
+                            false,
+                            None,
+                            None,
+                        )
+                    })?;
                     let out_qargs = dag.get_qargs(packed_instr.qubits);
                     apply_synth_dag(&mut out_dag, out_qargs, &synth_dag)?;
                 }
@@ -536,7 +545,6 @@ fn get_2q_decomposer_from_basis(
 /// return `None``. The list can contain any `DecomposerElement`. This function
 /// will exit early if an ideal decomposition is found.
 fn get_2q_decomposers_from_target(
-    py: Python,
     target: &Target,
     qubits: &[PhysicalQubit; 2],
     approximation_degree: Option<f64>,
@@ -637,12 +645,15 @@ fn get_2q_decomposers_from_target(
             let rxx_equivalent_gate = if let Some(std_gate) = gate.operation.try_standard_gate() {
                 RXXEquivalent::Standard(std_gate)
             } else {
-                let module = PyModule::import(py, "builtins")?;
-                let py_type = module.getattr("type")?;
-                let gate_type = py_type
-                    .call1((gate.clone().into_pyobject(py)?,))?
-                    .downcast_into::<PyType>()?
-                    .unbind();
+                // Non-standard gates are Python objects, so there is an interpreter to use here.
+                let gate_type = Python::with_gil(|py| -> PyResult<Py<PyType>> {
+                    let module = PyModule::import(py, "builtins")?;
+                    let py_type = module.getattr("type")?;
+                    Ok(py_type
+                        .call1((gate.clone().into_pyobject(py)?,))?
+                        .downcast_into::<PyType>()?
+                        .unbind())
+                })?;
 
                 RXXEquivalent::CustomPython(gate_type)
             };
@@ -723,6 +734,36 @@ fn get_2q_decomposers_from_target(
     }
 
     // Step 3: Try XXDecomposers (Python)
+    if !python_is_initialized() {
+        return Ok(Some(decomposers));
+    }
+    Python::with_gil(|py| {
+        add_xx_decomposers(
+            py,
+            target,
+            &qargs,
+            &available_1q_basis,
+            &available_2q_basis,
+            &supercontrolled_basis,
+            approximation_degree,
+            &mut decomposers,
+        )
+    })?;
+    Ok(Some(decomposers))
+}
+
+/// Add the Python-space XXDecomposers for the `controlled` gates of `available_2q_basis` to
+/// `decomposers`.
+fn add_xx_decomposers(
+    py: Python,
+    target: &Target,
+    qargs: &Qargs,
+    available_1q_basis: &IndexSet<&str, ::ahash::RandomState>,
+    available_2q_basis: &IndexMap<&str, (NormalOperation, Option<f64>), ::ahash::RandomState>,
+    supercontrolled_basis: &IndexMap<&str, (NormalOperation, Option<f64>), ::ahash::RandomState>,
+    approximation_degree: Option<f64>,
+    decomposers: &mut Vec<DecomposerElement>,
+) -> PyResult<()> {
     #[inline]
     fn is_controlled(op: &NormalOperation) -> bool {
         match op.operation.matrix(&op.params) {
@@ -782,12 +823,12 @@ fn get_2q_decomposers_from_target(
     }
     if basis_2q_fidelity_dict.len() > 0 {
         let xx_decomposer: &Bound<'_, PyAny> = imports::XX_DECOMPOSER.get_bound(py);
-        for basis_1q in available_1q_basis {
+        for basis_1q in available_1q_basis.iter().copied() {
             let pi2_decomposer = if let Some(pi_2_basis) = pi2_basis {
                 if pi_2_basis == "cx" && basis_1q == "ZSX" {
                     let fidelity = match approximation_degree {
                         Some(approx_degree) => approx_degree,
-                        None => match &target["cx"][&qargs] {
+                        None => match &target["cx"][qargs] {
                             Some(props) => 1.0 - props.error.unwrap_or_default(),
                             None => 1.0,
                         },
@@ -823,7 +864,7 @@ fn get_2q_decomposers_from_target(
             });
         }
     }
-    Ok(Some(decomposers))
+    Ok(())
 }
 
 /// Function to evaluate hardware-native direction, this allows to correct
@@ -1128,7 +1169,6 @@ fn reversed_synth_su4_dag(
 
 /// Score the synthesis output (DAG or sequence) based on the expected gate fidelity/error score.
 fn synth_error(
-    py: Python<'_>,
     synth_circuit: impl Iterator<
         Item = (
             String,
@@ -1153,20 +1193,27 @@ fn synth_error(
                     else {
                         continue;
                     };
-                    let are_params_close = if let Some(params) = inst_params {
-                        params.iter().zip(target_op.params.iter()).all(|(p1, p2)| {
-                            p1.is_close(py, p2, 1e-10)
-                                .expect("Unexpected parameter expression error.")
-                        })
-                    } else {
-                        false
+                    let are_params_close = || {
+                        if let Some(params) = inst_params {
+                            params.iter().zip(target_op.params.iter()).all(|(p1, p2)| {
+                                match (p1, p2) {
+                                    (Param::Float(p1), Param::Float(p2)) => {
+                                        relative_eq!(p1, p2, max_relative = 1e-10)
+                                    }
+                                    // Anything else can only have come from Python.
+                                    _ => Python::with_gil(|py| p1.is_close(py, p2, 1e-10))
+                                        .expect("Unexpected parameter expression error."),
+                                }
+                            })
+                        } else {
+                            false
+                        }
                     };
-                    let is_parametrized = target_op
-                        .params
-                        .iter()
-                        .any(|param| matches!(param, Param::ParameterExpression(_)));
+                    let is_parametrized = target_op.params.iter().any(|param| {
+                        matches!(param, Param::ParameterExpression(_) | Param::Symbolic(_))
+                    });
                     if target_op.operation.name() == inst_name
-                        && (is_parametrized || are_params_close)
+                        && (is_parametrized || are_params_close())
                     {
                         match target[name].get(&QargsRef::from(inst_qubits)) {
                             Some(Some(props)) => {
@@ -1192,7 +1239,6 @@ fn synth_error(
 /// the decompostion will use the given `basis_gates` and the first valid decomposition
 /// will be returned (no selection).
 fn run_2q_unitary_synthesis(
-    py: Python,
     unitary: ArrayView2<Complex64>,
     ref_qubits: &[PhysicalQubit; 2],
     coupling_edges: &HashSet<[PhysicalQubit; 2]>,
@@ -1209,7 +1255,6 @@ fn run_2q_unitary_synthesis(
     let decomposers = match target {
         Some(target) => {
             let decomposers_2q = get_2q_decomposers_from_target(
-                py,
                 target,
                 ref_qubits,
                 approximation_degree,
@@ -1250,7 +1295,7 @@ fn run_2q_unitary_synthesis(
                     preferred_dir,
                     approximation_degree,
                 )?;
-                apply_synth_sequence(py, out_dag, out_qargs, &synth)?;
+                apply_synth_sequence(out_dag, out_qargs, &synth)?;
             }
             DecomposerType::TwoQubitControlledU(_) => {
                 let synth = synth_su4_sequence(
@@ -1259,16 +1304,18 @@ fn run_2q_unitary_synthesis(
                     preferred_dir,
                     approximation_degree,
                 )?;
-                apply_synth_sequence(py, out_dag, out_qargs, &synth)?;
+                apply_synth_sequence(out_dag, out_qargs, &synth)?;
             }
             DecomposerType::XX(_) => {
-                let synth = synth_su4_dag(
-                    py,
-                    unitary,
-                    decomposer_item,
-                    preferred_dir,
-                    approximation_degree,
-                )?;
+                let synth = Python::with_gil(|py| {
+                    synth_su4_dag(
+                        py,
+                        unitary,
+                        decomposer_item,
+                        preferred_dir,
+                        approximation_degree,
+                    )
+                })?;
                 apply_synth_dag(out_dag, out_qargs, &synth)?;
             }
         }
@@ -1307,7 +1354,7 @@ fn run_2q_unitary_synthesis(
                         ),
                     }
                 });
-        let score = synth_error(py, scoring_info, target.unwrap());
+        let score = synth_error(scoring_info, target.unwrap());
         Ok((sequence, score))
     };
 
@@ -1327,8 +1374,9 @@ fn run_2q_unitary_synthesis(
                 synth_errors_sequence.push(synth_sequence(decomposer, preferred_dir)?);
             }
             DecomposerType::XX(_) => {
-                let synth_dag =
-                    synth_su4_dag(py, unitary, decomposer, preferred_dir, approximation_degree)?;
+                let synth_dag = Python::with_gil(|py| {
+                    synth_su4_dag(py, unitary, decomposer, preferred_dir, approximation_degree)
+                })?;
                 let scoring_info = synth_dag
                     .topological_op_nodes()
                     .expect("Unexpected error in dag.topological_op_nodes()")
@@ -1347,7 +1395,7 @@ fn run_2q_unitary_synthesis(
                             inst_qubits,
                         )
                     });
-                let score = synth_error(py, scoring_info, target.unwrap());
+                let score = synth_error(scoring_info, target.unwrap());
                 synth_errors_dag.push((synth_dag, score));
             }
         }
@@ -1368,13 +1416,13 @@ fn run_2q_unitary_synthesis(
 
     match (synth_sequence, synth_dag) {
         (None, None) => apply_original_op(out_dag)?,
-        (Some((sequence, _)), None) => apply_synth_sequence(py, out_dag, out_qargs, sequence)?,
+        (Some((sequence, _)), None) => apply_synth_sequence(out_dag, out_qargs, sequence)?,
         (None, Some((dag, _))) => apply_synth_dag(out_dag, out_qargs, dag)?,
         (Some((sequence, sequence_error)), Some((dag, dag_error))) => {
             if sequence_error > dag_error {
                 apply_synth_dag(out_dag, out_qargs, dag)?
             } else {
-                apply_synth_sequence(py, out_dag, out_qargs, sequence)?
+                apply_synth_sequence(out_dag, out_qargs, sequence)?
             }
         }
     };
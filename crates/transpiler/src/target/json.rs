@@ -0,0 +1,698 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2025
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! A stable JSON representation of a [Target].
+//!
+//! The format covers standard gates and standard instructions, their per-qargs
+//! [InstructionProperties], the [QubitProperties] and the timing constraints of the device.
+//! Each instruction parameter is either a JSON number for a fixed value, or a JSON string
+//! naming a free parameter, such as the `"theta"` of an `rz(theta)` that accepts any angle.
+//! Variadic operations, custom Python operations and parameter expressions that are not a
+//! single free parameter are rejected when serializing.
+//!
+//! Free parameters are restored as Rust-space [ParameterSymbol]s, so loading a document never
+//! requires Python.  Each name stands for a single parameter within one document: every
+//! instruction that names `"theta"` shares the same parameter.
+//!
+//! The top-level object carries a `version` field, which is [TARGET_JSON_VERSION] for
+//! documents written by this module.  Loading a document with a different version fails.
+
+use std::sync::Arc;
+
+use hashbrown::HashMap;
+use pyo3::prelude::*;
+use pyo3::{intern, PyErr};
+use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
+use thiserror::Error;
+
+use qiskit_circuit::operations::{
+    get_standard_gate_names, DelayUnit, Operation, OperationRef, Param, StandardGate,
+    StandardInstruction,
+};
+use qiskit_circuit::packed_instruction::PackedOperation;
+use qiskit_circuit::parameter_expression::ParameterExpression;
+use qiskit_circuit::parameter_symbol::{ParameterSymbol, SymbolicExpression};
+use qiskit_circuit::symbol_expr::SymbolExpr;
+use qiskit_circuit::PhysicalQubit;
+
+use super::{
+    InstructionProperties, PropsMap, Qargs, QubitProperties, Target, TargetError, TargetOperation,
+};
+
+/// The version of the JSON format written by [Target::to_json].
+pub const TARGET_JSON_VERSION: u32 = 1;
+
+/// Errors that can occur while converting a [Target] to or from JSON.
+#[derive(Debug, Error)]
+pub enum TargetJsonError {
+    /// The document is not valid JSON or does not follow the schema.
+    #[error["Invalid Target JSON: {0}"]]
+    Json(#[from] serde_json::Error),
+    /// The document was written with an incompatible version of the format.
+    #[error["Unsupported Target JSON version {0}, expected {TARGET_JSON_VERSION}."]]
+    UnsupportedVersion(u32),
+    /// The document names a gate that is not a standard gate.
+    #[error["Unknown standard gate '{0}'."]]
+    UnknownGate(String),
+    /// The document names a delay unit that does not exist.
+    #[error["Unknown delay unit '{0}'."]]
+    UnknownDelayUnit(String),
+    /// An instruction in the [Target] has no JSON representation.
+    #[error["Instruction '{instruction}' cannot be represented in JSON: {reason}."]]
+    Unsupported {
+        instruction: String,
+        reason: &'static str,
+    },
+    /// The number of parameters of an instruction does not match its operation.
+    #[error["Instruction '{instruction}' expects {expected} parameters, but {actual} were given."]]
+    ParameterMismatch {
+        instruction: String,
+        expected: u32,
+        actual: usize,
+    },
+    /// The length of the qubit properties does not match the number of qubits.
+    #[error["The number of qubits {num_qubits} does not match the length of the qubit properties {len}."]]
+    QubitPropertiesMismatch { num_qubits: u32, len: usize },
+    /// The instructions could not be added to the [Target].
+    #[error(transparent)]
+    Target(#[from] TargetError),
+    /// A Python-space parameter could not be inspected.
+    #[error["Python error while handling a free parameter: {0}"]]
+    Python(#[from] PyErr),
+}
+
+#[derive(Serialize, Deserialize)]
+struct TargetJson {
+    version: u32,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    num_qubits: Option<u32>,
+    #[serde(default)]
+    dt: Option<f64>,
+    #[serde(default = "default_constraint")]
+    granularity: u32,
+    #[serde(default = "default_constraint")]
+    min_length: u32,
+    #[serde(default = "default_constraint")]
+    pulse_alignment: u32,
+    #[serde(default = "default_constraint")]
+    acquire_alignment: u32,
+    #[serde(default)]
+    qubit_properties: Option<Vec<QubitPropertiesJson>>,
+    #[serde(default)]
+    concurrent_measurements: Option<Vec<Vec<u32>>>,
+    #[serde(default)]
+    instructions: Vec<InstructionJson>,
+}
+
+fn default_constraint() -> u32 {
+    1
+}
+
+#[derive(Serialize, Deserialize)]
+struct QubitPropertiesJson {
+    #[serde(default)]
+    t1: Option<f64>,
+    #[serde(default)]
+    t2: Option<f64>,
+    #[serde(default)]
+    frequency: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct InstructionJson {
+    name: String,
+    operation: OperationJson,
+    #[serde(default)]
+    params: Vec<ParamJson>,
+    properties: Vec<PropertiesEntryJson>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum ParamJson {
+    Float(f64),
+    /// The name of a free parameter.
+    Parameter(String),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum OperationJson {
+    Gate { name: String },
+    Measure,
+    Reset,
+    Barrier { num_qubits: u32 },
+    Delay { unit: String },
+}
+
+#[derive(Serialize, Deserialize)]
+struct PropertiesEntryJson {
+    /// `null` for global properties.
+    qargs: Option<Vec<u32>>,
+    #[serde(default)]
+    properties: Option<InstructionPropertiesJson>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct InstructionPropertiesJson {
+    #[serde(default)]
+    duration: Option<f64>,
+    #[serde(default)]
+    error: Option<f64>,
+}
+
+impl OperationJson {
+    fn from_operation(name: &str, operation: &PackedOperation) -> Result<Self, TargetJsonError> {
+        match operation.view() {
+            OperationRef::StandardGate(gate) => Ok(Self::Gate {
+                name: gate.name().to_string(),
+            }),
+            OperationRef::StandardInstruction(instruction) => Ok(match instruction {
+                StandardInstruction::Measure => Self::Measure,
+                StandardInstruction::Reset => Self::Reset,
+                StandardInstruction::Barrier(num_qubits) => Self::Barrier { num_qubits },
+                StandardInstruction::Delay(unit) => Self::Delay {
+                    unit: unit.to_string(),
+                },
+            }),
+            _ => Err(TargetJsonError::Unsupported {
+                instruction: name.to_string(),
+                reason: "only standard gates and instructions are supported",
+            }),
+        }
+    }
+
+    fn to_operation(&self) -> Result<PackedOperation, TargetJsonError> {
+        match self {
+            Self::Gate { name } => get_standard_gate_names()
+                .iter()
+                .position(|gate| gate == name)
+                .map(|index| ::bytemuck::checked::cast::<u8, StandardGate>(index as u8).into())
+                .ok_or_else(|| TargetJsonError::UnknownGate(name.clone())),
+            Self::Measure => Ok(StandardInstruction::Measure.into()),
+            Self::Reset => Ok(StandardInstruction::Reset.into()),
+            Self::Barrier { num_qubits } => Ok(StandardInstruction::Barrier(*num_qubits).into()),
+            Self::Delay { unit } => {
+                let unit = match unit.as_str() {
+                    "ns" => DelayUnit::NS,
+                    "ps" => DelayUnit::PS,
+                    "us" => DelayUnit::US,
+                    "ms" => DelayUnit::MS,
+                    "s" => DelayUnit::S,
+                    "dt" => DelayUnit::DT,
+                    "expr" => DelayUnit::EXPR,
+                    _ => return Err(TargetJsonError::UnknownDelayUnit(unit.clone())),
+                };
+                Ok(StandardInstruction::Delay(unit).into())
+            }
+        }
+    }
+}
+
+impl ParamJson {
+    fn from_param(name: &str, param: &Param) -> Result<Self, TargetJsonError> {
+        let unsupported = || TargetJsonError::Unsupported {
+            instruction: name.to_string(),
+            reason: "only fixed floating-point parameters and free parameters are supported",
+        };
+        match param {
+            Param::Float(value) => Ok(Self::Float(*value)),
+            Param::ParameterExpression(ob) => Python::with_gil(|py| {
+                let ob = ob.bind(py);
+                // This is either the Rust-space expression itself, or a Python-space
+                // `ParameterExpression` (including `Parameter`) wrapping one.
+                let expr = match ob.extract::<ParameterExpression>() {
+                    Ok(expr) => expr,
+                    Err(_) => ob
+                        .getattr(intern!(py, "_symbol_expr"))?
+                        .extract::<ParameterExpression>()?,
+                };
+                match expr.expr() {
+                    SymbolExpr::Symbol(symbol) => Ok(Self::Parameter(symbol.to_string())),
+                    _ => Err(unsupported()),
+                }
+            }),
+            Param::Symbolic(expr) => expr
+                .as_symbol()
+                .map(|symbol| Self::Parameter(symbol.name().to_string()))
+                .ok_or_else(unsupported),
+            Param::Obj(_) => Err(unsupported()),
+        }
+    }
+}
+
+/// Build the parameters of an instruction, creating each named free parameter only once.
+fn params_from_json(
+    params: Vec<ParamJson>,
+    free_params: &mut HashMap<String, Arc<SymbolicExpression>>,
+) -> SmallVec<[Param; 3]> {
+    params
+        .into_iter()
+        .map(|param| match param {
+            ParamJson::Float(value) => Param::Float(value),
+            ParamJson::Parameter(name) => Param::Symbolic(
+                free_params
+                    .entry(name)
+                    .or_insert_with_key(|name| {
+                        Arc::new(SymbolicExpression::from_symbol(Arc::new(
+                            ParameterSymbol::new_unique(name.clone()),
+                        )))
+                    })
+                    .clone(),
+            ),
+        })
+        .collect()
+}
+
+impl From<&InstructionProperties> for InstructionPropertiesJson {
+    fn from(value: &InstructionProperties) -> Self {
+        Self {
+            duration: value.duration,
+            error: value.error,
+        }
+    }
+}
+
+impl From<InstructionPropertiesJson> for InstructionProperties {
+    fn from(value: InstructionPropertiesJson) -> Self {
+        InstructionProperties::new(value.duration, value.error)
+    }
+}
+
+impl Target {
+    /// Serializes the [Target] into the JSON format described in the [module-level
+    /// documentation](self).
+    ///
+    /// # Returns
+    ///
+    /// * `Ok`: The JSON document as a string.
+    /// * `Err`: [TargetJsonError::Unsupported] if any instruction in the [Target] is not a
+    ///   standard gate or instruction, or has a parameter that is neither a fixed
+    ///   floating-point value nor a single free parameter.
+    pub fn to_json(&self) -> Result<String, TargetJsonError> {
+        let instructions = self
+            .gate_map
+            .iter()
+            .map(|(name, props_map)| {
+                let TargetOperation::Normal(operation) = &self._gate_name_map[name] else {
+                    return Err(TargetJsonError::Unsupported {
+                        instruction: name.clone(),
+                        reason: "variadic operations are not supported",
+                    });
+                };
+                let params = operation
+                    .params
+                    .iter()
+                    .map(|param| ParamJson::from_param(name, param))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let properties = props_map
+                    .iter()
+                    .map(|(qargs, props)| PropertiesEntryJson {
+                        qargs: match qargs {
+                            Qargs::Global => None,
+                            Qargs::Concrete(qargs) => Some(qargs.iter().map(|q| q.0).collect()),
+                        },
+                        properties: props.as_ref().map(InstructionPropertiesJson::from),
+                    })
+                    .collect();
+                Ok(InstructionJson {
+                    name: name.clone(),
+                    operation: OperationJson::from_operation(name, &operation.operation)?,
+                    params,
+                    properties,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let out = TargetJson {
+            version: TARGET_JSON_VERSION,
+            description: self.description.clone(),
+            num_qubits: self.num_qubits,
+            dt: self.dt,
+            granularity: self.granularity,
+            min_length: self.min_length,
+            pulse_alignment: self.pulse_alignment,
+            acquire_alignment: self.acquire_alignment,
+            qubit_properties: self.qubit_properties.as_ref().map(|props| {
+                props
+                    .iter()
+                    .map(|prop| QubitPropertiesJson {
+                        t1: prop.t1,
+                        t2: prop.t2,
+                        frequency: prop.frequency,
+                    })
+                    .collect()
+            }),
+            concurrent_measurements: self.concurrent_measurements.as_ref().map(|groups| {
+                groups
+                    .iter()
+                    .map(|group| group.iter().map(|q| q.0).collect())
+                    .collect()
+            }),
+            instructions,
+        };
+        Ok(serde_json::to_string(&out)?)
+    }
+
+    /// Builds a [Target] from a JSON document produced by [Target::to_json].
+    ///
+    /// Instructions are added in the order they appear in the document, so the resulting
+    /// [Target] is equivalent to the one that was serialized.  Free parameters are restored
+    /// as Rust-space [ParameterSymbol]s, with one shared parameter for each name.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok`: The deserialized [Target].
+    /// * `Err`: [TargetJsonError] if the document is malformed, has an unsupported version,
+    ///   or describes instructions that are inconsistent with their operations.
+    pub fn from_json(json: &str) -> Result<Target, TargetJsonError> {
+        let input: TargetJson = serde_json::from_str(json)?;
+        if input.version != TARGET_JSON_VERSION {
+            return Err(TargetJsonError::UnsupportedVersion(input.version));
+        }
+        let qubit_properties: Option<Vec<QubitProperties>> = input.qubit_properties.map(|props| {
+            props
+                .into_iter()
+                .map(|prop| QubitProperties {
+                    t1: prop.t1,
+                    t2: prop.t2,
+                    frequency: prop.frequency,
+                })
+                .collect()
+        });
+        let num_qubits = match (&qubit_properties, input.num_qubits) {
+            (Some(props), Some(num_qubits)) if num_qubits > 0 => {
+                if num_qubits as usize != props.len() {
+                    return Err(TargetJsonError::QubitPropertiesMismatch {
+                        num_qubits,
+                        len: props.len(),
+                    });
+                }
+                Some(num_qubits)
+            }
+            (Some(props), _) => Some(props.len() as u32),
+            (None, num_qubits) => num_qubits,
+        };
+        let mut target = Target {
+            description: input.description,
+            num_qubits,
+            dt: input.dt,
+            granularity: input.granularity,
+            min_length: input.min_length,
+            pulse_alignment: input.pulse_alignment,
+            acquire_alignment: input.acquire_alignment,
+            qubit_properties,
+            concurrent_measurements: input.concurrent_measurements.map(|groups| {
+                groups
+                    .into_iter()
+                    .map(|group| group.into_iter().map(PhysicalQubit).collect())
+                    .collect()
+            }),
+            ..Default::default()
+        };
+        let mut free_params = HashMap::new();
+        for instruction in input.instructions {
+            let operation = instruction.operation.to_operation()?;
+            // Standard instructions such as `delay` may carry their duration as a parameter,
+            // so only gates are checked against their expected number of parameters.
+            if matches!(instruction.operation, OperationJson::Gate { .. })
+                && operation.num_params() as usize != instruction.params.len()
+            {
+                return Err(TargetJsonError::ParameterMismatch {
+                    instruction: instruction.name,
+                    expected: operation.num_params(),
+                    actual: instruction.params.len(),
+                });
+            }
+            let params = params_from_json(instruction.params, &mut free_params);
+            let props_map: PropsMap = instruction
+                .properties
+                .into_iter()
+                .map(|entry| {
+                    let qargs = match entry.qargs {
+                        Some(qargs) => qargs.into_iter().map(PhysicalQubit).collect(),
+                        None => Qargs::Global,
+                    };
+                    (qargs, entry.properties.map(InstructionProperties::from))
+                })
+                .collect();
+            target.add_instruction(operation, &params, Some(&instruction.name), Some(props_map))?;
+        }
+        Ok(target)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pyo3::prelude::*;
+    use qiskit_circuit::operations::{DelayUnit, Param, StandardGate, StandardInstruction};
+    use qiskit_circuit::parameter_expression::ParameterExpression;
+    use qiskit_circuit::PhysicalQubit;
+
+    use super::{TargetJsonError, TARGET_JSON_VERSION};
+    use crate::target::{InstructionProperties, Qargs, QubitProperties, Target, TargetOperation};
+
+    fn sample_target() -> Target {
+        let mut target = Target::new(
+            Some("sample".to_string()),
+            Some(2),
+            Some(2.2e-10),
+            Some(16),
+            Some(64),
+            Some(16),
+            Some(16),
+            Some(vec![
+                QubitProperties {
+                    t1: Some(1.5e-4),
+                    t2: Some(9.0e-5),
+                    frequency: Some(5.1e9),
+                },
+                QubitProperties {
+                    t1: None,
+                    t2: None,
+                    frequency: None,
+                },
+            ]),
+            Some(vec![vec![PhysicalQubit(0), PhysicalQubit(1)]]),
+        )
+        .unwrap();
+        let cx_props = [
+            (
+                Qargs::from([PhysicalQubit(0), PhysicalQubit(1)]),
+                Some(InstructionProperties::new(Some(5.0e-7), Some(1.0e-2))),
+            ),
+            (Qargs::from([PhysicalQubit(1), PhysicalQubit(0)]), None),
+        ];
+        target
+            .add_instruction(
+                StandardGate::CX.into(),
+                &[],
+                None,
+                Some(cx_props.into_iter().collect()),
+            )
+            .unwrap();
+        target
+            .add_instruction(
+                StandardGate::RX.into(),
+                &[1.5.into()],
+                Some("rx_fixed"),
+                Some(
+                    [(
+                        Qargs::from([PhysicalQubit(0)]),
+                        Some(InstructionProperties::new(None, Some(1.0e-4))),
+                    )]
+                    .into_iter()
+                    .collect(),
+                ),
+            )
+            .unwrap();
+        target
+            .add_instruction(StandardInstruction::Measure.into(), &[], None, None)
+            .unwrap();
+        target
+            .add_instruction(
+                StandardInstruction::Delay(DelayUnit::DT).into(),
+                &[128.0.into()],
+                None,
+                None,
+            )
+            .unwrap();
+        target
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let target = sample_target();
+        let json = target.to_json().unwrap();
+        let loaded = Target::from_json(&json).unwrap();
+
+        assert_eq!(loaded.description, target.description);
+        assert_eq!(loaded.num_qubits, target.num_qubits);
+        assert_eq!(loaded.dt, target.dt);
+        assert_eq!(loaded.granularity, 16);
+        assert_eq!(loaded.min_length, 64);
+        assert_eq!(loaded.pulse_alignment, 16);
+        assert_eq!(loaded.acquire_alignment, 16);
+        assert_eq!(loaded.qubit_properties, target.qubit_properties);
+        assert_eq!(
+            loaded.concurrent_measurements,
+            target.concurrent_measurements
+        );
+        assert_eq!(
+            loaded.operation_names().collect::<Vec<_>>(),
+            vec!["cx", "rx_fixed", "measure", "delay"]
+        );
+        for name in target.keys() {
+            assert_eq!(loaded[name], target[name]);
+        }
+        // Serializing again yields the same document.
+        assert_eq!(loaded.to_json().unwrap(), json);
+    }
+
+    #[test]
+    fn test_load_minimal_document() {
+        let json = format!(
+            r#"{{"version": {TARGET_JSON_VERSION}, "instructions": [
+                {{"name": "h", "operation": {{"type": "gate", "name": "h"}},
+                  "properties": [{{"qargs": [3], "properties": {{"error": 0.001}}}}]}}
+            ]}}"#
+        );
+        let target = Target::from_json(&json).unwrap();
+        assert_eq!(target.num_qubits, Some(4));
+        assert_eq!(target.granularity, 1);
+        assert_eq!(target.get_error("h", &[PhysicalQubit(3)]), Some(0.001));
+        assert_eq!(target.get_duration("h", &[PhysicalQubit(3)]), None);
+    }
+
+    #[test]
+    fn test_invalid_documents() {
+        let result = Target::from_json(r#"{"version": 0}"#);
+        assert!(matches!(
+            result,
+            Err(TargetJsonError::UnsupportedVersion(0))
+        ));
+
+        let json = format!(
+            r#"{{"version": {TARGET_JSON_VERSION}, "instructions": [
+                {{"name": "foo", "operation": {{"type": "gate", "name": "foo"}}, "properties": []}}
+            ]}}"#
+        );
+        let Err(err) = Target::from_json(&json) else {
+            panic!("The operation did not fail as expected.");
+        };
+        assert_eq!(err.to_string(), "Unknown standard gate 'foo'.");
+
+        let json = format!(
+            r#"{{"version": {TARGET_JSON_VERSION}, "instructions": [
+                {{"name": "rz", "operation": {{"type": "gate", "name": "rz"}}, "properties": []}}
+            ]}}"#
+        );
+        assert!(matches!(
+            Target::from_json(&json),
+            Err(TargetJsonError::ParameterMismatch {
+                expected: 1,
+                actual: 0,
+                ..
+            })
+        ));
+
+        assert!(matches!(
+            Target::from_json("not json"),
+            Err(TargetJsonError::Json(_))
+        ));
+    }
+
+    #[test]
+    fn test_load_free_parameters() {
+        let json = format!(
+            r#"{{"version":{TARGET_JSON_VERSION},"num_qubits":1,"instructions":[
+                {{"name":"rz","operation":{{"type":"gate","name":"rz"}},"params":["theta"],
+                  "properties":[{{"qargs":[0],"properties":null}}]}},
+                {{"name":"rx","operation":{{"type":"gate","name":"rx"}},"params":["theta"],
+                  "properties":[{{"qargs":[0],"properties":null}}]}}
+            ]}}"#
+        );
+        let target = Target::from_json(&json).unwrap();
+        let symbol = |name: &str| {
+            let TargetOperation::Normal(operation) = &target._gate_name_map[name] else {
+                panic!("'{name}' is not a normal operation");
+            };
+            let [Param::Symbolic(expr)] = operation.params.as_slice() else {
+                panic!("'{name}' does not have a single symbolic parameter");
+            };
+            expr.as_symbol().unwrap().clone()
+        };
+        // Both instructions share the one parameter named "theta".
+        assert_eq!(symbol("rz").name(), "theta");
+        assert_eq!(symbol("rz"), symbol("rx"));
+
+        let out = target.to_json().unwrap();
+        assert!(out
+            .contains(r#""name":"rz","operation":{"type":"gate","name":"rz"},"params":["theta"]"#));
+        assert_eq!(Target::from_json(&out).unwrap().to_json().unwrap(), out);
+    }
+
+    #[test]
+    fn test_free_parameters_by_name() {
+        Python::with_gil(|py| {
+            let mut target =
+                Target::new(None, Some(1), None, None, None, None, None, None, None).unwrap();
+            let theta = Py::new(py, ParameterExpression::Symbol("theta".to_string()))
+                .unwrap()
+                .into_any();
+            target
+                .add_instruction(
+                    StandardGate::RZ.into(),
+                    &[Param::ParameterExpression(theta)],
+                    None,
+                    None,
+                )
+                .unwrap();
+            target
+                .add_instruction(StandardGate::RX.into(), &[0.5.into()], None, None)
+                .unwrap();
+            let json = target.to_json().unwrap();
+            assert!(json.contains(
+                r#""name":"rz","operation":{"type":"gate","name":"rz"},"params":["theta"]"#
+            ));
+            assert!(json.contains(r#""params":[0.5]"#));
+        });
+    }
+
+    #[test]
+    fn test_parameter_expression_unsupported() {
+        Python::with_gil(|py| {
+            let mut target =
+                Target::new(None, Some(1), None, None, None, None, None, None, None).unwrap();
+            let expr = Py::new(
+                py,
+                ParameterExpression::Expression("2*theta".to_string()).unwrap(),
+            )
+            .unwrap()
+            .into_any();
+            target
+                .add_instruction(
+                    StandardGate::RZ.into(),
+                    &[Param::ParameterExpression(expr)],
+                    None,
+                    None,
+                )
+                .unwrap();
+            assert!(matches!(
+                target.to_json(),
+                Err(TargetJsonError::Unsupported { instruction, .. }) if instruction == "rz"
+            ));
+        });
+    }
+}
@@ -0,0 +1,86 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2025
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Fixtures shared by the Rust tests of the scheduling-related passes, both in this crate and in
+//! crates that depend on it (through the `test-utils` feature).
+
+use qiskit_circuit::operations::{DelayUnit, Param, StandardGate, StandardInstruction};
+use qiskit_circuit::packed_instruction::PackedOperation;
+use qiskit_circuit::PhysicalQubit;
+
+use crate::target::{InstructionProperties, Qargs, Target};
+
+/// A `(qargs, properties)` pair of an instruction with the given duration.
+pub fn props(qargs: &[u32], duration: f64) -> (Qargs, Option<InstructionProperties>) {
+    (
+        qargs.iter().map(|q| PhysicalQubit(*q)).collect(),
+        Some(InstructionProperties::new(Some(duration), None)),
+    )
+}
+
+/// A two-qubit target with `dt = 1`, 160dt X and Y gates, a 500dt CX gate, 1000dt measurements
+/// and delays on both qubits.  The `min_length` and the pulse and acquire `alignment` are set
+/// from the arguments.
+pub fn scheduling_target(min_length: u32, alignment: u32) -> Target {
+    let mut target = Target::new(
+        None,
+        Some(2),
+        Some(1.),
+        None,
+        Some(min_length),
+        Some(alignment),
+        Some(alignment),
+        None,
+        None,
+    )
+    .unwrap();
+    let x_props = [props(&[0], 160.), props(&[1], 160.)];
+    for gate in [StandardGate::X, StandardGate::Y] {
+        target
+            .add_instruction(
+                gate.into(),
+                &[],
+                None,
+                Some(x_props.iter().cloned().collect()),
+            )
+            .unwrap();
+    }
+    target
+        .add_instruction(
+            StandardGate::CX.into(),
+            &[],
+            None,
+            Some([props(&[0, 1], 500.)].into_iter().collect()),
+        )
+        .unwrap();
+    target
+        .add_instruction(
+            PackedOperation::from_standard_instruction(StandardInstruction::Measure),
+            &[],
+            None,
+            Some(
+                [props(&[0], 1000.), props(&[1], 1000.)]
+                    .into_iter()
+                    .collect(),
+            ),
+        )
+        .unwrap();
+    target
+        .add_instruction(
+            PackedOperation::from_standard_instruction(StandardInstruction::Delay(DelayUnit::DT)),
+            &[Param::Float(0.)],
+            Some("delay"),
+            Some([props(&[0], 0.), props(&[1], 0.)].into_iter().collect()),
+        )
+        .unwrap();
+    target
+}
@@ -0,0 +1,691 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2025
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! A preset transpilation pipeline driven entirely from Rust.
+//!
+//! [transpile] chains the Rust cores of the transpiler passes in the same stages as the preset
+//! pass managers (init, layout and routing, translation, optimization), without needing a Python
+//! interpreter.  The translation stage uses the ``"synthesis"`` translation method of the preset
+//! pass managers rather than the [BasisTranslator](crate::passes::basis_translator), since the
+//! standard equivalence library is defined in Python:
+//!
+//! * [run_high_level_synthesis] unrolls operations on three or more qubits through their
+//!   definitions;
+//! * [run_consolidate_blocks] collects the one- and two-qubit runs that are outside the
+//!   [Target], or that can be synthesized with fewer two-qubit gates, into unitaries;
+//! * [run_unitary_synthesis] synthesizes those unitaries into the gates of the [Target].
+//!
+//! Circuits containing any other operation, such as control flow, custom Python gates or
+//! gates with unbound parameters, are rejected with [TranspileError::UnsupportedOperation].
+//! Targets whose gates unitary synthesis cannot use, and which could only be reached through the
+//! equivalence library, are rejected with [TranspileError::NoSynthesisBasis].
+
+use std::f64::consts::FRAC_PI_4;
+
+use approx::relative_eq;
+use hashbrown::HashSet;
+use rustworkx_core::petgraph::stable_graph::NodeIndex;
+use smallvec::SmallVec;
+
+use qiskit_circuit::circuit_data::CircuitData;
+use qiskit_circuit::converters::dag_to_circuit;
+use qiskit_circuit::dag_circuit::DAGCircuit;
+use qiskit_circuit::nlayout::VirtualQubit;
+use qiskit_circuit::operations::{Operation, OperationRef, Param, StandardGate};
+use qiskit_circuit::packed_instruction::PackedInstruction;
+use qiskit_circuit::transpile_layout::{TranspileLayout, TranspileLayoutError};
+use qiskit_circuit::PhysicalQubit;
+use qiskit_synthesis::two_qubit_decompose::{
+    RXXEquivalent, TwoQubitBasisDecomposer, TwoQubitControlledUDecomposer,
+    TwoQubitWeylDecomposition,
+};
+
+use crate::passes::sabre::{sabre_layout_and_routing, Heuristic, SetScaling};
+use crate::passes::{
+    get_target_basis_set, run_consolidate_blocks, run_high_level_synthesis,
+    run_optimize_1q_gates_decomposition, run_unitary_synthesis, DecomposerType,
+    HighLevelSynthesisContext,
+};
+use crate::target::{Qargs, Target, TargetOperation};
+use thiserror::Error;
+
+/// Options controlling the [transpile] pipeline.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TranspileOptions {
+    /// The amount of optimization to perform, from 0 (none) to 3 (most), with the same
+    /// meaning as the ``optimization_level`` of the Python preset pass managers.
+    pub optimization_level: u8,
+    /// The seed for the stochastic parts of the pipeline.  If `None`, the pipeline is seeded
+    /// from system entropy.
+    pub seed: Option<u64>,
+    /// The degree of approximation allowed in synthesis, in `[0, 1]`.  `None` means the
+    /// approximation is derived from the error rates in the [Target].
+    pub approximation_degree: Option<f64>,
+}
+
+impl Default for TranspileOptions {
+    fn default() -> Self {
+        Self {
+            optimization_level: 2,
+            seed: None,
+            approximation_degree: Some(1.0),
+        }
+    }
+}
+
+/// The errors that [transpile] can return.
+#[derive(Debug, Error)]
+pub enum TranspileError {
+    #[error("given 'Target' was not initialized with a qubit count")]
+    MissingQubitCount,
+    #[error("the circuit has {circuit} qubits, but the 'Target' only has {target}")]
+    CircuitTooWide { circuit: usize, target: u32 },
+    /// The circuit contains an operation that the pipeline cannot translate without Python.
+    #[error("cannot transpile '{name}': {reason}")]
+    UnsupportedOperation { name: String, reason: &'static str },
+    /// A two-qubit operation is on a pair of qubits with no usable two-qubit gate in the
+    /// [Target] to synthesize it with.
+    #[error("cannot synthesize '{name}' on qubits ({}, {}): the 'Target' has no usable two-qubit gate on them", .qubits[0].0, .qubits[1].0)]
+    NoTwoQubitBasis {
+        name: String,
+        qubits: [PhysicalQubit; 2],
+    },
+    /// The operations on some qubits need translating, but the [Target] has no gates on them
+    /// that unitary synthesis can use.  Only the
+    /// [BasisTranslator](crate::passes::basis_translator) could translate them, and its standard
+    /// equivalence library is defined in Python.
+    #[error("the 'Target' has no gates on qubits {qubits:?} that synthesis can use; translating to it needs the basis translator, whose equivalence library is only available from Python")]
+    NoSynthesisBasis { qubits: Vec<PhysicalQubit> },
+    /// An operation is left that the [Target] does not support on its qubits, and that the
+    /// pipeline could not translate.
+    #[error("cannot translate '{name}' on qubits {qubits:?} to the 'Target' without the basis translator, whose equivalence library is only available from Python")]
+    Untranslatable {
+        name: String,
+        qubits: Vec<PhysicalQubit>,
+    },
+    #[error(transparent)]
+    Layout(#[from] TranspileLayoutError),
+    /// One of the underlying passes failed on a circuit that the earlier checks accepted.
+    #[error("unexpected failure in the {0} pass")]
+    Pass(&'static str),
+}
+
+/// The two-qubit gates that are equivalent to ``rxx`` up to single-qubit gates, which synthesis
+/// can use with any angle.
+const PARAM_GATES: [StandardGate; 8] = [
+    StandardGate::RXX,
+    StandardGate::RZZ,
+    StandardGate::RYY,
+    StandardGate::RZX,
+    StandardGate::CPhase,
+    StandardGate::CRX,
+    StandardGate::CRY,
+    StandardGate::CRZ,
+];
+
+/// Per-level settings for Sabre: the number of forwards-backwards iterations, the number of
+/// routing trials per layout and the number of random layout trials.
+fn sabre_settings(optimization_level: u8) -> (usize, usize, usize) {
+    match optimization_level {
+        0 => (0, 1, 0),
+        1 => (2, 5, 5),
+        2 => (2, 10, 10),
+        _ => (4, 20, 20),
+    }
+}
+
+/// Transpile a circuit for a [Target].
+///
+/// The pipeline mirrors the stages of the Python preset pass managers with the ``"synthesis"``
+/// translation method:
+///
+/// 1. unroll operations on 3 or more qubits with [run_high_level_synthesis];
+/// 2. choose a layout and route with [sabre_layout_and_routing];
+/// 3. translate to the [Target] with [run_consolidate_blocks] and [run_unitary_synthesis];
+/// 4. at optimization level 1 and above, resynthesize the single-qubit runs with
+///    [run_optimize_1q_gates_decomposition].
+///
+/// No Python interpreter is needed.
+///
+/// # Returns
+///
+/// * `Ok`: the transpiled circuit, defined over every physical qubit of the [Target], with its
+///   [TranspileLayout] attached.
+/// * `Err`: a [TranspileError] if the [Target] has no qubit count or is too small for the
+///   circuit, if the circuit contains an operation that cannot be translated, or if translating
+///   to the [Target] needs the equivalence library, which is only available from Python.
+pub fn transpile(
+    circuit: &CircuitData,
+    target: &Target,
+    options: &TranspileOptions,
+) -> Result<CircuitData, TranspileError> {
+    let Some(num_physical_qubits) = target.num_qubits else {
+        return Err(TranspileError::MissingQubitCount);
+    };
+    if circuit.num_qubits() > num_physical_qubits as usize {
+        return Err(TranspileError::CircuitTooWide {
+            circuit: circuit.num_qubits(),
+            target: num_physical_qubits,
+        });
+    }
+    for inst in circuit.iter() {
+        check_supported(inst)?;
+    }
+    let optimization_level = options.optimization_level.min(3);
+    let dag = DAGCircuit::from_circuit_data(circuit.clone(), false)
+        .expect("circuits of supported operations can always be converted to a DAG");
+
+    // Init: unroll operations on 3 or more qubits, so layout and routing only see 1q and 2q
+    // gates.
+    let no_names = HashSet::new();
+    let hls_context = HighLevelSynthesisContext {
+        hls_op_names: &no_names,
+        target: Some(target),
+        equivalence_library: None,
+        device_insts: &no_names,
+        use_physical_indices: false,
+        min_qubits: 3,
+        unroll_definitions: true,
+        py_data: None,
+    };
+    let mut dag = run_high_level_synthesis(&dag, &hls_context, true)
+        .map_err(|_| TranspileError::Pass("HighLevelSynthesis"))?
+        .unwrap_or(dag);
+
+    // Layout and routing.
+    let (max_iterations, num_swap_trials, num_random_trials) = sabre_settings(optimization_level);
+    let partial_layouts = if optimization_level == 0 {
+        vec![(0..dag.num_qubits() as u32)
+            .map(|q| Some(PhysicalQubit(q)))
+            .collect()]
+    } else {
+        vec![]
+    };
+    let heuristic = Heuristic::new(
+        None,
+        None,
+        None,
+        Some(10 * num_physical_qubits as usize),
+        1e-10,
+        None,
+        None,
+    )
+    .with_basic(1.0, SetScaling::Constant)
+    .with_lookahead(0.5, 20, SetScaling::Size)
+    .with_decay(0.001, 5)
+    .expect("the decay settings are valid");
+    let (mut dag, initial_layout, final_layout) = sabre_layout_and_routing(
+        &mut dag,
+        target,
+        &heuristic,
+        max_iterations,
+        num_swap_trials,
+        num_random_trials,
+        options.seed,
+        partial_layouts,
+        false,
+        false,
+    )
+    .map_err(|_| TranspileError::Pass("SabreLayout"))?;
+
+    // Translation: collect what is outside the target into unitaries and synthesize them.
+    let to_indices = |runs: Vec<Vec<NodeIndex>>| -> Vec<Vec<usize>> {
+        runs.into_iter()
+            .map(|run| run.into_iter().map(|node| node.index()).collect())
+            .collect()
+    };
+    let blocks = to_indices(dag.collect_2q_runs().unwrap_or_default());
+    let runs = to_indices(
+        dag.collect_1q_runs()
+            .map(|runs| runs.collect())
+            .unwrap_or_default(),
+    );
+    if let Some((decomposer, basis_gate)) =
+        consolidation_decomposer(target, options.approximation_degree)
+    {
+        run_consolidate_blocks(
+            &mut dag,
+            decomposer,
+            basis_gate.name(),
+            false,
+            Some(target),
+            None,
+            Some(blocks),
+            Some(runs),
+        )
+        .map_err(|_| TranspileError::Pass("ConsolidateBlocks"))?;
+    }
+    check_synthesis_basis(&dag, target)?;
+    let coupling_edges: HashSet<[PhysicalQubit; 2]> = target
+        .qargs()
+        .into_iter()
+        .flatten()
+        .filter_map(|qargs| match qargs {
+            Qargs::Concrete(qargs) => match qargs.as_slice() {
+                [q0, q1] => Some([*q0, *q1]),
+                _ => None,
+            },
+            Qargs::Global => None,
+        })
+        .collect();
+    let qubit_indices = (0..dag.num_qubits()).collect();
+    let mut dag = run_unitary_synthesis(
+        &mut dag,
+        qubit_indices,
+        0,
+        Some(target),
+        HashSet::new(),
+        HashSet::from(["unitary".to_string()]),
+        coupling_edges,
+        options.approximation_degree,
+        None,
+        None,
+    )
+    .map_err(|_| TranspileError::Pass("UnitarySynthesis"))?;
+
+    // Optimization.
+    if optimization_level >= 1 {
+        run_optimize_1q_gates_decomposition(&mut dag, Some(target), None, None)
+            .map_err(|_| TranspileError::Pass("Optimize1qGatesDecomposition"))?;
+    }
+    check_translated(&dag, target)?;
+
+    // Routing leaves the state of virtual qubit `v` on `final_layout[v]`, having started on
+    // `initial_layout[v]`.
+    let mut routing_permutation = vec![PhysicalQubit(0); initial_layout.num_qubits()];
+    for (virt, start) in initial_layout.iter_virtual() {
+        routing_permutation[start.index()] = virt.to_phys(&final_layout);
+    }
+    let layout = TranspileLayout::new(
+        initial_layout,
+        (0..circuit.num_qubits() as u32).map(VirtualQubit).collect(),
+        Some(routing_permutation),
+    )?;
+    let mut out = dag_to_circuit(&dag, false).expect("a DAG can always be converted to a circuit");
+    out.set_layout(Some(layout));
+    Ok(out)
+}
+
+/// Check that an instruction is something the pipeline can translate.
+fn check_supported(inst: &PackedInstruction) -> Result<(), TranspileError> {
+    let unsupported = |reason| TranspileError::UnsupportedOperation {
+        name: inst.op.name().to_string(),
+        reason,
+    };
+    match inst.op.view() {
+        OperationRef::Unitary(unitary) if unitary.num_qubits() >= 3 => Err(unsupported(
+            "unitaries on 3 or more qubits cannot be synthesized",
+        )),
+        OperationRef::StandardGate(_) | OperationRef::Unitary(_) => {
+            if inst
+                .params_view()
+                .iter()
+                .all(|param| matches!(param, Param::Float(_)))
+            {
+                Ok(())
+            } else {
+                Err(unsupported(
+                    "only gates with bound parameters are supported",
+                ))
+            }
+        }
+        OperationRef::StandardInstruction(_) => Ok(()),
+        _ if inst.op.control_flow() => Err(unsupported("control flow is not supported")),
+        _ => Err(unsupported(
+            "only standard gates, unitaries and standard instructions are supported",
+        )),
+    }
+}
+
+/// The two-qubit gates [run_consolidate_blocks] can count the cost of a block in, in order of
+/// preference, with the decomposer for them.  This is the choice the Python ``ConsolidateBlocks``
+/// pass makes from the operation names of the [Target].
+fn consolidation_decomposer(
+    target: &Target,
+    approximation_degree: Option<f64>,
+) -> Option<(DecomposerType, StandardGate)> {
+    const GATES: [StandardGate; 4] = [
+        StandardGate::CX,
+        StandardGate::CZ,
+        StandardGate::ISwap,
+        StandardGate::ECR,
+    ];
+    let names: HashSet<&str> = target.operation_names().collect();
+    if let Some(gate) = PARAM_GATES
+        .into_iter()
+        .find(|gate| names.contains(gate.name()))
+    {
+        let decomposer =
+            TwoQubitControlledUDecomposer::new_inner(RXXEquivalent::Standard(gate), "ZXZ")
+                .expect("the gate is equivalent to RXX");
+        return Some((DecomposerType::TwoQubitControlledU(decomposer), gate));
+    }
+    if let Some(gate) = GATES.into_iter().find(|gate| names.contains(gate.name())) {
+        let decomposer = TwoQubitBasisDecomposer::new_inner(
+            gate.name().to_string(),
+            gate.matrix(&[]).unwrap().view(),
+            approximation_degree.unwrap_or(1.0),
+            "U",
+            None,
+        )
+        .expect("the gate is a valid basis gate");
+        return Some((DecomposerType::TwoQubitBasis(decomposer), gate));
+    }
+    None
+}
+
+/// Check that the [Target] has gates that [run_unitary_synthesis] can synthesize every unitary
+/// in the circuit with: a single-qubit Euler basis on each of its qubits and, for two-qubit
+/// unitaries, a two-qubit gate on the pair that is either equivalent to ``rxx`` with a free
+/// angle or supercontrolled.
+fn check_synthesis_basis(dag: &DAGCircuit, target: &Target) -> Result<(), TranspileError> {
+    let supports_2q_synthesis = |qubits: [PhysicalQubit; 2]| {
+        [qubits, [qubits[1], qubits[0]]].iter().any(|qargs| {
+            let Ok(names) = target.operation_names_for_qargs(qargs) else {
+                return false;
+            };
+            names.into_iter().any(|name| {
+                let Some(TargetOperation::Normal(op)) = target.operation_from_name(name) else {
+                    return false;
+                };
+                let Some(gate) = op.operation.try_standard_gate() else {
+                    return false;
+                };
+                if gate.num_qubits() != 2 {
+                    return false;
+                }
+                if !op
+                    .params
+                    .iter()
+                    .all(|param| matches!(param, Param::Float(_)))
+                {
+                    return PARAM_GATES.contains(&gate);
+                }
+                let matrix = gate
+                    .matrix(&op.params)
+                    .expect("standard gates have a matrix");
+                TwoQubitWeylDecomposition::new_inner(matrix.view(), None, None)
+                    .is_ok_and(|kak| relative_eq!(kak.a(), FRAC_PI_4) && relative_eq!(kak.c(), 0.0))
+            })
+        })
+    };
+    for (_, inst) in dag.op_nodes(false) {
+        if !matches!(inst.op.view(), OperationRef::Unitary(_)) {
+            continue;
+        }
+        let qubits: Vec<PhysicalQubit> = dag
+            .get_qargs(inst.qubits)
+            .iter()
+            .map(|q| PhysicalQubit(q.0))
+            .collect();
+        let has_1q_basis = qubits.iter().all(|qubit| {
+            get_target_basis_set(target, *qubit)
+                .get_bases()
+                .next()
+                .is_some()
+        });
+        let has_2q_basis = match qubits.as_slice() {
+            [q0, q1] => supports_2q_synthesis([*q0, *q1]),
+            _ => true,
+        };
+        if !(has_1q_basis && has_2q_basis) {
+            return Err(TranspileError::NoSynthesisBasis { qubits });
+        }
+    }
+    Ok(())
+}
+
+/// Check that every operation left in the translated circuit is supported by the [Target].
+fn check_translated(dag: &DAGCircuit, target: &Target) -> Result<(), TranspileError> {
+    for (_, inst) in dag.op_nodes(false) {
+        if inst.op.directive() {
+            continue;
+        }
+        let qubits: SmallVec<[PhysicalQubit; 2]> = dag
+            .get_qargs(inst.qubits)
+            .iter()
+            .map(|q| PhysicalQubit(q.0))
+            .collect();
+        if target.instruction_supported_with_params(inst.op.name(), &qubits, inst.params_view()) {
+            continue;
+        }
+        return Err(match qubits.as_slice() {
+            [q0, q1] => TranspileError::NoTwoQubitBasis {
+                name: inst.op.name().to_string(),
+                qubits: [*q0, *q1],
+            },
+            _ => TranspileError::Untranslatable {
+                name: inst.op.name().to_string(),
+                qubits: qubits.to_vec(),
+            },
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use qiskit_circuit::operations::StandardInstruction;
+    use qiskit_circuit::packed_instruction::PackedOperation;
+    use qiskit_circuit::parameter_symbol::{ParameterSymbol, SymbolicExpression};
+    use qiskit_circuit::{Clbit, Qubit};
+
+    use super::*;
+
+    /// A target with ``rz`` (for any angle), ``sx`` and ``x`` on every qubit, ``cx`` along a
+    /// line in the forwards direction only, and measurements.
+    fn line_target(num_qubits: u32) -> Target {
+        let mut target = Target::new(
+            None,
+            Some(num_qubits),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let one_qubit = || {
+            (0..num_qubits)
+                .map(|q| (Qargs::from_iter([PhysicalQubit(q)]), None))
+                .collect()
+        };
+        let theta = Param::Symbolic(Arc::new(SymbolicExpression::from_symbol(Arc::new(
+            ParameterSymbol::new_unique("theta".to_string()),
+        ))));
+        target
+            .add_instruction(StandardGate::RZ.into(), &[theta], None, Some(one_qubit()))
+            .unwrap();
+        for gate in [StandardGate::SX, StandardGate::X] {
+            target
+                .add_instruction(gate.into(), &[], None, Some(one_qubit()))
+                .unwrap();
+        }
+        let line = (0..num_qubits - 1)
+            .map(|q| {
+                (
+                    Qargs::from_iter([PhysicalQubit(q), PhysicalQubit(q + 1)]),
+                    None,
+                )
+            })
+            .collect();
+        target
+            .add_instruction(StandardGate::CX.into(), &[], None, Some(line))
+            .unwrap();
+        target
+            .add_instruction(
+                PackedOperation::from_standard_instruction(StandardInstruction::Measure),
+                &[],
+                None,
+                Some(one_qubit()),
+            )
+            .unwrap();
+        target
+    }
+
+    fn options(optimization_level: u8) -> TranspileOptions {
+        TranspileOptions {
+            optimization_level,
+            seed: Some(42),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_transpile_to_target() {
+        let mut circuit = CircuitData::with_capacity(3, 3, 0, Param::Float(0.)).unwrap();
+        circuit.push_standard_gate(StandardGate::H, &[], &[Qubit(0)]);
+        circuit.push_standard_gate(StandardGate::CX, &[], &[Qubit(0), Qubit(2)]);
+        circuit.push_standard_gate(StandardGate::CCX, &[], &[Qubit(2), Qubit(1), Qubit(0)]);
+        circuit.push_standard_gate(StandardGate::RY, &[Param::Float(0.25)], &[Qubit(1)]);
+        circuit.push_standard_gate(StandardGate::CZ, &[], &[Qubit(1), Qubit(0)]);
+        for q in 0..3 {
+            circuit.push_packed_operation(
+                PackedOperation::from_standard_instruction(StandardInstruction::Measure),
+                &[],
+                &[Qubit(q)],
+                &[Clbit(q)],
+            );
+        }
+        let target = line_target(3);
+        for level in 0..=3 {
+            let out = transpile(&circuit, &target, &options(level)).unwrap();
+            assert_eq!(out.num_qubits(), 3);
+            for inst in out.iter() {
+                let qubits: Vec<PhysicalQubit> = out
+                    .get_qargs(inst.qubits)
+                    .iter()
+                    .map(|q| PhysicalQubit(q.0))
+                    .collect();
+                assert!(
+                    target.instruction_supported_with_params(
+                        inst.op.name(),
+                        &qubits,
+                        inst.params_view()
+                    ),
+                    "level {level}: '{}' on {qubits:?} is not in the target",
+                    inst.op.name()
+                );
+            }
+            assert_eq!(
+                out.iter()
+                    .filter(|inst| inst.op.name() == "measure")
+                    .count(),
+                3
+            );
+            assert!(out.layout().is_some());
+        }
+    }
+
+    #[test]
+    fn test_transpile_unsupported_operation() {
+        let mut circuit = CircuitData::with_capacity(1, 0, 0, Param::Float(0.)).unwrap();
+        let theta = Param::Symbolic(Arc::new(SymbolicExpression::from_symbol(Arc::new(
+            ParameterSymbol::new_unique("theta".to_string()),
+        ))));
+        circuit.push_standard_gate(StandardGate::RZ, &[theta], &[Qubit(0)]);
+        let err = transpile(&circuit, &line_target(2), &options(1)).unwrap_err();
+        assert!(
+            matches!(&err, TranspileError::UnsupportedOperation { name, .. } if name == "rz"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn test_transpile_target_errors() {
+        let circuit = CircuitData::with_capacity(3, 0, 0, Param::Float(0.)).unwrap();
+        let err = transpile(&circuit, &line_target(2), &options(1)).unwrap_err();
+        assert!(matches!(
+            err,
+            TranspileError::CircuitTooWide {
+                circuit: 3,
+                target: 2
+            }
+        ));
+        let err = transpile(&circuit, &Target::default(), &options(1)).unwrap_err();
+        assert!(matches!(err, TranspileError::MissingQubitCount));
+    }
+
+    /// Add `gate` with `params` to `target` on each of `qargs`.
+    fn add_gate(
+        target: &mut Target,
+        gate: StandardGate,
+        params: &[Param],
+        name: Option<&str>,
+        qargs: &[&[u32]],
+    ) {
+        let qargs = qargs
+            .iter()
+            .map(|qargs| (qargs.iter().copied().map(PhysicalQubit).collect(), None))
+            .collect();
+        target
+            .add_instruction(gate.into(), params, name, Some(qargs))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_transpile_no_synthesis_basis() {
+        // `x` alone is no Euler basis, so an `h` can't be synthesized on qubit 0.
+        let mut target =
+            Target::new(None, Some(2), None, None, None, None, None, None, None).unwrap();
+        add_gate(&mut target, StandardGate::X, &[], None, &[&[0], &[1]]);
+        add_gate(&mut target, StandardGate::CX, &[], None, &[&[0, 1]]);
+        let mut circuit = CircuitData::with_capacity(2, 0, 0, Param::Float(0.)).unwrap();
+        circuit.push_standard_gate(StandardGate::H, &[], &[Qubit(0)]);
+        let err = transpile(&circuit, &target, &options(0)).unwrap_err();
+        assert!(
+            matches!(&err, TranspileError::NoSynthesisBasis { qubits } if qubits == &[PhysicalQubit(0)]),
+            "unexpected error: {err}"
+        );
+
+        // A fixed-angle `rzz` isn't supercontrolled, so it can't be synthesized with either.
+        let mut target =
+            Target::new(None, Some(3), None, None, None, None, None, None, None).unwrap();
+        let theta = Param::Symbolic(Arc::new(SymbolicExpression::from_symbol(Arc::new(
+            ParameterSymbol::new_unique("theta".to_string()),
+        ))));
+        add_gate(
+            &mut target,
+            StandardGate::RZ,
+            &[theta],
+            None,
+            &[&[0], &[1], &[2]],
+        );
+        add_gate(
+            &mut target,
+            StandardGate::SX,
+            &[],
+            None,
+            &[&[0], &[1], &[2]],
+        );
+        add_gate(&mut target, StandardGate::CX, &[], None, &[&[0, 1]]);
+        add_gate(
+            &mut target,
+            StandardGate::RZZ,
+            &[Param::Float(0.3)],
+            None,
+            &[&[1, 2]],
+        );
+        let mut circuit = CircuitData::with_capacity(3, 0, 0, Param::Float(0.)).unwrap();
+        circuit.push_standard_gate(StandardGate::CX, &[], &[Qubit(0), Qubit(1)]);
+        circuit.push_standard_gate(StandardGate::CZ, &[], &[Qubit(2), Qubit(1)]);
+        let err = transpile(&circuit, &target, &options(0)).unwrap_err();
+        assert!(
+            matches!(&err, TranspileError::NoSynthesisBasis { qubits } if qubits == &[PhysicalQubit(2), PhysicalQubit(1)]),
+            "unexpected error: {err}"
+        );
+    }
+}
@@ -32,4 +32,12 @@
 
 /**
  * @defgroup QkTargetEntry QkTargetEntry
+ */
+
+/**
+ * @defgroup QkTranspiler QkTranspiler
+ */
+
+/**
+ * @defgroup QkTranspileLayout QkTranspileLayout
  */
\ No newline at end of file
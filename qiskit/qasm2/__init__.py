@@ -89,6 +89,23 @@ unnecessary (empty-statement) semicolons; the ``OPENQASM 2.0;`` version statemen
 a couple of other quality-of-life improvements without emitting any errors.  You can use the
 letter-of-the-spec mode with ``strict=True``.
 
+.. _qasm2-recovery-mode:
+
+Error recovery
+--------------
+
+By default, the loaders stop at the first problem in the program.  If you pass ``recover=True``,
+the parser instead records the problem, skips to the end of the invalid statement (the next ``;``,
+or the closing ``}`` of a gate definition), and carries on, so that all the lexical and semantic
+problems in the program are reported at once.  If there were any problems, the single
+:exc:`QASM2ParseError` raised at the end has a message with one line per problem, and a
+``diagnostics`` attribute containing a :class:`Diagnostic` for each of them.  Recovery mode can be
+combined with strict mode.  Exceptions raised by :ref:`custom classical functions
+<qasm2-custom-classical>` are never recovered from.
+
+.. autoclass:: Diagnostic
+    :members:
+
 
 .. _qasm2-export:
 
@@ -532,6 +549,7 @@ __all__ = [
     "LEGACY_CUSTOM_INSTRUCTIONS",
     "LEGACY_CUSTOM_CLASSICAL",
     "LEGACY_INCLUDE_PATH",
+    "Diagnostic",
     "QASM2Error",
     "QASM2ParseError",
     "QASM2ExportError",
@@ -543,6 +561,7 @@ from typing import Iterable, Union, Optional, Literal
 
 # pylint: disable=c-extension-no-member
 from qiskit._accelerate import qasm2 as _qasm2
+from qiskit._accelerate.qasm2 import Diagnostic
 from qiskit.circuit import QuantumCircuit
 from . import parse as _parse
 from .exceptions import QASM2Error, QASM2ParseError, QASM2ExportError
@@ -582,6 +601,7 @@ def loads(
     custom_instructions: Iterable[CustomInstruction] = (),
     custom_classical: Iterable[CustomClassical] = (),
     strict: bool = False,
+    recover: bool = False,
 ) -> QuantumCircuit:
     """Parse an OpenQASM 2 program from a string into a :class:`.QuantumCircuit`.
 
@@ -594,6 +614,8 @@ def loads(
         custom_classical: any custom classical functions that should be used during the parsing of
             classical expressions.  See :ref:`qasm2-custom-classical` for more.
         strict: whether to run in :ref:`strict mode <qasm2-strict-mode>`.
+        recover: whether to continue parsing after an invalid statement, and report all the
+            problems in the program together.  See :ref:`qasm2-recovery-mode`.
 
     Returns:
         A circuit object representing the same OpenQASM 2 program.
@@ -609,6 +631,7 @@ def loads(
             ],
             tuple(custom_classical),
             strict,
+            recover,
         ),
         custom_instructions,
     )
@@ -622,6 +645,7 @@ def load(
     custom_instructions: Iterable[CustomInstruction] = (),
     custom_classical: Iterable[CustomClassical] = (),
     strict: bool = False,
+    recover: bool = False,
 ) -> QuantumCircuit:
     """Parse an OpenQASM 2 program from a file into a :class:`.QuantumCircuit`.  The given path
     should be ASCII or UTF-8 encoded, and contain the OpenQASM 2 program.
@@ -638,6 +662,8 @@ def load(
         custom_classical: any custom classical functions that should be used during the parsing of
             classical expressions.  See :ref:`qasm2-custom-classical` for more.
         strict: whether to run in :ref:`strict mode <qasm2-strict-mode>`.
+        recover: whether to continue parsing after an invalid statement, and report all the
+            problems in the program together.  See :ref:`qasm2-recovery-mode`.
 
     Returns:
         A circuit object representing the same OpenQASM 2 program.
@@ -664,6 +690,7 @@ def load(
             ],
             tuple(custom_classical),
             strict,
+            recover,
         ),
         custom_instructions,
     )
@@ -20,7 +20,18 @@ class QASM2Error(QiskitError):
 
 
 class QASM2ParseError(QASM2Error):
-    """An error raised because of a failure to parse an OpenQASM 2 file."""
+    """An error raised because of a failure to parse an OpenQASM 2 file.
+
+    Attributes:
+        diagnostics (tuple[Diagnostic, ...]): the structured :class:`.qasm2.Diagnostic` records of
+            each problem found in the program.  When parsing with ``recover=True``, this contains
+            every problem found in the program.  This is empty for errors that did not come from
+            the lexer or parser.
+    """
+
+    def __init__(self, *message, diagnostics=()):
+        super().__init__(*message)
+        self.diagnostics = tuple(diagnostics)
 
 
 class QASM2ExportError(QASM2Error):
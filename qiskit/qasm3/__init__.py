@@ -224,6 +224,17 @@ available on this module, if you wish to build on top of it:
 
     A tuple of :class:`CustomGate` objects specifying the Qiskit constructors to use for the
     ``stdgates.inc`` include file.
+
+If the native importer fails to parse a program, the :exc:`QASM3ImporterError` it raises has a
+``diagnostics`` attribute.  This is a tuple of :class:`Diagnostic` objects, one for each problem
+found by the parser or the semantic analysis, which give the location of the problem in the source
+in a machine-readable form:
+
+.. autoclass:: Diagnostic
+    :members:
+
+.. autoclass:: DiagnosticSeverity
+    :members:
 """
 
 from __future__ import annotations
@@ -237,7 +248,7 @@ from qiskit.circuit import library
 from qiskit.exceptions import ExperimentalWarning
 from qiskit.utils import optionals as _optionals
 
-from .._accelerate.qasm3 import CustomGate
+from .._accelerate.qasm3 import CustomGate, Diagnostic, DiagnosticSeverity
 from .exceptions import QASM3Error, QASM3ExporterError, QASM3ImporterError
 from .experimental import ExperimentalFeatures
 from .exporter import Exporter
@@ -403,6 +414,11 @@ def dumps_experimental(
     alias_classical_registers=False,
     allow_aliasing=False,
     indent="  ",
+    annotation_handlers=None,
+    chain_else_if=False,
+    max_line_width=None,
+    label_comments=False,
+    source_map=None,
 ):
     """<overridden by functools.wraps>"""
     warnings.warn(
@@ -424,6 +440,11 @@ def dumps_experimental(
             "alias_classical_registers": alias_classical_registers,
             "allow_aliasing": allow_aliasing,
             "indent": indent,
+            "annotation_handlers": annotation_handlers,
+            "chain_else_if": chain_else_if,
+            "max_line_width": max_line_width,
+            "label_comments": label_comments,
+            "source_map": source_map,
         },
     )
 
@@ -439,6 +460,11 @@ def dump_experimental(
     alias_classical_registers=False,
     allow_aliasing=False,
     indent="  ",
+    annotation_handlers=None,
+    chain_else_if=False,
+    max_line_width=None,
+    label_comments=False,
+    source_map=None,
 ):
     """<overridden by functools.wraps>"""
     warnings.warn(
@@ -462,5 +488,10 @@ def dump_experimental(
             "alias_classical_registers": alias_classical_registers,
             "allow_aliasing": allow_aliasing,
             "indent": indent,
+            "annotation_handlers": annotation_handlers,
+            "chain_else_if": chain_else_if,
+            "max_line_width": max_line_width,
+            "label_comments": label_comments,
+            "source_map": source_map,
         },
     )
@@ -24,4 +24,14 @@ class QASM3ExporterError(QASM3Error):
 
 
 class QASM3ImporterError(QASM3Error):
-    """An error raised during the OpenQASM 3 importer."""
+    """An error raised during the OpenQASM 3 importer.
+
+    Attributes:
+        diagnostics (tuple[Diagnostic, ...]): the structured :class:`.qasm3.Diagnostic` records
+            of each problem found in the program, if the error came from parsing or semantic
+            analysis in the native importer.  This is empty for other errors.
+    """
+
+    def __init__(self, *message, diagnostics=()):
+        super().__init__(*message)
+        self.diagnostics = tuple(diagnostics)
@@ -30,6 +30,7 @@ Operators
    ScalarOp
    SparseObservable
    SparsePauliOp
+   SparseFermionOp
    PauliLindbladMap
    QubitSparsePauli
    QubitSparsePauliList
@@ -124,6 +125,7 @@ from qiskit._accelerate.pauli_lindblad_map import (
     QubitSparsePauli,
     PauliLindbladMap,
 )
+from qiskit._accelerate.sparse_fermion_op import SparseFermionOp
 from qiskit._accelerate.sparse_observable import SparseObservable
 
 from .analysis import hellinger_distance, hellinger_fidelity, Z2Symmetries
@@ -37,6 +37,7 @@ from qiskit.quantum_info.operators.channel.quantum_channel import QuantumChannel
 from qiskit.quantum_info.operators.channel.superop import SuperOp
 
 from qiskit._accelerate.pauli_expval import density_expval_pauli_no_x, density_expval_pauli_with_x
+from qiskit._accelerate.sparse_observable import SparseObservable
 from qiskit.quantum_info.states.statevector import Statevector
 
 if TYPE_CHECKING:
@@ -416,6 +417,11 @@ class DensityMatrix(QuantumState, TolerancesMixin):
                 for z, x, coeff in zip(oper.paulis.z, oper.paulis.x, oper.coeffs)
             )
 
+        if isinstance(oper, SparseObservable) and self.num_qubits is not None:
+            if qargs is not None:
+                oper = oper.apply_layout(qargs, self.num_qubits)
+            return oper.expectation_value(self.data)
+
         if not isinstance(oper, Operator):
             oper = Operator(oper)
         return np.trace(Operator(self).dot(oper, qargs=qargs).data)
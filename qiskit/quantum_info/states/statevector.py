@@ -37,6 +37,7 @@ from qiskit._accelerate.pauli_expval import (
     expval_pauli_no_x,
     expval_pauli_with_x,
 )
+from qiskit._accelerate.sparse_observable import SparseObservable
 
 if TYPE_CHECKING:
     from qiskit import circuit
@@ -519,6 +520,11 @@ class Statevector(QuantumState, TolerancesMixin):
                 for z, x, coeff in zip(oper.paulis.z, oper.paulis.x, oper.coeffs)
             )
 
+        if isinstance(oper, SparseObservable) and self.num_qubits is not None:
+            if qargs is not None:
+                oper = oper.apply_layout(qargs, self.num_qubits)
+            return oper.expectation_value(self.data)
+
         val = self.evolve(oper, qargs=qargs)
         conj = self.conjugate()
         return np.dot(conj.data, val.data)
@@ -31,8 +31,8 @@ def sampled_expectation_value(dist, oper):
 
     Parameters:
         dist (Counts or QuasiDistribution or ProbDistribution or dict): Input sampled distribution
-        oper (str or :class:`~.quantum_info.Pauli` or SparsePauliOp): The operator for the
-            observable
+        oper (str or :class:`~.quantum_info.Pauli` or SparsePauliOp or SparseObservable): The
+            operator for the observable
 
     Returns:
         float: The expectation value
@@ -40,7 +40,15 @@ def sampled_expectation_value(dist, oper):
         QiskitError: if the input distribution or operator is an invalid type
     """
     from .counts import Counts
-    from qiskit.quantum_info import Pauli, SparsePauliOp
+    from qiskit.quantum_info import Pauli, SparsePauliOp, SparseObservable
+
+    if isinstance(oper, SparseObservable):
+        if not isinstance(dist, (Counts, dict, QuasiDistribution, ProbDistribution)):
+            raise QiskitError("Invalid input distribution type")
+        try:
+            return oper.expectation_value_counts(dist).real
+        except ValueError as err:
+            raise QiskitError(str(err)) from err
 
     # This should be removed when these return bit-string keys
     if isinstance(dist, (QuasiDistribution, ProbDistribution)):
@@ -110,6 +110,7 @@ class SabreLayout(TransformationPass):
         swap_trials=None,
         layout_trials=None,
         skip_routing=False,
+        absorb_final_permutation=False,
     ):
         """SabreLayout initializer.
 
@@ -148,6 +149,11 @@ class SabreLayout(TransformationPass):
                 will be set in the property set. This is a tradeoff to run custom
                 routing with multiple layout trials, as using this option will cause
                 SabreLayout to run the routing stage internally but not use that result.
+            absorb_final_permutation (bool): If this is set ``True``, the terminal measurements
+                of the routed circuit are moved after every inserted swap, so each measures its
+                qubit at its final physical position and the permutation of the measured qubits
+                is absorbed into the measurements.  No swaps are added to undo the permutation;
+                it is reported in the ``final_layout`` property as usual.
 
         Raises:
             TranspilerError: If both ``routing_pass`` and ``swap_trials`` or
@@ -179,6 +185,7 @@ class SabreLayout(TransformationPass):
         self.swap_trials = default_num_processes() if swap_trials is None else swap_trials
         self.layout_trials = default_num_processes() if layout_trials is None else layout_trials
         self.skip_routing = skip_routing
+        self.absorb_final_permutation = absorb_final_permutation
 
     @property
     def coupling_map(self):  # pylint: disable=missing-function-docstring
@@ -282,6 +289,7 @@ class SabreLayout(TransformationPass):
             seed=self.seed,
             partial_layouts=starting_layouts,
             skip_routing=self.skip_routing,
+            absorb_final_permutation=self.absorb_final_permutation,
         )
         sabre_stop = time.perf_counter()
         logger.debug(
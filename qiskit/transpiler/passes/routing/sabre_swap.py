@@ -71,7 +71,15 @@ class SabreSwap(TransformationPass):
     `arXiv:1809.02573 <https://arxiv.org/pdf/1809.02573.pdf>`_
     """
 
-    def __init__(self, coupling_map, heuristic="basic", seed=None, fake_run=False, trials=None):
+    def __init__(
+        self,
+        coupling_map,
+        heuristic="basic",
+        seed=None,
+        fake_run=False,
+        trials=None,
+        absorb_final_permutation=False,
+    ):
         r"""SabreSwap initializer.
 
         Args:
@@ -87,6 +95,11 @@ class SabreSwap(TransformationPass):
                 CPUs on the local system. For reproducible results it is recommended
                 that you set this explicitly, as the output will be deterministic for
                 a fixed number of trials.
+            absorb_final_permutation (bool): If true, the terminal measurements of the routed
+                circuit are moved after every inserted swap, so each measures its qubit at its
+                final physical position and the permutation of the measured qubits is absorbed
+                into the measurements.  No swaps are added to undo the permutation; it is
+                reported in the ``final_layout`` property as usual.
 
         Raises:
             TranspilerError: If the specified heuristic is not valid.
@@ -157,6 +170,7 @@ class SabreSwap(TransformationPass):
         self.seed = seed
         self.trials = default_num_processes() if trials is None else trials
         self.fake_run = fake_run
+        self.absorb_final_permutation = absorb_final_permutation
 
     @functools.cached_property
     def dist_matrix(self):  # pylint: disable=missing-function-docstring
@@ -233,7 +247,13 @@ class SabreSwap(TransformationPass):
         initial_layout = NLayout.generate_trivial_layout(num_dag_qubits)
         sabre_start = time.perf_counter()
         dag, final_layout = sabre_routing(
-            dag, self._routing_target, heuristic, initial_layout, self.trials, self.seed
+            dag,
+            self._routing_target,
+            heuristic,
+            initial_layout,
+            self.trials,
+            self.seed,
+            absorb_final_permutation=self.absorb_final_permutation,
         )
         sabre_stop = time.perf_counter()
         LOG.debug("Sabre swap algorithm execution complete in: %s", sabre_stop - sabre_start)
@@ -22,6 +22,7 @@ from qiskit.dagcircuit import DAGCircuit, DAGOpNode, DAGOutNode
 from qiskit.transpiler.basepasses import AnalysisPass
 from qiskit.transpiler.exceptions import TranspilerError
 from qiskit.transpiler.target import Target
+from qiskit._accelerate.scheduling import constrained_reschedule
 
 
 class ConstrainedReschedule(AnalysisPass):
@@ -83,9 +84,9 @@ class ConstrainedReschedule(AnalysisPass):
         super().__init__()
         self.acquire_align = acquire_alignment
         self.pulse_align = pulse_alignment
+        self.target = target
         if target is not None:
             self.durations = target.durations()
-            self.target = target
             self.acquire_align = target.acquire_alignment
             self.pulse_align = target.pulse_alignment
 
@@ -234,6 +235,15 @@ class ConstrainedReschedule(AnalysisPass):
 
         node_start_time = self.property_set["node_start_time"]
 
+        if self.target is not None and self.target.dt is not None:
+            constrained_reschedule(
+                dag,
+                self.target,
+                node_start_time,
+                self.property_set.get("clbit_write_latency", 0),
+            )
+            return
+
         for node in dag.topological_op_nodes():
 
             start_time = node_start_time.get(node)
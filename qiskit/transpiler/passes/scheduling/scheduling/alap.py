@@ -13,6 +13,7 @@
 """ALAP Scheduling."""
 from qiskit.circuit import Measure
 from qiskit.transpiler.exceptions import TranspilerError
+from qiskit._accelerate.scheduling import alap_schedule_analysis
 
 from qiskit.transpiler.passes.scheduling.scheduling.base_scheduler import BaseScheduler
 
@@ -44,6 +45,12 @@ class ALAPScheduleAnalysis(BaseScheduler):
 
         clbit_write_latency = self.property_set.get("clbit_write_latency", 0)
 
+        if self.target is not None:
+            self.property_set["node_start_time"] = alap_schedule_analysis(
+                dag, self.target, clbit_write_latency
+            )
+            return
+
         node_start_time = {}
         idle_before = {q: 0 for q in dag.qubits + dag.clbits}
         for node in reversed(list(dag.topological_op_nodes())):
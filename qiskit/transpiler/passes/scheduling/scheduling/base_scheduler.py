@@ -41,6 +41,7 @@ class BaseScheduler(AnalysisPass):
         """
         super().__init__()
         self.durations = durations
+        self.target = target
         if target is not None:
             self.durations = target.durations()
 
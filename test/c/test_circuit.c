@@ -882,6 +882,96 @@ cleanup:
     return result;
 }
 
+int test_dump_load(void) {
+    QkCircuit *qc = qk_circuit_new(0, 2);
+    QkQuantumRegister *qr = qk_quantum_register_new(3, "qr");
+    qk_circuit_add_quantum_register(qc, qr);
+    qk_quantum_register_free(qr);
+    uint32_t q0[1] = {0};
+    uint32_t q01[2] = {0, 1};
+    uint32_t q012[3] = {0, 1, 2};
+    double theta[1] = {0.5};
+    qk_circuit_gate(qc, QkGate_H, q0, NULL);
+    qk_circuit_gate(qc, QkGate_CX, q01, NULL);
+    qk_circuit_gate(qc, QkGate_RZ, q0, theta);
+    qk_circuit_barrier(qc, q012, 3);
+    qk_circuit_measure(qc, 1, 1);
+
+    int result = Ok;
+    size_t len;
+    uint8_t *bytes;
+    if (qk_circuit_dump(qc, &bytes, &len, NULL) != QkExitCode_Success) {
+        printf("Failed to dump the circuit\n");
+        qk_circuit_free(qc);
+        return RuntimeError;
+    }
+    QkCircuit *loaded;
+    char *error;
+    if (qk_circuit_load(bytes, len, &loaded, &error) != QkExitCode_Success) {
+        printf("Failed to load the circuit: %s\n", error);
+        qk_str_free(error);
+        result = RuntimeError;
+        goto cleanup;
+    }
+    if (qk_circuit_num_qubits(loaded) != 3 || qk_circuit_num_clbits(loaded) != 2) {
+        printf("The loaded circuit has the wrong number of bits\n");
+        result = EqualityError;
+        goto cleanup;
+    }
+    size_t num_instructions = qk_circuit_num_instructions(qc);
+    if (qk_circuit_num_instructions(loaded) != num_instructions) {
+        printf("The loaded circuit has the wrong number of instructions\n");
+        result = EqualityError;
+        goto cleanup;
+    }
+    for (size_t i = 0; i < num_instructions; i++) {
+        QkCircuitInstruction expected, actual;
+        qk_circuit_get_instruction(qc, i, &expected);
+        qk_circuit_get_instruction(loaded, i, &actual);
+        bool equal = strcmp(expected.name, actual.name) == 0 &&
+                     expected.num_qubits == actual.num_qubits &&
+                     expected.num_clbits == actual.num_clbits &&
+                     expected.num_params == actual.num_params;
+        for (uint32_t j = 0; equal && j < expected.num_qubits; j++) {
+            equal = expected.qubits[j] == actual.qubits[j];
+        }
+        for (uint32_t j = 0; equal && j < expected.num_clbits; j++) {
+            equal = expected.clbits[j] == actual.clbits[j];
+        }
+        for (uint32_t j = 0; equal && j < expected.num_params; j++) {
+            equal = expected.params[j] == actual.params[j];
+        }
+        qk_circuit_instruction_clear(&expected);
+        qk_circuit_instruction_clear(&actual);
+        if (!equal) {
+            printf("Instruction %zu differs after loading\n", i);
+            result = EqualityError;
+            goto cleanup;
+        }
+    }
+
+    // Truncated data should fail to load rather than crash.
+    QkCircuit *truncated;
+    QkExitCode code = qk_circuit_load(bytes, len - 1, &truncated, &error);
+    if (code != QkExitCode_SerializationMalformed || truncated != NULL) {
+        printf("Loading truncated data gave exit code %d\n", code);
+        qk_circuit_free(truncated);
+        result = EqualityError;
+    } else {
+        if (strstr(error, "unexpected end of data") == NULL) {
+            printf("Unexpected error message for truncated data: %s\n", error);
+            result = EqualityError;
+        }
+        qk_str_free(error);
+    }
+
+cleanup:
+    qk_bytes_free(bytes, len);
+    qk_circuit_free(qc);
+    qk_circuit_free(loaded);
+    return result;
+}
+
 int test_circuit(void) {
     int num_failed = 0;
     num_failed += RUN_TEST(test_empty);
@@ -901,6 +991,7 @@ int test_circuit(void) {
     num_failed += RUN_TEST(test_not_unitary_gate);
     num_failed += RUN_TEST(test_unitary_gate_1q);
     num_failed += RUN_TEST(test_unitary_gate_3q);
+    num_failed += RUN_TEST(test_dump_load);
 
     fflush(stderr);
     fprintf(stderr, "=== Number of failed subtests: %i\n", num_failed);
@@ -0,0 +1,393 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2025.
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+#include "common.h"
+#include <qiskit.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdio.h>
+#include <string.h>
+
+/**
+ * Compare the exported program of a circuit with the expected text.
+ */
+static int check_qasm2(QkCircuit *qc, const char *expected) {
+    char *qasm;
+    char *error;
+    if (qk_circuit_to_qasm2(qc, &qasm, &error) != QkExitCode_Success) {
+        printf("Failed to export the circuit: %s\n", error);
+        qk_str_free(error);
+        return RuntimeError;
+    }
+    int result = Ok;
+    if (strcmp(qasm, expected) != 0) {
+        printf("Expected:\n%s\nbut got:\n%s\n", expected, qasm);
+        result = EqualityError;
+    }
+    qk_str_free(qasm);
+    return result;
+}
+
+/**
+ * Test exporting gates from qelib1.inc and built-in instructions.
+ */
+int test_qasm2_bell(void) {
+    QkCircuit *qc = qk_circuit_new(0, 0);
+    QkQuantumRegister *qr = qk_quantum_register_new(2, "q");
+    QkClassicalRegister *cr = qk_classical_register_new(2, "c");
+    qk_circuit_add_quantum_register(qc, qr);
+    qk_circuit_add_classical_register(qc, cr);
+    qk_quantum_register_free(qr);
+    qk_classical_register_free(cr);
+
+    uint32_t qubits[2] = {0, 1};
+    double angles[3] = {1.5707963267948966, -2.356194490192345, 0.125};
+    qk_circuit_gate(qc, QkGate_H, qubits, NULL);
+    qk_circuit_gate(qc, QkGate_CX, qubits, NULL);
+    qk_circuit_gate(qc, QkGate_U, qubits, angles);
+    qk_circuit_barrier(qc, qubits, 2);
+    qk_circuit_measure(qc, 0, 0);
+    qk_circuit_measure(qc, 1, 1);
+    qk_circuit_reset(qc, 1);
+
+    int result = check_qasm2(qc, "OPENQASM 2.0;\n"
+                                 "include \"qelib1.inc\";\n"
+                                 "qreg q[2];\n"
+                                 "creg c[2];\n"
+                                 "h q[0];\n"
+                                 "cx q[0],q[1];\n"
+                                 "u(pi/2,-3*pi/4,0.125) q[0];\n"
+                                 "barrier q[0],q[1];\n"
+                                 "measure q[0] -> c[0];\n"
+                                 "measure q[1] -> c[1];\n"
+                                 "reset q[1];");
+    qk_circuit_free(qc);
+    return result;
+}
+
+/**
+ * Test that bits outside registers and gates outside qelib1.inc are defined.
+ */
+int test_qasm2_definitions(void) {
+    QkCircuit *qc = qk_circuit_new(2, 0);
+    uint32_t qubits[2] = {0, 1};
+    double half[1] = {0.5};
+    double quarter[1] = {0.25};
+    qk_circuit_gate(qc, QkGate_ECR, qubits, NULL);
+    qk_circuit_gate(qc, QkGate_ECR, qubits, NULL);
+    qk_circuit_gate(qc, QkGate_RZX, qubits, half);
+    qk_circuit_gate(qc, QkGate_RZX, qubits, quarter);
+    qk_circuit_gate(qc, QkGate_RZX, qubits, half);
+    qk_circuit_delay(qc, 1, 100.0, QkDelayUnit_NS);
+
+    int result = check_qasm2(
+        qc, "OPENQASM 2.0;\n"
+            "include \"qelib1.inc\";\n"
+            "gate ecr q0,q1 { s q0; sx q1; cx q0,q1; x q0; }\n"
+            "gate rzx q0,q1 { h q1; cx q0,q1; rz(0.5) q1; cx q0,q1; h q1; }\n"
+            "gate rzx0 q0,q1 { h q1; cx q0,q1; rz(0.25) q1; cx q0,q1; h q1; }\n"
+            "opaque delay(param0) q0;\n"
+            "qreg qregless[2];\n"
+            "ecr qregless[0],qregless[1];\n"
+            "ecr qregless[0],qregless[1];\n"
+            "rzx qregless[0],qregless[1];\n"
+            "rzx0 qregless[0],qregless[1];\n"
+            "rzx qregless[0],qregless[1];\n"
+            "delay(100.0) qregless[1];");
+    qk_circuit_free(qc);
+    return result;
+}
+
+/**
+ * Test that gates whose definitions involve arithmetic on their parameters are defined.
+ */
+int test_qasm2_parameter_arithmetic(void) {
+    QkCircuit *qc = qk_circuit_new(2, 0);
+    uint32_t qubits[2] = {0, 1};
+    double angles[2] = {0.5, 0.25};
+    qk_circuit_gate(qc, QkGate_R, qubits, angles);
+    qk_circuit_gate(qc, QkGate_XXMinusYY, qubits, angles);
+    qk_circuit_gate(qc, QkGate_XXPlusYY, qubits, angles);
+
+    int result = check_qasm2(
+        qc, "OPENQASM 2.0;\n"
+            "include \"qelib1.inc\";\n"
+            "gate r q0 { u(0.5,-1.3207963267948966,1.3207963267948966) q0; }\n"
+            "gate xx_minus_yy q0,q1 { rz(-0.25) q1; sdg q0; sx q0; s q0; s q1; cx q0,q1; "
+            "ry(0.25) q0; ry(-0.25) q1; cx q0,q1; sdg q1; sdg q0; sxdg q0; s q0; rz(0.25) q1; }\n"
+            "gate xx_plus_yy q0,q1 { rz(0.25) q0; sdg q1; sx q1; s q1; s q0; cx q1,q0; "
+            "ry(-0.25) q1; ry(-0.25) q0; cx q1,q0; sdg q0; sdg q1; sxdg q1; s q1; rz(-0.25) q0; }\n"
+            "qreg qregless[2];\n"
+            "r qregless[0];\n"
+            "xx_minus_yy qregless[0],qregless[1];\n"
+            "xx_plus_yy qregless[0],qregless[1];");
+    qk_circuit_free(qc);
+    return result;
+}
+
+/**
+ * Test that circuits OpenQASM 2 can't represent fail to export.
+ */
+int test_qasm2_unrepresentable(void) {
+    QkCircuit *qc = qk_circuit_new(1, 0);
+    QkComplex64 matrix[4] = {{0, 0}, {1, 0}, {1, 0}, {0, 0}};
+    uint32_t qubits[1] = {0};
+    qk_circuit_unitary(qc, matrix, qubits, 1, true);
+
+    int result = Ok;
+    char *qasm;
+    char *error;
+    QkExitCode code = qk_circuit_to_qasm2(qc, &qasm, &error);
+    if (code != QkExitCode_Qasm2ExportError || qasm != NULL) {
+        printf("Exporting a unitary gave exit code %d\n", code);
+        qk_str_free(qasm);
+        result = EqualityError;
+    } else {
+        if (strstr(error, "cannot represent 'unitary'") == NULL) {
+            printf("Unexpected error message: %s\n", error);
+            result = EqualityError;
+        }
+        qk_str_free(error);
+    }
+    qk_circuit_free(qc);
+    return result;
+}
+
+/**
+ * Test that a loaded program exports back to the same text.
+ */
+int test_qasm2_load_roundtrip(void) {
+    const char *program = "OPENQASM 2.0;\n"
+                          "include \"qelib1.inc\";\n"
+                          "qreg q[2];\n"
+                          "creg c[2];\n"
+                          "h q[0];\n"
+                          "cx q[0],q[1];\n"
+                          "barrier q[0],q[1];\n"
+                          "measure q[0] -> c[0];\n"
+                          "measure q[1] -> c[1];\n"
+                          "reset q[1];";
+    QkCircuit *qc;
+    char *error;
+    if (qk_circuit_from_qasm2(program, true, false, NULL, 0, &qc, &error) != QkExitCode_Success) {
+        printf("Failed to load the program: %s\n", error);
+        qk_str_free(error);
+        return RuntimeError;
+    }
+    int result = Ok;
+    if (qk_circuit_num_qubits(qc) != 2 || qk_circuit_num_clbits(qc) != 2) {
+        printf("Expected 2 qubits and 2 clbits, but got %u and %u\n", qk_circuit_num_qubits(qc),
+               qk_circuit_num_clbits(qc));
+        result = EqualityError;
+    } else {
+        result = check_qasm2(qc, program);
+    }
+    qk_circuit_free(qc);
+    return result;
+}
+
+/**
+ * Test that gates defined in the program are expanded where they are applied.
+ */
+int test_qasm2_load_expands_gates(void) {
+    const char *program = "include \"qelib1.inc\";\n"
+                          "gate my(a) x, y { rx(2 * a) x; cx x, y; }\n"
+                          "qreg q[2];\n"
+                          "my(0.25) q[1], q[0];\n"
+                          "U(0, 0, 0.5) q[0];\n";
+    QkCircuit *qc;
+    char *error;
+    if (qk_circuit_from_qasm2(program, false, false, NULL, 0, &qc, &error) != QkExitCode_Success) {
+        printf("Failed to load the program: %s\n", error);
+        qk_str_free(error);
+        return RuntimeError;
+    }
+    int result = check_qasm2(qc, "OPENQASM 2.0;\n"
+                                 "include \"qelib1.inc\";\n"
+                                 "qreg q[2];\n"
+                                 "rx(0.5) q[1];\n"
+                                 "cx q[1],q[0];\n"
+                                 "u(0,0,0.5) q[0];");
+    qk_circuit_free(qc);
+    return result;
+}
+
+/**
+ * Test that invalid or unrepresentable programs fail to load with a description of the problem.
+ */
+int test_qasm2_load_invalid(void) {
+    const char *programs[4] = {
+        "qreg q[1]; h q[0];",
+        "qreg q[1];",
+        "include \"qelib1.inc\"; opaque o a; qreg q[1]; o q[0];",
+        "include \"qelib1.inc\"; qreg q[1]; creg c[1]; if (c == 1) x q[0];",
+    };
+    // The second program is only invalid in strict mode.
+    bool strict[4] = {false, true, false, false};
+    const char *messages[4] = {
+        "<input>:1,11: 'h' is not defined in this scope",
+        "<input>:1,0: [strict] the first statement must be 'OPENQASM 2.0;'",
+        "the opaque gate 'o' cannot be represented without Python",
+        "conditional statements cannot be represented without Python",
+    };
+    int result = Ok;
+    for (int i = 0; i < 4; i++) {
+        QkCircuit *qc;
+        char *error;
+        QkExitCode code =
+            qk_circuit_from_qasm2(programs[i], strict[i], false, NULL, 0, &qc, &error);
+        if (code == QkExitCode_Success) {
+            printf("Loading the program unexpectedly succeeded:\n%s\n", programs[i]);
+            qk_circuit_free(qc);
+            result = EqualityError;
+            continue;
+        }
+        if (code != QkExitCode_Qasm2LoadError || qc != NULL) {
+            printf("Unexpected exit code %d for the program:\n%s\n", code, programs[i]);
+            result = EqualityError;
+        } else if (strstr(error, messages[i]) == NULL) {
+            printf("Expected an error containing '%s', but got '%s'\n", messages[i], error);
+            result = EqualityError;
+        }
+        qk_str_free(error);
+    }
+    return result;
+}
+
+/**
+ * Test that include files are found in the given include path, and only there.
+ */
+int test_qasm2_load_include_path(void) {
+    const char *filename = "qk_test_qasm2_include.inc";
+    FILE *file = fopen(filename, "w");
+    if (file == NULL) {
+        printf("Failed to create the include file\n");
+        return RuntimeError;
+    }
+    fputs("gate my a { x a; }\n", file);
+    fclose(file);
+
+    const char *program = "include \"qelib1.inc\";\n"
+                          "include \"qk_test_qasm2_include.inc\";\n"
+                          "qreg q[1];\n"
+                          "my q[0];\n";
+    const char *include_path[2] = {"no-such-directory", "."};
+    int result = Ok;
+    QkCircuit *qc;
+    char *error;
+    if (qk_circuit_from_qasm2(program, false, false, include_path, 2, &qc, &error) !=
+        QkExitCode_Success) {
+        printf("Failed to load the program: %s\n", error);
+        qk_str_free(error);
+        result = RuntimeError;
+    } else {
+        result = check_qasm2(qc, "OPENQASM 2.0;\n"
+                                 "include \"qelib1.inc\";\n"
+                                 "qreg q[1];\n"
+                                 "x q[0];");
+        qk_circuit_free(qc);
+    }
+
+    if (result == Ok) {
+        if (qk_circuit_from_qasm2(program, false, false, include_path, 1, &qc, &error) !=
+            QkExitCode_Qasm2LoadError) {
+            printf("Loading the program without the include directory unexpectedly succeeded\n");
+            qk_circuit_free(qc);
+            result = EqualityError;
+        } else {
+            qk_str_free(error);
+        }
+    }
+    remove(filename);
+    return result;
+}
+
+/**
+ * Test that an exported program with gates outside qelib1.inc loads back with the legacy custom
+ * instructions, and only with them.
+ */
+int test_qasm2_load_legacy_roundtrip(void) {
+    QkCircuit *qc = qk_circuit_new(2, 0);
+    uint32_t qubits[2] = {0, 1};
+    qk_circuit_gate(qc, QkGate_SX, qubits, NULL);
+    qk_circuit_gate(qc, QkGate_Swap, qubits, NULL);
+    char *program;
+    char *error;
+    if (qk_circuit_to_qasm2(qc, &program, &error) != QkExitCode_Success) {
+        printf("Failed to export the circuit: %s\n", error);
+        qk_str_free(error);
+        qk_circuit_free(qc);
+        return RuntimeError;
+    }
+    qk_circuit_free(qc);
+
+    int result = Ok;
+    QkCircuit *loaded;
+    if (qk_circuit_from_qasm2(program, false, false, NULL, 0, &loaded, &error) !=
+        QkExitCode_Qasm2LoadError) {
+        printf("Loading the program without the legacy instructions unexpectedly succeeded\n");
+        qk_circuit_free(loaded);
+        result = EqualityError;
+        goto cleanup;
+    }
+    qk_str_free(error);
+
+    if (qk_circuit_from_qasm2(program, false, true, NULL, 0, &loaded, &error) !=
+        QkExitCode_Success) {
+        printf("Failed to load the program: %s\n", error);
+        qk_str_free(error);
+        result = RuntimeError;
+        goto cleanup;
+    }
+    const char *names[2] = {"sx", "swap"};
+    if (qk_circuit_num_instructions(loaded) != 2) {
+        printf("Expected 2 instructions, but got %zu\n", qk_circuit_num_instructions(loaded));
+        result = EqualityError;
+    } else {
+        for (size_t i = 0; i < 2; i++) {
+            QkCircuitInstruction inst;
+            qk_circuit_get_instruction(loaded, i, &inst);
+            if (strcmp(inst.name, names[i]) != 0) {
+                printf("Expected '%s', but got '%s'\n", names[i], inst.name);
+                result = EqualityError;
+            }
+            qk_circuit_instruction_clear(&inst);
+        }
+    }
+    if (result == Ok) {
+        result = check_qasm2(loaded, program);
+    }
+    qk_circuit_free(loaded);
+
+cleanup:
+    qk_str_free(program);
+    return result;
+}
+
+int test_qasm2(void) {
+    int num_failed = 0;
+    num_failed += RUN_TEST(test_qasm2_bell);
+    num_failed += RUN_TEST(test_qasm2_definitions);
+    num_failed += RUN_TEST(test_qasm2_parameter_arithmetic);
+    num_failed += RUN_TEST(test_qasm2_unrepresentable);
+    num_failed += RUN_TEST(test_qasm2_load_roundtrip);
+    num_failed += RUN_TEST(test_qasm2_load_expands_gates);
+    num_failed += RUN_TEST(test_qasm2_load_invalid);
+    num_failed += RUN_TEST(test_qasm2_load_include_path);
+    num_failed += RUN_TEST(test_qasm2_load_legacy_roundtrip);
+
+    fflush(stderr);
+    fprintf(stderr, "=== Number of failed subtests: %i\n", num_failed);
+
+    return num_failed;
+}
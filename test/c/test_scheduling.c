@@ -0,0 +1,176 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2025.
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+#include "common.h"
+#include <qiskit.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdio.h>
+#include <string.h>
+
+/**
+ * A single-qubit target with ``dt = 1ns``, in which ``x`` takes 160 dt and ``measure`` takes
+ * 1000 dt.
+ */
+static QkTarget *scheduling_target(void) {
+    QkTarget *target = qk_target_new(1);
+    qk_target_set_dt(target, 1e-9);
+    uint32_t qargs[1] = {0};
+    QkTargetEntry *x = qk_target_entry_new(QkGate_X);
+    qk_target_entry_add_property(x, qargs, 1, 160e-9, 0.0);
+    qk_target_add_instruction(target, x);
+    QkTargetEntry *measure = qk_target_entry_new_measure();
+    qk_target_entry_add_property(measure, qargs, 1, 1000e-9, 0.0);
+    qk_target_add_instruction(target, measure);
+    return target;
+}
+
+/**
+ * ``X(0); delay(100[ns]) (0); measure(0, 0); X(0)``.
+ */
+static QkCircuit *scheduling_circuit(void) {
+    QkCircuit *qc = qk_circuit_new(1, 1);
+    qk_circuit_gate(qc, QkGate_X, (uint32_t[]){0}, NULL);
+    qk_circuit_delay(qc, 0, 100.0, QkDelayUnit_NS);
+    qk_circuit_measure(qc, 0, 0);
+    qk_circuit_gate(qc, QkGate_X, (uint32_t[]){0}, NULL);
+    return qc;
+}
+
+static int check_start_times(const double *actual, const double *expected, size_t len) {
+    for (size_t i = 0; i < len; i++) {
+        if (actual[i] != expected[i]) {
+            printf("Start time %zu is %f, expected %f.", i, actual[i], expected[i]);
+            return EqualityError;
+        }
+    }
+    return Ok;
+}
+
+/**
+ * Test that ASAP and ALAP scheduling give the start times in instruction order.
+ */
+int test_schedule_analysis(void) {
+    int result = Ok;
+    QkCircuit *qc = scheduling_circuit();
+    QkTarget *target = scheduling_target();
+    double start_times[4];
+
+    QkExitCode code = qk_transpiler_asap_schedule_analysis(qc, target, 0.0, start_times, NULL);
+    if (code != QkExitCode_Success) {
+        printf("ASAP scheduling failed with %d.", code);
+        result = RuntimeError;
+        goto cleanup;
+    }
+    result = check_start_times(start_times, (double[]){0., 160., 260., 1260.}, 4);
+    if (result != Ok) {
+        goto cleanup;
+    }
+
+    code = qk_transpiler_alap_schedule_analysis(qc, target, 0.0, start_times, NULL);
+    if (code != QkExitCode_Success) {
+        printf("ALAP scheduling failed with %d.", code);
+        result = RuntimeError;
+        goto cleanup;
+    }
+    result = check_start_times(start_times, (double[]){0., 160., 260., 1260.}, 4);
+
+cleanup:
+    qk_target_free(target);
+    qk_circuit_free(qc);
+    return result;
+}
+
+/**
+ * Test that scheduling aligns the start times to the alignment constraints of the target, and
+ * that rescheduling aligns an unaligned schedule the same way.
+ */
+int test_schedule_alignment(void) {
+    int result = Ok;
+    QkCircuit *qc = scheduling_circuit();
+    QkTarget *target = scheduling_target();
+    double expected[4] = {0., 160., 272., 1280.};
+    double start_times[4];
+
+    qk_target_set_pulse_alignment(target, 16);
+    qk_target_set_acquire_alignment(target, 16);
+    QkExitCode code = qk_transpiler_asap_schedule_analysis(qc, target, 0.0, start_times, NULL);
+    if (code != QkExitCode_Success) {
+        printf("ASAP scheduling failed with %d.", code);
+        result = RuntimeError;
+        goto cleanup;
+    }
+    result = check_start_times(start_times, expected, 4);
+    if (result != Ok) {
+        goto cleanup;
+    }
+
+    double unaligned[4] = {0., 160., 260., 1260.};
+    code = qk_transpiler_constrained_reschedule(qc, target, 0.0, unaligned, NULL);
+    if (code != QkExitCode_Success) {
+        printf("Rescheduling failed with %d.", code);
+        result = RuntimeError;
+        goto cleanup;
+    }
+    result = check_start_times(unaligned, expected, 4);
+
+cleanup:
+    qk_target_free(target);
+    qk_circuit_free(qc);
+    return result;
+}
+
+/**
+ * Test that rescheduling for a target without dt fails with a message and leaves the start
+ * times alone.
+ */
+int test_constrained_reschedule_no_dt(void) {
+    int result = Ok;
+    QkCircuit *qc = qk_circuit_new(1, 1);
+    qk_circuit_measure(qc, 0, 0);
+    QkTarget *target = qk_target_new(1);
+    qk_target_add_instruction(target, qk_target_entry_new_measure());
+    double start_times[1] = {100.};
+
+    char *error = NULL;
+    QkExitCode code = qk_transpiler_constrained_reschedule(qc, target, 0.0, start_times, &error);
+    if (code != QkExitCode_TranspilerError) {
+        printf("Rescheduling without dt returned %d.", code);
+        result = RuntimeError;
+        goto cleanup;
+    }
+    if (error == NULL || strstr(error, "dt") == NULL) {
+        printf("Unexpected error message: %s", error == NULL ? "(null)" : error);
+        result = EqualityError;
+    } else if (start_times[0] != 100.) {
+        printf("The start time was changed to %f.", start_times[0]);
+        result = EqualityError;
+    }
+    qk_str_free(error);
+
+cleanup:
+    qk_target_free(target);
+    qk_circuit_free(qc);
+    return result;
+}
+
+int test_scheduling(void) {
+    int num_failed = 0;
+    num_failed += RUN_TEST(test_schedule_analysis);
+    num_failed += RUN_TEST(test_schedule_alignment);
+    num_failed += RUN_TEST(test_constrained_reschedule_no_dt);
+
+    fflush(stderr);
+    fprintf(stderr, "=== Number of failed subtests: %i\n", num_failed);
+
+    return num_failed;
+}
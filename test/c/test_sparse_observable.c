@@ -12,6 +12,7 @@
 
 #include "common.h"
 #include <complex.h>
+#include <math.h>
 #include <qiskit.h>
 #include <stdbool.h>
 #include <stdint.h>
@@ -159,6 +160,44 @@ int test_compose_map(void) {
     return Ok;
 }
 
+/**
+ * Test applying a layout to an observable.
+ */
+int test_apply_layout(void) {
+    int result = Ok;
+    QkObs *op = qk_obs_zero(2);
+    QkComplex64 coeff = {1.0, 0.0};
+    QkBitTerm op_bits[2] = {QkBitTerm_X, QkBitTerm_Z};
+    uint32_t op_indices[2] = {0, 1};
+    QkObsTerm term = {coeff, 2, op_bits, op_indices, 2};
+    qk_obs_add_term(op, &term);
+
+    uint32_t layout[2] = {3, 0};
+    QkObs *mapped = qk_obs_apply_layout(op, layout, 4);
+
+    QkObs *expected = qk_obs_zero(4);
+    QkBitTerm expected_bits[2] = {QkBitTerm_Z, QkBitTerm_X};
+    uint32_t expected_indices[2] = {0, 3};
+    QkObsTerm expected_term = {coeff, 2, expected_bits, expected_indices, 4};
+    qk_obs_add_term(expected, &expected_term);
+
+    if (mapped == NULL || !qk_obs_equal(expected, mapped)) {
+        result = EqualityError;
+    }
+
+    uint32_t duplicate[2] = {1, 1};
+    QkObs *invalid = qk_obs_apply_layout(op, duplicate, 4);
+    if (invalid != NULL) {
+        qk_obs_free(invalid);
+        result = RuntimeError;
+    }
+
+    qk_obs_free(op);
+    qk_obs_free(mapped);
+    qk_obs_free(expected);
+    return result;
+}
+
 /**
  * Test composing an observables with a scalar observable.
  */
@@ -721,6 +760,205 @@ int test_obsterm_str(void) {
     return result;
 }
 
+/**
+ * Build the observable 2 (|+><+|)_0 (|1><1|)_1 used in the matrix tests.
+ */
+static QkObs *projector_observable(void) {
+    QkObs *obs = qk_obs_zero(2);
+    QkComplex64 coeff = {2.0, 0.0};
+    QkBitTerm bit_terms[2] = {QkBitTerm_Plus, QkBitTerm_One};
+    uint32_t indices[2] = {0, 1};
+    QkObsTerm term = {coeff, 2, bit_terms, indices, 2};
+    qk_obs_add_term(obs, &term);
+    return obs;
+}
+
+/**
+ * Test writing out the dense matrix of an observable with projectors.
+ */
+int test_to_matrix_dense(void) {
+    QkObs *obs = projector_observable();
+    QkComplex64 matrix[16];
+    int exit_code = qk_obs_to_matrix_dense(obs, matrix);
+    qk_obs_free(obs);
+    if (exit_code != QkExitCode_Success) {
+        return RuntimeError;
+    }
+
+    for (size_t row = 0; row < 4; row++) {
+        for (size_t col = 0; col < 4; col++) {
+            // Only the rows and columns where qubit 1 is in |1> are non-zero.
+            double expected = (row >= 2 && col >= 2) ? 1.0 : 0.0;
+            QkComplex64 value = matrix[4 * row + col];
+            if (value.re != expected || value.im != 0.0) {
+                printf("Unexpected element (%zu, %zu): %f + %fi\n", row, col, value.re, value.im);
+                return EqualityError;
+            }
+        }
+    }
+    return Ok;
+}
+
+/**
+ * Test building the CSR matrix of an observable with projectors.
+ */
+int test_to_matrix_sparse(void) {
+    QkObs *obs = projector_observable();
+    QkCsrMatrix matrix;
+    int exit_code = qk_obs_to_matrix_sparse(obs, &matrix);
+    qk_obs_free(obs);
+    if (exit_code != QkExitCode_Success) {
+        return RuntimeError;
+    }
+
+    int result = Ok;
+    size_t indptr[5] = {0, 0, 0, 2, 4};
+    size_t indices[4] = {2, 3, 2, 3};
+    if (matrix.dim != 4 || matrix.nnz != 4) {
+        result = EqualityError;
+        goto cleanup;
+    }
+    for (size_t i = 0; i < 5; i++) {
+        if (matrix.indptr[i] != indptr[i]) {
+            result = EqualityError;
+            goto cleanup;
+        }
+    }
+    for (size_t i = 0; i < 4; i++) {
+        if (matrix.indices[i] != indices[i] || matrix.data[i].re != 1.0 ||
+            matrix.data[i].im != 0.0) {
+            result = EqualityError;
+            goto cleanup;
+        }
+    }
+
+cleanup:
+    qk_csrmatrix_free(matrix);
+    return result;
+}
+
+/**
+ * Test that building the matrix of a large observable fails cleanly.
+ */
+int test_to_matrix_too_many_qubits(void) {
+    QkObs *obs = qk_obs_identity(100);
+    QkComplex64 matrix[1];
+    QkCsrMatrix csr;
+    int dense_exit = qk_obs_to_matrix_dense(obs, matrix);
+    int sparse_exit = qk_obs_to_matrix_sparse(obs, &csr);
+    qk_obs_free(obs);
+
+    return (dense_exit != QkExitCode_TooManyQubits || sparse_exit != QkExitCode_TooManyQubits)
+               ? EqualityError
+               : Ok;
+}
+
+/**
+ * Test the expectation value of an observable with projectors against a statevector.
+ */
+int test_expectation_value_statevector(void) {
+    // 2 (|+><+|)_0 (|1><1|)_1 against (|10> + |11>) / sqrt(2) = |1>|+>.
+    QkObs *obs = projector_observable();
+    double amp = 1.0 / sqrt(2.0);
+    QkComplex64 state[4] = {{0.0, 0.0}, {0.0, 0.0}, {amp, 0.0}, {amp, 0.0}};
+    QkComplex64 expval;
+    int exit_code = qk_obs_expectation_value_statevector(obs, state, &expval);
+    qk_obs_free(obs);
+
+    if (exit_code != QkExitCode_Success) {
+        return RuntimeError;
+    }
+    if (fabs(expval.re - 2.0) > 1e-12 || fabs(expval.im) > 1e-12) {
+        printf("Unexpected expectation value: %f + %fi\n", expval.re, expval.im);
+        return EqualityError;
+    }
+    return Ok;
+}
+
+/**
+ * Test the expectation value of an observable with projectors against a density matrix.
+ */
+int test_expectation_value_density_matrix(void) {
+    // 2 (|+><+|)_0 (|1><1|)_1 against the maximally mixed state.
+    QkObs *obs = projector_observable();
+    QkComplex64 state[16];
+    for (size_t i = 0; i < 16; i++) {
+        state[i] = (QkComplex64){(i % 5 == 0) ? 0.25 : 0.0, 0.0};
+    }
+    QkComplex64 expval;
+    int exit_code = qk_obs_expectation_value_density_matrix(obs, state, &expval);
+    qk_obs_free(obs);
+
+    if (exit_code != QkExitCode_Success) {
+        return RuntimeError;
+    }
+    if (fabs(expval.re - 0.5) > 1e-12 || fabs(expval.im) > 1e-12) {
+        printf("Unexpected expectation value: %f + %fi\n", expval.re, expval.im);
+        return EqualityError;
+    }
+    return Ok;
+}
+
+/**
+ * Test estimating the expectation value of an observable from measurement counts.
+ */
+int test_expectation_value_counts(void) {
+    // 2 (|+><+|)_0 (|1><1|)_1, with qubit 0 measured in the X basis.
+    QkObs *obs = projector_observable();
+    const char *outcomes[3] = {"10", "11", "00"};
+    double counts[3] = {60.0, 20.0, 20.0};
+    QkBitTerm basis[2] = {QkBitTerm_X, QkBitTerm_Z};
+    QkComplex64 expval;
+    int exit_code = qk_obs_expectation_value_counts(obs, outcomes, counts, 3, basis, &expval);
+    // Measuring qubit 0 in the Z basis is incompatible with the |+><+| projector.
+    int incompatible = qk_obs_expectation_value_counts(obs, outcomes, counts, 3, NULL, &expval);
+    qk_obs_free(obs);
+
+    if (exit_code != QkExitCode_Success || incompatible != QkExitCode_IncompatibleBasis) {
+        return RuntimeError;
+    }
+    if (fabs(expval.re - 1.2) > 1e-12 || fabs(expval.im) > 1e-12) {
+        printf("Unexpected expectation value: %f + %fi\n", expval.re, expval.im);
+        return EqualityError;
+    }
+    return Ok;
+}
+
+/**
+ * Test that the expectation value of a large observable against a state fails cleanly.
+ */
+int test_expectation_value_too_many_qubits(void) {
+    // The states would have more bytes than fit in an `isize` on 64-bit platforms, though the
+    // number of elements still fits in a `size_t`.
+    QkObs *sv_obs = qk_obs_identity(60);
+    QkObs *dm_obs = qk_obs_identity(30);
+    QkComplex64 state[1] = {{1.0, 0.0}};
+    QkComplex64 expval;
+    int sv_exit = qk_obs_expectation_value_statevector(sv_obs, state, &expval);
+    int dm_exit = qk_obs_expectation_value_density_matrix(dm_obs, state, &expval);
+    qk_obs_free(sv_obs);
+    qk_obs_free(dm_obs);
+
+    return (sv_exit != QkExitCode_TooManyQubits || dm_exit != QkExitCode_TooManyQubits)
+               ? EqualityError
+               : Ok;
+}
+
+/**
+ * Test that estimating an expectation value from counts with no shots is an input error.
+ */
+int test_expectation_value_counts_no_shots(void) {
+    QkObs *obs = projector_observable();
+    const char *outcomes[1] = {"10"};
+    double counts[1] = {0.0};
+    QkBitTerm basis[2] = {QkBitTerm_X, QkBitTerm_Z};
+    QkComplex64 expval;
+    int exit_code = qk_obs_expectation_value_counts(obs, outcomes, counts, 1, basis, &expval);
+    qk_obs_free(obs);
+
+    return (exit_code != QkExitCode_CInputError) ? EqualityError : Ok;
+}
+
 int test_sparse_observable(void) {
     int num_failed = 0;
     num_failed += RUN_TEST(test_zero);
@@ -728,6 +966,7 @@ int test_sparse_observable(void) {
     num_failed += RUN_TEST(test_add);
     num_failed += RUN_TEST(test_compose);
     num_failed += RUN_TEST(test_compose_map);
+    num_failed += RUN_TEST(test_apply_layout);
     num_failed += RUN_TEST(test_compose_scalar);
     num_failed += RUN_TEST(test_mult);
     num_failed += RUN_TEST(test_canonicalize);
@@ -746,6 +985,14 @@ int test_sparse_observable(void) {
     num_failed += RUN_TEST(test_direct_fail);
     num_failed += RUN_TEST(test_obs_str);
     num_failed += RUN_TEST(test_obsterm_str);
+    num_failed += RUN_TEST(test_to_matrix_dense);
+    num_failed += RUN_TEST(test_to_matrix_sparse);
+    num_failed += RUN_TEST(test_to_matrix_too_many_qubits);
+    num_failed += RUN_TEST(test_expectation_value_statevector);
+    num_failed += RUN_TEST(test_expectation_value_density_matrix);
+    num_failed += RUN_TEST(test_expectation_value_counts);
+    num_failed += RUN_TEST(test_expectation_value_too_many_qubits);
+    num_failed += RUN_TEST(test_expectation_value_counts_no_shots);
 
     fflush(stderr);
     fprintf(stderr, "=== Number of failed subtests: %i\n", num_failed);
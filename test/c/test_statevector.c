@@ -0,0 +1,170 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2025.
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+#include "common.h"
+#include <complex.h>
+#include <math.h>
+#include <qiskit.h>
+#include <stdint.h>
+#include <stdio.h>
+
+static const double PI = 3.14159265358979323846;
+static const double SQRT1_2 = 0.70710678118654752440;
+
+/**
+ * Test the statevector of a Bell circuit.
+ */
+int test_bell_statevector(void) {
+    QkCircuit *qc = qk_circuit_new(2, 0);
+    uint32_t qubits[2] = {0, 1};
+    qk_circuit_gate(qc, QkGate_H, qubits, NULL);
+    qk_circuit_gate(qc, QkGate_CX, qubits, NULL);
+
+    QkComplex64 statevector[4];
+    int result = Ok;
+    QkExitCode code = qk_circuit_statevector(qc, NULL, statevector);
+    if (code != QkExitCode_Success) {
+        result = RuntimeError;
+        goto cleanup;
+    }
+
+    double expected[4] = {SQRT1_2, 0., 0., SQRT1_2};
+    for (int i = 0; i < 4; i++) {
+        if (fabs(statevector[i].re - expected[i]) > 1e-12 || fabs(statevector[i].im) > 1e-12) {
+            printf("Amplitude %d is %f + %fi, expected %f", i, statevector[i].re,
+                   statevector[i].im, expected[i]);
+            result = EqualityError;
+            goto cleanup;
+        }
+    }
+
+cleanup:
+    qk_circuit_free(qc);
+    return result;
+}
+
+/**
+ * Test the statevector of a circuit with a unitary gate and a global-phase gate.
+ */
+int test_unitary_statevector(void) {
+    QkCircuit *qc = qk_circuit_new(2, 0);
+    QkComplex64 c0 = {0, 0};
+    QkComplex64 c1 = {1, 0};
+    QkComplex64 x[4] = {c0, c1, c1, c0};
+    uint32_t qubit[1] = {1};
+    qk_circuit_unitary(qc, x, qubit, 1, true);
+    double phase[1] = {PI / 2};
+    qk_circuit_gate(qc, QkGate_GlobalPhase, NULL, phase);
+
+    QkComplex64 statevector[4];
+    int result = Ok;
+    QkExitCode code = qk_circuit_statevector(qc, NULL, statevector);
+    if (code != QkExitCode_Success) {
+        result = RuntimeError;
+        goto cleanup;
+    }
+    // X on qubit 1 with a phase of i gives i|10>.
+    if (fabs(statevector[2].re) > 1e-12 || fabs(statevector[2].im - 1.) > 1e-12) {
+        printf("Amplitude 2 is %f + %fi, expected i", statevector[2].re, statevector[2].im);
+        result = EqualityError;
+    }
+
+cleanup:
+    qk_circuit_free(qc);
+    return result;
+}
+
+/**
+ * Test seeded sampling of a GHZ circuit.
+ */
+int test_ghz_sample_memory(void) {
+    QkCircuit *qc = qk_circuit_new(3, 3);
+    uint32_t qubit[1] = {0};
+    qk_circuit_gate(qc, QkGate_H, qubit, NULL);
+    for (uint32_t i = 1; i < 3; i++) {
+        uint32_t qubits[2] = {0, i};
+        qk_circuit_gate(qc, QkGate_CX, qubits, NULL);
+    }
+    for (uint32_t i = 0; i < 3; i++) {
+        qk_circuit_measure(qc, i, i);
+    }
+
+    uint64_t seed = 42;
+    uint64_t memory[100];
+    uint64_t other[100];
+    int result = Ok;
+    QkExitCode code = qk_circuit_sample_memory(qc, 100, &seed, memory);
+    QkExitCode other_code = qk_circuit_sample_memory(qc, 100, &seed, other);
+    if (code != QkExitCode_Success || other_code != QkExitCode_Success) {
+        result = RuntimeError;
+        goto cleanup;
+    }
+    for (int i = 0; i < 100; i++) {
+        if (memory[i] != 0 && memory[i] != 7) {
+            printf("Shot %d has invalid outcome %lu", i, (unsigned long)memory[i]);
+            result = EqualityError;
+            goto cleanup;
+        }
+        if (memory[i] != other[i]) {
+            printf("Shot %d differs between runs with the same seed", i);
+            result = EqualityError;
+            goto cleanup;
+        }
+    }
+
+cleanup:
+    qk_circuit_free(qc);
+    return result;
+}
+
+/**
+ * Test that a reset in the middle of a circuit is simulated.
+ */
+int test_mid_circuit_reset(void) {
+    QkCircuit *qc = qk_circuit_new(1, 1);
+    uint32_t qubit[1] = {0};
+    qk_circuit_gate(qc, QkGate_X, qubit, NULL);
+    qk_circuit_reset(qc, 0);
+    qk_circuit_measure(qc, 0, 0);
+
+    uint64_t memory[10];
+    int result = Ok;
+    QkExitCode code = qk_circuit_sample_memory(qc, 10, NULL, memory);
+    if (code != QkExitCode_Success) {
+        result = RuntimeError;
+        goto cleanup;
+    }
+    for (int i = 0; i < 10; i++) {
+        if (memory[i] != 0) {
+            printf("Shot %d is %lu, expected 0", i, (unsigned long)memory[i]);
+            result = EqualityError;
+            goto cleanup;
+        }
+    }
+
+cleanup:
+    qk_circuit_free(qc);
+    return result;
+}
+
+int test_statevector(void) {
+    int num_failed = 0;
+    num_failed += RUN_TEST(test_bell_statevector);
+    num_failed += RUN_TEST(test_unitary_statevector);
+    num_failed += RUN_TEST(test_ghz_sample_memory);
+    num_failed += RUN_TEST(test_mid_circuit_reset);
+
+    fflush(stderr);
+    fprintf(stderr, "=== Number of failed subtests: %i\n", num_failed);
+
+    return num_failed;
+}
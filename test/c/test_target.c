@@ -416,6 +416,144 @@ cleanup:
     return result;
 }
 
+/**
+ * Test serializing a Target to JSON and loading it back.
+ */
+int test_target_json(void) {
+    int result = Ok;
+    QkTarget *target = qk_target_new(2);
+    qk_target_set_dt(target, 2.2e-10);
+    qk_target_set_granularity(target, 16);
+    QkTargetEntry *cx_entry = qk_target_entry_new(QkGate_CX);
+    uint32_t qargs[2] = {0, 1};
+    qk_target_entry_add_property(cx_entry, qargs, 2, 5e-7, 0.01);
+    qk_target_add_instruction(target, cx_entry);
+    QkTargetEntry *measure_entry = qk_target_entry_new_measure();
+    for (uint32_t i = 0; i < 2; i++) {
+        uint32_t q[1] = {i};
+        qk_target_entry_add_property(measure_entry, q, 1, 1e-6, 0.02);
+    }
+    qk_target_add_instruction(target, measure_entry);
+
+    char *json = NULL;
+    char *error = NULL;
+    QkTarget *loaded = NULL;
+    if (qk_target_to_json(target, &json, &error) != QkExitCode_Success) {
+        printf("The target could not be serialized: %s", error);
+        qk_str_free(error);
+        result = RuntimeError;
+        goto cleanup;
+    }
+    if (qk_target_from_json(json, &loaded, &error) != QkExitCode_Success) {
+        printf("The serialized target %s could not be loaded: %s", json, error);
+        qk_str_free(error);
+        result = RuntimeError;
+        goto cleanup;
+    }
+    if (qk_target_num_qubits(loaded) != 2 || qk_target_num_instructions(loaded) != 2) {
+        printf("The loaded target has %u qubits and %zu instructions.",
+               qk_target_num_qubits(loaded), qk_target_num_instructions(loaded));
+        result = EqualityError;
+        goto cleanup;
+    }
+    if (qk_target_dt(loaded) != 2.2e-10 || qk_target_granularity(loaded) != 16) {
+        printf("The timing constraints of the loaded target do not match.");
+        result = EqualityError;
+        goto cleanup;
+    }
+    char *reserialized;
+    qk_target_to_json(loaded, &reserialized, NULL);
+    if (strcmp(json, reserialized) != 0) {
+        printf("Round-tripped JSON %s does not match %s.", reserialized, json);
+        result = EqualityError;
+    }
+    qk_str_free(reserialized);
+
+    QkTarget *invalid;
+    QkExitCode code =
+        qk_target_from_json("{\"version\": 1, \"instructions\": 3}", &invalid, &error);
+    if (code != QkExitCode_TargetJson || invalid != NULL) {
+        printf("Loading an invalid document did not fail as expected.");
+        result = RuntimeError;
+    } else {
+        if (strstr(error, "Invalid Target JSON") == NULL) {
+            printf("Unexpected error message: %s", error);
+            result = EqualityError;
+        }
+        qk_str_free(error);
+    }
+
+cleanup:
+    if (json != NULL) {
+        qk_str_free(json);
+    }
+    qk_target_free(loaded);
+    qk_target_free(target);
+    return result;
+}
+
+/**
+ * Test round-tripping a Target with a parameterized rz through JSON.
+ */
+int test_target_json_free_parameter(void) {
+    int result = Ok;
+    const char *document =
+        "{\"version\": 1, \"num_qubits\": 2, \"instructions\": ["
+        "{\"name\": \"rz\", \"operation\": {\"type\": \"gate\", \"name\": \"rz\"}, "
+        "\"params\": [\"theta\"], \"properties\": ["
+        "{\"qargs\": [0], \"properties\": {\"duration\": 0.0, \"error\": 0.0}}, "
+        "{\"qargs\": [1], \"properties\": {\"duration\": 0.0, \"error\": 0.0}}]}, "
+        "{\"name\": \"rz_fixed\", \"operation\": {\"type\": \"gate\", \"name\": \"rz\"}, "
+        "\"params\": [0.5], \"properties\": [{\"qargs\": [0], \"properties\": null}]}]}";
+    QkTarget *target = NULL;
+    QkTarget *loaded = NULL;
+    char *json = NULL;
+    char *error = NULL;
+    if (qk_target_from_json(document, &target, &error) != QkExitCode_Success) {
+        printf("The document could not be loaded: %s", error);
+        qk_str_free(error);
+        result = RuntimeError;
+        goto cleanup;
+    }
+    if (qk_target_num_instructions(target) != 2) {
+        printf("The loaded target has %zu instructions.", qk_target_num_instructions(target));
+        result = EqualityError;
+        goto cleanup;
+    }
+    if (qk_target_to_json(target, &json, &error) != QkExitCode_Success) {
+        printf("The target with a free parameter could not be serialized: %s", error);
+        qk_str_free(error);
+        result = RuntimeError;
+        goto cleanup;
+    }
+    if (strstr(json, "\"params\":[\"theta\"]") == NULL) {
+        printf("The free parameter is missing from %s.", json);
+        result = EqualityError;
+        goto cleanup;
+    }
+    if (qk_target_from_json(json, &loaded, &error) != QkExitCode_Success) {
+        printf("The serialized target %s could not be loaded: %s", json, error);
+        qk_str_free(error);
+        result = RuntimeError;
+        goto cleanup;
+    }
+    char *reserialized;
+    qk_target_to_json(loaded, &reserialized, NULL);
+    if (strcmp(json, reserialized) != 0) {
+        printf("Round-tripped JSON %s does not match %s.", reserialized, json);
+        result = EqualityError;
+    }
+    qk_str_free(reserialized);
+
+cleanup:
+    if (json != NULL) {
+        qk_str_free(json);
+    }
+    qk_target_free(loaded);
+    qk_target_free(target);
+    return result;
+}
+
 int test_target(void) {
     int num_failed = 0;
     num_failed += RUN_TEST(test_empty_target);
@@ -423,6 +561,8 @@ int test_target(void) {
     num_failed += RUN_TEST(test_target_entry_construction);
     num_failed += RUN_TEST(test_target_add_instruction);
     num_failed += RUN_TEST(test_target_update_instruction);
+    num_failed += RUN_TEST(test_target_json);
+    num_failed += RUN_TEST(test_target_json_free_parameter);
 
     fflush(stderr);
     fprintf(stderr, "=== Number of failed subtests: %i\n", num_failed);
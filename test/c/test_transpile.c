@@ -0,0 +1,475 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2025.
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+#include "common.h"
+#include <math.h>
+#include <qiskit.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdio.h>
+#include <string.h>
+
+/**
+ * Test the default transpiler options.
+ */
+int test_transpiler_default_options(void) {
+    QkTranspileOptions options = qk_transpiler_default_options();
+    if (options.optimization_level != 2 || options.seed >= 0 ||
+        options.approximation_degree != 1.0) {
+        printf("Unexpected default options: level %u, seed %lld, approximation %f.",
+               options.optimization_level, (long long)options.seed,
+               options.approximation_degree);
+        return EqualityError;
+    }
+    return Ok;
+}
+
+/**
+ * Test that transpiling a circuit wider than the target fails with a message.
+ */
+int test_transpile_too_wide(void) {
+    int result = Ok;
+    QkCircuit *qc = qk_circuit_new(3, 0);
+    QkTarget *target = qk_target_new(2);
+    qk_target_add_instruction(target, qk_target_entry_new(QkGate_CX));
+
+    QkTranspileOptions options = qk_transpiler_default_options();
+    options.seed = 7;
+    QkTranspileResult out;
+    char *error = NULL;
+    QkExitCode code = qk_transpile(qc, target, &options, &out, &error);
+    if (code != QkExitCode_TranspilerError) {
+        printf("Transpiling a 3q circuit for a 2q target returned %d.", code);
+        result = RuntimeError;
+        if (code == QkExitCode_Success) {
+            qk_transpile_result_clear(&out);
+        }
+        goto cleanup;
+    }
+    if (error == NULL || strstr(error, "only has 2") == NULL) {
+        printf("Unexpected error message: %s", error == NULL ? "(null)" : error);
+        result = EqualityError;
+    }
+    if (error != NULL) {
+        qk_str_free(error);
+    }
+
+cleanup:
+    qk_target_free(target);
+    qk_circuit_free(qc);
+    return result;
+}
+
+/**
+ * Build a target with ``rz``, ``sx`` and ``x`` on every qubit, ``cx`` along a line in the
+ * forwards direction only, and measurements.
+ *
+ * An ``rz`` that accepts any angle can only be added through the JSON representation, so the
+ * target starts from a document with that entry.
+ */
+static QkTarget *line_target(uint32_t num_qubits) {
+    char document[1024];
+    size_t length = snprintf(document, sizeof(document),
+                             "{\"version\": 1, \"num_qubits\": %u, \"instructions\": ["
+                             "{\"name\": \"rz\", \"operation\": {\"type\": \"gate\", "
+                             "\"name\": \"rz\"}, \"params\": [\"theta\"], \"properties\": [",
+                             num_qubits);
+    for (uint32_t q = 0; q < num_qubits; q++) {
+        length += snprintf(document + length, sizeof(document) - length,
+                           "%s{\"qargs\": [%u], \"properties\": "
+                           "{\"duration\": 3.5e-8, \"error\": 1e-4}}",
+                           q == 0 ? "" : ", ", q);
+    }
+    snprintf(document + length, sizeof(document) - length, "]}]}");
+    QkTarget *target = NULL;
+    qk_target_from_json(document, &target, NULL);
+
+    QkGate one_qubit_gates[2] = {QkGate_SX, QkGate_X};
+    for (int i = 0; i < 2; i++) {
+        QkTargetEntry *entry = qk_target_entry_new(one_qubit_gates[i]);
+        for (uint32_t q = 0; q < num_qubits; q++) {
+            uint32_t qargs[1] = {q};
+            qk_target_entry_add_property(entry, qargs, 1, 3.5e-8, 1e-4);
+        }
+        qk_target_add_instruction(target, entry);
+    }
+    QkTargetEntry *cx = qk_target_entry_new(QkGate_CX);
+    for (uint32_t q = 0; q + 1 < num_qubits; q++) {
+        uint32_t qargs[2] = {q, q + 1};
+        qk_target_entry_add_property(cx, qargs, 2, 5e-7, 1e-2);
+    }
+    qk_target_add_instruction(target, cx);
+    QkTargetEntry *measure = qk_target_entry_new_measure();
+    for (uint32_t q = 0; q < num_qubits; q++) {
+        uint32_t qargs[1] = {q};
+        qk_target_entry_add_property(measure, qargs, 1, 1e-6, 1e-2);
+    }
+    qk_target_add_instruction(target, measure);
+    return target;
+}
+
+/**
+ * Test transpiling a circuit with a three-qubit gate and gates outside the target.
+ */
+int test_transpile_to_target(void) {
+    int result = Ok;
+    QkCircuit *qc = qk_circuit_new(3, 3);
+    qk_circuit_gate(qc, QkGate_H, (uint32_t[]){0}, NULL);
+    qk_circuit_gate(qc, QkGate_CX, (uint32_t[]){0, 2}, NULL);
+    qk_circuit_gate(qc, QkGate_CCX, (uint32_t[]){2, 1, 0}, NULL);
+    qk_circuit_gate(qc, QkGate_RY, (uint32_t[]){1}, (double[]){0.25});
+    qk_circuit_gate(qc, QkGate_CZ, (uint32_t[]){1, 0}, NULL);
+    for (uint32_t q = 0; q < 3; q++) {
+        qk_circuit_measure(qc, q, q);
+    }
+    QkTarget *target = line_target(3);
+
+    for (uint8_t level = 0; level <= 3; level++) {
+        QkTranspileOptions options = qk_transpiler_default_options();
+        options.optimization_level = level;
+        options.seed = 42;
+        QkTranspileResult out;
+        char *error = NULL;
+        QkExitCode code = qk_transpile(qc, target, &options, &out, &error);
+        if (code != QkExitCode_Success) {
+            printf("Transpiling at level %u failed: %s", level, error == NULL ? "(null)" : error);
+            if (error != NULL) {
+                qk_str_free(error);
+            }
+            result = RuntimeError;
+            goto cleanup;
+        }
+
+        if (qk_circuit_num_qubits(out.circuit) != 3 || qk_circuit_num_clbits(out.circuit) != 3) {
+            printf("Level %u: unexpected circuit size %u qubits, %u clbits.", level,
+                   qk_circuit_num_qubits(out.circuit), qk_circuit_num_clbits(out.circuit));
+            result = EqualityError;
+        }
+        size_t num_measures = 0;
+        size_t num_instructions = qk_circuit_num_instructions(out.circuit);
+        for (size_t i = 0; i < num_instructions && result == Ok; i++) {
+            QkCircuitInstruction inst;
+            qk_circuit_get_instruction(out.circuit, i, &inst);
+            if (strcmp(inst.name, "cx") == 0) {
+                if (inst.qubits[1] != inst.qubits[0] + 1) {
+                    printf("Level %u: cx on unsupported qubits (%u, %u).", level, inst.qubits[0],
+                           inst.qubits[1]);
+                    result = EqualityError;
+                }
+            } else if (strcmp(inst.name, "measure") == 0) {
+                num_measures++;
+            } else if (strcmp(inst.name, "rz") != 0 && strcmp(inst.name, "sx") != 0 &&
+                       strcmp(inst.name, "x") != 0) {
+                printf("Level %u: instruction '%s' is not in the target.", level, inst.name);
+                result = EqualityError;
+            }
+            qk_circuit_instruction_clear(&inst);
+        }
+        if (result == Ok && num_measures != 3) {
+            printf("Level %u: expected 3 measurements, found %zu.", level, num_measures);
+            result = EqualityError;
+        }
+
+        // The initial layout is a permutation of the physical qubits, and at level 0 it is the
+        // trivial layout.
+        uint32_t initial[3];
+        qk_transpile_layout_initial_layout(out.layout, true, initial);
+        bool seen[3] = {false, false, false};
+        for (uint32_t q = 0; q < 3 && result == Ok; q++) {
+            if (initial[q] >= 3 || seen[initial[q]] || (level == 0 && initial[q] != q)) {
+                printf("Level %u: unexpected initial layout [%u, %u, %u].", level, initial[0],
+                       initial[1], initial[2]);
+                result = EqualityError;
+                break;
+            }
+            seen[initial[q]] = true;
+        }
+        qk_transpile_result_clear(&out);
+        if (result != Ok) {
+            break;
+        }
+    }
+
+cleanup:
+    qk_target_free(target);
+    qk_circuit_free(qc);
+    return result;
+}
+
+/**
+ * Test that operations that can't be translated without Python are rejected with a message.
+ */
+int test_transpile_unsupported_operation(void) {
+    int result = Ok;
+    QkCircuit *qc = qk_circuit_new(3, 0);
+    QkComplex64 identity[64];
+    for (int i = 0; i < 64; i++) {
+        identity[i] = (QkComplex64){(i % 9 == 0) ? 1.0 : 0.0, 0.0};
+    }
+    qk_circuit_unitary(qc, identity, (uint32_t[]){0, 1, 2}, 3, false);
+    QkTarget *target = line_target(3);
+
+    QkTranspileResult out;
+    char *error = NULL;
+    QkExitCode code = qk_transpile(qc, target, NULL, &out, &error);
+    if (code != QkExitCode_TranspilerError) {
+        printf("Transpiling a 3q unitary returned %d.", code);
+        result = RuntimeError;
+        if (code == QkExitCode_Success) {
+            qk_transpile_result_clear(&out);
+        }
+        goto cleanup;
+    }
+    if (error == NULL || strstr(error, "cannot transpile 'unitary'") == NULL) {
+        printf("Unexpected error message: %s", error == NULL ? "(null)" : error);
+        result = EqualityError;
+    }
+    if (error != NULL) {
+        qk_str_free(error);
+    }
+
+cleanup:
+    qk_target_free(target);
+    qk_circuit_free(qc);
+    return result;
+}
+
+/**
+ * Test that targets that can only be reached through the equivalence library are rejected.
+ */
+int test_transpile_no_synthesis_basis(void) {
+    int result = Ok;
+    QkCircuit *qc = qk_circuit_new(2, 0);
+    qk_circuit_gate(qc, QkGate_H, (uint32_t[]){0}, NULL);
+    // ``x`` alone is not a basis that single-qubit synthesis can use.
+    QkTarget *target = qk_target_new(2);
+    QkTargetEntry *x = qk_target_entry_new(QkGate_X);
+    qk_target_entry_add_property(x, (uint32_t[]){0}, 1, 3.5e-8, 1e-4);
+    qk_target_entry_add_property(x, (uint32_t[]){1}, 1, 3.5e-8, 1e-4);
+    qk_target_add_instruction(target, x);
+    QkTargetEntry *cx = qk_target_entry_new(QkGate_CX);
+    qk_target_entry_add_property(cx, (uint32_t[]){0, 1}, 2, 5e-7, 1e-2);
+    qk_target_add_instruction(target, cx);
+
+    QkTranspileResult out;
+    char *error = NULL;
+    QkExitCode code = qk_transpile(qc, target, NULL, &out, &error);
+    if (code != QkExitCode_TranspilerError) {
+        printf("Transpiling to a target without a synthesis basis returned %d.", code);
+        result = RuntimeError;
+        if (code == QkExitCode_Success) {
+            qk_transpile_result_clear(&out);
+        }
+        goto cleanup;
+    }
+    if (error == NULL || strstr(error, "basis translator") == NULL) {
+        printf("Unexpected error message: %s", error == NULL ? "(null)" : error);
+        result = EqualityError;
+    }
+    if (error != NULL) {
+        qk_str_free(error);
+    }
+
+cleanup:
+    qk_target_free(target);
+    qk_circuit_free(qc);
+    return result;
+}
+
+/**
+ * Test that a gate whose angle is fixed in the target is only kept with that angle.
+ */
+int test_transpile_fixed_angle(void) {
+    int result = Ok;
+    QkCircuit *qc = qk_circuit_new(2, 0);
+    qk_circuit_gate(qc, QkGate_RZZ, (uint32_t[]){0, 1}, (double[]){0.7});
+    QkTarget *target = line_target(2);
+    QkTargetEntry *rzz = qk_target_entry_new_fixed(QkGate_RZZ, (double[]){0.3});
+    qk_target_entry_add_property(rzz, (uint32_t[]){0, 1}, 2, 5e-7, 1e-2);
+    qk_target_add_instruction(target, rzz);
+
+    QkTranspileOptions options = qk_transpiler_default_options();
+    options.optimization_level = 0;
+    QkTranspileResult out;
+    char *error = NULL;
+    QkExitCode code = qk_transpile(qc, target, &options, &out, &error);
+    if (code != QkExitCode_Success) {
+        printf("Transpiling failed: %s", error == NULL ? "(null)" : error);
+        if (error != NULL) {
+            qk_str_free(error);
+        }
+        result = RuntimeError;
+        goto cleanup;
+    }
+    size_t num_instructions = qk_circuit_num_instructions(out.circuit);
+    for (size_t i = 0; i < num_instructions && result == Ok; i++) {
+        QkCircuitInstruction inst;
+        qk_circuit_get_instruction(out.circuit, i, &inst);
+        if (strcmp(inst.name, "rzz") == 0 && inst.params[0] != 0.3) {
+            printf("rzz(%f) is not in the target.", inst.params[0]);
+            result = EqualityError;
+        }
+        qk_circuit_instruction_clear(&inst);
+    }
+    qk_transpile_result_clear(&out);
+
+cleanup:
+    qk_target_free(target);
+    qk_circuit_free(qc);
+    return result;
+}
+
+/**
+ * Check that ``array`` is a permutation of ``0..length``.
+ */
+static bool is_permutation(const uint32_t *array, uint32_t length) {
+    bool seen[8] = {false};
+    for (uint32_t i = 0; i < length; i++) {
+        if (array[i] >= length || seen[array[i]]) {
+            return false;
+        }
+        seen[array[i]] = true;
+    }
+    return true;
+}
+
+/**
+ * Test the initial layout, the routing permutation and the final layout of a circuit that
+ * needs routing, on a target with more qubits than the circuit.
+ */
+int test_transpile_layout_routed(void) {
+    int result = Ok;
+    // All three pairs interact, which can't be laid out on a line without a swap.
+    QkCircuit *qc = qk_circuit_new(3, 0);
+    qk_circuit_gate(qc, QkGate_CX, (uint32_t[]){0, 1}, NULL);
+    qk_circuit_gate(qc, QkGate_CX, (uint32_t[]){1, 2}, NULL);
+    qk_circuit_gate(qc, QkGate_CX, (uint32_t[]){2, 0}, NULL);
+    qk_circuit_gate(qc, QkGate_CX, (uint32_t[]){0, 1}, NULL);
+    QkTarget *target = line_target(5);
+
+    QkTranspileOptions options = qk_transpiler_default_options();
+    options.optimization_level = 1;
+    options.seed = 2025;
+    QkTranspileResult out;
+    char *error = NULL;
+    QkExitCode code = qk_transpile(qc, target, &options, &out, &error);
+    if (code != QkExitCode_Success) {
+        printf("Transpiling failed: %s", error == NULL ? "(null)" : error);
+        if (error != NULL) {
+            qk_str_free(error);
+        }
+        result = RuntimeError;
+        goto cleanup;
+    }
+
+    uint32_t num_input = qk_transpile_layout_num_input_qubits(out.layout);
+    uint32_t num_output = qk_transpile_layout_num_output_qubits(out.layout);
+    if (num_input != 3 || num_output != 5) {
+        printf("Unexpected layout size: %u input and %u output qubits.", num_input, num_output);
+        result = EqualityError;
+        goto clear;
+    }
+
+    uint32_t initial[5], initial_filtered[3], final[5], final_filtered[3], permutation[5];
+    qk_transpile_layout_initial_layout(out.layout, false, initial);
+    qk_transpile_layout_initial_layout(out.layout, true, initial_filtered);
+    qk_transpile_layout_final_layout(out.layout, false, final);
+    qk_transpile_layout_final_layout(out.layout, true, final_filtered);
+    qk_transpile_layout_routing_permutation(out.layout, permutation);
+
+    if (!is_permutation(initial, 5) || !is_permutation(final, 5) ||
+        !is_permutation(permutation, 5)) {
+        printf("The layouts and the routing permutation must be permutations of 5 qubits.");
+        result = EqualityError;
+        goto clear;
+    }
+    bool trivial_permutation = true;
+    for (uint32_t q = 0; q < 5; q++) {
+        trivial_permutation = trivial_permutation && permutation[q] == q;
+    }
+    if (trivial_permutation) {
+        printf("Routing the circuit should have permuted the qubits.");
+        result = EqualityError;
+        goto clear;
+    }
+    for (uint32_t v = 0; v < 5; v++) {
+        // The filtered layouts are the entries of the input qubits.
+        if (v < 3 && (initial_filtered[v] != initial[v] || final_filtered[v] != final[v])) {
+            printf("Filtered layouts do not match the full layouts for qubit %u.", v);
+            result = EqualityError;
+            break;
+        }
+        // Each qubit ends where routing moved the physical qubit it started on.
+        if (final[v] != permutation[initial[v]]) {
+            printf("Qubit %u starts on %u and ends on %u, but routing moves %u to %u.", v,
+                   initial[v], final[v], initial[v], permutation[initial[v]]);
+            result = EqualityError;
+            break;
+        }
+    }
+
+clear:
+    qk_transpile_result_clear(&out);
+cleanup:
+    qk_target_free(target);
+    qk_circuit_free(qc);
+    return result;
+}
+
+/**
+ * Test that a layout can be moved out of the result and freed on its own.
+ */
+int test_transpile_layout_free(void) {
+    QkCircuit *qc = qk_circuit_new(2, 0);
+    qk_circuit_gate(qc, QkGate_CX, (uint32_t[]){1, 0}, NULL);
+    QkTarget *target = line_target(3);
+
+    int result = Ok;
+    QkTranspileResult out;
+    if (qk_transpile(qc, target, NULL, &out, NULL) != QkExitCode_Success) {
+        printf("Transpiling failed.");
+        result = RuntimeError;
+        goto cleanup;
+    }
+    QkTranspileLayout *layout = out.layout;
+    out.layout = NULL;
+    qk_transpile_result_clear(&out);
+    if (qk_transpile_layout_num_input_qubits(layout) != 2 ||
+        qk_transpile_layout_num_output_qubits(layout) != 3) {
+        printf("Unexpected layout size after moving it out of the result.");
+        result = EqualityError;
+    }
+    qk_transpile_layout_free(layout);
+
+cleanup:
+    qk_target_free(target);
+    qk_circuit_free(qc);
+    return result;
+}
+
+int test_transpile(void) {
+    int num_failed = 0;
+    num_failed += RUN_TEST(test_transpiler_default_options);
+    num_failed += RUN_TEST(test_transpile_too_wide);
+    num_failed += RUN_TEST(test_transpile_to_target);
+    num_failed += RUN_TEST(test_transpile_unsupported_operation);
+    num_failed += RUN_TEST(test_transpile_no_synthesis_basis);
+    num_failed += RUN_TEST(test_transpile_fixed_angle);
+    num_failed += RUN_TEST(test_transpile_layout_routed);
+    num_failed += RUN_TEST(test_transpile_layout_free);
+
+    fflush(stderr);
+    fprintf(stderr, "=== Number of failed subtests: %i\n", num_failed);
+
+    return num_failed;
+}
@@ -871,3 +871,87 @@ class TestStrict(QiskitTestCase):
             qiskit.qasm2.QASM2ParseError, r"\[strict\] barrier statements must have at least one"
         ):
             qiskit.qasm2.loads(program, strict=True)
+
+
+class TestRecovery(QiskitTestCase):
+    def test_reports_all_problems(self):
+        program = """\
+OPENQASM 2.0;
+qreg q[2];
+U(0, 0, 0) r[0];
+CX q[0] q[1];
+U(1.0e, 0, 0) q[0];
+creg c[2];
+measure q -> d;
+"""
+        with self.assertRaises(qiskit.qasm2.QASM2ParseError) as cm:
+            qiskit.qasm2.loads(program, recover=True)
+        diagnostics = cm.exception.diagnostics
+        self.assertEqual(
+            [(d.file, d.line, d.column) for d in diagnostics],
+            [("<input>", 3, 11), ("<input>", 4, 0), ("<input>", 5, 2), ("<input>", 7, 13)],
+        )
+        self.assertEqual(diagnostics[0].message, "'r' is not defined in this scope")
+        self.assertEqual(
+            diagnostics[1].message,
+            "needed the end of the argument list, but instead saw an identifier",
+        )
+        self.assertEqual(diagnostics[2].message, "needed to see an integer exponent for this float")
+        self.assertEqual(diagnostics[3].message, "'d' is not defined in this scope")
+        self.assertEqual(str(cm.exception).count("\n"), 3)
+        self.assertIn("<input>:3,11: 'r' is not defined in this scope", str(cm.exception))
+
+    def test_resynchronizes_after_gate_body(self):
+        program = """\
+qreg q[1];
+gate bad a { U(0, 0, 0) b; }
+gate good a { U(0, 0, 0) a; }
+good q[0];
+bad q[0];
+"""
+        with self.assertRaises(qiskit.qasm2.QASM2ParseError) as cm:
+            qiskit.qasm2.loads(program, recover=True)
+        self.assertEqual(
+            [(d.line, d.message) for d in cm.exception.diagnostics],
+            [
+                (2, "'b' is not defined in this scope"),
+                (5, "'bad' is not defined in this scope"),
+            ],
+        )
+
+    def test_recover_with_strict(self):
+        program = "qreg q[1]; U(0, 0, 0) q[0],; ;"
+        with self.assertRaises(qiskit.qasm2.QASM2ParseError) as cm:
+            qiskit.qasm2.loads(program, strict=True, recover=True)
+        messages = [d.message for d in cm.exception.diagnostics]
+        self.assertEqual(len(messages), 3)
+        self.assertRegex(messages[0], r"\[strict\] the first statement")
+        self.assertRegex(messages[1], r"\[strict\] .*trailing comma")
+        self.assertRegex(messages[2], r"\[strict\] .*empty statement")
+
+    def test_valid_program_is_unaffected(self):
+        program = 'OPENQASM 2.0; include "qelib1.inc"; qreg q[2]; h q[0]; cx q[0], q[1];'
+        self.assertEqual(
+            qiskit.qasm2.loads(program, recover=True), qiskit.qasm2.loads(program, recover=False)
+        )
+
+    def test_default_stops_at_first_problem(self):
+        program = "qreg q[1]; U(0, 0, 0) r[0]; U(0, 0, 0) s[0];"
+        with self.assertRaises(qiskit.qasm2.QASM2ParseError) as cm:
+            qiskit.qasm2.loads(program)
+        self.assertEqual(len(cm.exception.diagnostics), 1)
+        self.assertEqual(cm.exception.diagnostics[0].message, "'r' is not defined in this scope")
+        self.assertNotIn("'s'", str(cm.exception))
+
+    def test_custom_classical_errors_are_not_recovered(self):
+        def bad(_):
+            raise ValueError("bad")
+
+        program = "qreg q[1]; U(bad(0.5), 0, 0) q[0]; U(0, 0, 0) r[0];"
+        with self.assertRaisesRegex(qiskit.qasm2.QASM2ParseError, "caught exception") as cm:
+            qiskit.qasm2.loads(
+                program,
+                custom_classical=[qiskit.qasm2.CustomClassical("bad", 1, bad)],
+                recover=True,
+            )
+        self.assertIsInstance(cm.exception.__cause__, ValueError)
@@ -18,6 +18,7 @@
 from io import StringIO
 from math import pi
 import re
+import unittest
 import warnings
 
 
@@ -28,12 +29,14 @@ from qiskit import QuantumRegister, ClassicalRegister, QuantumCircuit, transpile
 from qiskit.circuit import Parameter, Qubit, Clbit, Duration, Gate, ParameterVector, annotation
 from qiskit.circuit.classical import expr, types
 from qiskit.circuit.controlflow import CASE_DEFAULT
-from qiskit.circuit.library import PauliEvolutionGate
+from qiskit.circuit.library import PauliEvolutionGate, XGate
 from qiskit.qasm3 import (
     Exporter,
     dumps,
     dump,
     dumps_experimental,
+    dump_experimental,
+    loads,
     QASM3ExporterError,
     ExperimentalFeatures,
 )
@@ -41,6 +44,7 @@ from qiskit.qasm3.exporter import QASM3Builder
 from qiskit.qasm3.printer import BasicPrinter
 from qiskit.qasm3.exceptions import QASM3ImporterError
 from qiskit.quantum_info import Pauli
+from qiskit.utils import optionals
 from test import QiskitTestCase  # pylint: disable=wrong-import-order
 
 
@@ -2853,8 +2857,83 @@ class TestQASM3ExporterRust(QiskitTestCase):
         """Test pi constant (disable_constants=False)"""
         circuit = QuantumCircuit(2)
         circuit.u(2 * pi, 3 * pi, -5 * pi, 0)
-        with self.assertRaises(QASM3ImporterError):
-            dumps_experimental(circuit, disable_constants=False)
+        circuit.rz(-3 * pi / 4, 1)
+        circuit.rx(0.25, 1)
+        expected_qasm = "\n".join(
+            [
+                "OPENQASM 3.0;",
+                'include "stdgates.inc";',
+                "qubit[2] q;",
+                "U(2*pi, 3*pi, -5*pi) q[0];",
+                "rz(-3*pi/4) q[1];",
+                "rx(0.25) q[1];",
+                "",
+            ]
+        )
+        self.assertEqual(dumps_experimental(circuit, disable_constants=False), expected_qasm)
+
+    def test_unbound_parameters_are_inputs(self):
+        """Test that unbound parameters are declared as inputs and used in expressions."""
+        theta = ParameterVector("θ", 2)
+        a = Parameter("a")
+        circuit = QuantumCircuit(1)
+        circuit.rx(a, 0)
+        circuit.ry(theta[0].sin(), 0)
+        circuit.rz(theta[1] ** 2, 0)
+        expected_qasm = "\n".join(
+            [
+                "OPENQASM 3.0;",
+                'include "stdgates.inc";',
+                "input float[64] a;",
+                "input float[64] _θ_0_;",
+                "input float[64] _θ_1_;",
+                "qubit[1] q;",
+                "rx(a) q[0];",
+                "ry(sin(_θ_0_)) q[0];",
+                "rz(_θ_1_ ** 2) q[0];",
+                "",
+            ]
+        )
+        self.assertEqual(dumps_experimental(circuit), expected_qasm)
+
+    def test_loop_parameter_is_not_an_input(self):
+        """Test that a ``for`` loop variable is declared by its loop, not as an input."""
+        parameter = Parameter("i")
+        body = QuantumCircuit(1)
+        body.rx(parameter, 0)
+        circuit = QuantumCircuit(1)
+        circuit.for_loop(range(2), parameter, body, [0], [])
+        expected_qasm = "\n".join(
+            [
+                "OPENQASM 3.0;",
+                'include "stdgates.inc";',
+                "qubit[1] q;",
+                "for i in [0:1] {",
+                "  rx(i) q[0];",
+                "}",
+                "",
+            ]
+        )
+        self.assertEqual(dumps_experimental(circuit), expected_qasm)
+
+    def test_parameterized_delay(self):
+        """Test that a symbolic delay duration is scaled by one unit of the delay."""
+        t = Parameter("t")
+        circuit = QuantumCircuit(1)
+        circuit.delay(t, 0, unit="dt")
+        circuit.delay(100, 0, unit="dt")
+        expected_qasm = "\n".join(
+            [
+                "OPENQASM 3.0;",
+                'include "stdgates.inc";',
+                "input float[64] t;",
+                "qubit[1] q;",
+                "delay[t * 1dt] q[0];",
+                "delay[100dt] q[0];",
+                "",
+            ]
+        )
+        self.assertEqual(dumps_experimental(circuit), expected_qasm)
 
     def test_pi_disable_constants_true(self):
         """Test pi constant (disable_constants=True)"""
@@ -3149,6 +3228,209 @@ class TestQASM3ExporterRust(QiskitTestCase):
         )
         self.assertEqual(dumps_experimental(qc, allow_aliasing=True), expected_qasm)
 
+    def test_if_else_legacy_conditions(self):
+        """Test that `if_else` with register and bit conditions is exported."""
+        qr = QuantumRegister(2, "q")
+        cr = ClassicalRegister(2, "c")
+        qc = QuantumCircuit(qr, cr)
+        qc.measure(0, 0)
+        with qc.if_test((cr, 1)) as else_:
+            qc.x(1)
+        with else_:
+            qc.h(1)
+        with qc.if_test((cr[0], False)):
+            qc.z(0)
+        expected_qasm = "\n".join(
+            [
+                "OPENQASM 3.0;",
+                'include "stdgates.inc";',
+                "bit[2] c;",
+                "qubit[2] q;",
+                "c[0] = measure q[0];",
+                "if (c == 1) {",
+                "  x q[1];",
+                "} else {",
+                "  h q[1];",
+                "}",
+                "if (!c[0]) {",
+                "  z q[0];",
+                "}",
+                "",
+            ]
+        )
+        self.assertEqual(dumps_experimental(qc), expected_qasm)
+
+    def test_while_loop_expr_condition(self):
+        """Test that `while_loop` with an `Expr` condition, `break` and `continue` is exported."""
+        qr = QuantumRegister(1, "q")
+        cr = ClassicalRegister(2, "c")
+        qc = QuantumCircuit(qr, cr)
+        with qc.while_loop(expr.logic_and(expr.less(cr, 3), expr.logic_not(cr[1]))):
+            qc.h(0)
+            qc.measure(0, 1)
+            with qc.if_test(expr.equal(expr.bit_and(cr, 2), 2)):
+                qc.break_loop()
+            qc.continue_loop()
+        expected_qasm = "\n".join(
+            [
+                "OPENQASM 3.0;",
+                'include "stdgates.inc";',
+                "bit[2] c;",
+                "qubit[1] q;",
+                "while (c < 3 && !c[1]) {",
+                "  h q[0];",
+                "  c[1] = measure q[0];",
+                "  if ((c & 2) == 2) {",
+                "    break;",
+                "  }",
+                "  continue;",
+                "}",
+                "",
+            ]
+        )
+        self.assertEqual(dumps_experimental(qc), expected_qasm)
+
+    def test_for_loop(self):
+        """Test that `for_loop` over ranges and explicit sets is exported."""
+        parameter = Parameter("my_x")
+        body = QuantumCircuit(1)
+        body.rx(parameter, 0)
+        anonymous = QuantumCircuit(1)
+        anonymous.h(0)
+
+        qc = QuantumCircuit(2)
+        qc.for_loop(range(1, 6, 2), parameter, body, [1], [])
+        qc.for_loop([0, 3, 4], None, anonymous, [0], [])
+        expected_qasm = "\n".join(
+            [
+                "OPENQASM 3.0;",
+                'include "stdgates.inc";',
+                "qubit[2] q;",
+                "for my_x in [1:2:5] {",
+                "  rx(my_x) q[1];",
+                "}",
+                "for _ in {0, 3, 4} {",
+                "  h q[0];",
+                "}",
+                "",
+            ]
+        )
+        self.assertEqual(dumps_experimental(qc), expected_qasm)
+
+    def test_switch_case(self):
+        """Test that `switch_case` is exported with a hoisted target variable."""
+        qr = QuantumRegister(1, "q")
+        cr = ClassicalRegister(2, "c")
+        qc = QuantumCircuit(qr, cr)
+        with qc.switch(cr) as case:
+            with case(0):
+                qc.x(0)
+            with case(1, 2):
+                qc.z(0)
+            with case(case.DEFAULT):
+                qc.h(0)
+        expected_qasm = "\n".join(
+            [
+                "OPENQASM 3.0;",
+                'include "stdgates.inc";',
+                "bit[2] c;",
+                "int switch_dummy;",
+                "qubit[1] q;",
+                "switch_dummy = c;",
+                "switch (switch_dummy) {",
+                "  case 0 {",
+                "    x q[0];",
+                "  }",
+                "  case 1, 2 {",
+                "    z q[0];",
+                "  }",
+                "  default {",
+                "    h q[0];",
+                "  }",
+                "}",
+                "",
+            ]
+        )
+        self.assertEqual(dumps_experimental(qc), expected_qasm)
+
+    def test_control_flow_standalone_var_conditions(self):
+        """Test that control flow conditioned on standalone variables is exported."""
+        qr = QuantumRegister(1, "q")
+        qc = QuantumCircuit(qr)
+        a = qc.add_input("a", types.Bool())
+        b = qc.add_var("b", expr.lift(3, types.Uint(8)))
+        qc.add_stretch("s")
+        with qc.while_loop(a):
+            qc.x(0)
+            qc.store(b, expr.add(b, 1))
+        with qc.if_test(expr.equal(b, 3)):
+            qc.h(0)
+        expected_qasm = "\n".join(
+            [
+                "OPENQASM 3.0;",
+                'include "stdgates.inc";',
+                "input bool a;",
+                "qubit[1] q;",
+                "uint[8] b;",
+                "stretch s;",
+                "b = 3;",
+                "while (a) {",
+                "  x q[0];",
+                "  b = b + 1;",
+                "}",
+                "if (b == 3) {",
+                "  h q[0];",
+                "}",
+                "",
+            ]
+        )
+        self.assertEqual(dumps_experimental(qc), expected_qasm)
+
+    def test_control_flow_round_trip(self):
+        """Test that exported control flow is read back in by `loads` to an equivalent program."""
+        qr = QuantumRegister(2, "q")
+        cr = ClassicalRegister(2, "c")
+        qc = QuantumCircuit(qr, cr)
+        qc.h(0)
+        qc.measure(0, 0)
+        with qc.if_test((cr, 1)) as else_:
+            qc.x(1)
+        with else_:
+            qc.h(1)
+        with qc.while_loop(expr.less(cr, 3)):
+            qc.measure(1, 1)
+            with qc.if_test(cr[1]):
+                qc.break_loop()
+        qc.for_loop(range(3), None, QuantumCircuit(1), [0], [])
+        exported = dumps_experimental(qc)
+        expected_qasm = "\n".join(
+            [
+                "OPENQASM 3.0;",
+                'include "stdgates.inc";',
+                "bit[2] c;",
+                "qubit[2] q;",
+                "h q[0];",
+                "c[0] = measure q[0];",
+                "if (c == 1) {",
+                "  x q[1];",
+                "} else {",
+                "  h q[1];",
+                "}",
+                "while (c < 3) {",
+                "  c[1] = measure q[1];",
+                "  if (c[1]) {",
+                "    break;",
+                "  }",
+                "}",
+                "for _ in [0:2] {",
+                "}",
+                "",
+            ]
+        )
+        self.assertEqual(exported, expected_qasm)
+        if optionals.HAS_QASM3_IMPORT:
+            self.assertEqual(dumps_experimental(loads(exported)), exported)
+
     def test_annotations(self):
         """Test that the annotation-serialisation framework works."""
         # pylint: disable=missing-class-docstring,missing-function-docstring
@@ -3247,3 +3529,186 @@ box {
         )
         self.assertEqual(prog.strip(), expected.strip())
         self.assertTrue(skip_triggered)
+
+    def test_box_experimental(self):
+        """Test that 'box' statements can be exported by the Rust exporter."""
+        qc = QuantumCircuit(2)
+        with qc.box():
+            qc.x(0)
+        with qc.box(duration=50.0, unit="ms"):
+            qc.h(1)
+        with qc.box(duration=200, unit="dt"):
+            with qc.box(duration=10, unit="dt"):
+                pass
+
+        expected = """\
+OPENQASM 3.0;
+include "stdgates.inc";
+qubit[2] q;
+box {
+  x q[0];
+}
+box[50ms] {
+  h q[1];
+}
+box[200dt] {
+  box[10dt] {
+  }
+}
+"""
+        self.assertEqual(dumps_experimental(qc), expected)
+
+    def test_annotations_experimental(self):
+        """Test that the Rust exporter uses the annotation handlers of parent namespaces."""
+        # pylint: disable=missing-class-docstring,missing-function-docstring
+
+        class Calibration(annotation.Annotation):
+            namespace = "cal.drag"
+
+            def __init__(self, x):
+                self.x = x
+
+        class Twirl(annotation.Annotation):
+            namespace = "mitigation.twirl"
+
+        class Static(annotation.Annotation):
+            namespace = "static"
+
+        class CalibrationHandler(annotation.OpenQASM3Serializer):
+            def load(self, namespace, payload):
+                raise NotImplementedError("unused in test")
+
+            def dump(self, annotation):  # pylint: disable=redefined-outer-name
+                return f"{annotation.x:#04x}"
+
+        class ExactHandler(annotation.OpenQASM3Serializer):
+            def load(self, namespace, payload):
+                raise NotImplementedError("unused in test")
+
+            def dump(self, annotation):  # pylint: disable=redefined-outer-name
+                if annotation.namespace != "static":
+                    return NotImplemented
+                return ""
+
+        class GlobalHandler(annotation.OpenQASM3Serializer):
+            def load(self, namespace, payload):
+                raise NotImplementedError("unused in test")
+
+            def dump(self, annotation):  # pylint: disable=redefined-outer-name
+                return "global"
+
+        qc = QuantumCircuit(1)
+        with qc.box([Calibration(10), Twirl()]):
+            with qc.box([Static()], duration=100, unit="dt"):
+                qc.x(0)
+        expected = """\
+OPENQASM 3.0;
+include "stdgates.inc";
+qubit[1] q;
+@mitigation.twirl global
+@cal.drag 0x0a
+box {
+  @static
+  box[100dt] {
+    x q[0];
+  }
+}
+"""
+        handlers = {"cal": CalibrationHandler(), "static": ExactHandler(), "": GlobalHandler()}
+        self.assertEqual(dumps_experimental(qc, annotation_handlers=handlers), expected)
+
+        with self.assertRaisesRegex(QASM3ImporterError, "No configured annotation serializer"):
+            dumps_experimental(qc, annotation_handlers={"cal": CalibrationHandler()})
+
+    def test_chain_else_if_experimental(self):
+        """Test that the Rust exporter can chain nested `if` statements into `else if`."""
+        qr = QuantumRegister(1, "q")
+        cr = ClassicalRegister(2, "c")
+        qc = QuantumCircuit(qr, cr)
+        with qc.if_test((cr, 0)) as else_:
+            qc.x(0)
+        with else_:
+            with qc.if_test((cr, 1)) as inner_else:
+                qc.y(0)
+            with inner_else:
+                qc.z(0)
+        expected_qasm = "\n".join(
+            [
+                "OPENQASM 3.0;",
+                'include "stdgates.inc";',
+                "bit[2] c;",
+                "qubit[1] q;",
+                "if (c == 0) {",
+                "  x q[0];",
+                "} else if (c == 1) {",
+                "  y q[0];",
+                "} else {",
+                "  z q[0];",
+                "}",
+                "",
+            ]
+        )
+        self.assertEqual(dumps_experimental(qc, chain_else_if=True), expected_qasm)
+        self.assertNotIn("else if", dumps_experimental(qc))
+
+    def test_max_line_width_experimental(self):
+        """Test that the Rust exporter wraps long argument lists."""
+        qc = QuantumCircuit(5)
+        qc.barrier()
+        qc.cx(0, 1)
+        expected_qasm = "\n".join(
+            [
+                "OPENQASM 3.0;",
+                'include "stdgates.inc";',
+                "qubit[5] q;",
+                "barrier q[0], q[1],",
+                "  q[2], q[3], q[4];",
+                "cx q[0], q[1];",
+                "",
+            ]
+        )
+        self.assertEqual(dumps_experimental(qc, max_line_width=20), expected_qasm)
+
+    def test_label_comments_experimental(self):
+        """Test that the Rust exporter can write instruction labels as comments."""
+        qc = QuantumCircuit(1)
+        qc.append(XGate(label="first\nflip"), [0])
+        qc.h(0)
+        expected_qasm = "\n".join(
+            [
+                "OPENQASM 3.0;",
+                'include "stdgates.inc";',
+                "qubit[1] q;",
+                "// first flip",
+                "x q[0];",
+                "h q[0];",
+                "",
+            ]
+        )
+        self.assertEqual(dumps_experimental(qc, label_comments=True), expected_qasm)
+        self.assertNotIn("//", dumps_experimental(qc))
+
+    def test_source_map_experimental(self):
+        """Test that the Rust exporter maps each instruction to the lines it was written to."""
+        qr = QuantumRegister(1, "q")
+        cr = ClassicalRegister(1, "c")
+        qc = QuantumCircuit(qr, cr)
+        qc.append(XGate(label="flip"), [0])
+        qc.measure(0, 0)
+        with qc.if_test((cr, 1)):
+            qc.h(0)
+        source_map = {}
+        program = dumps_experimental(qc, label_comments=True, source_map=source_map)
+        lines = program.splitlines()
+        self.assertEqual(list(source_map), [0, 1, 2])
+        self.assertEqual(lines[source_map[0].start : source_map[0].stop], ["// flip", "x q[0];"])
+        self.assertEqual(lines[source_map[1].start : source_map[1].stop], ["c[0] = measure q[0];"])
+        self.assertEqual(
+            lines[source_map[2].start : source_map[2].stop], ["if (c == 1) {", "  h q[0];", "}"]
+        )
+
+        stream = StringIO()
+        dump_source_map = {}
+        dump_experimental(qc, stream, label_comments=True, source_map=dump_source_map)
+        self.assertEqual(stream.getvalue(), program)
+        self.assertEqual(dump_source_map, source_map)
@@ -17,6 +17,7 @@
 # don't want to get into a situation where updates to `qiskit_qasm3_import` breaks Terra's test
 # suite due to too specific tests on the Terra side.
 
+import math
 import os
 import tempfile
 import unittest
@@ -26,6 +27,7 @@ from qiskit import qasm3
 from qiskit.exceptions import ExperimentalWarning
 from qiskit.circuit import QuantumCircuit, QuantumRegister, ClassicalRegister, Qubit, Clbit
 from qiskit.circuit import library as lib, annotation
+from qiskit.circuit.classical import expr, types
 from qiskit.utils import optionals
 from test import QiskitTestCase  # pylint: disable=wrong-import-order
 
@@ -468,3 +470,281 @@ class TestQASM3Import(QiskitTestCase):
         }
         self.assertEqual(stdgates["rx"], (1, 1))
         self.assertEqual(stdgates["cphase"], (1, 2))
+
+    def test_classical_declarations(self):
+        program = """
+            OPENQASM 3.0;
+            include "stdgates.inc";
+            qubit[1] q;
+            const float[64] half_pi = pi / 2;
+            uint[8] x = 3;
+            uint y = 5;
+            bool b;
+            float[64] f = 1.5;
+            rx(half_pi) q[0];
+            x = 4;
+            b = (x == 4);
+        """
+        parsed = qasm3.loads_experimental(program)
+        x, y, b, f = (parsed.get_var(name) for name in "xybf")
+        self.assertEqual(x.type, types.Uint(8))
+        self.assertEqual(y.type, types.Uint(3))
+        self.assertEqual(b.type, types.Bool())
+        self.assertEqual(f.type, types.Float())
+        expected = QuantumCircuit(QuantumRegister(1, "q"))
+        expected.add_var(x, expr.lift(3, types.Uint(8)))
+        expected.add_var(y, expr.lift(5))
+        expected.add_uninitialized_var(b)
+        expected.add_var(f, expr.lift(1.5))
+        expected.rx(math.pi / 2, 0)
+        expected.store(x, expr.lift(4, types.Uint(8)))
+        expected.store(b, expr.equal(x, 4))
+        self.assertEqual(parsed, expected)
+
+    def test_if_else(self):
+        program = """
+            OPENQASM 3.0;
+            include "stdgates.inc";
+            qubit[1] q;
+            bit[2] c;
+            c[0] = measure q[0];
+            if (c[0]) {
+                x q[0];
+            } else {
+                h q[0];
+            }
+            if (c == 3) {
+                z q[0];
+            }
+        """
+        parsed = qasm3.loads_experimental(program)
+        q, c = QuantumRegister(1, "q"), ClassicalRegister(2, "c")
+        expected = QuantumCircuit(q, c)
+        expected.measure(q[0], c[0])
+        with expected.if_test(expr.lift(c[0])) as else_:
+            expected.x(0)
+        with else_:
+            expected.h(0)
+        with expected.if_test(expr.equal(c, 3)):
+            expected.z(0)
+        self.assertEqual(parsed, expected)
+
+    def test_while_loop(self):
+        program = """
+            OPENQASM 3.0;
+            include "stdgates.inc";
+            qubit[1] q;
+            uint[8] i = 0;
+            while (i != 5) {
+                i = (i + 1);
+                if (i == 2) {
+                    continue;
+                }
+                if (i == 4) {
+                    break;
+                }
+                x q[0];
+            }
+        """
+        parsed = qasm3.loads_experimental(program)
+        i = parsed.get_var("i")
+        expected = QuantumCircuit(QuantumRegister(1, "q"))
+        expected.add_var(i, expr.lift(0, types.Uint(8)))
+        with expected.while_loop(expr.not_equal(i, 5)):
+            expected.store(i, expr.add(i, 1))
+            with expected.if_test(expr.equal(i, 2)):
+                expected.continue_loop()
+            with expected.if_test(expr.equal(i, 4)):
+                expected.break_loop()
+            expected.x(0)
+        self.assertEqual(parsed, expected)
+
+    def test_for_loop(self):
+        program = """
+            OPENQASM 3.0;
+            include "stdgates.inc";
+            input float[64] a;
+            qubit[1] q;
+            for uint i in [0:2] {
+                rx(i) q[0];
+            }
+            for uint j in [4:-2:0] {
+                rz(a) q[0];
+            }
+            for uint k in {1, 3} {
+                x q[0];
+            }
+        """
+        parsed = qasm3.loads_experimental(program)
+        a = parsed.get_parameter("a")
+        i, j, k = (instruction.operation.params[1] for instruction in parsed.data)
+        self.assertEqual([i.name, j.name, k.name], ["i", "j", "k"])
+        expected = QuantumCircuit(QuantumRegister(1, "q"))
+        with expected.for_loop(range(0, 3), i):
+            expected.rx(i, 0)
+        with expected.for_loop(range(4, -1, -2), j):
+            expected.rz(a, 0)
+        with expected.for_loop((1, 3), k):
+            expected.x(0)
+        self.assertEqual(parsed, expected)
+
+    def test_switch(self):
+        program = """
+            OPENQASM 3.0;
+            include "stdgates.inc";
+            qubit[2] q;
+            bit[2] c;
+            c = measure q;
+            switch (c) {
+                case 0 {
+                    x q[0];
+                }
+                case 1, 2 {
+                    y q[0];
+                }
+                default {
+                    z q[0];
+                }
+            }
+        """
+        parsed = qasm3.loads_experimental(program)
+        q, c = QuantumRegister(2, "q"), ClassicalRegister(2, "c")
+        expected = QuantumCircuit(q, c)
+        expected.measure(q, c)
+        with expected.switch(expr.lift(c)) as case:
+            with case(0):
+                expected.x(0)
+            with case(1, 2):
+                expected.y(0)
+            with case(case.DEFAULT):
+                expected.z(0)
+        self.assertEqual(parsed, expected)
+
+    def test_delay_stretch_and_reset(self):
+        program = """
+            OPENQASM 3.0;
+            qubit[2] q;
+            stretch s;
+            delay[100ns] q[0];
+            delay[s] q[1];
+            delay[10dt] q;
+            reset q[0];
+            reset q;
+        """
+        parsed = qasm3.loads_experimental(program)
+        s = parsed.get_stretch("s")
+        q = QuantumRegister(2, "q")
+        expected = QuantumCircuit(q)
+        expected.add_stretch(s)
+        expected.delay(100.0, q[0], "ns")
+        expected.delay(s, q[1])
+        expected.delay(10, q, "dt")
+        expected.reset(q[0])
+        expected.reset(q)
+        self.assertEqual(parsed, expected)
+
+    def test_inputs_and_outputs(self):
+        program = """
+            OPENQASM 3.0;
+            include "stdgates.inc";
+            input bool flag;
+            input float[64] theta;
+            output bit result;
+            qubit[1] q;
+            rx(theta) q[0];
+            if (flag) {
+                result = measure q[0];
+            }
+        """
+        parsed = qasm3.loads_experimental(program)
+        flag = parsed.get_var("flag")
+        theta = parsed.get_parameter("theta")
+        self.assertEqual(flag.type, types.Bool())
+        expected = QuantumCircuit([Clbit()], QuantumRegister(1, "q"), inputs=[flag])
+        expected.rx(theta, 0)
+        with expected.if_test(flag):
+            expected.measure(0, 0)
+        self.assertEqual(parsed, expected)
+
+    def test_unsupported_classical_constructs_raise(self):
+        with self.assertRaisesRegex(qasm3.QASM3ImporterError, "signed integers"):
+            qasm3.loads_experimental("OPENQASM 3.0; int[8] x;")
+
+    def test_box_is_rejected(self):
+        program = "OPENQASM 3.0;\nqubit q;\nbox[100ns] { reset q; }\n"
+        with self.assertRaisesRegex(
+            qasm3.QASM3ImporterError, "'box' statements cannot be imported"
+        ) as cm:
+            qasm3.loads_experimental(program)
+        (diagnostic,) = cm.exception.diagnostics
+        self.assertEqual((diagnostic.line, diagnostic.column), (3, 1))
+        self.assertEqual(diagnostic.severity, qasm3.DiagnosticSeverity.ERROR)
+
+    def test_syntax_error_diagnostics(self):
+        program = "OPENQASM 3.0;\nqubit q\nh q;\n"
+        with self.assertRaises(qasm3.QASM3ImporterError) as cm:
+            qasm3.loads_experimental(program)
+        diagnostics = cm.exception.diagnostics
+        self.assertEqual(len(diagnostics), 1)
+        (diagnostic,) = diagnostics
+        self.assertIsInstance(diagnostic, qasm3.Diagnostic)
+        self.assertIsNone(diagnostic.file)
+        self.assertEqual((diagnostic.line, diagnostic.column), (2, 8))
+        self.assertEqual(diagnostic.span, (21, 21))
+        self.assertEqual(diagnostic.severity, qasm3.DiagnosticSeverity.ERROR)
+        self.assertIn("SEMICOLON", diagnostic.message)
+        self.assertIn("<input>:2:8: error:", str(cm.exception))
+
+    def test_semantic_error_diagnostics(self):
+        program = "OPENQASM 3.0;\nqubit q;\n  foo q;\n"
+        with self.assertRaises(qasm3.QASM3ImporterError) as cm:
+            qasm3.loads_experimental(program)
+        errors = [
+            diagnostic
+            for diagnostic in cm.exception.diagnostics
+            if diagnostic.severity == qasm3.DiagnosticSeverity.ERROR
+        ]
+        self.assertEqual(len(errors), 1)
+        self.assertEqual((errors[0].line, errors[0].column), (3, 3))
+        self.assertEqual(program[slice(*errors[0].span)], "foo")
+        self.assertEqual(errors[0].message, "undefined gate: 'foo'")
+
+    def test_incompatible_types_diagnostic_is_error(self):
+        program = "OPENQASM 3.0;\nqubit q;\nfoo q;\nbit b = 1.5;\n"
+        with self.assertRaises(qasm3.QASM3ImporterError) as cm:
+            qasm3.loads_experimental(program)
+        diagnostics = cm.exception.diagnostics
+        self.assertEqual(
+            [diagnostic.message for diagnostic in diagnostics],
+            ["undefined gate: 'foo'", "incompatible types: 'bit b = 1.5;'"],
+        )
+        for diagnostic in diagnostics:
+            self.assertEqual(diagnostic.severity, qasm3.DiagnosticSeverity.ERROR)
+        self.assertIn("<input>:4:1: error: incompatible types", str(cm.exception))
+
+    def test_load_diagnostics_include_file(self):
+        with tempfile.TemporaryDirectory() as tmp_dir:
+            tmp_path = os.path.join(tmp_dir, "bad.qasm")
+            with open(tmp_path, "w") as fptr:
+                fptr.write("OPENQASM 3.0;\nqubit q;\nfoo q;\n")
+            with self.assertRaises(qasm3.QASM3ImporterError) as cm:
+                qasm3.load_experimental(tmp_path)
+        (diagnostic,) = cm.exception.diagnostics
+        self.assertEqual(diagnostic.file, tmp_path)
+        self.assertEqual(diagnostic.line, 3)
+
+    def test_other_errors_have_no_diagnostics(self):
+        with self.assertRaises(qasm3.QASM3ImporterError) as cm:
+            qasm3.loads_experimental("OPENQASM 3.0; int[8] x;")
+        self.assertEqual(cm.exception.diagnostics, ())
+
+    def test_annotations_are_rejected(self):
+        program = """
+            OPENQASM 3.0;
+            include "stdgates.inc";
+            qubit q;
+            @cal.drag 0x0a
+            x q;
+        """
+        with self.assertRaisesRegex(qasm3.QASM3ImporterError, "annotations cannot be imported"):
+            qasm3.loads_experimental(program)
@@ -0,0 +1,181 @@
+# This code is part of Qiskit.
+#
+# (C) Copyright IBM 2025
+#
+# This code is licensed under the Apache License, Version 2.0. You may
+# obtain a copy of this license in the LICENSE.txt file in the root directory
+# of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+#
+# Any modifications or derivative works of this code must retain this
+# copyright notice, and modified files need to carry a notice indicating
+# that they have been altered from the originals.
+
+# pylint: disable=missing-module-docstring,missing-class-docstring,missing-function-docstring
+
+import copy
+import pickle
+
+import ddt
+import numpy as np
+
+from qiskit.quantum_info import SparseFermionOp, SparseObservable
+
+from test import QiskitTestCase  # pylint: disable=wrong-import-order
+
+MAPPINGS = ("jordan_wigner", "parity", "bravyi_kitaev")
+
+
+def example_op():
+    return SparseFermionOp.from_sparse_list(
+        [
+            ("+-", (1, 0), 0.5),
+            ("+-", (0, 1), 0.5),
+            ("+-+-", (3, 2, 1, 0), 1j),
+            ("-+", (2, 2), -0.25),
+            ("", (), 2.0),
+        ],
+        num_modes=4,
+    )
+
+
+@ddt.ddt
+class TestSparseFermionOp(QiskitTestCase):
+    def test_construction(self):
+        op = example_op()
+        self.assertEqual(op.num_modes, 4)
+        self.assertEqual(op.num_terms, 5)
+        self.assertEqual(len(op), 5)
+        np.testing.assert_equal(op.coeffs, [0.5, 0.5, 1j, -0.25, 2.0])
+        np.testing.assert_equal(op.actions, [1, 0, 1, 0, 1, 0, 1, 0, 0, 1])
+        np.testing.assert_equal(op.indices, [1, 0, 0, 1, 3, 2, 1, 0, 2, 2])
+        np.testing.assert_equal(op.boundaries, [0, 2, 4, 8, 10, 10])
+        self.assertEqual(
+            SparseFermionOp.from_raw_parts(4, op.coeffs, op.actions, op.indices, op.boundaries), op
+        )
+        self.assertEqual(SparseFermionOp(op.to_sparse_list(), num_modes=4), op)
+        self.assertEqual(SparseFermionOp(op), op)
+        self.assertEqual(SparseFermionOp.zero(4).num_terms, 0)
+        self.assertEqual(SparseFermionOp.identity(4).to_sparse_list(), [("", [], 1.0)])
+
+    def test_construction_errors(self):
+        with self.assertRaisesRegex(ValueError, "'num_modes' must be provided"):
+            SparseFermionOp([("+", (0,), 1.0)])
+        with self.assertRaisesRegex(ValueError, "out of range"):
+            SparseFermionOp.from_sparse_list([("+", (2,), 1.0)], num_modes=2)
+        with self.assertRaisesRegex(ValueError, "does not match indices"):
+            SparseFermionOp.from_sparse_list([("+-", (0,), 1.0)], num_modes=2)
+        with self.assertRaisesRegex(ValueError, "alphabet"):
+            SparseFermionOp.from_sparse_list([("+X", (0, 1), 1.0)], num_modes=2)
+        with self.assertRaisesRegex(ValueError, "must all be 0 or 1"):
+            SparseFermionOp.from_raw_parts(2, [1.0], np.array([2], dtype=np.uint8), [0], [0, 1])
+        with self.assertRaisesRegex(ValueError, "boundaries"):
+            SparseFermionOp.from_raw_parts(2, [1.0], np.array([1], dtype=np.uint8), [0], [0, 2])
+        with self.assertRaisesRegex(TypeError, "unknown input format"):
+            SparseFermionOp(1.0)
+
+    def test_repr(self):
+        op = SparseFermionOp.from_sparse_list([("+-", (1, 0), 1.0), ("", (), -0.5)], num_modes=2)
+        self.assertEqual(
+            repr(op), "<SparseFermionOp with 2 terms on 2 modes: (1+0j)(+_1 -_0) + (-0.5+0j)()>"
+        )
+        self.assertEqual(
+            repr(SparseFermionOp.zero(1)), "<SparseFermionOp with 0 terms on 1 mode: 0.0>"
+        )
+
+    def test_pickle_and_copy(self):
+        op = example_op()
+        self.assertEqual(pickle.loads(pickle.dumps(op)), op)
+        self.assertEqual(copy.copy(op), op)
+        self.assertEqual(copy.deepcopy(op), op)
+
+    def test_arithmetic(self):
+        op = example_op()
+        self.assertEqual((op + op).num_terms, 2 * op.num_terms)
+        self.assertEqual((op - op).simplify(), SparseFermionOp.zero(4))
+        self.assertEqual((-op).simplify(), (op * -1).simplify())
+        np.testing.assert_equal((2j * op).coeffs, 2j * op.coeffs)
+        with self.assertRaisesRegex(ValueError, "mismatched numbers of modes"):
+            _ = op + SparseFermionOp.zero(3)
+
+    def test_adjoint(self):
+        op = SparseFermionOp.from_sparse_list([("++-", (2, 1, 0), 1j)], num_modes=3)
+        self.assertEqual(
+            op.adjoint(), SparseFermionOp.from_sparse_list([("+--", (0, 1, 2), -1j)], num_modes=3)
+        )
+        self.assertEqual(example_op().adjoint().adjoint(), example_op())
+
+    def test_simplify(self):
+        op = SparseFermionOp.from_sparse_list(
+            [("+-", (1, 0), 1.0), ("-+", (0, 1), 1.0), ("+-", (1, 0), 2.0), ("+", (0,), 1e-10)],
+            num_modes=2,
+        )
+        self.assertEqual(
+            op.simplify(),
+            SparseFermionOp.from_sparse_list(
+                [("+-", (1, 0), 3.0), ("-+", (0, 1), 1.0)], num_modes=2
+            ),
+        )
+        self.assertEqual(op.simplify(tol=0.0).num_terms, 3)
+
+    def test_normal_order(self):
+        op = SparseFermionOp.from_sparse_list([("-+", (0, 0), 1.0)], num_modes=1)
+        self.assertEqual(
+            op.normal_order(),
+            SparseFermionOp.from_sparse_list([("+-", (0, 0), -1.0), ("", (), 1.0)], num_modes=1),
+        )
+        # `a_0 a_1^dagger` is `-a_1^dagger a_0`, which then cancels.
+        op = SparseFermionOp.from_sparse_list(
+            [("-+", (0, 1), 1.0), ("+-", (1, 0), 1.0), ("++", (0, 0), 1.0)], num_modes=2
+        )
+        self.assertEqual(op.normal_order(), SparseFermionOp.zero(2))
+        ordered = example_op().normal_order()
+        self.assertEqual(ordered.normal_order(), ordered)
+
+    @ddt.data(*MAPPINGS)
+    def test_to_sparse_observable_anticommutation(self, mapping):
+        num_modes = 4
+        identity = np.eye(2**num_modes)
+        for i in range(num_modes):
+            for j in range(num_modes):
+                anticommutator = SparseFermionOp.from_sparse_list(
+                    [("-+", (i, j), 1.0), ("+-", (j, i), 1.0)], num_modes=num_modes
+                )
+                np.testing.assert_allclose(
+                    anticommutator.to_sparse_observable(mapping).to_matrix(),
+                    identity if i == j else 0.0,
+                    atol=1e-12,
+                )
+
+    @ddt.data(*MAPPINGS)
+    def test_to_sparse_observable_preserves_structure(self, mapping):
+        op = example_op()
+        matrix = op.to_sparse_observable(mapping).to_matrix()
+        np.testing.assert_allclose(
+            op.normal_order().to_sparse_observable(mapping).to_matrix(), matrix, atol=1e-12
+        )
+        np.testing.assert_allclose(
+            op.adjoint().to_sparse_observable(mapping).to_matrix(), matrix.conj().T, atol=1e-12
+        )
+
+    def test_mappings_are_isospectral(self):
+        op = example_op()
+        hermitian = op + op.adjoint()
+        spectra = [
+            np.linalg.eigvalsh(hermitian.to_sparse_observable(mapping).to_matrix())
+            for mapping in MAPPINGS
+        ]
+        for spectrum in spectra[1:]:
+            np.testing.assert_allclose(spectrum, spectra[0], atol=1e-10)
+
+    def test_to_sparse_observable_number_operator(self):
+        number = SparseFermionOp.from_sparse_list([("+-", (1, 1), 1.0)], num_modes=2)
+        self.assertEqual(
+            number.to_sparse_observable(),
+            SparseObservable.from_list([("II", 0.5), ("ZI", -0.5)]),
+        )
+        self.assertEqual(
+            number.to_sparse_observable("parity"),
+            SparseObservable.from_list([("II", 0.5), ("ZZ", -0.5)]),
+        )
+        with self.assertRaisesRegex(ValueError, "unknown fermion-to-qubit mapping"):
+            number.to_sparse_observable("bad")
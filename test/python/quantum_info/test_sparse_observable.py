@@ -20,11 +20,22 @@ import unittest
 
 import ddt
 import numpy as np
+import scipy.sparse
 
 from qiskit import transpile
 from qiskit.circuit import Measure, Parameter, library, QuantumCircuit
 from qiskit.exceptions import QiskitError
-from qiskit.quantum_info import SparseObservable, SparsePauliOp, Pauli, PauliList
+from qiskit.quantum_info import (
+    DensityMatrix,
+    Pauli,
+    PauliList,
+    SparseObservable,
+    SparsePauliOp,
+    Statevector,
+    random_density_matrix,
+    random_statevector,
+)
+from qiskit.result import Counts, ProbDistribution, sampled_expectation_value
 from qiskit.transpiler import Target
 
 from test import QiskitTestCase, combine  # pylint: disable=wrong-import-order
@@ -43,6 +54,19 @@ def single_cases():
     ]
 
 
+SINGLE_QUBIT_MATRICES = {
+    "X": np.array([[0, 1], [1, 0]]),
+    "Y": np.array([[0, -1j], [1j, 0]]),
+    "Z": np.array([[1, 0], [0, -1]]),
+    "+": np.array([[0.5, 0.5], [0.5, 0.5]]),
+    "-": np.array([[0.5, -0.5], [-0.5, 0.5]]),
+    "r": np.array([[0.5, -0.5j], [0.5j, 0.5]]),
+    "l": np.array([[0.5, 0.5j], [-0.5j, 0.5]]),
+    "0": np.array([[1, 0], [0, 0]]),
+    "1": np.array([[0, 0], [0, 1]]),
+}
+
+
 def lnn_target(num_qubits):
     """Create a simple `Target` object with an arbitrary basis-gate set, and open-path
     connectivity."""
@@ -1799,6 +1823,78 @@ class TestSparseObservable(QiskitTestCase):
         )
         self.assertEqual(obs.pauli_bases(), expected)
 
+    def test_pauli_base(self):
+        obs = SparseObservable.from_list([("0XI", 1.0), ("1IX", 2.0), ("IX+", -1j)])
+        self.assertEqual(obs.pauli_base(), Pauli("ZXX"))
+        self.assertEqual(SparseObservable.identity(3).pauli_base(), Pauli("III"))
+        with self.assertRaisesRegex(ValueError, "no common measurement basis"):
+            SparseObservable.from_list([("XI", 1.0), ("+I", 1.0), ("ZI", 1.0)]).pauli_base()
+
+    def test_group_commuting_paulis(self):
+        """Test that the grouping matches `SparsePauliOp` when there are no projectors."""
+        labels = ["XX", "YY", "IZ", "ZZ", "XY", "YX", "IX", "ZI"]
+        obs = SparseObservable.from_list([(label, i + 1) for i, label in enumerate(labels)])
+        spo = SparsePauliOp.from_list([(label, i + 1) for i, label in enumerate(labels)])
+
+        def terms(group):
+            return sorted((label, tuple(indices)) for label, indices, _ in group.to_sparse_list())
+
+        for qubit_wise in (True, False):
+            with self.subTest(qubit_wise=qubit_wise):
+                groups = obs.group_commuting(qubit_wise=qubit_wise)
+                expected = spo.group_commuting(qubit_wise=qubit_wise)
+                self.assertEqual(
+                    sorted(terms(group) for group in groups),
+                    sorted(
+                        terms(SparseObservable.from_sparse_pauli_op(group)) for group in expected
+                    ),
+                )
+
+    def test_group_commuting_projectors(self):
+        # `0I` needs qubit 1 measured on its own, so isn't compatible with `XZ`, even though `ZI`
+        # would be.
+        obs = SparseObservable.from_list([("ZX", 1), ("XZ", 2), ("0I", 3), ("XI", 4)])
+        expected = [
+            SparseObservable.from_list([("ZX", 1), ("0I", 3)]),
+            SparseObservable.from_list([("XZ", 2), ("XI", 4)]),
+        ]
+        self.assertEqual(obs.group_commuting(), expected)
+        self.assertEqual(obs.group_commuting(qubit_wise=True), expected)
+        self.assertEqual([group.pauli_base() for group in expected], [Pauli("ZX"), Pauli("XZ")])
+
+    @ddt.data(True, False)
+    def test_group_commuting_partitions(self, qubit_wise):
+        obs = SparseObservable.from_list(
+            [
+                ("IIIII", 1.0),
+                ("IXYZI", 2.0),
+                ("+-II+", 1j),
+                ("rlrlr", -0.5),
+                ("01010", -0.25),
+                ("rlYII", 1.0),
+                ("ZZIIX", 0.5),
+                ("XXYYZ", 0.75),
+            ]
+        )
+        groups = obs.group_commuting(qubit_wise=qubit_wise)
+        self.assertEqual(sum(len(group) for group in groups), len(obs))
+        self.assertEqual(
+            sorted(term[:2] for group in groups for term in group.to_sparse_list()),
+            sorted(term[:2] for term in obs.to_sparse_list()),
+        )
+        np.testing.assert_allclose(
+            sum(group.to_matrix() for group in groups), obs.to_matrix(), atol=1e-12
+        )
+        if qubit_wise:
+            for group in groups:
+                base = group.pauli_base()
+                for term_base in group.pauli_bases():
+                    # Every qubit a term needs measuring is measured in the same basis.
+                    active = term_base.x | term_base.z
+                    np.testing.assert_equal(term_base.x, base.x & active)
+                    np.testing.assert_equal(term_base.z, base.z & active)
+        self.assertEqual(SparseObservable.zero(3).group_commuting(qubit_wise=qubit_wise), [])
+
     def test_iteration(self):
         self.assertEqual(list(SparseObservable.zero(5)), [])
         self.assertEqual(tuple(SparseObservable.zero(0)), ())
@@ -2153,6 +2249,124 @@ class TestSparseObservable(QiskitTestCase):
 
             self.assertEqual(expected.simplify(), obs_paulis.simplify())
 
+    def test_to_matrix_projectors(self):
+        """Test the matrix forms of the single-qubit alphabet."""
+        for label, matrix in SINGLE_QUBIT_MATRICES.items():
+            with self.subTest(label=label):
+                obs = SparseObservable(label)
+                np.testing.assert_allclose(obs.to_matrix(), matrix)
+                np.testing.assert_allclose(obs.to_matrix(sparse=True).toarray(), matrix)
+
+    @ddt.idata(single_cases())
+    def test_to_matrix(self, obs):
+        """Test the matrix conversion against a reference built from Kronecker products."""
+        if obs.num_qubits > 10:
+            self.skipTest("too many qubits for a matrix")
+        expected = np.zeros((2**obs.num_qubits, 2**obs.num_qubits), dtype=complex)
+        for label, indices, coeff in obs.to_sparse_list():
+            factors = [np.eye(2)] * obs.num_qubits
+            for bit_label, index in zip(label, indices):
+                factors[index] = SINGLE_QUBIT_MATRICES[bit_label]
+            # Qubit 0 is the least significant, so it is the last factor of the product.
+            term = np.array([[coeff]])
+            for factor in factors:
+                term = np.kron(factor, term)
+            expected += term
+        for force_serial in (False, True):
+            with self.subTest(force_serial=force_serial):
+                np.testing.assert_allclose(obs.to_matrix(force_serial=force_serial), expected)
+                sparse = obs.to_matrix(sparse=True, force_serial=force_serial)
+                self.assertIsInstance(sparse, scipy.sparse.csr_matrix)
+                np.testing.assert_allclose(sparse.toarray(), expected)
+
+    def test_to_matrix_matches_paulis(self):
+        """Test that the direct matrix conversion matches the Pauli-only form."""
+        obs = SparseObservable.from_list([("+r1", 1.5), ("-l0", -0.5j), ("XYZ", 0.25)])
+        np.testing.assert_allclose(obs.to_matrix(), obs.as_paulis().to_matrix())
+        np.testing.assert_allclose(
+            obs.to_matrix(), SparsePauliOp.from_sparse_observable(obs).to_matrix()
+        )
+
+    def test_to_matrix_little_endian(self):
+        """Test that qubit 0 corresponds to the least significant bit of the matrix index."""
+        obs = SparseObservable.from_sparse_list([("1", [0], 1.0)], num_qubits=2)
+        np.testing.assert_allclose(obs.to_matrix(), np.diag([0, 1, 0, 1]))
+
+    def test_to_matrix_too_many_qubits(self):
+        """Test that large observables are rejected rather than allocating huge matrices."""
+        obs = SparseObservable.identity(100)
+        with self.assertRaisesRegex(ValueError, "cannot build a matrix"):
+            obs.to_matrix()
+        with self.assertRaisesRegex(ValueError, "cannot build a matrix"):
+            obs.to_matrix(sparse=True)
+
+    def test_expectation_value_statevector(self):
+        """Test the expectation value against statevectors, including through `Statevector`."""
+        obs = SparseObservable.from_list([("+r1", 1.5), ("-l0", -0.5j), ("XYZ", 0.25)])
+        state = random_statevector(8, seed=2025)
+        expected = np.vdot(state.data, obs.to_matrix() @ state.data)
+        self.assertAlmostEqual(obs.expectation_value(state), expected)
+        self.assertAlmostEqual(obs.expectation_value(state.data), expected)
+        self.assertAlmostEqual(state.expectation_value(obs), expected)
+        with self.assertRaisesRegex(ValueError, "not compatible"):
+            obs.expectation_value(np.ones(4, dtype=complex))
+
+    def test_expectation_value_density_matrix(self):
+        """Test the expectation value against density matrices, including via `DensityMatrix`."""
+        obs = SparseObservable.from_list([("+r1", 1.5), ("-l0", -0.5j), ("XYZ", 0.25)])
+        state = random_density_matrix(8, seed=2025)
+        expected = np.trace(obs.to_matrix() @ state.data)
+        self.assertAlmostEqual(obs.expectation_value(state), expected)
+        # Fortran-ordered arrays must give the same result.
+        self.assertAlmostEqual(obs.expectation_value(np.asfortranarray(state.data)), expected)
+        self.assertAlmostEqual(state.expectation_value(obs), expected)
+
+    def test_expectation_value_qargs(self):
+        """Test that the `qargs` of the state methods are respected."""
+        obs = SparseObservable.from_list([("1+", 1.0)])
+        state = Statevector.from_label("0+11")
+        # Qubit 0 of the observable on qubit 2 of the state, and qubit 1 on qubit 1.
+        self.assertAlmostEqual(state.expectation_value(obs, [2, 1]), 1.0)
+        self.assertAlmostEqual(state.expectation_value(obs, [0, 3]), 0.0)
+        self.assertAlmostEqual(DensityMatrix(state).expectation_value(obs, [2, 1]), 1.0)
+
+    def test_expectation_value_counts(self):
+        """Test estimating the expectation value from counts in different bases."""
+        obs = SparseObservable.from_list([("+0", 1.0), ("XZ", 0.5), ("I1", 2j)])
+        counts = {"00": 60, "01": 20, "10": 20}
+        self.assertAlmostEqual(obs.expectation_value_counts(counts, basis="XZ"), 0.7 + 0.4j)
+        # Spaces between registers and hexadecimal outcomes are both allowed.
+        self.assertAlmostEqual(
+            obs.expectation_value_counts({"0 0": 60, "0x1": 20, "0x2": 20}, basis="XZ"),
+            0.7 + 0.4j,
+        )
+        self.assertAlmostEqual(
+            obs.expectation_value_counts(Counts(counts), basis="XZ"), 0.7 + 0.4j
+        )
+        self.assertAlmostEqual(
+            obs.expectation_value_counts(ProbDistribution({0: 0.6, 1: 0.2, 2: 0.2}), basis="XZ"),
+            0.7 + 0.4j,
+        )
+        with self.assertRaisesRegex(ValueError, "different basis"):
+            obs.expectation_value_counts(counts)
+        with self.assertRaisesRegex(ValueError, "invalid measurement basis"):
+            obs.expectation_value_counts(counts, basis="XI")
+        with self.assertRaisesRegex(ValueError, "not a valid measurement outcome"):
+            obs.expectation_value_counts({"001": 1}, basis="XZ")
+
+    def test_expectation_value_counts_matches_paulis(self):
+        """Test that projectors give the same estimate as their Pauli expansion."""
+        obs = SparseObservable.from_list([("01Z", 1.5), ("1IZ", -0.5), ("IZ0", 0.25j)])
+        counts = {"000": 10, "011": 25, "101": 5, "110": 40, "111": 20}
+        self.assertAlmostEqual(
+            obs.expectation_value_counts(counts),
+            obs.as_paulis().expectation_value_counts(counts),
+        )
+        self.assertAlmostEqual(
+            sampled_expectation_value(counts, obs),
+            sampled_expectation_value(counts, SparsePauliOp.from_sparse_observable(obs)),
+        )
+
     def test_sparse_list_roundtrip(self):
         """Test dumping into a sparse list and constructing from one."""
         obs = SparseObservable.from_list(
@@ -2335,6 +2549,63 @@ class TestSparseObservable(QiskitTestCase):
         with self.assertRaisesRegex(ValueError, "duplicate indices in qargs"):
             SparseObservable.identity(5).compose("XYZX", qargs=[0, 1, 1, 0])
 
+    def test_commutator_matches_compose(self):
+        left = SparseObservable.from_list([("IXYZ", 1.5), ("+-rl", -0.25j), ("01IZ", 0.75)])
+        right = SparseObservable.from_list([("XYZ+", 2.0), ("I0lZ", 1j), ("YYII", -0.5)])
+        forwards = right.compose(left).to_matrix()
+        backwards = left.compose(right).to_matrix()
+        np.testing.assert_allclose(
+            left.commutator(right).to_matrix(), forwards - backwards, atol=1e-12
+        )
+        np.testing.assert_allclose(
+            left.anticommutator(right).to_matrix(), forwards + backwards, atol=1e-12
+        )
+
+    def test_commutator_paulis(self):
+        x, y, z = SparseObservable("X"), SparseObservable("Y"), SparseObservable("Z")
+        self.assertEqual(x.commutator(y), 2j * z)
+        self.assertEqual(x.anticommutator(x), 2 * SparseObservable.identity(1))
+        # Terms that cancel exactly are never produced.
+        self.assertEqual(x.commutator(x).num_terms, 0)
+        self.assertEqual(x.anticommutator(z).num_terms, 0)
+        self.assertEqual(SparseObservable("XX").commutator("YY").num_terms, 0)
+        self.assertEqual(SparseObservable("Z0").commutator("ZI").num_terms, 0)
+
+    def test_commutator_coerces(self):
+        base = SparseObservable("XZ")
+        self.assertEqual(base.commutator("ZZ"), base.commutator(SparseObservable("ZZ")))
+        self.assertEqual(base.anticommutator("+Z"), base.anticommutator(SparseObservable("+Z")))
+        with self.assertRaisesRegex(TypeError, "unknown type for commutator"):
+            base.commutator(None)
+        with self.assertRaisesRegex(TypeError, "unknown type for anticommutator"):
+            base.anticommutator(None)
+        with self.assertRaisesRegex(TypeError, "unknown type for nested_commutators"):
+            base.nested_commutators(None, 2)
+
+    def test_commutator_failure_qubit_mismatch(self):
+        with self.assertRaisesRegex(ValueError, "mismatched numbers of qubits"):
+            SparseObservable.identity(5).commutator(SparseObservable.identity(2))
+        with self.assertRaisesRegex(ValueError, "mismatched numbers of qubits"):
+            SparseObservable.identity(5).anticommutator(SparseObservable.identity(2))
+        with self.assertRaisesRegex(ValueError, "mismatched numbers of qubits"):
+            SparseObservable.identity(5).nested_commutators(SparseObservable.identity(2), 1)
+
+    def test_nested_commutators(self):
+        left = SparseObservable.from_list([("XI", 0.5), ("ZZ", -1.0), ("0Y", 0.25)])
+        right = SparseObservable.from_list([("Z+", 1.0), ("IY", 2j)])
+        nested = left.nested_commutators(right, 3)
+        self.assertEqual(len(nested), 4)
+        self.assertEqual(nested[0], right)
+        left_matrix = left.to_matrix()
+        expected = right.to_matrix()
+        for level in nested[1:]:
+            expected = left_matrix @ expected - expected @ left_matrix
+            np.testing.assert_allclose(level.to_matrix(), expected, atol=1e-12)
+        self.assertEqual(left.nested_commutators(right, 0), [right])
+
+        x, z = SparseObservable("X"), SparseObservable("Z")
+        self.assertEqual(x.nested_commutators(z, 2), [z, -2j * SparseObservable("Y"), 4 * z])
+
 
 def canonicalize_term(pauli, indices, coeff):
     # canonicalize a sparse list term by sorting by indices (which is unique as
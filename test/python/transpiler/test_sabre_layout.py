@@ -352,6 +352,33 @@ barrier q18585[5],q18585[2],q18585[8],q18585[3],q18585[6];
         _ = pm.run(qc)
         self.assertIsNotNone(pm.property_set.get("layout"))
 
+    def test_absorb_final_permutation(self):
+        """Test that absorbing the final permutation into the measurements does not add swaps."""
+        qc = quantum_volume(6, seed=3)
+        qc.measure_all()
+        qc = Unroll3qOrMore()(qc)
+        cmap = CouplingMap.from_line(8)
+
+        def route(absorb):
+            pass_ = SabreLayout(
+                cmap,
+                seed=7,
+                swap_trials=2,
+                layout_trials=2,
+                absorb_final_permutation=absorb,
+            )
+            return pass_(qc), pass_.property_set
+
+        plain, plain_props = route(False)
+        absorbed, absorbed_props = route(True)
+        self.assertEqual(absorbed.count_ops().get("swap", 0), plain.count_ops().get("swap", 0))
+        self.assertEqual(absorbed_props["layout"], plain_props["layout"])
+        self.assertEqual(absorbed_props["final_layout"], plain_props["final_layout"])
+        first_measure = next(
+            i for i, inst in enumerate(absorbed.data) if inst.operation.name == "measure"
+        )
+        self.assertNotIn("swap", {inst.operation.name for inst in absorbed.data[first_measure:]})
+
     def test_all_to_all(self):
         """An implicitly all-to-all backend should just become physical with the trivial layout."""
         qc = QuantumCircuit(QuantumRegister(5, "virtuals"))
@@ -19,16 +19,25 @@ import ddt
 import numpy.random
 
 from qiskit.circuit import Clbit, ControlFlowOp, Qubit
-from qiskit.circuit.library import CCXGate, HGate, Measure, SwapGate
+from qiskit.circuit.library import CCXGate, CXGate, HGate, Measure, SwapGate
 from qiskit.circuit.classical import expr, types
 from qiskit.circuit.random import random_circuit
 from qiskit.compiler.transpiler import transpile
 from qiskit.converters import circuit_to_dag, dag_to_circuit
+from qiskit.dagcircuit import DAGOutNode
 from qiskit.providers.fake_provider import GenericBackendV2
 from qiskit.transpiler.passes import SabreSwap, CheckMap
 from qiskit.transpiler.passes.routing.sabre_swap import Heuristic, SetScaling
-from qiskit.transpiler import CouplingMap, Layout, PassManager, Target, TranspilerError
+from qiskit.transpiler import (
+    CouplingMap,
+    InstructionProperties,
+    Layout,
+    PassManager,
+    Target,
+    TranspilerError,
+)
 from qiskit import ClassicalRegister, QuantumRegister, QuantumCircuit
+from qiskit.quantum_info import Operator
 from qiskit.utils import optionals
 from test.utils._canonical import canonicalize_control_flow  # pylint: disable=wrong-import-order
 from test import QiskitTestCase  # pylint: disable=wrong-import-order
@@ -207,6 +216,106 @@ class TestSabreSwap(QiskitTestCase):
 
         self.assertEqual(new_qc.num_nonlocal_gates(), 7)
 
+    def test_fidelity_heuristic_avoids_bad_edges(self):
+        """Test that the fidelity heuristic routes around the high-error edges of a target."""
+        target = Target(num_qubits=4)
+        # A ring where the two edges touching qubit 1 are 100x worse than the others.
+        errors = {(0, 1): 1e-1, (1, 2): 1e-1, (2, 3): 1e-3, (3, 0): 1e-3}
+        target.add_instruction(
+            CXGate(),
+            {
+                qargs: InstructionProperties(error=error)
+                for edge, error in errors.items()
+                for qargs in (edge, edge[::-1])
+            },
+        )
+
+        qc = QuantumCircuit(QuantumRegister(4, "q"))
+        qc.cx(0, 2)
+        dag = circuit_to_dag(qc)
+
+        heuristic = (
+            Heuristic(attempt_limit=40)
+            .with_basic(1.0, SetScaling.Constant)
+            .with_fidelity(1.0, SetScaling.Constant)
+        )
+        for seed in range(10):
+            routed = SabreSwap(target, heuristic, seed=seed, trials=1).run(dag)
+            for node in routed.two_qubit_ops():
+                self.assertNotIn(1, [routed.find_bit(q).index for q in node.qargs])
+
+    def test_bridge_preserves_layout(self):
+        """Test that a distance-two CX is routed with a bridge when every swap that would route it
+        makes the following gates worse."""
+        qc = QuantumCircuit(QuantumRegister(3, "q"))
+        qc.cx(0, 2)
+        qc.cx(0, 1)
+        qc.cx(1, 2)
+
+        heuristic = (
+            Heuristic(attempt_limit=30)
+            .with_basic(1.0, SetScaling.Constant)
+            .with_lookahead(0.5, 20, SetScaling.Constant)
+            .with_bridge()
+        )
+        routed = SabreSwap(CouplingMap.from_line(3), heuristic, seed=0, trials=1)(qc)
+        self.assertEqual(routed.count_ops(), {"cx": 6})
+        self.assertEqual(Operator(routed), Operator(qc))
+
+        # Without the bridge option, the same circuit needs a swap.
+        heuristic = (
+            Heuristic(attempt_limit=30)
+            .with_basic(1.0, SetScaling.Constant)
+            .with_lookahead(0.5, 20, SetScaling.Constant)
+        )
+        routed = SabreSwap(CouplingMap.from_line(3), heuristic, seed=0, trials=1)(qc)
+        self.assertIn("swap", routed.count_ops())
+
+    def test_absorb_final_permutation(self):
+        """Test that the terminal measurements can absorb the final permutation without any swaps
+        being added to undo it."""
+        qc = QuantumCircuit(QuantumRegister(5, "q"), ClassicalRegister(3, "c"))
+        qc.cx(0, 4)
+        qc.measure(0, 0)
+        qc.h(1)
+        qc.cx(1, 4)
+        qc.cx(2, 3)
+        qc.cx(2, 4)
+        qc.measure(4, 1)
+        qc.measure(2, 2)
+        coupling = CouplingMap.from_line(5)
+
+        def route(absorb):
+            pass_ = SabreSwap(coupling, "basic", seed=0, trials=1, absorb_final_permutation=absorb)
+            return pass_(qc), pass_.property_set["final_layout"]
+
+        plain, plain_final = route(False)
+        absorbed, absorbed_final = route(True)
+        self.assertEqual(absorbed.count_ops().get("swap", 0), plain.count_ops().get("swap", 0))
+        self.assertEqual(absorbed_final, plain_final)
+
+        # Each measurement comes after every swap, and reads its qubit at its final position.
+        dag = circuit_to_dag(absorbed)
+        for node in dag.op_nodes(Measure):
+            self.assertTrue(all(isinstance(succ, DAGOutNode) for succ in dag.successors(node)))
+        last_swap = max(
+            (i for i, inst in enumerate(absorbed.data) if inst.operation.name == "swap"),
+            default=-1,
+        )
+        measurements = {
+            absorbed.find_bit(inst.clbits[0]).index: absorbed.find_bit(inst.qubits[0]).index
+            for inst in absorbed.data[last_swap + 1 :]
+            if inst.operation.name == "measure"
+        }
+        self.assertEqual(len(measurements), 3)
+        self.assertEqual(
+            measurements,
+            {
+                clbit: absorbed_final[absorbed.qubits[virtual]]
+                for clbit, virtual in [(0, 0), (1, 4), (2, 2)]
+            },
+        )
+
     def test_do_not_change_cm(self):
         """Coupling map should not change.
         See https://github.com/Qiskit/qiskit-terra/issues/5675"""
@@ -14,14 +14,16 @@
 
 import unittest
 
-from ddt import ddt, data
+from ddt import ddt, data, unpack
 from qiskit import QuantumCircuit
-from qiskit.circuit import Measure
-from qiskit.circuit.library import CXGate, HGate
+from qiskit.circuit import Measure, Reset
+from qiskit.circuit.library import CXGate, HGate, XGate
+from qiskit.converters import circuit_to_dag
 from qiskit.transpiler.instruction_durations import InstructionDurations
 from qiskit.transpiler.passes import (
     ASAPScheduleAnalysis,
     ALAPScheduleAnalysis,
+    ConstrainedReschedule,
     PadDelay,
 )
 from qiskit.transpiler.passmanager import PassManager
@@ -333,6 +335,78 @@ class TestSchedulingAndPaddingPass(QiskitTestCase):
 
         self.assertEqual(scheduled, qc)
 
+    @data(ALAPScheduleAnalysis, ASAPScheduleAnalysis)
+    def test_respect_target_alignment(self, schedule_pass):
+        """Test that scheduling with a target aligns the start times to the target's alignment
+        constraints, so a following rescheduling pass moves nothing."""
+        qc = QuantumCircuit(1, 1)
+        qc.x(0)
+        qc.delay(100, 0)
+        qc.measure(0, 0)
+        qc.x(0)
+
+        target = Target(num_qubits=1, dt=1, pulse_alignment=16, acquire_alignment=16)
+        target.add_instruction(XGate(), {(0,): InstructionProperties(duration=160)})
+        target.add_instruction(Measure(), {(0,): InstructionProperties(duration=1000)})
+
+        pm = PassManager([schedule_pass(target=target)])
+        pm.run(qc)
+        self.assertEqual(sorted(pm.property_set["node_start_time"].values()), [0, 160, 272, 1280])
+
+        pm = PassManager([schedule_pass(target=target), ConstrainedReschedule(target=target)])
+        pm.run(qc)
+        self.assertEqual(sorted(pm.property_set["node_start_time"].values()), [0, 160, 272, 1280])
+
+    @data(
+        (ALAPScheduleAnalysis, 0),
+        (ALAPScheduleAnalysis, 100),
+        (ASAPScheduleAnalysis, 0),
+        (ASAPScheduleAnalysis, 100),
+    )
+    @unpack
+    def test_target_matches_durations(self, schedule_pass, clbit_write_latency):
+        """Test that scheduling with a target gives the same start times as scheduling with the
+        equivalent instruction durations."""
+        qc = QuantumCircuit(3, 2)
+        qc.x(0)
+        qc.cx(0, 1)
+        qc.delay(100, 2)
+        qc.measure(1, 0)
+        qc.x(0)
+        qc.barrier()
+        qc.cx(1, 2)
+        qc.measure(0, 1)
+        qc.measure(2, 1)
+        qc.reset(1)
+        qc.x(1)
+
+        target = Target(num_qubits=3, dt=1)
+        target.add_instruction(
+            XGate(), {(q,): InstructionProperties(duration=160) for q in range(3)}
+        )
+        target.add_instruction(
+            CXGate(),
+            {qargs: InstructionProperties(duration=500) for qargs in [(0, 1), (1, 2)]},
+        )
+        target.add_instruction(
+            Measure(), {(q,): InstructionProperties(duration=1000) for q in range(3)}
+        )
+        target.add_instruction(
+            Reset(), {(q,): InstructionProperties(duration=700) for q in range(3)}
+        )
+
+        dag = circuit_to_dag(qc)
+        start_times = []
+        for scheduler in (
+            schedule_pass(target=target),
+            schedule_pass(durations=target.durations()),
+        ):
+            scheduler.property_set["clbit_write_latency"] = clbit_write_latency
+            scheduler.run(dag)
+            node_start_time = scheduler.property_set["node_start_time"]
+            start_times.append({node._node_id: time for node, time in node_start_time.items()})
+        self.assertEqual(start_times[0], start_times[1])
+
     @data(ALAPScheduleAnalysis, ASAPScheduleAnalysis)
     def test_respect_target_instruction_constraints(self, schedule_pass):
         """Test if DD pass does not pad delays for qubits that do not support delay instructions.